@@ -1,14 +1,21 @@
 use std::sync::Arc;
 
+use serde::{Deserialize, Serialize};
+
+use crate::app::{ClickList, Form};
 use crate::error::GritError;
 use crate::forge::Forge;
+use crate::outbox::Mutation;
 use crate::types::{
-    ActionRun, Commit, CommitDetail, Issue, MergeMethod, MyPr, PrSummary, PullRequest, Repository,
-    ReviewEvent, ReviewRequest,
+    ActionRun, Artifact, Comment, Commit, CommitCheck, CommitDetail, CommitFile, CommitHit,
+    DiffLine, ForkStatus, Issue, IssueRef, IssueTemplate, Label, MergeMethod, Milestone, MyPr,
+    PageInfo, PrChecks, PrReviews, PrSummary, PullRequest, Reaction, RepoMeta, RepoStats,
+    Repository, ReviewComment, ReviewEvent, ReviewRequest, ReviewThread, ReviewerLoad,
+    Subscription,
 };
 
 /// Tab selection for repo view
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum RepoTab {
     #[default]
     PullRequests,
@@ -17,12 +24,110 @@ pub enum RepoTab {
     Actions,
 }
 
+/// Why the milestone list is being fetched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MilestonePurpose {
+    /// Assign the selected issue to a milestone
+    Assign,
+    /// Narrow the PR and issue tabs to one milestone
+    Filter,
+}
+
+/// What the issue form popup writes to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FormTarget {
+    /// Opens an issue, with a description from the editor
+    #[default]
+    NewIssue,
+    Issue(u64),
+    Pr(u64),
+}
+
+/// What a bulk action does to each marked PR or issue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkOp {
+    Close,
+    /// Add these labels, keeping the ones already set
+    AddLabels(Vec<String>),
+}
+
+impl BulkOp {
+    /// For the status bar while it runs
+    pub fn verb(&self) -> &'static str {
+        match self {
+            BulkOp::Close => "Closing",
+            BulkOp::AddLabels(_) => "Labeling",
+        }
+    }
+
+    /// For the report once it's done
+    pub fn past(&self) -> &'static str {
+        match self {
+            BulkOp::Close => "Closed",
+            BulkOp::AddLabels(_) => "Labeled",
+        }
+    }
+}
+
 /// What to confirm
 #[derive(Debug, Clone)]
 pub enum ConfirmAction {
     ClosePr(u64),
-    MergePr { number: u64, method: MergeMethod },
+    MergePr {
+        number: u64,
+        method: MergeMethod,
+        /// Head branch to delete once merged
+        delete_branch: Option<String>,
+        /// Leave the merge to the forge, once checks pass
+        auto: bool,
+    },
     CloseIssue(u64),
+    /// Close every marked PR or issue of the current tab
+    CloseMarked(Vec<u64>),
+    ForkRepo {
+        owner: String,
+        repo: String,
+    },
+    /// Update the current fork's `branch` from upstream
+    SyncFork {
+        branch: String,
+        upstream: String,
+        behind: u64,
+    },
+    /// Open a PR reverting these commits of merged PR `number`
+    RevertPr {
+        number: u64,
+        commits: Vec<Commit>,
+    },
+    /// Drop a repo, or one of its PRs, that the forge no longer has from
+    /// recent items and the cache
+    ForgetMissing {
+        owner: String,
+        repo: String,
+        number: Option<u64>,
+    },
+}
+
+/// A repo or PR grit knew by a name the forge didn't answer to
+#[derive(Debug, Clone)]
+pub struct Missing {
+    pub owner: String,
+    pub repo: String,
+    /// The PR asked for; `None` when it was the repo itself
+    pub number: Option<u64>,
+    /// The 404 that raised the question; `None` when a remembered name is
+    /// checked before anything failed
+    pub error: Option<GritError>,
+}
+
+/// git commands to run in the terminal while the TUI is suspended
+#[derive(Debug, Clone)]
+pub struct GitRun {
+    pub dir: std::path::PathBuf,
+    /// Arguments for each git invocation, stopping at the first failure
+    pub steps: Vec<Vec<String>>,
+    /// Flash message once every step succeeded
+    pub done: String,
 }
 
 /// Context for editor suspend
@@ -38,19 +143,56 @@ pub enum EditorContext {
         repo: String,
         number: u64,
     },
+    CommentOnCommit {
+        owner: String,
+        repo: String,
+        sha: String,
+    },
     ReviewPr {
         owner: String,
         repo: String,
         number: u64,
         event: ReviewEvent,
     },
+    /// Comment on one diff line, queued for the current PR's next review
+    LineComment { path: String, line: DiffLine },
+    /// Description and topics, starting from the current `meta`
+    EditRepo {
+        owner: String,
+        repo: String,
+        meta: RepoMeta,
+    },
+    /// Description of an issue whose title and metadata are in `form`
+    NewIssue {
+        owner: String,
+        repo: String,
+        form: Form,
+    },
 }
 
 #[derive(Debug, Clone)]
 #[allow(clippy::enum_variant_names)]
 pub enum Action {
+    /// Initial load: opens the configured start screen
+    Start,
     Quit,
     Back,
+    /// Reopen the repo or PR viewed before the current one (`Ctrl+O`)
+    JumpBack,
+    /// Reopen the PR or commit Back just left (`Ctrl+L`)
+    Forward,
+    /// Open another workspace on Home, keeping the current one as it is
+    NewWorkspace,
+    NextWorkspace,
+    PrevWorkspace,
+    CloseWorkspace,
+    Tick,
+    /// Run a movement `count` times, from a count prefix like `10j`
+    Repeat(usize, Box<Action>),
+    /// Scroll the list so its selection sits mid-view (`zz`)
+    CenterSelection,
+    /// Collapse Home's selected section to its title, or expand it (`za`)
+    ToggleHomeSection,
     ScrollUp,
     ScrollDown,
     PageUp,
@@ -58,6 +200,8 @@ pub enum Action {
     GoToTop,
     GoToBottom,
     Select,
+    /// Mouse click on row `index` of a list; a click on the selected row opens it
+    ClickRow(ClickList, usize),
     NextTab,
     PrevTab,
 
@@ -66,35 +210,140 @@ pub enum Action {
     HomeLoaded {
         review_requests: Vec<ReviewRequest>,
         my_prs: Vec<MyPr>,
+        assigned: Vec<IssueRef>,
+        mentions: Vec<IssueRef>,
         load_id: u64,
     },
+    /// Step the review request order: newest, oldest, repo, author
+    CycleReviewSort,
+    /// Pick which `[searches]` entry Home's Saved section runs
+    ShowSavedSearchSelect,
+    /// A page of a saved search's results, tagged with its name; page 1
+    /// replaces what the section showed
+    SavedSearchLoaded(String, u32, Vec<IssueRef>, PageInfo),
 
     // Navigation
     SwitchRepoTab(RepoTab),
+    /// Show or hide the preview pane next to RepoView's PR and issue lists
+    TogglePreview,
+    /// Diff view: side by side or unified
+    ToggleSplitDiff,
+    /// Full PR for the preview pane, tagged with owner/repo
+    PreviewPrLoaded(String, String, Box<PullRequest>),
+    /// Issue description for the preview pane: owner, repo, number, body
+    PreviewIssueLoaded(String, String, u64, Option<String>),
 
     // Repo list
-    ReposLoaded(Vec<Repository>, Option<u64>, u64),
+    ReposLoaded(Vec<Repository>, PageInfo, u64),
+    /// Switch between the repo list and the owner | repo | PR columns
+    ToggleBrowse,
+    /// PRs for the repo highlighted in the browse columns, tagged with owner/repo
+    BrowsePrsLoaded(String, String, Vec<PrSummary>),
 
     // PR operations
-    PrsLoaded(Vec<PrSummary>, Option<u64>, u64),
+    PrsLoaded(Vec<PrSummary>, PageInfo, u64),
     PrDetailLoaded(Box<PullRequest>, u64),
+    PrCommentsLoaded(Vec<Comment>, u64),
+    PrReviewsLoaded(PrReviews, u64),
+    PrFilesLoaded(Vec<CommitFile>, u64),
+    NextFile,
+    PrevFile,
+    PrCommitsLoaded(Vec<Commit>, u64),
+    NextPrCommit,
+    PrevPrCommit,
+    PrChecksLoaded(PrChecks, u64),
+    ViewedFilesLoaded(Vec<String>, u64),
+    ToggleBotComments,
+
+    /// Pick whose open PRs the PR tab lists
+    ShowPrFilterSelect,
+
+    // Milestones
+    ShowMilestoneSelect(MilestonePurpose),
+    MilestonesLoaded(Vec<Milestone>, MilestonePurpose),
+    MilestoneSet,
+
+    // Marks on RepoView's PR and issue lists
+    /// Mark or unmark the selected row, then move down
+    ToggleMark,
+    /// The first press anchors a range at the selection, the second marks
+    /// everything between
+    MarkRange,
+    ClearMarks,
+    /// Another call of the running bulk action finished
+    BulkProgress(usize),
+    /// The bulk action ended; the numbers whose call failed, with why
+    BulkDone(Vec<(u64, String)>),
+
+    // Labels
+    ShowLabelSelect,
+    LabelsLoaded(Vec<Label>),
+    LabelsUpdated,
+
+    // Task list in a PR description
+    /// Tick and clear the description's `- [ ]` items
+    ShowChecklist,
+    ChecklistUpdated,
+
+    // Issue templates
+    /// Offer the repo's issue templates, or go straight to a blank form
+    ShowIssueTemplateSelect,
+    IssueTemplatesLoaded(Vec<IssueTemplate>),
+
+    // Title, labels, assignees and milestone form
+    ShowForm(FormTarget),
+    FormInput(char),
+    FormBackspace,
+    FormNextField,
+    FormPrevField,
+    SubmitForm,
+    /// Saved; for a new issue, the number it got
+    FormSaved(FormTarget, u64),
+
+    // Review threads
+    ReviewThreadsLoaded(Vec<ReviewThread>, u64),
+    NextThread,
+    PrevThread,
+    ToggleResolvedThreads,
+    ToggleThreadResolved,
+    ThreadResolved {
+        thread_id: String,
+        resolved: bool,
+    },
 
     // Issues
-    IssuesLoaded(Vec<Issue>, Option<u64>, u64),
+    IssuesLoaded(Vec<Issue>, PageInfo, u64),
 
     // Commits
-    CommitsLoaded(Vec<Commit>, Option<u64>, u64),
+    CommitsLoaded(Vec<Commit>, PageInfo, u64),
     CommitDetailLoaded(Box<CommitDetail>, u64),
+    /// CI checks for the commit with this sha
+    CommitChecksLoaded(String, Vec<CommitCheck>, u64),
 
     // Actions (workflow runs)
-    ActionRunsLoaded(Vec<ActionRun>, Option<u64>, u64),
+    ActionRunsLoaded(Vec<ActionRun>, PageInfo, u64),
+    ActionRunsPolled(Vec<ActionRun>, u64),
+    /// Show one row per workflow on the Actions tab, or single runs again
+    ToggleWorkflowSummary,
+    /// Recent runs across several pages, for the per-workflow summary
+    WorkflowHistoryLoaded(Vec<ActionRun>, u64),
+    /// Pick one of the selected run's artifacts to download
+    ShowArtifactSelect,
+    ArtifactsLoaded(Vec<Artifact>),
+    /// Bytes of the running download written so far, of the total if known
+    DownloadProgress(u64, Option<u64>),
+    ArtifactDownloaded(std::path::PathBuf),
+    DownloadFailed(GritError),
+    /// Bytes of the PR diff read so far, of the total if known
+    DiffProgress(u64, Option<u64>),
+    DiffFailed(GritError),
 
     // Pagination: append next page to existing list
-    ReposAppended(Vec<Repository>, Option<u64>, u64),
-    PrsAppended(Vec<PrSummary>, Option<u64>, u64),
-    IssuesAppended(Vec<Issue>, Option<u64>, u64),
-    CommitsAppended(Vec<Commit>, Option<u64>, u64),
-    ActionRunsAppended(Vec<ActionRun>, Option<u64>, u64),
+    ReposAppended(Vec<Repository>, PageInfo, u64),
+    PrsAppended(Vec<PrSummary>, PageInfo, u64),
+    IssuesAppended(Vec<Issue>, PageInfo, u64),
+    CommitsAppended(Vec<Commit>, PageInfo, u64),
+    ActionRunsAppended(Vec<ActionRun>, PageInfo, u64),
 
     // Search
     EnterSearchMode,
@@ -108,10 +357,24 @@ pub enum Action {
 
     // Pager
     ViewDiff,
+    /// Diff the PR's description and comments against what they were when opened
+    ShowPrChanges,
     SuspendForPager(String),
+    /// Page a diff spooled to this temp file, removed afterwards
+    SuspendForPagerFile(std::sync::Arc<tempfile::TempPath>),
+    /// The PR diff is down, in memory or spooled to a file
+    DiffLoaded(crate::http::Spooled),
 
     // Polish
+    /// Reload the current list, or the whole detail view
     Refresh,
+    /// Reload only the selected item
+    RefreshItem,
+    /// Reload the current view from the forge, skipping the cache
+    HardRefresh,
+    /// A `RefreshItem` finished; the label names what was reloaded
+    ItemRefreshed(String),
+    ShowRepoList,
     OpenInBrowser,
     YankUrl,
 
@@ -121,35 +384,157 @@ pub enum Action {
     ConfirmYes,
     ConfirmNo,
     PrMerged,
+    /// The merged PR's head branch is gone too
+    BranchDeleted(String),
+    /// The forge will merge this PR once its checks pass
+    AutoMergeEnabled(u64),
     PrClosed,
     CommentPosted,
+    /// Pick which commits of the merged PR to revert
+    ShowRevertSelect,
+    PrReverted(u64),
+
+    // One-line comment prompt
+    StartQuickComment,
+    QuickCommentInput(char),
+    QuickCommentBackspace,
+    SubmitQuickComment,
+    CancelQuickComment,
+
+    // Find a commit across all repos
+    StartFindCommit,
+    FindCommitInput(char),
+    FindCommitBackspace,
+    SubmitFindCommit,
+    /// Hits from refreshing every repo's commits, for the query they answer
+    CommitHitsLoaded(String, Vec<CommitHit>),
+
+    // Jump to a PR or issue by number
+    StartJump,
+    JumpInput(char),
+    JumpBackspace,
+    SubmitJump,
+    JumpedToPr(Box<PullRequest>, u64),
+    /// No PR had the number, but this issue does
+    JumpedToIssue(Issue, u64),
 
     // Mutations - Issue
     IssueClosed,
 
+    // Reviewer assignment
+    ShowReviewerLoad,
+    ReviewerLoadLoaded(Vec<ReviewerLoad>),
+    ReviewersRequested(String),
+
     // Review
     ShowReviewSelect,
     ReviewSubmitted,
+    /// Queue a line comment on the current PR until the review is submitted
+    AddReviewComment(ReviewComment),
+
+    // Reactions
+    /// Reaction picker for the PR, or the comment at the top of the view
+    ShowReactionSelect,
+    ReactionAdded(Reaction),
 
     // Editor
     SuspendForEditor(EditorContext),
+    /// Pick a `[snippets]` entry to start the editor with
+    ShowSnippetSelect(EditorContext),
+    /// Open a local file in $EDITOR; nothing is posted afterwards
+    SuspendForEditFile(std::path::PathBuf),
+    OpenFileInEditor,
+
+    // Repository metadata
+    /// Issue, PR, contributor and language counts for the current repo
+    ShowInsights,
+    RepoStatsLoaded(String, String, RepoStats),
+    /// Star or unstar the selected repo
+    ToggleStar,
+    /// Whether the signed-in user has starred owner/repo
+    StarStateLoaded(String, String, bool),
+    /// Starring owner/repo didn't go through; undo the optimistic change
+    StarFailed(String, String, GritError),
+    /// Fork the selected repo, after confirming
+    ForkRepo,
+    RepoForked(Repository),
+    /// Upstream comparison for owner/repo; `None` when it isn't a fork
+    ForkStatusLoaded(String, String, Option<ForkStatus>),
+    /// Update the current fork from upstream, after confirming
+    SyncFork,
+    /// The fork's branch was brought up to date
+    ForkSynced(String),
+    /// Pick how much of the selected repo's activity notifies you
+    ShowWatchSelect,
+    SubscriptionSet(String, Subscription),
+    EditRepoMeta,
+    RepoMetaLoaded(String, String, RepoMeta),
+    RepoMetaUpdated,
+
+    // Local git
+    CloneRepo,
+    CheckoutPr,
+    SuspendForGit(GitRun),
+    GitFinished(String),
+
+    // Command palette
+    ShowPalette,
+    PaletteInput(char),
+    PaletteBackspace,
 
     // Popup navigation
     PopupUp,
     PopupDown,
     PopupSelect,
+    /// Check/uncheck the highlighted item in a multi-select popup
+    PopupToggle,
+
+    // Background tasks
+    /// A spawned task is done, by registry id
+    TaskFinished(u64),
+    /// List running tasks to cancel one
+    ShowTasks,
+    /// Pick the next / previous `#123` or `@user` in the open PR
+    NextXref,
+    PrevXref,
+    /// Drop the picked reference so Enter opens files and commits again
+    ClearXref,
+    /// Clear the header's new review request / failed check badge
+    DismissAlerts,
+    /// Tail grit's log file on the debug screen
+    ShowLogs,
 
     // Forge switching
     ShowForgeSelect,
     SwitchForge(usize),
     ForgeReady(Arc<dyn Forge>, String),
+    /// A forge couldn't be set up: its name and why
+    ForgeFailed(String, String),
+
+    // Organizations / groups
+    ShowOrgSelect,
+    OrgsLoaded(Vec<String>),
+
+    // Offline mode
+    /// A mutation couldn't reach the forge; hold it in the outbox
+    MutationQueued(Mutation),
+    /// A queued mutation went through on replay
+    OutboxSent(Mutation),
+    /// The forge answered again after being unreachable
+    Online,
+
+    // Repos and PRs that moved or went away
+    /// A load 404'd; find out whether the repo moved
+    NotFound(Missing),
+    /// Where the missing repo lives now, or why that couldn't be told
+    RepoLocated(Missing, Result<(String, String), GritError>),
 
-    Error(String),
+    Error(GritError),
     None,
 }
 
 impl From<GritError> for Action {
     fn from(err: GritError) -> Self {
-        Action::Error(err.to_string())
+        Action::Error(err)
     }
 }
@@ -1,32 +1,290 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::Range;
+use std::rc::Rc;
 use std::sync::Arc;
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
-use crate::action::{Action, ConfirmAction, EditorContext, RepoTab};
+use crate::action::{
+    Action, BulkOp, ConfirmAction, EditorContext, FormTarget, GitRun, MilestonePurpose, Missing,
+    RepoTab,
+};
 use crate::cache;
+use crate::config::{HomeConfig, StartScreen, UiConfig};
+use crate::drafts;
+use crate::error::GritError;
 use crate::event::Event;
 use crate::forge::Forge;
+use crate::http::Spooled;
+use crate::links::{BodyLink, CrossRef, LinkRule, TrackerLink};
+use crate::notify;
+use crate::outbox::{self, Mutation};
 use crate::types::{
-    ActionRun, Commit, CommitDetail, HomeData, Issue, MyPr, PagedResult, PrSummary, PullRequest,
-    Repository, ReviewRequest,
+    commit_matches, format_size, line_diff, patch_line_numbers, set_task, split_rows,
+    summarize_workflows, task_items, wrap_line, ActionRun, ActionStatus, Artifact, Comment, Commit,
+    CommitCheck, CommitDetail, CommitFile, CommitHit, DiffLine, ForkStatus, HomeData, Issue,
+    IssueFields, IssueRef, IssueTemplate, Label, LastLocation, MergeMethod, Milestone, MyPr,
+    PageInfo, PagedResult, PrChecks, PrFilter, PrReviews, PrState, PrSummary, PullRequest,
+    Reaction, ReactionTarget, RecentItem, RepoMeta, RepoStats, Repository, ReviewComment,
+    ReviewRequest, ReviewThread, ReviewerLoad, SplitRow, Subscription, TaskItem, WorkflowSummary,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Screen {
-    Home,         // Dashboard with review requests + your PRs
+    #[default]
+    Home, // Dashboard with review requests + your PRs
     RepoList,     // Repository browser
     RepoView,     // Repo view with tabs (PRs, Issues, Commits, Actions)
     PrDetail,     // PR detail view
     CommitDetail, // Commit detail view
+    Browse,       // Miller columns: owners | repos | PRs
+    DiffView,     // One PR file's diff, line by line
+    Logs,         // Tail of grit's log file
 }
 
-/// Section of the home screen
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Section of the home screen. The aliases are how `[home]` names them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum HomeSection {
     #[default]
+    #[serde(alias = "review_requests")]
+    ReviewRequests,
+    #[serde(alias = "my_prs")]
+    MyPrs,
+    #[serde(alias = "assigned")]
+    Assigned,
+    #[serde(alias = "mentions")]
+    Mentions,
+    /// Results of a `[searches]` entry; only there when some are configured
+    #[serde(alias = "saved")]
+    Saved,
+    #[serde(alias = "recent")]
+    Recent,
+}
+
+/// Home's sections in their usual order, with their usual heights
+const HOME_SECTIONS: [(HomeSection, u16); 6] = [
+    (HomeSection::ReviewRequests, 20),
+    (HomeSection::MyPrs, 16),
+    (HomeSection::Assigned, 16),
+    (HomeSection::Mentions, 16),
+    (HomeSection::Saved, 20),
+    (HomeSection::Recent, 12),
+];
+
+/// Order of Home's review requests, cycled with `s`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReviewSort {
+    /// Most recently updated first
+    #[default]
+    Newest,
+    /// Longest waiting first
+    Oldest,
+    Repo,
+    Author,
+}
+
+impl ReviewSort {
+    pub fn next(self) -> Self {
+        match self {
+            ReviewSort::Newest => ReviewSort::Oldest,
+            ReviewSort::Oldest => ReviewSort::Repo,
+            ReviewSort::Repo => ReviewSort::Author,
+            ReviewSort::Author => ReviewSort::Newest,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ReviewSort::Newest => "newest first",
+            ReviewSort::Oldest => "oldest first",
+            ReviewSort::Repo => "by repo",
+            ReviewSort::Author => "by author",
+        }
+    }
+}
+
+/// Focused column of the browse screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BrowseColumn {
+    #[default]
+    Owners,
+    Repos,
+    PullRequests,
+}
+
+/// A configured forge's client, or why it couldn't be set up
+#[derive(Debug, Clone)]
+pub enum ForgeState {
+    Ready(Arc<dyn Forge>),
+    Failed(String),
+}
+
+/// Preview pane beside RepoView's PR and issue lists. Details load once the
+/// selection rests on an item, from the cache first.
+#[derive(Debug, Clone, Default)]
+pub struct PreviewState {
+    pub enabled: bool,
+    /// Repo the details below belong to
+    repo: Option<(String, String)>,
+    pub prs: HashMap<u64, PullRequest>,
+    pub issue_bodies: HashMap<u64, Option<String>>,
+    /// Item the preview is following, and when the selection moved to it
+    target: Option<(RepoTab, u64)>,
+    moved_at: Option<std::time::Instant>,
+    /// Items refreshed from the forge this visit
+    fetched: HashSet<(RepoTab, u64)>,
+}
+
+/// Speculative fetches around the PR list selection, so Enter usually opens
+/// a PR straight from the cache
+#[derive(Debug, Clone, Default)]
+struct PrPrefetch {
+    /// Row the prefetch is following, and when the selection moved to it
+    target: Option<(String, String, u64)>,
+    moved_at: Option<std::time::Instant>,
+    /// PRs fetched or in flight, by the `updated_at` the list showed for them
+    warmed: HashSet<(String, String, u64, chrono::DateTime<chrono::Utc>)>,
+}
+
+/// Keys typed toward a command that takes several: the count of `10j`, the
+/// first `g` of `gg`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PendingKeys {
+    pub count: Option<usize>,
+    pub prefix: Option<char>,
+    /// When the last of them was typed
+    at: Option<std::time::Instant>,
+}
+
+impl PendingKeys {
+    /// Nothing pending once KEY_SEQUENCE_TIMEOUT passed without another key
+    fn live(self) -> Self {
+        match self.at {
+            Some(at) if at.elapsed() < KEY_SEQUENCE_TIMEOUT => self,
+            _ => Self::default(),
+        }
+    }
+
+    /// The keys so far, for the status bar
+    pub fn shown(self) -> Option<String> {
+        let live = self.live();
+        if live.count.is_none() && live.prefix.is_none() {
+            return None;
+        }
+        let count = live.count.map(|n| n.to_string()).unwrap_or_default();
+        Some(format!(
+            "{}{}",
+            count,
+            live.prefix.map(String::from).unwrap_or_default()
+        ))
+    }
+}
+
+/// An artifact or PR diff coming down, for the status bar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Download {
+    pub name: String,
+    pub received: u64,
+    pub total: Option<u64>,
+}
+
+/// PRs or issues marked in a RepoView list for a bulk action
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Marks {
+    /// The repo and tab the numbers belong to; marks elsewhere are ignored
+    repo: Option<(String, String)>,
+    tab: RepoTab,
+    pub numbers: BTreeSet<u64>,
+    /// Where `V` started a range
+    pub anchor: Option<u64>,
+}
+
+/// A bulk action in flight, for the status bar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bulk {
+    pub op: BulkOp,
+    /// "pr" or "issue"
+    pub kind: &'static str,
+    pub done: usize,
+    pub total: usize,
+}
+
+/// "PR", "issues" and so on, for `count` items of `kind`
+pub fn item_noun(kind: &str, count: usize) -> &'static str {
+    match (kind, count == 1) {
+        ("pr", true) => "PR",
+        ("pr", false) => "PRs",
+        (_, true) => "issue",
+        (_, false) => "issues",
+    }
+}
+
+/// A spawned load or mutation still running, for the tasks popup
+#[derive(Debug)]
+pub struct Task {
+    pub label: String,
+    pub started_at: std::time::Instant,
+    /// The `load_id` a load belongs to; it's aborted once that's superseded
+    load_id: Option<u64>,
+    handle: tokio::task::AbortHandle,
+}
+
+/// Line numbers and side-by-side rows of one patch, worked out once rather
+/// than on every frame and keypress
+#[derive(Debug, Default)]
+pub struct DiffLayout {
+    patch: String,
+    pub numbers: Vec<Option<DiffLine>>,
+    pub rows: Vec<SplitRow>,
+}
+
+/// List a mouse click can land in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClickList {
     ReviewRequests,
     MyPrs,
+    Assigned,
+    Mentions,
+    Saved,
+    Recent,
+    Repos,
+    Prs,
+    Issues,
+    Commits,
+    Actions,
+    Workflows,
+    Browse(BrowseColumn),
+    Diff,
+}
+
+/// Region of the last frame that reacts to clicks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickTarget {
+    /// Rows of a list; `first` is the item drawn on the top row
+    Rows {
+        list: ClickList,
+        first: usize,
+    },
+    Tab(RepoTab),
+}
+
+/// Browse screen state. Owners and repos are derived from `App::repos`;
+/// `prs` belong to the repo highlighted in the middle column.
+#[derive(Debug, Clone, Default)]
+pub struct BrowseState {
+    pub column: BrowseColumn,
+    pub owner_index: usize,
+    pub repo_index: usize,
+    pub pr_index: usize,
+    pub prs: Vec<PrSummary>,
+    pub prs_repo: Option<(String, String)>,
+    /// Repos refreshed from the forge this session; the rest show cached PRs
+    fetched: HashSet<(String, String)>,
+    /// When the highlighted repo last changed
+    moved_at: Option<std::time::Instant>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -35,6 +293,297 @@ pub enum InputMode {
     Search,
     Confirm,
     SelectPopup,
+    /// Checkbox list: Space toggles, Enter applies
+    MultiSelectPopup,
+    /// Command palette: typing filters, Enter runs
+    Palette,
+    /// One-line comment prompt: Enter posts, Esc cancels
+    Comment,
+    /// Commit search prompt: Enter searches every repo
+    FindCommit,
+    /// PR or issue number prompt: Enter opens it
+    JumpToNumber,
+    /// Repo insights overlay: Esc closes
+    Insights,
+    /// Issue or PR form: Tab moves between fields, Enter saves, Esc cancels
+    Form,
+}
+
+/// Names of the form popup's fields, in Tab order
+pub const FORM_FIELDS: [&str; 4] = ["Title", "Labels", "Assignees", "Milestone"];
+
+/// Title and metadata being typed into the form popup. Labels and assignees
+/// are comma-separated; the milestone is matched by title when saving
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Form {
+    pub target: FormTarget,
+    /// One per `FORM_FIELDS`
+    pub values: [String; 4],
+    pub focus: usize,
+}
+
+impl Form {
+    fn new(
+        target: FormTarget,
+        title: &str,
+        labels: &[Label],
+        assignees: &[String],
+        milestone: Option<&str>,
+    ) -> Self {
+        let labels: Vec<&str> = labels.iter().map(|l| l.name.as_str()).collect();
+        Form {
+            target,
+            values: [
+                title.to_string(),
+                labels.join(", "),
+                assignees.join(", "),
+                milestone.unwrap_or_default().to_string(),
+            ],
+            focus: 0,
+        }
+    }
+
+    /// A new issue's form filled from a template's front matter
+    fn from_template(template: &IssueTemplate) -> Self {
+        Form {
+            target: FormTarget::NewIssue,
+            values: [
+                template.title.clone(),
+                template.labels.join(", "),
+                template.assignees.join(", "),
+                String::new(),
+            ],
+            focus: 0,
+        }
+    }
+
+    /// The form as the forge takes it, with the milestone looked up in
+    /// `milestones`; an empty milestone clears it
+    pub fn fields(&self, milestones: &[Milestone]) -> Result<IssueFields, GritError> {
+        let list = |value: &str| -> Vec<String> {
+            value
+                .split(',')
+                .map(|s| s.trim().trim_start_matches('@').to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        };
+        let milestone = match self.values[3].trim() {
+            "" => None,
+            title => Some(
+                milestones
+                    .iter()
+                    .find(|m| m.title.eq_ignore_ascii_case(title))
+                    .ok_or_else(|| GritError::NotFound(format!("milestone \"{}\"", title)))?
+                    .id,
+            ),
+        };
+        Ok(IssueFields {
+            title: self.values[0].trim().to_string(),
+            labels: list(&self.values[1]),
+            assignees: list(&self.values[2]),
+            milestone,
+        })
+    }
+}
+
+/// A command palette entry: what runs and the key that does the same
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    pub name: &'static str,
+    pub key: String,
+    pub action: Action,
+}
+
+/// List in PR detail that Enter opens an item from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrFocus {
+    /// Enter pages the selected file's diff
+    #[default]
+    Files,
+    /// Enter opens the selected commit
+    Commits,
+}
+
+/// Unfinished work that a quit would lose
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PendingWork {
+    /// A quick comment cancelled before posting
+    Draft {
+        owner: String,
+        repo: String,
+        number: u64,
+    },
+    /// Line comments queued for a review that hasn't been submitted
+    ReviewComments {
+        owner: String,
+        repo: String,
+        number: u64,
+        count: usize,
+    },
+    /// In-progress runs on the open Actions tab
+    WatchedRuns(usize),
+}
+
+impl std::fmt::Display for PendingWork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PendingWork::Draft {
+                owner,
+                repo,
+                number,
+            } => write!(f, "draft on {}/{}#{}", owner, repo, number),
+            PendingWork::ReviewComments {
+                owner,
+                repo,
+                number,
+                count,
+            } => write!(
+                f,
+                "{} review comments on {}/{}#{}",
+                count, owner, repo, number
+            ),
+            PendingWork::WatchedRuns(n) => write!(f, "{} watched runs", n),
+        }
+    }
+}
+
+/// What got done this session, for the recap printed on quit
+#[derive(Debug, Clone)]
+pub struct SessionStats {
+    pub started: std::time::Instant,
+    pub reviews: u32,
+    pub comments: u32,
+    pub merges: u32,
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self {
+            started: std::time::Instant::now(),
+            reviews: 0,
+            comments: 0,
+            merges: 0,
+        }
+    }
+}
+
+impl SessionStats {
+    /// "2 PRs reviewed, 5 comments posted, 1 merge in 1h 04m"
+    pub fn summary(&self) -> String {
+        let plural =
+            |n: u32, one: &str, many: &str| format!("{} {}", n, if n == 1 { one } else { many });
+        let secs = self.started.elapsed().as_secs();
+        let time = match secs {
+            0..=59 => format!("{}s", secs),
+            60..=3599 => format!("{}m", secs / 60),
+            _ => format!("{}h {:02}m", secs / 3600, secs / 60 % 60),
+        };
+        format!(
+            "{} reviewed, {} posted, {} in {}",
+            plural(self.reviews, "PR", "PRs"),
+            plural(self.comments, "comment", "comments"),
+            plural(self.merges, "merge", "merges"),
+            time
+        )
+    }
+}
+
+/// A PR's description and conversation as first shown (the cached copy
+/// from the last visit, if any), so refreshes can say what changed since
+#[derive(Debug, Clone, Default)]
+pub struct PrSnapshot {
+    /// `None` until the PR first loads
+    pub body: Option<String>,
+    /// `None` until the comments first load
+    pub comments: Option<Vec<Comment>>,
+}
+
+impl PrSnapshot {
+    pub fn changed(&self, body: &str, comments: &[Comment]) -> bool {
+        let same = |old: &[Comment]| {
+            old.len() == comments.len()
+                && old
+                    .iter()
+                    .zip(comments)
+                    .all(|(o, c)| o.id == c.id && o.body == c.body)
+        };
+        self.body.as_deref().is_some_and(|old| old != body)
+            || self.comments.as_deref().is_some_and(|old| !same(old))
+    }
+
+    /// Diff from the snapshot to now for the pager; `None` if nothing changed
+    pub fn changes(&self, number: u64, body: &str, comments: &[Comment]) -> Option<String> {
+        if !self.changed(body, comments) {
+            return None;
+        }
+        let mut out = format!("--- #{} when opened\n+++ #{} now\n", number, number);
+        let mut hunk = |header: String, lines: Vec<String>| {
+            out.push_str(&format!("@@ {} @@\n", header));
+            for line in lines {
+                out.push_str(&line);
+                out.push('\n');
+            }
+        };
+
+        if let Some(old) = self.body.as_deref().filter(|old| *old != body) {
+            hunk("description".to_string(), line_diff(old, body));
+        }
+        let old_comments = self.comments.as_deref().unwrap_or_default();
+        if self.comments.is_some() {
+            for c in comments {
+                match old_comments.iter().find(|o| o.id == c.id) {
+                    None => hunk(
+                        format!("new comment by {}", c.author),
+                        line_diff("", &c.body),
+                    ),
+                    Some(o) if o.body != c.body => hunk(
+                        format!("comment by {} edited", c.author),
+                        line_diff(&o.body, &c.body),
+                    ),
+                    Some(_) => {}
+                }
+            }
+            for o in old_comments {
+                if !comments.iter().any(|c| c.id == o.id) {
+                    hunk(
+                        format!("comment by {} deleted", o.author),
+                        line_diff(&o.body, ""),
+                    );
+                }
+            }
+        }
+        Some(out)
+    }
+}
+
+/// Kind of a line in the PR detail body (description followed by conversation)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrLine {
+    Body,
+    Section,
+    /// First line of a comment; `target` is where `+` reacts when it's at the top
+    CommentHeader {
+        bot: bool,
+        target: ReactionTarget,
+    },
+    Comment,
+    /// Header of a review thread; `index` points into `App::review_threads`
+    ThreadHeader {
+        index: usize,
+        resolved: bool,
+    },
+    /// A changed file; `index` points into `App::pr_files`
+    File {
+        index: usize,
+    },
+    /// A commit in the PR; `index` points into `App::pr_commits`
+    Commit {
+        index: usize,
+    },
+    /// An entry of the Links footer; `index` points into `App::body_links`
+    Link {
+        index: usize,
+    },
 }
 
 #[derive(Debug, Clone, Default)]
@@ -45,10 +594,180 @@ pub struct SearchState {
     pub current_match: usize,
     /// For content views: (line_index, byte_start, byte_end)
     pub content_matches: Vec<(usize, usize, usize)>,
+    /// `App::detail_width` the PR detail matches were found at; they're
+    /// found again once a resize rewraps the lines
+    pub wrap_width: usize,
 }
 
-const PAGE_SIZE: usize = 50;
 const PREFETCH_THRESHOLD: usize = 5;
+/// PageUp/PageDown step until a frame has been drawn
+const DEFAULT_PAGE_SIZE: usize = 10;
+/// How often the Actions tab re-polls while any visible run is still queued or running
+const ACTIONS_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+/// How long a selection has to rest before the forge is asked about it: the
+/// browse column's PRs, the preview pane's details
+const BROWSE_SETTLE: std::time::Duration = std::time::Duration::from_millis(400);
+/// Pause between background detail fetches so prefetching never bursts the API
+/// Entries kept in the Recent list
+const RECENT_LIMIT: usize = 30;
+/// Depth of the `Ctrl+O` back stack
+const BACK_STACK_LIMIT: usize = 100;
+/// Pause between background detail fetches so prefetching never bursts the API
+const HOME_PREFETCH_SPACING: std::time::Duration = std::time::Duration::from_millis(500);
+/// Task label of artifact downloads, whose status bar line goes with them
+const DOWNLOAD_TASK: &str = "Download artifact";
+const DIFF_TASK: &str = "Load diff";
+/// PR diffs past this size are spooled to a temp file for the pager
+const DIFF_SPOOL_BYTES: usize = 8 * 1024 * 1024;
+/// Repos whose commits are fetched at once when finding a commit
+const FIND_COMMIT_CONCURRENCY: usize = 8;
+/// How long the PR list selection rests before its neighbors are prefetched
+const PR_PREFETCH_SETTLE: std::time::Duration = std::time::Duration::from_millis(250);
+/// Rows on each side of the selection whose details are prefetched
+const PR_PREFETCH_NEIGHBORS: usize = 2;
+/// Lines of the log file the debug screen holds
+const LOG_LINES: usize = 1000;
+/// Calls a bulk action keeps in flight at once
+const BULK_CONCURRENCY: usize = 4;
+/// PR details fetched at once by the list prefetcher
+const PR_PREFETCH_CONCURRENCY: usize = 2;
+/// How often an offline session checks whether the forge is reachable again
+const OFFLINE_PROBE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15);
+/// How long a count or `g`/`z` waits for the rest of its command, as vim's
+/// `timeoutlen`
+const KEY_SEQUENCE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+/// Pages of runs fetched to build the Actions tab's per-workflow summary
+const WORKFLOW_HISTORY_PAGES: u32 = 3;
+/// Largest count prefix; more would only spin through the list
+const MAX_COUNT: usize = 9999;
+/// Merge popup rows, in order; the auto-merge and delete-branch checkboxes
+/// follow them
+const MERGE_METHODS: [MergeMethod; 3] =
+    [MergeMethod::Merge, MergeMethod::Squash, MergeMethod::Rebase];
+
+/// Where grit was left and what was selected there, saved on quit and offered
+/// back on the next start with `restore_session`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    pub location: LastLocation,
+    pub home_section: HomeSection,
+    pub repo_tab: RepoTab,
+    /// Selection in the list `location` shows
+    pub index: usize,
+    /// PR open on top of it: owner, repo, number
+    pub pr: Option<(String, String, u64)>,
+    /// How far down the PR's conversation was scrolled
+    pub scroll: usize,
+}
+
+impl Session {
+    /// What restoring brings back, for the prompt
+    pub fn label(&self) -> String {
+        match (&self.pr, &self.location) {
+            (Some((owner, repo, number)), _) => format!("{}/{} #{}", owner, repo, number),
+            (None, LastLocation::Repo { owner, name }) => format!("{}/{}", owner, name),
+            (None, LastLocation::Repos) => "the repo list".to_string(),
+            (None, LastLocation::Home) => "Home".to_string(),
+        }
+    }
+
+    /// Whether restoring would only land where a fresh start does anyway
+    fn is_fresh(&self) -> bool {
+        self.location == LastLocation::Home
+            && self.home_section == HomeSection::default()
+            && self.index == 0
+            && self.pr.is_none()
+    }
+}
+
+/// A screen left for a PR or commit: Back returns to it, and what Back left
+/// is kept the same way for Forward
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NavEntry {
+    pub screen: Screen,
+    pub repo: Option<(String, String)>,
+    /// PR shown there, when it was a PR detail
+    pub pr: Option<u64>,
+    /// Commit shown there, when it was a commit detail
+    pub commit: Option<String>,
+    pub scroll_offset: usize,
+}
+
+impl NavEntry {
+    /// Whether both are the same PR or commit screen
+    fn same_place(&self, other: &NavEntry) -> bool {
+        self.screen == other.screen
+            && self.repo == other.repo
+            && self.pr == other.pr
+            && self.commit == other.commit
+    }
+}
+
+/// What each workspace (`Ctrl+T`) keeps to itself: where it is, what's
+/// selected and the repo and PR it has open. Home's lists, the repo list,
+/// drafts and running tasks are shared.
+#[derive(Default)]
+pub struct Workspace {
+    screen: Screen,
+    nav_stack: Vec<NavEntry>,
+    nav_forward: Vec<NavEntry>,
+    repos_screen: Screen,
+    search: SearchState,
+    back_stack: Vec<RecentItem>,
+    home_section: HomeSection,
+    review_index: usize,
+    my_pr_index: usize,
+    assigned_index: usize,
+    mentions_index: usize,
+    saved_index: usize,
+    recent_index: usize,
+    repo_index: usize,
+    current_repo: Option<(String, String)>,
+    repo_tab: RepoTab,
+    prs: Vec<PrSummary>,
+    pr_index: usize,
+    prs_pagination: PaginationState,
+    issues: Vec<Issue>,
+    issue_index: usize,
+    issues_pagination: PaginationState,
+    commits: Vec<Commit>,
+    commit_index: usize,
+    commits_pagination: PaginationState,
+    action_runs: Vec<ActionRun>,
+    action_index: usize,
+    actions_pagination: PaginationState,
+    workflow_summary: bool,
+    workflows: Vec<WorkflowSummary>,
+    workflow_index: usize,
+    milestones: Vec<Milestone>,
+    labels: Vec<Label>,
+    milestone_filter: Option<Milestone>,
+    pr_filter: PrFilter,
+    marks: Marks,
+    prs_seen_until: Option<chrono::DateTime<chrono::Utc>>,
+    issues_seen_until: Option<chrono::DateTime<chrono::Utc>>,
+    fork_status: Option<ForkStatus>,
+    repo_stats: Option<RepoStats>,
+    current_pr: Option<PullRequest>,
+    pr_comments: Vec<Comment>,
+    pr_seen: PrSnapshot,
+    pr_reviews: PrReviews,
+    pr_checks: PrChecks,
+    pr_files: Vec<CommitFile>,
+    file_index: usize,
+    pr_commits: Vec<Commit>,
+    pr_commit_index: usize,
+    pr_focus: PrFocus,
+    diff_cursor: usize,
+    viewed_files: Option<Vec<String>>,
+    review_threads: Vec<ReviewThread>,
+    thread_index: usize,
+    xref_index: Option<usize>,
+    current_commit: Option<CommitDetail>,
+    commit_checks: Option<(String, Vec<CommitCheck>)>,
+    scroll_offset: usize,
+    list_offsets: std::cell::RefCell<HashMap<ClickList, usize>>,
+}
 
 #[derive(Debug, Clone)]
 pub struct PaginationState {
@@ -56,6 +775,7 @@ pub struct PaginationState {
     pub has_more: bool,
     pub loading_more: bool,
     pub total_count: Option<u64>,
+    pub total_pages: Option<u32>,
 }
 
 impl Default for PaginationState {
@@ -65,8 +785,34 @@ impl Default for PaginationState {
             has_more: false,
             loading_more: false,
             total_count: None,
+            total_pages: None,
+        }
+    }
+}
+
+impl PaginationState {
+    /// State after loading the first page
+    fn first(info: PageInfo, len: usize, page_size: usize) -> Self {
+        Self {
+            page: 1,
+            has_more: info.has_more(1, len, page_size),
+            loading_more: false,
+            total_count: info.total_count,
+            total_pages: info.pages(page_size),
         }
     }
+
+    /// Record another page; totals the forge didn't repeat are kept
+    fn appended(&mut self, info: PageInfo, len: usize, page_size: usize) {
+        self.loading_more = false;
+        self.total_count = info.total_count.or(self.total_count);
+        self.total_pages = info.pages(page_size).or(self.total_pages);
+        let known = PageInfo {
+            total_count: self.total_count,
+            total_pages: self.total_pages,
+        };
+        self.has_more = known.has_more(self.page, len, page_size);
+    }
 }
 
 pub struct App {
@@ -79,6 +825,28 @@ pub struct App {
     pub popup_items: Vec<String>,
     pub popup_index: usize,
     pub popup_title: String,
+    /// Checked state per `popup_items` entry in a multi-select popup
+    pub popup_checked: Vec<bool>,
+    /// The merge popup's "merge when checks pass" box
+    merge_auto: bool,
+    /// The merge popup's "delete branch after merge" box
+    merge_delete_branch: bool,
+    pub palette_query: String,
+    pub comment_input: String,
+    /// Fields of the issue or PR form popup
+    pub form: Form,
+    /// Templates offered for a new issue, in popup order after "Blank"
+    issue_templates: Vec<IssueTemplate>,
+    /// Body of the template the new issue form started from; its description
+    /// editor opens on it
+    template_body: Option<String>,
+    pub find_query: String,
+    /// Digits typed into the `#` prompt
+    pub jump_input: String,
+    /// Commits matching `find_query`, newest first
+    pub commit_hits: Vec<CommitHit>,
+    /// Quick comments cancelled before posting, by (owner, repo, PR number)
+    pub comment_drafts: BTreeMap<(String, String, u64), String>,
 
     // Flash message (transient success messages)
     pub flash_message: Option<(String, std::time::Instant)>,
@@ -88,7 +856,33 @@ pub struct App {
     pub my_prs: Vec<MyPr>,
     pub home_section: HomeSection,
     pub review_index: usize,
+    pub review_sort: ReviewSort,
     pub my_pr_index: usize,
+    /// Open issues assigned to you
+    pub assigned: Vec<IssueRef>,
+    pub assigned_index: usize,
+    /// Open issues and PRs that mention you
+    pub mentions: Vec<IssueRef>,
+    pub mentions_index: usize,
+    /// Named queries from `[searches]`
+    pub searches: Vec<(String, String)>,
+    /// Which Home sections show, in what order and how tall
+    home_config: HomeConfig,
+    /// Home sections shrunk to their title with `za`
+    pub collapsed: HashSet<HomeSection>,
+    /// Which of them the Saved section runs
+    pub search_choice: usize,
+    pub saved: Vec<IssueRef>,
+    pub saved_index: usize,
+    pub saved_pagination: PaginationState,
+    /// Repos and PRs opened lately, newest first
+    pub recent: Vec<RecentItem>,
+    pub recent_index: usize,
+    /// Items opened this session, oldest first; `Ctrl+O` walks back down it
+    pub back_stack: Vec<RecentItem>,
+    /// One slot per workspace; the live one's slot is empty until it's left
+    pub workspaces: Vec<Workspace>,
+    pub workspace_index: usize,
 
     // Repo view
     pub repo_tab: RepoTab,
@@ -97,26 +891,154 @@ pub struct App {
     pub issues: Vec<Issue>,
     pub commits: Vec<Commit>,
     pub action_runs: Vec<ActionRun>,
+    pub milestones: Vec<Milestone>,
+    pub labels: Vec<Label>,
+    /// Only show PRs and issues in this milestone; the forge does the filtering
+    pub milestone_filter: Option<Milestone>,
+    /// Whose open PRs the PR tab lists
+    pub pr_filter: PrFilter,
+    marks: Marks,
+    /// Newest PR / issue update in the cache when the repo was opened. Anything
+    /// updated after that is new since the last visit and gets a badge.
+    pub prs_seen_until: Option<chrono::DateTime<chrono::Utc>>,
+    pub issues_seen_until: Option<chrono::DateTime<chrono::Utc>>,
     pub issue_index: usize,
     pub commit_index: usize,
     pub action_index: usize,
+    /// The Actions tab shows one row per workflow instead of single runs
+    pub workflow_summary: bool,
+    /// Workflows of the current repo, built from its recent run history
+    pub workflows: Vec<WorkflowSummary>,
+    pub workflow_index: usize,
+    /// Artifacts of the run the download popup was opened on
+    artifacts: Vec<Artifact>,
+    /// Counts behind the insights popup, for the current repo
+    pub repo_stats: Option<RepoStats>,
+    /// Canned replies from `[snippets]`, by name
+    pub snippets: Vec<(String, String)>,
+    /// Where the chosen snippet goes once picked
+    snippet_context: Option<EditorContext>,
+    /// Text the next comment or review editor starts with
+    pub editor_prefill: Option<String>,
+    /// How the current repo compares to its upstream, if it's a fork
+    pub fork_status: Option<ForkStatus>,
+    /// Star state of repos looked up or changed this session, by "owner/repo"
+    starred: HashMap<String, bool>,
+    /// The artifact download in flight; one at a time
+    pub download: Option<Download>,
+    /// The PR diff coming down for the pager, apart from artifact downloads
+    pub diff_download: Option<Download>,
+    /// The bulk action in flight; one at a time
+    pub bulk: Option<Bulk>,
+    /// Spawned work still in flight, by id
+    pub tasks: std::cell::RefCell<BTreeMap<u64, Task>>,
+    next_task_id: std::cell::Cell<u64>,
+    /// Ids behind the rows of the tasks popup
+    task_rows: Vec<u64>,
+
+    // Browse columns
+    pub browse: BrowseState,
+    pub preview: PreviewState,
+    pr_prefetch: PrPrefetch,
+    /// Where Back from a repo returns to: the repo list or the browse columns
+    repos_screen: Screen,
 
     // Existing state
     pub repos: Vec<Repository>,
+    /// Organization or group the repo list shows; `None` for the user's own repos
+    pub repo_scope: Option<String>,
+    /// Choices of the "Repositories Of" popup
+    pub orgs: Vec<String>,
     pub prs: Vec<PrSummary>,
     pub current_pr: Option<PullRequest>,
+    pub pr_comments: Vec<Comment>,
+    /// What the PR looked like when opened, for "show changes"
+    pub pr_seen: PrSnapshot,
+    pub pr_reviews: PrReviews,
+    /// Candidates of the "Request Review" popup, least loaded first
+    pub reviewer_load: Vec<ReviewerLoad>,
+    pub pr_files: Vec<CommitFile>,
+    pub file_index: usize,
+    pub pr_commits: Vec<Commit>,
+    pub pr_commit_index: usize,
+    /// Which PR detail list Enter acts on: the last one stepped through
+    pub pr_focus: PrFocus,
+    /// Cursor line in the diff view of `pr_files[file_index]`
+    pub diff_cursor: usize,
+    /// Show the diff view side by side (`v`) rather than unified
+    pub diff_split: bool,
+    /// Whether the last frame really drew it side by side; narrow terminals
+    /// fall back to unified
+    pub diff_split_shown: std::cell::Cell<bool>,
+    /// Layout of the last patch the diff view showed
+    diff_layout: std::cell::RefCell<Rc<DiffLayout>>,
+    /// Line comments waiting for the next review, by (owner, repo, PR number)
+    pub review_drafts: BTreeMap<(String, String, u64), Vec<ReviewComment>>,
+    pub pr_checks: PrChecks,
+    /// `None` when the forge can't report viewed files
+    pub viewed_files: Option<Vec<String>>,
+    pub review_threads: Vec<ReviewThread>,
+    pub thread_index: usize,
+    /// The `#123` / `@user` picked with Tab or `n`, into `App::pr_xrefs`
+    pub xref_index: Option<usize>,
+    pub hide_resolved_threads: bool,
+    pub hide_bot_comments: bool,
     pub current_commit: Option<CommitDetail>,
+    /// CI checks, tagged with the sha they were reported on
+    commit_checks: Option<(String, Vec<CommitCheck>)>,
     pub repo_index: usize,
     pub pr_index: usize,
     pub scroll_offset: usize,
     pub loading: bool,
+    /// Set for the duration of a hard refresh so loads skip the cache
+    bypass_cache: bool,
     pub error: Option<String>,
+    /// The forge couldn't be reached; views come from the cache until it answers
+    pub offline: bool,
+    /// When the last cached copy served was written, for the OFFLINE header
+    pub cached_at: std::cell::Cell<Option<std::time::SystemTime>>,
+    /// Mutations that couldn't reach the forge, replayed once it answers again
+    pub outbox: Vec<Mutation>,
+    /// Last reachability check while offline or holding queued mutations
+    last_probe: Option<std::time::Instant>,
+    /// When a rate-limited view reloads itself
+    retry_at: Option<std::time::Instant>,
     pub should_quit: bool,
     pub current_repo: Option<(String, String)>,
-    prev_screen: Option<Screen>,
+    /// Screens left for the PR or commit open now, innermost last
+    pub nav_stack: Vec<NavEntry>,
+    /// PRs and commits Back left, most recent last
+    pub nav_forward: Vec<NavEntry>,
     pub forge_name: String,
     forge: Arc<dyn Forge>,
+    /// Config name of the forge in use
+    pub active_forge: String,
+    /// Forges set up so far, by config name; each one fails on its own
+    pub forge_states: HashMap<String, ForgeState>,
     pub forge_configs: Vec<crate::config::ForgeConfig>,
+    pub ui_config: UiConfig,
+    /// `[[ui.links]]` patterns, compiled once
+    pub link_rules: Vec<LinkRule>,
+    /// Repo from the command line or the git checkout grit was started in;
+    /// opened by `Start`, along with `start_pr` when one was asked for
+    pub start_repo: Option<(String, String)>,
+    pub start_pr: Option<u64>,
+    /// Session saved by the last quit, while the prompt to restore it is up
+    pub saved_session: Option<Session>,
+    /// Conversation scroll to put back once a restored PR loads
+    session_scroll: Option<usize>,
+    /// New review requests and failed checks found by Home refreshes, shown
+    /// in the header until dismissed with `!`
+    pub alerts: Vec<String>,
+    /// Home has loaded once for this forge, so later loads can say what's new
+    home_seen: bool,
+    /// Log file the debug screen tails
+    pub log_path: Option<std::path::PathBuf>,
+    /// Its last lines, re-read on ticks while the screen is open
+    pub log_lines: Vec<String>,
+    /// Size of the log when last read, so unchanged files aren't re-read
+    log_size: u64,
+    pub stats: SessionStats,
     action_tx: mpsc::UnboundedSender<Action>,
     load_id: u64,
 
@@ -126,6 +1048,24 @@ pub struct App {
     pub issues_pagination: PaginationState,
     pub commits_pagination: PaginationState,
     pub actions_pagination: PaginationState,
+
+    // Live polling of in-progress workflow runs
+    actions_last_poll: Option<std::time::Instant>,
+
+    /// Clickable regions of the last frame, recorded while rendering
+    pub click_map: std::cell::RefCell<Vec<(ratatui::layout::Rect, ClickTarget)>>,
+    /// Rows the focused list or detail view showed last frame; 0 before the first
+    pub page_height: std::cell::Cell<usize>,
+    /// Columns inside the PR detail body last frame, which its prose wraps
+    /// to; 0 before the first, leaving lines whole
+    pub detail_width: std::cell::Cell<usize>,
+    /// Where each list was scrolled last frame, so the view only moves once
+    /// the selection reaches an edge
+    pub list_offsets: std::cell::RefCell<HashMap<ClickList, usize>>,
+    /// `zz` asked for the next frame to draw the selection mid-list
+    pub center_selection: std::cell::Cell<bool>,
+    /// Count and first key of a command still being typed
+    pub pending_keys: std::cell::Cell<PendingKeys>,
 }
 
 impl App {
@@ -133,6 +1073,7 @@ impl App {
         forge: Arc<dyn Forge>,
         action_tx: mpsc::UnboundedSender<Action>,
         forge_configs: Vec<crate::config::ForgeConfig>,
+        ui_config: UiConfig,
     ) -> Self {
         let forge_name = forge.name().to_string();
         Self {
@@ -145,6 +1086,18 @@ impl App {
             popup_items: Vec::new(),
             popup_index: 0,
             popup_title: String::new(),
+            popup_checked: Vec::new(),
+            merge_auto: false,
+            merge_delete_branch: false,
+            palette_query: String::new(),
+            comment_input: String::new(),
+            form: Form::default(),
+            issue_templates: Vec::new(),
+            template_body: None,
+            find_query: String::new(),
+            jump_input: String::new(),
+            commit_hits: Vec::new(),
+            comment_drafts: BTreeMap::new(),
 
             // Flash
             flash_message: None,
@@ -154,7 +1107,24 @@ impl App {
             my_prs: Vec::new(),
             home_section: HomeSection::default(),
             review_index: 0,
+            review_sort: ReviewSort::default(),
             my_pr_index: 0,
+            assigned: Vec::new(),
+            assigned_index: 0,
+            mentions: Vec::new(),
+            mentions_index: 0,
+            searches: Vec::new(),
+            home_config: HomeConfig::default(),
+            collapsed: HashSet::new(),
+            search_choice: 0,
+            saved: Vec::new(),
+            saved_index: 0,
+            saved_pagination: PaginationState::default(),
+            recent: Vec::new(),
+            recent_index: 0,
+            back_stack: Vec::new(),
+            workspaces: vec![Workspace::default()],
+            workspace_index: 0,
 
             // Repo view
             repo_tab: RepoTab::default(),
@@ -163,26 +1133,98 @@ impl App {
             issues: Vec::new(),
             commits: Vec::new(),
             action_runs: Vec::new(),
+            milestones: Vec::new(),
+            labels: Vec::new(),
+            milestone_filter: None,
+            pr_filter: PrFilter::default(),
+            marks: Marks::default(),
+            prs_seen_until: None,
+            issues_seen_until: None,
             issue_index: 0,
             commit_index: 0,
             action_index: 0,
+            workflow_summary: false,
+            workflows: Vec::new(),
+            workflow_index: 0,
+            artifacts: Vec::new(),
+            repo_stats: None,
+            snippets: Vec::new(),
+            snippet_context: None,
+            editor_prefill: None,
+            fork_status: None,
+            starred: HashMap::new(),
+            download: None,
+            diff_download: None,
+            bulk: None,
+            tasks: Default::default(),
+            next_task_id: Default::default(),
+            task_rows: Vec::new(),
 
             // Existing
             repos: Vec::new(),
+            repo_scope: None,
+            orgs: Vec::new(),
             prs: Vec::new(),
             current_pr: None,
+            pr_comments: Vec::new(),
+            pr_seen: PrSnapshot::default(),
+            pr_reviews: PrReviews::default(),
+            reviewer_load: Vec::new(),
+            pr_files: Vec::new(),
+            file_index: 0,
+            pr_commits: Vec::new(),
+            pr_commit_index: 0,
+            pr_focus: PrFocus::default(),
+            diff_cursor: 0,
+            diff_split: false,
+            diff_split_shown: Default::default(),
+            diff_layout: Default::default(),
+            review_drafts: BTreeMap::new(),
+            pr_checks: PrChecks::default(),
+            viewed_files: None,
+            review_threads: Vec::new(),
+            thread_index: 0,
+            xref_index: None,
+            hide_resolved_threads: false,
+            hide_bot_comments: false,
             current_commit: None,
+            commit_checks: None,
             repo_index: 0,
             pr_index: 0,
             scroll_offset: 0,
             loading: false,
+            bypass_cache: false,
             error: None,
+            offline: false,
+            cached_at: Default::default(),
+            outbox: Vec::new(),
+            last_probe: None,
+            retry_at: None,
             should_quit: false,
             current_repo: None,
-            prev_screen: None,
+            nav_stack: Vec::new(),
+            nav_forward: Vec::new(),
+            browse: BrowseState::default(),
+            preview: PreviewState::default(),
+            pr_prefetch: PrPrefetch::default(),
+            repos_screen: Screen::RepoList,
             forge_name,
             forge,
+            active_forge: String::new(),
+            forge_states: HashMap::new(),
             forge_configs,
+            link_rules: crate::links::compile(&ui_config.links),
+            ui_config,
+            start_repo: None,
+            start_pr: None,
+            saved_session: None,
+            session_scroll: None,
+            alerts: Vec::new(),
+            home_seen: false,
+            log_path: crate::logging::log_path(),
+            log_lines: Vec::new(),
+            log_size: 0,
+            stats: SessionStats::default(),
             action_tx,
             load_id: 0,
 
@@ -192,20 +1234,90 @@ impl App {
             issues_pagination: PaginationState::default(),
             commits_pagination: PaginationState::default(),
             actions_pagination: PaginationState::default(),
+
+            actions_last_poll: None,
+            click_map: Default::default(),
+            page_height: Default::default(),
+            detail_width: Default::default(),
+            list_offsets: Default::default(),
+            center_selection: Default::default(),
+            pending_keys: Default::default(),
         }
     }
 
     pub fn handle_event(&self, event: Event) -> Action {
         match event {
             Event::Key(key) => self.handle_key(key),
+            Event::Mouse(mouse) => self.handle_mouse(mouse),
+            Event::Tick => Action::Tick,
+            _ => Action::None,
+        }
+    }
+
+    fn handle_mouse(&self, mouse: MouseEvent) -> Action {
+        if self.input_mode != InputMode::Normal {
+            return Action::None;
+        }
+        match mouse.kind {
+            MouseEventKind::ScrollDown => Action::ScrollDown,
+            MouseEventKind::ScrollUp => Action::ScrollUp,
+            MouseEventKind::Down(MouseButton::Left) => {
+                let position = ratatui::layout::Position::new(mouse.column, mouse.row);
+                let map = self.click_map.borrow();
+                match map.iter().find(|(area, _)| area.contains(position)) {
+                    Some((area, ClickTarget::Rows { list, first })) => {
+                        Action::ClickRow(*list, first + (mouse.row - area.y) as usize)
+                    }
+                    Some((_, ClickTarget::Tab(tab))) if *tab != self.repo_tab => {
+                        Action::SwitchRepoTab(*tab)
+                    }
+                    _ => Action::None,
+                }
+            }
             _ => Action::None,
         }
     }
 
     fn handle_key(&self, key: KeyEvent) -> Action {
         match &self.input_mode {
-            InputMode::Normal => self.handle_key_normal(key),
+            InputMode::Normal => self.handle_key_sequence(key),
             InputMode::Search => self.handle_key_search(key),
+            InputMode::Comment => match key.code {
+                KeyCode::Esc => Action::CancelQuickComment,
+                KeyCode::Enter => Action::SubmitQuickComment,
+                KeyCode::Backspace => Action::QuickCommentBackspace,
+                KeyCode::Char(c) => Action::QuickCommentInput(c),
+                _ => Action::None,
+            },
+            InputMode::Form => match key.code {
+                KeyCode::Esc => Action::ConfirmNo,
+                KeyCode::Enter => Action::SubmitForm,
+                KeyCode::Tab | KeyCode::Down => Action::FormNextField,
+                KeyCode::BackTab | KeyCode::Up => Action::FormPrevField,
+                KeyCode::Backspace => Action::FormBackspace,
+                KeyCode::Char(c) => Action::FormInput(c),
+                _ => Action::None,
+            },
+            InputMode::FindCommit => match key.code {
+                KeyCode::Esc => Action::ConfirmNo,
+                KeyCode::Enter => Action::SubmitFindCommit,
+                KeyCode::Backspace => Action::FindCommitBackspace,
+                KeyCode::Char(c) => Action::FindCommitInput(c),
+                _ => Action::None,
+            },
+            InputMode::JumpToNumber => match key.code {
+                KeyCode::Esc => Action::ConfirmNo,
+                KeyCode::Enter => Action::SubmitJump,
+                KeyCode::Backspace => Action::JumpBackspace,
+                KeyCode::Char(c) if c.is_ascii_digit() => Action::JumpInput(c),
+                _ => Action::None,
+            },
+            InputMode::Insights => match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('I') => {
+                    Action::ConfirmNo
+                }
+                _ => Action::None,
+            },
             InputMode::Confirm => match key.code {
                 KeyCode::Char('y') => Action::ConfirmYes,
                 KeyCode::Char('n') | KeyCode::Esc => Action::ConfirmNo,
@@ -214,6 +1326,30 @@ impl App {
             InputMode::SelectPopup => match key.code {
                 KeyCode::Char('j') | KeyCode::Down => Action::PopupDown,
                 KeyCode::Char('k') | KeyCode::Up => Action::PopupUp,
+                KeyCode::Char(' ') => Action::PopupToggle,
+                KeyCode::Enter => Action::PopupSelect,
+                KeyCode::Esc => Action::ConfirmNo,
+                _ => Action::None,
+            },
+            InputMode::Palette => match key.code {
+                KeyCode::Esc => Action::ConfirmNo,
+                KeyCode::Enter => Action::PopupSelect,
+                KeyCode::Down => Action::PopupDown,
+                KeyCode::Up => Action::PopupUp,
+                KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    Action::PopupDown
+                }
+                KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    Action::PopupUp
+                }
+                KeyCode::Backspace => Action::PaletteBackspace,
+                KeyCode::Char(c) => Action::PaletteInput(c),
+                _ => Action::None,
+            },
+            InputMode::MultiSelectPopup => match key.code {
+                KeyCode::Char('j') | KeyCode::Down => Action::PopupDown,
+                KeyCode::Char('k') | KeyCode::Up => Action::PopupUp,
+                KeyCode::Char(' ') => Action::PopupToggle,
                 KeyCode::Enter => Action::PopupSelect,
                 KeyCode::Esc => Action::ConfirmNo,
                 _ => Action::None,
@@ -231,9 +1367,78 @@ impl App {
         }
     }
 
-    fn handle_key_normal(&self, key: KeyEvent) -> Action {
-        use crossterm::event::KeyModifiers;
+    /// Count prefixes (`10j`) and two-key commands (`gg`, `zz`) on top of
+    /// `handle_key_normal`. Keys that only start a command wait in
+    /// `pending_keys` and do nothing yet.
+    fn handle_key_sequence(&self, key: KeyEvent) -> Action {
+        let pending = self.pending_keys.take().live();
+        let hold = |keys: PendingKeys| {
+            self.pending_keys.set(PendingKeys {
+                at: Some(std::time::Instant::now()),
+                ..keys
+            });
+            Action::None
+        };
+        if let (KeyCode::Char(c), KeyModifiers::NONE) = (key.code, key.modifiers) {
+            match (pending.prefix, c) {
+                (Some('g'), 'g') => return Action::GoToTop,
+                (Some('z'), 'z') => return Action::CenterSelection,
+                (Some('z'), 'a') if self.screen == Screen::Home => {
+                    return Action::ToggleHomeSection
+                }
+                // As in vim, an unknown second key cancels the command
+                (Some(_), _) => return Action::None,
+                (None, '0'..='9') if c != '0' || pending.count.is_some() => {
+                    let digit = c.to_digit(10).unwrap_or(0) as usize;
+                    let count = pending.count.unwrap_or(0) * 10 + digit;
+                    return hold(PendingKeys {
+                        count: Some(count.min(MAX_COUNT)),
+                        ..pending
+                    });
+                }
+                // `g` stays a single key where it clones or checks out
+                (None, 'g') if matches!(self.handle_key_normal(key), Action::GoToTop) => {
+                    return hold(PendingKeys {
+                        prefix: Some('g'),
+                        ..pending
+                    });
+                }
+                (None, 'z') => {
+                    return hold(PendingKeys {
+                        prefix: Some('z'),
+                        ..pending
+                    });
+                }
+                _ => {}
+            }
+        }
+        let action = self.handle_key_normal(key);
+        match pending.count {
+            Some(count) if count > 1 && is_movement(&action) => {
+                Action::Repeat(count, Box::new(action))
+            }
+            _ => action,
+        }
+    }
 
+    fn handle_key_normal(&self, key: KeyEvent) -> Action {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            match key.code {
+                KeyCode::Char('t') => return Action::NewWorkspace,
+                KeyCode::Char('n') => return Action::NextWorkspace,
+                KeyCode::Char('p') => return Action::PrevWorkspace,
+                KeyCode::Char('w') => return Action::CloseWorkspace,
+                KeyCode::Char('k') => return Action::ShowTasks,
+                KeyCode::Char('l') => return Action::Forward,
+                _ => {}
+            }
+        }
+        if key.code == KeyCode::Char('!') && !self.alerts.is_empty() {
+            return Action::DismissAlerts;
+        }
+        if key.code == KeyCode::F(12) {
+            return Action::ShowLogs;
+        }
         match key.code {
             KeyCode::Char('q') => {
                 if self.screen == Screen::Home {
@@ -245,6 +1450,10 @@ impl App {
             KeyCode::Esc => {
                 if self.search.active {
                     Action::ClearSearch
+                } else if self.screen == Screen::PrDetail && self.xref_index.is_some() {
+                    Action::ClearXref
+                } else if self.marks().is_some() {
+                    Action::ClearMarks
                 } else {
                     match self.screen {
                         Screen::Home => Action::Quit,
@@ -253,32 +1462,43 @@ impl App {
                 }
             }
 
+            // Command palette
+            KeyCode::Char(':') => Action::ShowPalette,
+
             // Search
             KeyCode::Char('/') => Action::EnterSearchMode,
             KeyCode::Char('n') if self.search.active => Action::SearchNext,
             KeyCode::Char('N') if self.search.active => Action::SearchPrev,
+            KeyCode::Char('n') if self.screen == Screen::PrDetail => Action::NextXref,
 
             // Vim navigation
             KeyCode::Char('j') | KeyCode::Down => Action::ScrollDown,
             KeyCode::Char('k') | KeyCode::Up => Action::ScrollUp,
+            // Local git
+            KeyCode::Char('g') if self.screen == Screen::RepoList => Action::CloneRepo,
+            KeyCode::Char('g') if self.screen == Screen::PrDetail => Action::CheckoutPr,
+
             KeyCode::Char('g') | KeyCode::Home => Action::GoToTop,
             KeyCode::Char('G') | KeyCode::End => Action::GoToBottom,
             KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::PageDown,
             KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::PageUp,
             KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::PageDown,
             KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::PageUp,
+            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::JumpBack,
             KeyCode::PageDown => Action::PageDown,
             KeyCode::PageUp => Action::PageUp,
 
             // Tab/section navigation (h/l switch tabs on tabbed screens, back/select on others)
             KeyCode::Char('h') | KeyCode::Left => match self.screen {
-                Screen::Home | Screen::RepoView => Action::PrevTab,
+                Screen::Home | Screen::RepoView | Screen::Browse => Action::PrevTab,
                 _ => Action::Back,
             },
             KeyCode::Char('l') | KeyCode::Right => match self.screen {
-                Screen::Home | Screen::RepoView => Action::NextTab,
+                Screen::Home | Screen::RepoView | Screen::Browse => Action::NextTab,
                 _ => Action::Select,
             },
+            KeyCode::Tab if self.screen == Screen::PrDetail => Action::NextXref,
+            KeyCode::BackTab if self.screen == Screen::PrDetail => Action::PrevXref,
             KeyCode::Tab => Action::NextTab,
             KeyCode::BackTab => Action::PrevTab,
 
@@ -286,13 +1506,23 @@ impl App {
 
             // Diff in pager
             KeyCode::Char('d')
-                if matches!(self.screen, Screen::PrDetail | Screen::CommitDetail) =>
+                if matches!(
+                    self.screen,
+                    Screen::PrDetail | Screen::CommitDetail | Screen::DiffView
+                ) =>
             {
                 Action::ViewDiff
             }
 
-            // Refresh
+            // Refresh: list, selected item, everything bypassing the cache
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::HardRefresh
+            }
             KeyCode::Char('r') => Action::Refresh,
+            KeyCode::Char('u') => Action::RefreshItem,
+            KeyCode::Char('R') if self.screen == Screen::Home => Action::ShowRepoList,
+            KeyCode::Char('s') if self.screen == Screen::Home => Action::CycleReviewSort,
+            KeyCode::Char('Q') if self.screen == Screen::Home => Action::ShowSavedSearchSelect,
 
             // Open in browser / Yank URL
             KeyCode::Char('o') => Action::OpenInBrowser,
@@ -300,6 +1530,43 @@ impl App {
 
             // PR mutations (PrDetail only)
             KeyCode::Char('m') if self.screen == Screen::PrDetail => Action::ShowMergeMethodSelect,
+            KeyCode::Char('E') if matches!(self.screen, Screen::RepoList | Screen::RepoView) => {
+                Action::EditRepoMeta
+            }
+            KeyCode::Char('I') if self.screen == Screen::RepoView => Action::ShowInsights,
+            KeyCode::Char('*') if matches!(self.screen, Screen::RepoList | Screen::RepoView) => {
+                Action::ToggleStar
+            }
+            KeyCode::Char('W') if matches!(self.screen, Screen::RepoList | Screen::RepoView) => {
+                Action::ShowWatchSelect
+            }
+            KeyCode::Char('Y') if matches!(self.screen, Screen::RepoList | Screen::RepoView) => {
+                Action::ForkRepo
+            }
+            KeyCode::Char('U') if self.screen == Screen::RepoView && self.fork_status.is_some() => {
+                Action::SyncFork
+            }
+            KeyCode::Char('V') if self.screen == Screen::PrDetail => Action::ShowRevertSelect,
+            // Marks for bulk actions
+            KeyCode::Char(' ') if self.mark_tab().is_some() => Action::ToggleMark,
+            KeyCode::Char('V') if self.mark_tab().is_some() => Action::MarkRange,
+            KeyCode::Char('x') if !self.marked_numbers().is_empty() => {
+                Action::ShowConfirm(ConfirmAction::CloseMarked(self.marked_numbers()))
+            }
+            KeyCode::Char('A') if self.screen == Screen::PrDetail => Action::ShowReviewerLoad,
+            KeyCode::Char('w') if self.screen == Screen::PrDetail => Action::ShowPrChanges,
+            KeyCode::Char('w')
+                if self.screen == Screen::RepoView && self.repo_tab == RepoTab::Actions =>
+            {
+                Action::ToggleWorkflowSummary
+            }
+            KeyCode::Char('D')
+                if self.screen == Screen::RepoView
+                    && self.repo_tab == RepoTab::Actions
+                    && !self.workflow_summary =>
+            {
+                Action::ShowArtifactSelect
+            }
             KeyCode::Char('x')
                 if matches!(self.screen, Screen::PrDetail)
                     || (self.screen == Screen::RepoView && self.repo_tab == RepoTab::Issues) =>
@@ -331,7 +1598,7 @@ impl App {
                     match self.screen {
                         Screen::PrDetail => {
                             if let Some(pr) = &self.current_pr {
-                                Action::SuspendForEditor(EditorContext::CommentOnPr {
+                                self.compose(EditorContext::CommentOnPr {
                                     owner: owner.clone(),
                                     repo: repo.clone(),
                                     number: pr.number,
@@ -342,7 +1609,7 @@ impl App {
                         }
                         Screen::RepoView => {
                             if let Some(issue) = self.issues.get(self.issue_index) {
-                                Action::SuspendForEditor(EditorContext::CommentOnIssue {
+                                self.compose(EditorContext::CommentOnIssue {
                                     owner: owner.clone(),
                                     repo: repo.clone(),
                                     number: issue.number,
@@ -357,7 +1624,51 @@ impl App {
                     Action::None
                 }
             }
-            KeyCode::Char('R') if self.screen == Screen::PrDetail => Action::ShowReviewSelect,
+            KeyCode::Char('C') if self.screen == Screen::CommitDetail => {
+                match (&self.current_repo, &self.current_commit) {
+                    (Some((owner, repo)), Some(commit)) => {
+                        self.compose(EditorContext::CommentOnCommit {
+                            owner: owner.clone(),
+                            repo: repo.clone(),
+                            sha: commit.sha.clone(),
+                        })
+                    }
+                    _ => Action::None,
+                }
+            }
+            KeyCode::Char('c') if self.screen == Screen::PrDetail => Action::StartQuickComment,
+            KeyCode::Char('c') if self.screen == Screen::DiffView => {
+                match (self.pr_files.get(self.file_index), self.diff_line()) {
+                    (Some(file), Some(line)) => self.compose(EditorContext::LineComment {
+                        path: file.filename.clone(),
+                        line,
+                    }),
+                    _ => Action::None,
+                }
+            }
+            KeyCode::Char('R') if matches!(self.screen, Screen::PrDetail | Screen::DiffView) => {
+                Action::ShowReviewSelect
+            }
+            KeyCode::Char('b') if self.screen == Screen::PrDetail => Action::ToggleBotComments,
+            KeyCode::Char('+') if self.screen == Screen::PrDetail => Action::ShowReactionSelect,
+            KeyCode::Char(']') if self.screen == Screen::PrDetail => Action::NextThread,
+            KeyCode::Char('[') if self.screen == Screen::PrDetail => Action::PrevThread,
+            KeyCode::Char('}') if matches!(self.screen, Screen::PrDetail | Screen::DiffView) => {
+                Action::NextFile
+            }
+            KeyCode::Char('{') if matches!(self.screen, Screen::PrDetail | Screen::DiffView) => {
+                Action::PrevFile
+            }
+            KeyCode::Char(')') if self.screen == Screen::PrDetail => Action::NextPrCommit,
+            KeyCode::Char('(') if self.screen == Screen::PrDetail => Action::PrevPrCommit,
+            KeyCode::Char('e') if self.screen == Screen::PrDetail => Action::OpenFileInEditor,
+            KeyCode::Char('E') if self.screen == Screen::PrDetail => match &self.current_pr {
+                Some(pr) => Action::ShowForm(FormTarget::Pr(pr.number)),
+                None => Action::None,
+            },
+            KeyCode::Char('t') if self.screen == Screen::PrDetail => Action::ToggleThreadResolved,
+            KeyCode::Char('T') if self.screen == Screen::PrDetail => Action::ToggleResolvedThreads,
+            KeyCode::Char('X') if self.screen == Screen::PrDetail => Action::ShowChecklist,
 
             // Repo view tab shortcuts
             KeyCode::Char('p') if self.screen == Screen::RepoView => {
@@ -372,49 +1683,301 @@ impl App {
             KeyCode::Char('a') if self.screen == Screen::RepoView => {
                 Action::SwitchRepoTab(RepoTab::Actions)
             }
+            KeyCode::Char('v') if self.screen == Screen::RepoView => Action::TogglePreview,
+            KeyCode::Char('v') if self.screen == Screen::DiffView => Action::ToggleSplitDiff,
+            KeyCode::Char('#') if self.screen == Screen::RepoView => Action::StartJump,
 
-            // Forge switching
-            KeyCode::Char('f') if self.screen == Screen::Home => Action::ShowForgeSelect,
-
-            _ => Action::None,
-        }
-    }
-
-    pub fn update(&mut self, action: Action) {
-        if self.error.is_some() && !matches!(action, Action::Quit | Action::Back) {
-            self.error = None;
-        }
-
-        match action {
-            Action::Quit => {
-                self.should_quit = true;
+            // Milestones
+            KeyCode::Char('M')
+                if self.screen == Screen::RepoView && self.repo_tab == RepoTab::Issues =>
+            {
+                Action::ShowMilestoneSelect(MilestonePurpose::Assign)
             }
-            Action::Back => match self.screen {
-                Screen::Home => {
+            KeyCode::Char('F')
+                if self.screen == Screen::RepoView
+                    && matches!(self.repo_tab, RepoTab::PullRequests | RepoTab::Issues) =>
+            {
+                Action::ShowMilestoneSelect(MilestonePurpose::Filter)
+            }
+            KeyCode::Char('P')
+                if self.screen == Screen::RepoView && self.repo_tab == RepoTab::PullRequests =>
+            {
+                Action::ShowPrFilterSelect
+            }
+
+            // Issue form
+            KeyCode::Char('N')
+                if self.screen == Screen::RepoView && self.repo_tab == RepoTab::Issues =>
+            {
+                Action::ShowIssueTemplateSelect
+            }
+            KeyCode::Char('e')
+                if self.screen == Screen::RepoView && self.repo_tab == RepoTab::Issues =>
+            {
+                match self.issues.get(self.issue_index) {
+                    Some(issue) => Action::ShowForm(FormTarget::Issue(issue.number)),
+                    None => Action::None,
+                }
+            }
+
+            // Labels
+            KeyCode::Char('L')
+                if self.screen == Screen::PrDetail
+                    || !self.marked_numbers().is_empty()
+                    || (self.screen == Screen::RepoView && self.repo_tab == RepoTab::Issues) =>
+            {
+                Action::ShowLabelSelect
+            }
+
+            // Forge switching
+            KeyCode::Char('f') if self.screen == Screen::Home => Action::ShowForgeSelect,
+            KeyCode::Char('O') if matches!(self.screen, Screen::RepoList | Screen::Browse) => {
+                Action::ShowOrgSelect
+            }
+
+            // Commit search across repos
+            KeyCode::Char('S')
+                if matches!(
+                    self.screen,
+                    Screen::Home | Screen::RepoList | Screen::Browse
+                ) =>
+            {
+                Action::StartFindCommit
+            }
+
+            // Column browser
+            KeyCode::Char('B')
+                if matches!(
+                    self.screen,
+                    Screen::Home | Screen::RepoList | Screen::Browse
+                ) =>
+            {
+                Action::ToggleBrowse
+            }
+
+            _ => Action::None,
+        }
+    }
+
+    pub fn update(&mut self, action: Action) {
+        if self.error.is_some() && !matches!(action, Action::Quit | Action::Back | Action::Tick) {
+            self.error = None;
+        }
+
+        match action {
+            Action::Start if self.start_repo.is_some() => {
+                if let Some((owner, name)) = self.start_repo.take() {
+                    self.open_repo(owner.clone(), name.clone());
+                    if let Some(number) = self.start_pr.take() {
+                        self.load_id += 1;
+                        self.loading = true;
+                        self.spawn_load_pr_detail(owner, name, number, self.load_id);
+                    }
+                }
+            }
+            Action::Start => {
+                self.recent = cache::read(&self.recent_key()).unwrap_or_default();
+                self.outbox = outbox::load(&self.forge_name);
+                match self.ui_config.start_screen {
+                    StartScreen::Home | StartScreen::Notifications => self.update(Action::LoadHome),
+                    StartScreen::Repos => match self.default_repo() {
+                        Some((owner, name)) => self.open_repo(owner, name),
+                        None => self.show_repo_list(),
+                    },
+                    StartScreen::Last => {
+                        match cache::read::<LastLocation>(&self.last_location_key()) {
+                            Some(LastLocation::Repo { owner, name }) => self.open_repo(owner, name),
+                            Some(LastLocation::Repos) => self.show_repo_list(),
+                            _ => self.update(Action::LoadHome),
+                        }
+                    }
+                }
+                if self.ui_config.restore_session {
+                    self.offer_session();
+                }
+            }
+            Action::NewWorkspace => {
+                self.workspaces.push(Workspace::default());
+                self.switch_workspace(self.workspaces.len() - 1);
+                if self.review_requests.is_empty() && self.my_prs.is_empty() {
+                    self.update(Action::LoadHome);
+                }
+            }
+            Action::NextWorkspace => {
+                self.switch_workspace((self.workspace_index + 1) % self.workspaces.len());
+            }
+            Action::PrevWorkspace => {
+                let len = self.workspaces.len();
+                self.switch_workspace((self.workspace_index + len - 1) % len);
+            }
+            Action::CloseWorkspace => {
+                let closing = self.workspace_index;
+                if self.workspaces.len() < 2 {
+                    self.flash_message = Some((
+                        "Only one workspace open".to_string(),
+                        std::time::Instant::now(),
+                    ));
+                    return;
+                }
+                let next = if closing + 1 < self.workspaces.len() {
+                    closing + 1
+                } else {
+                    closing - 1
+                };
+                self.switch_workspace(next);
+                self.workspaces.remove(closing);
+                if closing < next {
+                    self.workspace_index -= 1;
+                }
+            }
+            Action::Forward => {
+                let Some(next) = self.nav_forward.last().cloned() else {
+                    self.flash_message = Some((
+                        "Nothing to go forward to".to_string(),
+                        std::time::Instant::now(),
+                    ));
+                    return;
+                };
+                let Some((owner, repo)) = next.repo.clone() else {
+                    self.nav_forward.pop();
+                    return;
+                };
+                // From one PR straight to another: keep this one for Back
+                if self.screen == Screen::PrDetail && next.screen == Screen::PrDetail {
+                    self.leave_pr_for(next.clone());
+                }
+                self.current_repo = Some((owner.clone(), repo.clone()));
+                self.load_id += 1;
+                self.loading = true;
+                match (next.pr, next.commit) {
+                    (_, Some(sha)) if next.screen == Screen::CommitDetail => {
+                        self.spawn_load_commit_detail(owner, repo, sha, self.load_id);
+                    }
+                    (Some(number), _) => {
+                        self.spawn_load_pr_detail(owner, repo, number, self.load_id);
+                    }
+                    _ => {
+                        self.nav_forward.pop();
+                        self.loading = false;
+                    }
+                }
+            }
+            Action::JumpBack => {
+                if self.back_stack.len() < 2 {
+                    self.flash_message = Some((
+                        "Nothing to go back to".to_string(),
+                        std::time::Instant::now(),
+                    ));
+                    return;
+                }
+                self.back_stack.pop();
+                if let Some(item) = self.back_stack.last().cloned() {
+                    self.open_recent(item);
+                }
+            }
+            Action::Quit => {
+                let items = self.pending_work();
+                if items.is_empty() {
                     self.should_quit = true;
+                    return;
                 }
-                Screen::RepoList => {
+                self.popup_title = "Quit grit?".to_string();
+                self.popup_items = vec!["Quit anyway".to_string(), "Cancel".to_string()];
+                self.popup_items
+                    .extend(items.iter().map(|item| format!("Jump to {}", item)));
+                self.popup_index = 1;
+                self.input_mode = InputMode::SelectPopup;
+            }
+            Action::Repeat(count, action) => {
+                for _ in 0..count {
+                    self.update((*action).clone());
+                }
+            }
+            Action::CenterSelection => self.center_selection.set(true),
+            Action::ToggleHomeSection => {
+                if !self.collapsed.remove(&self.home_section) {
+                    self.collapsed.insert(self.home_section);
+                }
+            }
+            Action::Tick => {
+                self.abort_stale_loads();
+                self.probe_if_offline();
+                self.retry_if_due();
+                self.poll_action_runs_if_needed();
+                self.browse_refresh_if_settled();
+                self.preview_follow_selection();
+                self.prefetch_pr_neighbors();
+                self.rewrap_search_matches();
+                if self.screen == Screen::Logs {
+                    self.read_logs();
+                }
+            }
+            Action::Back => match self.screen {
+                Screen::Home => self.update(Action::Quit),
+                Screen::RepoList | Screen::Browse => {
                     self.screen = Screen::Home;
+                    // Started somewhere else, so the dashboard was never loaded
+                    if self.review_requests.is_empty() && self.my_prs.is_empty() {
+                        self.loading = true;
+                        self.load_id += 1;
+                        self.spawn_load_home(self.load_id);
+                    }
                 }
                 Screen::RepoView => {
-                    self.screen = Screen::RepoList;
+                    if self.repos.is_empty() {
+                        self.loading = true;
+                        self.load_id += 1;
+                        self.spawn_load_repos(self.load_id);
+                    }
+                    self.screen = self.repos_screen;
                     self.repo_tab = RepoTab::default();
                     self.prs.clear();
                     self.issues.clear();
                     self.commits.clear();
                     self.action_runs.clear();
+                    self.milestones.clear();
+                    self.labels.clear();
+                    self.milestone_filter = None;
+                    self.pr_filter = PrFilter::default();
                 }
                 Screen::PrDetail => {
-                    self.screen = self.prev_screen.unwrap_or(Screen::Home);
-                    self.current_pr = None;
-                    self.scroll_offset = 0;
-                    self.prev_screen = None;
+                    let left = self.nav_entry();
+                    self.nav_forward.push(left);
+                    let back_to = self.nav_stack.last().and_then(|entry| entry.pr);
+                    self.pop_nav(Screen::Home);
+                    self.close_pr();
+                    // Deep-linked PRs skip the repo's lists; load them now
+                    if self.screen == Screen::RepoView && self.prs.is_empty() {
+                        self.update(Action::Refresh);
+                    }
+                    // Back to the PR a reference was followed from
+                    if let (Screen::PrDetail, Some(number), Some((owner, repo))) =
+                        (self.screen, back_to, self.current_repo.clone())
+                    {
+                        self.load_id += 1;
+                        self.loading = true;
+                        self.spawn_load_pr_detail(owner, repo, number, self.load_id);
+                    }
+                }
+                Screen::DiffView => {
+                    self.screen = Screen::PrDetail;
+                    self.diff_cursor = 0;
+                    self.step_file(0);
+                }
+                Screen::Logs => {
+                    self.pop_nav(Screen::Home);
+                    self.log_lines.clear();
+                    self.log_size = 0;
                 }
                 Screen::CommitDetail => {
-                    self.screen = self.prev_screen.unwrap_or(Screen::RepoView);
+                    let left = self.nav_entry();
+                    self.nav_forward.push(left);
+                    self.pop_nav(Screen::RepoView);
                     self.current_commit = None;
-                    self.scroll_offset = 0;
-                    self.prev_screen = None;
+                    if self.screen == Screen::PrDetail {
+                        // Back on the PR, positioned at the commit just viewed
+                        self.step_pr_commit(0);
+                    }
                 }
             },
             Action::ScrollUp => match self.screen {
@@ -429,12 +1992,33 @@ impl App {
                             self.my_pr_index -= 1;
                         }
                     }
+                    HomeSection::Assigned => {
+                        if self.assigned_index > 0 {
+                            self.assigned_index -= 1;
+                        }
+                    }
+                    HomeSection::Mentions => {
+                        if self.mentions_index > 0 {
+                            self.mentions_index -= 1;
+                        }
+                    }
+                    HomeSection::Saved => {
+                        if self.saved_index > 0 {
+                            self.saved_index -= 1;
+                        }
+                    }
+                    HomeSection::Recent => {
+                        if self.recent_index > 0 {
+                            self.recent_index -= 1;
+                        }
+                    }
                 },
                 Screen::RepoList => {
                     if self.repo_index > 0 {
                         self.repo_index -= 1;
                     }
                 }
+                Screen::Browse => self.browse_select(|i, _| i.saturating_sub(1)),
                 Screen::RepoView => match self.repo_tab {
                     RepoTab::PullRequests => {
                         if self.pr_index > 0 {
@@ -451,17 +2035,21 @@ impl App {
                             self.commit_index -= 1;
                         }
                     }
+                    RepoTab::Actions if self.workflow_summary => {
+                        self.workflow_index = self.workflow_index.saturating_sub(1);
+                    }
                     RepoTab::Actions => {
                         if self.action_index > 0 {
                             self.action_index -= 1;
                         }
                     }
                 },
-                Screen::PrDetail | Screen::CommitDetail => {
+                Screen::PrDetail | Screen::CommitDetail | Screen::Logs => {
                     if self.scroll_offset > 0 {
                         self.scroll_offset -= 1;
                     }
                 }
+                Screen::DiffView => self.diff_step(-1),
             },
             Action::ScrollDown => {
                 match self.screen {
@@ -478,12 +2066,38 @@ impl App {
                                 self.my_pr_index += 1;
                             }
                         }
+                        HomeSection::Assigned => {
+                            if !self.assigned.is_empty()
+                                && self.assigned_index < self.assigned.len() - 1
+                            {
+                                self.assigned_index += 1;
+                            }
+                        }
+                        HomeSection::Mentions => {
+                            if !self.mentions.is_empty()
+                                && self.mentions_index < self.mentions.len() - 1
+                            {
+                                self.mentions_index += 1;
+                            }
+                        }
+                        HomeSection::Saved => {
+                            if !self.saved.is_empty() && self.saved_index < self.saved.len() - 1 {
+                                self.saved_index += 1;
+                            }
+                        }
+                        HomeSection::Recent => {
+                            if !self.recent.is_empty() && self.recent_index < self.recent.len() - 1
+                            {
+                                self.recent_index += 1;
+                            }
+                        }
                     },
                     Screen::RepoList => {
                         if !self.repos.is_empty() && self.repo_index < self.repos.len() - 1 {
                             self.repo_index += 1;
                         }
                     }
+                    Screen::Browse => self.browse_select(|i, _| i + 1),
                     Screen::RepoView => match self.repo_tab {
                         RepoTab::PullRequests => {
                             if !self.prs.is_empty() && self.pr_index < self.prs.len() - 1 {
@@ -502,6 +2116,10 @@ impl App {
                                 self.commit_index += 1;
                             }
                         }
+                        RepoTab::Actions if self.workflow_summary => {
+                            let max = self.workflows.len().saturating_sub(1);
+                            self.workflow_index = (self.workflow_index + 1).min(max);
+                        }
                         RepoTab::Actions => {
                             if !self.action_runs.is_empty()
                                 && self.action_index < self.action_runs.len() - 1
@@ -510,12 +2128,13 @@ impl App {
                             }
                         }
                     },
-                    Screen::PrDetail | Screen::CommitDetail => {
+                    Screen::PrDetail | Screen::CommitDetail | Screen::Logs => {
                         let max = self.max_scroll_offset();
                         if self.scroll_offset < max {
                             self.scroll_offset += 1;
                         }
                     }
+                    Screen::DiffView => self.diff_step(1),
                 }
                 self.check_pagination();
             }
@@ -525,15 +2144,22 @@ impl App {
                 Screen::Home => match self.home_section {
                     HomeSection::ReviewRequests => self.review_index = 0,
                     HomeSection::MyPrs => self.my_pr_index = 0,
+                    HomeSection::Assigned => self.assigned_index = 0,
+                    HomeSection::Mentions => self.mentions_index = 0,
+                    HomeSection::Saved => self.saved_index = 0,
+                    HomeSection::Recent => self.recent_index = 0,
                 },
                 Screen::RepoList => self.repo_index = 0,
+                Screen::Browse => self.browse_select(|_, _| 0),
                 Screen::RepoView => match self.repo_tab {
                     RepoTab::PullRequests => self.pr_index = 0,
                     RepoTab::Issues => self.issue_index = 0,
                     RepoTab::Commits => self.commit_index = 0,
+                    RepoTab::Actions if self.workflow_summary => self.workflow_index = 0,
                     RepoTab::Actions => self.action_index = 0,
                 },
-                Screen::PrDetail | Screen::CommitDetail => self.scroll_offset = 0,
+                Screen::PrDetail | Screen::CommitDetail | Screen::Logs => self.scroll_offset = 0,
+                Screen::DiffView => self.diff_cursor = 0,
             },
 
             // Vim: go to bottom (G, End)
@@ -550,12 +2176,33 @@ impl App {
                                 self.my_pr_index = self.my_prs.len() - 1;
                             }
                         }
+                        HomeSection::Assigned => {
+                            if !self.assigned.is_empty() {
+                                self.assigned_index = self.assigned.len() - 1;
+                            }
+                        }
+                        HomeSection::Mentions => {
+                            if !self.mentions.is_empty() {
+                                self.mentions_index = self.mentions.len() - 1;
+                            }
+                        }
+                        HomeSection::Saved => {
+                            if !self.saved.is_empty() {
+                                self.saved_index = self.saved.len() - 1;
+                            }
+                        }
+                        HomeSection::Recent => {
+                            if !self.recent.is_empty() {
+                                self.recent_index = self.recent.len() - 1;
+                            }
+                        }
                     },
                     Screen::RepoList => {
                         if !self.repos.is_empty() {
                             self.repo_index = self.repos.len() - 1;
                         }
                     }
+                    Screen::Browse => self.browse_select(|_, len| len.saturating_sub(1)),
                     Screen::RepoView => match self.repo_tab {
                         RepoTab::PullRequests => {
                             if !self.prs.is_empty() {
@@ -572,22 +2219,26 @@ impl App {
                                 self.commit_index = self.commits.len() - 1;
                             }
                         }
+                        RepoTab::Actions if self.workflow_summary => {
+                            self.workflow_index = self.workflows.len().saturating_sub(1);
+                        }
                         RepoTab::Actions => {
                             if !self.action_runs.is_empty() {
                                 self.action_index = self.action_runs.len() - 1;
                             }
                         }
                     },
-                    Screen::PrDetail | Screen::CommitDetail => {
+                    Screen::PrDetail | Screen::CommitDetail | Screen::Logs => {
                         self.scroll_offset = self.max_scroll_offset();
                     }
+                    Screen::DiffView => self.diff_cursor = self.diff_len().saturating_sub(1),
                 }
                 self.check_pagination();
             }
 
             // Vim: page up (Ctrl+u, Ctrl+b, PageUp)
             Action::PageUp => {
-                let page_size = 10;
+                let page_size = self.page_size();
                 match self.screen {
                     Screen::Home => match self.home_section {
                         HomeSection::ReviewRequests => {
@@ -596,10 +2247,23 @@ impl App {
                         HomeSection::MyPrs => {
                             self.my_pr_index = self.my_pr_index.saturating_sub(page_size);
                         }
+                        HomeSection::Assigned => {
+                            self.assigned_index = self.assigned_index.saturating_sub(page_size);
+                        }
+                        HomeSection::Mentions => {
+                            self.mentions_index = self.mentions_index.saturating_sub(page_size);
+                        }
+                        HomeSection::Saved => {
+                            self.saved_index = self.saved_index.saturating_sub(page_size);
+                        }
+                        HomeSection::Recent => {
+                            self.recent_index = self.recent_index.saturating_sub(page_size);
+                        }
                     },
                     Screen::RepoList => {
                         self.repo_index = self.repo_index.saturating_sub(page_size);
                     }
+                    Screen::Browse => self.browse_select(|i, _| i.saturating_sub(page_size)),
                     Screen::RepoView => match self.repo_tab {
                         RepoTab::PullRequests => {
                             self.pr_index = self.pr_index.saturating_sub(page_size);
@@ -610,19 +2274,23 @@ impl App {
                         RepoTab::Commits => {
                             self.commit_index = self.commit_index.saturating_sub(page_size);
                         }
+                        RepoTab::Actions if self.workflow_summary => {
+                            self.workflow_index = self.workflow_index.saturating_sub(page_size);
+                        }
                         RepoTab::Actions => {
                             self.action_index = self.action_index.saturating_sub(page_size);
                         }
                     },
-                    Screen::PrDetail | Screen::CommitDetail => {
+                    Screen::PrDetail | Screen::CommitDetail | Screen::Logs => {
                         self.scroll_offset = self.scroll_offset.saturating_sub(page_size);
                     }
+                    Screen::DiffView => self.diff_step(-(page_size as isize)),
                 }
             }
 
             // Vim: page down (Ctrl+d, Ctrl+f, PageDown)
             Action::PageDown => {
-                let page_size = 10;
+                let page_size = self.page_size();
                 match self.screen {
                     Screen::Home => match self.home_section {
                         HomeSection::ReviewRequests => {
@@ -633,11 +2301,28 @@ impl App {
                             let max = self.my_prs.len().saturating_sub(1);
                             self.my_pr_index = (self.my_pr_index + page_size).min(max);
                         }
+                        HomeSection::Assigned => {
+                            let max = self.assigned.len().saturating_sub(1);
+                            self.assigned_index = (self.assigned_index + page_size).min(max);
+                        }
+                        HomeSection::Mentions => {
+                            let max = self.mentions.len().saturating_sub(1);
+                            self.mentions_index = (self.mentions_index + page_size).min(max);
+                        }
+                        HomeSection::Saved => {
+                            let max = self.saved.len().saturating_sub(1);
+                            self.saved_index = (self.saved_index + page_size).min(max);
+                        }
+                        HomeSection::Recent => {
+                            let max = self.recent.len().saturating_sub(1);
+                            self.recent_index = (self.recent_index + page_size).min(max);
+                        }
                     },
                     Screen::RepoList => {
                         let max = self.repos.len().saturating_sub(1);
                         self.repo_index = (self.repo_index + page_size).min(max);
                     }
+                    Screen::Browse => self.browse_select(|i, _| i + page_size),
                     Screen::RepoView => match self.repo_tab {
                         RepoTab::PullRequests => {
                             let max = self.prs.len().saturating_sub(1);
@@ -651,15 +2336,20 @@ impl App {
                             let max = self.commits.len().saturating_sub(1);
                             self.commit_index = (self.commit_index + page_size).min(max);
                         }
+                        RepoTab::Actions if self.workflow_summary => {
+                            let max = self.workflows.len().saturating_sub(1);
+                            self.workflow_index = (self.workflow_index + page_size).min(max);
+                        }
                         RepoTab::Actions => {
                             let max = self.action_runs.len().saturating_sub(1);
                             self.action_index = (self.action_index + page_size).min(max);
                         }
                     },
-                    Screen::PrDetail | Screen::CommitDetail => {
+                    Screen::PrDetail | Screen::CommitDetail | Screen::Logs => {
                         let max = self.max_scroll_offset();
                         self.scroll_offset = (self.scroll_offset + page_size).min(max);
                     }
+                    Screen::DiffView => self.diff_step(page_size as isize),
                 }
                 self.check_pagination();
             }
@@ -667,9 +2357,17 @@ impl App {
             // Tab navigation (h/l, Tab/Shift+Tab, Left/Right)
             Action::NextTab => match self.screen {
                 Screen::Home => {
-                    self.home_section = match self.home_section {
-                        HomeSection::ReviewRequests => HomeSection::MyPrs,
-                        HomeSection::MyPrs => HomeSection::ReviewRequests,
+                    let order = self.home_order();
+                    let next = order
+                        .iter()
+                        .position(|&s| s == self.home_section)
+                        .map_or(0, |i| (i + 1) % order.len());
+                    self.home_section = order[next];
+                }
+                Screen::Browse => {
+                    self.browse.column = match self.browse.column {
+                        BrowseColumn::Owners => BrowseColumn::Repos,
+                        _ => BrowseColumn::PullRequests,
                     };
                 }
                 Screen::RepoView => {
@@ -711,9 +2409,17 @@ impl App {
             },
             Action::PrevTab => match self.screen {
                 Screen::Home => {
-                    self.home_section = match self.home_section {
-                        HomeSection::ReviewRequests => HomeSection::MyPrs,
-                        HomeSection::MyPrs => HomeSection::ReviewRequests,
+                    let order = self.home_order();
+                    let prev = order
+                        .iter()
+                        .position(|&s| s == self.home_section)
+                        .map_or(0, |i| (i + order.len() - 1) % order.len());
+                    self.home_section = order[prev];
+                }
+                Screen::Browse => {
+                    self.browse.column = match self.browse.column {
+                        BrowseColumn::PullRequests => BrowseColumn::Repos,
+                        _ => BrowseColumn::Owners,
                     };
                 }
                 Screen::RepoView => {
@@ -754,6 +2460,118 @@ impl App {
                 _ => {}
             },
 
+            Action::ClickRow(list, index) => {
+                let (current, len) = match list {
+                    ClickList::ReviewRequests => (
+                        (self.home_section == HomeSection::ReviewRequests)
+                            .then_some(self.review_index),
+                        self.review_requests.len(),
+                    ),
+                    ClickList::MyPrs => (
+                        (self.home_section == HomeSection::MyPrs).then_some(self.my_pr_index),
+                        self.my_prs.len(),
+                    ),
+                    ClickList::Assigned => (
+                        (self.home_section == HomeSection::Assigned).then_some(self.assigned_index),
+                        self.assigned.len(),
+                    ),
+                    ClickList::Mentions => (
+                        (self.home_section == HomeSection::Mentions).then_some(self.mentions_index),
+                        self.mentions.len(),
+                    ),
+                    ClickList::Saved => (
+                        (self.home_section == HomeSection::Saved).then_some(self.saved_index),
+                        self.saved.len(),
+                    ),
+                    ClickList::Recent => (
+                        (self.home_section == HomeSection::Recent).then_some(self.recent_index),
+                        self.recent.len(),
+                    ),
+                    ClickList::Repos => (Some(self.repo_index), self.repos.len()),
+                    ClickList::Prs => (Some(self.pr_index), self.prs.len()),
+                    ClickList::Issues => (Some(self.issue_index), self.issues.len()),
+                    ClickList::Commits => (Some(self.commit_index), self.commits.len()),
+                    ClickList::Actions => (Some(self.action_index), self.action_runs.len()),
+                    ClickList::Workflows => (Some(self.workflow_index), self.workflows.len()),
+                    ClickList::Browse(column) => {
+                        let (index, len) = match column {
+                            BrowseColumn::Owners => {
+                                (self.browse.owner_index, self.browse_owners().len())
+                            }
+                            BrowseColumn::Repos => {
+                                (self.browse.repo_index, self.browse_repos().len())
+                            }
+                            BrowseColumn::PullRequests => {
+                                (self.browse.pr_index, self.browse.prs.len())
+                            }
+                        };
+                        ((self.browse.column == column).then_some(index), len)
+                    }
+                    ClickList::Diff => match self.diff_split_rows() {
+                        Some(layout) => (
+                            layout
+                                .rows
+                                .iter()
+                                .position(|r| r.contains(self.diff_cursor)),
+                            layout.rows.len(),
+                        ),
+                        None => (Some(self.diff_cursor), self.diff_len()),
+                    },
+                };
+                if index >= len {
+                    return;
+                }
+                if current == Some(index) {
+                    if list != ClickList::Diff {
+                        self.update(Action::Select);
+                    }
+                    return;
+                }
+                match list {
+                    ClickList::ReviewRequests => {
+                        self.home_section = HomeSection::ReviewRequests;
+                        self.review_index = index;
+                    }
+                    ClickList::MyPrs => {
+                        self.home_section = HomeSection::MyPrs;
+                        self.my_pr_index = index;
+                    }
+                    ClickList::Assigned => {
+                        self.home_section = HomeSection::Assigned;
+                        self.assigned_index = index;
+                    }
+                    ClickList::Mentions => {
+                        self.home_section = HomeSection::Mentions;
+                        self.mentions_index = index;
+                    }
+                    ClickList::Saved => {
+                        self.home_section = HomeSection::Saved;
+                        self.saved_index = index;
+                    }
+                    ClickList::Recent => {
+                        self.home_section = HomeSection::Recent;
+                        self.recent_index = index;
+                    }
+                    ClickList::Repos => self.repo_index = index,
+                    ClickList::Prs => self.pr_index = index,
+                    ClickList::Issues => self.issue_index = index,
+                    ClickList::Commits => self.commit_index = index,
+                    ClickList::Actions => self.action_index = index,
+                    ClickList::Workflows => self.workflow_index = index,
+                    ClickList::Browse(column) => {
+                        self.browse.column = column;
+                        self.browse_select(|_, _| index);
+                    }
+                    ClickList::Diff => {
+                        self.diff_cursor = match self.diff_split_rows() {
+                            Some(layout) => layout.rows.get(index).map_or(0, SplitRow::line),
+                            None => index,
+                        }
+                    }
+                }
+                self.check_pagination();
+            }
+
             Action::Select => match self.screen {
                 Screen::Home => {
                     // Select a review request or my PR -> load PR detail
@@ -778,24 +2596,57 @@ impl App {
                                 self.spawn_load_pr_detail(owner, repo, number, self.load_id);
                             }
                         }
+                        HomeSection::Assigned => {
+                            if let Some(item) = self.assigned.get(self.assigned_index).cloned() {
+                                self.open_issue_ref(item);
+                            }
+                        }
+                        HomeSection::Mentions => {
+                            if let Some(item) = self.mentions.get(self.mentions_index).cloned() {
+                                self.open_issue_ref(item);
+                            }
+                        }
+                        HomeSection::Saved => {
+                            if let Some(item) = self.saved.get(self.saved_index).cloned() {
+                                self.open_issue_ref(item);
+                            }
+                        }
+                        HomeSection::Recent => {
+                            if let Some(item) = self.recent.get(self.recent_index).cloned() {
+                                self.open_recent(item);
+                            }
+                        }
                     }
                 }
                 Screen::RepoList => {
                     if let Some(repo) = self.repos.get(self.repo_index) {
                         let owner = repo.owner.clone();
                         let name = repo.name.clone();
-                        self.current_repo = Some((owner.clone(), name.clone()));
-                        self.screen = Screen::RepoView;
-                        self.repo_tab = RepoTab::PullRequests;
-                        self.pr_index = 0;
-                        self.issue_index = 0;
-                        self.commit_index = 0;
-                        self.action_index = 0;
-                        self.load_id += 1;
-                        // Load PRs for this repo
-                        self.spawn_load_prs(owner, name, self.load_id);
+                        self.open_repo(owner, name);
                     }
                 }
+                Screen::Browse => match self.browse.column {
+                    BrowseColumn::Owners => self.browse.column = BrowseColumn::Repos,
+                    BrowseColumn::Repos => {
+                        if let Some((owner, name)) = self.browse_selected_repo() {
+                            self.open_repo(owner, name);
+                        }
+                    }
+                    BrowseColumn::PullRequests => {
+                        let number = self
+                            .browse
+                            .prs
+                            .get(self.browse.pr_index)
+                            .map(|pr| pr.number);
+                        if let (Some(number), Some((owner, repo))) =
+                            (number, self.browse.prs_repo.clone())
+                        {
+                            self.current_repo = Some((owner.clone(), repo.clone()));
+                            self.load_id += 1;
+                            self.spawn_load_pr_detail(owner, repo, number, self.load_id);
+                        }
+                    }
+                },
                 Screen::RepoView => {
                     // In RepoView, Enter drills into the selected item
                     match self.repo_tab {
@@ -824,12 +2675,51 @@ impl App {
                                 }
                             }
                         }
+                        // A workflow opens the list of runs at its latest one
+                        RepoTab::Actions if self.workflow_summary => {
+                            if let Some(workflow) = self.workflows.get(self.workflow_index) {
+                                let latest = workflow.latest_id;
+                                self.workflow_summary = false;
+                                self.action_index = self
+                                    .action_runs
+                                    .iter()
+                                    .position(|r| r.id == latest)
+                                    .unwrap_or(0);
+                            }
+                        }
                         RepoTab::Actions => {
                             // TODO: Action run detail view
                         }
                     }
                 }
-                Screen::PrDetail | Screen::CommitDetail => {}
+                Screen::PrDetail if self.xref_index.is_some() => self.open_xref(),
+                Screen::PrDetail => match self.pr_focus {
+                    PrFocus::Files => {
+                        if let Some(file) = self.pr_files.get(self.file_index) {
+                            if file.patch.is_some() {
+                                self.diff_cursor = 0;
+                                self.screen = Screen::DiffView;
+                            } else {
+                                self.flash_message = Some((
+                                    format!("No diff to show for {}", file.filename),
+                                    std::time::Instant::now(),
+                                ));
+                            }
+                        }
+                    }
+                    PrFocus::Commits => {
+                        if let (Some((owner, repo)), Some(commit)) = (
+                            &self.current_repo,
+                            self.pr_commits.get(self.pr_commit_index),
+                        ) {
+                            let (owner, repo, sha) =
+                                (owner.clone(), repo.clone(), commit.sha.clone());
+                            self.load_id += 1;
+                            self.spawn_load_commit_detail(owner, repo, sha, self.load_id);
+                        }
+                    }
+                },
+                Screen::CommitDetail | Screen::DiffView | Screen::Logs => {}
             },
 
             // Home screen actions
@@ -837,20 +2727,108 @@ impl App {
                 self.loading = true;
                 self.load_id += 1;
                 self.spawn_load_home(self.load_id);
+                self.spawn_load_saved(1);
             }
             Action::HomeLoaded {
                 review_requests,
                 my_prs,
+                assigned,
+                mentions,
                 load_id,
             } => {
                 if load_id == self.load_id {
                     self.loading = false;
+                    let prev_review = self.review_requests.get(self.review_index).map(review_key);
+                    let prev_my_pr = self.my_prs.get(self.my_pr_index).map(my_pr_key);
+                    let prev_assigned = self.assigned.get(self.assigned_index).map(issue_ref_key);
+                    let prev_mention = self.mentions.get(self.mentions_index).map(issue_ref_key);
+                    if self.ui_config.notifications && self.home_seen {
+                        let alerts = notify::home_alerts(
+                            &self.review_requests,
+                            &review_requests,
+                            &self.my_prs,
+                            &my_prs,
+                        );
+                        if !alerts.is_empty() {
+                            notify::desktop(&alerts);
+                            self.alerts.extend(alerts);
+                        }
+                    }
+                    self.home_seen = true;
                     self.review_requests = review_requests;
+                    self.sort_review_requests();
                     self.my_prs = my_prs;
-                    self.review_index = self
-                        .review_index
-                        .min(self.review_requests.len().saturating_sub(1));
-                    self.my_pr_index = self.my_pr_index.min(self.my_prs.len().saturating_sub(1));
+                    self.assigned = assigned;
+                    self.mentions = mentions;
+                    self.review_index = reselect(
+                        &self.review_requests,
+                        self.review_index,
+                        prev_review,
+                        review_key,
+                    );
+                    self.my_pr_index =
+                        reselect(&self.my_prs, self.my_pr_index, prev_my_pr, my_pr_key);
+                    self.assigned_index = reselect(
+                        &self.assigned,
+                        self.assigned_index,
+                        prev_assigned,
+                        issue_ref_key,
+                    );
+                    self.mentions_index = reselect(
+                        &self.mentions,
+                        self.mentions_index,
+                        prev_mention,
+                        issue_ref_key,
+                    );
+                }
+            }
+            Action::CycleReviewSort => {
+                let prev = self.review_requests.get(self.review_index).map(review_key);
+                self.review_sort = self.review_sort.next();
+                self.sort_review_requests();
+                self.review_index =
+                    reselect(&self.review_requests, self.review_index, prev, review_key);
+                if self.home_order().contains(&HomeSection::ReviewRequests) {
+                    self.home_section = HomeSection::ReviewRequests;
+                }
+            }
+            Action::ShowSavedSearchSelect => {
+                if self.searches.is_empty() {
+                    self.flash_message = Some((
+                        "No saved searches; add them under [searches] in the config".to_string(),
+                        std::time::Instant::now(),
+                    ));
+                    return;
+                }
+                self.popup_title = "Saved Search".to_string();
+                self.popup_items = self
+                    .searches
+                    .iter()
+                    .map(|(name, query)| format!("{} - {}", name, query))
+                    .collect();
+                self.popup_index = self.search_choice;
+                self.input_mode = InputMode::SelectPopup;
+            }
+            Action::SavedSearchLoaded(name, page, items, page_info) => {
+                if self.searches.get(self.search_choice).map(|(n, _)| n) != Some(&name) {
+                    return;
+                }
+                let page_size = self.forge.page_size();
+                if page == 1 {
+                    let prev = self.saved.get(self.saved_index).map(issue_ref_key);
+                    self.saved_pagination =
+                        PaginationState::first(page_info, items.len(), page_size);
+                    self.saved = items;
+                    self.saved_index = reselect(&self.saved, self.saved_index, prev, issue_ref_key);
+                } else {
+                    self.saved_pagination
+                        .appended(page_info, items.len(), page_size);
+                    let known: HashSet<_> = self.saved.iter().map(issue_ref_key).collect();
+                    self.saved.extend(
+                        items
+                            .into_iter()
+                            .filter(|i| !known.contains(&issue_ref_key(i))),
+                    );
                 }
             }
             // Navigation actions
@@ -885,155 +2863,711 @@ impl App {
             }
 
             // Repo list
-            Action::ReposLoaded(repos, total, load_id) => {
+            Action::ReposLoaded(repos, page_info, load_id) => {
                 if load_id == self.load_id {
                     self.loading = false;
-                    self.repos_pagination = PaginationState {
-                        page: 1,
-                        has_more: repos.len() == PAGE_SIZE,
-                        loading_more: false,
-                        total_count: total,
-                    };
+                    self.repos_pagination =
+                        PaginationState::first(page_info, repos.len(), self.forge.page_size());
+                    let prev = self.repos.get(self.repo_index).map(repo_key);
+                    let browsed = self.browse_selected_repo();
                     self.repos = repos;
-                    self.repo_index = self.repo_index.min(self.repos.len().saturating_sub(1));
+                    self.repo_index = reselect(&self.repos, self.repo_index, prev, repo_key);
+                    match browsed {
+                        Some((owner, name)) => self.browse_focus(&owner, &name),
+                        None => {
+                            self.browse.owner_index = 0;
+                            self.browse.repo_index = 0;
+                        }
+                    }
+                    if self.screen == Screen::Browse {
+                        self.browse_preview();
+                    }
+                }
+            }
+            Action::ToggleBrowse => {
+                if self.screen == Screen::Browse {
+                    // Land the list on the repo highlighted in the columns
+                    if let Some((owner, name)) = self.browse_selected_repo() {
+                        if let Some(i) = self
+                            .repos
+                            .iter()
+                            .position(|r| r.owner == owner && r.name == name)
+                        {
+                            self.repo_index = i;
+                        }
+                    }
+                    self.screen = Screen::RepoList;
+                } else {
+                    if self.screen == Screen::RepoList {
+                        if let Some((owner, name)) = self.repos.get(self.repo_index).map(repo_key) {
+                            self.browse_focus(&owner, &name);
+                        }
+                    }
+                    self.screen = Screen::Browse;
+                    if self.repos.is_empty() {
+                        self.loading = true;
+                        self.load_id += 1;
+                        self.spawn_load_repos(self.load_id);
+                    }
+                    self.browse_preview();
+                }
+            }
+            Action::ToggleSplitDiff => {
+                self.diff_split = !self.diff_split;
+            }
+            Action::TogglePreview => {
+                self.preview.enabled = !self.preview.enabled;
+                self.preview_follow_selection();
+            }
+            Action::PreviewPrLoaded(owner, repo, pr) => {
+                if self.preview.repo == Some((owner, repo)) {
+                    self.preview.prs.insert(pr.number, *pr);
+                }
+            }
+            Action::PreviewIssueLoaded(owner, repo, number, body) => {
+                if self.preview.repo == Some((owner, repo)) {
+                    self.preview.issue_bodies.insert(number, body);
+                }
+            }
+            Action::BrowsePrsLoaded(owner, repo, prs) => {
+                if self.browse.prs_repo == Some((owner, repo)) {
+                    let prev = self
+                        .browse
+                        .prs
+                        .get(self.browse.pr_index)
+                        .map(|pr| pr.number);
+                    self.browse.pr_index =
+                        reselect(&prs, self.browse.pr_index, prev, |pr| pr.number);
+                    self.browse.prs = prs;
                 }
             }
 
             // PR operations
-            Action::PrsLoaded(prs, total, load_id) => {
+            Action::PrsLoaded(prs, page_info, load_id) => {
                 if load_id == self.load_id {
                     self.loading = false;
-                    self.prs_pagination = PaginationState {
-                        page: 1,
-                        has_more: prs.len() == PAGE_SIZE,
-                        loading_more: false,
-                        total_count: total,
-                    };
+                    self.prs_pagination =
+                        PaginationState::first(page_info, prs.len(), self.forge.page_size());
+                    let prev = self.prs.get(self.pr_index).map(|pr| pr.number);
                     self.prs = prs;
-                    self.pr_index = self.pr_index.min(self.prs.len().saturating_sub(1));
+                    self.pr_index = reselect(&self.prs, self.pr_index, prev, |pr| pr.number);
                 }
             }
             Action::PrDetailLoaded(pr, load_id) => {
                 if load_id == self.load_id {
                     self.loading = false;
+                    if self
+                        .current_pr
+                        .as_ref()
+                        .is_some_and(|p| p.number != pr.number)
+                    {
+                        self.pr_seen = PrSnapshot::default();
+                    }
+                    let first_load = self.screen != Screen::PrDetail
+                        || self
+                            .current_pr
+                            .as_ref()
+                            .is_none_or(|p| p.number != pr.number);
+                    if self.screen != Screen::PrDetail {
+                        self.push_nav(NavEntry {
+                            screen: Screen::PrDetail,
+                            repo: self.current_repo.clone(),
+                            pr: Some(pr.number),
+                            ..NavEntry::default()
+                        });
+                    }
+                    if first_load {
+                        if let Some((owner, repo)) = self.current_repo.clone() {
+                            self.record_visit(owner, repo, Some(pr.number), pr.title.clone());
+                        }
+                    }
+                    let was_changed = self.pr_changed();
+                    self.pr_seen
+                        .body
+                        .get_or_insert_with(|| pr.body.clone().unwrap_or_default());
                     self.current_pr = Some(*pr);
+                    self.flag_pr_changes(was_changed);
                     // Only transition screen on first load, not background refresh
                     if self.screen != Screen::PrDetail {
-                        self.prev_screen = Some(self.screen);
-                        self.scroll_offset = 0;
+                        self.scroll_offset = self.session_scroll.take().unwrap_or(0);
                         self.screen = Screen::PrDetail;
                     }
                 }
             }
-
-            // Issues
-            Action::IssuesLoaded(issues, total, load_id) => {
+            Action::PrCommentsLoaded(comments, load_id) => {
                 if load_id == self.load_id {
-                    self.loading = false;
-                    self.issues_pagination = PaginationState {
-                        page: 1,
-                        has_more: issues.len() == PAGE_SIZE,
-                        loading_more: false,
-                        total_count: total,
-                    };
-                    self.issues = issues;
-                    self.issue_index = self.issue_index.min(self.issues.len().saturating_sub(1));
+                    let was_changed = self.pr_changed();
+                    self.pr_seen
+                        .comments
+                        .get_or_insert_with(|| comments.clone());
+                    self.pr_comments = comments;
+                    self.flag_pr_changes(was_changed);
+                    if self.search.active {
+                        self.recompute_search_matches();
+                    }
                 }
             }
-
-            // Commits
-            Action::CommitsLoaded(commits, total, load_id) => {
+            Action::PrReviewsLoaded(reviews, load_id) => {
                 if load_id == self.load_id {
-                    self.loading = false;
-                    self.commits_pagination = PaginationState {
-                        page: 1,
-                        has_more: commits.len() == PAGE_SIZE,
-                        loading_more: false,
-                        total_count: total,
-                    };
-                    self.commits = commits;
-                    self.commit_index = self.commit_index.min(self.commits.len().saturating_sub(1));
+                    self.pr_reviews = reviews;
                 }
             }
-            Action::CommitDetailLoaded(commit, load_id) => {
+            Action::PrChecksLoaded(checks, load_id) => {
                 if load_id == self.load_id {
-                    self.loading = false;
-                    self.current_commit = Some(*commit);
+                    self.pr_checks = checks;
+                }
+            }
+            Action::ViewedFilesLoaded(paths, load_id) => {
+                if load_id == self.load_id {
+                    self.viewed_files = Some(paths);
+                }
+            }
+            Action::ReviewThreadsLoaded(threads, load_id) => {
+                if load_id == self.load_id {
+                    self.review_threads = threads;
+                    self.thread_index = self
+                        .thread_index
+                        .min(self.review_threads.len().saturating_sub(1));
+                    if self.search.active {
+                        self.recompute_search_matches();
+                    }
+                }
+            }
+            Action::NextXref => self.step_xref(1),
+            Action::PrevXref => self.step_xref(-1),
+            Action::ClearXref => self.xref_index = None,
+            Action::NextThread => self.step_thread(1),
+            Action::PrevThread => self.step_thread(-1),
+            Action::PrFilesLoaded(files, load_id) => {
+                if load_id == self.load_id {
+                    self.pr_files = files;
+                    self.file_index = self.file_index.min(self.pr_files.len().saturating_sub(1));
+                    if self.search.active {
+                        self.recompute_search_matches();
+                    }
+                }
+            }
+            Action::NextFile => self.step_file(1),
+            Action::PrevFile => self.step_file(-1),
+            Action::PrCommitsLoaded(commits, load_id) => {
+                if load_id == self.load_id {
+                    self.pr_commits = commits;
+                    self.pr_commit_index = self
+                        .pr_commit_index
+                        .min(self.pr_commits.len().saturating_sub(1));
+                    if self.search.active {
+                        self.recompute_search_matches();
+                    }
+                }
+            }
+            Action::NextPrCommit => self.step_pr_commit(1),
+            Action::PrevPrCommit => self.step_pr_commit(-1),
+            Action::OpenFileInEditor => {
+                if let (Some((owner, repo)), Some(pr), Some(file)) = (
+                    &self.current_repo,
+                    &self.current_pr,
+                    self.pr_files.get(self.file_index),
+                ) {
+                    self.spawn_open_file(
+                        owner.clone(),
+                        repo.clone(),
+                        pr.head_sha.clone(),
+                        pr.head_branch.clone(),
+                        file.filename.clone(),
+                    );
+                }
+            }
+            Action::EditRepoMeta => {
+                if let Some((owner, repo)) = self.target_repo() {
+                    self.loading = true;
+                    self.spawn_load_repo_meta(owner, repo);
+                }
+            }
+            Action::RepoMetaLoaded(owner, repo, meta) => {
+                self.loading = false;
+                let _ = self
+                    .action_tx
+                    .send(Action::SuspendForEditor(EditorContext::EditRepo {
+                        owner,
+                        repo,
+                        meta,
+                    }));
+            }
+            Action::ToggleStar => {
+                if let Some((owner, repo)) = self.target_repo() {
+                    match self.is_starred(&owner, &repo) {
+                        Some(starred) => self.set_star(owner, repo, !starred),
+                        None => self.spawn_load_star_state(owner, repo),
+                    }
+                }
+            }
+            Action::StarStateLoaded(owner, repo, starred) => {
+                self.starred.insert(format!("{}/{}", owner, repo), starred);
+                self.set_star(owner, repo, !starred);
+            }
+            Action::StarFailed(owner, repo, err) => {
+                if let Some(starred) = self.is_starred(&owner, &repo) {
+                    self.apply_star(&owner, &repo, !starred);
+                }
+                self.flash_message = None;
+                self.update(Action::Error(err));
+            }
+            Action::ForkRepo => {
+                if let Some((owner, repo)) = self.target_repo() {
+                    self.update(Action::ShowConfirm(ConfirmAction::ForkRepo { owner, repo }));
+                }
+            }
+            Action::RepoForked(fork) => {
+                self.loading = false;
+                self.flash_message = Some((
+                    format!("Forked to {}.", fork.full_path()),
+                    std::time::Instant::now(),
+                ));
+            }
+            Action::ForkStatusLoaded(owner, repo, status) => {
+                if self.current_repo.as_ref() == Some(&(owner, repo)) {
+                    self.fork_status = status;
+                }
+            }
+            Action::SyncFork => {
+                let Some(status) = &self.fork_status else {
+                    return;
+                };
+                if status.behind == 0 {
+                    self.flash_message = Some((
+                        format!("{} is up to date with {}.", status.branch, status.upstream),
+                        std::time::Instant::now(),
+                    ));
+                    return;
+                }
+                let confirm = ConfirmAction::SyncFork {
+                    branch: status.branch.clone(),
+                    upstream: status.upstream.clone(),
+                    behind: status.behind,
+                };
+                self.update(Action::ShowConfirm(confirm));
+            }
+            Action::ForkSynced(branch) => {
+                self.loading = false;
+                self.flash_message = Some((
+                    format!("Synced {} with upstream.", branch),
+                    std::time::Instant::now(),
+                ));
+                if let Some((owner, repo)) = self.current_repo.clone() {
+                    self.spawn_load_fork_status(owner, repo);
+                }
+            }
+            Action::ShowWatchSelect => {
+                if self.target_repo().is_some() {
+                    self.popup_title = "Watch".to_string();
+                    self.popup_items = Subscription::ALL.iter().map(|s| s.to_string()).collect();
+                    self.popup_index = 0;
+                    self.input_mode = InputMode::SelectPopup;
+                }
+            }
+            Action::SubscriptionSet(path, subscription) => {
+                let message = match subscription {
+                    Subscription::Watching => format!("Watching {}.", path),
+                    Subscription::Participating => {
+                        format!("Notified only when participating in {}.", path)
+                    }
+                    Subscription::Ignoring => format!("Ignoring {}.", path),
+                };
+                self.flash_message = Some((message, std::time::Instant::now()));
+            }
+            Action::RepoMetaUpdated => {
+                self.flash_message =
+                    Some(("Repository updated.".to_string(), std::time::Instant::now()));
+                if self.screen == Screen::RepoList {
+                    let _ = self.action_tx.send(Action::Refresh);
+                }
+            }
+            Action::CloneRepo => {
+                if let Some(repo) = self.repos.get(self.repo_index) {
+                    let dir = self.ui_config.clone_dir();
+                    let target = dir.join(&repo.name);
+                    if target.exists() {
+                        self.flash_message = Some((
+                            format!("{} already exists", target.display()),
+                            std::time::Instant::now(),
+                        ));
+                    } else if let Err(e) = std::fs::create_dir_all(&dir) {
+                        self.error = Some(format!("Could not create {}: {}", dir.display(), e));
+                    } else {
+                        let run = GitRun {
+                            dir,
+                            steps: vec![vec![
+                                "clone".to_string(),
+                                repo.clone_url(),
+                                repo.name.clone(),
+                            ]],
+                            done: format!("Cloned {} to {}", repo.full_path(), target.display()),
+                        };
+                        let _ = self.action_tx.send(Action::SuspendForGit(run));
+                    }
+                }
+            }
+            Action::CheckoutPr => {
+                if let (Some((owner, repo)), Some(pr)) = (&self.current_repo, &self.current_pr) {
+                    self.spawn_checkout_pr(
+                        owner.clone(),
+                        repo.clone(),
+                        pr.number,
+                        pr.head_branch.clone(),
+                    );
+                }
+            }
+            Action::GitFinished(msg) => {
+                self.flash_message = Some((msg, std::time::Instant::now()));
+            }
+            Action::ToggleResolvedThreads => {
+                self.hide_resolved_threads = !self.hide_resolved_threads;
+                self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+                if self.search.active {
+                    self.recompute_search_matches();
+                }
+            }
+            Action::ToggleThreadResolved => {
+                let visible = self.visible_thread_indices().contains(&self.thread_index);
+                if let (true, Some((owner, repo)), Some(pr), Some(thread)) = (
+                    visible,
+                    &self.current_repo,
+                    &self.current_pr,
+                    self.review_threads.get(self.thread_index),
+                ) {
+                    self.spawn_set_thread_resolved(
+                        owner.clone(),
+                        repo.clone(),
+                        pr.number,
+                        thread.id.clone(),
+                        !thread.resolved,
+                    );
+                }
+            }
+            Action::ThreadResolved {
+                thread_id,
+                resolved,
+            } => {
+                if let Some(thread) = self.review_threads.iter_mut().find(|t| t.id == thread_id) {
+                    thread.resolved = resolved;
+                }
+                let msg = if resolved {
+                    "Thread resolved."
+                } else {
+                    "Thread unresolved."
+                };
+                self.flash_message = Some((msg.to_string(), std::time::Instant::now()));
+                self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+            }
+            Action::ToggleBotComments => {
+                self.hide_bot_comments = !self.hide_bot_comments;
+                self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+                if self.search.active {
+                    self.recompute_search_matches();
+                }
+            }
+
+            // Issues
+            Action::IssuesLoaded(issues, page_info, load_id) => {
+                if load_id == self.load_id {
+                    self.loading = false;
+                    self.issues_pagination =
+                        PaginationState::first(page_info, issues.len(), self.forge.page_size());
+                    let prev = self.issues.get(self.issue_index).map(|i| i.number);
+                    self.issues = issues;
+                    self.issue_index = reselect(&self.issues, self.issue_index, prev, |i| i.number);
+                }
+            }
+
+            // Commits
+            Action::CommitsLoaded(commits, page_info, load_id) => {
+                if load_id == self.load_id {
+                    self.loading = false;
+                    self.commits_pagination =
+                        PaginationState::first(page_info, commits.len(), self.forge.page_size());
+                    let prev = self.commits.get(self.commit_index).map(|c| c.sha.clone());
+                    self.commits = commits;
+                    self.commit_index =
+                        reselect(&self.commits, self.commit_index, prev, |c| c.sha.clone());
+                }
+            }
+            Action::CommitDetailLoaded(commit, load_id) => {
+                if load_id == self.load_id {
+                    self.loading = false;
                     // Only transition screen on first load, not background refresh
                     if self.screen != Screen::CommitDetail {
-                        self.prev_screen = Some(self.screen);
+                        self.push_nav(NavEntry {
+                            screen: Screen::CommitDetail,
+                            repo: self.current_repo.clone(),
+                            commit: Some(commit.sha.clone()),
+                            ..NavEntry::default()
+                        });
                         self.scroll_offset = 0;
                         self.screen = Screen::CommitDetail;
                     }
+                    self.current_commit = Some(*commit);
+                }
+            }
+
+            Action::CommitChecksLoaded(sha, checks, load_id) => {
+                if load_id == self.load_id {
+                    self.commit_checks = Some((sha, checks));
                 }
             }
 
             // Actions (workflow runs)
-            Action::ActionRunsLoaded(runs, total, load_id) => {
+            Action::ActionRunsLoaded(runs, page_info, load_id) => {
                 if load_id == self.load_id {
                     self.loading = false;
-                    self.actions_pagination = PaginationState {
-                        page: 1,
-                        has_more: runs.len() == PAGE_SIZE,
-                        loading_more: false,
-                        total_count: total,
-                    };
+                    self.actions_pagination =
+                        PaginationState::first(page_info, runs.len(), self.forge.page_size());
+                    let prev = self.action_runs.get(self.action_index).map(|r| r.id);
                     self.action_runs = runs;
-                    self.action_index = self
-                        .action_index
-                        .min(self.action_runs.len().saturating_sub(1));
+                    self.action_index =
+                        reselect(&self.action_runs, self.action_index, prev, |r| r.id);
+                    self.actions_last_poll = Some(std::time::Instant::now());
+                }
+            }
+            Action::ToggleWorkflowSummary => {
+                self.workflow_summary = !self.workflow_summary;
+                if self.workflow_summary {
+                    if let Some((owner, repo)) = self.current_repo.clone() {
+                        self.spawn_load_workflow_history(owner, repo, self.load_id);
+                    }
+                }
+            }
+            Action::ShowArtifactSelect => {
+                if self.download.is_some() {
+                    self.flash_message = Some((
+                        "Wait for the running download to finish.".to_string(),
+                        std::time::Instant::now(),
+                    ));
+                    return;
+                }
+                if let (Some((owner, repo)), Some(run)) =
+                    (&self.current_repo, self.action_runs.get(self.action_index))
+                {
+                    self.loading = true;
+                    self.spawn_load_artifacts(owner.clone(), repo.clone(), run.id);
+                }
+            }
+            Action::ArtifactsLoaded(artifacts) => {
+                self.loading = false;
+                if artifacts.is_empty() {
+                    self.flash_message = Some((
+                        "This run kept no artifacts.".to_string(),
+                        std::time::Instant::now(),
+                    ));
+                    return;
+                }
+                self.popup_title = "Download Artifact".to_string();
+                self.popup_items = artifacts
+                    .iter()
+                    .map(|a| {
+                        let expired = if a.expired { ", expired" } else { "" };
+                        format!("{} ({}{})", a.name, format_size(a.size), expired)
+                    })
+                    .collect();
+                self.popup_index = 0;
+                self.artifacts = artifacts;
+                self.input_mode = InputMode::SelectPopup;
+            }
+            // Marks
+            Action::ToggleMark => {
+                let Some(number) = self.mark_rows().and_then(|(rows, i)| rows.get(i).copied())
+                else {
+                    return;
+                };
+                if let Some(marks) = self.marks_here() {
+                    if !marks.numbers.remove(&number) {
+                        marks.numbers.insert(number);
+                    }
+                    marks.anchor = None;
+                }
+                self.update(Action::ScrollDown);
+            }
+            Action::MarkRange => {
+                let Some((rows, index)) = self.mark_rows() else {
+                    return;
+                };
+                let Some(&number) = rows.get(index) else {
+                    return;
+                };
+                let Some(marks) = self.marks_here() else {
+                    return;
+                };
+                // An anchor that's no longer listed starts the range over
+                let start = marks
+                    .anchor
+                    .take()
+                    .and_then(|anchor| rows.iter().position(|&n| n == anchor));
+                let message = match start {
+                    Some(start) => {
+                        let range = start.min(index)..=start.max(index);
+                        marks.numbers.extend(&rows[range]);
+                        format!("{} marked", marks.numbers.len())
+                    }
+                    None => {
+                        marks.anchor = Some(number);
+                        format!(
+                            "Range from #{}; V again to mark up to the selection",
+                            number
+                        )
+                    }
+                };
+                self.flash_message = Some((message, std::time::Instant::now()));
+            }
+            Action::ClearMarks => {
+                self.marks = Marks::default();
+            }
+            Action::BulkProgress(done) => {
+                if let Some(bulk) = &mut self.bulk {
+                    bulk.done = done;
+                }
+            }
+            Action::BulkDone(failed) => {
+                let Some(bulk) = self.bulk.take() else {
+                    return;
+                };
+                // Whatever failed stays marked, ready to retry
+                self.marks
+                    .numbers
+                    .retain(|n| failed.iter().any(|(f, _)| f == n));
+                self.marks.anchor = None;
+                let succeeded = bulk.total - failed.len();
+                if failed.is_empty() {
+                    self.flash_message = Some((
+                        format!(
+                            "{} {} {}.",
+                            bulk.op.past(),
+                            bulk.total,
+                            item_noun(bulk.kind, bulk.total)
+                        ),
+                        std::time::Instant::now(),
+                    ));
+                } else {
+                    let errors: Vec<String> = failed
+                        .iter()
+                        .map(|(number, e)| format!("#{}: {}", number, e))
+                        .collect();
+                    self.error = Some(format!(
+                        "{} {} of {} {}; {}",
+                        bulk.op.past(),
+                        succeeded,
+                        bulk.total,
+                        item_noun(bulk.kind, bulk.total),
+                        errors.join("; ")
+                    ));
+                }
+                if succeeded > 0 {
+                    let _ = self.action_tx.send(Action::Refresh);
+                }
+            }
+
+            Action::DownloadProgress(received, total) => {
+                if let Some(download) = &mut self.download {
+                    download.received = received;
+                    download.total = total.or(download.total);
+                }
+            }
+            Action::ArtifactDownloaded(path) => {
+                self.download = None;
+                self.flash_message = Some((
+                    format!("Saved {}", path.display()),
+                    std::time::Instant::now(),
+                ));
+            }
+            Action::DownloadFailed(err) => {
+                self.download = None;
+                self.update(Action::Error(err));
+            }
+            Action::DiffProgress(received, total) => {
+                if let Some(download) = &mut self.diff_download {
+                    download.received = received;
+                    download.total = total.or(download.total);
+                }
+            }
+            Action::DiffFailed(err) => {
+                self.diff_download = None;
+                self.update(Action::Error(err));
+            }
+            Action::WorkflowHistoryLoaded(runs, load_id) => {
+                if load_id == self.load_id {
+                    self.workflows = summarize_workflows(&runs);
+                    self.workflow_index = self
+                        .workflow_index
+                        .min(self.workflows.len().saturating_sub(1));
+                }
+            }
+            Action::ActionRunsPolled(runs, load_id) => {
+                if load_id == self.load_id {
+                    self.merge_polled_action_runs(runs);
                 }
             }
 
             // Pagination append handlers
-            Action::ReposAppended(new_repos, total, load_id) => {
+            Action::ReposAppended(new_repos, page_info, load_id) => {
                 if load_id == self.load_id {
-                    self.repos_pagination.loading_more = false;
-                    self.repos_pagination.has_more = new_repos.len() == PAGE_SIZE;
-                    if total.is_some() {
-                        self.repos_pagination.total_count = total;
-                    }
-                    self.repos.extend(new_repos);
+                    self.repos_pagination.appended(
+                        page_info,
+                        new_repos.len(),
+                        self.forge.page_size(),
+                    );
+                    let known: HashSet<_> = self.repos.iter().map(repo_key).collect();
+                    self.repos.extend(
+                        new_repos
+                            .into_iter()
+                            .filter(|r| !known.contains(&repo_key(r))),
+                    );
                 }
             }
-            Action::PrsAppended(new_prs, total, load_id) => {
+            Action::PrsAppended(new_prs, page_info, load_id) => {
                 if load_id == self.load_id {
-                    self.prs_pagination.loading_more = false;
-                    self.prs_pagination.has_more = new_prs.len() == PAGE_SIZE;
-                    if total.is_some() {
-                        self.prs_pagination.total_count = total;
-                    }
-                    self.prs.extend(new_prs);
+                    self.prs_pagination
+                        .appended(page_info, new_prs.len(), self.forge.page_size());
+                    let known: HashSet<u64> = self.prs.iter().map(|pr| pr.number).collect();
+                    self.prs
+                        .extend(new_prs.into_iter().filter(|pr| !known.contains(&pr.number)));
                 }
             }
-            Action::IssuesAppended(new_issues, total, load_id) => {
+            Action::IssuesAppended(new_issues, page_info, load_id) => {
                 if load_id == self.load_id {
-                    self.issues_pagination.loading_more = false;
-                    self.issues_pagination.has_more = new_issues.len() == PAGE_SIZE;
-                    if total.is_some() {
-                        self.issues_pagination.total_count = total;
-                    }
-                    self.issues.extend(new_issues);
+                    self.issues_pagination.appended(
+                        page_info,
+                        new_issues.len(),
+                        self.forge.page_size(),
+                    );
+                    let known: HashSet<u64> = self.issues.iter().map(|i| i.number).collect();
+                    self.issues.extend(
+                        new_issues
+                            .into_iter()
+                            .filter(|i| !known.contains(&i.number)),
+                    );
                 }
             }
-            Action::CommitsAppended(new_commits, total, load_id) => {
+            Action::CommitsAppended(new_commits, page_info, load_id) => {
                 if load_id == self.load_id {
-                    self.commits_pagination.loading_more = false;
-                    self.commits_pagination.has_more = new_commits.len() == PAGE_SIZE;
-                    if total.is_some() {
-                        self.commits_pagination.total_count = total;
-                    }
-                    self.commits.extend(new_commits);
+                    self.commits_pagination.appended(
+                        page_info,
+                        new_commits.len(),
+                        self.forge.page_size(),
+                    );
+                    let known: HashSet<String> =
+                        self.commits.iter().map(|c| c.sha.clone()).collect();
+                    self.commits
+                        .extend(new_commits.into_iter().filter(|c| !known.contains(&c.sha)));
                 }
             }
-            Action::ActionRunsAppended(new_runs, total, load_id) => {
+            Action::ActionRunsAppended(new_runs, page_info, load_id) => {
                 if load_id == self.load_id {
-                    self.actions_pagination.loading_more = false;
-                    self.actions_pagination.has_more = new_runs.len() == PAGE_SIZE;
-                    if total.is_some() {
-                        self.actions_pagination.total_count = total;
-                    }
-                    self.action_runs.extend(new_runs);
+                    self.actions_pagination.appended(
+                        page_info,
+                        new_runs.len(),
+                        self.forge.page_size(),
+                    );
+                    let known: HashSet<u64> = self.action_runs.iter().map(|r| r.id).collect();
+                    self.action_runs
+                        .extend(new_runs.into_iter().filter(|r| !known.contains(&r.id)));
                 }
             }
 
@@ -1111,23 +3645,35 @@ impl App {
                     match self.screen {
                         Screen::PrDetail => {
                             if let Some(pr) = &self.current_pr {
+                                if self.diff_download.is_some() {
+                                    self.flash_message = Some((
+                                        "Wait for the diff that's loading.".to_string(),
+                                        std::time::Instant::now(),
+                                    ));
+                                    return;
+                                }
                                 let number = pr.number;
+                                self.diff_download = Some(Download {
+                                    name: format!("diff of #{}", number),
+                                    received: 0,
+                                    total: None,
+                                });
                                 self.spawn_load_pr_diff(owner.clone(), repo.clone(), number);
                             }
                         }
+                        Screen::DiffView => {
+                            if let Some(diff) = self
+                                .pr_files
+                                .get(self.file_index)
+                                .and_then(CommitFile::diff)
+                            {
+                                let _ = self.action_tx.send(Action::SuspendForPager(diff));
+                            }
+                        }
                         Screen::CommitDetail => {
                             if let Some(commit) = &self.current_commit {
-                                let mut diff = String::new();
-                                for file in &commit.files {
-                                    if let Some(patch) = &file.patch {
-                                        diff.push_str(&format!(
-                                            "diff --git a/{f} b/{f}\n",
-                                            f = file.filename
-                                        ));
-                                        diff.push_str(patch);
-                                        diff.push('\n');
-                                    }
-                                }
+                                let diff: String =
+                                    commit.files.iter().filter_map(CommitFile::diff).collect();
                                 let _ = self.action_tx.send(Action::SuspendForPager(diff));
                             }
                         }
@@ -1135,7 +3681,34 @@ impl App {
                     }
                 }
             }
-            Action::SuspendForPager(_) => {
+            Action::ShowPrChanges => {
+                if let Some(pr) = &self.current_pr {
+                    match self.pr_seen.changes(
+                        pr.number,
+                        pr.body.as_deref().unwrap_or(""),
+                        &self.pr_comments,
+                    ) {
+                        Some(changes) => {
+                            let _ = self.action_tx.send(Action::SuspendForPager(changes));
+                        }
+                        None => {
+                            self.flash_message = Some((
+                                format!("No changes to #{} since you opened it", pr.number),
+                                std::time::Instant::now(),
+                            ));
+                        }
+                    }
+                }
+            }
+            Action::DiffLoaded(diff) => {
+                self.diff_download = None;
+                let action = match diff {
+                    Spooled::Text(diff) => Action::SuspendForPager(diff),
+                    Spooled::File(path) => Action::SuspendForPagerFile(path),
+                };
+                let _ = self.action_tx.send(action);
+            }
+            Action::SuspendForPager(_) | Action::SuspendForPagerFile(_) => {
                 // Handled in main loop
             }
 
@@ -1143,15 +3716,18 @@ impl App {
             Action::Refresh => {
                 self.load_id += 1;
                 match self.screen {
-                    Screen::Home => {
-                        // On Home, r navigates to repo browser
+                    Screen::Home => self.update(Action::LoadHome),
+                    Screen::RepoList => {
                         self.loading = true;
                         self.spawn_load_repos(self.load_id);
-                        self.screen = Screen::RepoList;
                     }
-                    Screen::RepoList => {
+                    Screen::Browse => {
                         self.loading = true;
                         self.spawn_load_repos(self.load_id);
+                        if let Some((owner, repo)) = self.browse.prs_repo.clone() {
+                            self.browse.fetched.insert((owner.clone(), repo.clone()));
+                            self.spawn_browse_prs(owner, repo);
+                        }
                     }
                     Screen::RepoView => {
                         if let Some((owner, repo)) = &self.current_repo {
@@ -1190,6 +3766,8 @@ impl App {
                             }
                         }
                     }
+                    // The file list is already loaded; refresh from the PR itself
+                    Screen::DiffView => {}
                     Screen::CommitDetail => {
                         if let Some((owner, repo)) = &self.current_repo {
                             if let Some(commit) = &self.current_commit {
@@ -1202,38 +3780,167 @@ impl App {
                             }
                         }
                     }
+                    Screen::Logs => self.update(Action::ShowLogs),
                 }
             }
 
+            Action::RefreshItem => self.refresh_item(),
+            Action::HardRefresh => {
+                self.bypass_cache = true;
+                self.preview.prs.clear();
+                self.preview.issue_bodies.clear();
+                self.preview.fetched.clear();
+                self.preview.target = None;
+                self.update(Action::Refresh);
+                self.bypass_cache = false;
+            }
+            Action::ItemRefreshed(label) => {
+                self.flash_message =
+                    Some((format!("Refreshed {}", label), std::time::Instant::now()));
+            }
+            Action::ShowRepoList => self.show_repo_list(),
+
             // Open in browser
             Action::OpenInBrowser => {
-                if let Some(url) = self.current_item_url() {
-                    let _ = open::that(&url);
+                let targets = self.link_targets();
+                if targets.len() > 1 {
+                    self.popup_title = "Open Link".to_string();
+                    self.popup_items = targets.into_iter().map(|(label, _)| label).collect();
+                    self.popup_index = 0;
+                    self.input_mode = InputMode::SelectPopup;
+                } else if let Some((_, url)) = targets.first() {
+                    let _ = open::that(url);
                 }
             }
 
             // Yank URL
             Action::YankUrl => {
-                if let Some(url) = self.current_item_url() {
-                    if let Ok(mut clipboard) = arboard::Clipboard::new() {
-                        if clipboard.set_text(&url).is_ok() {
-                            self.flash_message =
-                                Some(("URL copied!".to_string(), std::time::Instant::now()));
-                        }
-                    }
+                let targets = self.link_targets();
+                if targets.len() > 1 {
+                    self.popup_title = "Copy Link".to_string();
+                    self.popup_items = targets.into_iter().map(|(label, _)| label).collect();
+                    self.popup_index = 0;
+                    self.input_mode = InputMode::SelectPopup;
+                } else if let Some((_, url)) = targets.first() {
+                    self.yank(url);
                 }
             }
 
             // Popup: merge method select
             Action::ShowMergeMethodSelect => {
+                if let Some(pr) = &self.current_pr {
+                    if !pr.mergeable_state.can_auto_merge() {
+                        self.error = Some(format!(
+                            "Cannot merge #{}: {}",
+                            pr.number, pr.mergeable_state
+                        ));
+                        return;
+                    }
+                }
+                let config = self.merge_config();
                 self.input_mode = InputMode::SelectPopup;
                 self.popup_title = "Merge Method".to_string();
-                self.popup_items = vec![
-                    "Merge commit".to_string(),
-                    "Squash and merge".to_string(),
-                    "Rebase and merge".to_string(),
-                ];
+                self.popup_items = MERGE_METHODS.iter().map(|m| m.to_string()).collect();
+                self.popup_index = config
+                    .default_merge_method
+                    .and_then(|method| MERGE_METHODS.iter().position(|&m| m == method))
+                    .unwrap_or(0);
+                self.merge_delete_branch = config.delete_branch_after_merge.unwrap_or(false);
+                // Blocked until checks or reviews clear: only auto-merge can go ahead
+                self.merge_auto = self
+                    .current_pr
+                    .as_ref()
+                    .is_some_and(|pr| !pr.mergeable_state.can_merge());
+                self.popup_items = self.merge_popup_items();
+            }
+
+            // Popup: commits to revert, all checked to start
+            Action::ShowRevertSelect => {
+                let Some(pr) = &self.current_pr else {
+                    return;
+                };
+                if pr.state != PrState::Merged {
+                    self.error = Some(format!(
+                        "Only merged PRs can be reverted, #{} is not",
+                        pr.number
+                    ));
+                    return;
+                }
+                if self.pr_commits.is_empty() {
+                    self.error = Some("No commits loaded for this PR".to_string());
+                    return;
+                }
+                self.popup_items = self
+                    .pr_commits
+                    .iter()
+                    .map(|c| {
+                        format!(
+                            "{} {}",
+                            &c.sha[..7.min(c.sha.len())],
+                            c.message.lines().next().unwrap_or("")
+                        )
+                    })
+                    .collect();
+                self.popup_checked = vec![true; self.pr_commits.len()];
+                self.popup_title = "Revert Commits".to_string();
+                self.popup_index = 0;
+                self.input_mode = InputMode::MultiSelectPopup;
+            }
+
+            // Popup: reviewers by open review requests
+            Action::ShowReviewerLoad => {
+                if let (Some((owner, repo)), Some(_)) = (&self.current_repo, &self.current_pr) {
+                    self.loading = true;
+                    self.spawn_load_reviewer_load(owner.clone(), repo.clone());
+                }
+            }
+            Action::ReviewerLoadLoaded(mut load) => {
+                self.loading = false;
+                let Some(pr) = &self.current_pr else {
+                    return;
+                };
+                load.retain(|r| r.login != pr.author);
+                load.sort_by(|a, b| (a.open_reviews, &a.login).cmp(&(b.open_reviews, &b.login)));
+                if load.is_empty() {
+                    self.error = Some("No one else can review this PR".to_string());
+                    return;
+                }
+                let width = load.iter().map(|r| r.login.len()).max().unwrap_or(0);
+                self.popup_items = load
+                    .iter()
+                    .map(|r| {
+                        let requested = if self.pr_reviews.requested.contains(&r.login) {
+                            " (requested)"
+                        } else {
+                            ""
+                        };
+                        format!(
+                            "{:<width$}  {:>3} open{}",
+                            r.login,
+                            r.open_reviews,
+                            requested,
+                            width = width
+                        )
+                    })
+                    .collect();
+                self.reviewer_load = load;
+                self.popup_title = "Request Review".to_string();
                 self.popup_index = 0;
+                self.input_mode = InputMode::SelectPopup;
+            }
+            Action::ReviewersRequested(login) => {
+                self.flash_message = Some((
+                    format!("Requested review from {}", login),
+                    std::time::Instant::now(),
+                ));
+                if let (Some((owner, repo)), Some(pr)) = (&self.current_repo, &self.current_pr) {
+                    self.spawn_load_pr_reviews(
+                        owner.clone(),
+                        repo.clone(),
+                        pr.number,
+                        self.load_id,
+                    );
+                }
             }
 
             // Popup: review select
@@ -1248,6 +3955,35 @@ impl App {
                 self.popup_index = 0;
             }
 
+            Action::ShowSnippetSelect(ctx) => {
+                self.snippet_context = Some(ctx);
+                self.popup_title = "Start From Snippet".to_string();
+                self.popup_items = std::iter::once("Blank".to_string())
+                    .chain(self.snippets.iter().map(|(name, _)| name.clone()))
+                    .collect();
+                self.popup_index = 0;
+                self.input_mode = InputMode::SelectPopup;
+            }
+
+            Action::ShowReactionSelect => {
+                let Some(target) = self.reaction_target() else {
+                    return;
+                };
+                self.popup_title = match target {
+                    ReactionTarget::Pr(number) => format!("React to #{}", number),
+                    ReactionTarget::Comment { .. } | ReactionTarget::ReviewComment { .. } => {
+                        "React to comment".to_string()
+                    }
+                };
+                self.popup_items = Reaction::ALL.iter().map(|r| r.to_string()).collect();
+                self.popup_index = 0;
+                self.input_mode = InputMode::SelectPopup;
+            }
+            Action::ReactionAdded(reaction) => {
+                self.flash_message =
+                    Some((format!("Reacted {}", reaction), std::time::Instant::now()));
+            }
+
             // Confirm dialog
             Action::ShowConfirm(confirm_action) => {
                 self.confirm_action = Some(confirm_action);
@@ -1263,9 +3999,21 @@ impl App {
                                 self.spawn_close_pr(owner.clone(), repo.clone(), number);
                             }
                         }
-                        ConfirmAction::MergePr { number, method } => {
+                        ConfirmAction::MergePr {
+                            number,
+                            method,
+                            delete_branch,
+                            auto,
+                        } => {
                             if let Some((owner, repo)) = &self.current_repo {
-                                self.spawn_merge_pr(owner.clone(), repo.clone(), number, method);
+                                self.spawn_merge_pr(
+                                    owner.clone(),
+                                    repo.clone(),
+                                    number,
+                                    method,
+                                    delete_branch,
+                                    auto,
+                                );
                             }
                         }
                         ConfirmAction::CloseIssue(number) => {
@@ -1273,6 +4021,38 @@ impl App {
                                 self.spawn_close_issue(owner.clone(), repo.clone(), number);
                             }
                         }
+                        ConfirmAction::CloseMarked(numbers) => {
+                            self.spawn_bulk(BulkOp::Close, numbers);
+                        }
+                        ConfirmAction::ForkRepo { owner, repo } => {
+                            self.loading = true;
+                            self.spawn_fork_repo(owner, repo);
+                        }
+                        ConfirmAction::SyncFork { branch, .. } => {
+                            if let Some((owner, repo)) = self.current_repo.clone() {
+                                self.loading = true;
+                                self.spawn_sync_fork(owner, repo, branch);
+                            }
+                        }
+                        ConfirmAction::ForgetMissing {
+                            owner,
+                            repo,
+                            number,
+                        } => self.forget_missing(owner, repo, number),
+                        ConfirmAction::RevertPr { number, commits } => {
+                            if let (Some((owner, repo)), Some(pr)) =
+                                (&self.current_repo, &self.current_pr)
+                            {
+                                if pr.number == number {
+                                    self.spawn_revert_pr(
+                                        owner.clone(),
+                                        repo.clone(),
+                                        pr.clone(),
+                                        commits,
+                                    );
+                                }
+                            }
+                        }
                     }
                 }
             }
@@ -1293,21 +4073,61 @@ impl App {
                     self.popup_index += 1;
                 }
             }
-            Action::PopupSelect => {
-                self.input_mode = InputMode::Normal;
-                // Determine what the popup was for based on title
-                if self.popup_title == "Merge Method" {
-                    if let Some(pr) = &self.current_pr {
-                        let method = match self.popup_index {
-                            0 => crate::types::MergeMethod::Merge,
-                            1 => crate::types::MergeMethod::Squash,
-                            _ => crate::types::MergeMethod::Rebase,
-                        };
+            // Command palette
+            Action::ShowPalette => {
+                self.palette_query.clear();
+                self.popup_title = "Command Palette".to_string();
+                self.input_mode = InputMode::Palette;
+                self.refresh_palette_items();
+            }
+            Action::PaletteInput(c) => {
+                self.palette_query.push(c);
+                self.refresh_palette_items();
+            }
+            Action::PaletteBackspace => {
+                self.palette_query.pop();
+                self.refresh_palette_items();
+            }
+
+            Action::PopupToggle => {
+                if self.popup_title == "Merge Method" {
+                    match self.popup_index.checked_sub(MERGE_METHODS.len()) {
+                        Some(0) => self.merge_auto = !self.merge_auto,
+                        Some(1) => self.merge_delete_branch = !self.merge_delete_branch,
+                        _ => return,
+                    }
+                    self.popup_items = self.merge_popup_items();
+                } else if let Some(checked) = self.popup_checked.get_mut(self.popup_index) {
+                    *checked = !*checked;
+                }
+            }
+            Action::PopupSelect => {
+                self.input_mode = InputMode::Normal;
+                // Determine what the popup was for based on title
+                if self.popup_title == "Merge Method" {
+                    let Some(&method) = MERGE_METHODS.get(self.popup_index) else {
+                        // A checkbox row: Enter ticks it like Space
+                        self.update(Action::PopupToggle);
+                        self.input_mode = InputMode::SelectPopup;
+                        return;
+                    };
+                    if let Some(pr) = &self.current_pr {
+                        if !self.merge_auto && !pr.mergeable_state.can_merge() {
+                            self.error = Some(format!(
+                                "Cannot merge #{} now: {}; tick auto-merge to merge once it clears",
+                                pr.number, pr.mergeable_state
+                            ));
+                            return;
+                        }
+                        let delete_branch = (self.merge_delete_branch && !pr.from_fork)
+                            .then(|| pr.head_branch.clone());
                         let _ = self
                             .action_tx
                             .send(Action::ShowConfirm(ConfirmAction::MergePr {
                                 number: pr.number,
                                 method,
+                                delete_branch,
+                                auto: self.merge_auto,
                             }));
                     }
                 } else if self.popup_title == "Submit Review" {
@@ -1318,19 +4138,507 @@ impl App {
                     };
                     if let Some((owner, repo)) = &self.current_repo {
                         if let Some(pr) = &self.current_pr {
-                            let _ = self.action_tx.send(Action::SuspendForEditor(
-                                EditorContext::ReviewPr {
-                                    owner: owner.clone(),
-                                    repo: repo.clone(),
-                                    number: pr.number,
-                                    event,
+                            let _ = self.action_tx.send(self.compose(EditorContext::ReviewPr {
+                                owner: owner.clone(),
+                                repo: repo.clone(),
+                                number: pr.number,
+                                event,
+                            }));
+                        }
+                    }
+                } else if self.popup_title == "Quit grit?" {
+                    match self.popup_index {
+                        0 => self.should_quit = true,
+                        1 => {}
+                        i => match self.pending_work().into_iter().nth(i - 2) {
+                            Some(
+                                PendingWork::Draft {
+                                    owner,
+                                    repo,
+                                    number,
+                                }
+                                | PendingWork::ReviewComments {
+                                    owner,
+                                    repo,
+                                    number,
+                                    ..
                                 },
+                            ) => {
+                                self.current_repo = Some((owner.clone(), repo.clone()));
+                                self.load_id += 1;
+                                self.spawn_load_pr_detail(owner, repo, number, self.load_id);
+                            }
+                            Some(PendingWork::WatchedRuns(_)) => {
+                                self.update(Action::SwitchRepoTab(RepoTab::Actions));
+                            }
+                            None => {}
+                        },
+                    }
+                } else if self.popup_title.starts_with("React to") {
+                    if let (Some((owner, repo)), Some(target), Some(&reaction)) = (
+                        &self.current_repo,
+                        self.reaction_target(),
+                        Reaction::ALL.get(self.popup_index),
+                    ) {
+                        self.spawn_add_reaction(owner.clone(), repo.clone(), target, reaction);
+                    }
+                } else if self.popup_title == "Open Link" {
+                    if let Some((_, url)) = self.link_targets().get(self.popup_index) {
+                        let _ = open::that(url);
+                    }
+                } else if self.popup_title == "Copy Link" {
+                    if let Some((_, url)) = self.link_targets().get(self.popup_index) {
+                        self.yank(url);
+                    }
+                } else if self.popup_title == "Download Artifact" {
+                    match self.artifacts.get(self.popup_index).cloned() {
+                        Some(artifact) if artifact.expired => {
+                            self.flash_message = Some((
+                                format!("{} has expired on the forge.", artifact.name),
+                                std::time::Instant::now(),
                             ));
                         }
+                        Some(artifact) => {
+                            self.download = Some(Download {
+                                name: artifact.name.clone(),
+                                received: 0,
+                                total: Some(artifact.size).filter(|&s| s > 0),
+                            });
+                            self.spawn_download_artifact(artifact);
+                        }
+                        None => {}
+                    }
+                } else if self.popup_title == "Saved Search" {
+                    if self.popup_index < self.searches.len() {
+                        self.search_choice = self.popup_index;
+                        self.saved.clear();
+                        self.saved_index = 0;
+                        self.saved_pagination = PaginationState::default();
+                        self.home_section = HomeSection::Saved;
+                        self.spawn_load_saved(1);
                     }
                 } else if self.popup_title == "Switch Forge" {
                     let _ = self.action_tx.send(Action::SwitchForge(self.popup_index));
+                } else if self.popup_title == "Set Milestone" {
+                    let milestone = self
+                        .popup_index
+                        .checked_sub(1)
+                        .and_then(|i| self.milestones.get(i))
+                        .map(|m| m.id);
+                    if let (Some((owner, repo)), Some(issue)) =
+                        (&self.current_repo, self.issues.get(self.issue_index))
+                    {
+                        self.spawn_set_milestone(
+                            owner.clone(),
+                            repo.clone(),
+                            issue.number,
+                            milestone,
+                        );
+                    }
+                } else if self.popup_title == "Command Palette" {
+                    if let Some(entry) = self.palette_matches().into_iter().nth(self.popup_index) {
+                        let _ = self.action_tx.send(entry.action);
+                    }
+                } else if self.popup_title == "Add Labels" {
+                    let labels: Vec<String> = self
+                        .labels
+                        .iter()
+                        .zip(&self.popup_checked)
+                        .filter(|(_, &checked)| checked)
+                        .map(|(label, _)| label.name.clone())
+                        .collect();
+                    if !labels.is_empty() {
+                        self.spawn_bulk(BulkOp::AddLabels(labels), self.marked_numbers());
+                    }
+                } else if self.popup_title == "Edit Labels" {
+                    if let (Some((owner, repo)), Some((kind, number, current))) =
+                        (&self.current_repo, self.label_target())
+                    {
+                        let (mut add, mut remove) = (Vec::new(), Vec::new());
+                        for (label, &checked) in self.labels.iter().zip(&self.popup_checked) {
+                            match (checked, current.iter().any(|c| c.name == label.name)) {
+                                (true, false) => add.push(label.name.clone()),
+                                (false, true) => remove.push(label.name.clone()),
+                                _ => {}
+                            }
+                        }
+                        if !add.is_empty() || !remove.is_empty() {
+                            self.spawn_update_labels(
+                                owner.clone(),
+                                repo.clone(),
+                                kind,
+                                number,
+                                add,
+                                remove,
+                            );
+                        }
+                    }
+                } else if self.popup_title == "Checklist" {
+                    let tasks = self.pr_tasks();
+                    let changed = tasks
+                        .iter()
+                        .zip(&self.popup_checked)
+                        .any(|(task, &checked)| task.done != checked);
+                    if let (true, Some((owner, repo)), Some(pr)) =
+                        (changed, self.current_repo.clone(), &self.current_pr)
+                    {
+                        self.spawn_update_checklist(
+                            owner,
+                            repo,
+                            pr.number,
+                            tasks,
+                            self.popup_checked.clone(),
+                        );
+                    }
+                } else if self.popup_title == "Request Review" {
+                    if let (Some((owner, repo)), Some(pr), Some(reviewer)) = (
+                        &self.current_repo,
+                        &self.current_pr,
+                        self.reviewer_load.get(self.popup_index),
+                    ) {
+                        self.spawn_request_reviewers(
+                            owner.clone(),
+                            repo.clone(),
+                            pr.number,
+                            reviewer.login.clone(),
+                        );
+                    }
+                } else if self.popup_title == "Revert Commits" {
+                    let commits: Vec<Commit> = self
+                        .pr_commits
+                        .iter()
+                        .zip(&self.popup_checked)
+                        .filter(|(_, &checked)| checked)
+                        .map(|(c, _)| c.clone())
+                        .collect();
+                    if let (Some(pr), false) = (&self.current_pr, commits.is_empty()) {
+                        let _ = self
+                            .action_tx
+                            .send(Action::ShowConfirm(ConfirmAction::RevertPr {
+                                number: pr.number,
+                                commits,
+                            }));
+                    }
+                } else if self.popup_title == "Find Commit" {
+                    if let Some(hit) = self.commit_hits.get(self.popup_index) {
+                        let (owner, repo) = (hit.owner.clone(), hit.repo.clone());
+                        let sha = hit.commit.sha.clone();
+                        self.current_repo = Some((owner.clone(), repo.clone()));
+                        self.load_id += 1;
+                        self.spawn_load_commit_detail(owner, repo, sha, self.load_id);
+                    }
+                } else if self.popup_title == "Repositories Of" {
+                    let scope = self
+                        .popup_index
+                        .checked_sub(1)
+                        .and_then(|i| self.orgs.get(i))
+                        .cloned();
+                    if scope != self.repo_scope {
+                        self.repo_scope = scope;
+                        self.repos.clear();
+                        self.repo_index = 0;
+                        self.repos_pagination = PaginationState::default();
+                        self.browse = BrowseState::default();
+                        if self.screen == Screen::Browse {
+                            self.loading = true;
+                            self.load_id += 1;
+                            self.spawn_load_repos(self.load_id);
+                        } else {
+                            self.show_repo_list();
+                        }
+                    }
+                } else if self.popup_title == "Cancel Task" {
+                    let id = self.task_rows.get(self.popup_index).copied();
+                    if let Some(task) = id.and_then(|id| self.tasks.borrow_mut().remove(&id)) {
+                        task.handle.abort();
+                        // Whatever it was loading won't arrive now
+                        self.loading = false;
+                        if task.label == DOWNLOAD_TASK {
+                            self.download = None;
+                        }
+                        if task.label == DIFF_TASK {
+                            self.diff_download = None;
+                        }
+                        self.flash_message = Some((
+                            format!("Cancelled: {}", task.label),
+                            std::time::Instant::now(),
+                        ));
+                    }
+                } else if self.popup_title == "Issue Template" {
+                    let template = self
+                        .popup_index
+                        .checked_sub(1)
+                        .and_then(|i| self.issue_templates.get(i))
+                        .cloned();
+                    self.update(Action::ShowForm(FormTarget::NewIssue));
+                    if let Some(template) = template {
+                        self.form = Form::from_template(&template);
+                        self.template_body = Some(template.body);
+                    }
+                } else if self.popup_title == "Start From Snippet" {
+                    if let Some(ctx) = self.snippet_context.take() {
+                        self.editor_prefill = self
+                            .popup_index
+                            .checked_sub(1)
+                            .and_then(|i| self.snippets.get(i))
+                            .map(|(_, body)| body.clone());
+                        let _ = self.action_tx.send(Action::SuspendForEditor(ctx));
+                    }
+                } else if self.popup_title == "Watch" {
+                    if let (Some(&subscription), Some((owner, repo))) =
+                        (Subscription::ALL.get(self.popup_index), self.target_repo())
+                    {
+                        self.spawn_set_subscription(owner, repo, subscription);
+                    }
+                } else if self.popup_title == "Restore Session" {
+                    if let Some(session) = self.saved_session.take() {
+                        if self.popup_index == 0 {
+                            self.restore_session(session);
+                        }
+                    }
+                } else if self.popup_title == "Filter Pull Requests" {
+                    if let Some(&filter) = self.forge.pr_filters().get(self.popup_index) {
+                        self.pr_filter = filter;
+                        self.pr_index = 0;
+                        let _ = self.action_tx.send(Action::Refresh);
+                    }
+                } else if self.popup_title == "Filter by Milestone" {
+                    self.milestone_filter = self
+                        .popup_index
+                        .checked_sub(1)
+                        .and_then(|i| self.milestones.get(i))
+                        .cloned();
+                    let _ = self.action_tx.send(Action::Refresh);
+                }
+            }
+
+            Action::ShowPrFilterSelect => {
+                self.popup_title = "Filter Pull Requests".to_string();
+                let filters = self.forge.pr_filters();
+                self.popup_items = filters.iter().map(|f| f.label().to_string()).collect();
+                // Start on the active filter so Enter keeps it
+                self.popup_index = filters
+                    .iter()
+                    .position(|&f| f == self.pr_filter)
+                    .unwrap_or(0);
+                self.input_mode = InputMode::SelectPopup;
+            }
+
+            // Milestones
+            Action::ShowMilestoneSelect(purpose) => {
+                if let Some((owner, repo)) = &self.current_repo {
+                    self.loading = true;
+                    self.spawn_load_milestones(owner.clone(), repo.clone(), purpose);
+                }
+            }
+            Action::MilestonesLoaded(milestones, purpose) => {
+                self.loading = false;
+                self.milestones = milestones;
+                self.input_mode = InputMode::SelectPopup;
+                let (title, first) = match purpose {
+                    MilestonePurpose::Assign => ("Set Milestone", "No milestone"),
+                    MilestonePurpose::Filter => ("Filter by Milestone", "All milestones"),
+                };
+                self.popup_title = title.to_string();
+                self.popup_items = std::iter::once(first.to_string())
+                    .chain(self.milestones.iter().map(|m| match m.due_on {
+                        Some(due) => format!("{} (due {})", m.title, due.format("%Y-%m-%d")),
+                        None => m.title.clone(),
+                    }))
+                    .collect();
+                // Start on the active filter so Enter keeps it
+                self.popup_index = match (purpose, &self.milestone_filter) {
+                    (MilestonePurpose::Filter, Some(active)) => self
+                        .milestones
+                        .iter()
+                        .position(|m| m.id == active.id)
+                        .map_or(0, |i| i + 1),
+                    _ => 0,
+                };
+            }
+            Action::MilestoneSet => {
+                self.flash_message =
+                    Some(("Milestone updated.".to_string(), std::time::Instant::now()));
+                let _ = self.action_tx.send(Action::Refresh);
+            }
+
+            // Labels
+            Action::ShowLabelSelect => {
+                let target = self.label_target().is_some() || !self.marked_numbers().is_empty();
+                if let (Some((owner, repo)), true) = (&self.current_repo, target) {
+                    self.loading = true;
+                    self.spawn_load_labels(owner.clone(), repo.clone());
+                }
+            }
+            Action::LabelsLoaded(labels) => {
+                self.loading = false;
+                self.labels = labels;
+                if self.labels.is_empty() {
+                    self.error = Some("No labels in this repository".to_string());
+                    return;
+                }
+                if !self.marked_numbers().is_empty() {
+                    self.popup_checked = vec![false; self.labels.len()];
+                    self.popup_items = self.labels.iter().map(|l| l.name.clone()).collect();
+                    self.popup_title = "Add Labels".to_string();
+                    self.popup_index = 0;
+                    self.input_mode = InputMode::MultiSelectPopup;
+                    return;
+                }
+                let Some((_, _, current)) = self.label_target() else {
+                    return;
+                };
+                self.popup_checked = self
+                    .labels
+                    .iter()
+                    .map(|l| current.iter().any(|c| c.name == l.name))
+                    .collect();
+                self.popup_items = self.labels.iter().map(|l| l.name.clone()).collect();
+                self.popup_title = "Edit Labels".to_string();
+                self.popup_index = 0;
+                self.input_mode = InputMode::MultiSelectPopup;
+            }
+            // Task list
+            Action::ShowChecklist => {
+                let tasks = self.pr_tasks();
+                if tasks.is_empty() {
+                    self.flash_message = Some((
+                        "No task list in the description".to_string(),
+                        std::time::Instant::now(),
+                    ));
+                    return;
+                }
+                self.popup_items = tasks.iter().map(|t| t.text.clone()).collect();
+                self.popup_checked = tasks.iter().map(|t| t.done).collect();
+                self.popup_title = "Checklist".to_string();
+                self.popup_index = 0;
+                self.input_mode = InputMode::MultiSelectPopup;
+            }
+            Action::ChecklistUpdated => {
+                self.flash_message =
+                    Some(("Checklist updated.".to_string(), std::time::Instant::now()));
+                let _ = self.action_tx.send(Action::Refresh);
+            }
+            Action::LabelsUpdated => {
+                self.flash_message =
+                    Some(("Labels updated.".to_string(), std::time::Instant::now()));
+                let _ = self.action_tx.send(Action::Refresh);
+            }
+
+            // Issue templates
+            Action::ShowIssueTemplateSelect => {
+                if let Some((owner, repo)) = self.current_repo.clone() {
+                    self.loading = true;
+                    self.spawn_load_issue_templates(owner, repo);
+                }
+            }
+            Action::IssueTemplatesLoaded(templates) => {
+                self.loading = false;
+                self.issue_templates = templates;
+                if self.issue_templates.is_empty() {
+                    self.update(Action::ShowForm(FormTarget::NewIssue));
+                    return;
                 }
+                self.popup_title = "Issue Template".to_string();
+                self.popup_items = std::iter::once("Blank".to_string())
+                    .chain(self.issue_templates.iter().map(|t| match &t.about {
+                        Some(about) => format!("{} - {}", t.name, about),
+                        None => t.name.clone(),
+                    }))
+                    .collect();
+                self.popup_index = 0;
+                self.input_mode = InputMode::SelectPopup;
+            }
+
+            // Issue form
+            Action::ShowForm(target) => {
+                if self.current_repo.is_none() {
+                    return;
+                }
+                self.template_body = None;
+                self.form = match target {
+                    FormTarget::NewIssue => Form {
+                        target,
+                        ..Form::default()
+                    },
+                    FormTarget::Issue(number) => {
+                        match self.issues.iter().find(|i| i.number == number) {
+                            Some(i) => Form::new(
+                                target,
+                                &i.title,
+                                &i.labels,
+                                &i.assignees,
+                                i.milestone.as_deref(),
+                            ),
+                            None => return,
+                        }
+                    }
+                    FormTarget::Pr(number) => {
+                        match self.current_pr.as_ref().filter(|pr| pr.number == number) {
+                            Some(pr) => Form::new(
+                                target,
+                                &pr.title,
+                                &pr.labels,
+                                &pr.assignees,
+                                pr.milestone.as_deref(),
+                            ),
+                            None => return,
+                        }
+                    }
+                };
+                self.input_mode = InputMode::Form;
+            }
+            Action::FormInput(c) => {
+                let focus = self.form.focus;
+                self.form.values[focus].push(c);
+            }
+            Action::FormBackspace => {
+                let focus = self.form.focus;
+                self.form.values[focus].pop();
+            }
+            Action::FormNextField => {
+                self.form.focus = (self.form.focus + 1) % FORM_FIELDS.len();
+            }
+            Action::FormPrevField => {
+                self.form.focus = (self.form.focus + FORM_FIELDS.len() - 1) % FORM_FIELDS.len();
+            }
+            Action::SubmitForm => {
+                if self.form.values[0].trim().is_empty() {
+                    self.form.focus = 0;
+                    self.flash_message = Some((
+                        "A title is required.".to_string(),
+                        std::time::Instant::now(),
+                    ));
+                    return;
+                }
+                self.input_mode = InputMode::Normal;
+                let Some((owner, repo)) = self.current_repo.clone() else {
+                    return;
+                };
+                let form = self.form.clone();
+                if form.target == FormTarget::NewIssue {
+                    let ctx = EditorContext::NewIssue { owner, repo, form };
+                    // The template stands in for a snippet
+                    let action = match self.template_body.take() {
+                        Some(body) => {
+                            self.editor_prefill = Some(body);
+                            Action::SuspendForEditor(ctx)
+                        }
+                        None => self.compose(ctx),
+                    };
+                    let _ = self.action_tx.send(action);
+                } else {
+                    self.spawn_save_form(owner, repo, form, String::new(), None);
+                }
+            }
+            Action::FormSaved(target, number) => {
+                let message = match target {
+                    FormTarget::NewIssue => format!("Opened issue #{}.", number),
+                    FormTarget::Issue(_) => format!("Issue #{} updated.", number),
+                    FormTarget::Pr(_) => format!("PR #{} updated.", number),
+                };
+                self.flash_message = Some((message, std::time::Instant::now()));
+                let _ = self.action_tx.send(Action::Refresh);
             }
 
             // Forge switching
@@ -1343,68 +4651,80 @@ impl App {
                 self.popup_items = self
                     .forge_configs
                     .iter()
-                    .map(|f| {
-                        if f.name == self.forge_name {
-                            format!("{} (active)", f.name)
-                        } else {
-                            f.name.clone()
-                        }
+                    .map(|f| match self.forge_states.get(&f.name) {
+                        _ if f.name == self.active_forge => format!("{} (active)", f.name),
+                        Some(ForgeState::Failed(e)) => format!("{} (unavailable: {})", f.name, e),
+                        _ => f.name.clone(),
                     })
                     .collect();
                 self.popup_index = 0;
             }
             Action::SwitchForge(idx) => {
-                if let Some(fc) = self.forge_configs.get(idx) {
-                    if fc.name == self.forge_name {
-                        return;
-                    }
-                    let fc = fc.clone();
-                    let tx = self.action_tx.clone();
-                    self.loading = true;
-                    self.error = None;
-                    tokio::spawn(async move {
-                        match crate::auth::load_forge_token(&fc).await {
-                            Ok(token) => {
-                                let forge: Arc<dyn crate::forge::Forge> = match fc.forge_type {
-                                    crate::config::ForgeType::GitHub => {
-                                        match crate::github::GitHub::new(token) {
-                                            Ok(gh) => Arc::new(gh),
-                                            Err(e) => {
-                                                tx.send(Action::Error(e.to_string())).ok();
-                                                return;
-                                            }
-                                        }
-                                    }
-                                    crate::config::ForgeType::GitLab => {
-                                        Arc::new(crate::gitlab::GitLab::new(fc.host.clone(), token))
-                                    }
-                                    crate::config::ForgeType::Gitea => {
-                                        Arc::new(crate::gitea::Gitea::new(fc.host.clone(), token))
-                                    }
-                                };
-                                tx.send(Action::ForgeReady(forge, fc.name.clone())).ok();
-                            }
-                            Err(e) => {
-                                tx.send(Action::Error(e)).ok();
-                            }
-                        }
-                    });
+                let Some(fc) = self.forge_configs.get(idx).cloned() else {
+                    return;
+                };
+                if fc.name == self.active_forge {
+                    return;
+                }
+                // Clients are built on first use and kept; failures are retried
+                if let Some(ForgeState::Ready(forge)) = self.forge_states.get(&fc.name) {
+                    let forge = Arc::clone(forge);
+                    self.update(Action::ForgeReady(forge, fc.name));
+                    return;
                 }
+                let tx = self.action_tx.clone();
+                self.loading = true;
+                self.error = None;
+                tokio::task::spawn_blocking(move || {
+                    let action = match crate::forge::connect(&fc) {
+                        Ok(forge) => Action::ForgeReady(forge, fc.name),
+                        Err(e) => Action::ForgeFailed(fc.name, e),
+                    };
+                    tx.send(action).ok();
+                });
+            }
+            Action::ForgeFailed(name, e) => {
+                self.loading = false;
+                self.error = Some(format!("{}: {}", name, e));
+                self.forge_states.insert(name, ForgeState::Failed(e));
             }
             Action::ForgeReady(new_forge, name) => {
+                self.forge_states
+                    .insert(name.clone(), ForgeState::Ready(Arc::clone(&new_forge)));
+                self.save_recent();
                 self.forge = new_forge;
-                self.forge_name = name;
+                self.forge_name = name.clone();
+                self.active_forge = name;
                 self.loading = false;
+                self.recent = cache::read(&self.recent_key()).unwrap_or_default();
+                self.recent_index = 0;
+                self.outbox = outbox::load(&self.forge_name);
+                self.offline = false;
+                self.cached_at.set(None);
+                self.back_stack.clear();
 
                 // Clear all data
                 self.repos.clear();
+                self.repo_scope = None;
                 self.prs.clear();
                 self.issues.clear();
                 self.commits.clear();
                 self.action_runs.clear();
                 self.review_requests.clear();
                 self.my_prs.clear();
+                self.alerts.clear();
+                self.home_seen = false;
+                self.assigned.clear();
+                self.mentions.clear();
+                self.saved.clear();
                 self.current_pr = None;
+                self.pr_comments.clear();
+                self.pr_reviews = PrReviews::default();
+                self.pr_checks = PrChecks::default();
+                self.viewed_files = None;
+                self.pr_files.clear();
+                self.pr_commits.clear();
+                self.review_threads.clear();
                 self.current_commit = None;
                 self.current_repo = None;
 
@@ -1416,6 +4736,9 @@ impl App {
                 self.action_index = 0;
                 self.review_index = 0;
                 self.my_pr_index = 0;
+                self.assigned_index = 0;
+                self.mentions_index = 0;
+                self.saved_index = 0;
                 self.scroll_offset = 0;
 
                 // Reset pagination
@@ -1424,18 +4747,41 @@ impl App {
                 self.issues_pagination = PaginationState::default();
                 self.commits_pagination = PaginationState::default();
                 self.actions_pagination = PaginationState::default();
+                self.saved_pagination = PaginationState::default();
 
                 // Navigate home and reload
                 self.screen = Screen::Home;
-                self.home_section = HomeSection::default();
+                self.home_section = self.home_order()[0];
                 let _ = self.action_tx.send(Action::LoadHome);
             }
 
             // Mutation results
             Action::PrMerged => {
+                self.stats.merges += 1;
                 self.flash_message = Some(("PR merged!".to_string(), std::time::Instant::now()));
                 let _ = self.action_tx.send(Action::Back);
             }
+            Action::AutoMergeEnabled(number) => {
+                if let Some(pr) = self.current_pr.as_mut().filter(|pr| pr.number == number) {
+                    pr.auto_merge = true;
+                }
+                self.flash_message = Some((
+                    format!("#{} will merge once checks pass", number),
+                    std::time::Instant::now(),
+                ));
+            }
+            Action::BranchDeleted(branch) => {
+                self.flash_message = Some((
+                    format!("PR merged, deleted {}", branch),
+                    std::time::Instant::now(),
+                ));
+            }
+            Action::PrReverted(number) => {
+                self.flash_message = Some((
+                    format!("Opened revert PR #{}", number),
+                    std::time::Instant::now(),
+                ));
+            }
             Action::PrClosed => {
                 self.flash_message = Some(("PR closed.".to_string(), std::time::Instant::now()));
                 let _ = self.action_tx.send(Action::Back);
@@ -1445,30 +4791,386 @@ impl App {
                 let _ = self.action_tx.send(Action::Refresh);
             }
             Action::CommentPosted => {
+                self.stats.comments += 1;
                 self.flash_message =
                     Some(("Comment posted.".to_string(), std::time::Instant::now()));
+                if self.screen == Screen::PrDetail {
+                    if let (Some((owner, repo)), Some(pr)) = (&self.current_repo, &self.current_pr)
+                    {
+                        self.spawn_load_pr_comments(
+                            owner.clone(),
+                            repo.clone(),
+                            pr.number,
+                            self.load_id,
+                        );
+                    }
+                }
+            }
+            Action::StartQuickComment => {
+                if self.current_pr.is_some() {
+                    self.comment_input = self
+                        .current_pr_key()
+                        .and_then(|key| self.comment_drafts.remove(&key))
+                        .unwrap_or_default();
+                    self.input_mode = InputMode::Comment;
+                }
+            }
+            Action::QuickCommentInput(c) => self.comment_input.push(c),
+            Action::QuickCommentBackspace => {
+                self.comment_input.pop();
+            }
+            Action::SubmitQuickComment => {
+                self.input_mode = InputMode::Normal;
+                let body = std::mem::take(&mut self.comment_input);
+                if body.trim().is_empty() {
+                    return;
+                }
+                if let (Some((owner, repo)), Some(pr)) = (&self.current_repo, &self.current_pr) {
+                    self.spawn_comment(owner.clone(), repo.clone(), pr.number, body, None);
+                }
+            }
+            Action::CancelQuickComment => {
+                self.input_mode = InputMode::Normal;
+                let body = std::mem::take(&mut self.comment_input);
+                if let (false, Some(key)) = (body.trim().is_empty(), self.current_pr_key()) {
+                    self.comment_drafts.insert(key, body);
+                    self.flash_message = Some((
+                        "Draft kept; c picks it up again.".to_string(),
+                        std::time::Instant::now(),
+                    ));
+                }
+            }
+            Action::StartFindCommit => {
+                self.find_query.clear();
+                self.input_mode = InputMode::FindCommit;
+            }
+            Action::FindCommitInput(c) => self.find_query.push(c),
+            Action::FindCommitBackspace => {
+                self.find_query.pop();
+            }
+            Action::SubmitFindCommit => {
+                self.input_mode = InputMode::Normal;
+                self.find_query = self.find_query.trim().to_string();
+                if self.find_query.is_empty() {
+                    return;
+                }
+                let repos: Vec<(String, String)> = if self.repos.is_empty() {
+                    cache::read::<Vec<Repository>>(&format!("{}_repos", self.forge_name))
+                        .unwrap_or_default()
+                } else {
+                    self.repos.clone()
+                }
+                .iter()
+                .map(|r| (r.owner.clone(), r.name.clone()))
+                .collect();
+
+                // Cached commits answer straight away; the refresh fills in the rest
+                let query = self.find_query.clone();
+                let cached: Vec<CommitHit> = repos
+                    .iter()
+                    .flat_map(|(owner, repo)| {
+                        let key = commits_cache_key(&self.forge_name, owner, repo);
+                        cache::read::<Vec<Commit>>(&key)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .filter(|c| commit_matches(c, &query))
+                            .map(|commit| CommitHit {
+                                owner: owner.clone(),
+                                repo: repo.clone(),
+                                commit,
+                            })
+                    })
+                    .collect();
+                self.commit_hits.clear();
+                self.merge_commit_hits(cached);
+                self.flash_message = Some((
+                    format!("Searching {} repos for \"{}\"...", repos.len(), query),
+                    std::time::Instant::now(),
+                ));
+                self.loading = true;
+                self.spawn_find_commit(query, repos);
+            }
+            Action::StartJump => {
+                self.jump_input.clear();
+                self.input_mode = InputMode::JumpToNumber;
+            }
+            Action::JumpInput(c) => self.jump_input.push(c),
+            Action::JumpBackspace => {
+                self.jump_input.pop();
+            }
+            Action::SubmitJump => {
+                self.input_mode = InputMode::Normal;
+                let number = std::mem::take(&mut self.jump_input).parse::<u64>();
+                if let (Ok(number), Some((owner, repo))) = (number, self.current_repo.clone()) {
+                    self.load_id += 1;
+                    self.loading = true;
+                    self.spawn_jump(owner, repo, number, self.load_id);
+                }
+            }
+            Action::JumpedToPr(pr, load_id) => {
+                if load_id == self.load_id {
+                    // Followed from another PR's `#123`
+                    if self.screen == Screen::PrDetail
+                        && self
+                            .current_pr
+                            .as_ref()
+                            .is_some_and(|p| p.number != pr.number)
+                    {
+                        self.leave_pr_for(NavEntry {
+                            screen: Screen::PrDetail,
+                            repo: self.current_repo.clone(),
+                            pr: Some(pr.number),
+                            ..NavEntry::default()
+                        });
+                    }
+                    if let Some((owner, repo)) = self.current_repo.clone() {
+                        self.spawn_load_pr_parts(owner, repo, pr.number, load_id);
+                    }
+                    self.update(Action::PrDetailLoaded(pr, load_id));
+                }
+            }
+            Action::JumpedToIssue(issue, load_id) => {
+                if load_id == self.load_id {
+                    self.loading = false;
+                    // Followed from a PR's `#123`: issues show on the repo's tab
+                    if self.screen == Screen::PrDetail {
+                        self.close_pr();
+                        self.nav_stack.clear();
+                        self.nav_forward.clear();
+                        self.scroll_offset = 0;
+                        self.screen = Screen::RepoView;
+                    }
+                    self.repo_tab = RepoTab::Issues;
+                    // Not on the loaded page: list it first so it can be selected
+                    self.issue_index =
+                        match self.issues.iter().position(|i| i.number == issue.number) {
+                            Some(index) => index,
+                            None => {
+                                self.issues.insert(0, issue);
+                                0
+                            }
+                        };
+                }
+            }
+            Action::CommitHitsLoaded(query, hits) => {
+                if query != self.find_query {
+                    return;
+                }
+                self.loading = false;
+                self.merge_commit_hits(hits);
+                if self.commit_hits.is_empty() {
+                    self.flash_message = Some((
+                        format!("No recent commit matches \"{}\".", query),
+                        std::time::Instant::now(),
+                    ));
+                }
+            }
+            Action::AddReviewComment(comment) => {
+                if let Some(key) = self.current_pr_key() {
+                    let drafts = self.review_drafts.entry(key).or_default();
+                    drafts.push(comment);
+                    let msg = format!("{} comment(s) pending; R submits the review.", drafts.len());
+                    self.flash_message = Some((msg, std::time::Instant::now()));
+                }
             }
             Action::ReviewSubmitted => {
+                self.stats.reviews += 1;
+                if let Some(key) = self.current_pr_key() {
+                    self.review_drafts.remove(&key);
+                }
                 self.flash_message =
                     Some(("Review submitted.".to_string(), std::time::Instant::now()));
+                if let (Some((owner, repo)), Some(pr)) = (&self.current_repo, &self.current_pr) {
+                    self.spawn_load_pr_reviews(
+                        owner.clone(),
+                        repo.clone(),
+                        pr.number,
+                        self.load_id,
+                    );
+                }
             }
 
             // Editor suspend - handled in main loop
             Action::SuspendForEditor(_) => {}
+            Action::SuspendForEditFile(_) => {}
+            Action::SuspendForGit(_) => {}
 
-            Action::Error(msg) => {
-                self.loading = false;
-                self.error = Some(msg);
+            Action::ShowInsights => {
+                if let Some((owner, repo)) = self.current_repo.clone() {
+                    self.loading = true;
+                    self.spawn_load_repo_stats(owner, repo);
+                }
+            }
+            Action::RepoStatsLoaded(owner, repo, stats) => {
+                if self.current_repo.as_ref() != Some(&(owner, repo)) {
+                    return;
+                }
+                self.repo_stats = Some(stats);
+                // A refresh landing after the popup closed leaves it closed
+                if self.loading && self.input_mode == InputMode::Normal {
+                    self.loading = false;
+                    self.input_mode = InputMode::Insights;
+                }
             }
-            Action::None => {}
-        }
 
-        // Clear flash messages after 3 seconds
-        if let Some((_, instant)) = &self.flash_message {
-            if instant.elapsed() > std::time::Duration::from_secs(3) {
-                self.flash_message = None;
+            Action::TaskFinished(id) => {
+                self.tasks.borrow_mut().remove(&id);
             }
-        }
+            Action::DismissAlerts => self.alerts.clear(),
+            Action::ShowLogs => {
+                if self.screen != Screen::Logs {
+                    self.push_nav(NavEntry {
+                        screen: Screen::Logs,
+                        repo: None,
+                        pr: None,
+                        commit: None,
+                        scroll_offset: 0,
+                    });
+                    self.screen = Screen::Logs;
+                }
+                self.log_size = 0;
+                self.read_logs();
+                self.scroll_offset = self.max_scroll_offset();
+            }
+            Action::ShowTasks => {
+                let tasks = self.tasks.borrow();
+                if tasks.is_empty() {
+                    drop(tasks);
+                    self.flash_message =
+                        Some(("Nothing running.".to_string(), std::time::Instant::now()));
+                    return;
+                }
+                // Longest running first: those are the ones worth cancelling
+                let mut rows: Vec<(&u64, &Task)> = tasks.iter().collect();
+                rows.sort_by_key(|(_, task)| task.started_at);
+                self.popup_items = rows
+                    .iter()
+                    .map(|(_, task)| {
+                        format!("{} ({}s)", task.label, task.started_at.elapsed().as_secs())
+                    })
+                    .collect();
+                self.task_rows = rows.iter().map(|(&id, _)| id).collect();
+                drop(tasks);
+                self.popup_title = "Cancel Task".to_string();
+                self.popup_index = 0;
+                self.input_mode = InputMode::SelectPopup;
+            }
+            Action::ShowOrgSelect => {
+                self.loading = true;
+                let tx = self.action_tx.clone();
+                let forge = Arc::clone(&self.forge);
+                self.spawn_task("Load organizations", async move {
+                    match forge.list_orgs().await {
+                        Ok(orgs) => tx.send(Action::OrgsLoaded(orgs)).ok(),
+                        Err(e) => tx.send(Action::Error(e)).ok(),
+                    };
+                });
+            }
+            Action::OrgsLoaded(orgs) => {
+                self.loading = false;
+                self.orgs = orgs;
+                self.popup_title = "Repositories Of".to_string();
+                self.popup_items = std::iter::once("Your repositories".to_string())
+                    .chain(self.orgs.iter().cloned())
+                    .collect();
+                // Start on the current scope so Enter keeps it
+                self.popup_index = self
+                    .repo_scope
+                    .as_ref()
+                    .and_then(|scope| self.orgs.iter().position(|o| o == scope))
+                    .map_or(0, |i| i + 1);
+                self.input_mode = InputMode::SelectPopup;
+            }
+
+            Action::MutationQueued(mutation) => {
+                self.loading = false;
+                self.go_offline();
+                let what = mutation.describe();
+                self.outbox.push(mutation);
+                outbox::save(&self.forge_name, &self.outbox);
+                self.flash_message = Some((
+                    format!("Offline: queued {} ({} waiting)", what, self.outbox.len()),
+                    std::time::Instant::now(),
+                ));
+            }
+            Action::OutboxSent(mutation) => {
+                self.flash_message = Some((
+                    format!("Sent queued {}.", mutation.describe()),
+                    std::time::Instant::now(),
+                ));
+            }
+            Action::Online => {
+                let was_offline = std::mem::replace(&mut self.offline, false);
+                let queued = std::mem::take(&mut self.outbox);
+                outbox::save(&self.forge_name, &self.outbox);
+                if !queued.is_empty() {
+                    self.flash_message = Some((
+                        format!("Back online: sending {} queued change(s)", queued.len()),
+                        std::time::Instant::now(),
+                    ));
+                } else if was_offline {
+                    self.flash_message =
+                        Some(("Back online.".to_string(), std::time::Instant::now()));
+                }
+                for mutation in queued {
+                    self.spawn_mutation(mutation.clone(), Action::OutboxSent(mutation), None);
+                }
+                if was_offline {
+                    self.update(Action::Refresh);
+                }
+            }
+            Action::NotFound(missing) => {
+                self.loading = false;
+                self.spawn_locate_repo(missing);
+            }
+            Action::RepoLocated(missing, located) => {
+                let moved = located
+                    .as_ref()
+                    .ok()
+                    .filter(|(owner, repo)| (owner, repo) != (&missing.owner, &missing.repo))
+                    .cloned();
+                if let Some((owner, repo)) = moved {
+                    self.follow_move(missing, owner, repo);
+                    return;
+                }
+                // A remembered name that still holds, or one whose own load
+                // will tell if it's gone
+                let Some(error) = missing.error else {
+                    return;
+                };
+                match located {
+                    // The repo is there, so the 404 was about something else
+                    Ok(_) if missing.number.is_none() => self.update(Action::Error(error)),
+                    Ok(_) | Err(GritError::NotFound(_)) => {
+                        self.update(Action::ShowConfirm(ConfirmAction::ForgetMissing {
+                            owner: missing.owner,
+                            repo: missing.repo,
+                            number: missing.number,
+                        }));
+                    }
+                    Err(err) => self.update(Action::Error(err)),
+                }
+            }
+            Action::Error(err) => {
+                self.loading = false;
+                if let GritError::Network(_) = err {
+                    self.go_offline();
+                } else {
+                    if let Some(wait) = err.retry_after() {
+                        self.retry_at = Some(std::time::Instant::now() + wait);
+                    }
+                    self.error = Some(err.with_hint());
+                }
+            }
+            Action::None => {}
+        }
+
+        // Clear flash messages after 3 seconds
+        if let Some((_, instant)) = &self.flash_message {
+            if instant.elapsed() > std::time::Duration::from_secs(3) {
+                self.flash_message = None;
+            }
+        }
     }
 
     fn recompute_search_matches(&mut self) {
@@ -1506,6 +5208,32 @@ impl App {
                         .map(|(i, _)| i)
                         .collect();
                 }
+                HomeSection::Assigned | HomeSection::Mentions | HomeSection::Saved => {
+                    let items = match self.home_section {
+                        HomeSection::Assigned => &self.assigned,
+                        HomeSection::Mentions => &self.mentions,
+                        _ => &self.saved,
+                    };
+                    self.search.match_indices = items
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, r)| {
+                            r.title.to_lowercase().contains(&query)
+                                || r.repo_name.to_lowercase().contains(&query)
+                                || r.author.to_lowercase().contains(&query)
+                        })
+                        .map(|(i, _)| i)
+                        .collect();
+                }
+                HomeSection::Recent => {
+                    self.search.match_indices = self
+                        .recent
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, r)| r.label().to_lowercase().contains(&query))
+                        .map(|(i, _)| i)
+                        .collect();
+                }
             },
             Screen::RepoList => {
                 self.search.match_indices = self
@@ -1513,8 +5241,7 @@ impl App {
                     .iter()
                     .enumerate()
                     .filter(|(_, r)| {
-                        r.name.to_lowercase().contains(&query)
-                            || r.owner.to_lowercase().contains(&query)
+                        r.full_path().to_lowercase().contains(&query)
                             || r.description
                                 .as_deref()
                                 .unwrap_or("")
@@ -1524,6 +5251,15 @@ impl App {
                     .map(|(i, _)| i)
                     .collect();
             }
+            Screen::Browse => {
+                self.search.match_indices = self
+                    .browse_column_names()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, name)| name.to_lowercase().contains(&query))
+                    .map(|(i, _)| i)
+                    .collect();
+            }
             Screen::RepoView => match self.repo_tab {
                 RepoTab::PullRequests => {
                     self.search.match_indices = self
@@ -1579,22 +5315,45 @@ impl App {
             },
             Screen::PrDetail => {
                 self.search.content_matches.clear();
-                if let Some(pr) = &self.current_pr {
-                    let body = pr.body.as_deref().unwrap_or("");
-                    for (line_idx, line) in body.lines().enumerate() {
-                        let lower = line.to_lowercase();
-                        let mut start = 0;
-                        while let Some(pos) = lower[start..].find(&query) {
-                            let byte_start = start + pos;
-                            let byte_end = byte_start + query.len();
-                            self.search
-                                .content_matches
-                                .push((line_idx, byte_start, byte_end));
-                            start = byte_end;
-                        }
+                self.search.wrap_width = self.detail_width.get();
+                // The diffs count as lines after the detail, so n/N runs on
+                // into them
+                let detail = self.pr_detail_lines();
+                let diffs = self
+                    .pr_files
+                    .iter()
+                    .flat_map(|f| f.patch.as_deref().unwrap_or("").lines());
+                let document = detail.iter().map(|(_, l)| l.as_str()).chain(diffs);
+                for (line_idx, line) in document.enumerate() {
+                    let lower = line.to_lowercase();
+                    let mut start = 0;
+                    while let Some(pos) = lower[start..].find(&query) {
+                        let byte_start = start + pos;
+                        let byte_end = byte_start + query.len();
+                        self.search
+                            .content_matches
+                            .push((line_idx, byte_start, byte_end));
+                        start = byte_end;
                     }
                 }
             }
+            Screen::DiffView => {
+                // A search from PR detail is replaced, not stepped through
+                self.search.content_matches.clear();
+                self.search.match_indices = self
+                    .pr_files
+                    .get(self.file_index)
+                    .and_then(|f| f.patch.as_deref())
+                    .map(|patch| {
+                        patch
+                            .lines()
+                            .enumerate()
+                            .filter(|(_, l)| l.to_lowercase().contains(&query))
+                            .map(|(i, _)| i)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+            }
             Screen::CommitDetail => {
                 self.search.content_matches.clear();
                 if let Some(commit) = &self.current_commit {
@@ -1615,6 +5374,10 @@ impl App {
                         line_idx += 1;
                     }
                     line_idx += 1; // blank after message
+                    let checks = self.current_commit_checks().len();
+                    if checks > 0 {
+                        line_idx += checks + 2; // "Checks:", one per check, blank
+                    }
                     for file in &commit.files {
                         line_idx += 1; // file header
                         if let Some(patch) = &file.patch {
@@ -1636,34 +5399,419 @@ impl App {
                     }
                 }
             }
+            Screen::Logs => {
+                self.search.content_matches.clear();
+                for (line_idx, line) in self.log_lines.iter().enumerate() {
+                    let lower = line.to_lowercase();
+                    let mut start = 0;
+                    while let Some(pos) = lower[start..].find(&query) {
+                        let byte_start = start + pos;
+                        let byte_end = byte_start + query.len();
+                        self.search
+                            .content_matches
+                            .push((line_idx, byte_start, byte_end));
+                        start = byte_end;
+                    }
+                }
+            }
         }
 
         self.search.current_match = 0;
     }
 
+    /// Find PR detail matches again once a resize has rewrapped its lines,
+    /// staying on the same match
+    fn rewrap_search_matches(&mut self) {
+        if self.screen != Screen::PrDetail
+            || self.search.query.is_empty()
+            || self.search.wrap_width == self.detail_width.get()
+        {
+            return;
+        }
+        let current = self.search.current_match;
+        self.recompute_search_matches();
+        self.search.current_match =
+            current.min(self.search.content_matches.len().saturating_sub(1));
+    }
+
     fn jump_to_current_match(&mut self) {
         if let Some(&idx) = self.search.match_indices.get(self.search.current_match) {
             match self.screen {
                 Screen::Home => match self.home_section {
                     HomeSection::ReviewRequests => self.review_index = idx,
                     HomeSection::MyPrs => self.my_pr_index = idx,
+                    HomeSection::Assigned => self.assigned_index = idx,
+                    HomeSection::Mentions => self.mentions_index = idx,
+                    HomeSection::Saved => self.saved_index = idx,
+                    HomeSection::Recent => self.recent_index = idx,
                 },
                 Screen::RepoList => self.repo_index = idx,
+                Screen::Browse => self.browse_select(|_, _| idx),
                 Screen::RepoView => match self.repo_tab {
                     RepoTab::PullRequests => self.pr_index = idx,
                     RepoTab::Issues => self.issue_index = idx,
                     RepoTab::Commits => self.commit_index = idx,
                     RepoTab::Actions => self.action_index = idx,
                 },
+                Screen::DiffView => self.diff_cursor = idx,
                 _ => {}
             }
         }
     }
 
     fn jump_to_content_match(&mut self) {
-        if let Some(&(line_idx, _, _)) = self.search.content_matches.get(self.search.current_match)
+        let Some(&(line_idx, _, _)) = self.search.content_matches.get(self.search.current_match)
+        else {
+            return;
+        };
+        // A PR search moves between the detail and the diff view as it goes
+        if matches!(self.screen, Screen::PrDetail | Screen::DiffView) {
+            if let Some((file, line)) = self.diff_position(line_idx) {
+                self.file_index = file;
+                self.diff_cursor = line;
+                self.screen = Screen::DiffView;
+                return;
+            }
+            self.screen = Screen::PrDetail;
+        }
+        self.scroll_offset = line_idx.saturating_sub(5);
+    }
+
+    /// File and patch line of a PR search line past the detail lines, which
+    /// the diffs follow in file order
+    fn diff_position(&self, line_idx: usize) -> Option<(usize, usize)> {
+        let mut rest = line_idx.checked_sub(self.pr_detail_lines().len())?;
+        for (index, file) in self.pr_files.iter().enumerate() {
+            let len = file.patch.as_deref().map_or(0, |p| p.lines().count());
+            if rest < len {
+                return Some((index, rest));
+            }
+            rest -= len;
+        }
+        None
+    }
+
+    /// Section of the PR the current search match is in, for the match counter
+    pub fn search_section(&self) -> Option<String> {
+        if !matches!(self.screen, Screen::PrDetail | Screen::DiffView)
+            || !self.search.match_indices.is_empty()
+        {
+            return None;
+        }
+        let &(line_idx, _, _) = self.search.content_matches.get(self.search.current_match)?;
+        if let Some((file, _)) = self.diff_position(line_idx) {
+            return Some(format!("diff {}", self.pr_files[file].filename));
+        }
+        let lines = self.pr_detail_lines();
+        let section = lines
+            .iter()
+            .take(line_idx + 1)
+            .rev()
+            .find_map(|(kind, text)| (*kind == PrLine::Section).then_some(text));
+        Some(match section {
+            Some(text) => {
+                let name = text.trim_start_matches("── ");
+                name.split(" (").next().unwrap_or(name).to_string()
+            }
+            None => "Description".to_string(),
+        })
+    }
+
+    /// Layout of the diff view's file, worked out again only once its patch
+    /// changes
+    pub fn diff_layout(&self) -> Option<Rc<DiffLayout>> {
+        let patch = self.pr_files.get(self.file_index)?.patch.as_deref()?;
+        let mut layout = self.diff_layout.borrow_mut();
+        if layout.patch != patch {
+            *layout = Rc::new(DiffLayout {
+                patch: patch.to_string(),
+                numbers: patch_line_numbers(patch),
+                rows: split_rows(patch),
+            });
+        }
+        Some(Rc::clone(&layout))
+    }
+
+    /// Lines in the diff view's file
+    fn diff_len(&self) -> usize {
+        self.diff_layout().map_or(0, |l| l.numbers.len())
+    }
+
+    /// Layout of the diff view's file, when it was drawn side by side
+    fn diff_split_rows(&self) -> Option<Rc<DiffLayout>> {
+        if !self.diff_split_shown.get() {
+            return None;
+        }
+        self.diff_layout()
+    }
+
+    /// Move the diff cursor by `delta` rows as drawn: patch lines when
+    /// unified, paired rows when side by side
+    fn diff_step(&mut self, delta: isize) {
+        match self.diff_split_rows() {
+            Some(layout) => {
+                let rows = &layout.rows;
+                let row = rows
+                    .iter()
+                    .position(|r| r.contains(self.diff_cursor))
+                    .unwrap_or(0);
+                let target = row
+                    .saturating_add_signed(delta)
+                    .min(rows.len().saturating_sub(1));
+                if let Some(r) = rows.get(target) {
+                    self.diff_cursor = r.line();
+                }
+            }
+            None => {
+                let max = self.diff_len().saturating_sub(1);
+                self.diff_cursor = self.diff_cursor.saturating_add_signed(delta).min(max);
+            }
+        }
+    }
+
+    /// Old/new line numbers under the diff view cursor; `None` on hunk headers
+    pub fn diff_line(&self) -> Option<DiffLine> {
+        self.diff_layout()?
+            .numbers
+            .get(self.diff_cursor)
+            .copied()
+            .flatten()
+    }
+
+    /// Spawn `task`, listed under `label` until it finishes or is cancelled
+    fn spawn_task<F>(&self, label: impl Into<String>, task: F)
+    where
+        F: std::future::Future + Send + 'static,
+        F::Output: Send,
+    {
+        self.spawn_tracked(label.into(), None, task);
+    }
+
+    /// Spawn a load for `load_id`. Its result is dropped once the user has
+    /// moved on, so it's aborted then too rather than using up rate limit.
+    fn spawn_load<F>(&self, label: &str, load_id: u64, task: F)
+    where
+        F: std::future::Future + Send + 'static,
+        F::Output: Send,
+    {
+        self.abort_stale_loads();
+        self.spawn_tracked(label.to_string(), Some(load_id), task);
+    }
+
+    /// Abort loads for a `load_id` that navigation or a refresh superseded
+    fn abort_stale_loads(&self) {
+        let current = self.load_id;
+        self.tasks.borrow_mut().retain(|_, task| {
+            let stale = task.load_id.is_some_and(|id| id < current);
+            if stale {
+                task.handle.abort();
+            }
+            !stale
+        });
+    }
+
+    fn spawn_tracked<F>(&self, label: String, load_id: Option<u64>, task: F)
+    where
+        F: std::future::Future + Send + 'static,
+        F::Output: Send,
+    {
+        let id = self.next_task_id.get();
+        self.next_task_id.set(id + 1);
+        let tx = self.action_tx.clone();
+        let handle = tokio::spawn(async move {
+            task.await;
+            tx.send(Action::TaskFinished(id)).ok();
+        });
+        self.tasks.borrow_mut().insert(
+            id,
+            Task {
+                label,
+                started_at: std::time::Instant::now(),
+                load_id,
+                handle: handle.abort_handle(),
+            },
+        );
+    }
+
+    /// Open the editor for a comment or review, offering the configured
+    /// snippets to start from first. A saved draft is picked up instead.
+    fn compose(&self, ctx: EditorContext) -> Action {
+        let has_draft = || {
+            drafts::key(&self.forge_name, &ctx)
+                .and_then(|key| drafts::load(&key))
+                .is_some()
+        };
+        if self.snippets.is_empty() || has_draft() {
+            Action::SuspendForEditor(ctx)
+        } else {
+            Action::ShowSnippetSelect(ctx)
+        }
+    }
+
+    /// The repo selected in the list, or the one being viewed
+    fn target_repo(&self) -> Option<(String, String)> {
+        match self.screen {
+            Screen::RepoList => self
+                .repos
+                .get(self.repo_index)
+                .map(|r| (r.owner.clone(), r.name.clone())),
+            _ => self.current_repo.clone(),
+        }
+    }
+
+    /// Whether owner/repo is starred, if known this session
+    pub fn is_starred(&self, owner: &str, repo: &str) -> Option<bool> {
+        self.starred.get(&format!("{}/{}", owner, repo)).copied()
+    }
+
+    /// Record a star change and move the repo's star count with it
+    fn apply_star(&mut self, owner: &str, repo: &str, starred: bool) {
+        let before = self.starred.insert(format!("{}/{}", owner, repo), starred);
+        if before == Some(starred) {
+            return;
+        }
+        for r in self
+            .repos
+            .iter_mut()
+            .filter(|r| r.owner == owner && r.name == repo)
+        {
+            r.stars = if starred {
+                r.stars + 1
+            } else {
+                r.stars.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Checks of the viewed commit, once loaded
+    pub fn current_commit_checks(&self) -> &[CommitCheck] {
+        match (&self.current_commit, &self.commit_checks) {
+            (Some(commit), Some((sha, checks))) if *sha == commit.sha => checks,
+            _ => &[],
+        }
+    }
+
+    /// Line comments queued on the open PR
+    pub fn pending_review_comments(&self) -> &[ReviewComment] {
+        self.current_pr_key()
+            .and_then(|key| self.review_drafts.get(&key))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    fn current_pr_key(&self) -> Option<(String, String, u64)> {
+        let (owner, repo) = self.current_repo.as_ref()?;
+        let pr = self.current_pr.as_ref()?;
+        Some((owner.clone(), repo.clone(), pr.number))
+    }
+
+    /// Runs the Actions tab keeps polling until they finish
+    fn watched_runs(&self) -> usize {
+        if self.screen != Screen::RepoView || self.repo_tab != RepoTab::Actions {
+            return 0;
+        }
+        self.action_runs
+            .iter()
+            .filter(|r| matches!(r.status, ActionStatus::Queued | ActionStatus::InProgress))
+            .count()
+    }
+
+    /// What quitting now would throw away, in the order the quit popup lists it
+    pub fn pending_work(&self) -> Vec<PendingWork> {
+        let mut items: Vec<PendingWork> = self
+            .comment_drafts
+            .keys()
+            .map(|(owner, repo, number)| PendingWork::Draft {
+                owner: owner.clone(),
+                repo: repo.clone(),
+                number: *number,
+            })
+            .collect();
+        items.extend(
+            self.review_drafts
+                .iter()
+                .map(
+                    |((owner, repo, number), comments)| PendingWork::ReviewComments {
+                        owner: owner.clone(),
+                        repo: repo.clone(),
+                        number: *number,
+                        count: comments.len(),
+                    },
+                ),
+        );
+        let runs = self.watched_runs();
+        if runs > 0 {
+            items.push(PendingWork::WatchedRuns(runs));
+        }
+        items
+    }
+
+    /// Whether the open PR's description or conversation differ from when it was opened
+    pub fn pr_changed(&self) -> bool {
+        self.current_pr.as_ref().is_some_and(|pr| {
+            self.pr_seen
+                .changed(pr.body.as_deref().unwrap_or(""), &self.pr_comments)
+        })
+    }
+
+    /// Point at "show changes" the first time a load brings something new
+    fn flag_pr_changes(&mut self, was_changed: bool) {
+        if !was_changed && self.pr_changed() {
+            self.flash_message = Some((
+                "PR updated since you last looked - w shows changes".to_string(),
+                std::time::Instant::now(),
+            ));
+        }
+    }
+
+    /// "drafts: 1, watched runs: 2" for the quit popup
+    pub fn quit_summary(&self) -> String {
+        let review_comments: usize = self.review_drafts.values().map(Vec::len).sum();
+        format!(
+            "drafts: {}, watched runs: {}",
+            self.comment_drafts.len() + review_comments,
+            self.watched_runs()
+        )
+    }
+
+    /// Re-fetch the first page of workflow runs while any of them is still
+    /// queued or running. Stops on its own once every visible run completes.
+    fn poll_action_runs_if_needed(&mut self) {
+        if self.screen != Screen::RepoView || self.repo_tab != RepoTab::Actions {
+            return;
+        }
+        let any_active = self
+            .action_runs
+            .iter()
+            .any(|r| matches!(r.status, ActionStatus::Queued | ActionStatus::InProgress));
+        if !any_active {
+            return;
+        }
+        if self
+            .actions_last_poll
+            .is_some_and(|t| t.elapsed() < ACTIONS_POLL_INTERVAL)
         {
-            self.scroll_offset = line_idx.saturating_sub(5);
+            return;
+        }
+        self.actions_last_poll = Some(std::time::Instant::now());
+        if let Some((owner, repo)) = &self.current_repo {
+            self.spawn_poll_action_runs(owner.clone(), repo.clone(), self.load_id);
+        }
+    }
+
+    /// Update existing rows in place by run id; runs not seen before are
+    /// prepended. The selection stays on the same run.
+    fn merge_polled_action_runs(&mut self, runs: Vec<ActionRun>) {
+        let mut new_runs = Vec::new();
+        for run in runs {
+            match self.action_runs.iter_mut().find(|r| r.id == run.id) {
+                Some(existing) => *existing = run,
+                None => new_runs.push(run),
+            }
+        }
+        if !new_runs.is_empty() {
+            self.action_index += new_runs.len();
+            new_runs.append(&mut self.action_runs);
+            self.action_runs = new_runs;
         }
     }
 
@@ -1671,1629 +5819,8277 @@ impl App {
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
         let cache_key = format!("{}_home", self.forge_name);
+        let forge_name = self.forge_name.clone();
+        let prefetch = self.ui_config.prefetch_limit();
 
         // Serve from cache immediately
-        if let Some(cached) = cache::read::<HomeData>(&cache_key) {
+        if let Some(cached) = self.cached::<HomeData>(&cache_key) {
             tx.send(Action::HomeLoaded {
                 review_requests: cached.review_requests,
                 my_prs: cached.my_prs,
+                assigned: cached.assigned,
+                mentions: cached.mentions,
                 load_id,
             })
             .ok();
         }
 
         // Background refresh
-        tokio::spawn(async move {
+        self.spawn_task("Load home", async move {
             let username = match forge.get_current_user().await {
                 Ok(u) => u,
                 Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
+                    tx.send(Action::Error(e)).ok();
                     return;
                 }
             };
 
-            let (review_result, my_prs_result) = tokio::join!(
-                forge.list_review_requests(&username),
-                forge.list_my_prs(&username)
+            let (prs_result, assigned_result, mentions_result) = tokio::join!(
+                forge.list_home_prs(&username),
+                forge.list_assigned_issues(&username),
+                forge.list_mentions(&username)
             );
 
-            match (review_result, my_prs_result) {
-                (Ok(review_requests), Ok(my_prs)) => {
+            match (prs_result, assigned_result, mentions_result) {
+                (Ok((review_requests, my_prs)), Ok(assigned), Ok(mentions)) => {
                     cache::write(
                         &cache_key,
                         &HomeData {
                             review_requests: review_requests.clone(),
                             my_prs: my_prs.clone(),
+                            assigned: assigned.clone(),
+                            mentions: mentions.clone(),
                         },
                     );
+                    let top: Vec<ReviewRequest> =
+                        review_requests.iter().take(prefetch).cloned().collect();
                     tx.send(Action::HomeLoaded {
                         review_requests,
                         my_prs,
+                        assigned,
+                        mentions,
                         load_id,
                     })
                     .ok();
+                    prefetch_pr_details(forge, &forge_name, top).await;
                 }
-                (Err(e), _) | (_, Err(e)) => {
-                    tx.send(Action::Error(e.to_string())).ok();
+                (Err(e), ..) | (_, Err(e), _) | (.., Err(e)) => {
+                    tx.send(Action::Error(e)).ok();
                 }
             }
         });
     }
 
-    fn spawn_load_repos(&self, load_id: u64) {
+    /// A page of the chosen `[searches]` entry
+    fn spawn_load_saved(&self, page: u32) {
+        let Some((name, query)) = self.searches.get(self.search_choice).cloned() else {
+            return;
+        };
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
-        let cache_key = format!("{}_repos", self.forge_name);
-
-        if let Some(cached) = cache::read::<Vec<Repository>>(&cache_key) {
-            tx.send(Action::ReposLoaded(cached, None, load_id)).ok();
-        }
-
-        tokio::spawn(async move {
-            match forge.list_repos(1).await {
-                Ok(PagedResult { items, total_count }) => {
-                    cache::write(&cache_key, &items);
-                    tx.send(Action::ReposLoaded(items, total_count, load_id))
+        self.spawn_task(format!("Search \"{}\"", name), async move {
+            match forge.search_issues(&query, page).await {
+                Ok(PagedResult { items, page_info }) => {
+                    tx.send(Action::SavedSearchLoaded(name, page, items, page_info))
                         .ok();
                 }
                 Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
+                    tx.send(Action::Error(e)).ok();
                 }
             }
         });
     }
 
-    fn spawn_load_prs(&self, owner: String, repo: String, load_id: u64) {
-        let tx = self.action_tx.clone();
-        let forge = Arc::clone(&self.forge);
-        let key = format!(
-            "prs_{}",
-            cache::forge_repo_key(&self.forge_name, &owner, &repo)
-        );
-
-        if let Some(cached) = cache::read::<Vec<PrSummary>>(&key) {
-            tx.send(Action::PrsLoaded(cached, None, load_id)).ok();
+    fn repos_cache_key(&self) -> String {
+        match &self.repo_scope {
+            Some(org) => format!("{}_org_{}_repos", self.forge_name, org.replace('/', "_")),
+            None => format!("{}_repos", self.forge_name),
         }
-
-        tokio::spawn(async move {
-            match forge.list_prs(&owner, &repo, 1).await {
-                Ok(PagedResult { items, total_count }) => {
-                    cache::write(&key, &items);
-                    tx.send(Action::PrsLoaded(items, total_count, load_id)).ok();
-                }
-                Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
-                }
-            }
-        });
     }
 
-    fn spawn_load_pr_detail(&self, owner: String, repo: String, number: u64, load_id: u64) {
+    fn spawn_load_repos(&self, load_id: u64) {
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
-        let key = format!(
-            "pr_{}_{}",
-            cache::forge_repo_key(&self.forge_name, &owner, &repo),
-            number
-        );
+        let cache_key = self.repos_cache_key();
+        let scope = self.repo_scope.clone();
 
-        if let Some(cached) = cache::read::<PullRequest>(&key) {
-            tx.send(Action::PrDetailLoaded(Box::new(cached), load_id))
+        if let Some(cached) = self.cached::<Vec<Repository>>(&cache_key) {
+            tx.send(Action::ReposLoaded(cached, PageInfo::default(), load_id))
                 .ok();
         }
 
-        tokio::spawn(async move {
-            match forge.get_pr(&owner, &repo, number).await {
-                Ok(pr) => {
-                    cache::write(&key, &pr);
-                    tx.send(Action::PrDetailLoaded(Box::new(pr), load_id)).ok();
+        self.spawn_load("Load repositories", load_id, async move {
+            let result = match &scope {
+                Some(org) => forge.list_org_repos(org, 1).await,
+                None => forge.list_repos(1).await,
+            };
+            match result {
+                Ok(PagedResult { items, page_info }) => {
+                    cache::write(&cache_key, &items);
+                    tx.send(Action::ReposLoaded(items, page_info, load_id)).ok();
                 }
                 Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
+                    tx.send(Action::Error(e)).ok();
                 }
             }
         });
     }
 
-    fn spawn_load_issues(&self, owner: String, repo: String, load_id: u64) {
+    fn prs_cache_key(&self, owner: &str, repo: &str) -> String {
+        format!(
+            "prs_{}",
+            cache::forge_repo_key(&self.forge_name, owner, repo)
+        )
+    }
+
+    fn spawn_load_prs(&self, owner: String, repo: String, load_id: u64) {
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
-        let key = format!(
-            "issues_{}",
-            cache::forge_repo_key(&self.forge_name, &owner, &repo)
-        );
-
-        if let Some(cached) = cache::read::<Vec<Issue>>(&key) {
-            tx.send(Action::IssuesLoaded(cached, None, load_id)).ok();
+        let filter = self.pr_filter;
+        let milestone = self.milestone_filter.clone();
+        // Filtered lists are cached apart, so the full one still marks what's new
+        let mut key = match filter {
+            PrFilter::All => self.prs_cache_key(&owner, &repo),
+            filter => format!("{}_{}", self.prs_cache_key(&owner, &repo), filter),
+        };
+        if let Some(milestone) = &milestone {
+            key = format!("{}_m{}", key, milestone.id);
+        }
+
+        if let Some(cached) = self.cached::<Vec<PrSummary>>(&key) {
+            tx.send(Action::PrsLoaded(cached, PageInfo::default(), load_id))
+                .ok();
         }
 
-        tokio::spawn(async move {
-            match forge.list_issues(&owner, &repo, 1).await {
-                Ok(PagedResult { items, total_count }) => {
+        self.spawn_load("Load pull requests", load_id, async move {
+            match forge
+                .list_prs(&owner, &repo, filter, milestone.as_ref(), 1)
+                .await
+            {
+                Ok(PagedResult { items, page_info }) => {
                     cache::write(&key, &items);
-                    tx.send(Action::IssuesLoaded(items, total_count, load_id))
-                        .ok();
+                    tx.send(Action::PrsLoaded(items, page_info, load_id)).ok();
+                }
+                Err(e @ GritError::NotFound(_)) => {
+                    tx.send(Action::NotFound(Missing {
+                        owner,
+                        repo,
+                        number: None,
+                        error: Some(e),
+                    }))
+                    .ok();
                 }
                 Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
+                    tx.send(Action::Error(e)).ok();
                 }
             }
         });
     }
 
-    fn spawn_load_commits(&self, owner: String, repo: String, load_id: u64) {
-        let tx = self.action_tx.clone();
-        let forge = Arc::clone(&self.forge);
-        let key = format!(
-            "commits_{}",
-            cache::forge_repo_key(&self.forge_name, &owner, &repo)
-        );
-
-        if let Some(cached) = cache::read::<Vec<Commit>>(&key) {
-            tx.send(Action::CommitsLoaded(cached, None, load_id)).ok();
+    /// Point the preview at the selected PR or issue: cached details show at
+    /// once, the forge is asked once the selection has rested there
+    fn preview_follow_selection(&mut self) {
+        if !self.preview.enabled || self.screen != Screen::RepoView {
+            return;
         }
-
-        tokio::spawn(async move {
-            match forge.list_commits(&owner, &repo, 1).await {
-                Ok(PagedResult { items, total_count }) => {
-                    cache::write(&key, &items);
-                    tx.send(Action::CommitsLoaded(items, total_count, load_id))
-                        .ok();
+        let Some((owner, repo)) = self.current_repo.clone() else {
+            return;
+        };
+        if self.preview.repo.as_ref() != Some(&(owner.clone(), repo.clone())) {
+            self.preview = PreviewState {
+                enabled: true,
+                repo: Some((owner.clone(), repo.clone())),
+                ..PreviewState::default()
+            };
+        }
+        let target = match self.repo_tab {
+            RepoTab::PullRequests => self.prs.get(self.pr_index).map(|pr| pr.number),
+            RepoTab::Issues => self.issues.get(self.issue_index).map(|i| i.number),
+            RepoTab::Commits | RepoTab::Actions => None,
+        };
+        let Some(target) = target.map(|number| (self.repo_tab, number)) else {
+            return;
+        };
+
+        if self.preview.target != Some(target) {
+            self.preview.target = Some(target);
+            self.preview.moved_at = Some(std::time::Instant::now());
+            let (tab, number) = target;
+            if tab == RepoTab::PullRequests && !self.preview.prs.contains_key(&number) {
+                let key = pr_cache_key(&self.forge_name, &owner, &repo, number);
+                if let Some(pr) = cache::read::<PullRequest>(&key) {
+                    self.preview.prs.insert(number, pr);
                 }
-                Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
+            } else if tab == RepoTab::Issues && !self.preview.issue_bodies.contains_key(&number) {
+                let key = issue_body_cache_key(&self.forge_name, &owner, &repo, number);
+                if let Some(body) = cache::read::<Option<String>>(&key) {
+                    self.preview.issue_bodies.insert(number, body);
                 }
             }
-        });
+            return;
+        }
+        if self
+            .preview
+            .moved_at
+            .is_some_and(|t| t.elapsed() >= BROWSE_SETTLE)
+        {
+            self.preview.moved_at = None;
+            if self.preview.fetched.insert(target) {
+                self.spawn_load_preview(owner, repo, target);
+            }
+        }
     }
 
-    fn spawn_load_action_runs(&self, owner: String, repo: String, load_id: u64) {
+    fn spawn_load_preview(&self, owner: String, repo: String, (tab, number): (RepoTab, u64)) {
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
-        let key = format!(
-            "actions_{}",
-            cache::forge_repo_key(&self.forge_name, &owner, &repo)
-        );
-
-        if let Some(cached) = cache::read::<Vec<ActionRun>>(&key) {
-            tx.send(Action::ActionRunsLoaded(cached, None, load_id))
-                .ok();
-        }
-
-        tokio::spawn(async move {
-            match forge.list_action_runs(&owner, &repo, 1).await {
-                Ok(PagedResult { items, total_count }) => {
-                    cache::write(&key, &items);
-                    tx.send(Action::ActionRunsLoaded(items, total_count, load_id))
+        let forge_name = self.forge_name.clone();
+
+        // Previews are best-effort: a failure keeps the cached copy, if any
+        self.spawn_task("Load preview", async move {
+            if tab == RepoTab::PullRequests {
+                if let Ok(pr) = forge.get_pr(&owner, &repo, number).await {
+                    cache::write(&pr_cache_key(&forge_name, &owner, &repo, number), &pr);
+                    tx.send(Action::PreviewPrLoaded(owner, repo, Box::new(pr)))
                         .ok();
                 }
-                Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
-                }
+            } else if let Ok(body) = forge.get_issue_body(&owner, &repo, number).await {
+                let key = issue_body_cache_key(&forge_name, &owner, &repo, number);
+                cache::write(&key, &body);
+                tx.send(Action::PreviewIssueLoaded(owner, repo, number, body))
+                    .ok();
             }
         });
     }
 
-    // Pagination: spawn methods for loading next pages (no cache)
-
-    fn spawn_load_repos_page(&self, page: u32, load_id: u64) {
+    /// Not tied to `load_id`: the result is matched against the highlighted repo instead
+    fn spawn_browse_prs(&self, owner: String, repo: String) {
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
-        tokio::spawn(async move {
-            match forge.list_repos(page).await {
-                Ok(PagedResult { items, total_count }) => {
-                    tx.send(Action::ReposAppended(items, total_count, load_id))
-                        .ok();
+        let key = self.prs_cache_key(&owner, &repo);
+
+        self.spawn_task("Load pull requests", async move {
+            match forge.list_prs(&owner, &repo, PrFilter::All, None, 1).await {
+                Ok(PagedResult { items, .. }) => {
+                    cache::write(&key, &items);
+                    tx.send(Action::BrowsePrsLoaded(owner, repo, items)).ok();
                 }
                 Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
+                    tx.send(Action::Error(e)).ok();
                 }
             }
         });
     }
 
-    fn spawn_load_prs_page(&self, owner: String, repo: String, page: u32, load_id: u64) {
+    fn spawn_load_pr_detail(&self, owner: String, repo: String, number: u64, load_id: u64) {
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
-        tokio::spawn(async move {
-            match forge.list_prs(&owner, &repo, page).await {
-                Ok(PagedResult { items, total_count }) => {
-                    tx.send(Action::PrsAppended(items, total_count, load_id))
-                        .ok();
+        let key = pr_cache_key(&self.forge_name, &owner, &repo, number);
+
+        if let Some(cached) = self.cached::<PullRequest>(&key) {
+            tx.send(Action::PrDetailLoaded(Box::new(cached), load_id))
+                .ok();
+        }
+
+        self.spawn_load_pr_parts(owner.clone(), repo.clone(), number, load_id);
+
+        self.spawn_load("Load PR", load_id, async move {
+            match forge.get_pr(&owner, &repo, number).await {
+                Ok(pr) => {
+                    cache::write(&key, &pr);
+                    tx.send(Action::PrDetailLoaded(Box::new(pr), load_id)).ok();
+                }
+                Err(e @ GritError::NotFound(_)) => {
+                    tx.send(Action::NotFound(Missing {
+                        owner,
+                        repo,
+                        number: Some(number),
+                        error: Some(e),
+                    }))
+                    .ok();
                 }
                 Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
+                    tx.send(Action::Error(e)).ok();
                 }
             }
         });
     }
 
-    fn spawn_load_issues_page(&self, owner: String, repo: String, page: u32, load_id: u64) {
+    /// Everything on the PR detail screen besides the PR itself
+    fn spawn_load_pr_parts(&self, owner: String, repo: String, number: u64, load_id: u64) {
+        self.spawn_load_pr_comments(owner.clone(), repo.clone(), number, load_id);
+        self.spawn_load_pr_reviews(owner.clone(), repo.clone(), number, load_id);
+        self.spawn_load_pr_files(owner.clone(), repo.clone(), number, load_id);
+        self.spawn_load_pr_commits(owner.clone(), repo.clone(), number, load_id);
+        self.spawn_load_review_threads(owner.clone(), repo.clone(), number, load_id);
+        self.spawn_load_review_progress(owner, repo, number, load_id);
+    }
+
+    /// Open `number` as a PR, or as an issue when no PR has that number
+    fn spawn_jump(&self, owner: String, repo: String, number: u64, load_id: u64) {
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
-        tokio::spawn(async move {
-            match forge.list_issues(&owner, &repo, page).await {
-                Ok(PagedResult { items, total_count }) => {
-                    tx.send(Action::IssuesAppended(items, total_count, load_id))
-                        .ok();
-                }
-                Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
+        let key = pr_cache_key(&self.forge_name, &owner, &repo, number);
+
+        self.spawn_load("Jump to number", load_id, async move {
+            let action = match forge.get_pr(&owner, &repo, number).await {
+                Ok(pr) => {
+                    cache::write(&key, &pr);
+                    Action::JumpedToPr(Box::new(pr), load_id)
                 }
-            }
+                Err(GritError::NotFound(_)) => match forge.get_issue(&owner, &repo, number).await {
+                    Ok(issue) => Action::JumpedToIssue(issue, load_id),
+                    Err(GritError::NotFound(_)) => Action::Error(GritError::NotFound(format!(
+                        "No PR or issue #{} in {}/{}",
+                        number, owner, repo
+                    ))),
+                    Err(e) => Action::Error(e),
+                },
+                Err(e) => Action::Error(e),
+            };
+            tx.send(action).ok();
         });
     }
 
-    fn spawn_load_commits_page(&self, owner: String, repo: String, page: u32, load_id: u64) {
+    fn spawn_load_pr_comments(&self, owner: String, repo: String, number: u64, load_id: u64) {
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
-        tokio::spawn(async move {
-            match forge.list_commits(&owner, &repo, page).await {
-                Ok(PagedResult { items, total_count }) => {
-                    tx.send(Action::CommitsAppended(items, total_count, load_id))
-                        .ok();
-                }
-                Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
-                }
+        let key = pr_comments_cache_key(&self.forge_name, &owner, &repo, number);
+
+        if let Some(cached) = self.cached::<Vec<Comment>>(&key) {
+            tx.send(Action::PrCommentsLoaded(cached, load_id)).ok();
+        }
+
+        self.spawn_load("Load PR comments", load_id, async move {
+            // Conversation is secondary to the PR itself; a failure here
+            // shouldn't replace the detail view with an error
+            if let Ok(comments) = forge.list_pr_comments(&owner, &repo, number).await {
+                cache::write(&key, &comments);
+                tx.send(Action::PrCommentsLoaded(comments, load_id)).ok();
             }
         });
     }
 
-    fn spawn_load_action_runs_page(&self, owner: String, repo: String, page: u32, load_id: u64) {
+    fn spawn_load_pr_commits(&self, owner: String, repo: String, number: u64, load_id: u64) {
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
-        tokio::spawn(async move {
-            match forge.list_action_runs(&owner, &repo, page).await {
-                Ok(PagedResult { items, total_count }) => {
-                    tx.send(Action::ActionRunsAppended(items, total_count, load_id))
-                        .ok();
-                }
-                Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
-                }
-            }
-        });
-    }
+        let key = format!(
+            "pr_commits_{}_{}",
+            cache::forge_repo_key(&self.forge_name, &owner, &repo),
+            number
+        );
 
-    /// Check if we need to fetch the next page and trigger if so
-    fn check_pagination(&mut self) {
-        match self.screen {
-            Screen::RepoList => {
-                if self.repo_index >= self.repos.len().saturating_sub(PREFETCH_THRESHOLD)
-                    && self.repos_pagination.has_more
-                    && !self.repos_pagination.loading_more
-                {
-                    self.repos_pagination.loading_more = true;
-                    self.repos_pagination.page += 1;
-                    self.spawn_load_repos_page(self.repos_pagination.page, self.load_id);
-                }
-            }
-            Screen::RepoView => match self.repo_tab {
-                RepoTab::PullRequests => {
-                    if self.pr_index >= self.prs.len().saturating_sub(PREFETCH_THRESHOLD)
-                        && self.prs_pagination.has_more
-                        && !self.prs_pagination.loading_more
-                    {
-                        self.prs_pagination.loading_more = true;
-                        self.prs_pagination.page += 1;
-                        if let Some((owner, repo)) = &self.current_repo {
-                            self.spawn_load_prs_page(
-                                owner.clone(),
-                                repo.clone(),
-                                self.prs_pagination.page,
-                                self.load_id,
-                            );
-                        }
-                    }
-                }
-                RepoTab::Issues => {
-                    if self.issue_index >= self.issues.len().saturating_sub(PREFETCH_THRESHOLD)
-                        && self.issues_pagination.has_more
-                        && !self.issues_pagination.loading_more
-                    {
-                        self.issues_pagination.loading_more = true;
-                        self.issues_pagination.page += 1;
-                        if let Some((owner, repo)) = &self.current_repo {
-                            self.spawn_load_issues_page(
-                                owner.clone(),
-                                repo.clone(),
-                                self.issues_pagination.page,
-                                self.load_id,
-                            );
-                        }
-                    }
-                }
-                RepoTab::Commits => {
-                    if self.commit_index >= self.commits.len().saturating_sub(PREFETCH_THRESHOLD)
-                        && self.commits_pagination.has_more
-                        && !self.commits_pagination.loading_more
-                    {
-                        self.commits_pagination.loading_more = true;
-                        self.commits_pagination.page += 1;
-                        if let Some((owner, repo)) = &self.current_repo {
-                            self.spawn_load_commits_page(
-                                owner.clone(),
-                                repo.clone(),
-                                self.commits_pagination.page,
-                                self.load_id,
-                            );
-                        }
-                    }
-                }
-                RepoTab::Actions => {
-                    if self.action_index
-                        >= self.action_runs.len().saturating_sub(PREFETCH_THRESHOLD)
-                        && self.actions_pagination.has_more
-                        && !self.actions_pagination.loading_more
-                    {
-                        self.actions_pagination.loading_more = true;
-                        self.actions_pagination.page += 1;
-                        if let Some((owner, repo)) = &self.current_repo {
-                            self.spawn_load_action_runs_page(
-                                owner.clone(),
-                                repo.clone(),
-                                self.actions_pagination.page,
-                                self.load_id,
-                            );
-                        }
-                    }
-                }
-            },
-            _ => {}
+        if let Some(cached) = self.cached::<Vec<Commit>>(&key) {
+            tx.send(Action::PrCommitsLoaded(cached, load_id)).ok();
         }
-    }
 
-    /// Calculate max scroll offset for current detail view
-    fn max_scroll_offset(&self) -> usize {
-        match self.screen {
-            Screen::PrDetail => {
-                if let Some(pr) = &self.current_pr {
-                    pr.body
-                        .as_deref()
-                        .unwrap_or("")
-                        .lines()
-                        .count()
-                        .saturating_sub(1)
-                } else {
-                    0
-                }
-            }
-            Screen::CommitDetail => {
-                if let Some(commit) = &self.current_commit {
-                    // Header lines (4) + message lines + blank + file entries
-                    let mut lines = 5; // header, blank, stats, blank, "Message:"
-                    lines += commit.message.lines().count();
-                    lines += 1; // blank after message
-                    for file in &commit.files {
-                        lines += 1; // file header
-                        if let Some(patch) = &file.patch {
-                            lines += patch.lines().count();
-                        }
-                        lines += 1; // blank after file
-                    }
-                    lines.saturating_sub(1)
-                } else {
-                    0
-                }
+        self.spawn_load("Load PR commits", load_id, async move {
+            if let Ok(commits) = forge.list_pr_commits(&owner, &repo, number).await {
+                cache::write(&key, &commits);
+                tx.send(Action::PrCommitsLoaded(commits, load_id)).ok();
             }
-            _ => 0,
-        }
+        });
     }
 
-    fn spawn_load_commit_detail(&self, owner: String, repo: String, sha: String, load_id: u64) {
+    fn spawn_load_pr_files(&self, owner: String, repo: String, number: u64, load_id: u64) {
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
         let key = format!(
-            "commit_{}_{}",
+            "pr_files_{}_{}",
             cache::forge_repo_key(&self.forge_name, &owner, &repo),
-            &sha[..7.min(sha.len())]
+            number
         );
 
-        if let Some(cached) = cache::read::<CommitDetail>(&key) {
-            tx.send(Action::CommitDetailLoaded(Box::new(cached), load_id))
-                .ok();
+        if let Some(cached) = self.cached::<Vec<CommitFile>>(&key) {
+            tx.send(Action::PrFilesLoaded(cached, load_id)).ok();
         }
 
-        tokio::spawn(async move {
-            match forge.get_commit(&owner, &repo, &sha).await {
-                Ok(commit) => {
-                    cache::write(&key, &commit);
-                    tx.send(Action::CommitDetailLoaded(Box::new(commit), load_id))
-                        .ok();
-                }
-                Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
-                }
+        self.spawn_load("Load PR files", load_id, async move {
+            if let Ok(files) = forge.list_pr_files(&owner, &repo, number).await {
+                cache::write(&key, &files);
+                tx.send(Action::PrFilesLoaded(files, load_id)).ok();
             }
         });
     }
 
-    fn spawn_load_pr_diff(&self, owner: String, repo: String, number: u64) {
+    /// Resolve a PR file in the local clone (git may need to fetch) off the UI thread
+    fn spawn_open_file(
+        &self,
+        owner: String,
+        repo: String,
+        sha: String,
+        branch: String,
+        path: String,
+    ) {
         let tx = self.action_tx.clone();
-        let forge = Arc::clone(&self.forge);
-        tokio::spawn(async move {
-            match forge.get_pr_diff(&owner, &repo, number).await {
-                Ok(diff) => {
-                    tx.send(Action::SuspendForPager(diff)).ok();
+        tokio::task::spawn_blocking(move || {
+            let Some(root) = crate::workspace::find_clone(&owner, &repo) else {
+                tx.send(Action::Error(GritError::Git(format!(
+                    "Not inside a clone of {}/{}",
+                    owner, repo
+                ))))
+                .ok();
+                return;
+            };
+            match crate::workspace::file_at(&root, &sha, &branch, &path) {
+                Ok(file) => {
+                    tx.send(Action::SuspendForEditFile(file)).ok();
                 }
                 Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
+                    tx.send(Action::Error(e)).ok();
                 }
             }
         });
     }
 
-    fn spawn_close_pr(&self, owner: String, repo: String, number: u64) {
+    /// Fetch the PR head into a local branch and check it out, once the clone
+    /// around the cwd is confirmed to be this repo
+    fn spawn_checkout_pr(&self, owner: String, repo: String, number: u64, branch: String) {
         let tx = self.action_tx.clone();
-        let forge = Arc::clone(&self.forge);
-        tokio::spawn(async move {
-            match forge.close_pr(&owner, &repo, number).await {
-                Ok(()) => {
-                    tx.send(Action::PrClosed).ok();
-                }
-                Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
-                }
-            }
+        let head_ref = self.forge.pr_head_ref(number);
+        tokio::task::spawn_blocking(move || {
+            let Some(root) = crate::workspace::find_clone(&owner, &repo) else {
+                tx.send(Action::Error(GritError::Git(format!(
+                    "Not inside a clone of {}/{}",
+                    owner, repo
+                ))))
+                .ok();
+                return;
+            };
+            let run = GitRun {
+                dir: root,
+                steps: vec![
+                    vec![
+                        "fetch".to_string(),
+                        "origin".to_string(),
+                        format!("{}:{}", head_ref, branch),
+                    ],
+                    vec!["checkout".to_string(), branch.clone()],
+                ],
+                done: format!("Checked out #{} as {}", number, branch),
+            };
+            tx.send(Action::SuspendForGit(run)).ok();
         });
     }
 
-    fn spawn_merge_pr(
-        &self,
-        owner: String,
-        repo: String,
-        number: u64,
-        method: crate::types::MergeMethod,
-    ) {
+    fn spawn_load_review_threads(&self, owner: String, repo: String, number: u64, load_id: u64) {
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
-        tokio::spawn(async move {
-            match forge
-                .merge_pr(&owner, &repo, number, method.as_api_str())
-                .await
-            {
-                Ok(()) => {
-                    tx.send(Action::PrMerged).ok();
-                }
-                Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
-                }
+        let key = format!(
+            "pr_threads_{}_{}",
+            cache::forge_repo_key(&self.forge_name, &owner, &repo),
+            number
+        );
+
+        if let Some(cached) = self.cached::<Vec<ReviewThread>>(&key) {
+            tx.send(Action::ReviewThreadsLoaded(cached, load_id)).ok();
+        }
+
+        self.spawn_load("Load review threads", load_id, async move {
+            if let Ok(threads) = forge.list_review_threads(&owner, &repo, number).await {
+                cache::write(&key, &threads);
+                tx.send(Action::ReviewThreadsLoaded(threads, load_id)).ok();
             }
         });
     }
 
-    fn spawn_close_issue(&self, owner: String, repo: String, number: u64) {
+    fn spawn_load_pr_reviews(&self, owner: String, repo: String, number: u64, load_id: u64) {
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
-        tokio::spawn(async move {
-            match forge.close_issue(&owner, &repo, number).await {
-                Ok(()) => {
-                    tx.send(Action::IssueClosed).ok();
-                }
-                Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
-                }
+        let key = format!(
+            "pr_reviews_{}_{}",
+            cache::forge_repo_key(&self.forge_name, &owner, &repo),
+            number
+        );
+
+        if let Some(cached) = self.cached::<PrReviews>(&key) {
+            tx.send(Action::PrReviewsLoaded(cached, load_id)).ok();
+        }
+
+        self.spawn_load("Load reviews", load_id, async move {
+            // Best-effort like comments: the detail view works without it
+            if let Ok(reviews) = forge.list_reviews(&owner, &repo, number).await {
+                cache::write(&key, &reviews);
+                tx.send(Action::PrReviewsLoaded(reviews, load_id)).ok();
             }
         });
     }
 
-    pub fn spawn_comment(&self, owner: String, repo: String, number: u64, body: String) {
+    /// Checks and viewed files feed the review progress bar; both best-effort
+    fn spawn_load_review_progress(&self, owner: String, repo: String, number: u64, load_id: u64) {
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
-        tokio::spawn(async move {
-            match forge.comment(&owner, &repo, number, &body).await {
-                Ok(()) => {
-                    tx.send(Action::CommentPosted).ok();
-                }
-                Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
-                }
+        let repo_key = cache::forge_repo_key(&self.forge_name, &owner, &repo);
+        let checks_key = format!("pr_checks_{}_{}", repo_key, number);
+        let viewed_key = format!("pr_viewed_{}_{}", repo_key, number);
+
+        if let Some(cached) = self.cached::<PrChecks>(&checks_key) {
+            tx.send(Action::PrChecksLoaded(cached, load_id)).ok();
+        }
+        if let Some(cached) = self.cached::<Vec<String>>(&viewed_key) {
+            tx.send(Action::ViewedFilesLoaded(cached, load_id)).ok();
+        }
+
+        self.spawn_load("Load review progress", load_id, async move {
+            if let Ok(checks) = forge.get_pr_checks(&owner, &repo, number).await {
+                cache::write(&checks_key, &checks);
+                tx.send(Action::PrChecksLoaded(checks, load_id)).ok();
+            }
+            if let Ok(paths) = forge.list_viewed_files(&owner, &repo, number).await {
+                cache::write(&viewed_key, &paths);
+                tx.send(Action::ViewedFilesLoaded(paths, load_id)).ok();
             }
         });
     }
 
-    pub fn spawn_submit_review(
-        &self,
-        owner: String,
-        repo: String,
-        number: u64,
-        event: crate::types::ReviewEvent,
-        body: String,
-    ) {
+    fn issues_cache_key(&self, owner: &str, repo: &str) -> String {
+        format!(
+            "issues_{}",
+            cache::forge_repo_key(&self.forge_name, owner, repo)
+        )
+    }
+
+    fn spawn_load_issues(&self, owner: String, repo: String, load_id: u64) {
         let tx = self.action_tx.clone();
         let forge = Arc::clone(&self.forge);
-        tokio::spawn(async move {
+        let milestone = self.milestone_filter.clone();
+        let key = match &milestone {
+            Some(milestone) => {
+                format!("{}_m{}", self.issues_cache_key(&owner, &repo), milestone.id)
+            }
+            None => self.issues_cache_key(&owner, &repo),
+        };
+
+        if let Some(cached) = self.cached::<Vec<Issue>>(&key) {
+            tx.send(Action::IssuesLoaded(cached, PageInfo::default(), load_id))
+                .ok();
+        }
+
+        self.spawn_load("Load issues", load_id, async move {
             match forge
-                .submit_review(&owner, &repo, number, event.as_api_str(), &body)
+                .list_issues(&owner, &repo, milestone.as_ref(), 1)
                 .await
             {
-                Ok(()) => {
-                    tx.send(Action::ReviewSubmitted).ok();
+                Ok(PagedResult { items, page_info }) => {
+                    cache::write(&key, &items);
+                    tx.send(Action::IssuesLoaded(items, page_info, load_id))
+                        .ok();
                 }
                 Err(e) => {
-                    tx.send(Action::Error(e.to_string())).ok();
+                    tx.send(Action::Error(e)).ok();
                 }
             }
         });
     }
 
-    /// Construct GitHub URL for the current item
-    pub(crate) fn current_item_url(&self) -> Option<String> {
-        match self.screen {
-            Screen::Home => match self.home_section {
-                HomeSection::ReviewRequests => {
-                    let req = self.review_requests.get(self.review_index)?;
-                    Some(self.forge.web_url(
-                        &req.repo_owner,
-                        &req.repo_name,
-                        "pr",
-                        &req.pr_number.to_string(),
-                    ))
+    fn spawn_load_commits(&self, owner: String, repo: String, load_id: u64) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let key = commits_cache_key(&self.forge_name, &owner, &repo);
+
+        if let Some(cached) = self.cached::<Vec<Commit>>(&key) {
+            tx.send(Action::CommitsLoaded(cached, PageInfo::default(), load_id))
+                .ok();
+        }
+
+        self.spawn_load("Load commits", load_id, async move {
+            match forge.list_commits(&owner, &repo, 1).await {
+                Ok(PagedResult { items, page_info }) => {
+                    cache::write(&key, &items);
+                    tx.send(Action::CommitsLoaded(items, page_info, load_id))
+                        .ok();
                 }
-                HomeSection::MyPrs => {
-                    let pr = self.my_prs.get(self.my_pr_index)?;
-                    Some(self.forge.web_url(
-                        &pr.repo_owner,
-                        &pr.repo_name,
-                        "pr",
-                        &pr.number.to_string(),
-                    ))
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
                 }
-            },
-            Screen::RepoList => {
-                let repo = self.repos.get(self.repo_index)?;
-                Some(self.forge.web_url(&repo.owner, &repo.name, "repo", ""))
             }
-            Screen::RepoView => {
-                let (owner, repo) = self.current_repo.as_ref()?;
-                match self.repo_tab {
-                    RepoTab::PullRequests => {
-                        let pr = self.prs.get(self.pr_index)?;
-                        Some(
-                            self.forge
-                                .web_url(owner, repo, "pr", &pr.number.to_string()),
-                        )
-                    }
-                    RepoTab::Issues => {
-                        let issue = self.issues.get(self.issue_index)?;
-                        Some(
-                            self.forge
-                                .web_url(owner, repo, "issue", &issue.number.to_string()),
-                        )
-                    }
-                    RepoTab::Commits => {
-                        let commit = self.commits.get(self.commit_index)?;
-                        Some(self.forge.web_url(owner, repo, "commit", &commit.sha))
-                    }
-                    RepoTab::Actions => {
-                        let run = self.action_runs.get(self.action_index)?;
-                        Some(
-                            self.forge
-                                .web_url(owner, repo, "action_run", &run.id.to_string()),
-                        )
+        });
+    }
+
+    /// Refresh the first page of commits of every repo, a few at a time, and
+    /// report the ones matching `query`
+    fn spawn_find_commit(&self, query: String, repos: Vec<(String, String)>) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let forge_name = self.forge_name.clone();
+        let remote = self.ui_config.remote_commit_search;
+
+        self.spawn_task("Find commit", async move {
+            let owners: Vec<String> = repos
+                .iter()
+                .map(|(owner, _)| owner.clone())
+                .collect::<std::collections::BTreeSet<_>>()
+                .into_iter()
+                .collect();
+            let fetched: Vec<_> = futures::stream::iter(repos)
+                .map(|(owner, repo)| {
+                    let forge = Arc::clone(&forge);
+                    async move {
+                        let commits = forge.list_commits(&owner, &repo, 1).await;
+                        (owner, repo, commits)
                     }
-                }
+                })
+                .buffer_unordered(FIND_COMMIT_CONCURRENCY)
+                .collect()
+                .await;
+
+            let mut hits = Vec::new();
+            for (owner, repo, commits) in fetched {
+                // A repo that fails just keeps its cached answer
+                let Ok(PagedResult { items, .. }) = commits else {
+                    continue;
+                };
+                cache::write(&commits_cache_key(&forge_name, &owner, &repo), &items);
+                hits.extend(items.into_iter().filter(|c| commit_matches(c, &query)).map(
+                    |commit| CommitHit {
+                        owner: owner.clone(),
+                        repo: repo.clone(),
+                        commit,
+                    },
+                ));
             }
-            Screen::PrDetail => {
-                let (owner, repo) = self.current_repo.as_ref()?;
-                let pr = self.current_pr.as_ref()?;
-                Some(
-                    self.forge
-                        .web_url(owner, repo, "pr", &pr.number.to_string()),
-                )
+            if remote {
+                if let Ok(found) = forge.search_commits(&query, &owners).await {
+                    hits.extend(found);
+                }
             }
-            Screen::CommitDetail => {
-                let (owner, repo) = self.current_repo.as_ref()?;
-                let commit = self.current_commit.as_ref()?;
-                Some(self.forge.web_url(owner, repo, "commit", &commit.sha))
+            tx.send(Action::CommitHitsLoaded(query, hits)).ok();
+        });
+    }
+
+    /// Add hits not already listed and show them, newest first, in the
+    /// "Find Commit" popup, keeping the highlighted hit if it's open
+    fn merge_commit_hits(&mut self, hits: Vec<CommitHit>) {
+        let selected = (self.input_mode == InputMode::SelectPopup
+            && self.popup_title == "Find Commit")
+            .then(|| self.commit_hits.get(self.popup_index))
+            .flatten()
+            .map(|hit| hit.commit.sha.clone());
+        let shown_before = !self.commit_hits.is_empty();
+        for hit in hits {
+            let listed = self.commit_hits.iter().any(|h| {
+                h.owner == hit.owner && h.repo == hit.repo && h.commit.sha == hit.commit.sha
+            });
+            if !listed {
+                self.commit_hits.push(hit);
             }
         }
+        if self.commit_hits.is_empty() {
+            return;
+        }
+        self.commit_hits
+            .sort_by_key(|hit| std::cmp::Reverse(hit.commit.date));
+        // Once the list was shown and closed, or the user is typing
+        // elsewhere, late hits don't pull the popup back up
+        let popup_open =
+            self.popup_title == "Find Commit" && self.input_mode == InputMode::SelectPopup;
+        if !popup_open && (shown_before || self.input_mode != InputMode::Normal) {
+            return;
+        }
+        self.input_mode = InputMode::SelectPopup;
+        self.popup_title = "Find Commit".to_string();
+        self.popup_items = self.commit_hits.iter().map(CommitHit::label).collect();
+        self.popup_index = selected
+            .and_then(|sha| self.commit_hits.iter().position(|h| h.commit.sha == sha))
+            .unwrap_or(0);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::github::GitHub;
-    use crate::types::*;
-    use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
+    fn spawn_load_action_runs(&self, owner: String, repo: String, load_id: u64) {
+        if self.workflow_summary {
+            self.spawn_load_workflow_history(owner.clone(), repo.clone(), load_id);
+        }
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let key = format!(
+            "actions_{}",
+            cache::forge_repo_key(&self.forge_name, &owner, &repo)
+        );
 
-    // ── Test helpers ──
+        if let Some(cached) = self.cached::<Vec<ActionRun>>(&key) {
+            tx.send(Action::ActionRunsLoaded(
+                cached,
+                PageInfo::default(),
+                load_id,
+            ))
+            .ok();
+        }
 
-    fn test_app() -> (App, mpsc::UnboundedReceiver<Action>) {
-        let github = GitHub::new("dummy_token".to_string()).unwrap();
-        let forge: Arc<dyn Forge> = Arc::new(github);
-        let (tx, rx) = mpsc::unbounded_channel();
-        (App::new(forge, tx, vec![]), rx)
+        self.spawn_load("Load workflow runs", load_id, async move {
+            match forge.list_action_runs(&owner, &repo, 1).await {
+                Ok(PagedResult { items, page_info }) => {
+                    cache::write(&key, &items);
+                    tx.send(Action::ActionRunsLoaded(items, page_info, load_id))
+                        .ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
     }
 
-    fn key(code: KeyCode) -> Event {
-        Event::Key(KeyEvent {
-            code,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        })
+    fn spawn_poll_action_runs(&self, owner: String, repo: String, load_id: u64) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_load("Poll workflow runs", load_id, async move {
+            // Polling is best-effort: a failed poll just waits for the next tick
+            if let Ok(PagedResult { items, .. }) = forge.list_action_runs(&owner, &repo, 1).await {
+                tx.send(Action::ActionRunsPolled(items, load_id)).ok();
+            }
+        });
     }
 
-    fn key_ctrl(c: char) -> Event {
-        Event::Key(KeyEvent {
-            code: KeyCode::Char(c),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        })
+    // Pagination: spawn methods for loading next pages (no cache)
+
+    fn spawn_load_repos_page(&self, page: u32, load_id: u64) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let scope = self.repo_scope.clone();
+        self.spawn_load("Load more repositories", load_id, async move {
+            let result = match &scope {
+                Some(org) => forge.list_org_repos(org, page).await,
+                None => forge.list_repos(page).await,
+            };
+            match result {
+                Ok(PagedResult { items, page_info }) => {
+                    tx.send(Action::ReposAppended(items, page_info, load_id))
+                        .ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
     }
 
-    fn make_repo(name: &str) -> Repository {
-        Repository {
-            owner: "testowner".to_string(),
-            name: name.to_string(),
-            description: Some("A test repo".to_string()),
-            url: format!("https://github.com/testowner/{}", name),
-            stars: 42,
-            updated_at: chrono::Utc::now(),
-        }
+    fn spawn_load_prs_page(&self, owner: String, repo: String, page: u32, load_id: u64) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let filter = self.pr_filter;
+        let milestone = self.milestone_filter.clone();
+        self.spawn_load("Load more pull requests", load_id, async move {
+            match forge
+                .list_prs(&owner, &repo, filter, milestone.as_ref(), page)
+                .await
+            {
+                Ok(PagedResult { items, page_info }) => {
+                    tx.send(Action::PrsAppended(items, page_info, load_id)).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
     }
 
-    fn make_pr_summary(number: u64, title: &str) -> PrSummary {
-        PrSummary {
-            number,
-            title: title.to_string(),
-            state: PrState::Open,
-            author: "testauthor".to_string(),
-            updated_at: chrono::Utc::now(),
-        }
+    fn spawn_load_issues_page(&self, owner: String, repo: String, page: u32, load_id: u64) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let milestone = self.milestone_filter.clone();
+        self.spawn_load("Load more issues", load_id, async move {
+            match forge
+                .list_issues(&owner, &repo, milestone.as_ref(), page)
+                .await
+            {
+                Ok(PagedResult { items, page_info }) => {
+                    tx.send(Action::IssuesAppended(items, page_info, load_id))
+                        .ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
     }
 
-    fn make_issue(number: u64, title: &str) -> Issue {
-        Issue {
-            number,
-            title: title.to_string(),
-            state: IssueState::Open,
-            author: "testauthor".to_string(),
-            labels: vec![],
-            comments: 0,
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-        }
+    fn spawn_load_commits_page(&self, owner: String, repo: String, page: u32, load_id: u64) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_load("Load more commits", load_id, async move {
+            match forge.list_commits(&owner, &repo, page).await {
+                Ok(PagedResult { items, page_info }) => {
+                    tx.send(Action::CommitsAppended(items, page_info, load_id))
+                        .ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
     }
 
-    fn make_commit(sha: &str, message: &str) -> Commit {
-        Commit {
-            sha: sha.to_string(),
-            message: message.to_string(),
-            author: "testauthor".to_string(),
-            date: chrono::Utc::now(),
+    /// The first WORKFLOW_HISTORY_PAGES pages of runs, for the per-workflow summary
+    fn spawn_load_workflow_history(&self, owner: String, repo: String, load_id: u64) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let key = format!(
+            "workflow_history_{}",
+            cache::forge_repo_key(&self.forge_name, &owner, &repo)
+        );
+
+        if let Some(cached) = self.cached::<Vec<ActionRun>>(&key) {
+            tx.send(Action::WorkflowHistoryLoaded(cached, load_id)).ok();
         }
-    }
 
-    fn make_review_request(owner: &str, repo: &str, number: u64) -> ReviewRequest {
-        ReviewRequest {
-            repo_owner: owner.to_string(),
-            repo_name: repo.to_string(),
+        self.spawn_load("Load workflow history", load_id, async move {
+            let mut runs = Vec::new();
+            for page in 1..=WORKFLOW_HISTORY_PAGES {
+                match forge.list_action_runs(&owner, &repo, page).await {
+                    Ok(PagedResult { items, .. }) => {
+                        let last = items.len() < forge.page_size();
+                        runs.extend(items);
+                        if last {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        tx.send(Action::Error(e)).ok();
+                        return;
+                    }
+                }
+            }
+            cache::write(&key, &runs);
+            tx.send(Action::WorkflowHistoryLoaded(runs, load_id)).ok();
+        });
+    }
+
+    fn spawn_load_repo_stats(&self, owner: String, repo: String) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let key = format!(
+            "repo_stats_{}",
+            cache::forge_repo_key(&self.forge_name, &owner, &repo)
+        );
+
+        if let Some(cached) = self.cached::<RepoStats>(&key) {
+            tx.send(Action::RepoStatsLoaded(owner.clone(), repo.clone(), cached))
+                .ok();
+        }
+
+        self.spawn_task("Load repo insights", async move {
+            match forge.get_repo_stats(&owner, &repo).await {
+                Ok(stats) => {
+                    cache::write(&key, &stats);
+                    tx.send(Action::RepoStatsLoaded(owner, repo, stats)).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    fn spawn_load_artifacts(&self, owner: String, repo: String, run_id: u64) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Load artifacts", async move {
+            match forge.list_artifacts(&owner, &repo, run_id).await {
+                Ok(artifacts) => tx.send(Action::ArtifactsLoaded(artifacts)).ok(),
+                Err(e) => tx.send(Action::Error(e)).ok(),
+            };
+        });
+    }
+
+    /// Stream the artifact's zip into `[ui] download_dir`
+    fn spawn_download_artifact(&self, artifact: Artifact) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let dir = self.ui_config.download_dir();
+        let file = format!("{}.zip", artifact.name.replace(['/', '\\'], "_"));
+        let path = dir.join(file);
+
+        self.spawn_task(DOWNLOAD_TASK, async move {
+            let result = async {
+                tokio::fs::create_dir_all(&dir).await?;
+                let response = forge.download_artifact(&artifact).await?;
+                crate::http::download(response, &path, |received, total| {
+                    tx.send(Action::DownloadProgress(received, total)).ok();
+                })
+                .await
+            }
+            .await;
+            match result {
+                Ok(()) => tx.send(Action::ArtifactDownloaded(path)).ok(),
+                Err(e) => {
+                    // Don't leave a truncated zip behind
+                    let _ = tokio::fs::remove_file(&path).await;
+                    tx.send(Action::DownloadFailed(e)).ok()
+                }
+            };
+        });
+    }
+
+    fn spawn_load_action_runs_page(&self, owner: String, repo: String, page: u32, load_id: u64) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_load("Load more workflow runs", load_id, async move {
+            match forge.list_action_runs(&owner, &repo, page).await {
+                Ok(PagedResult { items, page_info }) => {
+                    tx.send(Action::ActionRunsAppended(items, page_info, load_id))
+                        .ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    /// Check if we need to fetch the next page and trigger if so
+    fn check_pagination(&mut self) {
+        match self.screen {
+            Screen::Home
+                if self.home_section == HomeSection::Saved
+                    && self.saved_index >= self.saved.len().saturating_sub(PREFETCH_THRESHOLD)
+                    && self.saved_pagination.has_more
+                    && !self.saved_pagination.loading_more =>
+            {
+                self.saved_pagination.loading_more = true;
+                self.saved_pagination.page += 1;
+                self.spawn_load_saved(self.saved_pagination.page);
+            }
+            Screen::RepoList
+                if self.repo_index >= self.repos.len().saturating_sub(PREFETCH_THRESHOLD)
+                    && self.repos_pagination.has_more
+                    && !self.repos_pagination.loading_more =>
+            {
+                self.repos_pagination.loading_more = true;
+                self.repos_pagination.page += 1;
+                self.spawn_load_repos_page(self.repos_pagination.page, self.load_id);
+            }
+            // Later pages can add owners, so fetch them as the owner column nears its end
+            Screen::Browse
+                if self.browse.owner_index
+                    >= self
+                        .browse_owners()
+                        .len()
+                        .saturating_sub(PREFETCH_THRESHOLD)
+                    && self.repos_pagination.has_more
+                    && !self.repos_pagination.loading_more =>
+            {
+                self.repos_pagination.loading_more = true;
+                self.repos_pagination.page += 1;
+                self.spawn_load_repos_page(self.repos_pagination.page, self.load_id);
+            }
+            Screen::RepoView => match self.repo_tab {
+                RepoTab::PullRequests => {
+                    if self.pr_index >= self.prs.len().saturating_sub(PREFETCH_THRESHOLD)
+                        && self.prs_pagination.has_more
+                        && !self.prs_pagination.loading_more
+                    {
+                        self.prs_pagination.loading_more = true;
+                        self.prs_pagination.page += 1;
+                        if let Some((owner, repo)) = &self.current_repo {
+                            self.spawn_load_prs_page(
+                                owner.clone(),
+                                repo.clone(),
+                                self.prs_pagination.page,
+                                self.load_id,
+                            );
+                        }
+                    }
+                }
+                RepoTab::Issues => {
+                    if self.issue_index >= self.issues.len().saturating_sub(PREFETCH_THRESHOLD)
+                        && self.issues_pagination.has_more
+                        && !self.issues_pagination.loading_more
+                    {
+                        self.issues_pagination.loading_more = true;
+                        self.issues_pagination.page += 1;
+                        if let Some((owner, repo)) = &self.current_repo {
+                            self.spawn_load_issues_page(
+                                owner.clone(),
+                                repo.clone(),
+                                self.issues_pagination.page,
+                                self.load_id,
+                            );
+                        }
+                    }
+                }
+                RepoTab::Commits => {
+                    if self.commit_index >= self.commits.len().saturating_sub(PREFETCH_THRESHOLD)
+                        && self.commits_pagination.has_more
+                        && !self.commits_pagination.loading_more
+                    {
+                        self.commits_pagination.loading_more = true;
+                        self.commits_pagination.page += 1;
+                        if let Some((owner, repo)) = &self.current_repo {
+                            self.spawn_load_commits_page(
+                                owner.clone(),
+                                repo.clone(),
+                                self.commits_pagination.page,
+                                self.load_id,
+                            );
+                        }
+                    }
+                }
+                RepoTab::Actions => {
+                    if self.action_index
+                        >= self.action_runs.len().saturating_sub(PREFETCH_THRESHOLD)
+                        && self.actions_pagination.has_more
+                        && !self.actions_pagination.loading_more
+                    {
+                        self.actions_pagination.loading_more = true;
+                        self.actions_pagination.page += 1;
+                        if let Some((owner, repo)) = &self.current_repo {
+                            self.spawn_load_action_runs_page(
+                                owner.clone(),
+                                repo.clone(),
+                                self.actions_pagination.page,
+                                self.load_id,
+                            );
+                        }
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Cached copy of `key`, unless a hard refresh wants the forge's answer.
+    /// Offline, the cache is all there is, so it's served regardless.
+    fn cached<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
+        if self.bypass_cache && !self.offline {
+            return None;
+        }
+        let value = cache::read(key);
+        if value.is_some() {
+            self.cached_at.set(cache::modified(key));
+        }
+        value
+    }
+
+    /// The forge couldn't be reached: keep showing cached data and start
+    /// checking for it to come back
+    fn go_offline(&mut self) {
+        if !self.offline {
+            self.offline = true;
+            self.last_probe = Some(std::time::Instant::now());
+        }
+    }
+
+    /// Reload the view once a rate limit has refilled
+    fn retry_if_due(&mut self) {
+        if self
+            .retry_at
+            .is_some_and(|t| std::time::Instant::now() >= t)
+        {
+            self.retry_at = None;
+            self.error = None;
+            self.update(Action::Refresh);
+        }
+    }
+
+    /// While offline, or holding mutations from an earlier session, ask the
+    /// forge every so often whether it's reachable
+    fn probe_if_offline(&mut self) {
+        if !self.offline && self.outbox.is_empty() {
+            return;
+        }
+        if self
+            .last_probe
+            .is_some_and(|t| t.elapsed() < OFFLINE_PROBE_INTERVAL)
+        {
+            return;
+        }
+        self.last_probe = Some(std::time::Instant::now());
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Check connection", async move {
+            // Not the signed-in user: forges keep that from the first ask
+            if forge.list_repos(1).await.is_ok() {
+                tx.send(Action::Online).ok();
+            }
+        });
+    }
+
+    /// `u`: reload just the highlighted item rather than its whole list
+    fn refresh_item(&mut self) {
+        match self.screen {
+            Screen::Home => {
+                let selected = match self.home_section {
+                    HomeSection::ReviewRequests => self
+                        .review_requests
+                        .get(self.review_index)
+                        .map(|r| (r.repo_owner.clone(), r.repo_name.clone(), r.pr_number)),
+                    HomeSection::MyPrs => self
+                        .my_prs
+                        .get(self.my_pr_index)
+                        .map(|p| (p.repo_owner.clone(), p.repo_name.clone(), p.number)),
+                    HomeSection::Assigned => None,
+                    HomeSection::Mentions => self
+                        .mentions
+                        .get(self.mentions_index)
+                        .filter(|m| m.is_pr)
+                        .map(|m| (m.repo_owner.clone(), m.repo_name.clone(), m.number)),
+                    HomeSection::Saved => self
+                        .saved
+                        .get(self.saved_index)
+                        .filter(|m| m.is_pr)
+                        .map(|m| (m.repo_owner.clone(), m.repo_name.clone(), m.number)),
+                    HomeSection::Recent => self
+                        .recent
+                        .get(self.recent_index)
+                        .and_then(|r| Some((r.owner.clone(), r.repo.clone(), r.number?))),
+                };
+                if let Some((owner, repo, number)) = selected {
+                    self.spawn_refresh_pr(owner, repo, number);
+                }
+            }
+            Screen::RepoList | Screen::Browse => {
+                let selected = match self.screen {
+                    Screen::Browse => self.browse_selected_repo(),
+                    _ => self.repos.get(self.repo_index).map(repo_key),
+                };
+                if let Some((owner, repo)) = selected {
+                    self.browse.fetched.insert((owner.clone(), repo.clone()));
+                    self.spawn_browse_prs(owner, repo);
+                }
+            }
+            Screen::RepoView => {
+                let Some((owner, repo)) = self.current_repo.clone() else {
+                    return;
+                };
+                match self.repo_tab {
+                    RepoTab::PullRequests => {
+                        if let Some(number) = self.prs.get(self.pr_index).map(|pr| pr.number) {
+                            self.spawn_refresh_pr(owner, repo, number);
+                        }
+                    }
+                    RepoTab::Issues => {
+                        if let Some(number) = self.issues.get(self.issue_index).map(|i| i.number) {
+                            self.preview.fetched.insert((RepoTab::Issues, number));
+                            self.spawn_load_preview(owner, repo, (RepoTab::Issues, number));
+                        }
+                    }
+                    // Commits never change, and a run's status is all the list shows
+                    RepoTab::Commits | RepoTab::Actions => self.update(Action::Refresh),
+                }
+            }
+            Screen::PrDetail => {
+                if let (Some((owner, repo)), Some(pr)) = (&self.current_repo, &self.current_pr) {
+                    let tx = self.action_tx.clone();
+                    let forge = Arc::clone(&self.forge);
+                    let (owner, repo, number) = (owner.clone(), repo.clone(), pr.number);
+                    let key = pr_cache_key(&self.forge_name, &owner, &repo, number);
+                    let load_id = self.load_id;
+                    self.spawn_task("Refresh PR", async move {
+                        match forge.get_pr(&owner, &repo, number).await {
+                            Ok(pr) => {
+                                cache::write(&key, &pr);
+                                tx.send(Action::PrDetailLoaded(Box::new(pr), load_id)).ok();
+                            }
+                            Err(e) => {
+                                tx.send(Action::Error(e)).ok();
+                            }
+                        }
+                    });
+                }
+            }
+            Screen::CommitDetail | Screen::DiffView => self.update(Action::Refresh),
+            Screen::Logs => self.update(Action::Refresh),
+        }
+    }
+
+    /// Fetch one PR into the cache (and the preview pane) without opening it
+    fn spawn_refresh_pr(&mut self, owner: String, repo: String, number: u64) {
+        if self.preview.repo.as_ref() == Some(&(owner.clone(), repo.clone())) {
+            self.preview.fetched.insert((RepoTab::PullRequests, number));
+        }
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let key = pr_cache_key(&self.forge_name, &owner, &repo, number);
+        self.spawn_task("Refresh PR", async move {
+            match forge.get_pr(&owner, &repo, number).await {
+                Ok(pr) => {
+                    cache::write(&key, &pr);
+                    tx.send(Action::ItemRefreshed(format!("#{}", number))).ok();
+                    tx.send(Action::PreviewPrLoaded(owner, repo, Box::new(pr)))
+                        .ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    fn show_repo_list(&mut self) {
+        self.screen = Screen::RepoList;
+        self.loading = true;
+        self.load_id += 1;
+        self.spawn_load_repos(self.load_id);
+    }
+
+    fn open_repo(&mut self, owner: String, name: String) {
+        self.repos_screen = match self.screen {
+            Screen::Browse => Screen::Browse,
+            _ => Screen::RepoList,
+        };
+        // Snapshot before this visit's loads overwrite the cache
+        self.prs_seen_until = cache::read::<Vec<PrSummary>>(&self.prs_cache_key(&owner, &name))
+            .and_then(|prs| prs.iter().map(|pr| pr.updated_at).max());
+        self.issues_seen_until = cache::read::<Vec<Issue>>(&self.issues_cache_key(&owner, &name))
+            .and_then(|issues| issues.iter().map(|i| i.updated_at).max());
+        self.current_repo = Some((owner.clone(), name.clone()));
+        self.record_visit(owner.clone(), name.clone(), None, String::new());
+        self.screen = Screen::RepoView;
+        self.repo_tab = RepoTab::PullRequests;
+        self.pr_index = 0;
+        self.issue_index = 0;
+        self.commit_index = 0;
+        self.action_index = 0;
+        self.workflows.clear();
+        self.workflow_index = 0;
+        self.fork_status = None;
+        self.spawn_load_fork_status(owner.clone(), name.clone());
+        self.load_id += 1;
+        // Load PRs for this repo
+        self.spawn_load_prs(owner, name, self.load_id);
+    }
+
+    pub fn is_new_pr(&self, pr: &PrSummary) -> bool {
+        self.prs_seen_until.is_some_and(|seen| pr.updated_at > seen)
+    }
+
+    pub fn is_new_issue(&self, issue: &Issue) -> bool {
+        self.issues_seen_until
+            .is_some_and(|seen| issue.updated_at > seen)
+    }
+
+    /// Distinct repo owners, in the order they first appear in `repos`
+    pub fn browse_owners(&self) -> Vec<&str> {
+        let mut owners: Vec<&str> = Vec::new();
+        for repo in &self.repos {
+            if !owners.contains(&repo.owner.as_str()) {
+                owners.push(&repo.owner);
+            }
+        }
+        owners
+    }
+
+    /// Repos of the owner highlighted in the first column
+    pub fn browse_repos(&self) -> Vec<&Repository> {
+        let owners = self.browse_owners();
+        let Some(owner) = owners.get(self.browse.owner_index) else {
+            return Vec::new();
+        };
+        self.repos.iter().filter(|r| r.owner == *owner).collect()
+    }
+
+    fn browse_selected_repo(&self) -> Option<(String, String)> {
+        self.browse_repos()
+            .get(self.browse.repo_index)
+            .map(|r| repo_key(r))
+    }
+
+    /// Point the owner and repo columns at owner/name, if it's listed
+    fn browse_focus(&mut self, owner: &str, name: &str) {
+        let Some(owner_index) = self.browse_owners().iter().position(|o| *o == owner) else {
+            self.browse.owner_index = 0;
+            self.browse.repo_index = 0;
+            return;
+        };
+        self.browse.owner_index = owner_index;
+        self.browse.repo_index = self
+            .browse_repos()
+            .iter()
+            .position(|r| r.name == name)
+            .unwrap_or(0);
+    }
+
+    /// Move the cursor of the focused column; `f` maps (index, len) to the new index
+    fn browse_select(&mut self, f: impl FnOnce(usize, usize) -> usize) {
+        let len = match self.browse.column {
+            BrowseColumn::Owners => self.browse_owners().len(),
+            BrowseColumn::Repos => self.browse_repos().len(),
+            BrowseColumn::PullRequests => self.browse.prs.len(),
+        };
+        let index = match self.browse.column {
+            BrowseColumn::Owners => &mut self.browse.owner_index,
+            BrowseColumn::Repos => &mut self.browse.repo_index,
+            BrowseColumn::PullRequests => &mut self.browse.pr_index,
+        };
+        let next = f(*index, len).min(len.saturating_sub(1));
+        if next == *index {
+            return;
+        }
+        *index = next;
+        match self.browse.column {
+            BrowseColumn::Owners => {
+                self.browse.repo_index = 0;
+                self.browse_preview();
+            }
+            BrowseColumn::Repos => self.browse_preview(),
+            BrowseColumn::PullRequests => {}
+        }
+    }
+
+    /// Show cached PRs for the highlighted repo straight away. The forge is only
+    /// asked once the selection rests there, so scrolling past repos stays cheap.
+    fn browse_preview(&mut self) {
+        let selected = self.browse_selected_repo();
+        if selected == self.browse.prs_repo {
+            return;
+        }
+        self.browse.pr_index = 0;
+        self.browse.prs = selected
+            .as_ref()
+            .and_then(|(owner, repo)| cache::read(&self.prs_cache_key(owner, repo)))
+            .unwrap_or_default();
+        self.browse.prs_repo = selected;
+        self.browse.moved_at = Some(std::time::Instant::now());
+    }
+
+    /// Once the PR list selection rests, fetch the selected PR and the rows
+    /// around it into the cache. Each PR is fetched once per `updated_at`.
+    fn prefetch_pr_neighbors(&mut self) {
+        if self.screen != Screen::RepoView
+            || self.repo_tab != RepoTab::PullRequests
+            || self.offline
+            || self.ui_config.prefetch_limit() == 0
+        {
+            return;
+        }
+        let Some((owner, repo)) = self.current_repo.clone() else {
+            return;
+        };
+        let Some(selected) = self.prs.get(self.pr_index) else {
+            return;
+        };
+        let target = (owner.clone(), repo.clone(), selected.number);
+        if self.pr_prefetch.target.as_ref() != Some(&target) {
+            self.pr_prefetch.target = Some(target);
+            self.pr_prefetch.moved_at = Some(std::time::Instant::now());
+            return;
+        }
+        if self
+            .pr_prefetch
+            .moved_at
+            .is_none_or(|t| t.elapsed() < PR_PREFETCH_SETTLE)
+        {
+            return;
+        }
+        self.pr_prefetch.moved_at = None;
+
+        let start = self.pr_index.saturating_sub(PR_PREFETCH_NEIGHBORS);
+        let end = (self.pr_index + PR_PREFETCH_NEIGHBORS + 1).min(self.prs.len());
+        let mut rows: Vec<(usize, PrSummary)> = (start..end)
+            .map(|i| (i, self.prs[i].clone()))
+            .filter(|(_, pr)| {
+                self.pr_prefetch.warmed.insert((
+                    owner.clone(),
+                    repo.clone(),
+                    pr.number,
+                    pr.updated_at,
+                ))
+            })
+            .collect();
+        // Nearest first, so the likeliest Enter is ready soonest
+        rows.sort_by_key(|(i, _)| i.abs_diff(self.pr_index));
+        let prs: Vec<PrSummary> = rows.into_iter().map(|(_, pr)| pr).collect();
+        if !prs.is_empty() {
+            self.spawn_prefetch_prs(owner, repo, prs);
+        }
+    }
+
+    fn spawn_prefetch_prs(&self, owner: String, repo: String, prs: Vec<PrSummary>) {
+        let forge = Arc::clone(&self.forge);
+        let forge_name = self.forge_name.clone();
+        self.spawn_task("Prefetch PRs", async move {
+            futures::stream::iter(prs)
+                .map(|summary| {
+                    let forge = Arc::clone(&forge);
+                    let key = pr_cache_key(&forge_name, &owner, &repo, summary.number);
+                    let (owner, repo) = (owner.clone(), repo.clone());
+                    async move {
+                        if cache::read::<PullRequest>(&key)
+                            .is_some_and(|pr| pr.updated_at >= summary.updated_at)
+                        {
+                            return;
+                        }
+                        // Failures are ignored; Enter just fetches as usual
+                        if let Ok(pr) = forge.get_pr(&owner, &repo, summary.number).await {
+                            cache::write(&key, &pr);
+                        }
+                    }
+                })
+                .buffer_unordered(PR_PREFETCH_CONCURRENCY)
+                .collect::<Vec<()>>()
+                .await;
+        });
+    }
+
+    fn browse_refresh_if_settled(&mut self) {
+        if self.screen != Screen::Browse
+            || self
+                .browse
+                .moved_at
+                .is_none_or(|t| t.elapsed() < BROWSE_SETTLE)
+        {
+            return;
+        }
+        self.browse.moved_at = None;
+        let Some((owner, repo)) = self.browse.prs_repo.clone() else {
+            return;
+        };
+        if self.browse.fetched.insert((owner.clone(), repo.clone())) {
+            self.spawn_browse_prs(owner, repo);
+        }
+    }
+
+    /// Entries of the focused browse column, as search sees them
+    fn browse_column_names(&self) -> Vec<String> {
+        match self.browse.column {
+            BrowseColumn::Owners => self.browse_owners().into_iter().map(String::from).collect(),
+            BrowseColumn::Repos => self
+                .browse_repos()
+                .into_iter()
+                .map(|r| r.name.clone())
+                .collect(),
+            BrowseColumn::PullRequests => self
+                .browse
+                .prs
+                .iter()
+                .map(|pr| format!("#{} {}", pr.number, pr.title))
+                .collect(),
+        }
+    }
+
+    /// Merge methods, then the checkboxes that apply to the current PR
+    fn merge_popup_items(&self) -> Vec<String> {
+        let mut items: Vec<String> = MERGE_METHODS.iter().map(|m| m.to_string()).collect();
+        if let Some(pr) = &self.current_pr {
+            items.push(checkbox_item(self.merge_auto, "Merge when checks pass"));
+            // A fork's branch isn't ours to delete
+            if !pr.from_fork {
+                items.push(checkbox_item(
+                    self.merge_delete_branch,
+                    &format!("Delete {} after merge", pr.head_branch),
+                ));
+            }
+        }
+        items
+    }
+
+    /// Merge popup defaults for the current repo on the current forge
+    fn merge_config(&self) -> crate::config::MergeConfig {
+        let forge = self
+            .forge_configs
+            .iter()
+            .find(|fc| fc.name == self.forge_name);
+        match (forge, &self.current_repo) {
+            (Some(forge), Some((owner, repo))) => forge.merge_config(owner, repo),
+            (Some(forge), None) => forge.merge,
+            _ => Default::default(),
+        }
+    }
+
+    fn default_repo(&self) -> Option<(String, String)> {
+        self.forge_configs
+            .iter()
+            .find(|fc| fc.name == self.forge_name)?
+            .default_repo()
+    }
+
+    fn last_location_key(&self) -> String {
+        format!("{}_last_location", self.forge_name)
+    }
+
+    /// Where to pick up next time with `start_screen = "last"`
+    pub fn last_location(&self) -> LastLocation {
+        let in_repo = match self.screen {
+            Screen::Home => return LastLocation::Home,
+            Screen::RepoList | Screen::Browse => return LastLocation::Repos,
+            // The debug screen isn't worth coming back to
+            Screen::Logs => return LastLocation::Home,
+            Screen::RepoView => true,
+            // Details opened from Home have a repo but no repo view behind them
+            Screen::PrDetail | Screen::DiffView | Screen::CommitDetail => {
+                self.nav_stack
+                    .iter()
+                    .rev()
+                    .map(|entry| entry.screen)
+                    .find(|&screen| !matches!(screen, Screen::PrDetail | Screen::CommitDetail))
+                    == Some(Screen::RepoView)
+            }
+        };
+        match &self.current_repo {
+            Some((owner, name)) if in_repo => LastLocation::Repo {
+                owner: owner.clone(),
+                name: name.clone(),
+            },
+            _ => LastLocation::Home,
+        }
+    }
+
+    pub fn save_last_location(&self) {
+        cache::write(&self.last_location_key(), &self.last_location());
+    }
+
+    /// Trade the live view with the one `workspace` holds
+    fn swap_workspace(&mut self, workspace: &mut Workspace) {
+        use std::mem::swap;
+        swap(&mut self.screen, &mut workspace.screen);
+        swap(&mut self.nav_stack, &mut workspace.nav_stack);
+        swap(&mut self.nav_forward, &mut workspace.nav_forward);
+        swap(&mut self.repos_screen, &mut workspace.repos_screen);
+        swap(&mut self.search, &mut workspace.search);
+        swap(&mut self.back_stack, &mut workspace.back_stack);
+        swap(&mut self.home_section, &mut workspace.home_section);
+        swap(&mut self.review_index, &mut workspace.review_index);
+        swap(&mut self.my_pr_index, &mut workspace.my_pr_index);
+        swap(&mut self.assigned_index, &mut workspace.assigned_index);
+        swap(&mut self.mentions_index, &mut workspace.mentions_index);
+        swap(&mut self.saved_index, &mut workspace.saved_index);
+        swap(&mut self.recent_index, &mut workspace.recent_index);
+        swap(&mut self.repo_index, &mut workspace.repo_index);
+        swap(&mut self.current_repo, &mut workspace.current_repo);
+        swap(&mut self.repo_tab, &mut workspace.repo_tab);
+        swap(&mut self.prs, &mut workspace.prs);
+        swap(&mut self.pr_index, &mut workspace.pr_index);
+        swap(&mut self.prs_pagination, &mut workspace.prs_pagination);
+        swap(&mut self.issues, &mut workspace.issues);
+        swap(&mut self.issue_index, &mut workspace.issue_index);
+        swap(
+            &mut self.issues_pagination,
+            &mut workspace.issues_pagination,
+        );
+        swap(&mut self.commits, &mut workspace.commits);
+        swap(&mut self.commit_index, &mut workspace.commit_index);
+        swap(
+            &mut self.commits_pagination,
+            &mut workspace.commits_pagination,
+        );
+        swap(&mut self.action_runs, &mut workspace.action_runs);
+        swap(&mut self.action_index, &mut workspace.action_index);
+        swap(
+            &mut self.actions_pagination,
+            &mut workspace.actions_pagination,
+        );
+        swap(&mut self.workflow_summary, &mut workspace.workflow_summary);
+        swap(&mut self.workflows, &mut workspace.workflows);
+        swap(&mut self.workflow_index, &mut workspace.workflow_index);
+        swap(&mut self.milestones, &mut workspace.milestones);
+        swap(&mut self.labels, &mut workspace.labels);
+        swap(&mut self.marks, &mut workspace.marks);
+        swap(&mut self.milestone_filter, &mut workspace.milestone_filter);
+        swap(&mut self.pr_filter, &mut workspace.pr_filter);
+        swap(&mut self.prs_seen_until, &mut workspace.prs_seen_until);
+        swap(
+            &mut self.issues_seen_until,
+            &mut workspace.issues_seen_until,
+        );
+        swap(&mut self.fork_status, &mut workspace.fork_status);
+        swap(&mut self.repo_stats, &mut workspace.repo_stats);
+        swap(&mut self.current_pr, &mut workspace.current_pr);
+        swap(&mut self.pr_comments, &mut workspace.pr_comments);
+        swap(&mut self.pr_seen, &mut workspace.pr_seen);
+        swap(&mut self.pr_reviews, &mut workspace.pr_reviews);
+        swap(&mut self.pr_checks, &mut workspace.pr_checks);
+        swap(&mut self.pr_files, &mut workspace.pr_files);
+        swap(&mut self.file_index, &mut workspace.file_index);
+        swap(&mut self.pr_commits, &mut workspace.pr_commits);
+        swap(&mut self.pr_commit_index, &mut workspace.pr_commit_index);
+        swap(&mut self.pr_focus, &mut workspace.pr_focus);
+        swap(&mut self.diff_cursor, &mut workspace.diff_cursor);
+        swap(&mut self.viewed_files, &mut workspace.viewed_files);
+        swap(&mut self.review_threads, &mut workspace.review_threads);
+        swap(&mut self.thread_index, &mut workspace.thread_index);
+        swap(&mut self.xref_index, &mut workspace.xref_index);
+        swap(&mut self.current_commit, &mut workspace.current_commit);
+        swap(&mut self.commit_checks, &mut workspace.commit_checks);
+        swap(&mut self.scroll_offset, &mut workspace.scroll_offset);
+        swap(&mut self.list_offsets, &mut workspace.list_offsets);
+    }
+
+    /// The current screen as Back or Forward would come back to it
+    fn nav_entry(&self) -> NavEntry {
+        NavEntry {
+            screen: self.screen,
+            repo: self.current_repo.clone(),
+            pr: self.current_pr.as_ref().map(|pr| pr.number),
+            commit: self.current_commit.as_ref().map(|c| c.sha.clone()),
+            scroll_offset: self.scroll_offset,
+        }
+    }
+
+    /// Remember the current screen before opening `arriving`. Opening what
+    /// Forward offered next keeps the rest of the forward stack; anything
+    /// else drops it, as in a browser.
+    fn push_nav(&mut self, arriving: NavEntry) {
+        match self.nav_forward.last() {
+            Some(next) if next.same_place(&arriving) => {
+                self.nav_forward.pop();
+            }
+            _ => self.nav_forward.clear(),
+        }
+        let entry = self.nav_entry();
+        self.nav_stack.push(entry);
+    }
+
+    /// Return to the screen the current one was opened from, or `fallback`
+    fn pop_nav(&mut self, fallback: Screen) {
+        match self.nav_stack.pop() {
+            Some(entry) => {
+                self.screen = entry.screen;
+                self.scroll_offset = entry.scroll_offset;
+                if entry.repo.is_some() {
+                    self.current_repo = entry.repo;
+                }
+            }
+            None => {
+                self.screen = fallback;
+                self.scroll_offset = 0;
+            }
+        }
+    }
+
+    /// Drop the open PR and everything loaded along with it
+    fn close_pr(&mut self) {
+        self.current_pr = None;
+        self.pr_comments.clear();
+        self.pr_seen = PrSnapshot::default();
+        self.pr_reviews = PrReviews::default();
+        self.pr_checks = PrChecks::default();
+        self.viewed_files = None;
+        self.pr_files.clear();
+        self.file_index = 0;
+        self.pr_commits.clear();
+        self.pr_commit_index = 0;
+        self.pr_focus = PrFocus::default();
+        self.review_threads.clear();
+        self.thread_index = 0;
+        self.xref_index = None;
+    }
+
+    /// Leave the open PR for `arriving`, another PR, keeping it for Back
+    fn leave_pr_for(&mut self, arriving: NavEntry) {
+        self.push_nav(arriving);
+        self.close_pr();
+        self.scroll_offset = 0;
+    }
+
+    /// Put the live view away in its slot and bring in workspace `index`
+    fn switch_workspace(&mut self, index: usize) {
+        if index == self.workspace_index || index >= self.workspaces.len() {
+            return;
+        }
+        let mut slots = std::mem::take(&mut self.workspaces);
+        self.swap_workspace(&mut slots[self.workspace_index]);
+        self.swap_workspace(&mut slots[index]);
+        self.workspaces = slots;
+        self.workspace_index = index;
+        // Loads still in flight belong to the workspace that was left
+        self.load_id += 1;
+        self.loading = false;
+    }
+
+    /// Header names of the workspaces, in order
+    pub fn workspace_labels(&self) -> Vec<String> {
+        self.workspaces
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                if i == self.workspace_index {
+                    workspace_label(self.screen, &self.current_repo, self.current_pr.as_ref())
+                } else {
+                    workspace_label(w.screen, &w.current_repo, w.current_pr.as_ref())
+                }
+            })
+            .collect()
+    }
+
+    fn session_key(&self) -> String {
+        format!("{}_session", self.forge_name)
+    }
+
+    /// Screen, selection and open PR, to be offered back next start
+    pub fn session(&self) -> Session {
+        let location = self.last_location();
+        let index = match location {
+            LastLocation::Home => match self.home_section {
+                HomeSection::ReviewRequests => self.review_index,
+                HomeSection::MyPrs => self.my_pr_index,
+                HomeSection::Assigned => self.assigned_index,
+                HomeSection::Mentions => self.mentions_index,
+                HomeSection::Saved => self.saved_index,
+                HomeSection::Recent => self.recent_index,
+            },
+            LastLocation::Repos => self.repo_index,
+            LastLocation::Repo { .. } => match self.repo_tab {
+                RepoTab::PullRequests => self.pr_index,
+                RepoTab::Issues => self.issue_index,
+                RepoTab::Commits => self.commit_index,
+                RepoTab::Actions => self.action_index,
+            },
+        };
+        let on_pr = match self.screen {
+            Screen::PrDetail | Screen::DiffView => true,
+            Screen::CommitDetail => {
+                self.nav_stack.last().map(|entry| entry.screen) == Some(Screen::PrDetail)
+            }
+            _ => false,
+        };
+        let pr = match (&self.current_repo, &self.current_pr) {
+            (Some((owner, repo)), Some(pr)) if on_pr => {
+                Some((owner.clone(), repo.clone(), pr.number))
+            }
+            _ => None,
+        };
+        Session {
+            location,
+            home_section: self.home_section,
+            repo_tab: self.repo_tab,
+            index,
+            pr,
+            scroll: if self.screen == Screen::PrDetail {
+                self.scroll_offset
+            } else {
+                0
+            },
+        }
+    }
+
+    pub fn save_session(&self) {
+        if self.ui_config.restore_session {
+            cache::write(&self.session_key(), &self.session());
+        }
+    }
+
+    /// Ask whether to pick up where the last session quit
+    fn offer_session(&mut self) {
+        let Some(session) = cache::read::<Session>(&self.session_key()) else {
+            return;
+        };
+        if session.is_fresh() {
+            return;
+        }
+        self.popup_title = "Restore Session".to_string();
+        self.popup_items = vec![
+            format!("Back to {}", session.label()),
+            "Start fresh".to_string(),
+        ];
+        self.popup_index = 0;
+        self.input_mode = InputMode::SelectPopup;
+        self.saved_session = Some(session);
+    }
+
+    fn restore_session(&mut self, session: Session) {
+        match session.location {
+            LastLocation::Home => {
+                if self.screen != Screen::Home {
+                    self.screen = Screen::Home;
+                    self.update(Action::LoadHome);
+                }
+                self.home_section = session.home_section;
+                if !self.home_order().contains(&self.home_section) {
+                    self.home_section = self.home_order()[0];
+                }
+                match session.home_section {
+                    HomeSection::ReviewRequests => self.review_index = session.index,
+                    HomeSection::MyPrs => self.my_pr_index = session.index,
+                    HomeSection::Assigned => self.assigned_index = session.index,
+                    HomeSection::Mentions => self.mentions_index = session.index,
+                    HomeSection::Saved => self.saved_index = session.index,
+                    HomeSection::Recent => self.recent_index = session.index,
+                }
+            }
+            LastLocation::Repos => {
+                self.show_repo_list();
+                self.repo_index = session.index;
+            }
+            LastLocation::Repo { owner, name } => {
+                self.check_remembered(&owner, &name, None);
+                self.open_repo(owner, name);
+                if session.repo_tab != RepoTab::PullRequests {
+                    self.update(Action::SwitchRepoTab(session.repo_tab));
+                }
+                // Kept through the load, which reselects the nearest row
+                match session.repo_tab {
+                    RepoTab::PullRequests => self.pr_index = session.index,
+                    RepoTab::Issues => self.issue_index = session.index,
+                    RepoTab::Commits => self.commit_index = session.index,
+                    RepoTab::Actions => self.action_index = session.index,
+                }
+            }
+        }
+        if let Some((owner, repo, number)) = session.pr {
+            self.check_remembered(&owner, &repo, Some(number));
+            self.current_repo = Some((owner.clone(), repo.clone()));
+            self.session_scroll = Some(session.scroll);
+            self.load_id += 1;
+            self.loading = true;
+            self.spawn_load_pr_detail(owner, repo, number, self.load_id);
+        }
+    }
+
+    fn recent_key(&self) -> String {
+        format!("{}_recent", self.forge_name)
+    }
+
+    pub fn save_recent(&self) {
+        if !self.recent.is_empty() {
+            cache::write(&self.recent_key(), &self.recent);
+        }
+    }
+
+    /// Note an opened repo or PR in the Recent list and the back stack
+    fn record_visit(&mut self, owner: String, repo: String, number: Option<u64>, title: String) {
+        let item = RecentItem {
+            owner,
+            repo,
+            number,
+            title,
+            visited_at: chrono::Utc::now(),
+        };
+        self.recent.retain(|r| !r.same_target(&item));
+        self.recent.insert(0, item.clone());
+        self.recent.truncate(RECENT_LIMIT);
+        if !self.back_stack.last().is_some_and(|b| b.same_target(&item)) {
+            self.back_stack.push(item);
+            if self.back_stack.len() > BACK_STACK_LIMIT {
+                self.back_stack.remove(0);
+            }
+        }
+    }
+
+    /// Reopen a repo or PR from the Recent list or the back stack
+    fn open_recent(&mut self, item: RecentItem) {
+        self.check_remembered(&item.owner, &item.repo, item.number);
+        match item.number {
+            None => self.open_repo(item.owner, item.repo),
+            Some(number) => {
+                self.current_repo = Some((item.owner.clone(), item.repo.clone()));
+                self.load_id += 1;
+                self.loading = true;
+                self.spawn_load_pr_detail(item.owner, item.repo, number, self.load_id);
+            }
+        }
+    }
+
+    /// Drop a repo or PR the forge no longer has from recent items and the
+    /// cache, and step away from it if it's on screen
+    fn forget_missing(&mut self, owner: String, repo: String, number: Option<u64>) {
+        let gone = |item: &RecentItem| {
+            item.owner == owner && item.repo == repo && (number.is_none() || item.number == number)
+        };
+        self.recent.retain(|item| !gone(item));
+        self.back_stack.retain(|item| !gone(item));
+        // Written even when empty, or the entries would come back next run
+        cache::write(&self.recent_key(), &self.recent);
+        for key in repo_cache_keys(&self.forge_name, &owner, &repo, number) {
+            cache::remove(&key);
+        }
+
+        let here = self.current_repo.as_ref() == Some(&(owner.clone(), repo.clone()));
+        let showing = match number {
+            Some(number) => {
+                self.screen == Screen::PrDetail
+                    && self
+                        .current_pr
+                        .as_ref()
+                        .is_some_and(|pr| pr.number == number)
+            }
+            None => matches!(self.screen, Screen::RepoView | Screen::PrDetail),
+        };
+        if here && showing {
+            self.update(Action::Back);
+        }
+        let what = match number {
+            Some(number) => format!("PR #{} of {}/{}", number, owner, repo),
+            None => format!("{}/{}", owner, repo),
+        };
+        self.flash_message = Some((format!("Forgot {}", what), std::time::Instant::now()));
+    }
+
+    /// Make sure a repo known from an earlier run still goes by that name.
+    /// Forges redirect renamed repos, so loads under the old name work and
+    /// nothing would otherwise correct it; repos just listed are trusted.
+    fn check_remembered(&self, owner: &str, repo: &str, number: Option<u64>) {
+        if self
+            .repos
+            .iter()
+            .any(|r| r.owner == owner && r.name == repo)
+        {
+            return;
+        }
+        self.spawn_locate_repo(Missing {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            number,
+            error: None,
+        });
+    }
+
+    fn spawn_locate_repo(&self, missing: Missing) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Locate repository", async move {
+            let located = forge.locate_repo(&missing.owner, &missing.repo).await;
+            tx.send(Action::RepoLocated(missing, located)).ok();
+        });
+    }
+
+    /// Point everything that knew the repo by its old name at the new one,
+    /// and reopen whatever was being opened there
+    fn follow_move(&mut self, missing: Missing, owner: String, repo: String) {
+        let old = (missing.owner, missing.repo);
+        for item in self.recent.iter_mut().chain(self.back_stack.iter_mut()) {
+            if (&item.owner, &item.repo) == (&old.0, &old.1) {
+                item.owner = owner.clone();
+                item.repo = repo.clone();
+            }
+        }
+        for entry in &mut self.nav_stack {
+            if entry.repo.as_ref() == Some(&old) {
+                entry.repo = Some((owner.clone(), repo.clone()));
+            }
+        }
+        self.save_recent();
+        for key in repo_cache_keys(&self.forge_name, &old.0, &old.1, None) {
+            cache::remove(&key);
+        }
+        self.flash_message = Some((
+            format!("{}/{} moved to {}/{}", old.0, old.1, owner, repo),
+            std::time::Instant::now(),
+        ));
+
+        if self.current_repo.as_ref() != Some(&old) {
+            return;
+        }
+        match missing.number {
+            Some(number) => {
+                self.current_repo = Some((owner.clone(), repo.clone()));
+                self.load_id += 1;
+                self.loading = true;
+                self.spawn_load_pr_detail(owner, repo, number, self.load_id);
+            }
+            None if self.screen == Screen::RepoView => self.open_repo(owner, repo),
+            None => self.current_repo = Some((owner, repo)),
+        }
+    }
+
+    fn sort_review_requests(&mut self) {
+        let requests = &mut self.review_requests;
+        match self.review_sort {
+            ReviewSort::Newest => requests.sort_by_key(|r| std::cmp::Reverse(r.updated_at)),
+            ReviewSort::Oldest => requests.sort_by_key(|r| r.updated_at),
+            ReviewSort::Repo => requests.sort_by(|a, b| {
+                (&a.repo_owner, &a.repo_name, a.pr_number).cmp(&(
+                    &b.repo_owner,
+                    &b.repo_name,
+                    b.pr_number,
+                ))
+            }),
+            ReviewSort::Author => requests.sort_by(|a, b| {
+                a.author
+                    .to_lowercase()
+                    .cmp(&b.author.to_lowercase())
+                    .then(a.updated_at.cmp(&b.updated_at))
+            }),
+        }
+    }
+
+    /// Open an Assigned or Mentions entry: a PR's detail, or an issue's repo
+    /// on the Issues tab since there is no issue detail screen
+    fn open_issue_ref(&mut self, item: IssueRef) {
+        if item.is_pr {
+            self.current_repo = Some((item.repo_owner.clone(), item.repo_name.clone()));
+            self.load_id += 1;
+            self.spawn_load_pr_detail(item.repo_owner, item.repo_name, item.number, self.load_id);
+        } else {
+            self.open_repo(item.repo_owner, item.repo_name);
+            self.update(Action::SwitchRepoTab(RepoTab::Issues));
+        }
+    }
+
+    fn issue_ref_url(&self, item: &IssueRef) -> String {
+        let kind = if item.is_pr { "pr" } else { "issue" };
+        self.forge.web_url(
+            &item.repo_owner,
+            &item.repo_name,
+            kind,
+            &item.number.to_string(),
+        )
+    }
+
+    /// Palette entries available on this screen, best match for the query first
+    pub fn palette_matches(&self) -> Vec<PaletteEntry> {
+        let mut seen = HashSet::new();
+        let keys = crate::palette::KEYS
+            .iter()
+            .map(|&c| (KeyCode::Char(c), c.to_string()))
+            .chain(
+                crate::palette::NAMED_KEYS
+                    .iter()
+                    .map(|&(code, name)| (code, name.to_string())),
+            );
+        let mut scored: Vec<(i64, PaletteEntry)> = keys
+            .filter_map(|(code, key)| {
+                let action = self.handle_key_normal(KeyEvent::new(code, KeyModifiers::NONE));
+                let name = crate::palette::label(&action)?;
+                if !seen.insert(name) {
+                    return None;
+                }
+                let score = crate::palette::fuzzy_score(&self.palette_query, name)?;
+                Some((score, PaletteEntry { name, key, action }))
+            })
+            .collect();
+        // Stable sort keeps KEYS order among equal scores
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    fn refresh_palette_items(&mut self) {
+        self.popup_items = self
+            .palette_matches()
+            .into_iter()
+            .map(|e| e.name.to_string())
+            .collect();
+        self.popup_index = 0;
+    }
+
+    /// The PR or issue whose labels `L` edits: (kind, number, current labels)
+    fn label_target(&self) -> Option<(&'static str, u64, &[Label])> {
+        match self.screen {
+            Screen::PrDetail => self
+                .current_pr
+                .as_ref()
+                .map(|pr| ("pr", pr.number, pr.labels.as_slice())),
+            Screen::RepoView if self.repo_tab == RepoTab::Issues => self
+                .issues
+                .get(self.issue_index)
+                .map(|issue| ("issue", issue.number, issue.labels.as_slice())),
+            _ => None,
+        }
+    }
+
+    /// Apply `[home]`: which sections show and which start collapsed
+    pub fn set_home_config(&mut self, home: HomeConfig) {
+        self.collapsed = home.collapsed.iter().copied().collect();
+        self.home_config = home;
+        if !self.home_order().contains(&self.home_section) {
+            self.home_section = self.home_order()[0];
+        }
+    }
+
+    /// Home's sections top to bottom with their heights in percent. Saved
+    /// only shows once there are searches to run.
+    pub fn home_sections(&self) -> Vec<(HomeSection, u16)> {
+        let home = &self.home_config;
+        let mut seen = HashSet::new();
+        let sections: Vec<(HomeSection, u16)> = if home.sections.is_empty() {
+            HOME_SECTIONS
+                .iter()
+                .enumerate()
+                .map(|(i, &(section, size))| (section, home.sizes.get(i).copied().unwrap_or(size)))
+                .collect()
+        } else {
+            let even = (100 / home.sections.len()).max(1) as u16;
+            home.sections
+                .iter()
+                .enumerate()
+                .map(|(i, &section)| (section, home.sizes.get(i).copied().unwrap_or(even)))
+                .collect()
+        };
+        let shown: Vec<(HomeSection, u16)> = sections
+            .into_iter()
+            .filter(|&(section, _)| section != HomeSection::Saved || !self.searches.is_empty())
+            .filter(|&(section, _)| seen.insert(section))
+            .collect();
+        // A `sections` of only `saved` without searches would leave Home empty
+        if shown.is_empty() {
+            vec![HOME_SECTIONS[0]]
+        } else {
+            shown
+        }
+    }
+
+    /// The order Tab steps through Home's sections
+    fn home_order(&self) -> Vec<HomeSection> {
+        self.home_sections().into_iter().map(|(s, _)| s).collect()
+    }
+
+    /// The RepoView tab whose rows can be marked, when one is on screen
+    fn mark_tab(&self) -> Option<RepoTab> {
+        (self.screen == Screen::RepoView
+            && matches!(self.repo_tab, RepoTab::PullRequests | RepoTab::Issues))
+        .then_some(self.repo_tab)
+    }
+
+    /// "pr" or "issue", for what the marks on screen are
+    fn mark_kind(&self) -> &'static str {
+        if self.repo_tab == RepoTab::PullRequests {
+            "pr"
+        } else {
+            "issue"
+        }
+    }
+
+    /// Numbers of the markable rows on screen, and the selected row
+    fn mark_rows(&self) -> Option<(Vec<u64>, usize)> {
+        match self.mark_tab()? {
+            RepoTab::PullRequests => {
+                Some((self.prs.iter().map(|pr| pr.number).collect(), self.pr_index))
+            }
+            _ => Some((
+                self.issues.iter().map(|issue| issue.number).collect(),
+                self.issue_index,
+            )),
+        }
+    }
+
+    /// Marks of the list on screen, while there are any (or a range is
+    /// started)
+    pub fn marks(&self) -> Option<&Marks> {
+        let tab = self.mark_tab()?;
+        (self.marks.tab == tab
+            && self.marks.repo == self.current_repo
+            && (!self.marks.numbers.is_empty() || self.marks.anchor.is_some()))
+        .then_some(&self.marks)
+    }
+
+    /// The marks for the list on screen, dropping any left on another list
+    fn marks_here(&mut self) -> Option<&mut Marks> {
+        let tab = self.mark_tab()?;
+        if self.marks.tab != tab || self.marks.repo != self.current_repo {
+            self.marks = Marks {
+                repo: self.current_repo.clone(),
+                tab,
+                ..Marks::default()
+            };
+        }
+        Some(&mut self.marks)
+    }
+
+    /// Marked numbers of the list on screen, in order
+    pub fn marked_numbers(&self) -> Vec<u64> {
+        self.marks()
+            .map(|marks| marks.numbers.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Task-list items of the open PR's description
+    pub fn pr_tasks(&self) -> Vec<TaskItem> {
+        self.current_pr
+            .as_ref()
+            .and_then(|pr| pr.body.as_deref())
+            .map(task_items)
+            .unwrap_or_default()
+    }
+
+    /// Lines of the PR detail body: the description, then the conversation.
+    /// Shared by render, search and scrolling so line indices always agree.
+    pub fn pr_detail_lines(&self) -> Vec<(PrLine, String)> {
+        let Some(pr) = &self.current_pr else {
+            return Vec::new();
+        };
+
+        let mut lines: Vec<(PrLine, String)> = pr
+            .body
+            .as_deref()
+            .unwrap_or("No description provided.")
+            .lines()
+            .map(|l| (PrLine::Body, l.replace('\t', "    ")))
+            .collect();
+        // Task-list items as checkboxes, at the item's indent
+        for task in self.pr_tasks() {
+            if let Some((_, text)) = lines.get_mut(task.line) {
+                let indent = text.len() - text.trim_start().len();
+                let mark = if task.done { "☑" } else { "☐" };
+                *text = format!("{}{} {}", &text[..indent], mark, task.text);
+            }
+        }
+
+        if !self.pr_commits.is_empty() {
+            self.push_commit_lines(&mut lines);
+        }
+        if !self.pr_files.is_empty() {
+            self.push_file_lines(&mut lines);
+        }
+        if !self.pr_comments.is_empty() {
+            self.push_conversation_lines(&mut lines, pr.number);
+        }
+        if !self.review_threads.is_empty() {
+            self.push_thread_lines(&mut lines, pr.number);
+        }
+        self.push_link_lines(&mut lines);
+
+        // The description and comments wrap to the pane; headers and list
+        // entries stay on one line
+        let width = self.detail_width.get();
+        lines
+            .into_iter()
+            .flat_map(|(kind, text)| match kind {
+                PrLine::Body | PrLine::Comment if width > 0 => wrap_line(&text, width)
+                    .into_iter()
+                    .map(|line| (kind, line))
+                    .collect(),
+                _ => vec![(kind, text)],
+            })
+            .collect()
+    }
+
+    /// Numbered footer of the URLs in the description and comments, so one
+    /// can be picked with `o` or `y`
+    fn push_link_lines(&self, lines: &mut Vec<(PrLine, String)>) {
+        let links = self.pr_body_links();
+        if links.is_empty() {
+            return;
+        }
+        lines.push((PrLine::Body, String::new()));
+        lines.push((PrLine::Section, format!("── Links ({})", links.len())));
+        for (index, link) in links.iter().enumerate() {
+            lines.push((PrLine::Link { index }, link.label(index)));
+        }
+    }
+
+    fn push_file_lines(&self, lines: &mut Vec<(PrLine, String)>) {
+        lines.push((PrLine::Body, String::new()));
+        lines.push((
+            PrLine::Section,
+            format!("── Files changed ({})", self.pr_files.len()),
+        ));
+        for (index, file) in self.pr_files.iter().enumerate() {
+            let status = match file.status.as_str() {
+                "added" => 'A',
+                "removed" => 'D',
+                "renamed" => 'R',
+                _ => 'M',
+            };
+            lines.push((
+                PrLine::File { index },
+                format!(
+                    "{} {}  +{} -{}",
+                    status, file.filename, file.additions, file.deletions
+                ),
+            ));
+        }
+    }
+
+    fn push_commit_lines(&self, lines: &mut Vec<(PrLine, String)>) {
+        lines.push((PrLine::Body, String::new()));
+        lines.push((
+            PrLine::Section,
+            format!("── Commits ({})", self.pr_commits.len()),
+        ));
+        for (index, commit) in self.pr_commits.iter().enumerate() {
+            lines.push((
+                PrLine::Commit { index },
+                format!(
+                    "{} {}  @{}",
+                    &commit.sha[..7.min(commit.sha.len())],
+                    commit.message,
+                    commit.author
+                ),
+            ));
+        }
+    }
+
+    fn push_conversation_lines(&self, lines: &mut Vec<(PrLine, String)>, number: u64) {
+        let bot_count = self
+            .pr_comments
+            .iter()
+            .filter(|c| self.ui_config.is_bot(&c.author))
+            .count();
+        let mut section = format!("── Conversation ({} comments)", self.pr_comments.len());
+        if bot_count > 0 {
+            if self.hide_bot_comments {
+                section.push_str(&format!(" · {} bot hidden", bot_count));
+            } else {
+                section.push_str(&format!(" · {} bot", bot_count));
+            }
+        }
+        lines.push((PrLine::Body, String::new()));
+        lines.push((PrLine::Section, section));
+
+        for comment in &self.pr_comments {
+            let bot = self.ui_config.is_bot(&comment.author);
+            if bot && self.hide_bot_comments {
+                continue;
+            }
+            lines.push((PrLine::Body, String::new()));
+            lines.push((
+                PrLine::CommentHeader {
+                    bot,
+                    target: ReactionTarget::Comment {
+                        pr: number,
+                        id: comment.id,
+                    },
+                },
+                format!(
+                    "@{} · {}",
+                    comment.author,
+                    comment.created_at.format("%Y-%m-%d %H:%M")
+                ),
+            ));
+            for l in comment.body.lines() {
+                lines.push((PrLine::Comment, l.replace('\t', "    ")));
+            }
+        }
+    }
+
+    fn push_thread_lines(&self, lines: &mut Vec<(PrLine, String)>, number: u64) {
+        let resolved_count = self.review_threads.iter().filter(|t| t.resolved).count();
+        let mut section = format!("── Review threads ({})", self.review_threads.len());
+        if resolved_count > 0 {
+            if self.hide_resolved_threads {
+                section.push_str(&format!(" · {} resolved hidden", resolved_count));
+            } else {
+                section.push_str(&format!(" · {} resolved", resolved_count));
+            }
+        }
+        lines.push((PrLine::Body, String::new()));
+        lines.push((PrLine::Section, section));
+
+        for index in self.visible_thread_indices() {
+            let thread = &self.review_threads[index];
+            let location = match (&thread.path, thread.line) {
+                (Some(path), Some(line)) => format!("{}:{}", path, line),
+                (Some(path), None) => path.clone(),
+                _ => "general".to_string(),
+            };
+            let status = if thread.resolved {
+                "✓ resolved"
+            } else {
+                "○ unresolved"
+            };
+            lines.push((PrLine::Body, String::new()));
+            lines.push((
+                PrLine::ThreadHeader {
+                    index,
+                    resolved: thread.resolved,
+                },
+                format!("{} · {}", status, location),
+            ));
+            for comment in &thread.comments {
+                lines.push((
+                    PrLine::CommentHeader {
+                        bot: self.ui_config.is_bot(&comment.author),
+                        target: ReactionTarget::ReviewComment {
+                            pr: number,
+                            id: comment.id,
+                        },
+                    },
+                    format!(
+                        "  @{} · {}",
+                        comment.author,
+                        comment.created_at.format("%Y-%m-%d %H:%M")
+                    ),
+                ));
+                for l in comment.body.lines() {
+                    lines.push((PrLine::Comment, format!("  {}", l.replace('\t', "    "))));
+                }
+            }
+        }
+    }
+
+    /// Indices into `review_threads` that are currently shown
+    fn visible_thread_indices(&self) -> Vec<usize> {
+        self.review_threads
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| !(self.hide_resolved_threads && t.resolved))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Move the thread selection by `delta` visible threads and scroll to it
+    fn step_thread(&mut self, delta: isize) {
+        let visible = self.visible_thread_indices();
+        if visible.is_empty() {
+            return;
+        }
+        let next = match visible.iter().position(|&i| i == self.thread_index) {
+            Some(pos) => (pos as isize + delta).clamp(0, visible.len() as isize - 1) as usize,
+            None => 0,
+        };
+        self.thread_index = visible[next];
+        if let Some(line) = self.pr_detail_lines().iter().position(
+            |(kind, _)| matches!(kind, PrLine::ThreadHeader { index, .. } if *index == self.thread_index),
+        ) {
+            self.scroll_offset = line.min(self.max_scroll_offset());
+        }
+    }
+
+    /// `#123` and `@user` references in the open PR's description and
+    /// comments, with the `pr_detail_lines` line each is on
+    pub fn pr_xrefs(&self) -> Vec<(usize, Range<usize>, CrossRef)> {
+        self.pr_detail_lines()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, (kind, _))| matches!(kind, PrLine::Body | PrLine::Comment))
+            .flat_map(|(line, (_, text))| {
+                crate::links::cross_refs(&text)
+                    .into_iter()
+                    .map(move |(range, target)| (line, range, target))
+            })
+            .collect()
+    }
+
+    /// The reference Tab or `n` picked, while it's still there
+    pub fn selected_xref(&self) -> Option<(usize, Range<usize>, CrossRef)> {
+        self.pr_xrefs().into_iter().nth(self.xref_index?)
+    }
+
+    /// Pick the next reference (previous for a negative `delta`), wrapping
+    /// around, and scroll it into view
+    fn step_xref(&mut self, delta: isize) {
+        let xrefs = self.pr_xrefs();
+        if xrefs.is_empty() {
+            self.flash_message = Some((
+                "No #numbers or @mentions here".to_string(),
+                std::time::Instant::now(),
+            ));
+            return;
+        }
+        let next = match self.xref_index {
+            Some(i) => (i as isize + delta).rem_euclid(xrefs.len() as isize) as usize,
+            None if delta < 0 => xrefs.len() - 1,
+            None => 0,
+        };
+        self.xref_index = Some(next);
+        let line = xrefs[next].0;
+        if line < self.scroll_offset || line >= self.scroll_offset + self.page_height.get().max(1) {
+            self.scroll_offset = line.min(self.max_scroll_offset());
+        }
+    }
+
+    /// Follow the picked reference: a number opens that PR (or the issue on
+    /// the repo's Issues tab), a user their profile in the browser
+    fn open_xref(&mut self) {
+        let Some((_, _, target)) = self.selected_xref() else {
+            return;
+        };
+        match target {
+            CrossRef::User(user) => {
+                let url = self.forge.web_url("", "", "user", &user);
+                let _ = open::that(url);
+            }
+            CrossRef::Number(number) => {
+                if self
+                    .current_pr
+                    .as_ref()
+                    .is_some_and(|pr| pr.number == number)
+                {
+                    return;
+                }
+                if let Some((owner, repo)) = self.current_repo.clone() {
+                    self.load_id += 1;
+                    self.loading = true;
+                    self.spawn_jump(owner, repo, number, self.load_id);
+                }
+            }
+        }
+    }
+
+    fn step_file(&mut self, delta: isize) {
+        if self.pr_files.is_empty() {
+            return;
+        }
+        self.pr_focus = PrFocus::Files;
+        let prev = self.file_index;
+        self.file_index =
+            (self.file_index as isize + delta).clamp(0, self.pr_files.len() as isize - 1) as usize;
+        if self.file_index != prev {
+            self.diff_cursor = 0;
+        }
+        if let Some(line) = self.pr_detail_lines().iter().position(
+            |(kind, _)| matches!(kind, PrLine::File { index } if *index == self.file_index),
+        ) {
+            self.scroll_offset = line.min(self.max_scroll_offset());
+        }
+    }
+
+    /// Move the commit selection by `delta` and scroll to it; 0 just scrolls
+    fn step_pr_commit(&mut self, delta: isize) {
+        if self.pr_commits.is_empty() {
+            return;
+        }
+        self.pr_focus = PrFocus::Commits;
+        self.pr_commit_index = (self.pr_commit_index as isize + delta)
+            .clamp(0, self.pr_commits.len() as isize - 1) as usize;
+        if let Some(line) = self.pr_detail_lines().iter().position(
+            |(kind, _)| matches!(kind, PrLine::Commit { index } if *index == self.pr_commit_index),
+        ) {
+            self.scroll_offset = line.min(self.max_scroll_offset());
+        }
+    }
+
+    /// PageUp/PageDown step: the height of what's on screen
+    fn page_size(&self) -> usize {
+        match self.page_height.get() {
+            0 => DEFAULT_PAGE_SIZE,
+            height => height,
+        }
+    }
+
+    /// Calculate max scroll offset for current detail view: the last line
+    /// at the bottom once the viewport is known, at the top before
+    fn max_scroll_offset(&self) -> usize {
+        let total = match self.screen {
+            Screen::PrDetail => self.pr_detail_lines().len(),
+            Screen::CommitDetail => {
+                if let Some(commit) = &self.current_commit {
+                    // Header lines (4) + message lines + blank + file entries
+                    let mut lines = 5; // header, blank, stats, blank, "Message:"
+                    lines += commit.message.lines().count();
+                    lines += 1; // blank after message
+                    for file in &commit.files {
+                        lines += 1; // file header
+                        if let Some(patch) = &file.patch {
+                            lines += patch.lines().count();
+                        }
+                        lines += 1; // blank after file
+                    }
+                    let links = self.body_links().len();
+                    if links > 0 {
+                        lines += links + 1; // "Links:" and one per link
+                    }
+                    lines
+                } else {
+                    0
+                }
+            }
+            Screen::Logs => self.log_lines.len(),
+            _ => 0,
+        };
+        total.saturating_sub(self.page_height.get().max(1))
+    }
+
+    /// Re-read the tail of the log for the debug screen, staying at the
+    /// bottom if that's where the view was
+    fn read_logs(&mut self) {
+        let Some(path) = &self.log_path else {
+            return;
+        };
+        let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        if size == self.log_size && !self.log_lines.is_empty() {
+            return;
+        }
+        let following = self.scroll_offset >= self.max_scroll_offset();
+        self.log_lines = crate::logging::tail(path, LOG_LINES);
+        self.log_size = size;
+        if following {
+            self.scroll_offset = self.max_scroll_offset();
+        }
+    }
+
+    fn spawn_load_commit_detail(&self, owner: String, repo: String, sha: String, load_id: u64) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let key = format!(
+            "commit_{}_{}",
+            cache::forge_repo_key(&self.forge_name, &owner, &repo),
+            &sha[..7.min(sha.len())]
+        );
+
+        if let Some(cached) = self.cached::<CommitDetail>(&key) {
+            tx.send(Action::CommitDetailLoaded(Box::new(cached), load_id))
+                .ok();
+        }
+        self.spawn_load_commit_checks(owner.clone(), repo.clone(), sha.clone(), load_id);
+
+        self.spawn_load("Load commit", load_id, async move {
+            match forge.get_commit(&owner, &repo, &sha).await {
+                Ok(commit) => {
+                    cache::write(&key, &commit);
+                    tx.send(Action::CommitDetailLoaded(Box::new(commit), load_id))
+                        .ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    /// Best-effort, like the PR review progress checks
+    fn spawn_load_commit_checks(&self, owner: String, repo: String, sha: String, load_id: u64) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let key = format!(
+            "commit_checks_{}_{}",
+            cache::forge_repo_key(&self.forge_name, &owner, &repo),
+            &sha[..7.min(sha.len())]
+        );
+
+        if let Some(cached) = self.cached::<Vec<CommitCheck>>(&key) {
+            tx.send(Action::CommitChecksLoaded(sha.clone(), cached, load_id))
+                .ok();
+        }
+
+        self.spawn_load("Load commit checks", load_id, async move {
+            if let Ok(checks) = forge.get_commit_checks(&owner, &repo, &sha).await {
+                cache::write(&key, &checks);
+                tx.send(Action::CommitChecksLoaded(sha, checks, load_id))
+                    .ok();
+            }
+        });
+    }
+
+    /// Stream the PR's diff for the pager, spooling huge ones to a temp file
+    fn spawn_load_pr_diff(&self, owner: String, repo: String, number: u64) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task(DIFF_TASK, async move {
+            let result = async {
+                match forge.stream_pr_diff(&owner, &repo, number).await? {
+                    Some(response) => {
+                        crate::http::spool(response, DIFF_SPOOL_BYTES, |received, total| {
+                            tx.send(Action::DiffProgress(received, total)).ok();
+                        })
+                        .await
+                    }
+                    None => forge
+                        .get_pr_diff(&owner, &repo, number)
+                        .await
+                        .map(Spooled::Text),
+                }
+            }
+            .await;
+            match result {
+                Ok(diff) => tx.send(Action::DiffLoaded(diff)).ok(),
+                Err(e) => tx.send(Action::DiffFailed(e)).ok(),
+            };
+        });
+    }
+
+    fn spawn_close_pr(&self, owner: String, repo: String, number: u64) {
+        self.spawn_mutation(
+            Mutation::ClosePr {
+                owner,
+                repo,
+                number,
+            },
+            Action::PrClosed,
+            None,
+        );
+    }
+
+    /// Merge, then delete the head branch if `delete_branch` names it. With
+    /// `auto`, the forge is asked to merge once checks pass instead.
+    fn spawn_merge_pr(
+        &self,
+        owner: String,
+        repo: String,
+        number: u64,
+        method: MergeMethod,
+        delete_branch: Option<String>,
+        auto: bool,
+    ) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Merge PR", async move {
+            if auto {
+                let result = forge
+                    .enable_auto_merge(
+                        &owner,
+                        &repo,
+                        number,
+                        method.as_api_str(),
+                        delete_branch.is_some(),
+                    )
+                    .await;
+                match result {
+                    Ok(()) => tx.send(Action::AutoMergeEnabled(number)).ok(),
+                    Err(e) => tx.send(Action::Error(e)).ok(),
+                };
+                return;
+            }
+            if let Err(e) = forge
+                .merge_pr(&owner, &repo, number, method.as_api_str())
+                .await
+            {
+                tx.send(Action::Error(e)).ok();
+                return;
+            }
+            tx.send(Action::PrMerged).ok();
+            if let Some(branch) = delete_branch {
+                match forge.delete_branch(&owner, &repo, &branch).await {
+                    Ok(()) => tx.send(Action::BranchDeleted(branch)).ok(),
+                    Err(e) => tx.send(Action::Error(e)).ok(),
+                };
+            }
+        });
+    }
+
+    fn spawn_load_reviewer_load(&self, owner: String, repo: String) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Load reviewer load", async move {
+            match forge.reviewer_load(&owner, &repo).await {
+                Ok(load) => {
+                    tx.send(Action::ReviewerLoadLoaded(load)).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    fn spawn_request_reviewers(&self, owner: String, repo: String, number: u64, login: String) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Request reviewers", async move {
+            match forge
+                .request_reviewers(&owner, &repo, number, std::slice::from_ref(&login))
+                .await
+            {
+                Ok(()) => {
+                    tx.send(Action::ReviewersRequested(login)).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    fn spawn_revert_pr(&self, owner: String, repo: String, pr: PullRequest, commits: Vec<Commit>) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Revert PR", async move {
+            match forge.revert_pr(&owner, &repo, &pr, &commits).await {
+                Ok(number) => {
+                    tx.send(Action::PrReverted(number)).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    /// A repo whose templates can't be read still gets a blank form
+    fn spawn_load_issue_templates(&self, owner: String, repo: String) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Load issue templates", async move {
+            match forge.list_issue_templates(&owner, &repo).await {
+                Ok(templates) => {
+                    tx.send(Action::IssueTemplatesLoaded(templates)).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                    tx.send(Action::IssueTemplatesLoaded(Vec::new())).ok();
+                }
+            }
+        });
+    }
+
+    /// Write the description back with each of `shown` set as `checked`
+    /// says. It's read again first, so edits made since it loaded survive;
+    /// if its task list changed meanwhile, nothing is written.
+    fn spawn_update_checklist(
+        &self,
+        owner: String,
+        repo: String,
+        number: u64,
+        shown: Vec<TaskItem>,
+        checked: Vec<bool>,
+    ) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Update checklist", async move {
+            let result = async {
+                let pr = forge.get_pr(&owner, &repo, number).await?;
+                let mut body = pr.body.unwrap_or_default();
+                let tasks = task_items(&body);
+                let same = tasks.len() == shown.len()
+                    && tasks.iter().zip(&shown).all(|(a, b)| a.text == b.text);
+                if !same {
+                    return Err(GritError::Api(
+                        "The task list changed on the forge; refresh and try again".to_string(),
+                    ));
+                }
+                for (task, &done) in tasks.iter().zip(&checked) {
+                    if task.done != done {
+                        body = set_task(&body, task, done);
+                    }
+                }
+                forge.update_pr(&owner, &repo, number, &body).await
+            }
+            .await;
+            match result {
+                Ok(()) => {
+                    tx.send(Action::ChecklistUpdated).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    fn spawn_load_labels(&self, owner: String, repo: String) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Load labels", async move {
+            match forge.list_labels(&owner, &repo).await {
+                Ok(labels) => {
+                    tx.send(Action::LabelsLoaded(labels)).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    fn spawn_update_labels(
+        &self,
+        owner: String,
+        repo: String,
+        kind: &'static str,
+        number: u64,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Update labels", async move {
+            let result = async {
+                if !add.is_empty() {
+                    forge.add_labels(&owner, &repo, kind, number, &add).await?;
+                }
+                if !remove.is_empty() {
+                    forge
+                        .remove_labels(&owner, &repo, kind, number, &remove)
+                        .await?;
+                }
+                Ok::<(), crate::error::GritError>(())
+            }
+            .await;
+            match result {
+                Ok(()) => {
+                    tx.send(Action::LabelsUpdated).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    fn spawn_load_milestones(&self, owner: String, repo: String, purpose: MilestonePurpose) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Load milestones", async move {
+            match forge.list_milestones(&owner, &repo).await {
+                Ok(milestones) => {
+                    tx.send(Action::MilestonesLoaded(milestones, purpose)).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    fn spawn_set_milestone(
+        &self,
+        owner: String,
+        repo: String,
+        number: u64,
+        milestone: Option<u64>,
+    ) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Set milestone", async move {
+            match forge.set_milestone(&owner, &repo, number, milestone).await {
+                Ok(()) => {
+                    tx.send(Action::MilestoneSet).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    /// Write the form's issue or PR; `body` is the description of a new issue
+    pub fn spawn_save_form(
+        &self,
+        owner: String,
+        repo: String,
+        form: Form,
+        body: String,
+        draft: Option<String>,
+    ) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let label = match form.target {
+            FormTarget::NewIssue => "Open issue",
+            FormTarget::Issue(_) => "Update issue",
+            FormTarget::Pr(_) => "Update PR",
+        };
+        self.spawn_task(label, async move {
+            let result = async {
+                let milestones = if form.values[3].trim().is_empty() {
+                    Vec::new()
+                } else {
+                    forge.list_milestones(&owner, &repo).await?
+                };
+                let fields = form.fields(&milestones)?;
+                match form.target {
+                    FormTarget::NewIssue => forge.create_issue(&owner, &repo, &fields, &body).await,
+                    FormTarget::Issue(number) => forge
+                        .update_fields(&owner, &repo, "issue", number, &fields)
+                        .await
+                        .map(|()| number),
+                    FormTarget::Pr(number) => forge
+                        .update_fields(&owner, &repo, "pr", number, &fields)
+                        .await
+                        .map(|()| number),
+                }
+            }
+            .await;
+            match result {
+                Ok(number) => {
+                    if let Some(key) = draft {
+                        drafts::clear(&key);
+                    }
+                    tx.send(Action::FormSaved(form.target, number)).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    fn spawn_set_thread_resolved(
+        &self,
+        owner: String,
+        repo: String,
+        number: u64,
+        thread_id: String,
+        resolved: bool,
+    ) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Resolve thread", async move {
+            match forge
+                .set_thread_resolved(&owner, &repo, number, &thread_id, resolved)
+                .await
+            {
+                Ok(()) => {
+                    tx.send(Action::ThreadResolved {
+                        thread_id,
+                        resolved,
+                    })
+                    .ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    fn spawn_close_issue(&self, owner: String, repo: String, number: u64) {
+        self.spawn_mutation(
+            Mutation::CloseIssue {
+                owner,
+                repo,
+                number,
+            },
+            Action::IssueClosed,
+            None,
+        );
+    }
+
+    /// Run `op` on each of `numbers` in the current tab, a few calls at a
+    /// time, reporting each one that finishes
+    fn spawn_bulk(&mut self, op: BulkOp, numbers: Vec<u64>) {
+        let Some((owner, repo)) = self.current_repo.clone() else {
+            return;
+        };
+        if self.bulk.is_some() {
+            self.error = Some("A bulk action is already running".to_string());
+            return;
+        }
+        let kind = self.mark_kind();
+        let total = numbers.len();
+        self.bulk = Some(Bulk {
+            op: op.clone(),
+            kind,
+            done: 0,
+            total,
+        });
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let label = format!("{} {} {}", op.verb(), total, item_noun(kind, total));
+        self.spawn_task(label, async move {
+            let mut calls = futures::stream::iter(numbers)
+                .map(|number| {
+                    let (forge, owner, repo, op) =
+                        (Arc::clone(&forge), owner.clone(), repo.clone(), op.clone());
+                    async move {
+                        let result = match op {
+                            BulkOp::Close if kind == "pr" => {
+                                forge.close_pr(&owner, &repo, number).await
+                            }
+                            BulkOp::Close => forge.close_issue(&owner, &repo, number).await,
+                            BulkOp::AddLabels(labels) => {
+                                forge.add_labels(&owner, &repo, kind, number, &labels).await
+                            }
+                        };
+                        (number, result)
+                    }
+                })
+                .buffer_unordered(BULK_CONCURRENCY);
+            let (mut done, mut failed) = (0, Vec::new());
+            while let Some((number, result)) = calls.next().await {
+                done += 1;
+                if let Err(e) = result {
+                    failed.push((number, e.to_string()));
+                }
+                tx.send(Action::BulkProgress(done)).ok();
+            }
+            failed.sort();
+            tx.send(Action::BulkDone(failed)).ok();
+        });
+    }
+
+    fn spawn_fork_repo(&self, owner: String, repo: String) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Fork repo", async move {
+            match forge.fork_repo(&owner, &repo).await {
+                Ok(fork) => tx.send(Action::RepoForked(fork)).ok(),
+                Err(e) => tx.send(Action::Error(e)).ok(),
+            };
+        });
+    }
+
+    /// Best-effort: a repo whose upstream can't be compared just shows no status
+    fn spawn_load_fork_status(&self, owner: String, repo: String) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        let key = format!(
+            "fork_status_{}",
+            cache::forge_repo_key(&self.forge_name, &owner, &repo)
+        );
+
+        if let Some(cached) = self.cached::<Option<ForkStatus>>(&key) {
+            tx.send(Action::ForkStatusLoaded(
+                owner.clone(),
+                repo.clone(),
+                cached,
+            ))
+            .ok();
+        }
+
+        self.spawn_task("Compare fork", async move {
+            if let Ok(status) = forge.get_fork_status(&owner, &repo).await {
+                cache::write(&key, &status);
+                tx.send(Action::ForkStatusLoaded(owner, repo, status)).ok();
+            }
+        });
+    }
+
+    fn spawn_sync_fork(&self, owner: String, repo: String, branch: String) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Sync fork", async move {
+            match forge.sync_fork(&owner, &repo, &branch).await {
+                Ok(()) => tx.send(Action::ForkSynced(branch)).ok(),
+                Err(e) => tx.send(Action::Error(e)).ok(),
+            };
+        });
+    }
+
+    fn spawn_load_star_state(&self, owner: String, repo: String) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Check star", async move {
+            match forge.is_starred(&owner, &repo).await {
+                Ok(starred) => tx.send(Action::StarStateLoaded(owner, repo, starred)).ok(),
+                Err(e) => tx.send(Action::Error(e)).ok(),
+            };
+        });
+    }
+
+    /// Show the new star state and count right away, then tell the forge;
+    /// `StarFailed` undoes it if that fails
+    fn set_star(&mut self, owner: String, repo: String, starred: bool) {
+        self.apply_star(&owner, &repo, starred);
+        let verb = if starred { "Starred" } else { "Unstarred" };
+        self.flash_message = Some((
+            format!("{} {}/{}.", verb, owner, repo),
+            std::time::Instant::now(),
+        ));
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Star repo", async move {
+            if let Err(e) = forge.set_star(&owner, &repo, starred).await {
+                tx.send(Action::StarFailed(owner, repo, e)).ok();
+            }
+        });
+    }
+
+    fn spawn_set_subscription(&self, owner: String, repo: String, subscription: Subscription) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Watch repo", async move {
+            match forge.set_subscription(&owner, &repo, subscription).await {
+                Ok(()) => tx
+                    .send(Action::SubscriptionSet(
+                        format!("{}/{}", owner, repo),
+                        subscription,
+                    ))
+                    .ok(),
+                Err(e) => tx.send(Action::Error(e)).ok(),
+            };
+        });
+    }
+
+    fn spawn_load_repo_meta(&self, owner: String, repo: String) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Load repo settings", async move {
+            match forge.get_repo_meta(&owner, &repo).await {
+                Ok(meta) => {
+                    tx.send(Action::RepoMetaLoaded(owner, repo, meta)).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    pub fn spawn_update_repo_meta(&self, owner: String, repo: String, meta: RepoMeta) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Update repo", async move {
+            match forge.update_repo_meta(&owner, &repo, &meta).await {
+                Ok(()) => {
+                    tx.send(Action::RepoMetaUpdated).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    /// `draft` names the saved copy of `body` to drop once it's posted
+    pub fn spawn_comment(
+        &self,
+        owner: String,
+        repo: String,
+        number: u64,
+        body: String,
+        draft: Option<String>,
+    ) {
+        self.spawn_mutation(
+            Mutation::Comment {
+                owner,
+                repo,
+                number,
+                body,
+            },
+            Action::CommentPosted,
+            draft,
+        );
+    }
+
+    pub fn spawn_add_reaction(
+        &self,
+        owner: String,
+        repo: String,
+        target: ReactionTarget,
+        reaction: Reaction,
+    ) {
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Add reaction", async move {
+            match forge.add_reaction(&owner, &repo, target, reaction).await {
+                Ok(()) => {
+                    tx.send(Action::ReactionAdded(reaction)).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    pub fn spawn_commit_comment(
+        &self,
+        owner: String,
+        repo: String,
+        sha: String,
+        body: String,
+        draft: Option<String>,
+    ) {
+        self.spawn_mutation(
+            Mutation::CommitComment {
+                owner,
+                repo,
+                sha,
+                body,
+            },
+            Action::CommentPosted,
+            draft,
+        );
+    }
+
+    /// Send `mutation`, dispatching `done` once it lands. Offline, or if no
+    /// connection to the forge could be made, it goes to the outbox instead;
+    /// either way the `draft` it was written in isn't needed any more. A
+    /// timeout is only an error: the forge may have it already, and sending
+    /// it again later could post it twice.
+    fn spawn_mutation(&self, mutation: Mutation, done: Action, draft: Option<String>) {
+        let tx = self.action_tx.clone();
+        if self.offline {
+            if let Some(key) = &draft {
+                drafts::clear(key);
+            }
+            tx.send(Action::MutationQueued(mutation)).ok();
+            return;
+        }
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task(mutation.describe(), async move {
+            match mutation.apply(forge.as_ref()).await {
+                Ok(()) => {
+                    if let Some(key) = &draft {
+                        drafts::clear(key);
+                    }
+                    tx.send(done).ok();
+                }
+                Err(GritError::Network(_)) => {
+                    if let Some(key) = &draft {
+                        drafts::clear(key);
+                    }
+                    tx.send(Action::MutationQueued(mutation)).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    pub fn spawn_submit_review(
+        &self,
+        owner: String,
+        repo: String,
+        number: u64,
+        event: crate::types::ReviewEvent,
+        body: String,
+        draft: Option<String>,
+    ) {
+        let comments = self
+            .review_drafts
+            .get(&(owner.clone(), repo.clone(), number))
+            .cloned()
+            .unwrap_or_default();
+        // Queued line comments can go out without a summary body
+        if body.trim().is_empty() && comments.is_empty() {
+            return;
+        }
+        let tx = self.action_tx.clone();
+        let forge = Arc::clone(&self.forge);
+        self.spawn_task("Submit review", async move {
+            match forge
+                .submit_review(&owner, &repo, number, event.as_api_str(), &body, &comments)
+                .await
+            {
+                Ok(()) => {
+                    if let Some(key) = &draft {
+                        drafts::clear(key);
+                    }
+                    tx.send(Action::ReviewSubmitted).ok();
+                }
+                Err(e) => {
+                    tx.send(Action::Error(e)).ok();
+                }
+            }
+        });
+    }
+
+    /// The comment at the top of the PR detail view, or the PR itself
+    pub fn reaction_target(&self) -> Option<ReactionTarget> {
+        let pr = self.current_pr.as_ref()?;
+        let lines = self.pr_detail_lines();
+        let top = self.scroll_offset.min(lines.len().saturating_sub(1));
+        for (kind, _) in lines.iter().take(top + 1).rev() {
+            match kind {
+                PrLine::CommentHeader { target, .. } => return Some(*target),
+                PrLine::Comment => continue,
+                _ => break,
+            }
+        }
+        Some(ReactionTarget::Pr(pr.number))
+    }
+
+    /// Tracker references in the current item's title and description
+    pub fn tracker_links(&self) -> Vec<TrackerLink> {
+        if self.link_rules.is_empty() {
+            return Vec::new();
+        }
+        let texts: Vec<&str> = match self.screen {
+            Screen::PrDetail => self
+                .current_pr
+                .iter()
+                .flat_map(|pr| [pr.title.as_str(), pr.body.as_deref().unwrap_or("")])
+                .collect(),
+            Screen::CommitDetail => self
+                .current_commit
+                .iter()
+                .map(|c| c.message.as_str())
+                .collect(),
+            Screen::RepoView => match self.repo_tab {
+                RepoTab::PullRequests => self
+                    .prs
+                    .get(self.pr_index)
+                    .iter()
+                    .flat_map(|pr| {
+                        let body = self.preview.prs.get(&pr.number);
+                        [
+                            pr.title.as_str(),
+                            body.and_then(|p| p.body.as_deref()).unwrap_or(""),
+                        ]
+                    })
+                    .collect(),
+                RepoTab::Issues => self
+                    .issues
+                    .get(self.issue_index)
+                    .iter()
+                    .flat_map(|issue| {
+                        let body = self.preview.issue_bodies.get(&issue.number);
+                        [
+                            issue.title.as_str(),
+                            body.and_then(|b| b.as_deref()).unwrap_or(""),
+                        ]
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+        crate::links::find(&self.link_rules, texts)
+    }
+
+    /// Put `url` on the clipboard
+    fn yank(&mut self, url: &str) {
+        if let Ok(mut clipboard) = arboard::Clipboard::new() {
+            if clipboard.set_text(url).is_ok() {
+                self.flash_message = Some(("URL copied!".to_string(), std::time::Instant::now()));
+            }
+        }
+    }
+
+    /// URLs in the current item's description (and on PR detail, in its
+    /// comments too), numbered as the Links footer shows them
+    pub fn body_links(&self) -> Vec<BodyLink> {
+        let body = match self.screen {
+            Screen::PrDetail => return self.pr_body_links(),
+            Screen::CommitDetail => self.current_commit.as_ref().map(|c| c.message.as_str()),
+            Screen::RepoView => match self.repo_tab {
+                RepoTab::PullRequests => self
+                    .prs
+                    .get(self.pr_index)
+                    .and_then(|pr| self.preview.prs.get(&pr.number)?.body.as_deref()),
+                RepoTab::Issues => self
+                    .issues
+                    .get(self.issue_index)
+                    .and_then(|issue| self.preview.issue_bodies.get(&issue.number)?.as_deref()),
+                _ => None,
+            },
+            _ => None,
+        };
+        crate::links::urls(body)
+    }
+
+    /// URLs in the open PR's description and the comments on show
+    fn pr_body_links(&self) -> Vec<BodyLink> {
+        let Some(pr) = &self.current_pr else {
+            return Vec::new();
+        };
+        let comments = self
+            .pr_comments
+            .iter()
+            .filter(|c| !(self.hide_bot_comments && self.ui_config.is_bot(&c.author)));
+        let threads = self
+            .visible_thread_indices()
+            .into_iter()
+            .flat_map(|i| &self.review_threads[i].comments);
+        crate::links::urls(
+            pr.body
+                .as_deref()
+                .into_iter()
+                .chain(comments.chain(threads).map(|c| c.body.as_str())),
+        )
+    }
+
+    /// What `o` and `y` can pick from: the forge page, then any tracker
+    /// references, then the URLs in the body
+    fn link_targets(&self) -> Vec<(String, String)> {
+        self.current_item_url()
+            .map(|url| ("Forge page".to_string(), url))
+            .into_iter()
+            .chain(
+                self.tracker_links()
+                    .into_iter()
+                    .map(|link| (format!("{}  {}", link.token, link.url), link.url)),
+            )
+            .chain(
+                self.body_links()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, link)| (link.label(i), link.url.clone())),
+            )
+            .collect()
+    }
+
+    /// Construct GitHub URL for the current item
+    pub(crate) fn current_item_url(&self) -> Option<String> {
+        match self.screen {
+            Screen::Home => match self.home_section {
+                HomeSection::ReviewRequests => {
+                    let req = self.review_requests.get(self.review_index)?;
+                    Some(self.forge.web_url(
+                        &req.repo_owner,
+                        &req.repo_name,
+                        "pr",
+                        &req.pr_number.to_string(),
+                    ))
+                }
+                HomeSection::MyPrs => {
+                    let pr = self.my_prs.get(self.my_pr_index)?;
+                    Some(self.forge.web_url(
+                        &pr.repo_owner,
+                        &pr.repo_name,
+                        "pr",
+                        &pr.number.to_string(),
+                    ))
+                }
+                HomeSection::Assigned => {
+                    let item = self.assigned.get(self.assigned_index)?;
+                    Some(self.issue_ref_url(item))
+                }
+                HomeSection::Mentions => {
+                    let item = self.mentions.get(self.mentions_index)?;
+                    Some(self.issue_ref_url(item))
+                }
+                HomeSection::Saved => {
+                    let item = self.saved.get(self.saved_index)?;
+                    Some(self.issue_ref_url(item))
+                }
+                HomeSection::Recent => {
+                    let item = self.recent.get(self.recent_index)?;
+                    Some(match item.number {
+                        Some(n) => {
+                            self.forge
+                                .web_url(&item.owner, &item.repo, "pr", &n.to_string())
+                        }
+                        None => self.forge.web_url(&item.owner, &item.repo, "repo", ""),
+                    })
+                }
+            },
+            Screen::RepoList => {
+                let repo = self.repos.get(self.repo_index)?;
+                Some(self.forge.web_url(&repo.owner, &repo.name, "repo", ""))
+            }
+            Screen::Browse => match self.browse.column {
+                BrowseColumn::PullRequests => {
+                    let (owner, repo) = self.browse.prs_repo.as_ref()?;
+                    let pr = self.browse.prs.get(self.browse.pr_index)?;
+                    Some(
+                        self.forge
+                            .web_url(owner, repo, "pr", &pr.number.to_string()),
+                    )
+                }
+                _ => {
+                    let (owner, name) = self.browse_selected_repo()?;
+                    Some(self.forge.web_url(&owner, &name, "repo", ""))
+                }
+            },
+            Screen::RepoView => {
+                let (owner, repo) = self.current_repo.as_ref()?;
+                match self.repo_tab {
+                    RepoTab::PullRequests => {
+                        let pr = self.prs.get(self.pr_index)?;
+                        Some(
+                            self.forge
+                                .web_url(owner, repo, "pr", &pr.number.to_string()),
+                        )
+                    }
+                    RepoTab::Issues => {
+                        let issue = self.issues.get(self.issue_index)?;
+                        Some(
+                            self.forge
+                                .web_url(owner, repo, "issue", &issue.number.to_string()),
+                        )
+                    }
+                    RepoTab::Commits => {
+                        let commit = self.commits.get(self.commit_index)?;
+                        Some(self.forge.web_url(owner, repo, "commit", &commit.sha))
+                    }
+                    RepoTab::Actions => {
+                        let id = if self.workflow_summary {
+                            self.workflows.get(self.workflow_index)?.latest_id
+                        } else {
+                            self.action_runs.get(self.action_index)?.id
+                        };
+                        Some(
+                            self.forge
+                                .web_url(owner, repo, "action_run", &id.to_string()),
+                        )
+                    }
+                }
+            }
+            Screen::PrDetail | Screen::DiffView => {
+                let (owner, repo) = self.current_repo.as_ref()?;
+                let pr = self.current_pr.as_ref()?;
+                Some(
+                    self.forge
+                        .web_url(owner, repo, "pr", &pr.number.to_string()),
+                )
+            }
+            Screen::CommitDetail => {
+                let (owner, repo) = self.current_repo.as_ref()?;
+                let commit = self.current_commit.as_ref()?;
+                Some(self.forge.web_url(owner, repo, "commit", &commit.sha))
+            }
+            Screen::Logs => None,
+        }
+    }
+}
+
+/// Cached entries of owner/repo, or only those of its PR `number`; state such
+/// as drafts is left alone
+fn repo_cache_keys(forge_name: &str, owner: &str, repo: &str, number: Option<u64>) -> Vec<String> {
+    let repo_key = cache::forge_repo_key(forge_name, owner, repo);
+    let pr_suffix = number.map(|n| format!("{}_{}", repo_key, n));
+    cache::keys()
+        .into_iter()
+        .filter(|key| !crate::state::is_state_key(key))
+        .filter(|key| match &pr_suffix {
+            Some(suffix) => key.starts_with("pr_") && key.ends_with(suffix.as_str()),
+            None => key
+                .split_once(repo_key.as_str())
+                .is_some_and(|(_, rest)| rest.is_empty() || rest.starts_with('_')),
+        })
+        .collect()
+}
+
+fn pr_cache_key(forge_name: &str, owner: &str, repo: &str, number: u64) -> String {
+    format!(
+        "pr_{}_{}",
+        cache::forge_repo_key(forge_name, owner, repo),
+        number
+    )
+}
+
+fn issue_body_cache_key(forge_name: &str, owner: &str, repo: &str, number: u64) -> String {
+    format!(
+        "issue_body_{}_{}",
+        cache::forge_repo_key(forge_name, owner, repo),
+        number
+    )
+}
+
+fn commits_cache_key(forge_name: &str, owner: &str, repo: &str) -> String {
+    format!("commits_{}", cache::forge_repo_key(forge_name, owner, repo))
+}
+
+fn pr_comments_cache_key(forge_name: &str, owner: &str, repo: &str, number: u64) -> String {
+    format!(
+        "pr_comments_{}_{}",
+        cache::forge_repo_key(forge_name, owner, repo),
+        number
+    )
+}
+
+/// Warm the PR detail cache for the first few review requests, one at a time,
+/// so opening them from Home renders at once. PRs whose cached copy is at least
+/// as new as the request are skipped; failures are ignored.
+async fn prefetch_pr_details(
+    forge: Arc<dyn Forge>,
+    forge_name: &str,
+    requests: Vec<ReviewRequest>,
+) {
+    for req in requests {
+        let (owner, repo, number) = (&req.repo_owner, &req.repo_name, req.pr_number);
+        let key = pr_cache_key(forge_name, owner, repo, number);
+        if cache::read::<PullRequest>(&key).is_some_and(|pr| pr.updated_at >= req.updated_at) {
+            continue;
+        }
+        tokio::time::sleep(HOME_PREFETCH_SPACING).await;
+        if let Ok(pr) = forge.get_pr(owner, repo, number).await {
+            cache::write(&key, &pr);
+        }
+        if let Ok(comments) = forge.list_pr_comments(owner, repo, number).await {
+            cache::write(
+                &pr_comments_cache_key(forge_name, owner, repo, number),
+                &comments,
+            );
+        }
+    }
+}
+
+/// A merge popup checkbox row
+fn checkbox_item(checked: bool, label: &str) -> String {
+    let check = if checked { "[x]" } else { "[ ]" };
+    format!("{} {}", check, label)
+}
+
+/// Actions a count prefix repeats
+fn is_movement(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::ScrollUp
+            | Action::ScrollDown
+            | Action::PageUp
+            | Action::PageDown
+            | Action::SearchNext
+            | Action::SearchPrev
+            | Action::NextFile
+            | Action::PrevFile
+            | Action::NextPrCommit
+            | Action::PrevPrCommit
+            | Action::NextThread
+            | Action::PrevThread
+    )
+}
+
+/// Index of the previously selected item (matched by `key`) in a reloaded
+/// list, so a reorder doesn't silently move the cursor to a different item.
+/// Falls back to clamping the old index when the item is gone.
+/// Short name of a workspace: its screen, repo or PR
+fn workspace_label(
+    screen: Screen,
+    repo: &Option<(String, String)>,
+    pr: Option<&PullRequest>,
+) -> String {
+    match (screen, repo, pr) {
+        (Screen::Home, ..) => "Home".to_string(),
+        (Screen::RepoList | Screen::Browse, ..) => "Repos".to_string(),
+        (Screen::Logs, ..) => "Logs".to_string(),
+        (Screen::PrDetail | Screen::DiffView, Some((_, name)), Some(pr)) => {
+            format!("{} #{}", name, pr.number)
+        }
+        (_, Some((owner, name)), _) => format!("{}/{}", owner, name),
+        _ => "Repo".to_string(),
+    }
+}
+
+fn reselect<T, K: PartialEq>(
+    items: &[T],
+    index: usize,
+    previous: Option<K>,
+    key: impl Fn(&T) -> K,
+) -> usize {
+    previous
+        .and_then(|prev| items.iter().position(|item| key(item) == prev))
+        .unwrap_or_else(|| index.min(items.len().saturating_sub(1)))
+}
+
+fn repo_key(repo: &Repository) -> (String, String) {
+    (repo.owner.clone(), repo.name.clone())
+}
+
+fn review_key(review: &ReviewRequest) -> (String, String, u64) {
+    (
+        review.repo_owner.clone(),
+        review.repo_name.clone(),
+        review.pr_number,
+    )
+}
+
+fn my_pr_key(pr: &MyPr) -> (String, String, u64) {
+    (pr.repo_owner.clone(), pr.repo_name.clone(), pr.number)
+}
+
+fn issue_ref_key(item: &IssueRef) -> (String, String, u64) {
+    (item.repo_owner.clone(), item.repo_name.clone(), item.number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::GitHub;
+    use crate::types::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
+
+    // ── Test helpers ──
+
+    fn test_app() -> (App, mpsc::UnboundedReceiver<Action>) {
+        let github = GitHub::new(
+            "dummy_token".to_string(),
+            crate::http::DEFAULT_TIMEOUT,
+            &Default::default(),
+            crate::forge::DEFAULT_PAGE_SIZE,
+        )
+        .unwrap();
+        let forge: Arc<dyn Forge> = Arc::new(github);
+        let (tx, rx) = mpsc::unbounded_channel();
+        (App::new(forge, tx, vec![], UiConfig::default()), rx)
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        })
+    }
+
+    fn click(column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    fn nav_entry(screen: Screen) -> NavEntry {
+        NavEntry {
+            screen,
+            ..NavEntry::default()
+        }
+    }
+
+    fn key_ctrl(c: char) -> Event {
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        })
+    }
+
+    fn make_repo(name: &str) -> Repository {
+        Repository {
+            owner: "testowner".to_string(),
+            name: name.to_string(),
+            description: Some("A test repo".to_string()),
+            url: format!("https://github.com/testowner/{}", name),
+            stars: 42,
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn make_pr_summary(number: u64, title: &str) -> PrSummary {
+        PrSummary {
+            number,
+            title: title.to_string(),
+            state: PrState::Open,
+            author: "testauthor".to_string(),
+            updated_at: chrono::Utc::now(),
+            milestone: None,
+        }
+    }
+
+    fn make_issue(number: u64, title: &str) -> Issue {
+        Issue {
+            number,
+            title: title.to_string(),
+            state: IssueState::Open,
+            author: "testauthor".to_string(),
+            labels: vec![],
+            comments: 0,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            milestone: None,
+            assignees: vec![],
+        }
+    }
+
+    fn make_commit(sha: &str, message: &str) -> Commit {
+        Commit {
+            sha: sha.to_string(),
+            message: message.to_string(),
+            author: "testauthor".to_string(),
+            date: chrono::Utc::now(),
+        }
+    }
+
+    fn make_review_request(owner: &str, repo: &str, number: u64) -> ReviewRequest {
+        ReviewRequest {
+            repo_owner: owner.to_string(),
+            repo_name: repo.to_string(),
             pr_number: number,
             pr_title: format!("PR #{}", number),
             author: "someone".to_string(),
             updated_at: chrono::Utc::now(),
         }
-    }
+    }
+
+    fn make_my_pr(owner: &str, repo: &str, number: u64) -> MyPr {
+        MyPr {
+            repo_owner: owner.to_string(),
+            repo_name: repo.to_string(),
+            number,
+            title: format!("My PR #{}", number),
+            state: PrState::Open,
+            checks_status: ChecksStatus::Success,
+            mergeable: MergeableState::Unknown,
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn make_issue_ref(owner: &str, repo: &str, number: u64, is_pr: bool) -> IssueRef {
+        IssueRef {
+            repo_owner: owner.to_string(),
+            repo_name: repo.to_string(),
+            number,
+            title: format!("Item #{}", number),
+            author: "someone".to_string(),
+            is_pr,
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn make_pull_request(number: u64, body: &str) -> PullRequest {
+        PullRequest {
+            number,
+            title: format!("PR #{}", number),
+            body: Some(body.to_string()),
+            state: PrState::Open,
+            author: "testauthor".to_string(),
+            head_branch: "feature".to_string(),
+            head_sha: "abc123".to_string(),
+            from_fork: false,
+            auto_merge: false,
+            base_branch: "main".to_string(),
+            stats: PrStats {
+                additions: 10,
+                deletions: 5,
+                changed_files: 3,
+                commits: 2,
+                comments: 1,
+            },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            merged_at: None,
+            closed_at: None,
+            mergeable_state: MergeableState::Unknown,
+            milestone: None,
+            labels: vec![],
+            assignees: vec![],
+        }
+    }
+
+    fn make_commit_detail(sha: &str, message: &str, files: Vec<CommitFile>) -> CommitDetail {
+        CommitDetail {
+            sha: sha.to_string(),
+            message: message.to_string(),
+            author: "testauthor".to_string(),
+            date: chrono::Utc::now(),
+            stats: CommitStats {
+                additions: 10,
+                deletions: 5,
+                total: 15,
+            },
+            files,
+        }
+    }
+
+    fn make_action_run(id: u64, name: &str) -> ActionRun {
+        ActionRun {
+            id,
+            name: name.to_string(),
+            status: ActionStatus::Completed,
+            conclusion: Some(ActionConclusion::Success),
+            branch: "main".to_string(),
+            event: "push".to_string(),
+            created_at: chrono::Utc::now(),
+            duration: None,
+            workflow: None,
+        }
+    }
+
+    fn make_comment(id: u64, author: &str, body: &str) -> Comment {
+        Comment {
+            id,
+            author: author.to_string(),
+            body: body.to_string(),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn make_thread(id: &str, resolved: bool) -> ReviewThread {
+        ReviewThread {
+            id: id.to_string(),
+            path: Some("src/main.rs".to_string()),
+            line: Some(10),
+            resolved,
+            comments: vec![make_comment(1, "alice", &format!("thread {}", id))],
+        }
+    }
+
+    fn make_milestone(id: u64, title: &str) -> Milestone {
+        Milestone {
+            id,
+            title: title.to_string(),
+            due_on: None,
+        }
+    }
+
+    fn make_file(filename: &str, status: &str) -> CommitFile {
+        CommitFile {
+            filename: filename.to_string(),
+            status: status.to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: None,
+        }
+    }
+
+    fn make_label(name: &str) -> Label {
+        Label {
+            name: name.to_string(),
+            color: Some("d73a4a".to_string()),
+        }
+    }
+
+    // ── Key handling tests ──
+
+    mod key_handling {
+        use super::*;
+
+        // Normal mode
+
+        #[tokio::test]
+        async fn q_on_home_quits() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Char('q')));
+            assert!(matches!(action, Action::Quit));
+        }
+
+        #[tokio::test]
+        async fn shift_q_on_home_picks_a_saved_search() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Char('Q')));
+            assert!(matches!(action, Action::ShowSavedSearchSelect));
+        }
+
+        #[tokio::test]
+        async fn q_on_repo_list_goes_back() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            let action = app.handle_event(key(KeyCode::Char('q')));
+            assert!(matches!(action, Action::Back));
+        }
+
+        #[tokio::test]
+        async fn big_b_toggles_browse_columns() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            let action = app.handle_event(key(KeyCode::Char('B')));
+            assert!(matches!(action, Action::ToggleBrowse));
+            app.screen = Screen::RepoView;
+            let action = app.handle_event(key(KeyCode::Char('B')));
+            assert!(matches!(action, Action::None));
+        }
+
+        #[tokio::test]
+        async fn h_l_on_browse_move_between_columns() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::Browse;
+            let action = app.handle_event(key(KeyCode::Char('l')));
+            assert!(matches!(action, Action::NextTab));
+            let action = app.handle_event(key(KeyCode::Char('h')));
+            assert!(matches!(action, Action::PrevTab));
+        }
+
+        #[tokio::test]
+        async fn esc_on_home_quits() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Esc));
+            assert!(matches!(action, Action::Quit));
+        }
+
+        #[tokio::test]
+        async fn esc_on_repo_list_goes_back() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            let action = app.handle_event(key(KeyCode::Esc));
+            assert!(matches!(action, Action::Back));
+        }
+
+        #[tokio::test]
+        async fn esc_with_active_search_clears() {
+            let (mut app, _rx) = test_app();
+            app.search.active = true;
+            let action = app.handle_event(key(KeyCode::Esc));
+            assert!(matches!(action, Action::ClearSearch));
+        }
+
+        #[tokio::test]
+        async fn j_scrolls_down() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Char('j')));
+            assert!(matches!(action, Action::ScrollDown));
+        }
+
+        #[tokio::test]
+        async fn down_scrolls_down() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Down));
+            assert!(matches!(action, Action::ScrollDown));
+        }
+
+        #[tokio::test]
+        async fn k_scrolls_up() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Char('k')));
+            assert!(matches!(action, Action::ScrollUp));
+        }
+
+        #[tokio::test]
+        async fn up_scrolls_up() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Up));
+            assert!(matches!(action, Action::ScrollUp));
+        }
+
+        // Mouse
+
+        #[tokio::test]
+        async fn wheel_scrolls() {
+            let (app, _rx) = test_app();
+            let wheel = |kind| {
+                Event::Mouse(MouseEvent {
+                    kind,
+                    column: 0,
+                    row: 0,
+                    modifiers: KeyModifiers::NONE,
+                })
+            };
+            let action = app.handle_event(wheel(MouseEventKind::ScrollDown));
+            assert!(matches!(action, Action::ScrollDown));
+            let action = app.handle_event(wheel(MouseEventKind::ScrollUp));
+            assert!(matches!(action, Action::ScrollUp));
+        }
+
+        #[tokio::test]
+        async fn click_maps_to_row_below_offset() {
+            let (app, _rx) = test_app();
+            app.click_map.borrow_mut().push((
+                ratatui::layout::Rect::new(1, 4, 40, 10),
+                ClickTarget::Rows {
+                    list: ClickList::Repos,
+                    first: 5,
+                },
+            ));
+            let action = app.handle_event(click(3, 6));
+            assert!(matches!(action, Action::ClickRow(ClickList::Repos, 7)));
+            assert!(matches!(app.handle_event(click(0, 6)), Action::None));
+        }
+
+        #[tokio::test]
+        async fn click_on_tab_switches() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.click_map.borrow_mut().extend([
+                (
+                    ratatui::layout::Rect::new(1, 1, 19, 1),
+                    ClickTarget::Tab(RepoTab::PullRequests),
+                ),
+                (
+                    ratatui::layout::Rect::new(21, 1, 12, 1),
+                    ClickTarget::Tab(RepoTab::Issues),
+                ),
+            ]);
+            let action = app.handle_event(click(25, 1));
+            assert!(matches!(action, Action::SwitchRepoTab(RepoTab::Issues)));
+            // Already on that tab
+            assert!(matches!(app.handle_event(click(5, 1)), Action::None));
+            app.input_mode = InputMode::Search;
+            assert!(matches!(app.handle_event(click(25, 1)), Action::None));
+        }
+
+        #[tokio::test]
+        async fn gg_goes_to_top() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Char('g')));
+            assert!(matches!(action, Action::None));
+            assert_eq!(app.pending_keys.get().shown().as_deref(), Some("g"));
+            let action = app.handle_event(key(KeyCode::Char('g')));
+            assert!(matches!(action, Action::GoToTop));
+            assert_eq!(app.pending_keys.get().shown(), None);
+        }
+
+        #[tokio::test]
+        async fn counts_repeat_movements() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.repos = (0..20).map(|i| make_repo(&i.to_string())).collect();
+            for c in "12".chars() {
+                assert!(matches!(
+                    app.handle_event(key(KeyCode::Char(c))),
+                    Action::None
+                ));
+            }
+            let action = app.handle_event(key(KeyCode::Char('j')));
+            assert!(
+                matches!(&action, Action::Repeat(12, inner) if matches!(**inner, Action::ScrollDown))
+            );
+            app.update(action);
+            assert_eq!(app.repo_index, 12);
+
+            app.update(app.handle_event(key(KeyCode::Char('5'))));
+            app.update(app.handle_event(key(KeyCode::Char('k'))));
+            assert_eq!(app.repo_index, 7);
+
+            // Only movements take a count, and a lone 0 isn't one
+            app.handle_event(key(KeyCode::Char('3')));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('o'))),
+                Action::OpenInBrowser
+            ));
+            app.handle_event(key(KeyCode::Char('0')));
+            assert_eq!(app.pending_keys.get().shown(), None);
+        }
+
+        #[tokio::test]
+        async fn pending_keys_lapse() {
+            let (app, _rx) = test_app();
+            app.pending_keys.set(PendingKeys {
+                count: Some(5),
+                prefix: None,
+                at: Some(std::time::Instant::now() - KEY_SEQUENCE_TIMEOUT),
+            });
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('j'))),
+                Action::ScrollDown
+            ));
+        }
+
+        #[tokio::test]
+        async fn shift_p_filters_the_pr_tab() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.repo_tab = RepoTab::PullRequests;
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('P'))),
+                Action::ShowPrFilterSelect
+            ));
+            app.repo_tab = RepoTab::Issues;
+            assert!(!matches!(
+                app.handle_event(key(KeyCode::Char('P'))),
+                Action::ShowPrFilterSelect
+            ));
+        }
+
+        #[tokio::test]
+        async fn za_toggles_a_home_section() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::Home;
+            app.handle_event(key(KeyCode::Char('z')));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('a'))),
+                Action::ToggleHomeSection
+            ));
+        }
+
+        #[tokio::test]
+        async fn zz_centers_the_selection() {
+            let (mut app, _rx) = test_app();
+            app.handle_event(key(KeyCode::Char('z')));
+            let action = app.handle_event(key(KeyCode::Char('z')));
+            assert!(matches!(action, Action::CenterSelection));
+            app.update(action);
+            assert!(app.center_selection.get());
+
+            // Anything else after the first key cancels it
+            app.handle_event(key(KeyCode::Char('z')));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('j'))),
+                Action::None
+            ));
+        }
+
+        #[tokio::test]
+        async fn home_goes_to_top() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Home));
+            assert!(matches!(action, Action::GoToTop));
+        }
+
+        #[tokio::test]
+        async fn big_g_goes_to_bottom() {
+            let (app, _rx) = test_app();
+            // G is uppercase, which crossterm sends as Char('G') with SHIFT
+            let action = app.handle_event(key(KeyCode::Char('G')));
+            assert!(matches!(action, Action::GoToBottom));
+        }
+
+        #[tokio::test]
+        async fn end_goes_to_bottom() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::End));
+            assert!(matches!(action, Action::GoToBottom));
+        }
+
+        #[tokio::test]
+        async fn shift_c_on_commit_detail_comments_on_commit() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::CommitDetail;
+            app.current_repo = Some(("o".to_string(), "r".to_string()));
+            app.current_commit = Some(make_commit_detail("abc123", "msg", vec![]));
+            let action = app.handle_event(key(KeyCode::Char('C')));
+            assert!(matches!(
+                action,
+                Action::SuspendForEditor(EditorContext::CommentOnCommit { ref sha, .. })
+                    if sha == "abc123"
+            ));
+        }
+
+        #[tokio::test]
+        async fn plus_on_pr_detail_opens_reactions() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            let action = app.handle_event(key(KeyCode::Char('+')));
+            assert!(matches!(action, Action::ShowReactionSelect));
+        }
+
+        #[tokio::test]
+        async fn s_on_home_cycles_review_sort() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Char('s')));
+            assert!(matches!(action, Action::CycleReviewSort));
+        }
+
+        #[tokio::test]
+        async fn ctrl_o_jumps_back() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key_ctrl('o'));
+            assert!(matches!(action, Action::JumpBack));
+        }
+
+        #[tokio::test]
+        async fn ctrl_t_n_p_w_drive_workspaces() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            let keys = ['t', 'n', 'p', 'w'].map(|c| app.handle_event(key_ctrl(c)));
+            assert!(matches!(
+                keys,
+                [
+                    Action::NewWorkspace,
+                    Action::NextWorkspace,
+                    Action::PrevWorkspace,
+                    Action::CloseWorkspace
+                ]
+            ));
+        }
+
+        #[tokio::test]
+        async fn space_and_v_mark_rows_and_x_closes_the_marked() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.current_repo = Some(("o".to_string(), "r".to_string()));
+            app.repo_tab = RepoTab::PullRequests;
+            app.prs = vec![make_pr_summary(1, "a"), make_pr_summary(2, "b")];
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char(' '))),
+                Action::ToggleMark
+            ));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('V'))),
+                Action::MarkRange
+            ));
+            // Without marks, x on the PR list does nothing
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('x'))),
+                Action::None
+            ));
+            app.update(Action::ToggleMark);
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('x'))),
+                Action::ShowConfirm(ConfirmAction::CloseMarked(ref numbers)) if numbers == &[1]
+            ));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('L'))),
+                Action::ShowLabelSelect
+            ));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Esc)),
+                Action::ClearMarks
+            ));
+        }
+
+        #[tokio::test]
+        async fn x_opens_the_checklist_on_pr_detail() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('X'))),
+                Action::ShowChecklist
+            ));
+        }
+
+        #[tokio::test]
+        async fn tab_and_n_pick_references_on_pr_detail() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Tab)),
+                Action::NextXref
+            ));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('n'))),
+                Action::NextXref
+            ));
+            assert!(matches!(app.handle_event(key(KeyCode::Esc)), Action::Back));
+            app.xref_index = Some(0);
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Esc)),
+                Action::ClearXref
+            ));
+        }
+
+        #[tokio::test]
+        async fn ctrl_l_goes_forward() {
+            let (app, _rx) = test_app();
+            assert!(matches!(app.handle_event(key_ctrl('l')), Action::Forward));
+        }
+
+        #[tokio::test]
+        async fn ctrl_d_pages_down() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key_ctrl('d'));
+            assert!(matches!(action, Action::PageDown));
+        }
+
+        #[tokio::test]
+        async fn ctrl_u_pages_up() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key_ctrl('u'));
+            assert!(matches!(action, Action::PageUp));
+        }
+
+        #[tokio::test]
+        async fn slash_enters_search() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Char('/')));
+            assert!(matches!(action, Action::EnterSearchMode));
+        }
+
+        #[tokio::test]
+        async fn n_with_active_search_next() {
+            let (mut app, _rx) = test_app();
+            app.search.active = true;
+            let action = app.handle_event(key(KeyCode::Char('n')));
+            assert!(matches!(action, Action::SearchNext));
+        }
+
+        #[tokio::test]
+        async fn big_n_with_active_search_prev() {
+            let (mut app, _rx) = test_app();
+            app.search.active = true;
+            let action = app.handle_event(key(KeyCode::Char('N')));
+            assert!(matches!(action, Action::SearchPrev));
+        }
+
+        #[tokio::test]
+        async fn n_without_search_is_not_search_next() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Char('n')));
+            assert!(!matches!(action, Action::SearchNext));
+        }
+
+        #[tokio::test]
+        async fn enter_selects() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Enter));
+            assert!(matches!(action, Action::Select));
+        }
+
+        #[tokio::test]
+        async fn g_clones_on_repo_list_and_checks_out_on_pr_detail() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            let action = app.handle_event(key(KeyCode::Char('g')));
+            assert!(matches!(action, Action::CloneRepo));
+
+            app.screen = Screen::PrDetail;
+            let action = app.handle_event(key(KeyCode::Char('g')));
+            assert!(matches!(action, Action::CheckoutPr));
+        }
+
+        #[tokio::test]
+        async fn shift_e_edits_repo_meta_on_repo_screens() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            let action = app.handle_event(key(KeyCode::Char('E')));
+            assert!(matches!(action, Action::EditRepoMeta));
+
+            app.screen = Screen::RepoView;
+            let action = app.handle_event(key(KeyCode::Char('E')));
+            assert!(matches!(action, Action::EditRepoMeta));
+
+            app.screen = Screen::PrDetail;
+            let action = app.handle_event(key(KeyCode::Char('E')));
+            assert!(!matches!(action, Action::EditRepoMeta));
+        }
+
+        #[tokio::test]
+        async fn shift_a_on_pr_detail_shows_reviewer_load() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            let action = app.handle_event(key(KeyCode::Char('A')));
+            assert!(matches!(action, Action::ShowReviewerLoad));
+        }
+
+        #[tokio::test]
+        async fn shift_v_on_pr_detail_starts_revert() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            let action = app.handle_event(key(KeyCode::Char('V')));
+            assert!(matches!(action, Action::ShowRevertSelect));
+        }
+
+        #[tokio::test]
+        async fn d_on_pr_detail_views_diff() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            let action = app.handle_event(key(KeyCode::Char('d')));
+            assert!(matches!(action, Action::ViewDiff));
+        }
+
+        #[tokio::test]
+        async fn d_on_commit_detail_views_diff() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::CommitDetail;
+            let action = app.handle_event(key(KeyCode::Char('d')));
+            assert!(matches!(action, Action::ViewDiff));
+        }
+
+        #[tokio::test]
+        async fn d_on_repo_list_not_view_diff() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            let action = app.handle_event(key(KeyCode::Char('d')));
+            assert!(!matches!(action, Action::ViewDiff));
+        }
+
+        #[tokio::test]
+        async fn r_refreshes() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Char('r')));
+            assert!(matches!(action, Action::Refresh));
+            let action = app.handle_event(key(KeyCode::Char('u')));
+            assert!(matches!(action, Action::RefreshItem));
+            let action = app.handle_event(key_ctrl('r'));
+            assert!(matches!(action, Action::HardRefresh));
+        }
+
+        #[tokio::test]
+        async fn shift_r_on_home_shows_repos() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Char('R')));
+            assert!(matches!(action, Action::ShowRepoList));
+        }
+
+        #[tokio::test]
+        async fn o_opens_in_browser() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Char('o')));
+            assert!(matches!(action, Action::OpenInBrowser));
+        }
+
+        #[tokio::test]
+        async fn y_yanks_url() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Char('y')));
+            assert!(matches!(action, Action::YankUrl));
+        }
+
+        #[tokio::test]
+        async fn colon_opens_palette() {
+            let (app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Char(':')));
+            assert!(matches!(action, Action::ShowPalette));
+        }
+
+        #[tokio::test]
+        async fn palette_keys_type_into_query() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::Palette;
+            let action = app.handle_event(key(KeyCode::Char('q')));
+            assert!(matches!(action, Action::PaletteInput('q')));
+            let action = app.handle_event(key(KeyCode::Backspace));
+            assert!(matches!(action, Action::PaletteBackspace));
+            let action = app.handle_event(key(KeyCode::Esc));
+            assert!(matches!(action, Action::ConfirmNo));
+        }
+
+        #[tokio::test]
+        async fn file_keys_on_pr_detail() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            let action = app.handle_event(key(KeyCode::Char('}')));
+            assert!(matches!(action, Action::NextFile));
+            let action = app.handle_event(key(KeyCode::Char('{')));
+            assert!(matches!(action, Action::PrevFile));
+            let action = app.handle_event(key(KeyCode::Char('e')));
+            assert!(matches!(action, Action::OpenFileInEditor));
+            let action = app.handle_event(key(KeyCode::Char(')')));
+            assert!(matches!(action, Action::NextPrCommit));
+            let action = app.handle_event(key(KeyCode::Char('(')));
+            assert!(matches!(action, Action::PrevPrCommit));
+        }
+
+        #[tokio::test]
+        async fn m_on_pr_detail_shows_merge() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            let action = app.handle_event(key(KeyCode::Char('m')));
+            assert!(matches!(action, Action::ShowMergeMethodSelect));
+        }
+
+        #[tokio::test]
+        async fn m_on_repo_list_is_none() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            let action = app.handle_event(key(KeyCode::Char('m')));
+            assert!(matches!(action, Action::None));
+        }
+
+        #[tokio::test]
+        async fn big_r_on_pr_detail_shows_review() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            let action = app.handle_event(key(KeyCode::Char('R')));
+            assert!(matches!(action, Action::ShowReviewSelect));
+        }
+
+        #[tokio::test]
+        async fn b_on_pr_detail_toggles_bot_comments() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            let action = app.handle_event(key(KeyCode::Char('b')));
+            assert!(matches!(action, Action::ToggleBotComments));
+        }
+
+        #[tokio::test]
+        async fn big_m_on_issues_tab_assigns_milestone() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.repo_tab = RepoTab::Issues;
+            let action = app.handle_event(key(KeyCode::Char('M')));
+            assert!(matches!(
+                action,
+                Action::ShowMilestoneSelect(MilestonePurpose::Assign)
+            ));
+        }
+
+        #[tokio::test]
+        async fn big_l_edits_labels_on_issues_and_pr_detail() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.repo_tab = RepoTab::Issues;
+            let action = app.handle_event(key(KeyCode::Char('L')));
+            assert!(matches!(action, Action::ShowLabelSelect));
+            app.screen = Screen::PrDetail;
+            let action = app.handle_event(key(KeyCode::Char('L')));
+            assert!(matches!(action, Action::ShowLabelSelect));
+            app.screen = Screen::RepoView;
+            app.repo_tab = RepoTab::Commits;
+            let action = app.handle_event(key(KeyCode::Char('L')));
+            assert!(matches!(action, Action::None));
+        }
+
+        #[tokio::test]
+        async fn space_toggles_in_multi_select_popup() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::MultiSelectPopup;
+            let action = app.handle_event(key(KeyCode::Char(' ')));
+            assert!(matches!(action, Action::PopupToggle));
+            let action = app.handle_event(key(KeyCode::Enter));
+            assert!(matches!(action, Action::PopupSelect));
+        }
+
+        #[tokio::test]
+        async fn big_f_filters_prs_but_not_commits() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            let action = app.handle_event(key(KeyCode::Char('F')));
+            assert!(matches!(
+                action,
+                Action::ShowMilestoneSelect(MilestonePurpose::Filter)
+            ));
+            app.repo_tab = RepoTab::Commits;
+            let action = app.handle_event(key(KeyCode::Char('F')));
+            assert!(matches!(action, Action::None));
+        }
+
+        #[tokio::test]
+        async fn thread_keys_on_pr_detail() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char(']'))),
+                Action::NextThread
+            ));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('['))),
+                Action::PrevThread
+            ));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('t'))),
+                Action::ToggleThreadResolved
+            ));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('T'))),
+                Action::ToggleResolvedThreads
+            ));
+        }
+
+        #[tokio::test]
+        async fn p_on_repo_view_switches_tab() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            let action = app.handle_event(key(KeyCode::Char('p')));
+            assert!(matches!(
+                action,
+                Action::SwitchRepoTab(RepoTab::PullRequests)
+            ));
+        }
+
+        #[tokio::test]
+        async fn i_on_repo_view_switches_tab() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            let action = app.handle_event(key(KeyCode::Char('i')));
+            assert!(matches!(action, Action::SwitchRepoTab(RepoTab::Issues)));
+        }
+
+        #[tokio::test]
+        async fn c_on_repo_view_switches_tab() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            let action = app.handle_event(key(KeyCode::Char('c')));
+            assert!(matches!(action, Action::SwitchRepoTab(RepoTab::Commits)));
+        }
+
+        #[tokio::test]
+        async fn a_on_repo_view_switches_tab() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            let action = app.handle_event(key(KeyCode::Char('a')));
+            assert!(matches!(action, Action::SwitchRepoTab(RepoTab::Actions)));
+        }
+
+        // Search mode
+
+        #[tokio::test]
+        async fn search_esc_exits() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::Search;
+            let action = app.handle_event(key(KeyCode::Esc));
+            assert!(matches!(action, Action::ExitSearchMode));
+        }
+
+        #[tokio::test]
+        async fn search_enter_confirms() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::Search;
+            let action = app.handle_event(key(KeyCode::Enter));
+            assert!(matches!(action, Action::SearchConfirm));
+        }
+
+        #[tokio::test]
+        async fn search_backspace() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::Search;
+            let action = app.handle_event(key(KeyCode::Backspace));
+            assert!(matches!(action, Action::SearchBackspace));
+        }
+
+        #[tokio::test]
+        async fn search_char_input() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::Search;
+            let action = app.handle_event(key(KeyCode::Char('f')));
+            assert!(matches!(action, Action::SearchInput('f')));
+        }
+
+        #[tokio::test]
+        async fn search_other_key_none() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::Search;
+            let action = app.handle_event(key(KeyCode::Tab));
+            assert!(matches!(action, Action::None));
+        }
+
+        // Quick comment
+
+        #[tokio::test]
+        async fn c_on_pr_detail_starts_quick_comment() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            let action = app.handle_event(key(KeyCode::Char('c')));
+            assert!(matches!(action, Action::StartQuickComment));
+        }
+
+        #[tokio::test]
+        async fn form_keys_open_and_fill_the_form() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.repo_tab = RepoTab::Issues;
+            app.issues = vec![make_issue(3, "Bug")];
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('N'))),
+                Action::ShowIssueTemplateSelect
+            ));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('e'))),
+                Action::ShowForm(FormTarget::Issue(3))
+            ));
+
+            app.screen = Screen::PrDetail;
+            app.current_pr = Some(make_pull_request(7, "body"));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('E'))),
+                Action::ShowForm(FormTarget::Pr(7))
+            ));
+
+            app.input_mode = InputMode::Form;
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Tab)),
+                Action::FormNextField
+            ));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('e'))),
+                Action::FormInput('e')
+            ));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Enter)),
+                Action::SubmitForm
+            ));
+        }
+
+        #[tokio::test]
+        async fn c_on_repo_view_still_switches_to_commits() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            let action = app.handle_event(key(KeyCode::Char('c')));
+            assert!(matches!(action, Action::SwitchRepoTab(RepoTab::Commits)));
+        }
+
+        #[tokio::test]
+        async fn comment_mode_keys() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::Comment;
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('q'))),
+                Action::QuickCommentInput('q')
+            ));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Enter)),
+                Action::SubmitQuickComment
+            ));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Esc)),
+                Action::CancelQuickComment
+            ));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Backspace)),
+                Action::QuickCommentBackspace
+            ));
+        }
+
+        // Confirm mode
+
+        #[tokio::test]
+        async fn confirm_y_yes() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::Confirm;
+            let action = app.handle_event(key(KeyCode::Char('y')));
+            assert!(matches!(action, Action::ConfirmYes));
+        }
+
+        #[tokio::test]
+        async fn confirm_n_no() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::Confirm;
+            let action = app.handle_event(key(KeyCode::Char('n')));
+            assert!(matches!(action, Action::ConfirmNo));
+        }
+
+        #[tokio::test]
+        async fn confirm_esc_no() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::Confirm;
+            let action = app.handle_event(key(KeyCode::Esc));
+            assert!(matches!(action, Action::ConfirmNo));
+        }
+
+        // SelectPopup mode
+
+        #[tokio::test]
+        async fn popup_j_down() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::SelectPopup;
+            let action = app.handle_event(key(KeyCode::Char('j')));
+            assert!(matches!(action, Action::PopupDown));
+        }
+
+        #[tokio::test]
+        async fn popup_k_up() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::SelectPopup;
+            let action = app.handle_event(key(KeyCode::Char('k')));
+            assert!(matches!(action, Action::PopupUp));
+        }
+
+        #[tokio::test]
+        async fn popup_enter_select() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::SelectPopup;
+            let action = app.handle_event(key(KeyCode::Enter));
+            assert!(matches!(action, Action::PopupSelect));
+        }
+
+        #[tokio::test]
+        async fn popup_esc_cancels() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::SelectPopup;
+            let action = app.handle_event(key(KeyCode::Esc));
+            assert!(matches!(action, Action::ConfirmNo));
+        }
+    }
+
+    // ── State transition tests ──
+
+    mod state_transitions {
+        use super::*;
+
+        // Navigation
+
+        #[tokio::test]
+        async fn workspaces_keep_their_own_view() {
+            let (mut app, _rx) = test_app();
+            app.review_requests = vec![make_review_request("o", "r", 1)];
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            app.screen = Screen::PrDetail;
+            app.current_pr = Some(make_pull_request(7, "Fix"));
+            app.scroll_offset = 9;
+
+            app.update(Action::NewWorkspace);
+            assert_eq!(app.workspace_index, 1);
+            assert_eq!(app.screen, Screen::Home);
+            assert!(app.current_pr.is_none());
+            assert_eq!(app.review_requests.len(), 1);
+            assert_eq!(app.workspace_labels(), vec!["repo #7", "Home"]);
+
+            app.update(Action::NextWorkspace);
+            assert_eq!(app.workspace_index, 0);
+            assert_eq!(app.screen, Screen::PrDetail);
+            assert_eq!(app.scroll_offset, 9);
+            assert_eq!(app.current_pr.as_ref().map(|pr| pr.number), Some(7));
+        }
+
+        #[tokio::test]
+        async fn closing_a_workspace_moves_to_its_neighbor() {
+            let (mut app, _rx) = test_app();
+            app.update(Action::CloseWorkspace);
+            assert_eq!(app.workspaces.len(), 1);
+
+            app.update(Action::NewWorkspace);
+            app.screen = Screen::RepoList;
+            app.update(Action::NewWorkspace);
+            app.update(Action::PrevWorkspace);
+            app.update(Action::CloseWorkspace);
+            assert_eq!(app.workspaces.len(), 2);
+            assert_eq!(app.workspace_index, 1);
+            assert_eq!(app.workspace_labels(), vec!["Home", "Home"]);
+
+            app.update(Action::CloseWorkspace);
+            assert_eq!(app.workspace_index, 0);
+            assert_eq!(app.workspaces.len(), 1);
+        }
+
+        #[tokio::test]
+        async fn quit_sets_should_quit() {
+            let (mut app, _rx) = test_app();
+            app.update(Action::Quit);
+            assert!(app.should_quit);
+        }
+
+        #[tokio::test]
+        async fn back_from_home_quits() {
+            let (mut app, _rx) = test_app();
+            app.update(Action::Back);
+            assert!(app.should_quit);
+        }
+
+        #[tokio::test]
+        async fn back_from_repo_list_to_home() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.update(Action::Back);
+            assert_eq!(app.screen, Screen::Home);
+        }
+
+        #[tokio::test]
+        async fn clone_repo_suspends_for_git_clone_into_clone_dir() {
+            let (mut app, mut rx) = test_app();
+            let dir = std::env::temp_dir().join(format!("grit-clone-{}", std::process::id()));
+            app.ui_config.clone_dir = Some(dir.to_string_lossy().into_owned());
+            app.screen = Screen::RepoList;
+            app.repos = vec![make_repo("alpha")];
+
+            app.update(Action::CloneRepo);
+            match rx.try_recv() {
+                Ok(Action::SuspendForGit(run)) => {
+                    assert_eq!(run.dir, dir);
+                    assert_eq!(
+                        run.steps,
+                        vec![vec![
+                            "clone".to_string(),
+                            "https://github.com/testowner/alpha.git".to_string(),
+                            "alpha".to_string(),
+                        ]]
+                    );
+                }
+                other => panic!("expected git suspend, got {:?}", other),
+            }
+
+            // An existing checkout is left alone
+            std::fs::create_dir_all(dir.join("alpha")).unwrap();
+            app.update(Action::CloneRepo);
+            assert!(rx.try_recv().is_err());
+            assert!(app.flash_message.is_some());
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+
+        #[tokio::test]
+        async fn back_from_repo_view_to_repo_list() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.repo_tab = RepoTab::Issues;
+            app.prs = vec![make_pr_summary(1, "test")];
+            app.issues = vec![make_issue(1, "test")];
+            app.commits = vec![make_commit("abc123", "test")];
+            app.update(Action::Back);
+            assert_eq!(app.screen, Screen::RepoList);
+            assert_eq!(app.repo_tab, RepoTab::PullRequests);
+            assert!(app.prs.is_empty());
+            assert!(app.issues.is_empty());
+            assert!(app.commits.is_empty());
+        }
+
+        #[tokio::test]
+        async fn items_updated_since_last_visit_are_new() {
+            let (mut app, _rx) = test_app();
+            let seen = chrono::Utc::now() - chrono::Duration::hours(1);
+            let mut old = make_pr_summary(1, "old");
+            old.updated_at = seen;
+            let fresh = make_pr_summary(2, "fresh");
+            // Nothing cached on a first visit, so nothing is new
+            assert!(!app.is_new_pr(&fresh));
+
+            app.prs_seen_until = Some(seen);
+            assert!(!app.is_new_pr(&old));
+            assert!(app.is_new_pr(&fresh));
+
+            app.issues_seen_until = Some(seen);
+            assert!(app.is_new_issue(&make_issue(3, "fresh")));
+        }
+
+        fn owned_repo(owner: &str, name: &str) -> Repository {
+            Repository {
+                owner: owner.to_string(),
+                ..make_repo(name)
+            }
+        }
+
+        fn browse_app() -> (App, mpsc::UnboundedReceiver<Action>) {
+            let (mut app, rx) = test_app();
+            app.repos = vec![
+                owned_repo("alice", "one"),
+                owned_repo("acme", "api"),
+                owned_repo("alice", "two"),
+                owned_repo("acme", "web"),
+            ];
+            (app, rx)
+        }
+
+        #[tokio::test]
+        async fn browse_owners_are_distinct_in_list_order() {
+            let (app, _rx) = browse_app();
+            assert_eq!(app.browse_owners(), vec!["alice", "acme"]);
+        }
+
+        #[tokio::test]
+        async fn toggle_browse_focuses_repo_selected_in_list() {
+            let (mut app, _rx) = browse_app();
+            app.screen = Screen::RepoList;
+            app.repo_index = 3;
+            app.update(Action::ToggleBrowse);
+            assert_eq!(app.screen, Screen::Browse);
+            assert_eq!(app.browse.owner_index, 1);
+            assert_eq!(app.browse.repo_index, 1);
+            assert_eq!(
+                app.browse.prs_repo,
+                Some(("acme".to_string(), "web".to_string()))
+            );
+
+            app.update(Action::ToggleBrowse);
+            assert_eq!(app.screen, Screen::RepoList);
+            assert_eq!(app.repo_index, 3);
+        }
+
+        #[tokio::test]
+        async fn moving_owner_resets_repo_column() {
+            let (mut app, _rx) = browse_app();
+            app.screen = Screen::Browse;
+            app.browse.column = BrowseColumn::Repos;
+            app.update(Action::ScrollDown);
+            assert_eq!(app.browse.repo_index, 1);
+
+            app.update(Action::PrevTab);
+            app.update(Action::ScrollDown);
+            assert_eq!(app.browse.owner_index, 1);
+            assert_eq!(app.browse.repo_index, 0);
+            assert_eq!(
+                app.browse.prs_repo,
+                Some(("acme".to_string(), "api".to_string()))
+            );
+            // Already on the last owner
+            app.update(Action::ScrollDown);
+            assert_eq!(app.browse.owner_index, 1);
+        }
+
+        #[tokio::test]
+        async fn browse_prs_for_another_repo_are_dropped() {
+            let (mut app, _rx) = browse_app();
+            app.browse.prs_repo = Some(("alice".to_string(), "one".to_string()));
+            app.update(Action::BrowsePrsLoaded(
+                "acme".to_string(),
+                "api".to_string(),
+                vec![make_pr_summary(1, "stale")],
+            ));
+            assert!(app.browse.prs.is_empty());
+            app.update(Action::BrowsePrsLoaded(
+                "alice".to_string(),
+                "one".to_string(),
+                vec![make_pr_summary(2, "fresh")],
+            ));
+            assert_eq!(app.browse.prs.len(), 1);
+        }
+
+        #[tokio::test]
+        async fn back_from_repo_opened_in_browse_returns_to_columns() {
+            let (mut app, _rx) = browse_app();
+            app.screen = Screen::Browse;
+            app.browse.column = BrowseColumn::Repos;
+            app.update(Action::Select);
+            assert_eq!(app.screen, Screen::RepoView);
+            assert_eq!(
+                app.current_repo,
+                Some(("alice".to_string(), "one".to_string()))
+            );
+            app.update(Action::Back);
+            assert_eq!(app.screen, Screen::Browse);
+        }
+
+        #[tokio::test]
+        async fn back_from_pr_detail() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            app.nav_stack = vec![nav_entry(Screen::RepoView)];
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.scroll_offset = 5;
+            app.update(Action::Back);
+            assert_eq!(app.screen, Screen::RepoView);
+            assert!(app.current_pr.is_none());
+            assert_eq!(app.scroll_offset, 0);
+        }
+
+        #[tokio::test]
+        async fn back_from_commit_detail() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::CommitDetail;
+            app.nav_stack = vec![nav_entry(Screen::RepoView)];
+            app.current_commit = Some(make_commit_detail("abc123", "msg", vec![]));
+            app.update(Action::Back);
+            assert_eq!(app.screen, Screen::RepoView);
+            assert!(app.current_commit.is_none());
+            assert_eq!(app.scroll_offset, 0);
+        }
+
+        #[tokio::test]
+        async fn next_tab_on_home_toggles_section() {
+            let (mut app, _rx) = test_app();
+            assert_eq!(app.home_section, HomeSection::ReviewRequests);
+            app.update(Action::NextTab);
+            assert_eq!(app.home_section, HomeSection::MyPrs);
+            app.update(Action::NextTab);
+            assert_eq!(app.home_section, HomeSection::Assigned);
+            app.update(Action::NextTab);
+            assert_eq!(app.home_section, HomeSection::Mentions);
+            app.update(Action::NextTab);
+            assert_eq!(app.home_section, HomeSection::Recent);
+            app.update(Action::NextTab);
+            assert_eq!(app.home_section, HomeSection::ReviewRequests);
+            app.update(Action::PrevTab);
+            assert_eq!(app.home_section, HomeSection::Recent);
+            app.update(Action::PrevTab);
+            assert_eq!(app.home_section, HomeSection::Mentions);
+        }
+
+        #[tokio::test]
+        async fn saved_section_is_a_tab_once_searches_are_set() {
+            let (mut app, _rx) = test_app();
+            app.searches = vec![("mine".to_string(), "author:@me".to_string())];
+            app.home_section = HomeSection::Mentions;
+            app.update(Action::NextTab);
+            assert_eq!(app.home_section, HomeSection::Saved);
+            app.update(Action::NextTab);
+            assert_eq!(app.home_section, HomeSection::Recent);
+            app.update(Action::PrevTab);
+            assert_eq!(app.home_section, HomeSection::Saved);
+        }
+
+        #[tokio::test]
+        async fn home_config_picks_sections_order_and_collapse() {
+            let (mut app, _rx) = test_app();
+            app.set_home_config(HomeConfig {
+                sections: vec![HomeSection::Assigned, HomeSection::ReviewRequests],
+                sizes: vec![70],
+                collapsed: vec![HomeSection::ReviewRequests],
+            });
+            // Review requests is still shown, so it stays selected; a size
+            // left out is an even split's
+            assert_eq!(
+                app.home_sections(),
+                vec![
+                    (HomeSection::Assigned, 70),
+                    (HomeSection::ReviewRequests, 50)
+                ]
+            );
+            app.update(Action::NextTab);
+            assert_eq!(app.home_section, HomeSection::Assigned);
+            app.update(Action::NextTab);
+            assert_eq!(app.home_section, HomeSection::ReviewRequests);
+
+            assert!(app.collapsed.contains(&HomeSection::ReviewRequests));
+            app.update(Action::ToggleHomeSection);
+            assert!(app.collapsed.is_empty());
+
+            // A section that isn't shown can't stay selected
+            app.set_home_config(HomeConfig {
+                sections: vec![HomeSection::MyPrs, HomeSection::Saved],
+                ..HomeConfig::default()
+            });
+            assert_eq!(app.home_section, HomeSection::MyPrs);
+            assert_eq!(app.home_sections(), vec![(HomeSection::MyPrs, 50)]);
+        }
+
+        #[tokio::test]
+        async fn pr_filter_reloads_the_tab_from_the_forge() {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let forge = Arc::new(crate::testing::MockForge::default());
+            let mut app = App::new(forge, tx, vec![], UiConfig::default());
+            app.screen = Screen::RepoView;
+            app.current_repo = Some(("acme".to_string(), "rocket".to_string()));
+            app.repo_tab = RepoTab::PullRequests;
+
+            app.update(Action::ShowPrFilterSelect);
+            assert_eq!(app.popup_title, "Filter Pull Requests");
+            assert_eq!(app.popup_index, 0);
+            app.popup_index = 1;
+            app.update(Action::PopupSelect);
+            assert_eq!(app.pr_filter, PrFilter::Authored);
+            loop {
+                match rx.recv().await.unwrap() {
+                    action @ Action::Refresh => app.update(action),
+                    action @ Action::PrsLoaded(..) => {
+                        app.update(action);
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            let numbers: Vec<u64> = app.prs.iter().map(|pr| pr.number).collect();
+            assert_eq!(numbers, vec![40, 38]);
+
+            // Leaving the repo drops the filter
+            app.nav_stack.clear();
+            app.update(Action::Back);
+            assert_eq!(app.pr_filter, PrFilter::All);
+        }
+
+        #[tokio::test]
+        async fn pr_filter_popup_offers_only_what_the_forge_can_do() {
+            let (tx, _rx) = mpsc::unbounded_channel();
+            let forge = Arc::new(crate::testing::MockForge {
+                pr_filters: &[PrFilter::All, PrFilter::Authored],
+                ..crate::testing::MockForge::default()
+            });
+            let mut app = App::new(forge, tx, vec![], UiConfig::default());
+            app.pr_filter = PrFilter::Authored;
+            app.update(Action::ShowPrFilterSelect);
+            assert_eq!(app.popup_items, vec!["All open", "Authored by me"]);
+            assert_eq!(app.popup_index, 1);
+        }
+
+        fn recent_item(repo: &str, number: Option<u64>) -> RecentItem {
+            RecentItem {
+                owner: "acme".to_string(),
+                repo: repo.to_string(),
+                number,
+                title: String::new(),
+                visited_at: chrono::Utc::now(),
+            }
+        }
+
+        #[tokio::test]
+        async fn renamed_repo_is_followed_and_fixed_in_recent() {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let forge = Arc::new(crate::testing::MockForge {
+                moved: vec![(
+                    ("acme".to_string(), "rockets".to_string()),
+                    ("acme".to_string(), "rocket".to_string()),
+                )],
+                ..crate::testing::MockForge::default()
+            });
+            let mut app = App::new(forge, tx, vec![], UiConfig::default());
+            // Recent items go to the cache, where Home's snapshot would see them
+            app.forge_name = "mock_moved".to_string();
+            let stale = app.prs_cache_key("acme", "rockets");
+            cache::write(&stale, &Vec::<PrSummary>::new());
+            app.recent = vec![recent_item("rockets", None)];
+
+            app.open_recent(recent_item("rockets", None));
+            loop {
+                if let action @ Action::RepoLocated(..) = rx.recv().await.unwrap() {
+                    app.update(action);
+                    break;
+                }
+            }
+            assert_eq!(
+                app.current_repo,
+                Some(("acme".to_string(), "rocket".to_string()))
+            );
+            assert_eq!(app.screen, Screen::RepoView);
+            assert_eq!(app.recent.len(), 1);
+            assert_eq!(app.recent[0].repo, "rocket");
+            assert!(cache::read::<Vec<PrSummary>>(&stale).is_none());
+            let (flash, _) = app.flash_message.as_ref().unwrap();
+            assert_eq!(flash, "acme/rockets moved to acme/rocket");
+        }
+
+        #[tokio::test]
+        async fn missing_pr_asks_before_forgetting_it() {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let forge = Arc::new(crate::testing::MockForge::default());
+            let mut app = App::new(forge, tx, vec![], UiConfig::default());
+            app.forge_name = "mock_missing".to_string();
+            app.recent = vec![
+                recent_item("rocket", Some(999)),
+                recent_item("rocket", None),
+            ];
+
+            app.open_recent(recent_item("rocket", Some(999)));
+            while app.input_mode != InputMode::Confirm {
+                if let action @ (Action::NotFound(_) | Action::RepoLocated(..)) =
+                    rx.recv().await.unwrap()
+                {
+                    app.update(action);
+                }
+            }
+            assert!(matches!(
+                app.confirm_action,
+                Some(ConfirmAction::ForgetMissing {
+                    number: Some(999),
+                    ..
+                })
+            ));
+
+            app.update(Action::ConfirmYes);
+            assert_eq!(app.recent.len(), 1);
+            assert_eq!(app.recent[0].number, None);
+            assert_eq!(app.screen, Screen::Home);
+        }
+
+        #[tokio::test]
+        async fn deleted_repo_offers_to_forget_it() {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let forge = Arc::new(crate::testing::MockForge::default());
+            let mut app = App::new(forge, tx, vec![], UiConfig::default());
+            let missing = Missing {
+                owner: "acme".to_string(),
+                repo: "gone".to_string(),
+                number: None,
+                error: Some(GritError::NotFound("acme/gone".to_string())),
+            };
+
+            app.update(Action::NotFound(missing));
+            let located = rx.recv().await.unwrap();
+            assert!(matches!(
+                located,
+                Action::RepoLocated(_, Err(GritError::NotFound(_)))
+            ));
+            app.update(located);
+            assert_eq!(app.input_mode, InputMode::Confirm);
+            assert!(matches!(
+                app.confirm_action,
+                Some(ConfirmAction::ForgetMissing { number: None, .. })
+            ));
+        }
+
+        #[tokio::test]
+        async fn pr_diff_shows_progress_until_it_reaches_the_pager() {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let forge = Arc::new(crate::testing::MockForge::default());
+            let mut app = App::new(forge, tx, vec![], UiConfig::default());
+            app.screen = Screen::PrDetail;
+            app.current_repo = Some(("acme".to_string(), "rocket".to_string()));
+            app.current_pr = Some(make_pull_request(42, "body"));
+
+            // An artifact download doesn't hold the diff up
+            app.download = Some(Download {
+                name: "logs".to_string(),
+                received: 0,
+                total: None,
+            });
+            app.update(Action::ViewDiff);
+            assert!(app.flash_message.is_none());
+            assert_eq!(
+                app.diff_download.as_ref().map(|d| d.name.as_str()),
+                Some("diff of #42")
+            );
+            // One diff at a time
+            app.update(Action::ViewDiff);
+            assert!(app.flash_message.is_some());
+
+            let loaded = rx.recv().await.unwrap();
+            assert!(matches!(loaded, Action::DiffLoaded(Spooled::Text(_))));
+            app.update(loaded);
+            assert!(app.diff_download.is_none());
+            assert!(app.download.is_some());
+            let diff = loop {
+                if let Ok(Action::SuspendForPager(diff)) = rx.try_recv() {
+                    break diff;
+                }
+            };
+            assert!(diff.contains("Ignition runs in stages"));
+        }
+
+        fn marking_app() -> (App, mpsc::UnboundedReceiver<Action>) {
+            let (tx, rx) = mpsc::unbounded_channel();
+            let forge = Arc::new(crate::testing::MockForge::default());
+            let mut app = App::new(forge, tx, vec![], UiConfig::default());
+            app.screen = Screen::RepoView;
+            app.current_repo = Some(("acme".to_string(), "rocket".to_string()));
+            app.repo_tab = RepoTab::Issues;
+            app.issues = (1..=5).map(|n| make_issue(n, "issue")).collect();
+            (app, rx)
+        }
+
+        /// Feed back what the bulk action sends until it's done
+        async fn finish_bulk(app: &mut App, rx: &mut mpsc::UnboundedReceiver<Action>) {
+            while app.bulk.is_some() {
+                let action = rx.recv().await.unwrap();
+                if matches!(action, Action::BulkProgress(_) | Action::BulkDone(_)) {
+                    app.update(action);
+                }
+            }
+        }
+
+        #[tokio::test]
+        async fn marks_toggle_and_extend_over_a_range() {
+            let (mut app, _rx) = marking_app();
+            app.update(Action::ToggleMark);
+            assert_eq!(app.issue_index, 1);
+            app.update(Action::MarkRange);
+            app.issue_index = 3;
+            app.update(Action::MarkRange);
+            assert_eq!(app.marked_numbers(), vec![1, 2, 3, 4]);
+            app.issue_index = 2;
+            app.update(Action::ToggleMark);
+            assert_eq!(app.marked_numbers(), vec![1, 2, 4]);
+
+            // Marks belong to the list they were made on
+            app.repo_tab = RepoTab::PullRequests;
+            assert!(app.marked_numbers().is_empty());
+            app.repo_tab = RepoTab::Issues;
+            assert_eq!(app.marked_numbers(), vec![1, 2, 4]);
+            app.update(Action::ClearMarks);
+            assert!(app.marks().is_none());
+        }
+
+        #[tokio::test]
+        async fn closing_marked_issues_reports_progress_then_clears_them() {
+            let (mut app, mut rx) = marking_app();
+            app.update(Action::MarkRange);
+            app.issue_index = 2;
+            app.update(Action::MarkRange);
+            app.update(app.handle_event(key(KeyCode::Char('x'))));
+            app.update(Action::ConfirmYes);
+            let bulk = app.bulk.clone().unwrap();
+            assert_eq!((bulk.op, bulk.done, bulk.total), (BulkOp::Close, 0, 3));
+
+            finish_bulk(&mut app, &mut rx).await;
+            assert!(app.marks().is_none());
+            assert_eq!(app.flash_message.unwrap().0, "Closed 3 issues.");
+        }
+
+        #[tokio::test]
+        async fn failed_bulk_calls_are_listed_and_stay_marked() {
+            let (mut app, mut rx) = marking_app();
+            app.update(Action::ToggleMark);
+            app.update(Action::ToggleMark);
+            app.update(Action::LabelsLoaded(vec![
+                make_label("bug"),
+                make_label("ui"),
+            ]));
+            assert_eq!(app.popup_title, "Add Labels");
+            assert_eq!(app.popup_checked, vec![false, false]);
+            app.update(Action::PopupToggle);
+            app.update(Action::PopupSelect);
+
+            // The mock forge has no labels to add
+            finish_bulk(&mut app, &mut rx).await;
+            assert_eq!(
+                app.error.as_deref(),
+                Some(
+                    "Labeled 0 of 2 issues; #1: API error: Labels not supported by this forge; \
+                     #2: API error: Labels not supported by this forge"
+                )
+            );
+            assert_eq!(app.marked_numbers(), vec![1, 2]);
+        }
+
+        #[tokio::test]
+        async fn picking_a_saved_search_runs_it() {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let forge = Arc::new(crate::testing::MockForge::default());
+            let mut app = App::new(forge, tx, vec![], UiConfig::default());
+            app.searches = vec![
+                ("ignition".to_string(), "ignition".to_string()),
+                ("ignition prs".to_string(), "is:pr ignition".to_string()),
+            ];
+            app.update(Action::ShowSavedSearchSelect);
+            assert_eq!(app.popup_title, "Saved Search");
+            assert_eq!(app.popup_items[1], "ignition prs - is:pr ignition");
+            app.popup_index = 1;
+            app.update(Action::PopupSelect);
+            assert_eq!(app.home_section, HomeSection::Saved);
+
+            let loaded = loop {
+                match rx.recv().await.unwrap() {
+                    action @ Action::SavedSearchLoaded(..) => break action,
+                    _ => continue,
+                }
+            };
+            // Results of the search picked before are dropped
+            app.update(Action::SavedSearchLoaded(
+                "ignition".to_string(),
+                1,
+                vec![make_issue_ref("o", "r", 1, false)],
+                PageInfo::default(),
+            ));
+            assert!(app.saved.is_empty());
+            app.update(loaded);
+            let found: Vec<(u64, bool)> = app.saved.iter().map(|i| (i.number, i.is_pr)).collect();
+            assert_eq!(found, vec![(42, true)]);
+        }
+
+        #[tokio::test]
+        async fn select_assigned_issue_opens_issues_tab() {
+            let (mut app, _rx) = test_app();
+            app.assigned = vec![make_issue_ref("o", "r", 3, false)];
+            app.home_section = HomeSection::Assigned;
+            app.update(Action::Select);
+            assert_eq!(app.screen, Screen::RepoView);
+            assert_eq!(app.repo_tab, RepoTab::Issues);
+            assert_eq!(app.current_repo, Some(("o".to_string(), "r".to_string())));
+        }
+
+        #[tokio::test]
+        async fn home_mention_url_follows_kind() {
+            let (mut app, _rx) = test_app();
+            app.mentions = vec![
+                make_issue_ref("octo", "repo", 5, false),
+                make_issue_ref("octo", "repo", 6, true),
+            ];
+            app.home_section = HomeSection::Mentions;
+            assert_eq!(
+                app.current_item_url(),
+                Some("https://github.com/octo/repo/issues/5".to_string())
+            );
+            app.update(Action::ScrollDown);
+            assert_eq!(
+                app.current_item_url(),
+                Some("https://github.com/octo/repo/pull/6".to_string())
+            );
+        }
+
+        #[tokio::test]
+        async fn visits_fill_recent_newest_first() {
+            let (mut app, _rx) = test_app();
+            app.open_repo("o".to_string(), "a".to_string());
+            app.load_id = 7;
+            app.update(Action::PrDetailLoaded(
+                Box::new(make_pull_request(42, "")),
+                7,
+            ));
+            // A background refresh of the open PR isn't a new visit
+            app.load_id = 8;
+            app.update(Action::PrDetailLoaded(
+                Box::new(make_pull_request(42, "")),
+                8,
+            ));
+            assert_eq!(app.back_stack.len(), 2);
+            app.open_repo("o".to_string(), "b".to_string());
+            app.open_repo("o".to_string(), "a".to_string());
+            let labels: Vec<String> = app.recent.iter().map(|r| r.label()).collect();
+            assert_eq!(labels.len(), 3);
+            assert_eq!(labels[0], "o/a");
+            assert_eq!(labels[1], "o/b");
+            assert_eq!(app.recent[2].number, Some(42));
+            assert_eq!(app.back_stack.len(), 4);
+        }
+
+        #[tokio::test]
+        async fn jump_back_walks_the_back_stack() {
+            let (mut app, _rx) = test_app();
+            app.open_repo("o".to_string(), "a".to_string());
+            app.load_id = 7;
+            app.update(Action::PrDetailLoaded(
+                Box::new(make_pull_request(42, "")),
+                7,
+            ));
+            app.open_repo("o".to_string(), "b".to_string());
+
+            app.update(Action::JumpBack);
+            assert_eq!(app.current_repo, Some(("o".to_string(), "a".to_string())));
+            assert_eq!(app.back_stack.len(), 2);
+            assert_eq!(app.back_stack.last().unwrap().number, Some(42));
+
+            app.update(Action::JumpBack);
+            assert_eq!(app.screen, Screen::RepoView);
+            assert_eq!(app.back_stack.len(), 1);
+
+            app.update(Action::JumpBack);
+            assert_eq!(app.back_stack.len(), 1);
+            assert!(app.flash_message.is_some());
+        }
+
+        #[tokio::test]
+        async fn select_recent_repo_opens_it() {
+            let (mut app, _rx) = test_app();
+            app.recent = vec![RecentItem {
+                owner: "o".to_string(),
+                repo: "r".to_string(),
+                number: None,
+                title: String::new(),
+                visited_at: chrono::Utc::now(),
+            }];
+            app.home_section = HomeSection::Recent;
+            app.update(Action::Select);
+            assert_eq!(app.screen, Screen::RepoView);
+            assert_eq!(app.current_repo, Some(("o".to_string(), "r".to_string())));
+        }
+
+        #[tokio::test]
+        async fn next_tab_on_repo_view_cycles() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            assert_eq!(app.repo_tab, RepoTab::PullRequests);
+            app.update(Action::NextTab);
+            assert_eq!(app.repo_tab, RepoTab::Issues);
+            app.update(Action::NextTab);
+            assert_eq!(app.repo_tab, RepoTab::Commits);
+            app.update(Action::NextTab);
+            assert_eq!(app.repo_tab, RepoTab::Actions);
+            app.update(Action::NextTab);
+            assert_eq!(app.repo_tab, RepoTab::PullRequests);
+        }
+
+        #[tokio::test]
+        async fn prev_tab_on_repo_view_cycles_backward() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            assert_eq!(app.repo_tab, RepoTab::PullRequests);
+            app.update(Action::PrevTab);
+            assert_eq!(app.repo_tab, RepoTab::Actions);
+            app.update(Action::PrevTab);
+            assert_eq!(app.repo_tab, RepoTab::Commits);
+            app.update(Action::PrevTab);
+            assert_eq!(app.repo_tab, RepoTab::Issues);
+            app.update(Action::PrevTab);
+            assert_eq!(app.repo_tab, RepoTab::PullRequests);
+        }
+
+        #[tokio::test]
+        async fn switch_repo_tab_sets_tab_and_resets_index() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.issue_index = 5;
+            app.update(Action::SwitchRepoTab(RepoTab::Issues));
+            assert_eq!(app.repo_tab, RepoTab::Issues);
+            assert_eq!(app.issue_index, 0);
+        }
+
+        #[tokio::test]
+        async fn refresh_on_home_reloads_home() {
+            let (mut app, _rx) = test_app();
+            app.update(Action::Refresh);
+            assert_eq!(app.screen, Screen::Home);
+            assert!(app.loading);
+        }
+
+        #[tokio::test]
+        async fn orgs_loaded_opens_popup_on_current_scope() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.repo_scope = Some("acme".to_string());
+            app.update(Action::OrgsLoaded(vec![
+                "tools".to_string(),
+                "acme".to_string(),
+            ]));
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+            assert_eq!(app.popup_title, "Repositories Of");
+            assert_eq!(app.popup_items[0], "Your repositories");
+            assert_eq!(app.popup_index, 2);
+        }
+
+        #[tokio::test]
+        async fn choosing_org_scopes_repo_list() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.repos = vec![make_repo("mine")];
+            app.repo_index = 0;
+            app.update(Action::OrgsLoaded(vec!["acme".to_string()]));
+            app.popup_index = 1;
+            app.update(Action::PopupSelect);
+            assert_eq!(app.repo_scope, Some("acme".to_string()));
+            assert!(app.repos.is_empty());
+            assert!(app.loading);
+            assert_eq!(app.repos_cache_key(), "GitHub_org_acme_repos");
+
+            // Back to the user's own repos
+            app.update(Action::OrgsLoaded(vec!["acme".to_string()]));
+            app.popup_index = 0;
+            app.update(Action::PopupSelect);
+            assert_eq!(app.repo_scope, None);
+            assert_eq!(app.repos_cache_key(), "GitHub_repos");
+        }
+
+        #[tokio::test]
+        async fn show_repo_list_from_home() {
+            let (mut app, _rx) = test_app();
+            app.update(Action::ShowRepoList);
+            assert_eq!(app.screen, Screen::RepoList);
+            assert!(app.loading);
+        }
+
+        #[tokio::test]
+        async fn hard_refresh_drops_preview_and_restores_cache_use() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.preview.prs.insert(1, make_pull_request(1, "old"));
+            app.preview.fetched.insert((RepoTab::PullRequests, 1));
+            app.update(Action::HardRefresh);
+            assert!(app.preview.prs.is_empty());
+            assert!(app.preview.fetched.is_empty());
+            assert!(!app.bypass_cache);
+        }
+
+        #[tokio::test]
+        async fn refresh_item_on_commits_tab_refreshes_list() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.repo_tab = RepoTab::Commits;
+            app.current_repo = Some(("o".to_string(), "r".to_string()));
+            app.load_id = 3;
+            app.update(Action::RefreshItem);
+            assert!(app.loading);
+            assert_eq!(app.load_id, 4);
+        }
+
+        #[tokio::test]
+        async fn item_refreshed_flashes() {
+            let (mut app, _rx) = test_app();
+            app.update(Action::ItemRefreshed("#7".to_string()));
+            assert_eq!(app.flash_message.unwrap().0, "Refreshed #7");
+        }
+
+        #[tokio::test]
+        async fn refresh_on_repo_list_sets_loading() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.update(Action::Refresh);
+            assert_eq!(app.screen, Screen::RepoList);
+            assert!(app.loading);
+        }
+
+        // Scroll/Index
+
+        #[tokio::test]
+        async fn click_row_selects_then_opens() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.repos = vec![make_repo("a"), make_repo("b")];
+            app.update(Action::ClickRow(ClickList::Repos, 1));
+            assert_eq!(app.repo_index, 1);
+            assert_eq!(app.screen, Screen::RepoList);
+            app.update(Action::ClickRow(ClickList::Repos, 1));
+            assert_eq!(app.screen, Screen::RepoView);
+        }
+
+        #[tokio::test]
+        async fn click_row_past_end_ignored() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.repos = vec![make_repo("a")];
+            app.update(Action::ClickRow(ClickList::Repos, 3));
+            assert_eq!(app.repo_index, 0);
+            assert_eq!(app.screen, Screen::RepoList);
+        }
+
+        #[tokio::test]
+        async fn click_row_in_other_home_section_focuses_it() {
+            let (mut app, _rx) = test_app();
+            app.my_prs = vec![make_my_pr("o", "r", 1)];
+            app.update(Action::ClickRow(ClickList::MyPrs, 0));
+            assert_eq!(app.home_section, HomeSection::MyPrs);
+            assert_eq!(app.screen, Screen::Home);
+        }
+
+        #[tokio::test]
+        async fn scroll_down_increments_repo_index() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.repos = vec![make_repo("a"), make_repo("b"), make_repo("c")];
+            app.repo_index = 0;
+            app.update(Action::ScrollDown);
+            assert_eq!(app.repo_index, 1);
+        }
+
+        #[tokio::test]
+        async fn scroll_down_at_end_no_overflow() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.repos = vec![make_repo("a"), make_repo("b")];
+            app.repo_index = 1;
+            app.update(Action::ScrollDown);
+            assert_eq!(app.repo_index, 1);
+        }
+
+        #[tokio::test]
+        async fn scroll_down_empty_list_noop() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.update(Action::ScrollDown);
+            assert_eq!(app.repo_index, 0);
+        }
+
+        #[tokio::test]
+        async fn scroll_up_decrements_repo_index() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.repos = vec![make_repo("a"), make_repo("b")];
+            app.repo_index = 1;
+            app.update(Action::ScrollUp);
+            assert_eq!(app.repo_index, 0);
+        }
+
+        #[tokio::test]
+        async fn scroll_up_at_zero_stays() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.repos = vec![make_repo("a")];
+            app.repo_index = 0;
+            app.update(Action::ScrollUp);
+            assert_eq!(app.repo_index, 0);
+        }
+
+        #[tokio::test]
+        async fn go_to_top_resets_index() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.repos = vec![make_repo("a"), make_repo("b"), make_repo("c")];
+            app.repo_index = 2;
+            app.update(Action::GoToTop);
+            assert_eq!(app.repo_index, 0);
+        }
+
+        #[tokio::test]
+        async fn go_to_bottom_sets_last_index() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.repos = vec![make_repo("a"), make_repo("b"), make_repo("c")];
+            app.update(Action::GoToBottom);
+            assert_eq!(app.repo_index, 2);
+        }
+
+        #[tokio::test]
+        async fn go_to_bottom_empty_list_noop() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.update(Action::GoToBottom);
+            assert_eq!(app.repo_index, 0);
+        }
+
+        #[tokio::test]
+        async fn page_down_advances_by_10_clamped() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            // Create 5 repos — page down should clamp to index 4
+            app.repos = (0..5).map(|i| make_repo(&format!("r{}", i))).collect();
+            app.repo_index = 0;
+            app.update(Action::PageDown);
+            assert_eq!(app.repo_index, 4);
+        }
+
+        #[tokio::test]
+        async fn page_up_decrements_by_10_saturating() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.repos = (0..20).map(|i| make_repo(&format!("r{}", i))).collect();
+            app.repo_index = 5;
+            app.update(Action::PageUp);
+            assert_eq!(app.repo_index, 0);
+        }
+
+        #[tokio::test]
+        async fn page_down_steps_by_viewport_height() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoList;
+            app.repos = (0..40).map(|i| make_repo(&format!("r{}", i))).collect();
+            app.page_height.set(23);
+            app.update(Action::PageDown);
+            assert_eq!(app.repo_index, 23);
+            app.update(Action::PageUp);
+            assert_eq!(app.repo_index, 0);
+        }
+
+        #[tokio::test]
+        async fn detail_scroll_stops_with_last_line_at_bottom() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            app.current_pr = Some(make_pull_request(1, &"line\n".repeat(60)));
+            let total = app.pr_detail_lines().len();
+            app.page_height.set(20);
+            app.update(Action::GoToBottom);
+            assert_eq!(app.scroll_offset, total - 20);
+        }
+
+        // Data loading (load_id)
+
+        #[tokio::test]
+        async fn home_loaded_matching_id_updates_data() {
+            let (mut app, _rx) = test_app();
+            app.load_id = 1;
+            let rrs = vec![make_review_request("o", "r", 1)];
+            let prs = vec![make_my_pr("o", "r", 2)];
+            app.update(Action::HomeLoaded {
+                review_requests: rrs.clone(),
+                my_prs: prs.clone(),
+                assigned: vec![make_issue_ref("o", "r", 3, false)],
+                mentions: vec![make_issue_ref("o", "r", 4, true)],
+                load_id: 1,
+            });
+            assert_eq!(app.review_requests.len(), 1);
+            assert_eq!(app.my_prs.len(), 1);
+            assert_eq!(app.assigned.len(), 1);
+            assert_eq!(app.mentions.len(), 1);
+            assert!(!app.loading);
+        }
+
+        #[tokio::test]
+        async fn home_refresh_alerts_new_review_requests_until_dismissed() {
+            let (mut app, _rx) = test_app();
+            app.ui_config.notifications = true;
+            let load = |reviews: Vec<ReviewRequest>| Action::HomeLoaded {
+                review_requests: reviews,
+                my_prs: vec![],
+                assigned: vec![],
+                mentions: vec![],
+                load_id: 0,
+            };
+            // The first load is the baseline, not news
+            app.update(load(vec![make_review_request("o", "r", 1)]));
+            assert!(app.alerts.is_empty());
+            app.update(load(vec![
+                make_review_request("o", "r", 1),
+                make_review_request("o", "r", 2),
+            ]));
+            assert_eq!(app.alerts.len(), 1);
+            assert!(app.alerts[0].contains("o/r#2"));
+            // The badge stays through further refreshes until dismissed
+            app.update(load(vec![make_review_request("o", "r", 2)]));
+            assert_eq!(app.alerts.len(), 1);
+            let action = app.handle_key_normal(KeyEvent::from(KeyCode::Char('!')));
+            app.update(action);
+            assert!(app.alerts.is_empty());
+        }
+
+        #[tokio::test]
+        async fn home_refresh_is_quiet_without_notifications() {
+            let (mut app, _rx) = test_app();
+            for n in 1..=2 {
+                app.update(Action::HomeLoaded {
+                    review_requests: vec![make_review_request("o", "r", n)],
+                    my_prs: vec![],
+                    assigned: vec![],
+                    mentions: vec![],
+                    load_id: 0,
+                });
+            }
+            assert!(app.alerts.is_empty());
+        }
+
+        #[tokio::test]
+        async fn f12_tails_the_log_and_follows_new_lines() {
+            let (mut app, _rx) = test_app();
+            let dir = std::env::temp_dir().join(format!("grit-logs-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("grit.log");
+            let lines: Vec<String> = (0..20).map(|i| format!("line {}", i)).collect();
+            std::fs::write(&path, lines.join("\n")).unwrap();
+            app.log_path = Some(path.clone());
+            app.page_height.set(5);
+            app.screen = Screen::RepoList;
+
+            let action = app.handle_event(key(KeyCode::F(12)));
+            app.update(action);
+            assert_eq!(app.screen, Screen::Logs);
+            assert_eq!(app.log_lines.len(), 20);
+            assert_eq!(app.scroll_offset, 15);
+
+            // At the bottom, new lines keep the view there
+            std::fs::write(&path, format!("{}\nline 20\n", lines.join("\n"))).unwrap();
+            app.update(Action::Tick);
+            assert_eq!(app.scroll_offset, 16);
+            // Scrolled up, it stays put
+            app.update(Action::ScrollUp);
+            std::fs::write(&path, format!("{}\nline 20\nline 21\n", lines.join("\n"))).unwrap();
+            app.update(Action::Tick);
+            assert_eq!(app.scroll_offset, 15);
+
+            app.update(Action::Back);
+            assert_eq!(app.screen, Screen::RepoList);
+            assert!(app.log_lines.is_empty());
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+
+        #[tokio::test]
+        async fn palette_offers_logs() {
+            let (mut app, _rx) = test_app();
+            app.update(Action::ShowPalette);
+            for c in "logs".chars() {
+                app.update(Action::PaletteInput(c));
+            }
+            let entry = &app.palette_matches()[0];
+            assert_eq!((entry.name, entry.key.as_str()), ("logs", "F12"));
+        }
+
+        #[tokio::test]
+        async fn review_sort_cycles_and_keeps_selection() {
+            let (mut app, _rx) = test_app();
+            let mut old = make_review_request("zed", "r", 1);
+            old.updated_at = chrono::Utc::now() - chrono::Duration::days(5);
+            let mut new = make_review_request("abc", "r", 2);
+            new.author = "Zoe".to_string();
+            app.update(Action::HomeLoaded {
+                review_requests: vec![old, new],
+                my_prs: vec![],
+                assigned: vec![],
+                mentions: vec![],
+                load_id: 0,
+            });
+            let numbers = |app: &App| -> Vec<u64> {
+                app.review_requests.iter().map(|r| r.pr_number).collect()
+            };
+            assert_eq!(numbers(&app), vec![2, 1]);
+            assert_eq!(app.review_index, 0);
+
+            app.update(Action::CycleReviewSort);
+            assert_eq!(app.review_sort, ReviewSort::Oldest);
+            assert_eq!(numbers(&app), vec![1, 2]);
+            assert_eq!(app.review_index, 1);
+
+            app.update(Action::CycleReviewSort);
+            assert_eq!(app.review_sort, ReviewSort::Repo);
+            assert_eq!(numbers(&app), vec![2, 1]);
+
+            app.update(Action::CycleReviewSort);
+            assert_eq!(app.review_sort, ReviewSort::Author);
+            assert_eq!(numbers(&app), vec![1, 2]);
+
+            app.update(Action::CycleReviewSort);
+            assert_eq!(app.review_sort, ReviewSort::Newest);
+        }
+
+        #[tokio::test]
+        async fn home_loaded_stale_id_ignored() {
+            let (mut app, _rx) = test_app();
+            app.load_id = 2;
+            app.update(Action::HomeLoaded {
+                review_requests: vec![make_review_request("o", "r", 1)],
+                my_prs: vec![],
+                assigned: vec![],
+                mentions: vec![],
+                load_id: 1,
+            });
+            assert!(app.review_requests.is_empty());
+        }
+
+        #[tokio::test]
+        async fn repos_loaded_matching_id_updates() {
+            let (mut app, _rx) = test_app();
+            app.load_id = 3;
+            app.repo_index = 10;
+            let repos = vec![make_repo("a"), make_repo("b")];
+            app.update(Action::ReposLoaded(repos, PageInfo::default(), 3));
+            assert_eq!(app.repos.len(), 2);
+            assert_eq!(app.repo_index, 1); // clamped
+        }
+
+        #[tokio::test]
+        async fn repos_loaded_stale_id_ignored() {
+            let (mut app, _rx) = test_app();
+            app.load_id = 5;
+            app.update(Action::ReposLoaded(
+                vec![make_repo("a")],
+                PageInfo::default(),
+                3,
+            ));
+            assert!(app.repos.is_empty());
+        }
+
+        #[tokio::test]
+        async fn pr_detail_loaded_first_time_transitions_screen() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.load_id = 1;
+            let pr = make_pull_request(42, "test body");
+            app.update(Action::PrDetailLoaded(Box::new(pr), 1));
+            assert_eq!(app.screen, Screen::PrDetail);
+            assert!(app.current_pr.is_some());
+            assert_eq!(app.scroll_offset, 0);
+        }
+
+        #[tokio::test]
+        async fn pr_detail_loaded_already_on_pr_detail_no_scroll_reset() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            app.load_id = 2;
+            app.scroll_offset = 10;
+            let pr = make_pull_request(42, "updated body");
+            app.update(Action::PrDetailLoaded(Box::new(pr), 2));
+            assert_eq!(app.screen, Screen::PrDetail);
+            assert!(app.current_pr.is_some());
+            assert_eq!(app.scroll_offset, 10); // not reset
+        }
 
-    fn make_my_pr(owner: &str, repo: &str, number: u64) -> MyPr {
-        MyPr {
-            repo_owner: owner.to_string(),
-            repo_name: repo.to_string(),
-            number,
-            title: format!("My PR #{}", number),
-            state: PrState::Open,
-            checks_status: ChecksStatus::Success,
-            updated_at: chrono::Utc::now(),
+        // Popup & confirm
+
+        #[tokio::test]
+        async fn show_merge_method_select() {
+            let (mut app, _rx) = test_app();
+            app.update(Action::ShowMergeMethodSelect);
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+            assert_eq!(app.popup_items.len(), 3);
         }
-    }
 
-    fn make_pull_request(number: u64, body: &str) -> PullRequest {
-        PullRequest {
-            number,
-            title: format!("PR #{}", number),
-            body: Some(body.to_string()),
-            state: PrState::Open,
-            author: "testauthor".to_string(),
-            head_branch: "feature".to_string(),
-            base_branch: "main".to_string(),
-            stats: PrStats {
-                additions: 10,
-                deletions: 5,
-                changed_files: 3,
-                commits: 2,
-                comments: 1,
-            },
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-            merged_at: None,
-            closed_at: None,
+        #[tokio::test]
+        async fn merge_popup_starts_from_config_and_toggles_branch_deletion() {
+            let (mut app, mut rx) = test_app();
+            let config: crate::config::Config = toml::from_str(
+                r#"
+[[forges]]
+name = "GitHub"
+type = "github"
+host = "github.com"
+[forges.repos."owner/repo"]
+default_merge_method = "squash"
+"#,
+            )
+            .unwrap();
+            app.forge_configs = config.forges;
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            app.current_pr = Some(make_pull_request(7, "body"));
+            app.update(Action::ShowMergeMethodSelect);
+            assert_eq!(app.popup_index, 1);
+            assert_eq!(app.popup_items[4], "[ ] Delete feature after merge");
+
+            // Space only ticks checkbox rows
+            app.update(app.handle_event(key(KeyCode::Char(' '))));
+            app.popup_index = 4;
+            app.update(app.handle_event(key(KeyCode::Char(' '))));
+            assert_eq!(app.popup_items[3], "[ ] Merge when checks pass");
+            assert_eq!(app.popup_items[4], "[x] Delete feature after merge");
+            app.popup_index = 1;
+            app.update(Action::PopupSelect);
+            let confirm = std::iter::from_fn(|| rx.try_recv().ok()).find_map(|a| match a {
+                Action::ShowConfirm(confirm) => Some(confirm),
+                _ => None,
+            });
+            assert!(matches!(
+                confirm,
+                Some(ConfirmAction::MergePr {
+                    number: 7,
+                    method: MergeMethod::Squash,
+                    delete_branch: Some(ref branch),
+                    auto: false,
+                }) if branch == "feature"
+            ));
         }
-    }
 
-    fn make_commit_detail(sha: &str, message: &str, files: Vec<CommitFile>) -> CommitDetail {
-        CommitDetail {
-            sha: sha.to_string(),
-            message: message.to_string(),
-            author: "testauthor".to_string(),
-            date: chrono::Utc::now(),
-            stats: CommitStats {
-                additions: 10,
-                deletions: 5,
-                total: 15,
-            },
-            files,
+        #[tokio::test]
+        async fn blocked_pr_can_only_be_auto_merged() {
+            let (mut app, mut rx) = test_app();
+            let mut pr = make_pull_request(7, "body");
+            pr.mergeable_state = MergeableState::BlockedByChecks;
+            app.current_pr = Some(pr);
+            app.update(Action::ShowMergeMethodSelect);
+            assert_eq!(app.popup_items[3], "[x] Merge when checks pass");
+
+            app.popup_index = 3;
+            app.update(Action::PopupSelect);
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+            app.popup_index = 0;
+            app.update(Action::PopupSelect);
+            assert!(app.error.as_deref().unwrap().contains("auto-merge"));
+
+            app.error = None;
+            app.update(Action::ShowMergeMethodSelect);
+            app.update(Action::PopupSelect);
+            let confirm = std::iter::from_fn(|| rx.try_recv().ok()).find_map(|a| match a {
+                Action::ShowConfirm(confirm) => Some(confirm),
+                _ => None,
+            });
+            assert!(matches!(
+                confirm,
+                Some(ConfirmAction::MergePr { auto: true, .. })
+            ));
+
+            app.update(Action::AutoMergeEnabled(7));
+            assert!(app.current_pr.as_ref().unwrap().auto_merge);
         }
-    }
 
-    fn make_action_run(id: u64, name: &str) -> ActionRun {
-        ActionRun {
-            id,
-            name: name.to_string(),
-            status: ActionStatus::Completed,
-            conclusion: Some(ActionConclusion::Success),
-            branch: "main".to_string(),
-            event: "push".to_string(),
-            created_at: chrono::Utc::now(),
+        #[tokio::test]
+        async fn merge_blocked_when_pr_has_conflicts() {
+            let (mut app, _rx) = test_app();
+            let mut pr = make_pull_request(7, "body");
+            pr.mergeable_state = MergeableState::Conflicts;
+            app.current_pr = Some(pr);
+            app.update(Action::ShowMergeMethodSelect);
+            assert_eq!(app.input_mode, InputMode::Normal);
+            assert!(app.error.as_deref().unwrap().contains("merge conflicts"));
         }
-    }
 
-    // ── Key handling tests ──
+        #[tokio::test]
+        async fn merge_allowed_when_checks_failing() {
+            let (mut app, _rx) = test_app();
+            let mut pr = make_pull_request(7, "body");
+            pr.mergeable_state = MergeableState::Unstable;
+            app.current_pr = Some(pr);
+            app.update(Action::ShowMergeMethodSelect);
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+        }
 
-    mod key_handling {
-        use super::*;
+        #[tokio::test]
+        async fn show_review_select() {
+            let (mut app, _rx) = test_app();
+            app.update(Action::ShowReviewSelect);
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+            assert_eq!(app.popup_items.len(), 3);
+        }
 
-        // Normal mode
+        #[tokio::test]
+        async fn popup_down_increments() {
+            let (mut app, _rx) = test_app();
+            app.popup_items = vec!["a".into(), "b".into(), "c".into()];
+            app.popup_index = 0;
+            app.update(Action::PopupDown);
+            assert_eq!(app.popup_index, 1);
+        }
 
         #[tokio::test]
-        async fn q_on_home_quits() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::Char('q')));
-            assert!(matches!(action, Action::Quit));
+        async fn popup_up_decrements() {
+            let (mut app, _rx) = test_app();
+            app.popup_items = vec!["a".into(), "b".into(), "c".into()];
+            app.popup_index = 2;
+            app.update(Action::PopupUp);
+            assert_eq!(app.popup_index, 1);
         }
 
         #[tokio::test]
-        async fn q_on_repo_list_goes_back() {
+        async fn show_confirm_sets_state() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoList;
-            let action = app.handle_event(key(KeyCode::Char('q')));
-            assert!(matches!(action, Action::Back));
+            app.update(Action::ShowConfirm(ConfirmAction::ClosePr(42)));
+            assert_eq!(app.input_mode, InputMode::Confirm);
+            assert!(matches!(
+                app.confirm_action,
+                Some(ConfirmAction::ClosePr(42))
+            ));
         }
 
         #[tokio::test]
-        async fn esc_on_home_quits() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::Esc));
-            assert!(matches!(action, Action::Quit));
+        async fn confirm_no_resets() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::Confirm;
+            app.confirm_action = Some(ConfirmAction::ClosePr(42));
+            app.update(Action::ConfirmNo);
+            assert_eq!(app.input_mode, InputMode::Normal);
+            assert!(app.confirm_action.is_none());
+        }
+
+        // Preview pane
+
+        #[tokio::test]
+        async fn v_toggles_preview_in_repo_view() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.update(app.handle_event(key(KeyCode::Char('v'))));
+            assert!(app.preview.enabled);
+            app.update(app.handle_event(key(KeyCode::Char('v'))));
+            assert!(!app.preview.enabled);
+        }
+
+        #[tokio::test]
+        async fn preview_keeps_details_for_current_repo_only() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            app.issues = vec![make_issue(7, "Broken")];
+            app.repo_tab = RepoTab::Issues;
+            app.update(Action::TogglePreview);
+
+            app.update(Action::PreviewIssueLoaded(
+                "owner".to_string(),
+                "other".to_string(),
+                7,
+                Some("elsewhere".to_string()),
+            ));
+            assert!(app.preview.issue_bodies.is_empty());
+            app.update(Action::PreviewIssueLoaded(
+                "owner".to_string(),
+                "repo".to_string(),
+                7,
+                Some("steps".to_string()),
+            ));
+            assert_eq!(app.preview.issue_bodies[&7].as_deref(), Some("steps"));
+
+            // Opening another repo starts the preview afresh
+            app.current_repo = Some(("owner".to_string(), "next".to_string()));
+            app.update(Action::Tick);
+            assert!(app.preview.enabled);
+            assert!(app.preview.issue_bodies.is_empty());
+        }
+
+        // Find commit
+
+        fn hit(repo: &str, sha: &str, age_days: i64) -> CommitHit {
+            let mut commit = make_commit(sha, "Fix cache");
+            commit.date = chrono::Utc::now() - chrono::Duration::days(age_days);
+            CommitHit {
+                owner: "testowner".to_string(),
+                repo: repo.to_string(),
+                commit,
+            }
+        }
+
+        #[tokio::test]
+        async fn w_on_actions_lists_workflows_and_enter_picks_the_latest_run() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.repo_tab = RepoTab::Actions;
+            app.action_runs = vec![
+                make_action_run(3, "Docs"),
+                make_action_run(2, "CI"),
+                make_action_run(1, "CI"),
+            ];
+            for (i, run) in app.action_runs.iter_mut().enumerate() {
+                run.created_at -= chrono::Duration::minutes(i as i64);
+            }
+            app.update(app.handle_event(key(KeyCode::Char('w'))));
+            assert!(app.workflow_summary);
+
+            // The newest run of each workflow decides the order
+            let mut history = app.action_runs.clone();
+            history[0].created_at -= chrono::Duration::hours(1);
+            app.update(Action::WorkflowHistoryLoaded(history, app.load_id));
+            let names: Vec<&str> = app.workflows.iter().map(|w| w.name.as_str()).collect();
+            assert_eq!(names, ["CI", "Docs"]);
+
+            app.update(Action::ScrollDown);
+            app.update(Action::ScrollDown);
+            assert_eq!(app.workflow_index, 1);
+            app.update(Action::GoToTop);
+            app.update(Action::Select);
+            assert!(!app.workflow_summary);
+            assert_eq!(app.action_index, 1);
+        }
+
+        #[tokio::test]
+        async fn artifacts_popup_starts_one_download_at_a_time() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.repo_tab = RepoTab::Actions;
+            let artifact = |id, name: &str, expired| Artifact {
+                id,
+                name: name.to_string(),
+                size: 2048,
+                expired,
+                download_url: String::new(),
+            };
+            app.update(Action::ArtifactsLoaded(vec![
+                artifact(1, "old-coverage", true),
+                artifact(2, "test-binaries", false),
+            ]));
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+            assert_eq!(
+                app.popup_items,
+                ["old-coverage (2.0 KB, expired)", "test-binaries (2.0 KB)"]
+            );
+
+            // Expired artifacts can't be fetched any more
+            app.update(Action::PopupSelect);
+            assert!(app.download.is_none());
+
+            app.update(Action::ArtifactsLoaded(vec![artifact(
+                2,
+                "test-binaries",
+                false,
+            )]));
+            app.update(Action::PopupSelect);
+            assert_eq!(
+                app.download.as_ref().map(|d| (d.name.as_str(), d.total)),
+                Some(("test-binaries", Some(2048)))
+            );
+            app.update(Action::DownloadProgress(1024, None));
+            assert_eq!(app.download.as_ref().map(|d| d.received), Some(1024));
+
+            app.update(Action::ShowArtifactSelect);
+            assert!(!app.loading);
+            app.update(Action::ArtifactDownloaded("test-binaries.zip".into()));
+            assert!(app.download.is_none());
+        }
+
+        #[tokio::test]
+        async fn hash_prompt_takes_digits_and_opens_the_number() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            app.update(app.handle_event(key(KeyCode::Char('#'))));
+            assert_eq!(app.input_mode, InputMode::JumpToNumber);
+            for c in "12x3".chars() {
+                app.update(app.handle_event(key(KeyCode::Char(c))));
+            }
+            assert_eq!(app.jump_input, "123");
+
+            app.update(app.handle_event(key(KeyCode::Enter)));
+            assert_eq!(app.input_mode, InputMode::Normal);
+            assert!(app.loading);
+            let load_id = app.load_id;
+
+            app.update(Action::JumpedToPr(
+                Box::new(make_pull_request(123, "body")),
+                load_id,
+            ));
+            assert_eq!(app.screen, Screen::PrDetail);
+            assert_eq!(app.current_pr.as_ref().map(|p| p.number), Some(123));
+        }
+
+        #[tokio::test]
+        async fn jumping_to_an_issue_selects_it_on_the_issues_tab() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.issues = vec![make_issue(5, "five"), make_issue(4, "four")];
+            app.update(Action::JumpedToIssue(make_issue(4, "four"), app.load_id));
+            assert_eq!(app.repo_tab, RepoTab::Issues);
+            assert_eq!(app.issue_index, 1);
+
+            // Past the loaded page, the issue goes to the top of the list
+            app.update(Action::JumpedToIssue(make_issue(1, "one"), app.load_id));
+            assert_eq!(app.issues.len(), 3);
+            assert_eq!(app.issue_index, 0);
+            assert_eq!(app.issues[0].number, 1);
+
+            // A stale answer is ignored
+            app.update(Action::JumpedToIssue(make_issue(2, "two"), app.load_id + 1));
+            assert_eq!(app.issues.len(), 3);
+        }
+
+        #[tokio::test]
+        async fn find_commit_prompt_collects_query() {
+            let (mut app, _rx) = test_app();
+            let action = app.handle_event(key(KeyCode::Char('S')));
+            app.update(action);
+            assert_eq!(app.input_mode, InputMode::FindCommit);
+            for c in "abcd".chars() {
+                app.update(app.handle_event(key(KeyCode::Char(c))));
+            }
+            app.update(Action::FindCommitBackspace);
+            assert_eq!(app.find_query, "abc");
+            app.update(app.handle_event(key(KeyCode::Esc)));
+            assert_eq!(app.input_mode, InputMode::Normal);
+        }
+
+        #[tokio::test]
+        async fn commit_hits_open_popup_newest_first_without_duplicates() {
+            let (mut app, _rx) = test_app();
+            app.find_query = "cache".to_string();
+            app.update(Action::CommitHitsLoaded(
+                "cache".to_string(),
+                vec![hit("a", "aaaa111", 3), hit("b", "bbbb222", 1)],
+            ));
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+            assert_eq!(app.popup_title, "Find Commit");
+            app.popup_index = 1;
+            app.update(Action::CommitHitsLoaded(
+                "cache".to_string(),
+                vec![hit("a", "aaaa111", 3), hit("c", "cccc333", 0)],
+            ));
+            assert_eq!(app.commit_hits.len(), 3);
+            assert_eq!(app.commit_hits[0].repo, "c");
+            // Still on the hit that was highlighted
+            assert_eq!(app.popup_index, 2);
+            assert!(app.popup_items[0].starts_with("testowner/c  cccc333"));
+        }
+
+        #[tokio::test]
+        async fn stale_commit_hits_ignored() {
+            let (mut app, _rx) = test_app();
+            app.find_query = "new".to_string();
+            app.update(Action::CommitHitsLoaded(
+                "old".to_string(),
+                vec![hit("a", "aaaa111", 0)],
+            ));
+            assert!(app.commit_hits.is_empty());
+            assert_eq!(app.input_mode, InputMode::Normal);
+        }
+
+        #[tokio::test]
+        async fn closed_find_popup_stays_closed() {
+            let (mut app, _rx) = test_app();
+            app.find_query = "cache".to_string();
+            app.update(Action::CommitHitsLoaded(
+                "cache".to_string(),
+                vec![hit("a", "aaaa111", 0)],
+            ));
+            app.update(Action::ConfirmNo);
+            app.update(Action::CommitHitsLoaded(
+                "cache".to_string(),
+                vec![hit("b", "bbbb222", 0)],
+            ));
+            assert_eq!(app.input_mode, InputMode::Normal);
+            assert_eq!(app.commit_hits.len(), 2);
+        }
+
+        #[tokio::test]
+        async fn selecting_commit_hit_opens_its_repo() {
+            let (mut app, _rx) = test_app();
+            app.find_query = "cache".to_string();
+            app.update(Action::CommitHitsLoaded(
+                "cache".to_string(),
+                vec![hit("a", "aaaa111", 0)],
+            ));
+            app.update(Action::PopupSelect);
+            assert_eq!(
+                app.current_repo,
+                Some(("testowner".to_string(), "a".to_string()))
+            );
         }
 
+        // Quick comment
+
         #[tokio::test]
-        async fn esc_on_repo_list_goes_back() {
+        async fn quick_comment_needs_a_pr() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoList;
-            let action = app.handle_event(key(KeyCode::Esc));
-            assert!(matches!(action, Action::Back));
+            app.update(Action::StartQuickComment);
+            assert_eq!(app.input_mode, InputMode::Normal);
         }
 
         #[tokio::test]
-        async fn esc_with_active_search_clears() {
+        async fn quick_comment_typing_and_cancel() {
             let (mut app, _rx) = test_app();
-            app.search.active = true;
-            let action = app.handle_event(key(KeyCode::Esc));
-            assert!(matches!(action, Action::ClearSearch));
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.update(Action::StartQuickComment);
+            assert_eq!(app.input_mode, InputMode::Comment);
+            app.update(Action::QuickCommentInput('h'));
+            app.update(Action::QuickCommentInput('i'));
+            app.update(Action::QuickCommentBackspace);
+            assert_eq!(app.comment_input, "h");
+            app.update(Action::CancelQuickComment);
+            assert_eq!(app.input_mode, InputMode::Normal);
+            assert!(app.comment_input.is_empty());
         }
 
         #[tokio::test]
-        async fn j_scrolls_down() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::Char('j')));
-            assert!(matches!(action, Action::ScrollDown));
+        async fn form_starts_from_the_issue_and_resolves_its_milestone() {
+            let (mut app, _rx) = test_app();
+            app.current_repo = Some(("o".to_string(), "r".to_string()));
+            let mut issue = make_issue(3, "Bug");
+            issue.labels = vec![Label {
+                name: "bug".to_string(),
+                color: None,
+            }];
+            issue.assignees = vec!["alice".to_string()];
+            issue.milestone = Some("v1".to_string());
+            app.issues = vec![issue];
+
+            app.update(Action::ShowForm(FormTarget::Issue(3)));
+            assert_eq!(app.input_mode, InputMode::Form);
+            assert_eq!(
+                app.form.values,
+                ["Bug", "bug", "alice", "v1"].map(String::from)
+            );
+
+            app.update(Action::FormNextField);
+            for c in ", ui".chars() {
+                app.update(Action::FormInput(c));
+            }
+            app.update(Action::FormNextField);
+            for c in ", @bob".chars() {
+                app.update(Action::FormInput(c));
+            }
+            let milestones = [Milestone {
+                id: 42,
+                title: "V1".to_string(),
+                due_on: None,
+            }];
+            assert_eq!(
+                app.form.fields(&milestones).unwrap(),
+                IssueFields {
+                    title: "Bug".to_string(),
+                    labels: vec!["bug".to_string(), "ui".to_string()],
+                    assignees: vec!["alice".to_string(), "bob".to_string()],
+                    milestone: Some(42),
+                }
+            );
+            assert!(app.form.fields(&[]).is_err());
+
+            // Clearing the milestone field clears the milestone
+            app.update(Action::FormNextField);
+            app.update(Action::FormBackspace);
+            app.update(Action::FormBackspace);
+            assert_eq!(app.form.fields(&[]).unwrap().milestone, None);
         }
 
         #[tokio::test]
-        async fn down_scrolls_down() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::Down));
-            assert!(matches!(action, Action::ScrollDown));
+        async fn new_issue_form_needs_a_title_then_asks_for_a_description() {
+            let (mut app, mut rx) = test_app();
+            app.current_repo = Some(("o".to_string(), "r".to_string()));
+            app.update(Action::ShowForm(FormTarget::NewIssue));
+            app.update(Action::FormNextField);
+            app.update(Action::SubmitForm);
+            assert_eq!(app.input_mode, InputMode::Form);
+            assert_eq!(app.form.focus, 0);
+
+            app.update(Action::FormInput('T'));
+            app.update(Action::SubmitForm);
+            assert_eq!(app.input_mode, InputMode::Normal);
+            assert!(matches!(
+                rx.try_recv(),
+                Ok(Action::SuspendForEditor(EditorContext::NewIssue { form, .. }))
+                    if form.values[0] == "T"
+            ));
         }
 
         #[tokio::test]
-        async fn k_scrolls_up() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::Char('k')));
-            assert!(matches!(action, Action::ScrollUp));
+        async fn checklist_lists_the_description_tasks() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            app.current_pr = Some(make_pull_request(1, "No tasks here"));
+            app.update(Action::ShowChecklist);
+            assert_eq!(app.input_mode, InputMode::Normal);
+            assert!(app.flash_message.is_some());
+
+            app.current_pr = Some(make_pull_request(1, "- [x] build\n- [ ] ship\n"));
+            app.update(Action::ShowChecklist);
+            assert_eq!(app.input_mode, InputMode::MultiSelectPopup);
+            assert_eq!(app.popup_items, vec!["build", "ship"]);
+            assert_eq!(app.popup_checked, vec![true, false]);
+            let body: Vec<String> = app
+                .pr_detail_lines()
+                .into_iter()
+                .take(2)
+                .map(|(_, text)| text)
+                .collect();
+            assert_eq!(body, vec!["☑ build", "☐ ship"]);
         }
 
         #[tokio::test]
-        async fn up_scrolls_up() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::Up));
-            assert!(matches!(action, Action::ScrollUp));
+        async fn issue_template_fills_the_form_and_the_editor() {
+            let (mut app, mut rx) = test_app();
+            app.current_repo = Some(("o".to_string(), "r".to_string()));
+            app.snippets = vec![("thanks".to_string(), "Thanks!".to_string())];
+            app.update(Action::IssueTemplatesLoaded(vec![IssueTemplate::parse(
+                "bug.md",
+                "---\nname: Bug\nabout: Something broke\ntitle: '[BUG] '\nlabels: bug\n---\n## Steps\n",
+            )]));
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+            assert_eq!(app.popup_items, vec!["Blank", "Bug - Something broke"]);
+
+            app.popup_index = 1;
+            app.update(Action::PopupSelect);
+            assert_eq!(app.input_mode, InputMode::Form);
+            assert_eq!(app.form.values[0], "[BUG] ");
+            assert_eq!(app.form.values[1], "bug");
+
+            // The template's body goes to the editor in place of a snippet
+            app.update(Action::FormInput('x'));
+            app.update(Action::SubmitForm);
+            assert!(matches!(
+                rx.try_recv(),
+                Ok(Action::SuspendForEditor(EditorContext::NewIssue { .. }))
+            ));
+            assert_eq!(app.editor_prefill.as_deref(), Some("## Steps\n"));
         }
 
         #[tokio::test]
-        async fn g_goes_to_top() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::Char('g')));
-            assert!(matches!(action, Action::GoToTop));
+        async fn no_issue_templates_opens_a_blank_form() {
+            let (mut app, _rx) = test_app();
+            app.current_repo = Some(("o".to_string(), "r".to_string()));
+            app.update(Action::IssueTemplatesLoaded(vec![]));
+            assert_eq!(app.input_mode, InputMode::Form);
+            assert_eq!(app.form.target, FormTarget::NewIssue);
+            assert!(app.form.values.iter().all(String::is_empty));
         }
 
         #[tokio::test]
-        async fn home_goes_to_top() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::Home));
-            assert!(matches!(action, Action::GoToTop));
+        async fn cancelled_quick_comment_kept_as_draft() {
+            let (mut app, _rx) = test_app();
+            app.current_repo = Some(("o".to_string(), "r".to_string()));
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.update(Action::StartQuickComment);
+            app.update(Action::QuickCommentInput('h'));
+            app.update(Action::CancelQuickComment);
+            assert_eq!(app.comment_drafts.len(), 1);
+            app.update(Action::StartQuickComment);
+            assert_eq!(app.comment_input, "h");
+            assert!(app.comment_drafts.is_empty());
         }
 
         #[tokio::test]
-        async fn big_g_goes_to_bottom() {
-            let (app, _rx) = test_app();
-            // G is uppercase, which crossterm sends as Char('G') with SHIFT
-            let action = app.handle_event(key(KeyCode::Char('G')));
-            assert!(matches!(action, Action::GoToBottom));
+        async fn quit_without_pending_work_quits() {
+            let (mut app, _rx) = test_app();
+            app.update(Action::Quit);
+            assert!(app.should_quit);
         }
 
         #[tokio::test]
-        async fn end_goes_to_bottom() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::End));
-            assert!(matches!(action, Action::GoToBottom));
+        async fn quit_with_draft_asks_first() {
+            let (mut app, _rx) = test_app();
+            app.comment_drafts
+                .insert(("o".to_string(), "r".to_string(), 7), "wip".to_string());
+            app.update(Action::Back);
+            assert!(!app.should_quit);
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+            assert_eq!(app.popup_items[2], "Jump to draft on o/r#7");
+            assert_eq!(app.quit_summary(), "drafts: 1, watched runs: 0");
+
+            // Cancel is preselected
+            app.update(Action::PopupSelect);
+            assert!(!app.should_quit);
+
+            app.update(Action::Quit);
+            app.popup_index = 0;
+            app.update(Action::PopupSelect);
+            assert!(app.should_quit);
         }
 
         #[tokio::test]
-        async fn ctrl_d_pages_down() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key_ctrl('d'));
-            assert!(matches!(action, Action::PageDown));
+        async fn active_runs_on_actions_tab_are_watched() {
+            let (mut app, _rx) = test_app();
+            app.screen = Screen::RepoView;
+            app.repo_tab = RepoTab::Actions;
+            let mut run = make_action_run(1, "ci");
+            run.status = ActionStatus::InProgress;
+            app.action_runs = vec![run, make_action_run(2, "done")];
+            assert_eq!(app.pending_work(), vec![PendingWork::WatchedRuns(1)]);
         }
 
         #[tokio::test]
-        async fn ctrl_u_pages_up() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key_ctrl('u'));
-            assert!(matches!(action, Action::PageUp));
+        async fn blank_quick_comment_is_not_posted() {
+            let (mut app, _rx) = test_app();
+            app.current_repo = Some(("o".to_string(), "r".to_string()));
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.update(Action::StartQuickComment);
+            app.update(Action::QuickCommentInput(' '));
+            app.update(Action::SubmitQuickComment);
+            assert_eq!(app.input_mode, InputMode::Normal);
+            assert!(app.comment_input.is_empty());
         }
 
+        // Search state machine
+
         #[tokio::test]
-        async fn slash_enters_search() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::Char('/')));
-            assert!(matches!(action, Action::EnterSearchMode));
+        async fn enter_search_mode() {
+            let (mut app, _rx) = test_app();
+            app.search.query = "old".to_string();
+            app.update(Action::EnterSearchMode);
+            assert_eq!(app.input_mode, InputMode::Search);
+            assert!(app.search.query.is_empty());
         }
 
         #[tokio::test]
-        async fn n_with_active_search_next() {
+        async fn search_input_appends() {
             let (mut app, _rx) = test_app();
-            app.search.active = true;
-            let action = app.handle_event(key(KeyCode::Char('n')));
-            assert!(matches!(action, Action::SearchNext));
+            app.input_mode = InputMode::Search;
+            app.update(Action::SearchInput('a'));
+            assert_eq!(app.search.query, "a");
+            app.update(Action::SearchInput('b'));
+            assert_eq!(app.search.query, "ab");
         }
 
         #[tokio::test]
-        async fn big_n_with_active_search_prev() {
+        async fn search_backspace_pops() {
             let (mut app, _rx) = test_app();
-            app.search.active = true;
-            let action = app.handle_event(key(KeyCode::Char('N')));
-            assert!(matches!(action, Action::SearchPrev));
+            app.input_mode = InputMode::Search;
+            app.search.query = "ab".to_string();
+            app.update(Action::SearchBackspace);
+            assert_eq!(app.search.query, "a");
         }
 
         #[tokio::test]
-        async fn n_without_search_is_not_search_next() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::Char('n')));
-            assert!(!matches!(action, Action::SearchNext));
+        async fn search_backspace_empty_deactivates() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::Search;
+            app.search.query = "a".to_string();
+            app.search.active = true;
+            app.update(Action::SearchBackspace);
+            // query is now empty
+            assert!(app.search.query.is_empty());
+            assert!(!app.search.active);
         }
 
         #[tokio::test]
-        async fn enter_selects() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::Enter));
-            assert!(matches!(action, Action::Select));
+        async fn exit_search_mode_keeps_active() {
+            let (mut app, _rx) = test_app();
+            app.input_mode = InputMode::Search;
+            app.search.query = "foo".to_string();
+            app.update(Action::ExitSearchMode);
+            assert_eq!(app.input_mode, InputMode::Normal);
+            assert!(app.search.active);
         }
 
         #[tokio::test]
-        async fn d_on_pr_detail_views_diff() {
+        async fn search_confirm_activates() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::PrDetail;
-            let action = app.handle_event(key(KeyCode::Char('d')));
-            assert!(matches!(action, Action::ViewDiff));
+            app.input_mode = InputMode::Search;
+            app.search.query = "bar".to_string();
+            app.update(Action::SearchConfirm);
+            assert_eq!(app.input_mode, InputMode::Normal);
+            assert!(app.search.active);
         }
 
         #[tokio::test]
-        async fn d_on_commit_detail_views_diff() {
+        async fn clear_search_resets() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::CommitDetail;
-            let action = app.handle_event(key(KeyCode::Char('d')));
-            assert!(matches!(action, Action::ViewDiff));
+            app.search.query = "foo".to_string();
+            app.search.active = true;
+            app.search.match_indices = vec![0, 1, 2];
+            app.update(Action::ClearSearch);
+            assert!(app.search.query.is_empty());
+            assert!(!app.search.active);
+            assert!(app.search.match_indices.is_empty());
         }
 
         #[tokio::test]
-        async fn d_on_repo_list_not_view_diff() {
+        async fn search_on_repo_list_computes_matches() {
             let (mut app, _rx) = test_app();
             app.screen = Screen::RepoList;
-            let action = app.handle_event(key(KeyCode::Char('d')));
-            assert!(!matches!(action, Action::ViewDiff));
+            app.repos = vec![make_repo("foo-bar"), make_repo("baz"), make_repo("foo-qux")];
+            app.update(Action::SearchInput('f'));
+            app.update(Action::SearchInput('o'));
+            app.update(Action::SearchInput('o'));
+            assert_eq!(app.search.match_indices, vec![0, 2]);
         }
 
+        // Mutation results
+
         #[tokio::test]
-        async fn r_refreshes() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::Char('r')));
-            assert!(matches!(action, Action::Refresh));
+        async fn pr_merged_sets_flash() {
+            let (mut app, _rx) = test_app();
+            app.update(Action::PrMerged);
+            assert!(app.flash_message.is_some());
+            assert_eq!(app.flash_message.as_ref().unwrap().0, "PR merged!");
         }
 
         #[tokio::test]
-        async fn o_opens_in_browser() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::Char('o')));
-            assert!(matches!(action, Action::OpenInBrowser));
+        async fn pr_closed_sets_flash() {
+            let (mut app, _rx) = test_app();
+            app.update(Action::PrClosed);
+            assert!(app.flash_message.is_some());
+            assert_eq!(app.flash_message.as_ref().unwrap().0, "PR closed.");
         }
 
         #[tokio::test]
-        async fn y_yanks_url() {
-            let (app, _rx) = test_app();
-            let action = app.handle_event(key(KeyCode::Char('y')));
-            assert!(matches!(action, Action::YankUrl));
+        async fn issue_closed_sets_flash() {
+            let (mut app, _rx) = test_app();
+            app.update(Action::IssueClosed);
+            assert!(app.flash_message.is_some());
+            assert_eq!(app.flash_message.as_ref().unwrap().0, "Issue closed.");
         }
 
         #[tokio::test]
-        async fn m_on_pr_detail_shows_merge() {
+        async fn comment_posted_sets_flash() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::PrDetail;
-            let action = app.handle_event(key(KeyCode::Char('m')));
-            assert!(matches!(action, Action::ShowMergeMethodSelect));
+            app.update(Action::CommentPosted);
+            assert!(app.flash_message.is_some());
+            assert_eq!(app.flash_message.as_ref().unwrap().0, "Comment posted.");
         }
 
+        // Live polling of workflow runs
+
         #[tokio::test]
-        async fn m_on_repo_list_is_none() {
-            let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoList;
-            let action = app.handle_event(key(KeyCode::Char('m')));
-            assert!(matches!(action, Action::None));
+        async fn tick_maps_to_tick_action() {
+            let (app, _rx) = test_app();
+            assert!(matches!(app.handle_event(Event::Tick), Action::Tick));
         }
 
         #[tokio::test]
-        async fn big_r_on_pr_detail_shows_review() {
+        async fn tick_polls_actions_while_run_in_progress() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::PrDetail;
-            let action = app.handle_event(key(KeyCode::Char('R')));
-            assert!(matches!(action, Action::ShowReviewSelect));
+            app.screen = Screen::RepoView;
+            app.repo_tab = RepoTab::Actions;
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            let mut run = make_action_run(1, "CI");
+            run.status = ActionStatus::InProgress;
+            run.conclusion = None;
+            app.action_runs = vec![run];
+            app.update(Action::Tick);
+            assert!(app.actions_last_poll.is_some());
         }
 
         #[tokio::test]
-        async fn p_on_repo_view_switches_tab() {
+        async fn tick_skips_poll_when_all_runs_completed() {
             let (mut app, _rx) = test_app();
             app.screen = Screen::RepoView;
-            let action = app.handle_event(key(KeyCode::Char('p')));
-            assert!(matches!(
-                action,
-                Action::SwitchRepoTab(RepoTab::PullRequests)
-            ));
+            app.repo_tab = RepoTab::Actions;
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            app.action_runs = vec![make_action_run(1, "CI")];
+            app.update(Action::Tick);
+            assert!(app.actions_last_poll.is_none());
         }
 
         #[tokio::test]
-        async fn i_on_repo_view_switches_tab() {
+        async fn tick_does_not_clear_error() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoView;
-            let action = app.handle_event(key(KeyCode::Char('i')));
-            assert!(matches!(action, Action::SwitchRepoTab(RepoTab::Issues)));
+            app.error = Some("boom".to_string());
+            app.update(Action::Tick);
+            assert_eq!(app.error, Some("boom".to_string()));
         }
 
         #[tokio::test]
-        async fn c_on_repo_view_switches_tab() {
+        async fn polled_runs_update_in_place_and_prepend_new() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoView;
-            let action = app.handle_event(key(KeyCode::Char('c')));
-            assert!(matches!(action, Action::SwitchRepoTab(RepoTab::Commits)));
+            app.load_id = 1;
+            let mut running = make_action_run(1, "CI");
+            running.status = ActionStatus::InProgress;
+            running.conclusion = None;
+            app.action_runs = vec![running, make_action_run(2, "Lint")];
+            app.action_index = 1;
+            app.update(Action::ActionRunsPolled(
+                vec![make_action_run(3, "Deploy"), make_action_run(1, "CI")],
+                1,
+            ));
+            let ids: Vec<u64> = app.action_runs.iter().map(|r| r.id).collect();
+            assert_eq!(ids, vec![3, 1, 2]);
+            assert_eq!(app.action_runs[1].status, ActionStatus::Completed);
+            // Selection stays on the same run
+            assert_eq!(app.action_runs[app.action_index].id, 2);
         }
 
         #[tokio::test]
-        async fn a_on_repo_view_switches_tab() {
+        async fn polled_runs_stale_id_ignored() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoView;
-            let action = app.handle_event(key(KeyCode::Char('a')));
-            assert!(matches!(action, Action::SwitchRepoTab(RepoTab::Actions)));
+            app.load_id = 2;
+            app.action_runs = vec![make_action_run(1, "CI")];
+            app.update(Action::ActionRunsPolled(vec![make_action_run(9, "New")], 1));
+            assert_eq!(app.action_runs.len(), 1);
         }
 
-        // Search mode
-
         #[tokio::test]
-        async fn search_esc_exits() {
+        async fn pr_comments_appended_after_description() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::Search;
-            let action = app.handle_event(key(KeyCode::Esc));
-            assert!(matches!(action, Action::ExitSearchMode));
+            app.current_pr = Some(make_pull_request(1, "line one\nline two"));
+            app.update(Action::PrCommentsLoaded(
+                vec![make_comment(1, "alice", "looks good")],
+                0,
+            ));
+            let lines = app.pr_detail_lines();
+            assert_eq!(lines[0], (PrLine::Body, "line one".to_string()));
+            assert!(lines
+                .iter()
+                .any(|(kind, text)| *kind == PrLine::Section && text.contains("1 comments")));
+            assert!(lines.contains(&(PrLine::Comment, "looks good".to_string())));
         }
 
         #[tokio::test]
-        async fn search_enter_confirms() {
+        async fn pr_comments_stale_id_ignored() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::Search;
-            let action = app.handle_event(key(KeyCode::Enter));
-            assert!(matches!(action, Action::SearchConfirm));
+            app.load_id = 2;
+            app.update(Action::PrCommentsLoaded(
+                vec![make_comment(1, "alice", "hi")],
+                1,
+            ));
+            assert!(app.pr_comments.is_empty());
         }
 
         #[tokio::test]
-        async fn search_backspace() {
+        async fn reaction_targets_comment_at_top_of_view() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::Search;
-            let action = app.handle_event(key(KeyCode::Backspace));
-            assert!(matches!(action, Action::SearchBackspace));
+            app.screen = Screen::PrDetail;
+            app.current_pr = Some(make_pull_request(4, "body"));
+            app.pr_comments = vec![make_comment(11, "alice", "first\nsecond")];
+            assert_eq!(app.reaction_target(), Some(ReactionTarget::Pr(4)));
+
+            let lines = app.pr_detail_lines();
+            let second = lines.iter().position(|(_, text)| text == "second").unwrap();
+            app.scroll_offset = second;
+            assert_eq!(
+                app.reaction_target(),
+                Some(ReactionTarget::Comment { pr: 4, id: 11 })
+            );
+
+            app.update(Action::ShowReactionSelect);
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+            assert_eq!(app.popup_title, "React to comment");
+            assert_eq!(app.popup_items.len(), Reaction::ALL.len());
         }
 
         #[tokio::test]
-        async fn search_char_input() {
+        async fn toggle_bot_comments_hides_bots() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::Search;
-            let action = app.handle_event(key(KeyCode::Char('f')));
-            assert!(matches!(action, Action::SearchInput('f')));
+            app.ui_config.bots = vec!["renovate".to_string()];
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.pr_comments = vec![
+                make_comment(1, "dependabot[bot]", "bump serde"),
+                make_comment(2, "renovate", "update tokio"),
+                make_comment(3, "alice", "ship it"),
+            ];
+            let shown = app.pr_detail_lines();
+            assert!(shown
+                .iter()
+                .any(|(kind, _)| matches!(kind, PrLine::CommentHeader { bot: true, .. })));
+
+            app.update(Action::ToggleBotComments);
+            let lines = app.pr_detail_lines();
+            assert!(lines
+                .iter()
+                .all(|(kind, _)| !matches!(kind, PrLine::CommentHeader { bot: true, .. })));
+            assert!(lines.contains(&(PrLine::Comment, "ship it".to_string())));
+            assert!(lines.iter().any(|(_, text)| text.contains("2 bot hidden")));
         }
 
         #[tokio::test]
-        async fn search_other_key_none() {
+        async fn search_finds_matches_in_comments() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::Search;
-            let action = app.handle_event(key(KeyCode::Tab));
-            assert!(matches!(action, Action::None));
+            app.screen = Screen::PrDetail;
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.pr_comments = vec![make_comment(1, "alice", "needle here")];
+            app.search.query = "needle".to_string();
+            app.search.active = true;
+            app.recompute_search_matches();
+            assert_eq!(app.search.content_matches.len(), 1);
+            let (line_idx, _, _) = app.search.content_matches[0];
+            assert_eq!(app.pr_detail_lines()[line_idx].1, "needle here");
         }
 
-        // Confirm mode
-
         #[tokio::test]
-        async fn confirm_y_yes() {
+        async fn pr_search_runs_through_conversation_into_diffs() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::Confirm;
-            let action = app.handle_event(key(KeyCode::Char('y')));
-            assert!(matches!(action, Action::ConfirmYes));
+            app.screen = Screen::PrDetail;
+            app.current_pr = Some(make_pull_request(1, "a needle"));
+            app.pr_comments = vec![make_comment(1, "alice", "another needle")];
+            let mut file = make_file("src/lib.rs", "modified");
+            file.patch = Some("@@ -1 +1 @@\n-hay\n+needle".to_string());
+            app.pr_files = vec![make_file("README.md", "modified"), file];
+            app.search.query = "needle".to_string();
+            app.search.active = true;
+            app.recompute_search_matches();
+            assert_eq!(app.search.content_matches.len(), 3);
+            assert_eq!(app.search_section().as_deref(), Some("Description"));
+
+            app.update(Action::SearchNext);
+            assert_eq!(app.search_section().as_deref(), Some("Conversation"));
+
+            app.update(Action::SearchNext);
+            assert_eq!(app.screen, Screen::DiffView);
+            assert_eq!((app.file_index, app.diff_cursor), (1, 2));
+            assert_eq!(app.search_section().as_deref(), Some("diff src/lib.rs"));
+
+            app.update(Action::SearchNext);
+            assert_eq!(app.screen, Screen::PrDetail);
+            assert_eq!(app.search.current_match, 0);
         }
 
         #[tokio::test]
-        async fn confirm_n_no() {
+        async fn comments_wrap_to_the_pane_and_search_follows_resizes() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::Confirm;
-            let action = app.handle_event(key(KeyCode::Char('n')));
-            assert!(matches!(action, Action::ConfirmNo));
+            app.screen = Screen::PrDetail;
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.pr_comments = vec![make_comment(1, "alice", "a haystack with a needle")];
+            app.detail_width.set(12);
+            let lines = app.pr_detail_lines();
+            assert!(lines.contains(&(PrLine::Comment, "a haystack".to_string())));
+            assert!(lines.contains(&(PrLine::Comment, "with a".to_string())));
+
+            app.search.query = "needle".to_string();
+            app.search.active = true;
+            app.recompute_search_matches();
+            let (line_idx, start, end) = app.search.content_matches[0];
+            assert_eq!(&app.pr_detail_lines()[line_idx].1[start..end], "needle");
+
+            app.detail_width.set(80);
+            app.update(Action::Tick);
+            let (line_idx, start, _) = app.search.content_matches[0];
+            assert_eq!(
+                app.pr_detail_lines()[line_idx].1,
+                "a haystack with a needle"
+            );
+            assert_eq!(start, 18);
         }
 
         #[tokio::test]
-        async fn confirm_esc_no() {
+        async fn commit_checks_follow_their_sha_and_shift_search() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::Confirm;
-            let action = app.handle_event(key(KeyCode::Esc));
-            assert!(matches!(action, Action::ConfirmNo));
-        }
+            app.screen = Screen::CommitDetail;
+            let mut file = make_file("src/lib.rs", "modified");
+            file.patch = Some("+needle".to_string());
+            app.current_commit = Some(make_commit_detail("abc123", "msg", vec![file]));
+            let check = CommitCheck {
+                name: "build".to_string(),
+                state: ChecksStatus::Success,
+                duration: Some(72),
+            };
+            app.update(Action::CommitChecksLoaded(
+                "other".to_string(),
+                vec![check.clone()],
+                app.load_id,
+            ));
+            assert!(app.current_commit_checks().is_empty());
 
-        // SelectPopup mode
+            app.search.query = "needle".to_string();
+            app.search.active = true;
+            app.recompute_search_matches();
+            let before = app.search.content_matches[0].0;
+
+            app.update(Action::CommitChecksLoaded(
+                "abc123".to_string(),
+                vec![check],
+                app.load_id,
+            ));
+            assert_eq!(app.current_commit_checks().len(), 1);
+            app.recompute_search_matches();
+            assert_eq!(app.search.content_matches[0].0, before + 3);
+        }
 
         #[tokio::test]
-        async fn popup_j_down() {
+        async fn pr_reviews_loaded_and_stale_ignored() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::SelectPopup;
-            let action = app.handle_event(key(KeyCode::Char('j')));
-            assert!(matches!(action, Action::PopupDown));
+            app.load_id = 2;
+            let reviews = PrReviews {
+                requested: vec!["bob".to_string()],
+                reviews: vec![],
+                required_approvals: None,
+            };
+            app.update(Action::PrReviewsLoaded(reviews.clone(), 1));
+            assert!(app.pr_reviews.requested.is_empty());
+            app.update(Action::PrReviewsLoaded(reviews, 2));
+            assert_eq!(app.pr_reviews.requested, vec!["bob".to_string()]);
         }
 
         #[tokio::test]
-        async fn popup_k_up() {
+        async fn review_progress_loaded_and_stale_ignored() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::SelectPopup;
-            let action = app.handle_event(key(KeyCode::Char('k')));
-            assert!(matches!(action, Action::PopupUp));
+            app.load_id = 2;
+            let checks = PrChecks {
+                passed: 3,
+                failed: 0,
+                pending: 1,
+                neutral: 0,
+            };
+            app.update(Action::PrChecksLoaded(checks, 1));
+            app.update(Action::ViewedFilesLoaded(vec!["a.rs".to_string()], 1));
+            assert_eq!(app.pr_checks, PrChecks::default());
+            assert!(app.viewed_files.is_none());
+            app.update(Action::PrChecksLoaded(checks, 2));
+            app.update(Action::ViewedFilesLoaded(vec!["a.rs".to_string()], 2));
+            assert_eq!(app.pr_checks, checks);
+            assert_eq!(app.viewed_files, Some(vec!["a.rs".to_string()]));
         }
 
         #[tokio::test]
-        async fn popup_enter_select() {
+        async fn hide_resolved_threads() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::SelectPopup;
-            let action = app.handle_event(key(KeyCode::Enter));
-            assert!(matches!(action, Action::PopupSelect));
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.review_threads = vec![make_thread("a", true), make_thread("b", false)];
+            let lines = app.pr_detail_lines();
+            assert!(lines.contains(&(PrLine::Comment, "  thread a".to_string())));
+
+            app.update(Action::ToggleResolvedThreads);
+            let lines = app.pr_detail_lines();
+            assert!(!lines.contains(&(PrLine::Comment, "  thread a".to_string())));
+            assert!(lines.contains(&(PrLine::Comment, "  thread b".to_string())));
+            assert!(lines
+                .iter()
+                .any(|(_, text)| text.contains("1 resolved hidden")));
         }
 
         #[tokio::test]
-        async fn popup_esc_cancels() {
+        async fn next_thread_skips_hidden_and_scrolls() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::SelectPopup;
-            let action = app.handle_event(key(KeyCode::Esc));
-            assert!(matches!(action, Action::ConfirmNo));
+            app.screen = Screen::PrDetail;
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.review_threads = vec![
+                make_thread("a", false),
+                make_thread("b", true),
+                make_thread("c", false),
+            ];
+            app.hide_resolved_threads = true;
+            app.update(Action::NextThread);
+            assert_eq!(app.thread_index, 2);
+            let lines = app.pr_detail_lines();
+            assert!(matches!(
+                lines[app.scroll_offset].0,
+                PrLine::ThreadHeader { index: 2, .. }
+            ));
+            app.update(Action::PrevThread);
+            assert_eq!(app.thread_index, 0);
         }
-    }
 
-    // ── State transition tests ──
-
-    mod state_transitions {
-        use super::*;
-
-        // Navigation
+        #[tokio::test]
+        async fn pr_files_listed_and_stale_ignored() {
+            let (mut app, _rx) = test_app();
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.load_id = 2;
+            let files = vec![
+                make_file("src/a.rs", "added"),
+                make_file("b.md", "modified"),
+            ];
+            app.update(Action::PrFilesLoaded(files.clone(), 1));
+            assert!(app.pr_files.is_empty());
+            app.update(Action::PrFilesLoaded(files, 2));
+            let lines = app.pr_detail_lines();
+            assert!(lines.contains(&(PrLine::Section, "── Files changed (2)".to_string())));
+            assert!(lines.contains(&(PrLine::File { index: 0 }, "A src/a.rs  +1 -0".to_string())));
+            assert!(lines.contains(&(PrLine::File { index: 1 }, "M b.md  +1 -0".to_string())));
+        }
 
         #[tokio::test]
-        async fn quit_sets_should_quit() {
+        async fn pr_commits_listed_and_stepped() {
             let (mut app, _rx) = test_app();
-            app.update(Action::Quit);
-            assert!(app.should_quit);
+            app.screen = Screen::PrDetail;
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.load_id = 2;
+            let commits = vec![
+                make_commit("abc1234def", "First"),
+                make_commit("def5678abc", "Second"),
+            ];
+            app.update(Action::PrCommitsLoaded(commits.clone(), 1));
+            assert!(app.pr_commits.is_empty());
+            app.update(Action::PrCommitsLoaded(commits, 2));
+            let lines = app.pr_detail_lines();
+            assert!(lines.contains(&(PrLine::Section, "── Commits (2)".to_string())));
+            assert!(lines.contains(&(
+                PrLine::Commit { index: 1 },
+                "def5678 Second  @testauthor".to_string()
+            )));
+            app.update(Action::NextPrCommit);
+            assert_eq!(app.pr_commit_index, 1);
+            assert_eq!(
+                app.pr_detail_lines()[app.scroll_offset].0,
+                PrLine::Commit { index: 1 }
+            );
+            app.update(Action::NextPrCommit);
+            assert_eq!(app.pr_commit_index, 1);
         }
 
         #[tokio::test]
-        async fn back_from_home_quits() {
+        async fn commit_opened_from_pr_returns_to_pr() {
             let (mut app, _rx) = test_app();
+            app.screen = Screen::PrDetail;
+            app.nav_stack = vec![nav_entry(Screen::RepoView)];
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.pr_commits = vec![make_commit("abc1234", "First")];
+            app.pr_files = vec![make_file("a.rs", "modified")];
+            app.update(Action::NextPrCommit);
+            app.update(Action::Select);
+            let load_id = app.load_id;
+            app.update(Action::CommitDetailLoaded(
+                Box::new(make_commit_detail("abc1234", "First", vec![])),
+                load_id,
+            ));
+            assert_eq!(app.screen, Screen::CommitDetail);
+
             app.update(Action::Back);
-            assert!(app.should_quit);
+            assert_eq!(app.screen, Screen::PrDetail);
+            assert!(app.current_pr.is_some());
+            assert_eq!(app.pr_focus, PrFocus::Commits);
+            app.update(Action::Back);
+            assert_eq!(app.screen, Screen::RepoView);
         }
 
         #[tokio::test]
-        async fn back_from_repo_list_to_home() {
+        async fn home_pr_commit_unwinds_and_forward_reopens() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoList;
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            app.update(Action::PrDetailLoaded(
+                Box::new(make_pull_request(1, "body")),
+                app.load_id,
+            ));
+            assert_eq!(app.screen, Screen::PrDetail);
+            app.scroll_offset = 4;
+            app.update(Action::CommitDetailLoaded(
+                Box::new(make_commit_detail("abc1234", "First", vec![])),
+                app.load_id,
+            ));
+            assert_eq!(app.screen, Screen::CommitDetail);
+
+            app.update(Action::Back);
+            assert_eq!(app.screen, Screen::PrDetail);
+            assert_eq!(app.scroll_offset, 4);
             app.update(Action::Back);
             assert_eq!(app.screen, Screen::Home);
+            assert!(app.nav_stack.is_empty());
+            assert_eq!(app.nav_forward.len(), 2);
+
+            app.update(Action::Forward);
+            assert!(app.loading);
+            app.update(Action::PrDetailLoaded(
+                Box::new(make_pull_request(1, "body")),
+                app.load_id,
+            ));
+            assert_eq!(app.screen, Screen::PrDetail);
+            assert_eq!(app.nav_forward.len(), 1);
+            assert_eq!(app.nav_forward[0].commit.as_deref(), Some("abc1234"));
+
+            // Opening something else drops what Forward had left
+            app.update(Action::CommitDetailLoaded(
+                Box::new(make_commit_detail("def5678", "Second", vec![])),
+                app.load_id,
+            ));
+            assert!(app.nav_forward.is_empty());
         }
 
         #[tokio::test]
-        async fn back_from_repo_view_to_repo_list() {
+        async fn pr_reference_opens_and_back_returns() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoView;
-            app.repo_tab = RepoTab::Issues;
-            app.prs = vec![make_pr_summary(1, "test")];
-            app.issues = vec![make_issue(1, "test")];
-            app.commits = vec![make_commit("abc123", "test")];
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            app.update(Action::PrDetailLoaded(
+                Box::new(make_pull_request(1, "Follows #2, cc @jane")),
+                app.load_id,
+            ));
+            app.update(Action::PrevXref);
+            assert!(matches!(
+                app.selected_xref(),
+                Some((0, _, CrossRef::User(user))) if user == "jane"
+            ));
+            app.update(Action::NextXref);
+            assert!(matches!(
+                app.selected_xref(),
+                Some((0, _, CrossRef::Number(2)))
+            ));
+
+            app.update(Action::Select);
+            assert!(app.loading);
+            app.update(Action::JumpedToPr(
+                Box::new(make_pull_request(2, "body")),
+                app.load_id,
+            ));
+            assert_eq!(app.current_pr.as_ref().map(|pr| pr.number), Some(2));
+            assert_eq!(app.xref_index, None);
+            assert_eq!(app.nav_stack.len(), 2);
+
+            // Back reloads the PR the reference was in
             app.update(Action::Back);
-            assert_eq!(app.screen, Screen::RepoList);
-            assert_eq!(app.repo_tab, RepoTab::PullRequests);
-            assert!(app.prs.is_empty());
-            assert!(app.issues.is_empty());
-            assert!(app.commits.is_empty());
+            assert_eq!(app.screen, Screen::PrDetail);
+            assert!(app.current_pr.is_none() && app.loading);
+            app.update(Action::PrDetailLoaded(
+                Box::new(make_pull_request(1, "Follows #2, cc @jane")),
+                app.load_id,
+            ));
+            assert_eq!(app.current_pr.as_ref().map(|pr| pr.number), Some(1));
+            assert_eq!(app.nav_stack.len(), 1);
+            assert_eq!(app.nav_forward.len(), 1);
+            app.update(Action::Back);
+            assert_eq!(app.screen, Screen::Home);
         }
 
         #[tokio::test]
-        async fn back_from_pr_detail() {
-            let (mut app, _rx) = test_app();
+        async fn enter_on_file_opens_its_diff_view() {
+            let (mut app, mut rx) = test_app();
             app.screen = Screen::PrDetail;
-            app.prev_screen = Some(Screen::RepoView);
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
             app.current_pr = Some(make_pull_request(1, "body"));
-            app.scroll_offset = 5;
+            let mut patched = make_file("b.rs", "modified");
+            patched.patch = Some("@@ -1 +1 @@\n+b".to_string());
+            app.pr_files = vec![make_file("a.png", "added"), patched];
+            app.pr_commits = vec![make_commit("abc1234", "First")];
+
+            app.update(Action::Select);
+            assert!(rx.try_recv().is_err());
+            assert!(app.flash_message.is_some());
+
+            app.update(Action::NextFile);
+            app.update(Action::Select);
+            assert_eq!(app.screen, Screen::DiffView);
+
+            app.update(Action::ViewDiff);
+            match rx.try_recv() {
+                Ok(Action::SuspendForPager(diff)) => {
+                    assert_eq!(diff, "diff --git a/b.rs b/b.rs\n@@ -1 +1 @@\n+b\n")
+                }
+                other => panic!("expected pager, got {:?}", other),
+            }
+
             app.update(Action::Back);
-            assert_eq!(app.screen, Screen::RepoView);
-            assert!(app.current_pr.is_none());
-            assert_eq!(app.scroll_offset, 0);
+            assert_eq!(app.screen, Screen::PrDetail);
+            assert_eq!(app.file_index, 1);
+        }
+
+        fn diff_view_app() -> (App, mpsc::UnboundedReceiver<Action>) {
+            let (mut app, rx) = test_app();
+            app.screen = Screen::DiffView;
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            app.current_pr = Some(make_pull_request(7, "body"));
+            let mut file = make_file("src/lib.rs", "modified");
+            file.patch = Some("@@ -1,2 +1,2 @@\n ctx\n-old\n+new".to_string());
+            app.pr_files = vec![file];
+            (app, rx)
         }
 
         #[tokio::test]
-        async fn back_from_commit_detail() {
-            let (mut app, _rx) = test_app();
-            app.screen = Screen::CommitDetail;
-            app.prev_screen = Some(Screen::RepoView);
-            app.current_commit = Some(make_commit_detail("abc123", "msg", vec![]));
-            app.update(Action::Back);
-            assert_eq!(app.screen, Screen::RepoView);
-            assert!(app.current_commit.is_none());
-            assert_eq!(app.scroll_offset, 0);
+        async fn c_in_diff_view_comments_on_the_cursor_line() {
+            let (mut app, _rx) = diff_view_app();
+            // Hunk headers can't carry a comment
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('c'))),
+                Action::None
+            ));
+
+            app.update(Action::ScrollDown);
+            app.update(Action::ScrollDown);
+            match app.handle_event(key(KeyCode::Char('c'))) {
+                Action::SuspendForEditor(EditorContext::LineComment { path, line }) => {
+                    assert_eq!(path, "src/lib.rs");
+                    assert_eq!(
+                        line,
+                        DiffLine {
+                            old: Some(2),
+                            new: None
+                        }
+                    );
+                }
+                other => panic!("expected line comment, got {:?}", other),
+            }
+
+            app.update(Action::GoToBottom);
+            assert_eq!(app.diff_cursor, 3);
         }
 
         #[tokio::test]
-        async fn next_tab_on_home_toggles_section() {
-            let (mut app, _rx) = test_app();
-            assert_eq!(app.home_section, HomeSection::ReviewRequests);
-            app.update(Action::NextTab);
-            assert_eq!(app.home_section, HomeSection::MyPrs);
-            app.update(Action::NextTab);
-            assert_eq!(app.home_section, HomeSection::ReviewRequests);
+        async fn diff_layout_is_reused_until_the_patch_changes() {
+            let (mut app, _rx) = diff_view_app();
+            let first = app.diff_layout().unwrap();
+            assert_eq!(first.numbers.len(), 4);
+            assert!(Rc::ptr_eq(&first, &app.diff_layout().unwrap()));
+
+            app.pr_files[0].patch = Some("@@ -1 +1 @@\n-a\n+b".to_string());
+            let changed = app.diff_layout().unwrap();
+            assert!(!Rc::ptr_eq(&first, &changed));
+            assert_eq!(changed.rows.len(), 2);
         }
 
         #[tokio::test]
-        async fn next_tab_on_repo_view_cycles() {
-            let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoView;
-            assert_eq!(app.repo_tab, RepoTab::PullRequests);
-            app.update(Action::NextTab);
-            assert_eq!(app.repo_tab, RepoTab::Issues);
-            app.update(Action::NextTab);
-            assert_eq!(app.repo_tab, RepoTab::Commits);
-            app.update(Action::NextTab);
-            assert_eq!(app.repo_tab, RepoTab::Actions);
-            app.update(Action::NextTab);
-            assert_eq!(app.repo_tab, RepoTab::PullRequests);
+        async fn v_splits_the_diff_and_steps_by_rows() {
+            let (mut app, _rx) = diff_view_app();
+            let action = app.handle_event(key(KeyCode::Char('v')));
+            assert!(matches!(action, Action::ToggleSplitDiff));
+            app.update(action);
+            assert!(app.diff_split);
+
+            // The renderer reports whether the terminal was wide enough
+            app.diff_split_shown.set(true);
+            app.update(Action::ScrollDown);
+            app.update(Action::ScrollDown);
+            // `-old` and `+new` share a row, which lands on the addition
+            assert_eq!(app.diff_cursor, 3);
+            app.update(Action::ScrollUp);
+            assert_eq!(app.diff_cursor, 1);
+
+            app.update(Action::ToggleSplitDiff);
+            assert!(!app.diff_split);
         }
 
         #[tokio::test]
-        async fn prev_tab_on_repo_view_cycles_backward() {
+        async fn line_comments_batch_until_the_review_is_submitted() {
+            let (mut app, _rx) = diff_view_app();
+            for new in [1, 2] {
+                app.update(Action::AddReviewComment(ReviewComment {
+                    path: "src/lib.rs".to_string(),
+                    line: DiffLine {
+                        old: None,
+                        new: Some(new),
+                    },
+                    body: "nit".to_string(),
+                }));
+            }
+            assert_eq!(app.pending_review_comments().len(), 2);
+            assert_eq!(app.quit_summary(), "drafts: 2, watched runs: 0");
+            assert_eq!(
+                app.pending_work()[0].to_string(),
+                "2 review comments on owner/repo#7"
+            );
+
+            app.update(Action::ReviewSubmitted);
+            assert!(app.pending_review_comments().is_empty());
+            assert!(app.pending_work().is_empty());
+        }
+
+        #[tokio::test]
+        async fn next_file_scrolls_and_clamps() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoView;
-            assert_eq!(app.repo_tab, RepoTab::PullRequests);
-            app.update(Action::PrevTab);
-            assert_eq!(app.repo_tab, RepoTab::Actions);
-            app.update(Action::PrevTab);
-            assert_eq!(app.repo_tab, RepoTab::Commits);
-            app.update(Action::PrevTab);
-            assert_eq!(app.repo_tab, RepoTab::Issues);
-            app.update(Action::PrevTab);
-            assert_eq!(app.repo_tab, RepoTab::PullRequests);
+            app.screen = Screen::PrDetail;
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.pr_files = vec![make_file("a.rs", "modified"), make_file("b.rs", "removed")];
+            app.update(Action::NextFile);
+            assert_eq!(app.file_index, 1);
+            let lines = app.pr_detail_lines();
+            assert_eq!(lines[app.scroll_offset].0, PrLine::File { index: 1 });
+            app.update(Action::NextFile);
+            assert_eq!(app.file_index, 1);
+            app.update(Action::PrevFile);
+            assert_eq!(app.file_index, 0);
         }
 
         #[tokio::test]
-        async fn switch_repo_tab_sets_tab_and_resets_index() {
+        async fn thread_resolved_updates_in_place() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoView;
-            app.issue_index = 5;
-            app.update(Action::SwitchRepoTab(RepoTab::Issues));
-            assert_eq!(app.repo_tab, RepoTab::Issues);
-            assert_eq!(app.issue_index, 0);
+            app.review_threads = vec![make_thread("a", false)];
+            app.update(Action::ThreadResolved {
+                thread_id: "a".to_string(),
+                resolved: true,
+            });
+            assert!(app.review_threads[0].resolved);
+            assert!(app.flash_message.is_some());
         }
 
         #[tokio::test]
-        async fn refresh_on_home_goes_to_repo_list() {
+        async fn milestones_loaded_opens_popup() {
             let (mut app, _rx) = test_app();
-            app.update(Action::Refresh);
-            assert_eq!(app.screen, Screen::RepoList);
-            assert!(app.loading);
+            app.milestone_filter = Some(make_milestone(2, "v2"));
+            app.update(Action::MilestonesLoaded(
+                vec![make_milestone(1, "v1"), make_milestone(2, "v2")],
+                MilestonePurpose::Filter,
+            ));
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+            assert_eq!(app.popup_title, "Filter by Milestone");
+            assert_eq!(app.popup_items, vec!["All milestones", "v1", "v2"]);
+            // Active filter is preselected
+            assert_eq!(app.popup_index, 2);
         }
 
         #[tokio::test]
-        async fn refresh_on_repo_list_sets_loading() {
-            let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoList;
-            app.update(Action::Refresh);
-            assert_eq!(app.screen, Screen::RepoList);
-            assert!(app.loading);
+        async fn milestone_filter_selected_from_popup() {
+            let (mut app, mut rx) = test_app();
+            app.update(Action::MilestonesLoaded(
+                vec![make_milestone(1, "v1")],
+                MilestonePurpose::Filter,
+            ));
+            app.popup_index = 1;
+            app.update(Action::PopupSelect);
+            assert_eq!(
+                app.milestone_filter.as_ref().map(|m| m.title.as_str()),
+                Some("v1")
+            );
+            assert!(matches!(rx.try_recv(), Ok(Action::Refresh)));
         }
 
-        // Scroll/Index
+        #[tokio::test]
+        async fn milestone_filter_is_sent_to_the_forge() {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let forge = Arc::new(crate::testing::MockForge::default());
+            let mut app = App::new(forge, tx, vec![], UiConfig::default());
+            app.forge_name = "mock_milestone".to_string();
+            app.milestone_filter = Some(make_milestone(1, "v1.0"));
+            app.spawn_load_issues("acme".to_string(), "rocket".to_string(), app.load_id);
+            loop {
+                if let action @ Action::IssuesLoaded(..) = rx.recv().await.unwrap() {
+                    app.update(action);
+                    break;
+                }
+            }
+            assert_eq!(app.issues.len(), 1);
+            assert_eq!(app.issues[0].number, 12);
+        }
 
         #[tokio::test]
-        async fn scroll_down_increments_repo_index() {
-            let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoList;
-            app.repos = vec![make_repo("a"), make_repo("b"), make_repo("c")];
-            app.repo_index = 0;
-            app.update(Action::ScrollDown);
-            assert_eq!(app.repo_index, 1);
+        async fn repo_meta_loaded_opens_editor() {
+            let (mut app, mut rx) = test_app();
+            app.loading = true;
+            let meta = RepoMeta {
+                description: "desc".to_string(),
+                topics: vec!["rust".to_string()],
+            };
+            app.update(Action::RepoMetaLoaded(
+                "owner".to_string(),
+                "repo".to_string(),
+                meta.clone(),
+            ));
+            assert!(!app.loading);
+            match rx.try_recv() {
+                Ok(Action::SuspendForEditor(EditorContext::EditRepo {
+                    owner,
+                    repo,
+                    meta: sent,
+                })) => {
+                    assert_eq!((owner.as_str(), repo.as_str()), ("owner", "repo"));
+                    assert_eq!(sent, meta);
+                }
+                other => panic!("expected repo editor, got {:?}", other),
+            }
         }
 
         #[tokio::test]
-        async fn scroll_down_at_end_no_overflow() {
+        async fn repo_stats_open_insights_for_current_repo() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoList;
-            app.repos = vec![make_repo("a"), make_repo("b")];
-            app.repo_index = 1;
-            app.update(Action::ScrollDown);
-            assert_eq!(app.repo_index, 1);
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            let stats = RepoStats {
+                open_issues: 3,
+                merged_prs: 5,
+                ..Default::default()
+            };
+
+            app.loading = true;
+            app.update(Action::RepoStatsLoaded(
+                "other".to_string(),
+                "repo".to_string(),
+                stats.clone(),
+            ));
+            assert!(app.repo_stats.is_none());
+            assert_eq!(app.input_mode, InputMode::Normal);
+
+            app.update(Action::RepoStatsLoaded(
+                "owner".to_string(),
+                "repo".to_string(),
+                stats.clone(),
+            ));
+            assert!(!app.loading);
+            assert_eq!(app.input_mode, InputMode::Insights);
+            assert_eq!(app.repo_stats, Some(stats));
+
+            let action = app.handle_event(key(KeyCode::Esc));
+            app.update(action);
+            assert_eq!(app.input_mode, InputMode::Normal);
         }
 
         #[tokio::test]
-        async fn scroll_down_empty_list_noop() {
+        async fn starring_moves_the_count_until_it_fails() {
             let (mut app, _rx) = test_app();
             app.screen = Screen::RepoList;
-            app.update(Action::ScrollDown);
-            assert_eq!(app.repo_index, 0);
+            app.repos = vec![make_repo("grit")];
+
+            // First press looks the state up; the answer flips it
+            app.update(Action::StarStateLoaded(
+                "testowner".to_string(),
+                "grit".to_string(),
+                false,
+            ));
+            assert_eq!(app.repos[0].stars, 43);
+            assert_eq!(app.is_starred("testowner", "grit"), Some(true));
+
+            app.update(Action::StarFailed(
+                "testowner".to_string(),
+                "grit".to_string(),
+                GritError::Api("nope".to_string()),
+            ));
+            assert_eq!(app.repos[0].stars, 42);
+            assert_eq!(app.is_starred("testowner", "grit"), Some(false));
+            assert!(app.error.is_some());
+
+            // Known now, so the next press changes it without asking
+            let action = app.handle_event(key(KeyCode::Char('*')));
+            app.update(action);
+            assert_eq!(app.repos[0].stars, 43);
         }
 
         #[tokio::test]
-        async fn scroll_up_decrements_repo_index() {
+        async fn sync_fork_confirms_only_when_behind() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoList;
-            app.repos = vec![make_repo("a"), make_repo("b")];
-            app.repo_index = 1;
-            app.update(Action::ScrollUp);
-            assert_eq!(app.repo_index, 0);
+            app.screen = Screen::RepoView;
+            app.current_repo = Some(("me".to_string(), "grit".to_string()));
+            let status = ForkStatus {
+                upstream: "pders01/grit".to_string(),
+                branch: "main".to_string(),
+                upstream_branch: "main".to_string(),
+                ahead: 1,
+                behind: 0,
+            };
+
+            // Status for a repo no longer on screen is dropped
+            app.update(Action::ForkStatusLoaded(
+                "other".to_string(),
+                "grit".to_string(),
+                Some(status.clone()),
+            ));
+            assert!(app.fork_status.is_none());
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('U'))),
+                Action::None
+            ));
+
+            app.update(Action::ForkStatusLoaded(
+                "me".to_string(),
+                "grit".to_string(),
+                Some(status.clone()),
+            ));
+            app.update(Action::SyncFork);
+            assert!(app.confirm_action.is_none());
+            assert!(app.flash_message.is_some());
+
+            app.fork_status = Some(ForkStatus {
+                behind: 3,
+                ..status
+            });
+            let action = app.handle_event(key(KeyCode::Char('U')));
+            app.update(action);
+            assert!(matches!(
+                app.confirm_action,
+                Some(ConfirmAction::SyncFork { behind: 3, .. })
+            ));
         }
 
         #[tokio::test]
-        async fn scroll_up_at_zero_stays() {
-            let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoList;
-            app.repos = vec![make_repo("a")];
-            app.repo_index = 0;
-            app.update(Action::ScrollUp);
-            assert_eq!(app.repo_index, 0);
+        async fn snippet_picker_prefills_comment() {
+            let (mut app, mut rx) = test_app();
+            app.screen = Screen::PrDetail;
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            app.current_pr = Some(make_pull_request(7, "body"));
+            assert!(matches!(
+                app.handle_event(key(KeyCode::Char('C'))),
+                Action::SuspendForEditor(EditorContext::CommentOnPr { number: 7, .. })
+            ));
+
+            app.snippets = vec![("LGTM".to_string(), "Looks good!".to_string())];
+            let action = app.handle_event(key(KeyCode::Char('C')));
+            app.update(action);
+            assert_eq!(app.popup_items, vec!["Blank", "LGTM"]);
+
+            app.update(Action::PopupDown);
+            app.update(Action::PopupSelect);
+            assert_eq!(app.editor_prefill.as_deref(), Some("Looks good!"));
+            assert!(matches!(
+                rx.try_recv(),
+                Ok(Action::SuspendForEditor(EditorContext::CommentOnPr {
+                    number: 7,
+                    ..
+                }))
+            ));
         }
 
         #[tokio::test]
-        async fn go_to_top_resets_index() {
-            let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoList;
-            app.repos = vec![make_repo("a"), make_repo("b"), make_repo("c")];
-            app.repo_index = 2;
-            app.update(Action::GoToTop);
-            assert_eq!(app.repo_index, 0);
+        async fn tasks_are_listed_until_done_or_cancelled() {
+            let (mut app, mut rx) = test_app();
+            app.spawn_task("Quick", async {});
+            app.spawn_task("Slow", std::future::pending::<()>());
+            assert_eq!(app.tasks.borrow().len(), 2);
+
+            tokio::task::yield_now().await;
+            let finished = rx.recv().await.unwrap();
+            assert!(matches!(finished, Action::TaskFinished(0)));
+            app.update(finished);
+
+            app.loading = true;
+            let action = app.handle_event(Event::Key(KeyEvent::new(
+                KeyCode::Char('k'),
+                KeyModifiers::CONTROL,
+            )));
+            app.update(action);
+            assert_eq!(app.popup_title, "Cancel Task");
+            assert!(app.popup_items[0].starts_with("Slow"));
+
+            app.update(Action::PopupSelect);
+            assert!(app.tasks.borrow().is_empty());
+            assert!(!app.loading);
         }
 
         #[tokio::test]
-        async fn go_to_bottom_sets_last_index() {
+        async fn superseded_loads_are_aborted() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoList;
-            app.repos = vec![make_repo("a"), make_repo("b"), make_repo("c")];
-            app.update(Action::GoToBottom);
-            assert_eq!(app.repo_index, 2);
+            app.spawn_load("Load issues", app.load_id, std::future::pending::<()>());
+            app.spawn_task("Star repo", std::future::pending::<()>());
+
+            // A new load for the same load_id leaves the first alone
+            app.spawn_load("Load PR", app.load_id, std::future::pending::<()>());
+            assert_eq!(app.tasks.borrow().len(), 3);
+
+            app.load_id += 1;
+            app.spawn_load("Load commits", app.load_id, std::future::pending::<()>());
+            let labels: Vec<String> = app
+                .tasks
+                .borrow()
+                .values()
+                .map(|t| t.label.clone())
+                .collect();
+            assert_eq!(labels, vec!["Star repo", "Load commits"]);
         }
 
         #[tokio::test]
-        async fn go_to_bottom_empty_list_noop() {
+        async fn watch_popup_lists_subscriptions() {
             let (mut app, _rx) = test_app();
             app.screen = Screen::RepoList;
-            app.update(Action::GoToBottom);
-            assert_eq!(app.repo_index, 0);
+            app.repos = vec![make_repo("grit")];
+            let action = app.handle_event(key(KeyCode::Char('W')));
+            app.update(action);
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+            assert_eq!(app.popup_title, "Watch");
+            assert_eq!(app.popup_items.len(), Subscription::ALL.len());
         }
 
         #[tokio::test]
-        async fn page_down_advances_by_10_clamped() {
+        async fn refresh_with_new_content_offers_changes() {
+            let (mut app, mut rx) = test_app();
+            app.load_id = 1;
+            // Cached copy from the last visit, then the fresh one
+            app.update(Action::PrDetailLoaded(
+                Box::new(make_pull_request(9, "old text")),
+                1,
+            ));
+            app.update(Action::PrCommentsLoaded(
+                vec![make_comment(1, "alice", "first")],
+                1,
+            ));
+            assert!(!app.pr_changed());
+            assert!(app.flash_message.is_none());
+
+            app.update(Action::PrDetailLoaded(
+                Box::new(make_pull_request(9, "new text")),
+                1,
+            ));
+            app.update(Action::PrCommentsLoaded(
+                vec![
+                    make_comment(1, "alice", "first"),
+                    make_comment(2, "bob", "reply"),
+                ],
+                1,
+            ));
+            assert!(app.pr_changed());
+            assert!(app.flash_message.is_some());
+
+            app.update(Action::ShowPrChanges);
+            match rx.try_recv() {
+                Ok(Action::SuspendForPager(text)) => {
+                    assert!(text.contains("-old text\n+new text"));
+                    assert!(text.contains("@@ new comment by bob @@\n+reply"));
+                    assert!(!text.contains("alice"));
+                }
+                other => panic!("expected pager, got {:?}", other),
+            }
+        }
+
+        #[tokio::test]
+        async fn show_changes_without_changes_flashes() {
+            let (mut app, mut rx) = test_app();
+            app.update(Action::PrDetailLoaded(
+                Box::new(make_pull_request(9, "text")),
+                0,
+            ));
+            app.update(Action::ShowPrChanges);
+            assert!(rx.try_recv().is_err());
+            assert!(app.flash_message.is_some());
+        }
+
+        #[tokio::test]
+        async fn reviewer_load_lists_least_loaded_first_without_author() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoList;
-            // Create 5 repos — page down should clamp to index 4
-            app.repos = (0..5).map(|i| make_repo(&format!("r{}", i))).collect();
-            app.repo_index = 0;
-            app.update(Action::PageDown);
-            assert_eq!(app.repo_index, 4);
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            app.current_pr = Some(make_pull_request(5, "body"));
+            app.pr_reviews.requested = vec!["carol".to_string()];
+            let load = |login: &str, open_reviews| ReviewerLoad {
+                login: login.to_string(),
+                open_reviews,
+            };
+            app.update(Action::ReviewerLoadLoaded(vec![
+                load("carol", 4),
+                load("testauthor", 0),
+                load("bob", 1),
+                load("alice", 1),
+            ]));
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+            assert_eq!(app.popup_title, "Request Review");
+            let order: Vec<&str> = app.reviewer_load.iter().map(|r| r.login.as_str()).collect();
+            assert_eq!(order, vec!["alice", "bob", "carol"]);
+            assert!(app.popup_items[2].ends_with("(requested)"));
+            assert!(!app.popup_items[0].contains("requested"));
         }
 
         #[tokio::test]
-        async fn page_up_decrements_by_10_saturating() {
+        async fn revert_requires_merged_pr() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoList;
-            app.repos = (0..20).map(|i| make_repo(&format!("r{}", i))).collect();
-            app.repo_index = 5;
-            app.update(Action::PageUp);
-            assert_eq!(app.repo_index, 0);
+            app.current_pr = Some(make_pull_request(3, "body"));
+            app.pr_commits = vec![make_commit("aaaaaaaaaa", "first")];
+            app.update(Action::ShowRevertSelect);
+            assert!(app.error.is_some());
+            assert_eq!(app.input_mode, InputMode::Normal);
         }
 
-        // Data loading (load_id)
+        #[tokio::test]
+        async fn revert_confirms_only_checked_commits() {
+            let (mut app, mut rx) = test_app();
+            let mut pr = make_pull_request(3, "body");
+            pr.state = PrState::Merged;
+            app.current_pr = Some(pr);
+            app.pr_commits = vec![
+                make_commit("aaaaaaaaaa", "first\n\ndetails"),
+                make_commit("bbbbbbbbbb", "second"),
+            ];
+            app.update(Action::ShowRevertSelect);
+            assert_eq!(app.input_mode, InputMode::MultiSelectPopup);
+            assert_eq!(app.popup_items, vec!["aaaaaaa first", "bbbbbbb second"]);
+            assert_eq!(app.popup_checked, vec![true, true]);
+
+            app.update(Action::PopupToggle);
+            app.update(Action::PopupSelect);
+            match rx.try_recv() {
+                Ok(Action::ShowConfirm(ConfirmAction::RevertPr { number, commits })) => {
+                    assert_eq!(number, 3);
+                    assert_eq!(commits.len(), 1);
+                    assert_eq!(commits[0].sha, "bbbbbbbbbb");
+                }
+                other => panic!("expected revert confirm, got {:?}", other),
+            }
+        }
 
         #[tokio::test]
-        async fn home_loaded_matching_id_updates_data() {
+        async fn palette_lists_commands_for_screen() {
             let (mut app, _rx) = test_app();
-            app.load_id = 1;
-            let rrs = vec![make_review_request("o", "r", 1)];
-            let prs = vec![make_my_pr("o", "r", 2)];
-            app.update(Action::HomeLoaded {
-                review_requests: rrs.clone(),
-                my_prs: prs.clone(),
-                load_id: 1,
-            });
-            assert_eq!(app.review_requests.len(), 1);
-            assert_eq!(app.my_prs.len(), 1);
-            assert!(!app.loading);
+            app.screen = Screen::PrDetail;
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.update(Action::ShowPalette);
+            assert_eq!(app.input_mode, InputMode::Palette);
+            assert!(app.popup_items.contains(&"merge pr".to_string()));
+            assert!(app.popup_items.contains(&"close pr".to_string()));
+            assert!(!app.popup_items.contains(&"go to issues".to_string()));
+
+            app.screen = Screen::Home;
+            app.update(Action::ShowPalette);
+            assert!(app.popup_items.contains(&"browse repositories".to_string()));
+            assert!(!app.popup_items.contains(&"merge pr".to_string()));
         }
 
         #[tokio::test]
-        async fn home_loaded_stale_id_ignored() {
-            let (mut app, _rx) = test_app();
-            app.load_id = 2;
-            app.update(Action::HomeLoaded {
-                review_requests: vec![make_review_request("o", "r", 1)],
-                my_prs: vec![],
-                load_id: 1,
-            });
-            assert!(app.review_requests.is_empty());
+        async fn palette_filters_and_runs_selection() {
+            let (mut app, mut rx) = test_app();
+            app.screen = Screen::PrDetail;
+            app.current_pr = Some(make_pull_request(7, "body"));
+            app.update(Action::ShowPalette);
+            for c in "clospr".chars() {
+                app.update(Action::PaletteInput(c));
+            }
+            assert_eq!(app.popup_items[0], "close pr");
+            app.update(Action::PaletteBackspace);
+            assert_eq!(app.palette_query, "closp");
+            app.update(Action::PopupSelect);
+            assert_eq!(app.input_mode, InputMode::Normal);
+            assert!(matches!(
+                rx.try_recv(),
+                Ok(Action::ShowConfirm(ConfirmAction::ClosePr(7)))
+            ));
         }
 
         #[tokio::test]
-        async fn repos_loaded_matching_id_updates() {
+        async fn labels_loaded_checks_current_labels() {
             let (mut app, _rx) = test_app();
-            app.load_id = 3;
-            app.repo_index = 10;
-            let repos = vec![make_repo("a"), make_repo("b")];
-            app.update(Action::ReposLoaded(repos, None, 3));
-            assert_eq!(app.repos.len(), 2);
-            assert_eq!(app.repo_index, 1); // clamped
+            app.screen = Screen::RepoView;
+            app.repo_tab = RepoTab::Issues;
+            let mut issue = make_issue(1, "bug report");
+            // Matched by name, even when the issue's copy has no color
+            issue.labels = vec![Label {
+                name: "bug".to_string(),
+                color: None,
+            }];
+            app.issues = vec![issue];
+            app.update(Action::LabelsLoaded(vec![
+                make_label("bug"),
+                make_label("docs"),
+            ]));
+            assert_eq!(app.input_mode, InputMode::MultiSelectPopup);
+            assert_eq!(app.popup_title, "Edit Labels");
+            assert_eq!(app.popup_items, vec!["bug", "docs"]);
+            assert_eq!(app.popup_checked, vec![true, false]);
         }
 
         #[tokio::test]
-        async fn repos_loaded_stale_id_ignored() {
+        async fn popup_toggle_flips_highlighted_label() {
             let (mut app, _rx) = test_app();
-            app.load_id = 5;
-            app.update(Action::ReposLoaded(vec![make_repo("a")], None, 3));
-            assert!(app.repos.is_empty());
+            app.popup_checked = vec![true, false];
+            app.popup_index = 1;
+            app.update(Action::PopupToggle);
+            assert_eq!(app.popup_checked, vec![true, true]);
+            app.update(Action::PopupToggle);
+            assert_eq!(app.popup_checked, vec![true, false]);
         }
 
         #[tokio::test]
-        async fn pr_detail_loaded_first_time_transitions_screen() {
-            let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoView;
-            app.load_id = 1;
-            let pr = make_pull_request(42, "test body");
-            app.update(Action::PrDetailLoaded(Box::new(pr), 1));
-            assert_eq!(app.screen, Screen::PrDetail);
-            assert!(app.current_pr.is_some());
-            assert_eq!(app.scroll_offset, 0);
+        async fn unchanged_labels_apply_nothing() {
+            let (mut app, mut rx) = test_app();
+            app.screen = Screen::PrDetail;
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            let mut pr = make_pull_request(1, "body");
+            pr.labels = vec![make_label("bug")];
+            app.current_pr = Some(pr);
+            app.update(Action::LabelsLoaded(vec![make_label("bug")]));
+            app.update(Action::PopupSelect);
+            assert_eq!(app.input_mode, InputMode::Normal);
+            assert!(!app.loading);
+            assert!(rx.try_recv().is_err());
         }
 
         #[tokio::test]
-        async fn pr_detail_loaded_already_on_pr_detail_no_scroll_reset() {
+        async fn no_repo_labels_is_an_error() {
             let (mut app, _rx) = test_app();
             app.screen = Screen::PrDetail;
-            app.load_id = 2;
-            app.scroll_offset = 10;
-            let pr = make_pull_request(42, "updated body");
-            app.update(Action::PrDetailLoaded(Box::new(pr), 2));
-            assert_eq!(app.screen, Screen::PrDetail);
-            assert!(app.current_pr.is_some());
-            assert_eq!(app.scroll_offset, 10); // not reset
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.update(Action::LabelsLoaded(vec![]));
+            assert_eq!(app.input_mode, InputMode::Normal);
+            assert!(app.error.is_some());
         }
 
-        // Popup & confirm
+        #[tokio::test]
+        async fn start_on_repos_opens_default_repo() {
+            let (mut app, _rx) = test_app();
+            app.ui_config.start_screen = StartScreen::Repos;
+            app.forge_configs = vec![crate::config::ForgeConfig {
+                name: app.forge_name.clone(),
+                forge_type: crate::config::ForgeType::GitHub,
+                host: "github.com".to_string(),
+                token_env: None,
+                token_command: None,
+                oauth_client_id: None,
+                token_source: crate::config::TokenSource::default(),
+                timeout: None,
+                page_size: None,
+                endpoint: Default::default(),
+                transport: Default::default(),
+                merge: Default::default(),
+                repos: Default::default(),
+                default_repo: Some("owner/repo".to_string()),
+            }];
+            app.update(Action::Start);
+            assert_eq!(app.screen, Screen::RepoView);
+            assert_eq!(
+                app.current_repo,
+                Some(("owner".to_string(), "repo".to_string()))
+            );
+        }
+
+        fn forge_config(name: &str) -> crate::config::ForgeConfig {
+            crate::config::ForgeConfig {
+                name: name.to_string(),
+                forge_type: crate::config::ForgeType::Gitea,
+                host: "git.example.com".to_string(),
+                token_env: None,
+                token_command: None,
+                oauth_client_id: None,
+                token_source: crate::config::TokenSource::default(),
+                timeout: None,
+                page_size: None,
+                endpoint: Default::default(),
+                transport: Default::default(),
+                merge: Default::default(),
+                repos: Default::default(),
+                default_repo: None,
+            }
+        }
 
         #[tokio::test]
-        async fn show_merge_method_select() {
+        async fn forge_failure_is_kept_per_forge() {
             let (mut app, _rx) = test_app();
-            app.update(Action::ShowMergeMethodSelect);
-            assert_eq!(app.input_mode, InputMode::SelectPopup);
-            assert_eq!(app.popup_items.len(), 3);
+            app.active_forge = "github".to_string();
+            app.forge_configs = vec![forge_config("github"), forge_config("gitea")];
+            app.update(Action::ForgeFailed(
+                "gitea".to_string(),
+                "no token".to_string(),
+            ));
+            assert_eq!(app.error.as_deref(), Some("gitea: no token"));
+            assert_eq!(app.forge_name, "GitHub");
+
+            app.update(Action::ShowForgeSelect);
+            assert_eq!(
+                app.popup_items,
+                vec!["github (active)", "gitea (unavailable: no token)"]
+            );
         }
 
         #[tokio::test]
-        async fn show_review_select() {
+        async fn switching_back_reuses_ready_client() {
             let (mut app, _rx) = test_app();
-            app.update(Action::ShowReviewSelect);
-            assert_eq!(app.input_mode, InputMode::SelectPopup);
-            assert_eq!(app.popup_items.len(), 3);
+            app.forge_configs = vec![forge_config("github"), forge_config("work")];
+            app.active_forge = "work".to_string();
+            app.forge_states.insert(
+                "github".to_string(),
+                ForgeState::Ready(Arc::new(
+                    GitHub::new(
+                        "t".to_string(),
+                        crate::http::DEFAULT_TIMEOUT,
+                        &Default::default(),
+                        crate::forge::DEFAULT_PAGE_SIZE,
+                    )
+                    .unwrap(),
+                )),
+            );
+            app.repos = vec![make_repo("a")];
+            app.update(Action::SwitchForge(0));
+            assert_eq!(app.active_forge, "github");
+            assert_eq!(app.forge_name, "github");
+            assert!(app.repos.is_empty());
+            assert!(!app.loading);
         }
 
         #[tokio::test]
-        async fn popup_down_increments() {
+        async fn session_stats_count_reviews_comments_and_merges() {
             let (mut app, _rx) = test_app();
-            app.popup_items = vec!["a".into(), "b".into(), "c".into()];
-            app.popup_index = 0;
-            app.update(Action::PopupDown);
-            assert_eq!(app.popup_index, 1);
+            app.update(Action::ReviewSubmitted);
+            app.update(Action::CommentPosted);
+            app.update(Action::CommentPosted);
+            app.update(Action::PrMerged);
+            assert_eq!(
+                app.stats.summary(),
+                "1 PR reviewed, 2 comments posted, 1 merge in 0s"
+            );
+
+            app.stats.started -= std::time::Duration::from_secs(3900);
+            assert!(app.stats.summary().ends_with("in 1h 05m"));
         }
 
         #[tokio::test]
-        async fn popup_up_decrements() {
+        async fn start_opens_detected_repo_over_start_screen() {
             let (mut app, _rx) = test_app();
-            app.popup_items = vec!["a".into(), "b".into(), "c".into()];
-            app.popup_index = 2;
-            app.update(Action::PopupUp);
-            assert_eq!(app.popup_index, 1);
+            app.ui_config.start_screen = StartScreen::Repos;
+            app.start_repo = Some(("owner".to_string(), "cwd".to_string()));
+            app.update(Action::Start);
+            assert_eq!(app.screen, Screen::RepoView);
+            assert_eq!(
+                app.current_repo,
+                Some(("owner".to_string(), "cwd".to_string()))
+            );
+            assert!(app.start_repo.is_none());
         }
 
         #[tokio::test]
-        async fn show_confirm_sets_state() {
+        async fn start_deep_links_into_a_pr_and_back_loads_the_repo() {
             let (mut app, _rx) = test_app();
-            app.update(Action::ShowConfirm(ConfirmAction::ClosePr(42)));
-            assert_eq!(app.input_mode, InputMode::Confirm);
-            assert!(matches!(
-                app.confirm_action,
-                Some(ConfirmAction::ClosePr(42))
+            app.start_repo = Some(("owner".to_string(), "repo".to_string()));
+            app.start_pr = Some(7);
+            app.update(Action::Start);
+            assert!(app.start_pr.is_none());
+
+            let load_id = app.load_id;
+            app.update(Action::PrDetailLoaded(
+                Box::new(make_pull_request(7, "body")),
+                load_id,
             ));
+            assert_eq!(app.screen, Screen::PrDetail);
+
+            app.update(Action::Back);
+            assert_eq!(app.screen, Screen::RepoView);
+            assert!(app.loading);
+            assert!(app.load_id > load_id);
         }
 
         #[tokio::test]
-        async fn confirm_no_resets() {
+        async fn start_on_repos_without_default_shows_list() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::Confirm;
-            app.confirm_action = Some(ConfirmAction::ClosePr(42));
-            app.update(Action::ConfirmNo);
-            assert_eq!(app.input_mode, InputMode::Normal);
-            assert!(app.confirm_action.is_none());
+            app.ui_config.start_screen = StartScreen::Repos;
+            app.update(Action::Start);
+            assert_eq!(app.screen, Screen::RepoList);
+            assert!(app.loading);
         }
 
-        // Search state machine
-
         #[tokio::test]
-        async fn enter_search_mode() {
+        async fn last_location_follows_screen() {
             let (mut app, _rx) = test_app();
-            app.search.query = "old".to_string();
-            app.update(Action::EnterSearchMode);
-            assert_eq!(app.input_mode, InputMode::Search);
-            assert!(app.search.query.is_empty());
+            assert_eq!(app.last_location(), LastLocation::Home);
+            app.screen = Screen::RepoList;
+            assert_eq!(app.last_location(), LastLocation::Repos);
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            app.screen = Screen::PrDetail;
+            app.nav_stack = vec![nav_entry(Screen::RepoView)];
+            assert_eq!(
+                app.last_location(),
+                LastLocation::Repo {
+                    owner: "owner".to_string(),
+                    name: "repo".to_string()
+                }
+            );
+            // PR opened straight from the dashboard
+            app.nav_stack = vec![nav_entry(Screen::Home)];
+            assert_eq!(app.last_location(), LastLocation::Home);
         }
 
         #[tokio::test]
-        async fn search_input_appends() {
+        async fn restored_session_reopens_tab_selection_and_pr() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::Search;
-            app.update(Action::SearchInput('a'));
-            assert_eq!(app.search.query, "a");
-            app.update(Action::SearchInput('b'));
-            assert_eq!(app.search.query, "ab");
-        }
+            app.current_repo = Some(("owner".to_string(), "repo".to_string()));
+            app.screen = Screen::PrDetail;
+            app.nav_stack = vec![nav_entry(Screen::RepoView)];
+            app.repo_tab = RepoTab::Issues;
+            app.issue_index = 3;
+            app.current_pr = Some(make_pull_request(7, "Fix"));
+            app.scroll_offset = 12;
+            let session = app.session();
+            assert_eq!(session.label(), "owner/repo #7");
+            assert_eq!(session.index, 3);
 
-        #[tokio::test]
-        async fn search_backspace_pops() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::Search;
-            app.search.query = "ab".to_string();
-            app.update(Action::SearchBackspace);
-            assert_eq!(app.search.query, "a");
+            app.restore_session(session);
+            assert_eq!(app.screen, Screen::RepoView);
+            assert_eq!(app.repo_tab, RepoTab::Issues);
+            assert_eq!(app.issue_index, 3);
+            assert_eq!(app.session_scroll, Some(12));
+            assert!(app.loading);
         }
 
         #[tokio::test]
-        async fn search_backspace_empty_deactivates() {
-            let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::Search;
-            app.search.query = "a".to_string();
-            app.search.active = true;
-            app.update(Action::SearchBackspace);
-            // query is now empty
-            assert!(app.search.query.is_empty());
-            assert!(!app.search.active);
+        async fn session_left_on_home_top_is_not_offered() {
+            let (app, _rx) = test_app();
+            assert!(app.session().is_fresh());
         }
 
         #[tokio::test]
-        async fn exit_search_mode_keeps_active() {
+        async fn back_to_empty_repo_list_loads_repos() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::Search;
-            app.search.query = "foo".to_string();
-            app.update(Action::ExitSearchMode);
-            assert_eq!(app.input_mode, InputMode::Normal);
-            assert!(app.search.active);
+            app.screen = Screen::RepoView;
+            app.update(Action::Back);
+            assert_eq!(app.screen, Screen::RepoList);
+            assert!(app.loading);
         }
 
         #[tokio::test]
-        async fn search_confirm_activates() {
+        async fn leaving_repo_clears_milestone_filter() {
             let (mut app, _rx) = test_app();
-            app.input_mode = InputMode::Search;
-            app.search.query = "bar".to_string();
-            app.update(Action::SearchConfirm);
-            assert_eq!(app.input_mode, InputMode::Normal);
-            assert!(app.search.active);
+            app.screen = Screen::RepoView;
+            app.milestone_filter = Some(make_milestone(1, "v1"));
+            app.update(Action::Back);
+            assert!(app.milestone_filter.is_none());
         }
 
         #[tokio::test]
-        async fn clear_search_resets() {
+        async fn back_from_pr_detail_clears_comments() {
             let (mut app, _rx) = test_app();
-            app.search.query = "foo".to_string();
-            app.search.active = true;
-            app.search.match_indices = vec![0, 1, 2];
-            app.update(Action::ClearSearch);
-            assert!(app.search.query.is_empty());
-            assert!(!app.search.active);
-            assert!(app.search.match_indices.is_empty());
+            app.screen = Screen::PrDetail;
+            app.current_pr = Some(make_pull_request(1, "body"));
+            app.pr_comments = vec![make_comment(1, "alice", "hi")];
+            app.pr_reviews.requested = vec!["bob".to_string()];
+            app.pr_checks.passed = 2;
+            app.viewed_files = Some(vec!["a.rs".to_string()]);
+            app.pr_files = vec![make_file("a.rs", "modified")];
+            app.update(Action::Back);
+            assert!(app.pr_files.is_empty());
+            assert!(app.pr_comments.is_empty());
+            assert!(app.pr_reviews.requested.is_empty());
+            assert_eq!(app.pr_checks, PrChecks::default());
+            assert!(app.viewed_files.is_none());
         }
 
         #[tokio::test]
-        async fn search_on_repo_list_computes_matches() {
+        async fn error_sets_error_clears_loading() {
             let (mut app, _rx) = test_app();
-            app.screen = Screen::RepoList;
-            app.repos = vec![make_repo("foo-bar"), make_repo("baz"), make_repo("foo-qux")];
-            app.update(Action::SearchInput('f'));
-            app.update(Action::SearchInput('o'));
-            app.update(Action::SearchInput('o'));
-            assert_eq!(app.search.match_indices, vec![0, 2]);
+            app.loading = true;
+            app.update(Action::Error(GritError::Api(
+                "something failed".to_string(),
+            )));
+            assert_eq!(app.error, Some("API error: something failed".to_string()));
+            assert!(!app.loading);
         }
 
-        // Mutation results
-
         #[tokio::test]
-        async fn pr_merged_sets_flash() {
+        async fn rate_limit_schedules_a_retry() {
             let (mut app, _rx) = test_app();
-            app.update(Action::PrMerged);
-            assert!(app.flash_message.is_some());
-            assert_eq!(app.flash_message.as_ref().unwrap().0, "PR merged!");
+            app.update(Action::Error(GritError::RateLimited { reset: None }));
+            assert!(app.retry_at.is_some());
+            assert!(app.error.as_deref().unwrap().contains("retrying in 60s"));
+
+            app.retry_at = Some(std::time::Instant::now());
+            app.retry_if_due();
+            assert_eq!(app.retry_at, None);
+            assert_eq!(app.error, None);
         }
 
         #[tokio::test]
-        async fn pr_closed_sets_flash() {
+        async fn network_error_goes_offline_instead_of_erroring() {
             let (mut app, _rx) = test_app();
-            app.update(Action::PrClosed);
-            assert!(app.flash_message.is_some());
-            assert_eq!(app.flash_message.as_ref().unwrap().0, "PR closed.");
+            app.loading = true;
+            app.update(Action::Error(GritError::Network(
+                "error sending request for url (https://api.github.com/user)".to_string(),
+            )));
+            assert!(app.offline);
+            assert_eq!(app.error, None);
+            assert!(!app.loading);
         }
 
         #[tokio::test]
-        async fn issue_closed_sets_flash() {
+        async fn timeout_is_an_error_not_offline() {
             let (mut app, _rx) = test_app();
-            app.update(Action::IssueClosed);
-            assert!(app.flash_message.is_some());
-            assert_eq!(app.flash_message.as_ref().unwrap().0, "Issue closed.");
+            app.update(Action::Error(GritError::Timeout(
+                "operation timed out".to_string(),
+            )));
+            assert!(!app.offline);
+            assert!(app
+                .error
+                .as_deref()
+                .is_some_and(|e| e.starts_with("Timed out")));
         }
 
         #[tokio::test]
-        async fn comment_posted_sets_flash() {
+        async fn pr_prefetch_waits_for_the_selection_to_rest() {
             let (mut app, _rx) = test_app();
-            app.update(Action::CommentPosted);
-            assert!(app.flash_message.is_some());
-            assert_eq!(app.flash_message.as_ref().unwrap().0, "Comment posted.");
+            app.screen = Screen::RepoView;
+            app.repo_tab = RepoTab::PullRequests;
+            app.current_repo = Some(("o".to_string(), "r".to_string()));
+            app.prs = (1..=8).map(|n| make_pr_summary(n, "pr")).collect();
+            app.pr_index = 4;
+
+            // First tick only notes where the selection is
+            app.prefetch_pr_neighbors();
+            assert!(app.pr_prefetch.warmed.is_empty());
+
+            app.pr_prefetch.moved_at = Some(std::time::Instant::now() - PR_PREFETCH_SETTLE);
+            app.prefetch_pr_neighbors();
+            let mut warmed: Vec<u64> = app.pr_prefetch.warmed.iter().map(|k| k.2).collect();
+            warmed.sort();
+            assert_eq!(warmed, vec![3, 4, 5, 6, 7]);
+
+            // Moving by one only adds the newly uncovered row
+            app.pr_index = 5;
+            app.prefetch_pr_neighbors();
+            app.pr_prefetch.moved_at = Some(std::time::Instant::now() - PR_PREFETCH_SETTLE);
+            app.prefetch_pr_neighbors();
+            assert_eq!(app.pr_prefetch.warmed.len(), 6);
         }
 
         #[tokio::test]
-        async fn error_sets_error_clears_loading() {
-            let (mut app, _rx) = test_app();
-            app.loading = true;
-            app.update(Action::Error("something failed".to_string()));
-            assert_eq!(app.error, Some("something failed".to_string()));
-            assert!(!app.loading);
+        async fn mutations_are_queued_while_offline() {
+            let (mut app, mut rx) = test_app();
+            app.offline = true;
+            app.spawn_comment("o".to_string(), "r".to_string(), 4, "hi".to_string(), None);
+            match rx.try_recv() {
+                Ok(Action::MutationQueued(Mutation::Comment { number, body, .. })) => {
+                    assert_eq!(number, 4);
+                    assert_eq!(body, "hi");
+                }
+                other => panic!("expected queued comment, got {:?}", other),
+            }
         }
     }
 
@@ -3301,6 +14097,53 @@ mod tests {
 
     mod pagination {
         use super::*;
+        use crate::forge::DEFAULT_PAGE_SIZE as PAGE_SIZE;
+
+        #[tokio::test]
+        async fn full_page_follows_the_forge_page_size() {
+            let gitea = crate::gitea::Gitea::new(
+                "git.example.com".to_string(),
+                "t".into(),
+                crate::http::DEFAULT_TIMEOUT,
+                &Default::default(),
+                20,
+            )
+            .unwrap();
+            let (tx, _rx) = mpsc::unbounded_channel();
+            let mut app = App::new(Arc::new(gitea), tx, vec![], UiConfig::default());
+            app.load_id = 1;
+            let repos: Vec<Repository> = (0..20).map(|i| make_repo(&format!("r{}", i))).collect();
+            app.update(Action::ReposLoaded(repos, PageInfo::default(), 1));
+            assert!(app.repos_pagination.has_more);
+        }
+
+        #[tokio::test]
+        async fn saved_search_pages_append_until_the_total() {
+            let (mut app, _rx) = test_app();
+            app.searches = vec![("bugs".to_string(), "label:bug".to_string())];
+            app.home_section = HomeSection::Saved;
+            let info = PageInfo {
+                total_count: Some(PAGE_SIZE as u64 + 1),
+                total_pages: None,
+            };
+            let page: Vec<IssueRef> = (0..PAGE_SIZE as u64)
+                .map(|n| make_issue_ref("o", "r", n, false))
+                .collect();
+            app.update(Action::SavedSearchLoaded("bugs".to_string(), 1, page, info));
+            assert!(app.saved_pagination.has_more);
+
+            app.update(Action::GoToBottom);
+            assert!(app.saved_pagination.loading_more);
+            assert_eq!(app.saved_pagination.page, 2);
+            // A repeat from the first page is left out
+            let rest = vec![
+                make_issue_ref("o", "r", 0, false),
+                make_issue_ref("o", "r", 99, true),
+            ];
+            app.update(Action::SavedSearchLoaded("bugs".to_string(), 2, rest, info));
+            assert_eq!(app.saved.len(), PAGE_SIZE + 1);
+            assert!(!app.saved_pagination.has_more);
+        }
 
         #[tokio::test]
         async fn repos_loaded_sets_has_more_when_full_page() {
@@ -3309,7 +14152,7 @@ mod tests {
             let repos: Vec<Repository> = (0..PAGE_SIZE)
                 .map(|i| make_repo(&format!("r{}", i)))
                 .collect();
-            app.update(Action::ReposLoaded(repos, None, 1));
+            app.update(Action::ReposLoaded(repos, PageInfo::default(), 1));
             assert!(app.repos_pagination.has_more);
             assert_eq!(app.repos_pagination.page, 1);
         }
@@ -3319,10 +14162,50 @@ mod tests {
             let (mut app, _rx) = test_app();
             app.load_id = 1;
             let repos = vec![make_repo("a"), make_repo("b")];
-            app.update(Action::ReposLoaded(repos, None, 1));
+            app.update(Action::ReposLoaded(repos, PageInfo::default(), 1));
             assert!(!app.repos_pagination.has_more);
         }
 
+        #[tokio::test]
+        async fn page_count_decides_has_more() {
+            let (mut app, _rx) = test_app();
+            app.load_id = 1;
+            // GitHub filters PRs out of issue pages, so a short page isn't the last
+            let info = PageInfo {
+                total_count: None,
+                total_pages: Some(3),
+            };
+            app.update(Action::IssuesLoaded(vec![make_issue(1, "a")], info, 1));
+            assert!(app.issues_pagination.has_more);
+            assert_eq!(app.issues_pagination.total_pages, Some(3));
+
+            app.issues_pagination.page = 3;
+            app.update(Action::IssuesAppended(
+                vec![make_issue(2, "b")],
+                PageInfo::default(),
+                1,
+            ));
+            // The last page has no rel="last" link; the earlier count still applies
+            assert_eq!(app.issues_pagination.total_pages, Some(3));
+            assert!(!app.issues_pagination.has_more);
+        }
+
+        #[tokio::test]
+        async fn total_count_gives_page_count() {
+            let (mut app, _rx) = test_app();
+            app.load_id = 1;
+            let repos: Vec<Repository> = (0..PAGE_SIZE)
+                .map(|i| make_repo(&format!("r{}", i)))
+                .collect();
+            let info = PageInfo {
+                total_count: Some(120),
+                total_pages: None,
+            };
+            app.update(Action::ReposLoaded(repos, info, 1));
+            assert_eq!(app.repos_pagination.total_pages, Some(3));
+            assert!(app.repos_pagination.has_more);
+        }
+
         #[tokio::test]
         async fn repos_appended_extends_list() {
             let (mut app, _rx) = test_app();
@@ -3330,19 +14213,72 @@ mod tests {
             app.repos = vec![make_repo("a")];
             let new_repos = vec![make_repo("b"), make_repo("c")];
             app.repos_pagination.loading_more = true;
-            app.update(Action::ReposAppended(new_repos, None, 1));
+            app.update(Action::ReposAppended(new_repos, PageInfo::default(), 1));
             assert_eq!(app.repos.len(), 3);
             assert!(!app.repos_pagination.loading_more);
             assert!(!app.repos_pagination.has_more); // 2 < PAGE_SIZE
         }
 
+        #[tokio::test]
+        async fn appended_items_already_listed_are_skipped() {
+            let (mut app, _rx) = test_app();
+            app.load_id = 1;
+            app.prs = vec![make_pr_summary(3, "c"), make_pr_summary(2, "b")];
+            // #2 slid onto page two between requests
+            app.update(Action::PrsAppended(
+                vec![make_pr_summary(2, "b"), make_pr_summary(1, "a")],
+                PageInfo::default(),
+                1,
+            ));
+            let numbers: Vec<u64> = app.prs.iter().map(|pr| pr.number).collect();
+            assert_eq!(numbers, vec![3, 2, 1]);
+        }
+
+        #[tokio::test]
+        async fn reload_keeps_selection_on_same_item() {
+            let (mut app, _rx) = test_app();
+            app.load_id = 1;
+            app.prs = vec![make_pr_summary(3, "c"), make_pr_summary(2, "b")];
+            app.pr_index = 1;
+            // A new PR pushes #2 down a row
+            app.update(Action::PrsLoaded(
+                vec![
+                    make_pr_summary(4, "d"),
+                    make_pr_summary(3, "c"),
+                    make_pr_summary(2, "b"),
+                ],
+                PageInfo::default(),
+                1,
+            ));
+            assert_eq!(app.pr_index, 2);
+            assert_eq!(app.prs[app.pr_index].number, 2);
+        }
+
+        #[tokio::test]
+        async fn reload_clamps_when_selected_item_is_gone() {
+            let (mut app, _rx) = test_app();
+            app.load_id = 1;
+            app.repos = vec![make_repo("a"), make_repo("b"), make_repo("c")];
+            app.repo_index = 2;
+            app.update(Action::ReposLoaded(
+                vec![make_repo("a"), make_repo("b")],
+                PageInfo::default(),
+                1,
+            ));
+            assert_eq!(app.repo_index, 1);
+        }
+
         #[tokio::test]
         async fn repos_appended_stale_id_ignored() {
             let (mut app, _rx) = test_app();
             app.load_id = 5;
             app.repos = vec![make_repo("a")];
             app.repos_pagination.loading_more = true;
-            app.update(Action::ReposAppended(vec![make_repo("b")], None, 3));
+            app.update(Action::ReposAppended(
+                vec![make_repo("b")],
+                PageInfo::default(),
+                3,
+            ));
             assert_eq!(app.repos.len(), 1); // not extended
             assert!(app.repos_pagination.loading_more); // not cleared
         }
@@ -3417,7 +14353,7 @@ mod tests {
             app.prs_pagination.loading_more = true;
             app.update(Action::PrsAppended(
                 vec![make_pr_summary(2, "second")],
-                None,
+                PageInfo::default(),
                 1,
             ));
             assert_eq!(app.prs.len(), 2);
@@ -3431,7 +14367,11 @@ mod tests {
             app.repos_pagination.page = 3;
             app.repos_pagination.has_more = true;
             app.repos_pagination.loading_more = true;
-            app.update(Action::ReposLoaded(vec![make_repo("a")], None, 1));
+            app.update(Action::ReposLoaded(
+                vec![make_repo("a")],
+                PageInfo::default(),
+                1,
+            ));
             assert_eq!(app.repos_pagination.page, 1);
             assert!(!app.repos_pagination.has_more);
             assert!(!app.repos_pagination.loading_more);
@@ -3443,6 +14383,34 @@ mod tests {
     mod url_construction {
         use super::*;
 
+        fn gitlab_app() -> (App, mpsc::UnboundedReceiver<Action>) {
+            let gitlab = crate::gitlab::GitLab::new(
+                "https://gitlab.example.com/".to_string(),
+                "t".into(),
+                crate::http::DEFAULT_TIMEOUT,
+                &Default::default(),
+                crate::forge::DEFAULT_PAGE_SIZE,
+            )
+            .unwrap();
+            let (tx, rx) = mpsc::unbounded_channel();
+            (
+                App::new(Arc::new(gitlab), tx, vec![], UiConfig::default()),
+                rx,
+            )
+        }
+
+        #[tokio::test]
+        async fn gitlab_nested_group_urls() {
+            let (mut app, _rx) = gitlab_app();
+            app.screen = Screen::PrDetail;
+            app.current_repo = Some(("group/sub".to_string(), "project".to_string()));
+            app.current_pr = Some(make_pull_request(3, "body"));
+            assert_eq!(
+                app.current_item_url(),
+                Some("https://gitlab.example.com/group/sub/project/-/merge_requests/3".to_string())
+            );
+        }
+
         #[tokio::test]
         async fn home_review_requests_url() {
             let (mut app, _rx) = test_app();
@@ -3455,6 +14423,63 @@ mod tests {
             );
         }
 
+        #[tokio::test]
+        async fn open_offers_tracker_links_beside_forge_page() {
+            let (mut app, _rx) = test_app();
+            app.link_rules = crate::links::compile(&[crate::config::LinkPattern {
+                pattern: r"JIRA-\d+".to_string(),
+                url: "https://jira.example.com/browse/$0".to_string(),
+            }]);
+            app.current_repo = Some(("octo".to_string(), "repo".to_string()));
+            app.current_pr = Some(make_pull_request(3, "Closes JIRA-9"));
+            app.screen = Screen::PrDetail;
+            app.update(Action::OpenInBrowser);
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+            assert_eq!(app.popup_title, "Open Link");
+            assert_eq!(
+                app.popup_items,
+                vec![
+                    "Forge page".to_string(),
+                    "JIRA-9  https://jira.example.com/browse/JIRA-9".to_string(),
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn body_links_are_listed_and_offered_to_copy() {
+            let (mut app, _rx) = test_app();
+            app.current_repo = Some(("octo".to_string(), "repo".to_string()));
+            app.current_pr = Some(make_pull_request(
+                3,
+                "Design: https://example.com/spec\n![ui](https://img.example.com/ui.png)",
+            ));
+            app.pr_comments = vec![make_comment(1, "alice", "see https://example.com/spec")];
+            app.screen = Screen::PrDetail;
+
+            let lines = app.pr_detail_lines();
+            let footer: Vec<&String> = lines
+                .iter()
+                .filter(|(kind, _)| matches!(kind, PrLine::Link { .. }))
+                .map(|(_, text)| text)
+                .collect();
+            assert_eq!(
+                footer,
+                vec![
+                    "[1] https://example.com/spec",
+                    "[2] image https://img.example.com/ui.png"
+                ]
+            );
+
+            app.update(Action::YankUrl);
+            assert_eq!(app.input_mode, InputMode::SelectPopup);
+            assert_eq!(app.popup_title, "Copy Link");
+            assert_eq!(app.popup_items.len(), 3);
+            assert_eq!(
+                app.popup_items[2],
+                "[2] image https://img.example.com/ui.png"
+            );
+        }
+
         #[tokio::test]
         async fn home_my_prs_url() {
             let (mut app, _rx) = test_app();
@@ -1,14 +1,16 @@
 use std::io::Write;
 use std::time::Duration;
 
-use crate::config::{ForgeConfig, ForgeType};
+use crate::config::{ForgeConfig, ForgeType, TokenSource, Transport};
 
 /// Try to run a CLI command and capture stdout as a token
 fn try_cli_token(command: &str) -> Option<String> {
-    let output = std::process::Command::new("sh")
-        .args(["-c", command])
-        .output()
-        .ok()?;
+    run_for_token(std::process::Command::new("sh").args(["-c", command]))
+}
+
+/// Stdout of `command` as a token, if it succeeds and prints one
+fn run_for_token(command: &mut std::process::Command) -> Option<String> {
+    let output = command.output().ok()?;
 
     if output.status.success() {
         let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
@@ -48,12 +50,159 @@ fn save_forge_token(forge_name: &str, token: &str) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Save a token where the forge's `token_source` says
+fn store_token(forge_config: &ForgeConfig, token: &str) -> Result<(), String> {
+    match forge_config.token_source {
+        TokenSource::Keyring => crate::keyring::store(&forge_config.name, token),
+        TokenSource::File => save_forge_token(&forge_config.name, token)
+            .map_err(|e| format!("Could not save token: {}", e)),
+        TokenSource::Gh | TokenSource::Glab => Err(format!(
+            "Forge '{}' takes its token from {}; log in there instead",
+            forge_config.name,
+            cli_name(forge_config.token_source)
+        )),
+    }
+}
+
+fn cli_name(source: TokenSource) -> &'static str {
+    match source {
+        TokenSource::Glab => "glab",
+        _ => "gh",
+    }
+}
+
+/// Config directory of gh or glab: its override env var, then
+/// $XDG_CONFIG_HOME, then the platform config dir and ~/.config
+fn cli_config_dirs(env_var: &str, name: &str) -> Vec<std::path::PathBuf> {
+    if let Some(dir) = std::env::var_os(env_var) {
+        return vec![dir.into()];
+    }
+    let mut dirs: Vec<_> = std::env::var_os("XDG_CONFIG_HOME")
+        .map(|d| std::path::PathBuf::from(d).join(name))
+        .into_iter()
+        .collect();
+    // gh names its Windows directory differently
+    let platform = if cfg!(windows) && name == "gh" {
+        "GitHub CLI"
+    } else {
+        name
+    };
+    dirs.extend(dirs::config_dir().map(|d| d.join(platform)));
+    dirs.extend(dirs::home_dir().map(|d| d.join(".config").join(name)));
+    dirs
+}
+
+/// Value of `key` directly under the `host:` section of a CLI's YAML config.
+/// Just enough YAML for gh's hosts.yml and glab's config.yml: block mappings,
+/// optionally quoted scalars.
+fn yaml_host_value(yaml: &str, host: &str, key: &str) -> Option<String> {
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let unquote = |s: &str| s.trim().trim_matches(|c| c == '"' || c == '\'').to_string();
+
+    let mut lines = yaml
+        .lines()
+        .filter(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'));
+    let host_indent = lines
+        .by_ref()
+        .find(|l| unquote(l.trim().strip_suffix(':').unwrap_or("")) == host)
+        .map(indent)?;
+
+    let mut child_indent = None;
+    for line in lines {
+        let depth = indent(line);
+        if depth <= host_indent {
+            break;
+        }
+        // Only the host's own keys, not nested ones like gh's `users:`
+        if *child_indent.get_or_insert(depth) != depth {
+            continue;
+        }
+        if let Some((k, v)) = line.trim().split_once(':') {
+            if unquote(k) == key {
+                let value = unquote(v);
+                return (!value.is_empty()).then_some(value);
+            }
+        }
+    }
+    None
+}
+
+/// Token the official CLI holds for `host`: its YAML config first, then
+/// asking the CLI itself (gh keeps tokens in the system keyring by default)
+fn cli_token(source: TokenSource, host: &str) -> Option<String> {
+    // The host goes in as its own argument, never through a shell
+    let (env_var, dir, file, key, program, args) = match source {
+        TokenSource::Glab => (
+            "GLAB_CONFIG_DIR",
+            "glab-cli",
+            "config.yml",
+            "token",
+            "glab",
+            &["config", "get", "token", "--host", host][..],
+        ),
+        _ => (
+            "GH_CONFIG_DIR",
+            "gh",
+            "hosts.yml",
+            "oauth_token",
+            "gh",
+            &["auth", "token", "--hostname", host][..],
+        ),
+    };
+    cli_config_dirs(env_var, dir)
+        .into_iter()
+        .find_map(|d| std::fs::read_to_string(d.join(file)).ok())
+        .and_then(|yaml| yaml_host_value(&yaml, host, key))
+        .or_else(|| run_for_token(std::process::Command::new(program).args(args)))
+}
+
+/// The CLI whose login grit falls back to for a forge, if any
+fn fallback_cli(forge_config: &ForgeConfig) -> Option<TokenSource> {
+    match forge_config.token_source {
+        source @ (TokenSource::Gh | TokenSource::Glab) => Some(source),
+        _ => match forge_config.forge_type {
+            ForgeType::GitHub => Some(TokenSource::Gh),
+            ForgeType::GitLab => Some(TokenSource::Glab),
+            ForgeType::Gitea => None,
+        },
+    }
+}
+
 /// Load a token for any forge, trying multiple sources:
 /// 1. Forge-specific env var (from config)
-/// 2. Stored token from ~/.config/grit/tokens/{forge_name}
+/// 2. OS keyring when `token_source = "keyring"`, then the stored token from
+///    ~/.config/grit/tokens/{forge_name} (files left over until `grit auth migrate`)
+///    With `token_source = "gh"` / `"glab"` that CLI's login is used instead.
 /// 3. CLI command (from config)
-/// 4. GitHub-only: OAuth device flow
+/// 4. The gh (GitHub) or glab (GitLab) login for the forge's host
+/// 5. GitHub-only: OAuth device flow
 pub async fn load_forge_token(forge_config: &ForgeConfig) -> Result<String, String> {
+    let err = match find_forge_token(forge_config) {
+        Ok(token) => return Ok(token),
+        Err(e) => e,
+    };
+
+    // 5. GitHub-only: OAuth device flow
+    let cli_only = matches!(
+        forge_config.token_source,
+        TokenSource::Gh | TokenSource::Glab
+    );
+    if forge_config.forge_type == ForgeType::GitHub && !cli_only {
+        println!("No GitHub token found.");
+        println!("Starting GitHub OAuth device flow...");
+
+        let token = device_flow_auth(&device_flow(forge_config)?).await?;
+        if let Err(e) = store_token(forge_config, &token) {
+            eprintln!("Warning: {}", e);
+        }
+        return Ok(token);
+    }
+    Err(err)
+}
+
+/// Steps 1-4 of [`load_forge_token`]: a token that's already available,
+/// without prompting. Safe to call while the TUI is up.
+pub fn find_forge_token(forge_config: &ForgeConfig) -> Result<String, String> {
     // 1. Environment variable from config
     if let Some(env_var) = &forge_config.token_env {
         if let Ok(token) = std::env::var(env_var) {
@@ -64,6 +213,21 @@ pub async fn load_forge_token(forge_config: &ForgeConfig) -> Result<String, Stri
     }
 
     // 2. Forge-specific stored token
+    if let source @ (TokenSource::Gh | TokenSource::Glab) = forge_config.token_source {
+        return cli_token(source, &forge_config.host).ok_or_else(|| {
+            format!(
+                "No {} login for {}. Run `{} auth login`.",
+                cli_name(source),
+                forge_config.host,
+                cli_name(source)
+            )
+        });
+    }
+    if forge_config.token_source == TokenSource::Keyring {
+        if let Some(token) = crate::keyring::load(&forge_config.name)? {
+            return Ok(token);
+        }
+    }
     if let Some(token) = load_forge_stored_token(&forge_config.name) {
         return Ok(token);
     }
@@ -78,33 +242,131 @@ pub async fn load_forge_token(forge_config: &ForgeConfig) -> Result<String, Stri
     // 3. CLI command from config
     if let Some(cmd) = &forge_config.token_command {
         if let Some(token) = try_cli_token(cmd) {
-            let _ = save_forge_token(&forge_config.name, &token);
+            let _ = store_token(forge_config, &token);
             return Ok(token);
         }
     }
 
-    // 4. GitHub-only: OAuth device flow
-    if forge_config.forge_type == ForgeType::GitHub {
-        println!("No GitHub token found.");
-        println!("Starting GitHub OAuth device flow...");
-
-        let token = device_flow_auth(GITHUB_CLIENT_ID).await?;
-        if let Err(e) = save_forge_token(&forge_config.name, &token) {
-            eprintln!("Warning: could not save token: {}", e);
-        }
+    // 4. Official CLI login for this host
+    if let Some(token) = fallback_cli(forge_config).and_then(|s| cli_token(s, &forge_config.host)) {
         return Ok(token);
     }
 
     Err(format!(
-        "No token found for forge '{}'. Set {} or configure a token_command.",
+        "No token found for forge '{}'. Set {}, configure a token_command or run `grit auth login --forge {}`.",
         forge_config.name,
         forge_config
             .token_env
             .as_deref()
-            .unwrap_or("a token env var")
+            .unwrap_or("a token env var"),
+        forge_config.name
     ))
 }
 
+/// Run the device flow for a forge and store the token for later sessions
+pub async fn login(forge_config: &ForgeConfig) -> Result<(), String> {
+    if let source @ (TokenSource::Gh | TokenSource::Glab) = forge_config.token_source {
+        return Err(format!(
+            "Forge '{}' uses the {} login; run `{} auth login` instead",
+            forge_config.name,
+            cli_name(source),
+            cli_name(source)
+        ));
+    }
+    let flow = device_flow(forge_config)?;
+    let token = device_flow_auth(&flow).await?;
+    store_token(forge_config, &token)?;
+    println!(
+        "Logged in to {} ({}).",
+        forge_config.name, forge_config.host
+    );
+    Ok(())
+}
+
+/// Token files of a forge: its own, plus the legacy one for GitHub
+fn token_files(forge_config: &ForgeConfig) -> Vec<std::path::PathBuf> {
+    let mut paths: Vec<_> = forge_token_path(&forge_config.name).into_iter().collect();
+    if forge_config.forge_type == ForgeType::GitHub {
+        paths.extend(token_path());
+    }
+    paths
+}
+
+/// Delete the stored token of a forge; returns whether there was one.
+/// Tokens from env vars or a token_command are left alone.
+pub fn logout(forge_config: &ForgeConfig) -> Result<bool, String> {
+    let mut removed = forge_config.token_source == TokenSource::Keyring
+        && crate::keyring::delete(&forge_config.name)?;
+    for path in token_files(forge_config) {
+        match std::fs::remove_file(&path) {
+            Ok(()) => removed = true,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Could not remove {}: {}", path.display(), e)),
+        }
+    }
+    Ok(removed)
+}
+
+/// Move a token file into the OS keyring and delete the file; returns
+/// whether there was a file to move
+pub fn migrate(forge_config: &ForgeConfig) -> Result<bool, String> {
+    if forge_config.token_source != TokenSource::Keyring {
+        return Err(format!(
+            "Set token_source = \"keyring\" for forge '{}' first",
+            forge_config.name
+        ));
+    }
+    let token = load_forge_stored_token(&forge_config.name).or_else(|| {
+        (forge_config.forge_type == ForgeType::GitHub)
+            .then(load_stored_token)
+            .flatten()
+    });
+    let Some(token) = token else {
+        return Ok(false);
+    };
+
+    crate::keyring::store(&forge_config.name, &token)?;
+    for path in token_files(forge_config) {
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Could not remove {}: {}", path.display(), e)),
+        }
+    }
+    Ok(true)
+}
+
+/// Where `load_forge_token` would take the token from, without prompting
+pub fn token_source(forge_config: &ForgeConfig) -> Option<String> {
+    if let Some(env_var) = &forge_config.token_env {
+        if std::env::var(env_var).is_ok_and(|t| !t.is_empty()) {
+            return Some(format!("${}", env_var));
+        }
+    }
+    if let source @ (TokenSource::Gh | TokenSource::Glab) = forge_config.token_source {
+        return cli_token(source, &forge_config.host)
+            .map(|_| format!("{} login", cli_name(source)));
+    }
+    if forge_config.token_source == TokenSource::Keyring
+        && matches!(crate::keyring::load(&forge_config.name), Ok(Some(_)))
+    {
+        return Some(crate::keyring::STORE_NAME.to_string());
+    }
+    if load_forge_stored_token(&forge_config.name).is_some() {
+        return forge_token_path(&forge_config.name).map(|p| p.display().to_string());
+    }
+    if forge_config.forge_type == ForgeType::GitHub && load_stored_token().is_some() {
+        return token_path().map(|p| p.display().to_string());
+    }
+    if let Some(cmd) = &forge_config.token_command {
+        if try_cli_token(cmd).is_some() {
+            return Some(format!("`{}`", cmd));
+        }
+    }
+    let source = fallback_cli(forge_config)?;
+    cli_token(source, &forge_config.host).map(|_| format!("{} login", cli_name(source)))
+}
+
 /// Get the legacy config file path: ~/.config/grit/token
 fn token_path() -> Option<std::path::PathBuf> {
     let config_dir = dirs::config_dir()?;
@@ -123,16 +385,66 @@ fn load_stored_token() -> Option<String> {
     }
 }
 
-/// GitHub OAuth device flow
+/// Endpoints and client of an OAuth device authorization flow (RFC 8628)
+#[derive(Debug, PartialEq)]
+struct DeviceFlow {
+    /// Forge name shown in the instructions
+    forge: &'static str,
+    code_url: String,
+    token_url: String,
+    client_id: String,
+    scope: &'static str,
+    /// The forge's proxy and certificates
+    transport: Transport,
+}
+
+/// Device flow for a configured forge. Only GitHub: GitLab's OAuth tokens
+/// expire within hours and want a Bearer header, and Gitea has no device flow.
+fn device_flow(forge_config: &ForgeConfig) -> Result<DeviceFlow, String> {
+    let host = &forge_config.host;
+    let client_id = forge_config.oauth_client_id.clone();
+    match forge_config.forge_type {
+        ForgeType::GitHub => Ok(DeviceFlow {
+            forge: "GitHub",
+            code_url: format!("https://{}/login/device/code", host),
+            token_url: format!("https://{}/login/oauth/access_token", host),
+            // grit's OAuth app only exists on github.com, not on Enterprise hosts
+            client_id: match client_id {
+                Some(id) => id,
+                None if host == "github.com" => GITHUB_CLIENT_ID.to_string(),
+                None => {
+                    return Err(format!(
+                        "Set oauth_client_id for forge '{}' to an OAuth app on {}",
+                        forge_config.name, host
+                    ))
+                }
+            },
+            scope: "repo",
+            transport: forge_config.transport.clone(),
+        }),
+        ForgeType::GitLab => Err(format!(
+            "GitLab logins go through glab or a personal access token. Run `glab auth login`, or create a token under Preferences > Access tokens on {} (scope: api) and set token_env or token_command.",
+            host
+        )),
+        ForgeType::Gitea => Err(format!(
+            "Gitea has no device flow login. Create a token under Settings > Applications on {} and set token_env or token_command.",
+            host
+        )),
+    }
+}
+
+/// OAuth device flow
 /// Requires a registered OAuth App client_id (not secret)
-async fn device_flow_auth(client_id: &str) -> Result<String, String> {
-    let client = reqwest::Client::new();
+async fn device_flow_auth(flow: &DeviceFlow) -> Result<String, String> {
+    let client = crate::http::client(crate::http::DEFAULT_TIMEOUT, &flow.transport)
+        .map_err(|e| e.to_string())?;
+    let client_id = flow.client_id.as_str();
 
     // Step 1: Request device code
     let resp = client
-        .post("https://github.com/login/device/code")
+        .post(&flow.code_url)
         .header("Accept", "application/json")
-        .form(&[("client_id", client_id), ("scope", "repo")])
+        .form(&[("client_id", client_id), ("scope", flow.scope)])
         .send()
         .await
         .map_err(|e| e.to_string())?;
@@ -155,7 +467,7 @@ async fn device_flow_auth(client_id: &str) -> Result<String, String> {
 
     // Step 2: Show instructions to user
     println!();
-    println!("  To authenticate grit with GitHub:");
+    println!("  To authenticate grit with {}:", flow.forge);
     println!("  1. Open: {}", verification_uri);
     println!("  2. Enter code: {}", user_code);
     println!();
@@ -167,7 +479,7 @@ async fn device_flow_auth(client_id: &str) -> Result<String, String> {
         tokio::time::sleep(Duration::from_secs(interval)).await;
 
         let resp = client
-            .post("https://github.com/login/oauth/access_token")
+            .post(&flow.token_url)
             .header("Accept", "application/json")
             .form(&[
                 ("client_id", client_id),
@@ -216,3 +528,70 @@ async fn device_flow_auth(client_id: &str) -> Result<String, String> {
 // It is NOT secret - OAuth Apps use client_id publicly for device flow.
 // Users should register their own app or this can be updated with an official one.
 const GITHUB_CLIENT_ID: &str = "Ov23liYMRxFDN38Slfzr";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forge(forge_type: ForgeType, host: &str, client_id: Option<&str>) -> ForgeConfig {
+        ForgeConfig {
+            name: "test".to_string(),
+            forge_type,
+            host: host.to_string(),
+            token_env: None,
+            token_command: None,
+            default_repo: None,
+            oauth_client_id: client_id.map(str::to_string),
+            token_source: TokenSource::default(),
+            timeout: None,
+            page_size: None,
+            endpoint: Default::default(),
+            transport: Default::default(),
+            merge: Default::default(),
+            repos: Default::default(),
+        }
+    }
+
+    #[test]
+    fn github_flow_defaults_to_grit_client() {
+        let flow = device_flow(&forge(ForgeType::GitHub, "github.com", None)).unwrap();
+        assert_eq!(flow.code_url, "https://github.com/login/device/code");
+        assert_eq!(flow.client_id, GITHUB_CLIENT_ID);
+
+        assert!(device_flow(&forge(ForgeType::GitHub, "ghe.example.com", None)).is_err());
+    }
+
+    #[test]
+    fn gitlab_has_no_device_flow() {
+        assert!(device_flow(&forge(ForgeType::GitLab, "gitlab.com", Some("abc"))).is_err());
+    }
+
+    #[test]
+    fn yaml_host_value_reads_gh_hosts() {
+        let yaml = "github.com:\n    users:\n        octo:\n            oauth_token: nested\n    oauth_token: gho_abc\n    user: octo\nghe.example.com:\n    oauth_token: \"ghe_tok\"\n";
+        assert_eq!(
+            yaml_host_value(yaml, "github.com", "oauth_token"),
+            Some("gho_abc".to_string())
+        );
+        assert_eq!(
+            yaml_host_value(yaml, "ghe.example.com", "oauth_token"),
+            Some("ghe_tok".to_string())
+        );
+        assert_eq!(yaml_host_value(yaml, "gitlab.com", "oauth_token"), None);
+    }
+
+    #[test]
+    fn yaml_host_value_reads_glab_config() {
+        let yaml = "git_protocol: ssh\nhosts:\n    gitlab.com:\n        api_protocol: https\n        token: glpat-123\n    gitlab.company.com:\n        token: \"\"\n";
+        assert_eq!(
+            yaml_host_value(yaml, "gitlab.com", "token"),
+            Some("glpat-123".to_string())
+        );
+        assert_eq!(yaml_host_value(yaml, "gitlab.company.com", "token"), None);
+    }
+
+    #[test]
+    fn gitea_has_no_device_flow() {
+        assert!(device_flow(&forge(ForgeType::Gitea, "codeberg.org", Some("abc"))).is_err());
+    }
+}
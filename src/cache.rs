@@ -1,13 +1,62 @@
 use serde::{de::DeserializeOwned, Serialize};
 use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::SystemTime;
+
+/// Set by `isolate`; replaces the user's cache for the rest of the run
+static ISOLATED: OnceLock<PathBuf> = OnceLock::new();
 
 /// XDG-compatible cache directory: ~/.cache/grit/ (Linux) or ~/Library/Caches/grit/ (macOS)
 fn cache_dir() -> Option<PathBuf> {
-    let dir = dirs::cache_dir()?.join("grit");
+    let dir = match ISOLATED.get() {
+        Some(dir) => dir.clone(),
+        None => default_dir()?,
+    };
     std::fs::create_dir_all(&dir).ok()?;
     Some(dir)
 }
 
+#[cfg(not(test))]
+fn default_dir() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("grit"))
+}
+
+/// Tests start from an empty cache of their own, never the user's. Nothing
+/// runs once the last test is done, so each run clears out the caches
+/// earlier runs left behind instead.
+#[cfg(test)]
+fn default_dir() -> Option<PathBuf> {
+    static DIR: OnceLock<PathBuf> = OnceLock::new();
+    let dir = DIR.get_or_init(|| {
+        let root = std::env::temp_dir().join("grit-test-cache");
+        let stale = SystemTime::now() - std::time::Duration::from_secs(60 * 60);
+        for entry in std::fs::read_dir(&root).into_iter().flatten().flatten() {
+            let modified = entry.metadata().and_then(|m| m.modified());
+            if modified.is_ok_and(|m| m < stale) {
+                let _ = std::fs::remove_dir_all(entry.path());
+            }
+        }
+        let dir = root.join(std::process::id().to_string());
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    });
+    Some(dir.clone())
+}
+
+/// Start this run from an empty cache, local state included, and leave the
+/// user's alone; `discard_isolated` removes it at exit
+pub fn isolate() {
+    let dir = std::env::temp_dir().join(format!("grit-isolated-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let _ = ISOLATED.set(dir);
+}
+
+pub fn discard_isolated() {
+    if let Some(dir) = ISOLATED.get() {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
 fn cache_path(key: &str) -> Option<PathBuf> {
     Some(cache_dir()?.join(format!("{}.json", key)))
 }
@@ -19,15 +68,43 @@ pub fn read<T: DeserializeOwned>(key: &str) -> Option<T> {
     serde_json::from_str(&data).ok()
 }
 
+/// When `key` was last written, if it's cached at all
+pub fn modified(key: &str) -> Option<SystemTime> {
+    std::fs::metadata(cache_path(key)?).ok()?.modified().ok()
+}
+
 /// Write a value to cache. Silently ignores errors.
 pub fn write<T: Serialize>(key: &str, value: &T) {
+    let _ = try_write(key, value);
+}
+
+/// Write a value to cache, reporting what went wrong
+pub fn try_write<T: Serialize>(key: &str, value: &T) -> Result<(), String> {
+    let path = cache_path(key).ok_or("No cache directory")?;
+    let data = serde_json::to_string(value).map_err(|e| e.to_string())?;
+    std::fs::write(&path, data).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Drop a cached value, if there is one
+pub fn remove(key: &str) {
     if let Some(path) = cache_path(key) {
-        if let Ok(data) = serde_json::to_string(value) {
-            let _ = std::fs::write(path, data);
-        }
+        let _ = std::fs::remove_file(path);
     }
 }
 
+/// Keys of every cached value
+pub fn keys() -> Vec<String> {
+    let Some(entries) = cache_dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_suffix(".json").map(str::to_string)
+        })
+        .collect()
+}
+
 /// Sanitize owner/repo into a safe cache key segment
 pub fn repo_key(owner: &str, repo: &str) -> String {
     format!("{}_{}", owner.replace('/', "_"), repo.replace('/', "_"))
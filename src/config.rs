@@ -1,6 +1,10 @@
 use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
+use crate::app::HomeSection;
+use crate::types::MergeMethod;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ForgeType {
@@ -9,6 +13,32 @@ pub enum ForgeType {
     Gitea,
 }
 
+impl ForgeType {
+    /// Most items the forge's API returns per page; Gitea's default
+    /// MAX_RESPONSE_ITEMS is 50
+    pub fn max_page_size(self) -> usize {
+        match self {
+            ForgeType::GitHub | ForgeType::GitLab => 100,
+            ForgeType::Gitea => 50,
+        }
+    }
+}
+
+/// Where tokens from the device flow or a token_command are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenSource {
+    /// Plain file under ~/.config/grit/tokens
+    #[default]
+    File,
+    /// OS secret store (macOS Keychain, Secret Service); not on Windows yet
+    Keyring,
+    /// The GitHub CLI's login (`gh auth login`); grit stores nothing itself
+    Gh,
+    /// The GitLab CLI's login (`glab auth login`); grit stores nothing itself
+    Glab,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ForgeConfig {
     pub name: String,
@@ -17,34 +47,318 @@ pub struct ForgeConfig {
     pub host: String,
     pub token_env: Option<String>,
     pub token_command: Option<String>,
+    /// "owner/repo" to open when starting on the repos screen
+    pub default_repo: Option<String>,
+    /// OAuth application id for `grit auth login` on GitHub Enterprise; github.com
+    /// falls back to grit's own
+    pub oauth_client_id: Option<String>,
+    #[serde(default)]
+    pub token_source: TokenSource,
+    /// Seconds before a request to this forge gives up
+    pub timeout: Option<u64>,
+    /// Items per page of the repo, PR, issue, commit and run lists, and of
+    /// Home's sections
+    pub page_size: Option<usize>,
+    /// Scheme, port and path of a self-managed instance (GitLab)
+    #[serde(flatten)]
+    pub endpoint: Endpoint,
+    /// Proxy and certificates every request to this forge goes through
+    #[serde(flatten)]
+    pub transport: Transport,
+    /// Merge popup defaults for every repo on this forge
+    #[serde(flatten)]
+    pub merge: MergeConfig,
+    /// Merge popup defaults for single repos, keyed by "owner/repo"
+    #[serde(default)]
+    pub repos: HashMap<String, MergeConfig>,
+}
+
+/// Where a self-managed instance serves from, beyond its host
+#[derive(Debug, Clone, Deserialize, Default, PartialEq, Eq)]
+pub struct Endpoint {
+    /// "http" for internal instances; https when unset
+    pub scheme: Option<String>,
+    pub port: Option<u16>,
+    /// Relative URL the instance runs under, like "/gitlab"
+    pub base_path: Option<String>,
+}
+
+/// How requests reach a forge from behind a corporate proxy, or one with a
+/// certificate the system doesn't trust
+#[derive(Debug, Clone, Deserialize, Default, PartialEq, Eq)]
+pub struct Transport {
+    /// Proxy URL like "http://proxy.corp:3128" for every request; otherwise
+    /// `HTTPS_PROXY` and friends apply
+    pub proxy: Option<String>,
+    /// PEM file of extra root certificates to trust
+    pub ca_bundle: Option<PathBuf>,
+    /// Accept any certificate; for self-signed test instances only
+    #[serde(default)]
+    pub insecure_skip_verify: bool,
+}
+
+/// How the merge popup starts out
+#[derive(Debug, Clone, Copy, Deserialize, Default, PartialEq, Eq)]
+pub struct MergeConfig {
+    /// Method preselected in the popup
+    pub default_merge_method: Option<MergeMethod>,
+    /// Whether "delete branch after merge" starts checked
+    pub delete_branch_after_merge: Option<bool>,
+}
+
+impl ForgeConfig {
+    pub fn timeout(&self) -> std::time::Duration {
+        self.timeout
+            .map(std::time::Duration::from_secs)
+            .unwrap_or(crate::http::DEFAULT_TIMEOUT)
+    }
+
+    /// `page_size`, kept within what the forge's API allows
+    pub fn page_size(&self) -> usize {
+        self.page_size
+            .unwrap_or(crate::forge::DEFAULT_PAGE_SIZE)
+            .clamp(1, self.forge_type.max_page_size())
+    }
+
+    /// Root of the instance's web UI and API, like
+    /// "http://git.internal:8080/gitlab". `host` may carry the path itself
+    pub fn base_url(&self) -> String {
+        let endpoint = &self.endpoint;
+        let (host, path) = match self.host.trim_end_matches('/').split_once('/') {
+            Some((host, path)) => (host, format!("/{}", path)),
+            None => (self.host.trim_end_matches('/'), String::new()),
+        };
+        let mut url = format!(
+            "{}://{}",
+            endpoint.scheme.as_deref().unwrap_or("https"),
+            host
+        );
+        if let Some(port) = endpoint.port {
+            url.push_str(&format!(":{}", port));
+        }
+        url.push_str(&path);
+        if let Some(base) = endpoint.base_path.as_deref().map(|p| p.trim_matches('/')) {
+            if !base.is_empty() {
+                url.push('/');
+                url.push_str(base);
+            }
+        }
+        url
+    }
+
+    /// `default_repo` split into owner and name; GitLab owners may contain slashes
+    pub fn default_repo(&self) -> Option<(String, String)> {
+        crate::types::split_repo_path(self.default_repo.as_deref()?)
+    }
+
+    /// Merge defaults for `owner/repo`: the repo's own settings, falling back
+    /// to the forge's
+    pub fn merge_config(&self, owner: &str, repo: &str) -> MergeConfig {
+        let forge = self.merge;
+        match self.repos.get(&format!("{}/{}", owner, repo)) {
+            Some(own) => MergeConfig {
+                default_merge_method: own.default_merge_method.or(forge.default_merge_method),
+                delete_branch_after_merge: own
+                    .delete_branch_after_merge
+                    .or(forge.delete_branch_after_merge),
+            },
+            None => forge,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
-#[allow(dead_code)]
 pub struct GeneralConfig {
     pub default_forge: Option<String>,
 }
 
+/// Screen grit opens on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StartScreen {
+    /// Review dashboard
+    #[default]
+    Home,
+    /// Repository browser, or the forge's `default_repo` when set
+    Repos,
+    /// No notifications view yet, so this opens Home
+    Notifications,
+    /// Wherever the previous session quit
+    Last,
+}
+
+/// An external tracker reference: text matching `pattern` links to `url`,
+/// where `$0` is the whole match and `$1`.. are capture groups
+#[derive(Debug, Clone, Deserialize)]
+pub struct LinkPattern {
+    pub pattern: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct UiConfig {
+    /// Extra usernames to treat as bots (accounts ending in `[bot]` always are)
+    #[serde(default)]
+    pub bots: Vec<String>,
+    #[serde(default)]
+    pub start_screen: StartScreen,
+    /// Review requests whose details are fetched in the background after Home
+    /// loads; 0 turns prefetching off, including around the PR list selection
+    pub prefetch: Option<usize>,
+    /// Where `g` on the repo list clones to; `~/` expands to the home directory
+    pub clone_dir: Option<String>,
+    /// Where `D` on the Actions tab saves artifact zips; `~/` expands too
+    pub download_dir: Option<String>,
+    /// Open the repo behind the cwd's `origin` remote on startup (default true)
+    pub auto_repo: Option<bool>,
+    /// Print what was reviewed, commented and merged when grit exits
+    #[serde(default)]
+    pub session_summary: bool,
+    /// Save the screen, selection and open PR on quit and offer them back on
+    /// the next start
+    #[serde(default)]
+    pub restore_session: bool,
+    /// Desktop notification and header badge when a Home refresh finds new
+    /// review requests or failed checks on your PRs
+    #[serde(default)]
+    pub notifications: bool,
+    /// Click to select rows and tabs, wheel to scroll
+    #[serde(default)]
+    pub mouse: bool,
+    /// Also ask the forge's commit search when finding commits across repos
+    #[serde(default)]
+    pub remote_commit_search: bool,
+    /// Tracker references highlighted in titles and bodies and offered by `o`
+    #[serde(default)]
+    pub links: Vec<LinkPattern>,
+}
+
+/// Which sections Home shows, top to bottom, and how tall
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct HomeConfig {
+    /// Empty shows every section in the usual order
+    #[serde(default)]
+    pub sections: Vec<HomeSection>,
+    /// Height of each of `sections` in percent, in the same order; shared
+    /// evenly when unset
+    #[serde(default)]
+    pub sizes: Vec<u16>,
+    /// Sections that start collapsed to their title (`za` toggles)
+    #[serde(default)]
+    pub collapsed: Vec<HomeSection>,
+}
+
+/// What goes into the log file
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct LogConfig {
+    /// Level for modules without their own entry (default "warn")
+    pub level: Option<String>,
+    /// Levels by module path, e.g. "grit::github" = "debug"
+    #[serde(default)]
+    pub modules: BTreeMap<String, String>,
+}
+
+impl LogConfig {
+    /// The levels as an `EnvFilter` directive, e.g. "warn,grit::github=debug"
+    pub fn filter(&self) -> String {
+        let mut directives = vec![self.level.clone().unwrap_or_else(|| "warn".to_string())];
+        directives.extend(
+            self.modules
+                .iter()
+                .map(|(module, level)| format!("{}={}", module, level)),
+        );
+        directives.join(",")
+    }
+}
+
+/// A configured directory with `~/` expanded, the current directory when unset
+fn expand_dir(dir: Option<&str>) -> PathBuf {
+    match dir {
+        Some(dir) => match (dir.strip_prefix("~/"), dirs::home_dir()) {
+            (Some(rest), Some(home)) => home.join(rest),
+            _ => PathBuf::from(dir),
+        },
+        None => PathBuf::from("."),
+    }
+}
+
+impl UiConfig {
+    /// Directory clones land in, the current directory when unset
+    pub fn clone_dir(&self) -> PathBuf {
+        expand_dir(self.clone_dir.as_deref())
+    }
+
+    /// Directory artifacts land in, the current directory when unset
+    pub fn download_dir(&self) -> PathBuf {
+        expand_dir(self.download_dir.as_deref())
+    }
+
+    pub fn auto_repo(&self) -> bool {
+        self.auto_repo.unwrap_or(true)
+    }
+
+    pub fn prefetch_limit(&self) -> usize {
+        self.prefetch.unwrap_or(3)
+    }
+
+    pub fn is_bot(&self, login: &str) -> bool {
+        login.ends_with("[bot]") || self.bots.iter().any(|b| b.eq_ignore_ascii_case(login))
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     #[serde(default)]
-    #[allow(dead_code)]
     pub general: GeneralConfig,
+    /// Forge to start on ahead of the one the checkout's remote points at;
+    /// meant for a project's `.grit.toml`
+    pub forge: Option<String>,
+    /// "owner/repo" standing in for the `default_repo` of `forge` (of every
+    /// forge when unset); meant for a project's `.grit.toml` too
+    pub default_repo: Option<String>,
+    #[serde(default)]
+    pub ui: UiConfig,
+    #[serde(default)]
+    pub home: HomeConfig,
+    #[serde(default)]
+    pub log: LogConfig,
     #[serde(default)]
     pub forges: Vec<ForgeConfig>,
+    /// Canned replies by name, offered when writing a comment or review
+    #[serde(default)]
+    pub snippets: BTreeMap<String, String>,
+    /// Named searches, run in Home's Saved section
+    #[serde(default)]
+    pub searches: BTreeMap<String, String>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             general: GeneralConfig::default(),
+            forge: None,
+            default_repo: None,
+            ui: UiConfig::default(),
+            home: HomeConfig::default(),
+            log: LogConfig::default(),
             forges: vec![ForgeConfig {
                 name: "github".to_string(),
                 forge_type: ForgeType::GitHub,
                 host: "github.com".to_string(),
                 token_env: Some("GITHUB_TOKEN".to_string()),
                 token_command: Some("gh auth token".to_string()),
+                default_repo: None,
+                oauth_client_id: None,
+                token_source: TokenSource::default(),
+                timeout: None,
+                page_size: None,
+                endpoint: Default::default(),
+                transport: Default::default(),
+                merge: Default::default(),
+                repos: Default::default(),
             }],
+            snippets: BTreeMap::new(),
+            searches: BTreeMap::new(),
         }
     }
 }
@@ -54,6 +368,76 @@ pub fn config_path() -> Option<PathBuf> {
     Some(config_dir.join("grit").join("config.toml"))
 }
 
+/// Name of the per-project config looked for from the cwd upwards
+pub const PROJECT_FILE: &str = ".grit.toml";
+
+/// The nearest `.grit.toml` in the cwd or one of its parents
+pub fn project_config_path() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    cwd.ancestors()
+        .map(|dir| dir.join(PROJECT_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Top-level keys a project's `.grit.toml` may set. Forges and their
+/// tokens, hosts and transport stay with the user's own config: a cloned
+/// repo must not pick the command that prints a token or the server it's
+/// sent to.
+pub const PROJECT_KEYS: &[&str] = &[
+    "forge",
+    "default_repo",
+    "general",
+    "ui",
+    "home",
+    "snippets",
+    "searches",
+];
+
+/// A project layer narrowed to `PROJECT_KEYS`, with the keys it dropped
+pub fn project_layer(mut table: toml::Table) -> (toml::Table, Vec<String>) {
+    let dropped: Vec<String> = table
+        .keys()
+        .filter(|key| !PROJECT_KEYS.contains(&key.as_str()))
+        .cloned()
+        .collect();
+    for key in &dropped {
+        table.remove(key);
+    }
+    (table, dropped)
+}
+
+/// The TOML table in `path`, `None` when there's no such file
+pub fn read_table(path: &std::path::Path) -> Result<Option<toml::Table>, String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(format!(
+                "could not read config file {}: {}",
+                path.display(),
+                e
+            ))
+        }
+    };
+    content
+        .parse::<toml::Table>()
+        .map(Some)
+        .map_err(|e| format!("failed to parse {}: {}", path.display(), e))
+}
+
+/// Lay `over` on top of `base`: tables merge key by key, anything else
+/// (arrays included) is replaced
+fn merge_tables(base: &mut toml::Table, over: toml::Table) {
+    for (key, value) in over {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(over)) => merge_tables(base, over),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
 impl Config {
     /// Returns a documented example config file as a static string.
     pub fn example_toml() -> &'static str {
@@ -63,14 +447,101 @@ impl Config {
 # grit auto-detects which forge to use by matching your git remote's
 # hostname against the configured forges below. If no match is found,
 # the first forge in the list is used as the default.
+#
+# A .grit.toml in a project (the nearest one from the cwd upwards) is merged on
+# top of this file, table by table, so a client's checkout can start on its own
+# forge and repo with its own [ui] settings. It may set [general], [ui], [home],
+# [snippets] and [searches], but never [[forges]] or [log]: tokens, hosts and
+# proxies only come from this file. It also takes two top-level keys:
+# forge = "work-gitlab"           # forge to start on, ahead of the remote's
+# default_repo = "group/project"  # that forge's default_repo
 
 [general]
 # Optional: name of the default forge to use when auto-detection fails
 # default_forge = "github"
 
+[ui]
+# Usernames to style as bots and hide with `b` in PR conversations.
+# Accounts ending in [bot] (e.g. dependabot[bot]) are always treated as bots.
+# bots = ["renovate", "codecov"]
+# Screen to open on: home | repos | notifications | last
+# ("repos" opens the forge's default_repo directly when one is set;
+#  "notifications" opens home until a notifications view exists)
+# start_screen = "home"
+# Review requests to prefetch in the background after Home loads, so the
+# first few open instantly. Fetched one at a time. The PRs around the PR list
+# selection are prefetched too; 0 disables both.
+# prefetch = 3
+# Directory `g` on the repo list clones into (default: the current directory)
+# clone_dir = "~/src"
+# Directory `D` on the Actions tab downloads artifact zips into (default: the
+# current directory)
+# download_dir = "~/Downloads"
+# Open straight into the repo of the git checkout grit starts in, when its
+# origin is on the selected forge. Also turned off per run with --no-auto.
+# auto_repo = true
+# Print a one-line recap (PRs reviewed, comments, merges, time) on exit
+# session_summary = false
+# Remember the screen, tab, selection and open PR on quit, and offer to go
+# back there on the next start
+# restore_session = false
+# When a Home refresh finds a new review request or checks failing on one of
+# your PRs, show a desktop notification and a badge in the header (! dismisses)
+# notifications = false
+# Mouse: click rows and repo tabs to select (click again to open), wheel scrolls.
+# Capturing the mouse turns off the terminal's own text selection (most
+# terminals still select with Shift held).
+# mouse = false
+# Finding a commit across repos (S) checks each repo's recent commits; this also
+# asks the forge's own commit search (GitHub only), which reaches older history.
+# remote_commit_search = false
+# External tracker references in PR and issue titles and bodies are
+# highlighted and offered by `o` next to the forge page. $0 is the whole
+# match, $1.. its capture groups.
+# [[ui.links]]
+# pattern = "JIRA-\\d+"
+# url = "https://jira.example.com/browse/$0"
+
+[home]
+# Home's sections top to bottom, out of review_requests | my_prs | assigned |
+# mentions | saved | recent (saved only shows with [searches] set). Default: all.
+# sections = ["review_requests", "my_prs", "assigned"]
+# Height of each section in percent, in the order of `sections` (default: an
+# even split once sections is set)
+# sizes = [50, 25, 25]
+# Sections that start collapsed to their title; za collapses or expands the
+# selected one
+# collapsed = ["recent"]
+
+[log]
+# grit logs to grit.log in its data directory (~/.local/share/grit on Linux),
+# rotated at 1 MiB with three old copies kept. F12 shows the tail in the app.
+# Level for everything: error | warn | info | debug | trace (RUST_LOG overrides)
+# level = "warn"
+# Per-module levels, e.g. to see each API request and retry
+# [log.modules]
+# "grit::http" = "debug"
+
+# Canned replies. With any set, writing a comment or review first asks which
+# one to start $EDITOR from (or a blank buffer).
+# [snippets]
+# "LGTM with nits" = "Looks good to me! A few optional nits below."
+# "Please add tests" = "Thanks! Could you add tests covering this change?"
+
+# Saved searches, shown in a Saved section on Home; Q picks which one runs.
+# GitHub takes its search syntax as is. GitLab and Gitea understand is:pr
+# is:issue is:open is:closed author: assignee: review-requested: label: org:
+# (or group:) and free text; GitLab also is:merged, Gitea also mentions:, and
+# Gitea filters people as @me only. Items are open unless the query says so.
+# [searches]
+# "team PRs" = "is:pr team-review-requested:myorg/backend"
+# "my bugs" = "is:issue assignee:@me label:bug"
+
 # Each [[forges]] block defines a forge instance.
 # Required fields: name, type, host
-# Optional fields: token_env, token_command
+# Optional fields: token_env, token_command, default_repo, oauth_client_id,
+# token_source, timeout, page_size, default_merge_method, delete_branch_after_merge,
+# scheme, port, base_path, proxy, ca_bundle, insecure_skip_verify
 
 [[forges]]
 name = "github"
@@ -78,6 +549,21 @@ type = "github"                   # github | gitlab | gitea
 host = "github.com"
 token_env = "GITHUB_TOKEN"        # env var to read token from
 token_command = "gh auth token"   # fallback: run this command to get token
+# token_source = "keyring"        # keep stored tokens in the OS keychain instead of a file
+                                  # ("gh" / "glab": use that CLI's login, store nothing)
+# default_repo = "owner/repo"     # opened on startup with start_screen = "repos"
+# timeout = 30                    # seconds before a request gives up
+# page_size = 100                 # items per list page (default 50; GitHub and GitLab allow 100, Gitea 50)
+# default_merge_method = "squash" # merge popup preselection: merge | squash | rebase
+# delete_branch_after_merge = true  # start the popup's delete-branch box checked
+# proxy = "http://proxy.corp:3128"  # otherwise HTTPS_PROXY / NO_PROXY apply
+# ca_bundle = "/etc/ssl/corp-ca.pem"  # extra root certificates (PEM), e.g. for a MITM proxy
+# insecure_skip_verify = false    # accept any certificate (self-signed test instances only)
+
+# Per-repo merge defaults override the forge's; put them after its [[forges]] block
+# [forges.repos."owner/repo"]
+# default_merge_method = "rebase"
+# delete_branch_after_merge = false
 
 # Example: Add a GitLab instance
 # [[forges]]
@@ -86,6 +572,9 @@ token_command = "gh auth token"   # fallback: run this command to get token
 # host = "gitlab.company.com"
 # token_env = "GITLAB_TOKEN"
 # token_command = "glab auth token"
+# scheme = "http"                 # internal instances without TLS
+# port = 8080
+# base_path = "/gitlab"           # instance served under a relative URL
 
 # Example: Add a Gitea/Forgejo instance
 # [[forges]]
@@ -96,49 +585,97 @@ token_command = "gh auth token"   # fallback: run this command to get token
 "#
     }
 
+    /// The global config with the project's `.grit.toml`, if any, merged
+    /// on top
     pub fn load() -> Self {
-        let Some(path) = config_path() else {
-            return Config::default();
+        // An unreadable or invalid file is skipped with a warning
+        let read = |path: PathBuf| match read_table(&path) {
+            Ok(table) => Some((table?, path)),
+            Err(e) => {
+                eprintln!("grit: warning: {}", e);
+                None
+            }
         };
-
-        let content = match std::fs::read_to_string(&path) {
-            Ok(c) => c,
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                return Config::default();
+        let global = config_path().and_then(read);
+        let project = project_config_path().and_then(read).map(|(table, path)| {
+            let (table, dropped) = project_layer(table);
+            if !dropped.is_empty() {
+                eprintln!(
+                    "grit: warning: ignoring {} in {}; only the global config may set them",
+                    dropped.join(", "),
+                    path.display()
+                );
+            }
+            (table, path)
+        });
+        let has_forges = global
+            .iter()
+            .chain(&project)
+            .any(|(table, _)| table.contains_key("forges"));
+        if let Some((_, path)) = &global {
+            if !has_forges {
+                eprintln!(
+                    "grit: warning: config file {} has no [[forges]] entries, using defaults",
+                    path.display()
+                );
             }
+        }
+
+        let (layers, paths): (Vec<_>, Vec<_>) = global.into_iter().chain(project).unzip();
+        match Config::from_layers(layers) {
+            Ok(config) => config,
             Err(e) => {
+                let paths: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
                 eprintln!(
-                    "grit: warning: could not read config file {}: {}",
-                    path.display(),
+                    "grit: warning: failed to parse {}: {}",
+                    paths.join(" + "),
                     e
                 );
-                return Config::default();
+                Config::default()
             }
-        };
+        }
+    }
 
-        match toml::from_str::<Config>(&content) {
-            Ok(config) => {
-                if config.forges.is_empty() {
-                    eprintln!(
-                        "grit: warning: config file {} has no [[forges]] entries, using defaults",
-                        path.display()
-                    );
-                    Config::default()
-                } else {
-                    config
+    /// Config from tables merged in order, later ones overriding; without
+    /// any `[[forges]]` the default forges are used
+    pub fn from_layers(layers: Vec<toml::Table>) -> Result<Self, toml::de::Error> {
+        let mut merged = toml::Table::new();
+        for layer in layers {
+            merge_tables(&mut merged, layer);
+        }
+        let mut config: Config = toml::Value::Table(merged).try_into()?;
+        if config.forges.is_empty() {
+            config.forges = Config::default().forges;
+        }
+        if let Some(repo) = &config.default_repo {
+            for forge in &mut config.forges {
+                if config.forge.as_ref().is_none_or(|name| *name == forge.name) {
+                    forge.default_repo = Some(repo.clone());
                 }
             }
-            Err(e) => {
-                eprintln!("grit: warning: failed to parse {}: {}", path.display(), e);
-                Config::default()
-            }
         }
+        Ok(config)
     }
 }
 
 /// Detect which forge to use based on the current git remote origin.
 /// Returns the matching ForgeConfig, or None if no match.
 pub fn detect_forge(config: &Config) -> Option<&ForgeConfig> {
+    let host = extract_host(&origin_url()?)?;
+    config.forges.iter().find(|f| f.host == host)
+}
+
+/// The owner/repo the cwd's origin remote points at, if it's on `forge`
+pub fn detect_repo(forge: &ForgeConfig) -> Option<(String, String)> {
+    let url = origin_url()?;
+    if extract_host(&url)? != forge.host {
+        return None;
+    }
+    extract_repo(&url)
+}
+
+/// URL of the `origin` remote of the git checkout around the cwd
+fn origin_url() -> Option<String> {
     let output = std::process::Command::new("git")
         .args(["remote", "get-url", "origin"])
         .output()
@@ -148,10 +685,18 @@ pub fn detect_forge(config: &Config) -> Option<&ForgeConfig> {
         return None;
     }
 
-    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    let host = extract_host(&url)?;
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
 
-    config.forges.iter().find(|f| f.host == host)
+/// owner/repo from a remote URL in any form `extract_host` understands;
+/// nested GitLab groups stay in the owner
+fn extract_repo(url: &str) -> Option<(String, String)> {
+    let path = match url.strip_prefix("git@") {
+        Some(rest) => rest.split_once(':')?.1,
+        None => url.split("://").nth(1)?.split_once('/')?.1,
+    };
+    let path = path.trim_end_matches('/').trim_end_matches(".git");
+    crate::types::split_repo_path(path)
 }
 
 /// Extract hostname from SSH (git@host:...) or HTTPS (https://host/...) URLs
@@ -200,12 +745,71 @@ name = "work-gitlab"
 type = "gitlab"
 host = "gitlab.company.com"
 token_env = "GITLAB_TOKEN"
+token_source = "keyring"
 "#;
         let config: Config = toml::from_str(toml_str).unwrap();
         assert_eq!(config.forges.len(), 2);
         assert_eq!(config.forges[0].forge_type, ForgeType::GitHub);
         assert_eq!(config.forges[1].forge_type, ForgeType::GitLab);
         assert_eq!(config.forges[1].host, "gitlab.company.com");
+        assert_eq!(config.forges[0].token_source, TokenSource::File);
+        assert_eq!(config.forges[1].token_source, TokenSource::Keyring);
+    }
+
+    #[test]
+    fn base_url_takes_scheme_port_and_path() {
+        let toml_str = r#"
+[[forges]]
+name = "internal"
+type = "gitlab"
+host = "git.internal"
+scheme = "http"
+port = 8080
+base_path = "/gitlab/"
+
+[[forges]]
+name = "subpath"
+type = "gitlab"
+host = "example.com/gitlab"
+port = 8443
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.forges[0].base_url(),
+            "http://git.internal:8080/gitlab"
+        );
+        assert_eq!(
+            config.forges[1].base_url(),
+            "https://example.com:8443/gitlab"
+        );
+        assert_eq!(Config::default().forges[0].base_url(), "https://github.com");
+    }
+
+    #[test]
+    fn page_size_is_clamped_to_the_forge_limit() {
+        let toml_str = r#"
+[[forges]]
+name = "github"
+type = "github"
+host = "github.com"
+page_size = 100
+
+[[forges]]
+name = "codeberg"
+type = "gitea"
+host = "codeberg.org"
+page_size = 100
+
+[[forges]]
+name = "gitlab"
+type = "gitlab"
+host = "gitlab.com"
+page_size = 0
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let sizes: Vec<usize> = config.forges.iter().map(|f| f.page_size()).collect();
+        assert_eq!(sizes, vec![100, 50, 1]);
+        assert_eq!(Config::default().forges[0].page_size(), 50);
     }
 
     #[test]
@@ -215,6 +819,129 @@ token_env = "GITLAB_TOKEN"
         assert_eq!(config.forges[0].forge_type, ForgeType::GitHub);
     }
 
+    #[test]
+    fn parse_ui_bots() {
+        let toml_str = r#"
+[ui]
+bots = ["renovate", "Codecov"]
+
+[[forges]]
+name = "github"
+type = "github"
+host = "github.com"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.ui.bots, vec!["renovate", "Codecov"]);
+    }
+
+    #[test]
+    fn log_levels_become_a_filter() {
+        let toml_str = r#"
+[log]
+level = "info"
+
+[log.modules]
+"grit::http" = "debug"
+"octocrab" = "error"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.log.filter(), "info,grit::http=debug,octocrab=error");
+        assert_eq!(Config::default().log.filter(), "warn");
+    }
+
+    #[test]
+    fn parse_snippets() {
+        let toml_str = r#"
+[snippets]
+"Please add tests" = "Could you add tests?"
+"LGTM with nits" = """
+Looks good!
+"""
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let names: Vec<&str> = config.snippets.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["LGTM with nits", "Please add tests"]);
+        assert_eq!(config.snippets["LGTM with nits"], "Looks good!\n");
+    }
+
+    #[test]
+    fn is_bot_suffix_and_configured_list() {
+        let ui = UiConfig {
+            bots: vec!["Renovate".to_string()],
+            ..Default::default()
+        };
+        assert!(ui.is_bot("dependabot[bot]"));
+        assert!(ui.is_bot("renovate"));
+        assert!(!ui.is_bot("octocat"));
+    }
+
+    #[test]
+    fn parse_start_screen_and_default_repo() {
+        let toml_str = r#"
+[ui]
+start_screen = "repos"
+
+[[forges]]
+name = "gitlab"
+type = "gitlab"
+host = "gitlab.com"
+default_repo = "group/sub/project"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.ui.start_screen, StartScreen::Repos);
+        assert_eq!(
+            config.forges[0].default_repo(),
+            Some(("group/sub".to_string(), "project".to_string()))
+        );
+    }
+
+    #[test]
+    fn repo_merge_defaults_override_the_forge() {
+        let toml_str = r#"
+[[forges]]
+name = "github"
+type = "github"
+host = "github.com"
+default_merge_method = "squash"
+delete_branch_after_merge = true
+
+[forges.repos."owner/repo"]
+default_merge_method = "rebase"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let forge = &config.forges[0];
+        let own = forge.merge_config("owner", "repo");
+        assert_eq!(own.default_merge_method, Some(MergeMethod::Rebase));
+        assert_eq!(own.delete_branch_after_merge, Some(true));
+        let other = forge.merge_config("owner", "other");
+        assert_eq!(other.default_merge_method, Some(MergeMethod::Squash));
+    }
+
+    #[test]
+    fn start_screen_defaults_to_home() {
+        assert_eq!(UiConfig::default().start_screen, StartScreen::Home);
+    }
+
+    #[test]
+    fn prefetch_defaults_to_three_and_zero_disables() {
+        assert_eq!(UiConfig::default().prefetch_limit(), 3);
+        let config: Config = toml::from_str("[ui]\nprefetch = 0\n").unwrap();
+        assert_eq!(config.ui.prefetch_limit(), 0);
+    }
+
+    #[test]
+    fn clone_dir_expands_home() {
+        assert_eq!(UiConfig::default().clone_dir(), PathBuf::from("."));
+        let config: Config = toml::from_str("[ui]\nclone_dir = \"~/src\"\n").unwrap();
+        assert_eq!(config.ui.clone_dir(), dirs::home_dir().unwrap().join("src"));
+    }
+
+    #[test]
+    fn example_toml_parses() {
+        let config: Config = toml::from_str(Config::example_toml()).unwrap();
+        assert!(!config.forges.is_empty());
+    }
+
     #[test]
     fn extract_host_ssh() {
         assert_eq!(
@@ -239,6 +966,26 @@ token_env = "GITLAB_TOKEN"
         );
     }
 
+    #[test]
+    fn extract_repo_from_remote_urls() {
+        let repo = Some(("owner".to_string(), "repo".to_string()));
+        assert_eq!(extract_repo("git@github.com:owner/repo.git"), repo);
+        assert_eq!(extract_repo("https://github.com/owner/repo"), repo);
+        assert_eq!(extract_repo("ssh://git@host:2222/owner/repo.git"), repo);
+        assert_eq!(
+            extract_repo("https://gitlab.com/group/sub/project.git"),
+            Some(("group/sub".to_string(), "project".to_string()))
+        );
+        assert_eq!(extract_repo("/srv/git/repo.git"), None);
+    }
+
+    #[test]
+    fn auto_repo_defaults_on() {
+        assert!(UiConfig::default().auto_repo());
+        let config: Config = toml::from_str("[ui]\nauto_repo = false\n").unwrap();
+        assert!(!config.ui.auto_repo());
+    }
+
     #[test]
     fn extract_host_ssh_scheme() {
         assert_eq!(
@@ -260,10 +1007,92 @@ token_env = "GITLAB_TOKEN"
         assert_eq!(extract_host("not-a-url"), None);
     }
 
+    #[test]
+    fn project_layer_overrides_global() {
+        let global: toml::Table = r#"
+[ui]
+mouse = true
+start_screen = "home"
+
+[[forges]]
+name = "github"
+type = "github"
+host = "github.com"
+
+[[forges]]
+name = "work"
+type = "gitlab"
+host = "gitlab.company.com"
+default_repo = "team/old"
+"#
+        .parse()
+        .unwrap();
+        let project: toml::Table = r#"
+forge = "work"
+default_repo = "team/app"
+
+[ui]
+start_screen = "repos"
+"#
+        .parse()
+        .unwrap();
+        let config = Config::from_layers(vec![global, project]).unwrap();
+        assert_eq!(config.forge.as_deref(), Some("work"));
+        assert_eq!(config.ui.start_screen, StartScreen::Repos);
+        // Keys the project doesn't mention keep their global values
+        assert!(config.ui.mouse);
+        assert_eq!(config.forges.len(), 2);
+        assert_eq!(config.forges[0].default_repo, None);
+        assert_eq!(config.forges[1].default_repo.as_deref(), Some("team/app"));
+    }
+
+    #[test]
+    fn project_layer_cannot_touch_forges() {
+        let project: toml::Table = r#"
+forge = "github"
+
+[ui]
+mouse = true
+
+[[forges]]
+name = "github"
+type = "github"
+host = "evil.example.com"
+token_command = "touch /tmp/pwned; echo x"
+proxy = "http://evil.example.com:8080"
+insecure_skip_verify = true
+"#
+        .parse()
+        .unwrap();
+        let (project, dropped) = project_layer(project);
+        assert_eq!(dropped, vec!["forges".to_string()]);
+
+        let config = Config::from_layers(vec![project]).unwrap();
+        assert!(config.ui.mouse);
+        assert_eq!(config.forge.as_deref(), Some("github"));
+        let forge = &config.forges[0];
+        assert_eq!(forge.host, "github.com");
+        assert_eq!(forge.token_command.as_deref(), Some("gh auth token"));
+        assert_eq!(forge.transport, Transport::default());
+    }
+
+    #[test]
+    fn project_layer_alone_keeps_default_forges() {
+        let project: toml::Table = "[ui]\nmouse = true".parse().unwrap();
+        let config = Config::from_layers(vec![project]).unwrap();
+        assert!(config.ui.mouse);
+        assert_eq!(config.forges[0].name, "github");
+    }
+
     #[test]
     fn detect_forge_matches_config() {
         let config = Config {
             general: GeneralConfig::default(),
+            forge: None,
+            default_repo: None,
+            ui: UiConfig::default(),
+            home: HomeConfig::default(),
+            log: LogConfig::default(),
             forges: vec![
                 ForgeConfig {
                     name: "github".to_string(),
@@ -271,6 +1100,15 @@ token_env = "GITLAB_TOKEN"
                     host: "github.com".to_string(),
                     token_env: None,
                     token_command: None,
+                    oauth_client_id: None,
+                    token_source: TokenSource::default(),
+                    timeout: None,
+                    page_size: None,
+                    endpoint: Default::default(),
+                    transport: Default::default(),
+                    merge: Default::default(),
+                    repos: Default::default(),
+                    default_repo: None,
                 },
                 ForgeConfig {
                     name: "gitlab".to_string(),
@@ -278,8 +1116,19 @@ token_env = "GITLAB_TOKEN"
                     host: "gitlab.company.com".to_string(),
                     token_env: None,
                     token_command: None,
+                    oauth_client_id: None,
+                    token_source: TokenSource::default(),
+                    timeout: None,
+                    page_size: None,
+                    endpoint: Default::default(),
+                    transport: Default::default(),
+                    merge: Default::default(),
+                    repos: Default::default(),
+                    default_repo: None,
                 },
             ],
+            snippets: BTreeMap::new(),
+            searches: BTreeMap::new(),
         };
 
         // detect_forge will run `git remote get-url origin` — we can't control that in tests,
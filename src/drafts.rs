@@ -0,0 +1,91 @@
+use crate::action::EditorContext;
+use crate::cache;
+
+/// Cache key suffix of saved drafts
+pub const SUFFIX: &str = "_draft";
+
+/// Where the text written for `ctx` is kept until the forge has it; `None`
+/// for editor sessions that don't post anything
+pub fn key(forge: &str, ctx: &EditorContext) -> Option<String> {
+    let (owner, repo, target) = match ctx {
+        EditorContext::CommentOnPr {
+            owner,
+            repo,
+            number,
+        } => (owner, repo, format!("pr{}", number)),
+        EditorContext::CommentOnIssue {
+            owner,
+            repo,
+            number,
+        } => (owner, repo, format!("issue{}", number)),
+        EditorContext::CommentOnCommit { owner, repo, sha } => {
+            (owner, repo, format!("commit{}", &sha[..7.min(sha.len())]))
+        }
+        EditorContext::ReviewPr {
+            owner,
+            repo,
+            number,
+            ..
+        } => (owner, repo, format!("review{}", number)),
+        EditorContext::NewIssue { owner, repo, .. } => (owner, repo, "newissue".to_string()),
+        EditorContext::LineComment { .. } | EditorContext::EditRepo { .. } => return None,
+    };
+    Some(format!(
+        "{}_{}{}",
+        cache::forge_repo_key(forge, owner, repo),
+        target,
+        SUFFIX
+    ))
+}
+
+/// The saved draft, if there is one worth restoring
+pub fn load(key: &str) -> Option<String> {
+    cache::read::<String>(key).filter(|body| !body.trim().is_empty())
+}
+
+pub fn save(key: &str, body: &str) {
+    cache::write(key, &body);
+}
+
+/// Forget a draft once it's posted (or emptied on purpose)
+pub fn clear(key: &str) {
+    cache::remove(key);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_tell_prs_issues_and_reviews_apart() {
+        let pr = EditorContext::CommentOnPr {
+            owner: "o".to_string(),
+            repo: "r".to_string(),
+            number: 4,
+        };
+        let issue = EditorContext::CommentOnIssue {
+            owner: "o".to_string(),
+            repo: "r".to_string(),
+            number: 4,
+        };
+        let review = EditorContext::ReviewPr {
+            owner: "o".to_string(),
+            repo: "r".to_string(),
+            number: 4,
+            event: crate::types::ReviewEvent::Approve,
+        };
+        let keys: Vec<String> = [pr, issue, review]
+            .iter()
+            .filter_map(|ctx| key("github", ctx))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "github_o_r_pr4_draft",
+                "github_o_r_issue4_draft",
+                "github_o_r_review4_draft"
+            ]
+        );
+        assert!(keys.iter().all(|k| crate::state::is_state_key(k)));
+    }
+}
@@ -1,6 +1,13 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+/// Wait before retrying a rate limit whose reset time the forge didn't say
+const RATE_LIMIT_FALLBACK: Duration = Duration::from_secs(60);
+
+#[derive(Error, Debug, Clone, Serialize, Deserialize)]
 pub enum GritError {
     #[error("API error: {0}")]
     Api(String),
@@ -8,8 +15,146 @@ pub enum GritError {
     #[error("Authentication error: {0}")]
     Auth(String),
 
+    /// 401/403: the token is missing, expired or lacks a scope
+    #[error("Not authorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// The forge's rate limit ran out; `reset` is when it refills, if known
+    #[error("Rate limited, retrying in {}s", rate_limit_wait(reset).as_secs())]
+    RateLimited { reset: Option<DateTime<Utc>> },
+
+    /// The forge couldn't be reached at all: the request never left
+    #[error("Network error: {0}")]
+    Network(String),
+
+    /// The request went out but no answer came back in time; the forge may
+    /// have acted on it
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
+    /// 400/422: the forge understood the request but refused its content
+    #[error("Rejected: {message}")]
+    Validation { message: String },
+
+    #[error("git: {0}")]
+    Git(String),
+
     #[error("IO error: {0}")]
-    Io(#[from] std::io::Error),
+    Io(String),
+}
+
+impl From<std::io::Error> for GritError {
+    fn from(err: std::io::Error) -> Self {
+        GritError::Io(err.to_string())
+    }
+}
+
+impl GritError {
+    /// Classify a failed response by status code. `message` already says
+    /// what failed; `reset` comes from the rate-limit headers, if any.
+    pub fn from_status(status: u16, message: String, reset: Option<DateTime<Utc>>) -> Self {
+        match status {
+            429 => GritError::RateLimited { reset },
+            // GitHub answers an exhausted rate limit with 403
+            403 if reset.is_some() => GritError::RateLimited { reset },
+            401 | 403 => GritError::Unauthorized(message),
+            404 => GritError::NotFound(message),
+            400 | 422 => GritError::Validation { message },
+            _ => GritError::Api(message),
+        }
+    }
+
+    /// What the user can do about it, when there's something
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            GritError::Unauthorized(_) => {
+                Some("check that the token is valid and has the repo scope (grit auth status)")
+            }
+            GritError::NotFound(_) => Some("private repos look missing to tokens without access"),
+            GritError::Auth(_) => Some("run grit auth login"),
+            GritError::Timeout(_) => Some("it may have gone through; refresh before trying again"),
+            _ => None,
+        }
+    }
+
+    /// How long to wait before trying again unchanged, for errors where
+    /// waiting helps
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            GritError::RateLimited { reset } => Some(rate_limit_wait(reset)),
+            _ => None,
+        }
+    }
+
+    /// The message followed by its hint, for the status bar
+    pub fn with_hint(&self) -> String {
+        match self.hint() {
+            Some(hint) => format!("{} - {}", self, hint),
+            None => self.to_string(),
+        }
+    }
+}
+
+fn rate_limit_wait(reset: &Option<DateTime<Utc>>) -> Duration {
+    match reset {
+        Some(reset) => (*reset - Utc::now()).to_std().unwrap_or_default(),
+        None => RATE_LIMIT_FALLBACK,
+    }
 }
 
 pub type Result<T> = std::result::Result<T, GritError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statuses_map_to_variants() {
+        let msg = || "Merge failed".to_string();
+        assert!(matches!(
+            GritError::from_status(401, msg(), None),
+            GritError::Unauthorized(_)
+        ));
+        assert!(matches!(
+            GritError::from_status(404, msg(), None),
+            GritError::NotFound(_)
+        ));
+        assert!(matches!(
+            GritError::from_status(422, msg(), None),
+            GritError::Validation { .. }
+        ));
+        assert!(matches!(
+            GritError::from_status(429, msg(), None),
+            GritError::RateLimited { reset: None }
+        ));
+        assert!(matches!(
+            GritError::from_status(502, msg(), None),
+            GritError::Api(_)
+        ));
+    }
+
+    #[test]
+    fn forbidden_with_a_reset_is_a_rate_limit() {
+        let reset = Utc::now() + chrono::Duration::seconds(90);
+        let err = GritError::from_status(403, "x".to_string(), Some(reset));
+        let wait = err.retry_after().unwrap().as_secs();
+        assert!((88..=90).contains(&wait));
+        assert!(err.to_string().starts_with("Rate limited, retrying in 8"));
+    }
+
+    #[test]
+    fn hints_follow_the_message() {
+        let err = GritError::Unauthorized("Bad credentials".to_string());
+        assert!(err
+            .with_hint()
+            .starts_with("Not authorized: Bad credentials - "));
+        assert_eq!(err.retry_after(), None);
+        assert_eq!(
+            GritError::Api("boom".to_string()).with_hint(),
+            "API error: boom"
+        );
+    }
+}
@@ -1,10 +1,12 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
 
 #[derive(Debug, Clone)]
 pub enum Event {
     Tick,
     Render,
     Key(KeyEvent),
+    /// Only delivered with `[ui] mouse = true`
+    Mouse(MouseEvent),
 }
 
 impl Event {
@@ -1,28 +1,86 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 
+use crate::config::{ForgeConfig, ForgeType};
 use crate::error::{GritError, Result};
 use crate::types::{
-    ActionRun, ChecksStatus, Commit, CommitDetail, Issue, MyPr, PagedResult, PrSummary,
-    PullRequest, Repository, ReviewRequest,
+    ActionRun, Artifact, ChecksStatus, Comment, Commit, CommitCheck, CommitDetail, CommitFile,
+    CommitHit, ForkStatus, Issue, IssueFields, IssueRef, IssueTemplate, Label, Milestone, MyPr,
+    PageInfo, PagedResult, PrChecks, PrFilter, PrReviews, PrSummary, PullRequest, Reaction,
+    ReactionTarget, RepoMeta, RepoStats, Repository, ReviewComment, ReviewRequest, ReviewThread,
+    ReviewerLoad, Subscription,
 };
 
+/// Items per page of a list unless `page_size` says otherwise
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+
 #[async_trait]
 #[allow(dead_code)]
 pub trait Forge: Send + Sync + std::fmt::Debug {
     fn name(&self) -> &str;
     fn web_url(&self, owner: &str, repo: &str, kind: &str, id: &str) -> String;
+    /// Remote ref a PR's head commits can be fetched from
+    fn pr_head_ref(&self, number: u64) -> String {
+        format!("pull/{}/head", number)
+    }
+    /// Items each page of a list asks for; a shorter page is the last
+    fn page_size(&self) -> usize {
+        DEFAULT_PAGE_SIZE
+    }
+    /// The PR tab filters `list_prs` can apply
+    fn pr_filters(&self) -> &'static [PrFilter] {
+        &PrFilter::ALL
+    }
 
     // Core (required)
     async fn get_current_user(&self) -> Result<String>;
     async fn list_repos(&self, page: u32) -> Result<PagedResult<Repository>>;
-    async fn list_prs(&self, owner: &str, repo: &str, page: u32) -> Result<PagedResult<PrSummary>>;
+    /// Open PRs, narrowed by `filter` and `milestone` on the forge's side
+    async fn list_prs(
+        &self,
+        owner: &str,
+        repo: &str,
+        filter: PrFilter,
+        milestone: Option<&Milestone>,
+        page: u32,
+    ) -> Result<PagedResult<PrSummary>>;
     async fn get_pr(&self, owner: &str, repo: &str, number: u64) -> Result<PullRequest>;
-    async fn list_issues(&self, owner: &str, repo: &str, page: u32) -> Result<PagedResult<Issue>>;
+    /// Open issues, only those in `milestone` when one is given
+    async fn list_issues(
+        &self,
+        owner: &str,
+        repo: &str,
+        milestone: Option<&Milestone>,
+        page: u32,
+    ) -> Result<PagedResult<Issue>>;
+    async fn get_issue(&self, owner: &str, repo: &str, number: u64) -> Result<Issue>;
     async fn list_commits(&self, owner: &str, repo: &str, page: u32)
         -> Result<PagedResult<Commit>>;
     async fn get_commit(&self, owner: &str, repo: &str, sha: &str) -> Result<CommitDetail>;
     async fn get_pr_diff(&self, owner: &str, repo: &str, number: u64) -> Result<String>;
+    /// Start downloading the PR's diff, read as it streams in; `None` where
+    /// the forge only hands it over whole through `get_pr_diff`
+    async fn stream_pr_diff(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+    ) -> Result<Option<reqwest::Response>> {
+        Ok(None)
+    }
     async fn merge_pr(&self, owner: &str, repo: &str, number: u64, method: &str) -> Result<()>;
+    /// Have the forge merge once checks pass; `delete_branch` is honored where
+    /// the forge takes it with the request (GitHub follows the repo setting)
+    async fn enable_auto_merge(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        method: &str,
+        delete_branch: bool,
+    ) -> Result<()>;
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<()>;
     async fn close_pr(&self, owner: &str, repo: &str, number: u64) -> Result<()>;
     async fn close_issue(&self, owner: &str, repo: &str, number: u64) -> Result<()>;
     async fn comment(&self, owner: &str, repo: &str, number: u64, body: &str) -> Result<()>;
@@ -34,6 +92,27 @@ pub trait Forge: Send + Sync + std::fmt::Debug {
     async fn list_my_prs(&self, _username: &str) -> Result<Vec<MyPr>> {
         Ok(vec![])
     }
+    /// Home's two PR sections at once, for forges that can fetch them together
+    async fn list_home_prs(&self, username: &str) -> Result<(Vec<ReviewRequest>, Vec<MyPr>)> {
+        tokio::try_join!(
+            self.list_review_requests(username),
+            self.list_my_prs(username)
+        )
+    }
+    /// Open issues assigned to `username`
+    async fn list_assigned_issues(&self, _username: &str) -> Result<Vec<IssueRef>> {
+        Ok(vec![])
+    }
+    /// Open issues and PRs that mention `username`
+    async fn list_mentions(&self, _username: &str) -> Result<Vec<IssueRef>> {
+        Ok(vec![])
+    }
+    /// Issues and PRs matching a saved search, newest update first. GitHub
+    /// takes its own search syntax; the others the qualifiers `SearchQuery`
+    /// reads.
+    async fn search_issues(&self, _query: &str, _page: u32) -> Result<PagedResult<IssueRef>> {
+        Err(GritError::Api("Search not supported by this forge".into()))
+    }
     async fn list_action_runs(
         &self,
         _owner: &str,
@@ -42,9 +121,26 @@ pub trait Forge: Send + Sync + std::fmt::Debug {
     ) -> Result<PagedResult<ActionRun>> {
         Ok(PagedResult {
             items: vec![],
-            total_count: None,
+            page_info: PageInfo::default(),
         })
     }
+    /// Files a workflow run (a pipeline's jobs on GitLab) uploaded
+    async fn list_artifacts(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _run_id: u64,
+    ) -> Result<Vec<Artifact>> {
+        Err(GritError::Api(
+            "Artifacts not supported by this forge".into(),
+        ))
+    }
+    /// Start downloading an artifact's zip; the body is read as it streams in
+    async fn download_artifact(&self, _artifact: &Artifact) -> Result<reqwest::Response> {
+        Err(GritError::Api(
+            "Artifacts not supported by this forge".into(),
+        ))
+    }
     async fn get_check_status(
         &self,
         _owner: &str,
@@ -53,6 +149,159 @@ pub trait Forge: Send + Sync + std::fmt::Debug {
     ) -> Result<ChecksStatus> {
         Ok(ChecksStatus::None)
     }
+    async fn get_pr_checks(&self, _owner: &str, _repo: &str, _number: u64) -> Result<PrChecks> {
+        Ok(PrChecks::default())
+    }
+    /// Issue and PR counts, top contributors, languages and recent activity
+    async fn get_repo_stats(&self, _owner: &str, _repo: &str) -> Result<RepoStats> {
+        Err(GritError::Api(
+            "Repo insights not supported by this forge".into(),
+        ))
+    }
+    /// Every check reported on a commit, as named by the forge
+    async fn get_commit_checks(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _sha: &str,
+    ) -> Result<Vec<CommitCheck>> {
+        Ok(Vec::new())
+    }
+    /// Paths the current user has marked as viewed in the forge's web UI
+    async fn list_viewed_files(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+    ) -> Result<Vec<String>> {
+        Err(GritError::Api(
+            "Viewed files not supported by this forge".into(),
+        ))
+    }
+    /// Commits in a PR, oldest first
+    async fn list_pr_commits(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+    ) -> Result<Vec<Commit>> {
+        Ok(vec![])
+    }
+    async fn list_pr_files(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+    ) -> Result<Vec<CommitFile>> {
+        Ok(vec![])
+    }
+    async fn list_pr_comments(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+    ) -> Result<Vec<Comment>> {
+        Ok(vec![])
+    }
+    async fn list_reviews(&self, _owner: &str, _repo: &str, _number: u64) -> Result<PrReviews> {
+        Ok(PrReviews::default())
+    }
+    async fn list_review_threads(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+    ) -> Result<Vec<ReviewThread>> {
+        Ok(vec![])
+    }
+    async fn set_thread_resolved(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+        _thread_id: &str,
+        _resolved: bool,
+    ) -> Result<()> {
+        Err(GritError::Api(
+            "Resolving threads not supported by this forge".into(),
+        ))
+    }
+    async fn list_milestones(&self, _owner: &str, _repo: &str) -> Result<Vec<Milestone>> {
+        Ok(vec![])
+    }
+    /// Assign an issue to a milestone, or clear it with `None`
+    async fn set_milestone(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+        _milestone: Option<u64>,
+    ) -> Result<()> {
+        Err(GritError::Api(
+            "Milestones not supported by this forge".into(),
+        ))
+    }
+    async fn list_labels(&self, _owner: &str, _repo: &str) -> Result<Vec<Label>> {
+        Ok(vec![])
+    }
+    /// `kind` is "pr" or "issue", as for `web_url`
+    async fn add_labels(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _kind: &str,
+        _number: u64,
+        _labels: &[String],
+    ) -> Result<()> {
+        Err(GritError::Api("Labels not supported by this forge".into()))
+    }
+    async fn remove_labels(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _kind: &str,
+        _number: u64,
+        _labels: &[String],
+    ) -> Result<()> {
+        Err(GritError::Api("Labels not supported by this forge".into()))
+    }
+    /// Templates a new issue can start from; empty when the repo has none
+    async fn list_issue_templates(&self, _owner: &str, _repo: &str) -> Result<Vec<IssueTemplate>> {
+        Ok(vec![])
+    }
+    /// Open an issue; returns its number
+    async fn create_issue(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _fields: &IssueFields,
+        _body: &str,
+    ) -> Result<u64> {
+        Err(GritError::Api(
+            "Creating issues not supported by this forge".into(),
+        ))
+    }
+    /// Replace the title, labels, assignees and milestone of an issue or PR;
+    /// `kind` is "pr" or "issue", as for `web_url`
+    async fn update_fields(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _kind: &str,
+        _number: u64,
+        _fields: &IssueFields,
+    ) -> Result<()> {
+        Err(GritError::Api(
+            "Editing issues not supported by this forge".into(),
+        ))
+    }
+    /// Replace a PR's description
+    async fn update_pr(&self, _owner: &str, _repo: &str, _number: u64, _body: &str) -> Result<()> {
+        Err(GritError::Api(
+            "Editing descriptions not supported by this forge".into(),
+        ))
+    }
+    /// `comments` are anchored to diff lines and go up with the review
     async fn submit_review(
         &self,
         _owner: &str,
@@ -60,7 +309,280 @@ pub trait Forge: Send + Sync + std::fmt::Debug {
         _number: u64,
         _event: &str,
         _body: &str,
+        _comments: &[ReviewComment],
     ) -> Result<()> {
         Err(GritError::Api("Reviews not supported by this forge".into()))
     }
+    /// Where the repo lives now, following renames and transfers;
+    /// `NotFound` once it's gone
+    async fn locate_repo(&self, owner: &str, repo: &str) -> Result<(String, String)> {
+        Ok((owner.to_string(), repo.to_string()))
+    }
+    async fn get_repo_meta(&self, _owner: &str, _repo: &str) -> Result<RepoMeta> {
+        Err(GritError::Api(
+            "Editing repositories not supported by this forge".into(),
+        ))
+    }
+    /// Replace the description and the full topic list
+    async fn update_repo_meta(&self, _owner: &str, _repo: &str, _meta: &RepoMeta) -> Result<()> {
+        Err(GritError::Api(
+            "Editing repositories not supported by this forge".into(),
+        ))
+    }
+    /// Fork into the signed-in user's namespace, returning the new repo
+    async fn fork_repo(&self, _owner: &str, _repo: &str) -> Result<Repository> {
+        Err(GritError::Api("Forking not supported by this forge".into()))
+    }
+    /// Ahead/behind counts against the upstream, or `None` if not a fork
+    async fn get_fork_status(&self, _owner: &str, _repo: &str) -> Result<Option<ForkStatus>> {
+        Ok(None)
+    }
+    /// Bring the fork's `branch` up to date with its upstream
+    async fn sync_fork(&self, _owner: &str, _repo: &str, _branch: &str) -> Result<()> {
+        Err(GritError::Api(
+            "Syncing forks not supported by this forge".into(),
+        ))
+    }
+    /// Whether the signed-in user has starred the repo
+    async fn is_starred(&self, _owner: &str, _repo: &str) -> Result<bool> {
+        Err(GritError::Api("Stars not supported by this forge".into()))
+    }
+    async fn set_star(&self, _owner: &str, _repo: &str, _starred: bool) -> Result<()> {
+        Err(GritError::Api("Stars not supported by this forge".into()))
+    }
+    async fn set_subscription(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _subscription: Subscription,
+    ) -> Result<()> {
+        Err(GritError::Api(
+            "Watching repositories not supported by this forge".into(),
+        ))
+    }
+    /// People who can review PRs in the repo, with their open review requests
+    /// across the owning org or group
+    async fn reviewer_load(&self, _owner: &str, _repo: &str) -> Result<Vec<ReviewerLoad>> {
+        Err(GritError::Api(
+            "Reviewer load not supported by this forge".into(),
+        ))
+    }
+    /// Add users to a PR's requested reviewers
+    async fn request_reviewers(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+        _reviewers: &[String],
+    ) -> Result<()> {
+        Err(GritError::Api(
+            "Requesting reviewers not supported by this forge".into(),
+        ))
+    }
+    /// Revert `commits` of a merged PR on a new branch off its base and open
+    /// a PR for it; returns the new PR's number
+    async fn revert_pr(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _pr: &PullRequest,
+        _commits: &[Commit],
+    ) -> Result<u64> {
+        Err(GritError::Api(
+            "Reverting not supported by this forge".into(),
+        ))
+    }
+    /// An issue's description, for previews; issue lists leave it out
+    async fn get_issue_body(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+    ) -> Result<Option<String>> {
+        Err(GritError::Api(
+            "Issue descriptions not supported by this forge".into(),
+        ))
+    }
+    /// Organizations (GitHub, Gitea) or groups (GitLab, by full path) the
+    /// user belongs to
+    async fn list_orgs(&self) -> Result<Vec<String>> {
+        Err(GritError::Api(
+            "Organizations not supported by this forge".into(),
+        ))
+    }
+    /// Repos of an organization or group; GitLab includes subgroups
+    async fn list_org_repos(&self, _org: &str, _page: u32) -> Result<PagedResult<Repository>> {
+        Err(GritError::Api(
+            "Organizations not supported by this forge".into(),
+        ))
+    }
+    async fn add_reaction(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _target: ReactionTarget,
+        _reaction: Reaction,
+    ) -> Result<()> {
+        Err(GritError::Api(
+            "Reactions not supported by this forge".into(),
+        ))
+    }
+    /// Comment on a commit as a whole, not on one of its lines
+    async fn comment_on_commit(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _sha: &str,
+        _body: &str,
+    ) -> Result<()> {
+        Err(GritError::Api(
+            "Commit comments not supported by this forge".into(),
+        ))
+    }
+    /// Forge-side commit search across repos owned by `owners`
+    async fn search_commits(&self, _query: &str, _owners: &[String]) -> Result<Vec<CommitHit>> {
+        Err(GritError::Api(
+            "Commit search not supported by this forge".into(),
+        ))
+    }
+    /// Scopes the token was granted, `None` where the forge doesn't say
+    /// (GitHub's fine-grained tokens, Gitea)
+    async fn token_scopes(&self) -> Result<Option<Vec<String>>> {
+        Ok(None)
+    }
+    /// Scope a token needs for everything grit does
+    fn required_scope(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+/// Client for a configured forge
+pub fn client(forge_config: &ForgeConfig, token: String) -> Result<Arc<dyn Forge>> {
+    Ok(match forge_config.forge_type {
+        ForgeType::GitHub => Arc::new(crate::github::GitHub::new(
+            token,
+            forge_config.timeout(),
+            &forge_config.transport,
+            forge_config.page_size(),
+        )?),
+        ForgeType::GitLab => Arc::new(crate::gitlab::GitLab::new(
+            forge_config.base_url(),
+            token,
+            forge_config.timeout(),
+            &forge_config.transport,
+            forge_config.page_size(),
+        )?),
+        ForgeType::Gitea => Arc::new(crate::gitea::Gitea::new(
+            forge_config.host.clone(),
+            token,
+            forge_config.timeout(),
+            &forge_config.transport,
+            forge_config.page_size(),
+        )?),
+    })
+}
+
+/// Client for a forge whose token is already available; never prompts, so a
+/// forge that isn't set up just reports why
+pub fn connect(forge_config: &ForgeConfig) -> std::result::Result<Arc<dyn Forge>, String> {
+    let token = crate::auth::find_forge_token(forge_config)?;
+    client(forge_config, token).map_err(|e| e.to_string())
+}
+
+/// Totals from pagination headers: `X-Total` / `X-Total-Pages` (GitLab),
+/// `X-Total-Count` (Gitea), falling back to the `rel="last"` Link for the page count
+pub fn page_info(headers: &reqwest::header::HeaderMap) -> PageInfo {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    PageInfo {
+        total_count: header("x-total")
+            .or_else(|| header("x-total-count"))
+            .and_then(|s| s.parse().ok()),
+        total_pages: header("x-total-pages")
+            .and_then(|s| s.parse().ok())
+            .or_else(|| header("link").and_then(last_page)),
+    }
+}
+
+/// Where the page after this one is: the `rel="next"` entry of a Link header.
+/// None on the last page.
+pub fn next_link(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    let link = headers.get("link")?.to_str().ok()?;
+    link_target(link, "next").map(str::to_string)
+}
+
+/// GitLab's `X-Next-Page`, which is empty on the last page
+pub fn next_page(headers: &reqwest::header::HeaderMap) -> Option<u32> {
+    headers.get("x-next-page")?.to_str().ok()?.parse().ok()
+}
+
+/// URL of the entry with relation `rel` in a Link header
+fn link_target<'a>(link: &'a str, rel: &str) -> Option<&'a str> {
+    let rel = format!("rel=\"{}\"", rel);
+    let entry = link.split(',').find(|part| part.contains(&rel))?;
+    entry.split(['<', '>']).nth(1)
+}
+
+/// `page` query parameter of the `rel="last"` entry in a Link header
+fn last_page(link: &str) -> Option<u32> {
+    let url = link_target(link, "last")?;
+    let query = url.split_once('?')?.1;
+    query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("page="))
+        .and_then(|n| n.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn gitlab_totals() {
+        let info = page_info(&headers(&[("x-total", "230"), ("x-total-pages", "5")]));
+        assert_eq!(info.total_count, Some(230));
+        assert_eq!(info.total_pages, Some(5));
+    }
+
+    #[test]
+    fn gitea_count_and_last_link() {
+        let link =
+            "<https://git.example.com/api/v1/repos/o/r/pulls?limit=50&page=2>; rel=\"next\",\
+                    <https://git.example.com/api/v1/repos/o/r/pulls?limit=50&page=7>; rel=\"last\"";
+        let info = page_info(&headers(&[("x-total-count", "321"), ("link", link)]));
+        assert_eq!(info.total_count, Some(321));
+        assert_eq!(info.total_pages, Some(7));
+    }
+
+    #[test]
+    fn next_page_from_link_or_gitlab_header() {
+        let link =
+            "<https://git.example.com/api/v1/repos/o/r/pulls?limit=50&page=2>; rel=\"next\",\
+                    <https://git.example.com/api/v1/repos/o/r/pulls?limit=50&page=7>; rel=\"last\"";
+        assert_eq!(
+            next_link(&headers(&[("link", link)])).as_deref(),
+            Some("https://git.example.com/api/v1/repos/o/r/pulls?limit=50&page=2")
+        );
+        assert_eq!(next_page(&headers(&[("x-next-page", "3")])), Some(3));
+        // Both say nothing on the last page
+        let link = "<https://x/api?page=1>; rel=\"first\", <https://x/api?page=3>; rel=\"prev\"";
+        assert_eq!(next_link(&headers(&[("link", link)])), None);
+        assert_eq!(next_page(&headers(&[("x-next-page", "")])), None);
+    }
+
+    #[test]
+    fn no_headers_means_unknown() {
+        assert_eq!(page_info(&HeaderMap::new()), PageInfo::default());
+        // On the last page there's no rel="last" entry
+        let link = "<https://x/api?page=1>; rel=\"first\", <https://x/api?page=3>; rel=\"prev\"";
+        assert_eq!(page_info(&headers(&[("link", link)])).total_pages, None);
+    }
 }
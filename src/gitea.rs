@@ -1,18 +1,35 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::Deserialize;
+use tokio::sync::OnceCell;
 
+use crate::config::Transport;
 use crate::error::{GritError, Result};
-use crate::forge::Forge;
+use crate::forge::{next_link, page_info, Forge};
+use crate::http::{self, RetrySend};
 use crate::types::{
-    Commit, CommitDetail, CommitFile, CommitStats, Issue, IssueState, PagedResult, PrState,
-    PrStats, PrSummary, PullRequest, Repository,
+    language_shares, split_repo_path, ActionConclusion, ActionRun, ActionStatus, Artifact,
+    ChecksStatus, Comment, Commit, CommitCheck, CommitDetail, CommitFile, CommitStats, ForkStatus,
+    Issue, IssueFields, IssueRef, IssueState, IssueTemplate, Label, MergeableState, Milestone,
+    MyPr, PageInfo, PagedResult, PrChecks, PrFilter, PrReviews, PrState, PrStats, PrSummary,
+    PullRequest, Reaction, ReactionTarget, RepoMeta, RepoStats, Repository, Review, ReviewComment,
+    ReviewRequest, ReviewState, SearchQuery, SearchState, Subscription, INSIGHTS_TOP,
+    RECENT_ACTIVITY_DAYS,
 };
 
+/// Jobs per page of `/actions/tasks`
+const TASK_PAGE_SIZE: u64 = 50;
+
 pub struct Gitea {
     client: Client,
     host: String,
     token: String,
+    page_size: usize,
+    /// Signed-in user, asked for once; filters by person need the name
+    user: OnceCell<String>,
 }
 
 impl std::fmt::Debug for Gitea {
@@ -24,75 +41,177 @@ impl std::fmt::Debug for Gitea {
 }
 
 impl Gitea {
-    pub fn new(host: String, token: String) -> Self {
-        Self {
-            client: Client::new(),
-            host,
+    pub fn new(
+        host: String,
+        token: String,
+        timeout: Duration,
+        transport: &Transport,
+        page_size: usize,
+    ) -> Result<Self> {
+        Ok(Self {
+            client: http::client(timeout, transport)?,
+            host: host.trim_end_matches('/').to_string(),
             token,
-        }
+            page_size,
+            user: OnceCell::new(),
+        })
     }
 
     fn api_url(&self, path: &str) -> String {
         format!("https://{}/api/v1{}", self.host, path)
     }
 
-    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
+    async fn repo_labels(&self, owner: &str, repo: &str) -> Result<Vec<GtLabel>> {
+        let url = self.api_url(&format!("/repos/{}/{}/labels?limit=50", owner, repo));
+        self.get_json_all(&url).await
+    }
+
+    /// The label endpoints take ids, so resolve names against the repo's labels
+    async fn label_ids(&self, owner: &str, repo: &str, names: &[String]) -> Result<Vec<u64>> {
+        let labels = self.repo_labels(owner, repo).await?;
+        names
+            .iter()
+            .map(|name| {
+                labels
+                    .iter()
+                    .find(|l| &l.name == name)
+                    .map(|l| l.id)
+                    .ok_or_else(|| GritError::Validation {
+                        message: format!("No label named \"{}\" in {}/{}", name, owner, repo),
+                    })
+            })
+            .collect()
+    }
+
+    /// Open PRs across all repos matching a search flag like `created`
+    async fn search_open_prs(&self, filter: &str) -> Result<Vec<GtIssue>> {
+        self.search_open("type=pulls&", filter).await
+    }
+
+    /// Open issues and/or PRs across all repos; `kind` narrows the type
+    async fn search_open(&self, kind: &str, filter: &str) -> Result<Vec<GtIssue>> {
+        let url = self.api_url(&format!(
+            "/repos/issues/search?{}state=open&{}=true&limit={}",
+            kind, filter, self.page_size
+        ));
+        self.get_json(&url).await
+    }
+
+    async fn search_issue_refs(&self, kind: &str, filter: &str) -> Result<Vec<IssueRef>> {
+        let issues = self.search_open(kind, filter).await?;
+        Ok(issues.into_iter().filter_map(gt_issue_ref).collect())
+    }
+
+    /// Runs pieced together from their jobs, for Gitea 1.19 to 1.22 where
+    /// `/actions/tasks` is all there is. Runs are numbered by `run_number`; one
+    /// whose jobs straddle two pages keeps what its first page said.
+    async fn list_action_tasks(
+        &self,
+        owner: &str,
+        repo: &str,
+        page: u32,
+    ) -> Result<PagedResult<ActionRun>> {
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/actions/tasks?limit={}&page={}",
+            owner, repo, TASK_PAGE_SIZE, page
+        ));
+        let response: GtActionTasks = self.get_json(&url).await?;
+        // Totals count jobs, so only the page count carries over to runs
+        let total_pages = match response.total_count {
+            Some(total) => total.div_ceil(TASK_PAGE_SIZE) as u32,
+            None if response.workflow_runs.len() as u64 == TASK_PAGE_SIZE => page + 1,
+            None => page,
+        };
+        Ok(PagedResult {
+            items: gt_task_runs(response.workflow_runs),
+            page_info: PageInfo {
+                total_count: None,
+                total_pages: Some(total_pages),
+            },
+        })
+    }
+
+    /// Send a JSON body, reporting failures as "{what} failed (status): ..."
+    async fn send_json(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        body: &serde_json::Value,
+        what: &str,
+    ) -> Result<()> {
         let response = self
             .client
-            .get(url)
+            .request(method, url)
             .header("Authorization", format!("token {}", self.token))
-            .send()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
+            .json(body)
+            .send_retrying()
+            .await?;
+        http::check(response, what).await?;
+        Ok(())
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown error".to_string());
-            return Err(GritError::Api(format!("Gitea API {}: {}", status, text)));
-        }
+    /// Fetch a raw text endpoint such as a `.diff`
+    async fn get_text(&self, url: &str) -> Result<String> {
+        let response = self
+            .client
+            .get(url)
+            .header("Authorization", format!("token {}", self.token))
+            .send_retrying()
+            .await?;
+        let response = http::check(response, "Fetch diff").await?;
+        response.text().await.map_err(http::api_error)
+    }
 
-        response
-            .json()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let response = self
+            .client
+            .get(url)
+            .header("Authorization", format!("token {}", self.token))
+            .send_retrying()
+            .await?;
+        let response = http::check(response, "Gitea request").await?;
+        response.json().await.map_err(http::api_error)
     }
 
     async fn get_json_paged<T: serde::de::DeserializeOwned>(
         &self,
         url: &str,
-    ) -> Result<(Vec<T>, Option<u64>)> {
+    ) -> Result<(Vec<T>, PageInfo)> {
         let response = self
             .client
             .get(url)
             .header("Authorization", format!("token {}", self.token))
-            .send()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
+            .send_retrying()
+            .await?;
+        let response = http::check(response, "Gitea request").await?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown error".to_string());
-            return Err(GritError::Api(format!("Gitea API {}: {}", status, text)));
-        }
+        let page_info = page_info(response.headers());
 
-        let total_count = response
-            .headers()
-            .get("x-total-count")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|s| s.parse::<u64>().ok());
+        let items: Vec<T> = response.json().await.map_err(http::api_error)?;
 
-        let items: Vec<T> = response
-            .json()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
+        Ok((items, page_info))
+    }
 
-        Ok((items, total_count))
+    /// Every page of a list, following the Link header to the last one
+    async fn get_json_all<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        let mut url = url.to_string();
+        loop {
+            let response = self
+                .client
+                .get(&url)
+                .header("Authorization", format!("token {}", self.token))
+                .send_retrying()
+                .await?;
+            let response = http::check(response, "Gitea request").await?;
+            let next = next_link(response.headers());
+            let found: Vec<T> = response.json().await.map_err(http::api_error)?;
+            items.extend(found);
+            match next {
+                Some(next) => url = next,
+                None => return Ok(items),
+            }
+        }
     }
 }
 
@@ -102,10 +221,58 @@ impl Gitea {
 struct GtRepo {
     owner: Option<GtUser>,
     name: String,
+    full_name: Option<String>,
     description: Option<String>,
     html_url: Option<String>,
     stars_count: Option<u32>,
     updated_at: Option<String>,
+    default_branch: Option<String>,
+    parent: Option<Box<GtRepo>>,
+}
+
+#[derive(Deserialize)]
+struct GtCompare {
+    #[serde(default)]
+    total_commits: u64,
+}
+
+/// Front matter comes parsed; `content` is the body after it
+#[derive(Deserialize)]
+struct GtIssueTemplate {
+    name: String,
+    #[serde(default)]
+    about: String,
+    #[serde(default)]
+    title: String,
+    labels: Option<Vec<String>>,
+    assignees: Option<Vec<String>>,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    file_name: String,
+}
+
+#[derive(Deserialize)]
+struct GtArtifacts {
+    #[serde(default)]
+    artifacts: Vec<GtArtifact>,
+}
+
+#[derive(Deserialize)]
+struct GtArtifact {
+    id: u64,
+    name: String,
+    #[serde(default)]
+    size_in_bytes: u64,
+    #[serde(default)]
+    expired: bool,
+    archive_download_url: String,
+}
+
+#[derive(Deserialize)]
+struct GtTopics {
+    #[serde(default)]
+    topics: Vec<String>,
 }
 
 #[derive(Deserialize)]
@@ -113,6 +280,11 @@ struct GtUser {
     login: String,
 }
 
+#[derive(Deserialize)]
+struct GtOrg {
+    username: String,
+}
+
 #[derive(Deserialize)]
 struct GtPullRequest {
     number: u64,
@@ -127,6 +299,10 @@ struct GtPullRequest {
     changed_files: Option<u64>,
     comments: Option<u64>,
     merged: Option<bool>,
+    mergeable: Option<bool>,
+    milestone: Option<GtMilestone>,
+    labels: Option<Vec<GtLabel>>,
+    assignees: Option<Vec<GtUser>>,
     created_at: Option<String>,
     updated_at: Option<String>,
     merged_at: Option<String>,
@@ -137,6 +313,20 @@ struct GtPullRequest {
 struct GtPrRef {
     #[serde(rename = "ref")]
     ref_field: Option<String>,
+    sha: Option<String>,
+    repo_id: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct GtCombinedStatus {
+    #[serde(default)]
+    statuses: Vec<GtStatus>,
+}
+
+#[derive(Deserialize)]
+struct GtStatus {
+    status: String,
+    context: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -149,11 +339,89 @@ struct GtIssue {
     comments: Option<u32>,
     created_at: Option<String>,
     updated_at: Option<String>,
+    milestone: Option<GtMilestone>,
+    assignees: Option<Vec<GtUser>>,
+    /// Only set on results from the cross-repo search endpoint
+    repository: Option<GtIssueRepo>,
+    /// Present when the issue is a PR
+    pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct GtIssueRepo {
+    owner: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GtActionRuns {
+    #[serde(default)]
+    workflow_runs: Vec<GtActionRun>,
+    total_count: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct GtActionRun {
+    id: u64,
+    display_title: Option<String>,
+    path: Option<String>,
+    status: String,
+    conclusion: Option<String>,
+    head_branch: Option<String>,
+    event: Option<String>,
+    started_at: Option<String>,
+    completed_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GtActionTasks {
+    #[serde(default)]
+    workflow_runs: Vec<GtActionTask>,
+    total_count: Option<u64>,
+}
+
+/// One job of a run
+#[derive(Deserialize)]
+struct GtActionTask {
+    display_title: Option<String>,
+    /// Workflow file name
+    workflow_id: Option<String>,
+    run_number: Option<u64>,
+    /// A single word such as "running" or "failure"
+    status: String,
+    head_branch: Option<String>,
+    event: Option<String>,
+    run_started_at: Option<String>,
+    updated_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GtMilestone {
+    id: u64,
+    title: String,
+    due_on: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GtComment {
+    id: u64,
+    body: Option<String>,
+    user: Option<GtUser>,
+    created_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GtReview {
+    user: Option<GtUser>,
+    state: String,
+    submitted_at: Option<String>,
 }
 
 #[derive(Deserialize)]
 struct GtLabel {
+    id: u64,
     name: String,
+    color: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -215,53 +483,134 @@ impl Forge for Gitea {
         "Gitea"
     }
 
+    fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    /// Review requests can only be searched across all repos, not within one
+    fn pr_filters(&self) -> &'static [PrFilter] {
+        &[PrFilter::All, PrFilter::Authored, PrFilter::Assigned]
+    }
+
     fn web_url(&self, owner: &str, repo: &str, kind: &str, id: &str) -> String {
+        // Orgs and users share one flat namespace on Gitea
+        let repo_url = format!("https://{}/{}/{}", self.host, owner, repo);
         match kind {
-            "repo" => format!("https://{}/{}/{}", self.host, owner, repo),
-            "pr" => format!("https://{}/{}/{}/pulls/{}", self.host, owner, repo, id),
-            "issue" => format!("https://{}/{}/{}/issues/{}", self.host, owner, repo, id),
-            "commit" => format!("https://{}/{}/{}/commit/{}", self.host, owner, repo, id),
-            _ => format!("https://{}/{}/{}", self.host, owner, repo),
+            "user" => format!("https://{}/{}", self.host, id),
+            "pr" => format!("{}/pulls/{}", repo_url, id),
+            "issue" => format!("{}/issues/{}", repo_url, id),
+            "commit" => format!("{}/commit/{}", repo_url, id),
+            _ => repo_url,
         }
     }
 
     async fn get_current_user(&self) -> Result<String> {
-        let url = self.api_url("/user");
-        let user: GtUser = self.get_json(&url).await?;
-        Ok(user.login)
+        self.user
+            .get_or_try_init(|| async {
+                let url = self.api_url("/user");
+                let user: GtUser = self.get_json(&url).await?;
+                Ok(user.login)
+            })
+            .await
+            .cloned()
     }
 
     async fn list_repos(&self, page: u32) -> Result<PagedResult<Repository>> {
-        let url = self.api_url(&format!("/user/repos?sort=updated&limit=50&page={}", page));
-        let (repos, total_count) = self.get_json_paged::<GtRepo>(&url).await?;
-
-        let result = repos
-            .into_iter()
-            .map(|r| Repository {
-                owner: r
-                    .owner
-                    .map(|o| o.login)
-                    .unwrap_or_else(|| "unknown".to_string()),
-                name: r.name,
-                description: r.description.filter(|d| !d.is_empty()),
-                url: r.html_url.unwrap_or_default(),
-                stars: r.stars_count.unwrap_or(0),
-                updated_at: parse_optional_datetime(r.updated_at.as_deref()),
-            })
-            .collect();
+        let url = self.api_url(&format!(
+            "/user/repos?sort=updated&limit={}&page={}",
+            self.page_size, page
+        ));
+        let (repos, page_info) = self.get_json_paged::<GtRepo>(&url).await?;
 
         Ok(PagedResult {
-            items: result,
-            total_count,
+            items: repos.into_iter().map(gt_repository).collect(),
+            page_info,
         })
     }
 
-    async fn list_prs(&self, owner: &str, repo: &str, page: u32) -> Result<PagedResult<PrSummary>> {
+    async fn list_orgs(&self) -> Result<Vec<String>> {
+        let url = self.api_url("/user/orgs?limit=50");
+        let orgs: Vec<GtOrg> = self.get_json(&url).await?;
+        Ok(orgs.into_iter().map(|o| o.username).collect())
+    }
+
+    async fn list_org_repos(&self, org: &str, page: u32) -> Result<PagedResult<Repository>> {
         let url = self.api_url(&format!(
-            "/repos/{}/{}/pulls?state=open&sort=updated&limit=50&page={}",
-            owner, repo, page
+            "/orgs/{}/repos?limit={}&page={}",
+            org, self.page_size, page
+        ));
+        let (repos, page_info) = self.get_json_paged::<GtRepo>(&url).await?;
+
+        Ok(PagedResult {
+            items: repos.into_iter().map(gt_repository).collect(),
+            page_info,
+        })
+    }
+
+    async fn list_prs(
+        &self,
+        owner: &str,
+        repo: &str,
+        filter: PrFilter,
+        milestone: Option<&Milestone>,
+        page: u32,
+    ) -> Result<PagedResult<PrSummary>> {
+        // The pulls endpoint can't filter by person, but the issues one can
+        // and lists PRs too
+        let person = match filter {
+            PrFilter::All => None,
+            PrFilter::Authored => Some("created_by"),
+            PrFilter::Assigned => Some("assigned_by"),
+            // Not offered by `pr_filters`
+            PrFilter::ReviewRequested => {
+                return Err(GritError::Api(
+                    "Gitea can't list a repo's PRs awaiting your review".into(),
+                ))
+            }
+        };
+        if let Some(key) = person {
+            let me = self.get_current_user().await?;
+            let mut url = self.api_url(&format!(
+                "/repos/{}/{}/issues?type=pulls&state=open&{}={}&limit={}&page={}",
+                owner,
+                repo,
+                key,
+                urlencoding::encode(&me),
+                self.page_size,
+                page
+            ));
+            if let Some(milestone) = milestone {
+                url.push_str(&format!(
+                    "&milestones={}",
+                    urlencoding::encode(&milestone.title)
+                ));
+            }
+            let (issues, page_info) = self.get_json_paged::<GtIssue>(&url).await?;
+            let items = issues
+                .into_iter()
+                .map(|issue| PrSummary {
+                    number: issue.number,
+                    title: issue.title,
+                    state: gt_pr_state(&issue.state, None),
+                    author: issue
+                        .user
+                        .map(|u| u.login)
+                        .unwrap_or_else(|| "unknown".to_string()),
+                    updated_at: parse_optional_datetime(issue.updated_at.as_deref()),
+                    milestone: issue.milestone.map(|m| m.title),
+                })
+                .collect();
+            return Ok(PagedResult { items, page_info });
+        }
+
+        let mut url = self.api_url(&format!(
+            "/repos/{}/{}/pulls?state=open&sort=updated&limit={}&page={}",
+            owner, repo, self.page_size, page
         ));
-        let (prs, total_count) = self.get_json_paged::<GtPullRequest>(&url).await?;
+        if let Some(milestone) = milestone {
+            url.push_str(&format!("&milestone={}", milestone.id));
+        }
+        let (prs, page_info) = self.get_json_paged::<GtPullRequest>(&url).await?;
 
         let summaries = prs
             .into_iter()
@@ -274,18 +623,25 @@ impl Forge for Gitea {
                     .map(|u| u.login)
                     .unwrap_or_else(|| "unknown".to_string()),
                 updated_at: parse_optional_datetime(pr.updated_at.as_deref()),
+                milestone: pr.milestone.map(|m| m.title),
             })
             .collect();
 
         Ok(PagedResult {
             items: summaries,
-            total_count,
+            page_info,
         })
     }
 
     async fn get_pr(&self, owner: &str, repo: &str, number: u64) -> Result<PullRequest> {
         let url = self.api_url(&format!("/repos/{}/{}/pulls/{}", owner, repo, number));
         let pr: GtPullRequest = self.get_json(&url).await?;
+        let from_fork =
+            pr.head.as_ref().and_then(|h| h.repo_id) != pr.base.as_ref().and_then(|b| b.repo_id);
+        let (head_branch, head_sha) = pr
+            .head
+            .map(|h| (h.ref_field.unwrap_or_default(), h.sha.unwrap_or_default()))
+            .unwrap_or_default();
 
         Ok(PullRequest {
             number: pr.number,
@@ -296,7 +652,10 @@ impl Forge for Gitea {
                 .user
                 .map(|u| u.login)
                 .unwrap_or_else(|| "unknown".to_string()),
-            head_branch: pr.head.and_then(|h| h.ref_field).unwrap_or_default(),
+            head_branch,
+            head_sha,
+            from_fork,
+            auto_merge: false,
             base_branch: pr.base.and_then(|b| b.ref_field).unwrap_or_default(),
             stats: PrStats {
                 additions: pr.additions.unwrap_or(0),
@@ -309,45 +668,52 @@ impl Forge for Gitea {
             updated_at: parse_optional_datetime(pr.updated_at.as_deref()),
             merged_at: pr.merged_at.as_deref().map(parse_datetime),
             closed_at: pr.closed_at.as_deref().map(parse_datetime),
+            // Gitea only reports whether the branch merges cleanly
+            mergeable_state: match pr.mergeable {
+                Some(true) => MergeableState::Clean,
+                Some(false) => MergeableState::Conflicts,
+                None => MergeableState::Unknown,
+            },
+            milestone: pr.milestone.map(|m| m.title),
+            labels: pr
+                .labels
+                .unwrap_or_default()
+                .into_iter()
+                .map(gt_label)
+                .collect(),
+            assignees: pr
+                .assignees
+                .unwrap_or_default()
+                .into_iter()
+                .map(|u| u.login)
+                .collect(),
         })
     }
 
-    async fn list_issues(&self, owner: &str, repo: &str, page: u32) -> Result<PagedResult<Issue>> {
-        let url = self.api_url(&format!(
-            "/repos/{}/{}/issues?type=issues&state=open&sort=updated&limit=50&page={}",
-            owner, repo, page
+    async fn list_issues(
+        &self,
+        owner: &str,
+        repo: &str,
+        milestone: Option<&Milestone>,
+        page: u32,
+    ) -> Result<PagedResult<Issue>> {
+        let mut url = self.api_url(&format!(
+            "/repos/{}/{}/issues?type=issues&state=open&sort=updated&limit={}&page={}",
+            owner, repo, self.page_size, page
         ));
-        let (issues, total_count) = self.get_json_paged::<GtIssue>(&url).await?;
+        if let Some(milestone) = milestone {
+            url.push_str(&format!(
+                "&milestones={}",
+                urlencoding::encode(&milestone.title)
+            ));
+        }
+        let (issues, page_info) = self.get_json_paged::<GtIssue>(&url).await?;
 
-        let result = issues
-            .into_iter()
-            .map(|i| Issue {
-                number: i.number,
-                title: i.title,
-                state: if i.state == "closed" {
-                    IssueState::Closed
-                } else {
-                    IssueState::Open
-                },
-                author: i
-                    .user
-                    .map(|u| u.login)
-                    .unwrap_or_else(|| "unknown".to_string()),
-                labels: i
-                    .labels
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|l| l.name)
-                    .collect(),
-                comments: i.comments.unwrap_or(0),
-                created_at: parse_optional_datetime(i.created_at.as_deref()),
-                updated_at: parse_optional_datetime(i.updated_at.as_deref()),
-            })
-            .collect();
+        let result = issues.into_iter().map(gt_issue).collect();
 
         Ok(PagedResult {
             items: result,
-            total_count,
+            page_info,
         })
     }
 
@@ -358,42 +724,16 @@ impl Forge for Gitea {
         page: u32,
     ) -> Result<PagedResult<Commit>> {
         let url = self.api_url(&format!(
-            "/repos/{}/{}/commits?limit=50&page={}",
-            owner, repo, page
+            "/repos/{}/{}/commits?limit={}&page={}",
+            owner, repo, self.page_size, page
         ));
-        let (commits, total_count) = self.get_json_paged::<GtCommit>(&url).await?;
-
-        let result = commits
-            .into_iter()
-            .map(|c| {
-                let inner = c.commit.as_ref();
-                let message = inner
-                    .and_then(|i| i.message.as_deref())
-                    .and_then(|m| m.lines().next())
-                    .unwrap_or("")
-                    .to_string();
-                let author = inner
-                    .and_then(|i| i.author.as_ref())
-                    .and_then(|a| a.name.clone())
-                    .unwrap_or_else(|| "unknown".to_string());
-                let date = inner
-                    .and_then(|i| i.author.as_ref())
-                    .and_then(|a| a.date.as_deref())
-                    .map(parse_datetime)
-                    .unwrap_or_else(chrono::Utc::now);
+        let (commits, page_info) = self.get_json_paged::<GtCommit>(&url).await?;
 
-                Commit {
-                    sha: c.sha.unwrap_or_default(),
-                    message,
-                    author,
-                    date,
-                }
-            })
-            .collect();
+        let result = commits.into_iter().map(gt_commit).collect();
 
         Ok(PagedResult {
             items: result,
-            total_count,
+            page_info,
         })
     }
 
@@ -426,17 +766,29 @@ impl Forge for Gitea {
             },
         );
 
+        // The commit API has no patches; cut them out of the raw diff instead.
+        // Without it the file list still works, so a failure isn't fatal.
+        let mut patches = self
+            .get_text(&self.api_url(&format!(
+                "/repos/{}/{}/git/commits/{}.diff",
+                owner, repo, sha
+            )))
+            .await
+            .map(|diff| split_diff(&diff))
+            .unwrap_or_default();
+
         let files = detail
             .files
             .unwrap_or_default()
             .into_iter()
             .filter_map(|f| {
+                let filename = f.filename?;
                 Some(CommitFile {
-                    filename: f.filename?,
+                    patch: patches.remove(&filename),
+                    filename,
                     status: f.status.unwrap_or_else(|| "modified".to_string()),
                     additions: f.additions.unwrap_or(0),
                     deletions: f.deletions.unwrap_or(0),
-                    patch: None,
                 })
             })
             .collect();
@@ -452,29 +804,25 @@ impl Forge for Gitea {
     }
 
     async fn get_pr_diff(&self, owner: &str, repo: &str, number: u64) -> Result<String> {
-        let url = format!(
-            "https://{}/api/v1/repos/{}/{}/pulls/{}.diff",
-            self.host, owner, repo, number
-        );
+        let url = self.api_url(&format!("/repos/{}/{}/pulls/{}.diff", owner, repo, number));
+        self.get_text(&url).await
+    }
+
+    async fn stream_pr_diff(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Option<reqwest::Response>> {
+        let url = self.api_url(&format!("/repos/{}/{}/pulls/{}.diff", owner, repo, number));
         let response = self
             .client
             .get(&url)
             .header("Authorization", format!("token {}", self.token))
-            .send()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(GritError::Api(format!(
-                "Failed to fetch diff: {}",
-                response.status()
-            )));
-        }
-
-        response
-            .text()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))
+            .timeout(http::DOWNLOAD_TIMEOUT)
+            .send_retrying()
+            .await?;
+        http::check(response, "Fetch diff").await.map(Some)
     }
 
     async fn merge_pr(&self, owner: &str, repo: &str, number: u64, method: &str) -> Result<()> {
@@ -492,17 +840,46 @@ impl Forge for Gitea {
             .post(&url)
             .header("Authorization", format!("token {}", self.token))
             .json(&body)
-            .send()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
+            .send_retrying()
+            .await?;
+        http::check(response, "Merge").await?;
+        Ok(())
+    }
 
-        if !response.status().is_success() {
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown error".to_string());
-            return Err(GritError::Api(format!("Merge failed: {}", text)));
-        }
+    async fn enable_auto_merge(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        method: &str,
+        delete_branch: bool,
+    ) -> Result<()> {
+        let url = self.api_url(&format!("/repos/{}/{}/pulls/{}/merge", owner, repo, number));
+        let body = serde_json::json!({
+            "Do": method,
+            "merge_when_checks_succeed": true,
+            "delete_branch_after_merge": delete_branch,
+        });
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("token {}", self.token))
+            .json(&body)
+            .send_retrying()
+            .await?;
+        http::check(response, "Set auto-merge").await?;
+        Ok(())
+    }
+
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<()> {
+        let url = self.api_url(&format!("/repos/{}/{}/branches/{}", owner, repo, branch));
+        let response = self
+            .client
+            .delete(&url)
+            .header("Authorization", format!("token {}", self.token))
+            .send_retrying()
+            .await?;
+        http::check(response, "Delete branch").await?;
         Ok(())
     }
 
@@ -514,20 +891,27 @@ impl Forge for Gitea {
             .patch(&url)
             .header("Authorization", format!("token {}", self.token))
             .json(&body)
-            .send()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown error".to_string());
-            return Err(GritError::Api(format!("Close PR failed: {}", text)));
-        }
+            .send_retrying()
+            .await?;
+        http::check(response, "Close PR").await?;
         Ok(())
     }
 
+    async fn get_issue(&self, owner: &str, repo: &str, number: u64) -> Result<Issue> {
+        let url = self.api_url(&format!("/repos/{}/{}/issues/{}", owner, repo, number));
+        let issue: GtIssue = self.get_json(&url).await?;
+        Ok(gt_issue(issue))
+    }
+
+    async fn get_issue_body(&self, owner: &str, repo: &str, number: u64) -> Result<Option<String>> {
+        let url = self.api_url(&format!("/repos/{}/{}/issues/{}", owner, repo, number));
+        let issue: serde_json::Value = self.get_json(&url).await?;
+        Ok(issue
+            .get("body")
+            .and_then(|b| b.as_str())
+            .map(str::to_string))
+    }
+
     async fn close_issue(&self, owner: &str, repo: &str, number: u64) -> Result<()> {
         let url = self.api_url(&format!("/repos/{}/{}/issues/{}", owner, repo, number));
         let body = serde_json::json!({ "state": "closed" });
@@ -536,53 +920,1143 @@ impl Forge for Gitea {
             .patch(&url)
             .header("Authorization", format!("token {}", self.token))
             .json(&body)
-            .send()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown error".to_string());
-            return Err(GritError::Api(format!("Close issue failed: {}", text)));
-        }
+            .send_retrying()
+            .await?;
+        http::check(response, "Close issue").await?;
         Ok(())
     }
 
-    async fn comment(&self, owner: &str, repo: &str, number: u64, body: &str) -> Result<()> {
-        // In Gitea, PRs are issues — comment via issues API
-        let url = self.api_url(&format!(
-            "/repos/{}/{}/issues/{}/comments",
-            owner, repo, number
-        ));
-        let payload = serde_json::json!({ "body": body });
+    async fn locate_repo(&self, owner: &str, repo: &str) -> Result<(String, String)> {
+        // Gitea redirects renamed repos too
+        let url = self.api_url(&format!("/repos/{}/{}", owner, repo));
+        let info: GtRepo = self.get_json(&url).await?;
+        Ok(info
+            .full_name
+            .as_deref()
+            .and_then(split_repo_path)
+            .unwrap_or_else(|| (owner.to_string(), info.name)))
+    }
+
+    async fn get_repo_meta(&self, owner: &str, repo: &str) -> Result<RepoMeta> {
+        let url = self.api_url(&format!("/repos/{}/{}", owner, repo));
+        let info: GtRepo = self.get_json(&url).await?;
+        let url = self.api_url(&format!("/repos/{}/{}/topics", owner, repo));
+        let topics: GtTopics = self.get_json(&url).await?;
+        Ok(RepoMeta {
+            description: info.description.unwrap_or_default(),
+            topics: topics.topics,
+        })
+    }
+
+    async fn update_repo_meta(&self, owner: &str, repo: &str, meta: &RepoMeta) -> Result<()> {
+        let url = self.api_url(&format!("/repos/{}/{}", owner, repo));
+        let body = serde_json::json!({ "description": meta.description });
+        self.send_json(reqwest::Method::PATCH, &url, &body, "Update repository")
+            .await?;
+
+        let url = self.api_url(&format!("/repos/{}/{}/topics", owner, repo));
+        let body = serde_json::json!({ "topics": meta.topics });
+        self.send_json(reqwest::Method::PUT, &url, &body, "Update topics")
+            .await
+    }
+
+    async fn fork_repo(&self, owner: &str, repo: &str) -> Result<Repository> {
+        let url = self.api_url(&format!("/repos/{}/{}/forks", owner, repo));
         let response = self
             .client
             .post(&url)
             .header("Authorization", format!("token {}", self.token))
-            .json(&payload)
-            .send()
+            .json(&serde_json::json!({}))
+            .send_retrying()
+            .await?;
+        let response = http::check(response, "Fork").await?;
+        let fork: GtRepo = response.json().await.map_err(http::api_error)?;
+        Ok(gt_repository(fork))
+    }
+
+    async fn get_fork_status(&self, owner: &str, repo: &str) -> Result<Option<ForkStatus>> {
+        let url = self.api_url(&format!("/repos/{}/{}", owner, repo));
+        let fork: GtRepo = self.get_json(&url).await?;
+        let Some(upstream) = fork.parent else {
+            return Ok(None);
+        };
+        let (Some(name), Some(branch), Some(upstream_branch)) = (
+            upstream.full_name,
+            fork.default_branch,
+            upstream.default_branch,
+        ) else {
+            return Ok(None);
+        };
+
+        // Each side compared from the other: what the head has that the base lacks
+        let upstream_owner = upstream.owner.map(|o| o.login).unwrap_or_default();
+        let url = self.api_url(&format!(
+            "/repos/{}/compare/{}...{}:{}",
+            name, upstream_branch, owner, branch
+        ));
+        let ahead: GtCompare = self.get_json(&url).await?;
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/compare/{}...{}:{}",
+            owner, repo, branch, upstream_owner, upstream_branch
+        ));
+        let behind: GtCompare = self.get_json(&url).await?;
+        Ok(Some(ForkStatus {
+            upstream: name,
+            branch,
+            upstream_branch,
+            ahead: ahead.total_commits,
+            behind: behind.total_commits,
+        }))
+    }
+
+    async fn sync_fork(&self, owner: &str, repo: &str, branch: &str) -> Result<()> {
+        let url = self.api_url(&format!("/repos/{}/{}/merge-upstream", owner, repo));
+        let body = serde_json::json!({ "branch": branch });
+        self.send_json(reqwest::Method::POST, &url, &body, "Sync fork")
             .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
+    }
 
-        if !response.status().is_success() {
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown error".to_string());
-            return Err(GritError::Api(format!("Comment failed: {}", text)));
+    async fn is_starred(&self, owner: &str, repo: &str) -> Result<bool> {
+        let url = self.api_url(&format!("/user/starred/{}/{}", owner, repo));
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("token {}", self.token))
+            .send_retrying()
+            .await?;
+        // 204 when starred, 404 when not
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(false);
         }
-        Ok(())
+        http::check(response, "Star lookup").await?;
+        Ok(true)
     }
-}
 
-fn gt_pr_state(state: &str, merged: Option<bool>) -> PrState {
-    if merged == Some(true) {
-        PrState::Merged
-    } else if state == "closed" {
+    async fn set_star(&self, owner: &str, repo: &str, starred: bool) -> Result<()> {
+        let url = self.api_url(&format!("/user/starred/{}/{}", owner, repo));
+        let method = if starred {
+            reqwest::Method::PUT
+        } else {
+            reqwest::Method::DELETE
+        };
+        let response = self
+            .client
+            .request(method, &url)
+            .header("Authorization", format!("token {}", self.token))
+            .send_retrying()
+            .await?;
+        http::check(response, "Star").await?;
+        Ok(())
+    }
+
+    async fn set_subscription(
+        &self,
+        owner: &str,
+        repo: &str,
+        subscription: Subscription,
+    ) -> Result<()> {
+        // Gitea only knows watching or not; not watching still notifies on participation
+        let method = match subscription {
+            Subscription::Watching => reqwest::Method::PUT,
+            Subscription::Participating => reqwest::Method::DELETE,
+            Subscription::Ignoring => {
+                return Err(GritError::Api(
+                    "Gitea can't ignore a repository; choose Participating instead".into(),
+                ))
+            }
+        };
+        let url = self.api_url(&format!("/repos/{}/{}/subscription", owner, repo));
+        let response = self
+            .client
+            .request(method, &url)
+            .header("Authorization", format!("token {}", self.token))
+            .send_retrying()
+            .await?;
+        http::check(response, "Watch").await?;
+        Ok(())
+    }
+
+    async fn comment(&self, owner: &str, repo: &str, number: u64, body: &str) -> Result<()> {
+        // In Gitea, PRs are issues — comment via issues API
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/issues/{}/comments",
+            owner, repo, number
+        ));
+        let payload = serde_json::json!({ "body": body });
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("token {}", self.token))
+            .json(&payload)
+            .send_retrying()
+            .await?;
+        http::check(response, "Comment").await?;
+        Ok(())
+    }
+
+    /// Review comments are issue comments too, so they share the endpoint
+    async fn add_reaction(
+        &self,
+        owner: &str,
+        repo: &str,
+        target: ReactionTarget,
+        reaction: Reaction,
+    ) -> Result<()> {
+        let url = match target {
+            ReactionTarget::Pr(number) => self.api_url(&format!(
+                "/repos/{}/{}/issues/{}/reactions",
+                owner, repo, number
+            )),
+            ReactionTarget::Comment { id, .. } | ReactionTarget::ReviewComment { id, .. } => self
+                .api_url(&format!(
+                    "/repos/{}/{}/issues/comments/{}/reactions",
+                    owner, repo, id
+                )),
+        };
+        let body = serde_json::json!({ "content": reaction.as_api_str() });
+        self.send_json(reqwest::Method::POST, &url, &body, "Reaction")
+            .await
+    }
+
+    async fn list_pr_commits(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<Commit>> {
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/pulls/{}/commits?limit=50",
+            owner, repo, number
+        ));
+        let commits: Vec<GtCommit> = self.get_json_all(&url).await?;
+        Ok(commits.into_iter().map(gt_commit).collect())
+    }
+
+    async fn list_pr_files(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<CommitFile>> {
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/pulls/{}/files?limit=50",
+            owner, repo, number
+        ));
+        let files: Vec<GtCommitFile> = self.get_json_all(&url).await?;
+        // Same as commits: patches come from the raw diff, best-effort
+        let mut patches = self
+            .get_pr_diff(owner, repo, number)
+            .await
+            .map(|diff| split_diff(&diff))
+            .unwrap_or_default();
+
+        Ok(files
+            .into_iter()
+            .filter_map(|f| {
+                let filename = f.filename?;
+                Some(CommitFile {
+                    patch: patches.remove(&filename),
+                    filename,
+                    status: f.status.unwrap_or_else(|| "modified".to_string()),
+                    additions: f.additions.unwrap_or(0),
+                    deletions: f.deletions.unwrap_or(0),
+                })
+            })
+            .collect())
+    }
+
+    async fn list_pr_comments(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<Comment>> {
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/issues/{}/comments?limit=50",
+            owner, repo, number
+        ));
+        let comments: Vec<GtComment> = self.get_json_all(&url).await?;
+
+        Ok(comments
+            .into_iter()
+            .map(|c| Comment {
+                id: c.id,
+                author: c
+                    .user
+                    .map(|u| u.login)
+                    .unwrap_or_else(|| "unknown".to_string()),
+                body: c.body.unwrap_or_default(),
+                created_at: parse_optional_datetime(c.created_at.as_deref()),
+            })
+            .collect())
+    }
+
+    async fn list_milestones(&self, owner: &str, repo: &str) -> Result<Vec<Milestone>> {
+        let url = self.api_url(&format!("/repos/{}/{}/milestones?state=open", owner, repo));
+        let milestones: Vec<GtMilestone> = self.get_json(&url).await?;
+
+        Ok(milestones
+            .into_iter()
+            .map(|m| Milestone {
+                id: m.id,
+                title: m.title,
+                due_on: m.due_on.as_deref().map(parse_datetime),
+            })
+            .collect())
+    }
+
+    async fn set_milestone(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        milestone: Option<u64>,
+    ) -> Result<()> {
+        let url = self.api_url(&format!("/repos/{}/{}/issues/{}", owner, repo, number));
+        // 0 clears the milestone
+        let body = serde_json::json!({ "milestone": milestone.unwrap_or(0) });
+        let response = self
+            .client
+            .patch(&url)
+            .header("Authorization", format!("token {}", self.token))
+            .json(&body)
+            .send_retrying()
+            .await?;
+        http::check(response, "Set milestone").await?;
+        Ok(())
+    }
+
+    async fn list_labels(&self, owner: &str, repo: &str) -> Result<Vec<Label>> {
+        Ok(self
+            .repo_labels(owner, repo)
+            .await?
+            .into_iter()
+            .map(gt_label)
+            .collect())
+    }
+
+    // PRs are issues as far as labels are concerned, so `kind` doesn't matter
+    async fn add_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        _kind: &str,
+        number: u64,
+        labels: &[String],
+    ) -> Result<()> {
+        let ids = self.label_ids(owner, repo, labels).await?;
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/issues/{}/labels",
+            owner, repo, number
+        ));
+        let body = serde_json::json!({ "labels": ids });
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("token {}", self.token))
+            .json(&body)
+            .send_retrying()
+            .await?;
+        http::check(response, "Add labels").await?;
+        Ok(())
+    }
+
+    async fn remove_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        _kind: &str,
+        number: u64,
+        labels: &[String],
+    ) -> Result<()> {
+        for id in self.label_ids(owner, repo, labels).await? {
+            let url = self.api_url(&format!(
+                "/repos/{}/{}/issues/{}/labels/{}",
+                owner, repo, number, id
+            ));
+            let response = self
+                .client
+                .delete(&url)
+                .header("Authorization", format!("token {}", self.token))
+                .send_retrying()
+                .await?;
+            http::check(response, "Remove label").await?;
+        }
+        Ok(())
+    }
+
+    async fn list_issue_templates(&self, owner: &str, repo: &str) -> Result<Vec<IssueTemplate>> {
+        let url = self.api_url(&format!("/repos/{}/{}/issue_templates", owner, repo));
+        let templates: Vec<GtIssueTemplate> = self.get_json(&url).await?;
+        Ok(templates
+            .into_iter()
+            // Issue forms (`.yml`) aren't text for the editor
+            .filter(|t| t.file_name.to_lowercase().ends_with(".md"))
+            .map(|t| IssueTemplate {
+                name: t.name,
+                about: Some(t.about).filter(|a| !a.is_empty()),
+                title: t.title,
+                labels: t.labels.unwrap_or_default(),
+                assignees: t.assignees.unwrap_or_default(),
+                body: t.content,
+            })
+            .collect())
+    }
+
+    async fn create_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        fields: &IssueFields,
+        body: &str,
+    ) -> Result<u64> {
+        let url = self.api_url(&format!("/repos/{}/{}/issues", owner, repo));
+        let payload = serde_json::json!({
+            "title": fields.title,
+            "body": body,
+            "labels": self.label_ids(owner, repo, &fields.labels).await?,
+            "assignees": fields.assignees,
+            "milestone": fields.milestone.unwrap_or(0),
+        });
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("token {}", self.token))
+            .json(&payload)
+            .send_retrying()
+            .await?;
+        let response = http::check(response, "Create issue").await?;
+        let issue: GtIssue = response.json().await.map_err(http::api_error)?;
+        Ok(issue.number)
+    }
+
+    // PRs are issues as far as these fields are concerned, so `kind` doesn't
+    // matter. Labels take ids and have their own endpoint
+    async fn update_fields(
+        &self,
+        owner: &str,
+        repo: &str,
+        _kind: &str,
+        number: u64,
+        fields: &IssueFields,
+    ) -> Result<()> {
+        let issue = self.api_url(&format!("/repos/{}/{}/issues/{}", owner, repo, number));
+        // 0 clears the milestone
+        let payload = serde_json::json!({
+            "title": fields.title,
+            "assignees": fields.assignees,
+            "milestone": fields.milestone.unwrap_or(0),
+        });
+        self.send_json(reqwest::Method::PATCH, &issue, &payload, "Update")
+            .await?;
+        let ids = self.label_ids(owner, repo, &fields.labels).await?;
+        let labels = serde_json::json!({ "labels": ids });
+        self.send_json(
+            reqwest::Method::PUT,
+            &format!("{}/labels", issue),
+            &labels,
+            "Update labels",
+        )
+        .await
+    }
+
+    async fn update_pr(&self, owner: &str, repo: &str, number: u64, body: &str) -> Result<()> {
+        let url = self.api_url(&format!("/repos/{}/{}/pulls/{}", owner, repo, number));
+        let payload = serde_json::json!({ "body": body });
+        self.send_json(reqwest::Method::PATCH, &url, &payload, "Update description")
+            .await
+    }
+
+    async fn get_pr_checks(&self, owner: &str, repo: &str, number: u64) -> Result<PrChecks> {
+        let url = self.api_url(&format!("/repos/{}/{}/pulls/{}", owner, repo, number));
+        let pr: GtPullRequest = self.get_json(&url).await?;
+        let Some(sha) = pr.head.and_then(|h| h.sha) else {
+            return Ok(PrChecks::default());
+        };
+
+        // Gitea Actions report through commit statuses too
+        let url = self.api_url(&format!("/repos/{}/{}/commits/{}/status", owner, repo, sha));
+        let combined: GtCombinedStatus = self.get_json(&url).await?;
+
+        let mut checks = PrChecks::default();
+        for status in combined.statuses {
+            match status.status.as_str() {
+                "success" | "warning" => checks.passed += 1,
+                "failure" | "error" => checks.failed += 1,
+                _ => checks.pending += 1,
+            }
+        }
+        Ok(checks)
+    }
+
+    async fn get_repo_stats(&self, owner: &str, repo: &str) -> Result<RepoStats> {
+        let url = self.api_url(&format!("/repos/{}/{}", owner, repo));
+        let meta: serde_json::Value = self.get_json(&url).await?;
+
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/issues?state=closed&type=issues&limit=1",
+            owner, repo
+        ));
+        let (_, closed_issues) = self.get_json_paged::<serde_json::Value>(&url).await?;
+
+        // Gitea doesn't count merged PRs; split the closed total the way the
+        // latest page of closed PRs splits
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/pulls?state=closed&limit=50",
+            owner, repo
+        ));
+        let (closed, page_info) = self.get_json_paged::<GtPullRequest>(&url).await?;
+        let closed_total = page_info.total_count.unwrap_or(closed.len() as u64);
+        let merged_sample = closed.iter().filter(|pr| pr.merged == Some(true)).count();
+        let merged_prs = match closed.len() {
+            0 => 0,
+            n => (closed_total as f64 * merged_sample as f64 / n as f64).round() as u64,
+        };
+
+        let url = self.api_url(&format!("/repos/{}/{}/languages", owner, repo));
+        let languages: HashMap<String, u64> = self.get_json(&url).await?;
+
+        // No contributors endpoint either: count authors of the latest commits
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/commits?limit=50&stat=false&verification=false&files=false",
+            owner, repo
+        ));
+        let commits: Vec<GtCommit> = self.get_json(&url).await.unwrap_or_default();
+        let mut authors: HashMap<String, u64> = HashMap::new();
+        for name in commits.into_iter().filter_map(|c| c.commit?.author?.name) {
+            *authors.entry(name).or_default() += 1;
+        }
+        let mut contributors: Vec<(String, u64)> = authors.into_iter().collect();
+        contributors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        contributors.truncate(INSIGHTS_TOP);
+
+        let since = chrono::Utc::now() - chrono::Duration::days(RECENT_ACTIVITY_DAYS);
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/commits?since={}&limit=1&stat=false&verification=false&files=false",
+            owner,
+            repo,
+            urlencoding::encode(&since.to_rfc3339())
+        ));
+        let (recent, recent_info) = self
+            .get_json_paged::<serde_json::Value>(&url)
+            .await
+            .unwrap_or_default();
+
+        Ok(RepoStats {
+            open_issues: meta["open_issues_count"].as_u64().unwrap_or(0),
+            closed_issues: closed_issues.total_count.unwrap_or(0),
+            open_prs: meta["open_pr_counter"].as_u64().unwrap_or(0),
+            merged_prs,
+            closed_prs: closed_total - merged_prs,
+            contributors,
+            languages: language_shares(languages),
+            recent_commits: recent_info.total_count.unwrap_or(recent.len() as u64),
+        })
+    }
+
+    async fn get_commit_checks(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<CommitCheck>> {
+        let url = self.api_url(&format!("/repos/{}/{}/commits/{}/status", owner, repo, sha));
+        let combined: GtCombinedStatus = self.get_json(&url).await?;
+        // Statuses carry no start time, so no duration either
+        Ok(combined
+            .statuses
+            .into_iter()
+            .map(|status| CommitCheck {
+                name: status.context.unwrap_or_else(|| "status".to_string()),
+                state: match status.status.as_str() {
+                    "success" | "warning" => ChecksStatus::Success,
+                    "failure" | "error" => ChecksStatus::Failure,
+                    _ => ChecksStatus::Pending,
+                },
+                duration: None,
+            })
+            .collect())
+    }
+
+    async fn list_reviews(&self, owner: &str, repo: &str, number: u64) -> Result<PrReviews> {
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/pulls/{}/reviews?limit=50",
+            owner, repo, number
+        ));
+        let reviews: Vec<GtReview> = self.get_json_all(&url).await?;
+
+        let mut result = PrReviews::default();
+        for review in reviews {
+            let Some(user) = review.user else {
+                continue;
+            };
+            let state = match review.state.as_str() {
+                "APPROVED" => ReviewState::Approved,
+                "REQUEST_CHANGES" => ReviewState::ChangesRequested,
+                "COMMENT" => ReviewState::Commented,
+                // Gitea records review requests as reviews in this state
+                "REQUEST_REVIEW" => {
+                    result.requested.push(user.login);
+                    continue;
+                }
+                _ => continue,
+            };
+            result.reviews.push(Review {
+                reviewer: user.login,
+                state,
+                submitted_at: review.submitted_at.as_deref().map(parse_datetime),
+            });
+        }
+
+        Ok(result)
+    }
+
+    async fn list_review_requests(&self, _username: &str) -> Result<Vec<ReviewRequest>> {
+        let issues = self.search_open_prs("review_requested").await?;
+
+        Ok(issues
+            .into_iter()
+            .filter_map(|issue| {
+                let repo = issue.repository?;
+                Some(ReviewRequest {
+                    repo_owner: repo.owner,
+                    repo_name: repo.name,
+                    pr_number: issue.number,
+                    pr_title: issue.title,
+                    author: issue
+                        .user
+                        .map(|u| u.login)
+                        .unwrap_or_else(|| "unknown".to_string()),
+                    updated_at: parse_optional_datetime(issue.updated_at.as_deref()),
+                })
+            })
+            .collect())
+    }
+
+    async fn list_assigned_issues(&self, _username: &str) -> Result<Vec<IssueRef>> {
+        self.search_issue_refs("type=issues&", "assigned").await
+    }
+
+    async fn list_mentions(&self, _username: &str) -> Result<Vec<IssueRef>> {
+        self.search_issue_refs("", "mentioned").await
+    }
+
+    /// Gitea's search filters by people only as the signed-in user, so
+    /// `author:` and the like take `@me` alone
+    async fn search_issues(&self, query: &str, page: u32) -> Result<PagedResult<IssueRef>> {
+        let query = SearchQuery::parse(query).map_err(GritError::Api)?;
+        let state = match query.state {
+            SearchState::Open => "open",
+            SearchState::Closed => "closed",
+            SearchState::Merged => {
+                return Err(GritError::Api(
+                    "Gitea can't tell merged PRs from closed ones in a search".into(),
+                ))
+            }
+        };
+        let mut params = format!("state={}&limit={}&page={}", state, self.page_size, page);
+        match query.is_pr {
+            Some(true) => params.push_str("&type=pulls"),
+            Some(false) => params.push_str("&type=issues"),
+            None => {}
+        }
+        for (qualifier, filter, user) in [
+            ("author", "created", &query.author),
+            ("assignee", "assigned", &query.assignee),
+            ("mentions", "mentioned", &query.mentions),
+            (
+                "review-requested",
+                "review_requested",
+                &query.review_requested,
+            ),
+        ] {
+            match user.as_deref() {
+                Some("@me") => params.push_str(&format!("&{}=true", filter)),
+                Some(_) => {
+                    return Err(GritError::Api(format!(
+                        "Gitea only searches {}:@me",
+                        qualifier
+                    )))
+                }
+                None => {}
+            }
+        }
+        if !query.labels.is_empty() {
+            params.push_str(&format!(
+                "&labels={}",
+                urlencoding::encode(&query.labels.join(","))
+            ));
+        }
+        if let Some(owner) = &query.owner {
+            params.push_str(&format!("&owner={}", urlencoding::encode(owner)));
+        }
+        if !query.text.is_empty() {
+            params.push_str(&format!("&q={}", urlencoding::encode(&query.text)));
+        }
+        let url = self.api_url(&format!("/repos/issues/search?{}", params));
+        let (issues, page_info) = self.get_json_paged::<GtIssue>(&url).await?;
+        Ok(PagedResult {
+            items: issues.into_iter().filter_map(gt_issue_ref).collect(),
+            page_info,
+        })
+    }
+
+    async fn list_my_prs(&self, _username: &str) -> Result<Vec<MyPr>> {
+        let issues = self.search_open_prs("created").await?;
+
+        let mut my_prs = Vec::with_capacity(issues.len());
+        for issue in issues {
+            let Some(repo) = issue.repository else {
+                continue;
+            };
+            let checks_status = self
+                .get_check_status(&repo.owner, &repo.name, issue.number)
+                .await
+                .unwrap_or(ChecksStatus::None);
+
+            my_prs.push(MyPr {
+                repo_owner: repo.owner,
+                repo_name: repo.name,
+                number: issue.number,
+                title: issue.title,
+                state: gt_pr_state(&issue.state, None),
+                checks_status,
+                mergeable: MergeableState::Unknown,
+                updated_at: parse_optional_datetime(issue.updated_at.as_deref()),
+            });
+        }
+
+        Ok(my_prs)
+    }
+
+    async fn list_action_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        page: u32,
+    ) -> Result<PagedResult<ActionRun>> {
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/actions/runs?limit={}&page={}",
+            owner, repo, self.page_size, page
+        ));
+        let response: GtActionRuns = match self.get_json(&url).await {
+            Ok(response) => response,
+            // Gitea before 1.23 only lists the jobs of runs
+            Err(GritError::NotFound(_)) => return self.list_action_tasks(owner, repo, page).await,
+            Err(e) => return Err(e),
+        };
+
+        let runs = response
+            .workflow_runs
+            .into_iter()
+            .map(|run| {
+                let (status, conclusion) = gt_run_status(&run.status, run.conclusion.as_deref());
+                let workflow = run
+                    .path
+                    .as_deref()
+                    .and_then(|p| p.rsplit('/').next())
+                    .map(|p| p.split('@').next().unwrap_or(p).to_string());
+                // Runs only carry a title for the triggering commit; fall back
+                // to the workflow file name
+                let name = run
+                    .display_title
+                    .filter(|t| !t.is_empty())
+                    .or_else(|| workflow.clone())
+                    .unwrap_or_else(|| format!("Run #{}", run.id));
+                let started_at = parse_optional_datetime(run.started_at.as_deref());
+                let duration = match (status, run.completed_at.as_deref()) {
+                    (ActionStatus::Completed, Some(done)) => (parse_datetime(done) - started_at)
+                        .num_seconds()
+                        .try_into()
+                        .ok(),
+                    _ => None,
+                };
+                ActionRun {
+                    id: run.id,
+                    name,
+                    status,
+                    conclusion,
+                    branch: run.head_branch.unwrap_or_else(|| "unknown".to_string()),
+                    event: run.event.unwrap_or_else(|| "push".to_string()),
+                    created_at: started_at,
+                    duration,
+                    workflow,
+                }
+            })
+            .collect();
+
+        Ok(PagedResult {
+            items: runs,
+            page_info: PageInfo {
+                total_count: response.total_count,
+                total_pages: None,
+            },
+        })
+    }
+
+    async fn list_artifacts(&self, owner: &str, repo: &str, run_id: u64) -> Result<Vec<Artifact>> {
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/actions/runs/{}/artifacts",
+            owner, repo, run_id
+        ));
+        let response: GtArtifacts = self.get_json(&url).await?;
+        Ok(response
+            .artifacts
+            .into_iter()
+            .map(|a| Artifact {
+                id: a.id,
+                name: a.name,
+                size: a.size_in_bytes,
+                expired: a.expired,
+                download_url: a.archive_download_url,
+            })
+            .collect())
+    }
+
+    async fn download_artifact(&self, artifact: &Artifact) -> Result<reqwest::Response> {
+        let response = self
+            .client
+            .get(&artifact.download_url)
+            .header("Authorization", format!("token {}", self.token))
+            .timeout(http::DOWNLOAD_TIMEOUT)
+            .send_retrying()
+            .await?;
+        http::check(response, "Download artifact").await
+    }
+
+    async fn get_check_status(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<ChecksStatus> {
+        Ok(self.get_pr_checks(owner, repo, pr_number).await?.status())
+    }
+
+    async fn submit_review(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        event: &str,
+        body: &str,
+        comments: &[ReviewComment],
+    ) -> Result<()> {
+        let url = self.api_url(&format!(
+            "/repos/{}/{}/pulls/{}/reviews",
+            owner, repo, number
+        ));
+        // Positions are line numbers; 0 means "not on this side"
+        let comments: Vec<serde_json::Value> = comments
+            .iter()
+            .map(|c| {
+                serde_json::json!({
+                    "path": c.path,
+                    "body": c.body,
+                    "new_position": c.line.new.unwrap_or(0),
+                    "old_position": if c.line.new.is_some() { 0 } else { c.line.old.unwrap_or(0) },
+                })
+            })
+            .collect();
+        // Gitea names the approve event after the resulting review state
+        let event = match event {
+            "APPROVE" => "APPROVED",
+            other => other,
+        };
+        let payload = serde_json::json!({
+            "event": event,
+            "body": body,
+            "comments": comments,
+        });
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("token {}", self.token))
+            .json(&payload)
+            .send_retrying()
+            .await?;
+        http::check(response, "Review").await?;
+        Ok(())
+    }
+}
+
+fn gt_repository(r: GtRepo) -> Repository {
+    Repository {
+        owner: r
+            .owner
+            .map(|o| o.login)
+            .unwrap_or_else(|| "unknown".to_string()),
+        name: r.name,
+        description: r.description.filter(|d| !d.is_empty()),
+        url: r.html_url.unwrap_or_default(),
+        stars: r.stars_count.unwrap_or(0),
+        updated_at: parse_optional_datetime(r.updated_at.as_deref()),
+    }
+}
+
+fn gt_label(l: GtLabel) -> Label {
+    Label {
+        name: l.name,
+        color: l.color.map(|c| c.trim_start_matches('#').to_string()),
+    }
+}
+
+fn gt_issue(i: GtIssue) -> Issue {
+    Issue {
+        number: i.number,
+        title: i.title,
+        state: if i.state == "closed" {
+            IssueState::Closed
+        } else {
+            IssueState::Open
+        },
+        author: i
+            .user
+            .map(|u| u.login)
+            .unwrap_or_else(|| "unknown".to_string()),
+        labels: i
+            .labels
+            .unwrap_or_default()
+            .into_iter()
+            .map(gt_label)
+            .collect(),
+        comments: i.comments.unwrap_or(0),
+        milestone: i.milestone.map(|m| m.title),
+        assignees: i
+            .assignees
+            .unwrap_or_default()
+            .into_iter()
+            .map(|u| u.login)
+            .collect(),
+        created_at: parse_optional_datetime(i.created_at.as_deref()),
+        updated_at: parse_optional_datetime(i.updated_at.as_deref()),
+    }
+}
+
+/// A search hit; hits always carry their repo
+fn gt_issue_ref(issue: GtIssue) -> Option<IssueRef> {
+    let repo = issue.repository?;
+    Some(IssueRef {
+        repo_owner: repo.owner,
+        repo_name: repo.name,
+        number: issue.number,
+        title: issue.title,
+        author: issue
+            .user
+            .map(|u| u.login)
+            .unwrap_or_else(|| "unknown".to_string()),
+        is_pr: issue.pull_request.is_some(),
+        updated_at: parse_optional_datetime(issue.updated_at.as_deref()),
+    })
+}
+
+fn gt_commit(c: GtCommit) -> Commit {
+    let inner = c.commit.as_ref();
+    let message = inner
+        .and_then(|i| i.message.as_deref())
+        .and_then(|m| m.lines().next())
+        .unwrap_or("")
+        .to_string();
+    let author = inner
+        .and_then(|i| i.author.as_ref())
+        .and_then(|a| a.name.clone())
+        .unwrap_or_else(|| "unknown".to_string());
+    let date = inner
+        .and_then(|i| i.author.as_ref())
+        .and_then(|a| a.date.as_deref())
+        .map(parse_datetime)
+        .unwrap_or_else(chrono::Utc::now);
+
+    Commit {
+        sha: c.sha.unwrap_or_default(),
+        message,
+        author,
+        date,
+    }
+}
+
+fn gt_run_status(
+    status: &str,
+    conclusion: Option<&str>,
+) -> (ActionStatus, Option<ActionConclusion>) {
+    match status {
+        "queued" | "waiting" | "pending" | "requested" | "blocked" => (ActionStatus::Queued, None),
+        "in_progress" | "running" => (ActionStatus::InProgress, None),
+        _ => {
+            let conclusion = match conclusion.unwrap_or(status) {
+                "success" => ActionConclusion::Success,
+                "cancelled" => ActionConclusion::Cancelled,
+                "skipped" => ActionConclusion::Skipped,
+                _ => ActionConclusion::Failure,
+            };
+            (ActionStatus::Completed, Some(conclusion))
+        }
+    }
+}
+
+/// Fold consecutive jobs of the same run into one `ActionRun`: running while
+/// any job runs, queued while any waits, otherwise failed if any job failed
+fn gt_task_runs(tasks: Vec<GtActionTask>) -> Vec<ActionRun> {
+    let mut runs: Vec<(ActionRun, Option<String>)> = Vec::new();
+    for task in tasks {
+        let (status, conclusion) = gt_run_status(&task.status, None);
+        let started_at = parse_optional_datetime(task.run_started_at.as_deref());
+        let same_run = runs
+            .last_mut()
+            .filter(|(run, _)| task.run_number == Some(run.id) && task.workflow_id == run.workflow);
+        if let Some((run, finished)) = same_run {
+            run.status = match (run.status, status) {
+                (ActionStatus::InProgress, _) | (_, ActionStatus::InProgress) => {
+                    ActionStatus::InProgress
+                }
+                (ActionStatus::Queued, _) | (_, ActionStatus::Queued) => ActionStatus::Queued,
+                _ => ActionStatus::Completed,
+            };
+            if conclusion_rank(conclusion) > conclusion_rank(run.conclusion) {
+                run.conclusion = conclusion;
+            }
+            run.created_at = run.created_at.min(started_at);
+            *finished = finished.clone().max(task.updated_at);
+            continue;
+        }
+        let name = task
+            .display_title
+            .filter(|t| !t.is_empty())
+            .or_else(|| task.workflow_id.clone())
+            .unwrap_or_else(|| "Run".to_string());
+        runs.push((
+            ActionRun {
+                id: task.run_number.unwrap_or_default(),
+                name,
+                status,
+                conclusion,
+                branch: task.head_branch.unwrap_or_else(|| "unknown".to_string()),
+                event: task.event.unwrap_or_else(|| "push".to_string()),
+                created_at: started_at,
+                duration: None,
+                workflow: task.workflow_id,
+            },
+            task.updated_at,
+        ));
+    }
+
+    runs.into_iter()
+        .map(|(mut run, finished)| {
+            if run.status == ActionStatus::Completed {
+                run.duration = finished
+                    .as_deref()
+                    .map(parse_datetime)
+                    .and_then(|done| (done - run.created_at).num_seconds().try_into().ok());
+            } else {
+                run.conclusion = None;
+            }
+            run
+        })
+        .collect()
+}
+
+/// Failures outweigh cancellations, which outweigh successes and skips
+fn conclusion_rank(conclusion: Option<ActionConclusion>) -> u8 {
+    match conclusion {
+        Some(ActionConclusion::Failure | ActionConclusion::TimedOut) => 3,
+        Some(ActionConclusion::Cancelled) => 2,
+        Some(ActionConclusion::Success) => 1,
+        _ => 0,
+    }
+}
+
+fn gt_pr_state(state: &str, merged: Option<bool>) -> PrState {
+    if merged == Some(true) {
+        PrState::Merged
+    } else if state == "closed" {
         PrState::Closed
     } else {
         PrState::Open
     }
 }
+
+/// Split a unified `git diff` into per-file patches keyed by path. Like the
+/// `patch` GitHub returns, each starts at the first hunk; binary files have none.
+fn split_diff(diff: &str) -> HashMap<String, String> {
+    let mut patches = HashMap::new();
+    for section in diff.split("\ndiff --git ").filter(|s| !s.trim().is_empty()) {
+        let section = section.strip_prefix("diff --git ").unwrap_or(section);
+        let mut path = section
+            .lines()
+            .next()
+            .and_then(|header| header.rsplit_once(" b/"))
+            .map(|(_, b)| b.to_string());
+        let mut hunks = None;
+        for (offset, _) in section.match_indices('\n') {
+            let rest = &section[offset + 1..];
+            if let Some(p) = rest.strip_prefix("+++ b/") {
+                path = Some(p.lines().next().unwrap_or(p).to_string());
+            } else if rest.starts_with("@@") {
+                hunks = Some(rest.trim_end_matches('\n').to_string());
+                break;
+            }
+        }
+        if let (Some(path), Some(hunks)) = (path, hunks) {
+            patches.insert(path, hunks);
+        }
+    }
+    patches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "diff --git a/src/main.rs b/src/main.rs
+index 1111111..2222222 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1,2 +1,2 @@
+-fn main() {}
++fn main() { run() }
+ // end
+diff --git a/old.txt b/old.txt
+deleted file mode 100644
+index 3333333..0000000
+--- a/old.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+diff --git a/logo.png b/logo.png
+new file mode 100644
+index 0000000..4444444
+Binary files /dev/null and b/logo.png differ
+";
+
+    #[test]
+    fn patches_start_at_first_hunk() {
+        let patches = split_diff(DIFF);
+        assert_eq!(
+            patches["src/main.rs"],
+            "@@ -1,2 +1,2 @@\n-fn main() {}\n+fn main() { run() }\n // end"
+        );
+    }
+
+    #[test]
+    fn deleted_file_keeps_its_path() {
+        let patches = split_diff(DIFF);
+        assert_eq!(patches["old.txt"], "@@ -1 +0,0 @@\n-gone");
+    }
+
+    #[test]
+    fn binary_file_has_no_patch() {
+        assert!(!split_diff(DIFF).contains_key("logo.png"));
+    }
+
+    #[test]
+    fn jobs_fold_into_their_runs() {
+        let tasks: Vec<GtActionTask> = serde_json::from_str(
+            r#"[
+                {"display_title": "Fix", "workflow_id": "ci.yml", "run_number": 8,
+                 "status": "running", "run_started_at": "2024-05-01T10:00:00Z"},
+                {"display_title": "Fix", "workflow_id": "ci.yml", "run_number": 8,
+                 "status": "success", "run_started_at": "2024-05-01T10:00:00Z"},
+                {"display_title": "Add", "workflow_id": "ci.yml", "run_number": 7,
+                 "status": "success", "run_started_at": "2024-05-01T09:00:00Z",
+                 "updated_at": "2024-05-01T09:01:00Z"},
+                {"display_title": "Add", "workflow_id": "ci.yml", "run_number": 7,
+                 "status": "failure", "run_started_at": "2024-05-01T09:00:00Z",
+                 "updated_at": "2024-05-01T09:02:30Z"}
+            ]"#,
+        )
+        .unwrap();
+        let runs = gt_task_runs(tasks);
+        assert_eq!(runs.len(), 2);
+        assert_eq!((runs[0].id, runs[0].status), (8, ActionStatus::InProgress));
+        assert_eq!(runs[0].conclusion, None);
+        assert_eq!(runs[1].status, ActionStatus::Completed);
+        assert_eq!(runs[1].conclusion, Some(ActionConclusion::Failure));
+        assert_eq!(runs[1].duration, Some(150));
+        assert_eq!(runs[1].workflow.as_deref(), Some("ci.yml"));
+    }
+}
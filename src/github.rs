@@ -1,18 +1,36 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use ::http::Uri;
 use async_trait::async_trait;
+use octocrab::models::pulls::MergeableState as OctoMergeableState;
 use octocrab::models::IssueState as OctoIssueState;
-use octocrab::Octocrab;
+use octocrab::service::middleware::base_uri::BaseUriLayer;
+use octocrab::service::middleware::extra_headers::ExtraHeadersLayer;
+use octocrab::{AuthState, Octocrab, OctocrabBuilder};
+use reqwest::header::{HeaderValue, AUTHORIZATION, USER_AGENT};
 
+use crate::config::Transport;
 use crate::error::{GritError, Result};
 use crate::forge::Forge;
+use crate::http::{self, RetrySend};
 use crate::types::{
-    ActionConclusion, ActionRun, ActionStatus, ChecksStatus, Commit, CommitDetail, CommitFile,
-    CommitStats, Issue, IssueState, MyPr, PagedResult, PrState, PrStats, PrSummary, PullRequest,
-    Repository, ReviewRequest,
+    language_shares, split_repo_path, ActionConclusion, ActionRun, ActionStatus, Artifact,
+    ChecksStatus, Comment, Commit, CommitCheck, CommitDetail, CommitFile, CommitHit, CommitStats,
+    ForkStatus, Issue, IssueFields, IssueRef, IssueState, IssueTemplate, Label, MergeableState,
+    Milestone, MyPr, PageInfo, PagedResult, PrChecks, PrFilter, PrReviews, PrState, PrStats,
+    PrSummary, PullRequest, Reaction, ReactionTarget, RepoMeta, RepoStats, Repository, Review,
+    ReviewComment, ReviewRequest, ReviewState, ReviewThread, ReviewerLoad, Subscription,
+    INSIGHTS_TOP, RECENT_ACTIVITY_DAYS,
 };
 
 pub struct GitHub {
     client: Octocrab,
+    /// For the endpoints octocrab doesn't cover
+    http: reqwest::Client,
     token: String,
+    page_size: usize,
 }
 
 impl std::fmt::Debug for GitHub {
@@ -23,18 +41,270 @@ impl std::fmt::Debug for GitHub {
 
 impl From<octocrab::Error> for GritError {
     fn from(err: octocrab::Error) -> Self {
-        GritError::Api(err.to_string())
+        match err {
+            octocrab::Error::GitHub { source, .. } => {
+                let status = source.status_code.as_u16();
+                // octocrab keeps the headers to itself, so the reset time is unknown
+                if status == 403 && source.message.contains("rate limit") {
+                    GritError::RateLimited { reset: None }
+                } else {
+                    GritError::from_status(status, source.message, None)
+                }
+            }
+            // The Display of these appends a backtrace; the source is enough.
+            // Requests go through reqwest, whose errors say whether they left.
+            octocrab::Error::Service { source, .. } => match source.downcast::<reqwest::Error>() {
+                Ok(err) => http::api_error(*err),
+                Err(source) => GritError::Api(source.to_string()),
+            },
+            // Reading the body failed, so the request got there
+            octocrab::Error::Hyper { source, .. } => GritError::Api(source.to_string()),
+            err => GritError::Api(err.to_string()),
+        }
     }
 }
 
 impl GitHub {
-    pub fn new(token: String) -> Result<Self> {
-        let client = Octocrab::builder()
-            .personal_token(token.clone())
-            .build()
-            .map_err(|e| GritError::Auth(e.to_string()))?;
+    /// Run a GraphQL query and hand back its `data`, or its first error
+    async fn graphql_data(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let payload = serde_json::json!({ "query": query, "variables": variables });
+        let mut response: serde_json::Value = self.client.graphql(&payload).await?;
+        if let Some(message) = response
+            .pointer("/errors/0/message")
+            .and_then(|m| m.as_str())
+        {
+            return Err(GritError::Api(message.to_string()));
+        }
+        Ok(response["data"].take())
+    }
+
+    /// The PR as a unified diff; huge ones take a while to come down
+    async fn pr_diff_response(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<reqwest::Response> {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/pulls/{}",
+            owner, repo, number
+        );
+        let response = self
+            .http
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github.diff")
+            .timeout(http::DOWNLOAD_TIMEOUT)
+            .send_retrying()
+            .await?;
+        http::check(response, "Fetch diff").await
+    }
+
+    pub fn new(
+        token: String,
+        timeout: Duration,
+        transport: &Transport,
+        page_size: usize,
+    ) -> Result<Self> {
+        let http = http::client(timeout, transport)?;
+        let client = if *transport == Transport::default() {
+            // octocrab retries 5xx and connection failures on its own
+            Octocrab::builder()
+                .personal_token(token.clone())
+                .set_connect_timeout(Some(timeout))
+                .set_read_timeout(Some(timeout))
+                .set_write_timeout(Some(timeout))
+                .build()
+                .map_err(|e| GritError::Auth(e.to_string()))?
+        } else {
+            // octocrab's own client knows no proxies or extra certificates
+            let bearer = format!("Bearer {}", token)
+                .parse()
+                .map_err(|_| GritError::Auth("Token isn't a valid header".into()))?;
+            let headers = vec![
+                (USER_AGENT, HeaderValue::from_static("grit")),
+                (AUTHORIZATION, bearer),
+            ];
+            let Ok(client) = OctocrabBuilder::new_empty()
+                .with_service(http::ReqwestService(http.clone()))
+                .with_layer(&BaseUriLayer::new(Uri::from_static(
+                    "https://api.github.com",
+                )))
+                .with_layer(&ExtraHeadersLayer::new(Arc::new(headers)))
+                .with_auth(AuthState::None)
+                .build();
+            client
+        };
+
+        Ok(Self {
+            client,
+            http,
+            token,
+            page_size,
+        })
+    }
+
+    /// Roll up the check runs on a commit into a single status
+    async fn check_status_for_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<ChecksStatus> {
+        Ok(self.checks_for_sha(owner, repo, sha).await?.status())
+    }
+
+    /// Path -> (blob sha, mode) for every file in a commit's tree
+    async fn tree_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<HashMap<String, (String, String)>> {
+        let url = format!("/repos/{}/{}/git/trees/{}?recursive=1", owner, repo, sha);
+        let response: serde_json::Value = self.client.get(&url, None::<&()>).await?;
+        Ok(response["tree"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry["type"] == "blob")
+            .filter_map(|entry| {
+                Some((
+                    entry["path"].as_str()?.to_string(),
+                    (
+                        entry["sha"].as_str()?.to_string(),
+                        entry["mode"].as_str()?.to_string(),
+                    ),
+                ))
+            })
+            .collect())
+    }
+
+    /// Count the check runs on a commit by outcome
+    async fn checks_for_sha(&self, owner: &str, repo: &str, sha: &str) -> Result<PrChecks> {
+        let url = format!(
+            "/repos/{}/{}/commits/{}/check-runs?per_page=100",
+            owner, repo, sha
+        );
+        let response: serde_json::Value = self.client.get(&url, None::<&()>).await?;
+        Ok(gh_check_runs(&response))
+    }
+
+    /// Review requests and your open PRs from a single GraphQL query
+    async fn home_prs_graphql(&self, username: &str) -> Result<(Vec<ReviewRequest>, Vec<MyPr>)> {
+        let query = r#"
+            fragment HomePr on PullRequest {
+              number
+              title
+              updatedAt
+              isDraft
+              mergeable
+              author { login }
+              repository { name owner { login } }
+              commits(last: 1) { nodes { commit { statusCheckRollup { state } } } }
+            }
+            query($reviews: String!, $mine: String!, $first: Int!) {
+              reviews: search(query: $reviews, type: ISSUE, first: $first) {
+                nodes { ...HomePr }
+              }
+              mine: search(query: $mine, type: ISSUE, first: $first) {
+                nodes { ...HomePr }
+              }
+            }"#;
+        let payload = serde_json::json!({
+            "query": query,
+            "variables": {
+                "reviews": format!("is:pr is:open review-requested:{}", username),
+                "mine": format!("is:pr is:open author:{}", username),
+                "first": self.page_size,
+            },
+        });
+        let response: serde_json::Value = self.client.graphql(&payload).await?;
+        if let Some(message) = response
+            .pointer("/errors/0/message")
+            .and_then(|m| m.as_str())
+        {
+            return Err(GritError::Api(message.to_string()));
+        }
+        Ok(gh_home_prs(&response["data"]))
+    }
+
+    /// Issues and PRs matching a search query, newest activity first
+    async fn search_issue_refs(&self, query: &str, page: u32) -> Result<PagedResult<IssueRef>> {
+        let results = self
+            .client
+            .search()
+            .issues_and_pull_requests(query)
+            .sort("updated")
+            .order("desc")
+            .per_page(self.page_size as u8)
+            .page(page)
+            .send()
+            .await?;
+
+        let items = results
+            .items
+            .into_iter()
+            .filter_map(|issue| {
+                let mut parts = issue.repository_url.path_segments()?.rev();
+                let repo_name = parts.next()?.to_string();
+                let repo_owner = parts.next()?.to_string();
+                Some(IssueRef {
+                    repo_owner,
+                    repo_name,
+                    number: issue.number,
+                    title: issue.title,
+                    author: issue.user.login,
+                    is_pr: issue.pull_request.is_some(),
+                    updated_at: issue.updated_at,
+                })
+            })
+            .collect();
+        Ok(PagedResult {
+            items,
+            page_info: PageInfo {
+                total_count: results.total_count,
+                total_pages: None,
+            },
+        })
+    }
+
+    /// One repo's open PRs matching a search query
+    async fn search_pr_summaries(&self, query: &str, page: u32) -> Result<PagedResult<PrSummary>> {
+        let results = self
+            .client
+            .search()
+            .issues_and_pull_requests(query)
+            .sort("updated")
+            .order("desc")
+            .per_page(self.page_size as u8)
+            .page(page)
+            .send()
+            .await?;
 
-        Ok(Self { client, token })
+        let items = results
+            .items
+            .into_iter()
+            .map(|issue| PrSummary {
+                number: issue.number,
+                title: issue.title,
+                state: PrState::Open,
+                author: issue.user.login,
+                updated_at: issue.updated_at,
+                milestone: issue.milestone.map(|m| m.title),
+            })
+            .collect();
+        Ok(PagedResult {
+            items,
+            page_info: PageInfo {
+                total_count: results.total_count,
+                total_pages: None,
+            },
+        })
     }
 }
 
@@ -44,9 +314,14 @@ impl Forge for GitHub {
         "GitHub"
     }
 
+    fn page_size(&self) -> usize {
+        self.page_size
+    }
+
     fn web_url(&self, owner: &str, repo: &str, kind: &str, id: &str) -> String {
         match kind {
             "repo" => format!("https://github.com/{}/{}", owner, repo),
+            "user" => format!("https://github.com/{}", id),
             "pr" => format!("https://github.com/{}/{}/pull/{}", owner, repo, id),
             "issue" => format!("https://github.com/{}/{}/issues/{}", owner, repo, id),
             "commit" => format!("https://github.com/{}/{}/commit/{}", owner, repo, id),
@@ -62,6 +337,32 @@ impl Forge for GitHub {
         Ok(user.login)
     }
 
+    async fn token_scopes(&self) -> Result<Option<Vec<String>>> {
+        let response = self
+            .http
+            .get("https://api.github.com/user")
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .send_retrying()
+            .await?;
+        let response = http::check(response, "Token lookup").await?;
+        // Classic tokens list their scopes; fine-grained ones send no header
+        Ok(response
+            .headers()
+            .get("x-oauth-scopes")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| {
+                v.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            }))
+    }
+
+    fn required_scope(&self) -> Option<&'static str> {
+        Some("repo")
+    }
+
     async fn list_repos(&self, page: u32) -> Result<PagedResult<Repository>> {
         let repos = self
             .client
@@ -69,38 +370,85 @@ impl Forge for GitHub {
             .list_repos_for_authenticated_user()
             .sort("updated")
             .direction("desc")
-            .per_page(50)
+            .per_page(self.page_size as u8)
             .page(page as u8)
             .send()
             .await?;
 
-        let total = repos
-            .total_count
-            .or_else(|| repos.number_of_pages().map(|n| n as u64 * 50));
+        let page_info = PageInfo {
+            total_count: repos.total_count,
+            total_pages: repos.number_of_pages(),
+        };
 
-        let repositories = repos
-            .items
-            .into_iter()
-            .map(|repo| Repository {
-                owner: repo
-                    .owner
-                    .map(|o| o.login)
-                    .unwrap_or_else(|| "unknown".to_string()),
-                name: repo.name,
-                description: repo.description,
-                url: repo.html_url.map(|u| u.to_string()).unwrap_or_default(),
-                stars: repo.stargazers_count.unwrap_or(0),
-                updated_at: repo.updated_at.unwrap_or_else(chrono::Utc::now),
-            })
-            .collect();
+        Ok(PagedResult {
+            items: repos.items.into_iter().map(gh_repository).collect(),
+            page_info,
+        })
+    }
+
+    async fn list_orgs(&self) -> Result<Vec<String>> {
+        let orgs: Vec<serde_json::Value> = self
+            .client
+            .get("/user/orgs", Some(&[("per_page", "100")]))
+            .await?;
+        Ok(orgs
+            .iter()
+            .filter_map(|o| o.get("login")?.as_str().map(str::to_string))
+            .collect())
+    }
+
+    async fn list_org_repos(&self, org: &str, page: u32) -> Result<PagedResult<Repository>> {
+        let repos = self
+            .client
+            .orgs(org)
+            .list_repos()
+            .sort(octocrab::params::repos::Sort::Updated)
+            .direction(octocrab::params::Direction::Descending)
+            .per_page(self.page_size as u8)
+            .page(page)
+            .send()
+            .await?;
+
+        let page_info = PageInfo {
+            total_count: repos.total_count,
+            total_pages: repos.number_of_pages(),
+        };
 
         Ok(PagedResult {
-            items: repositories,
-            total_count: total,
+            items: repos.items.into_iter().map(gh_repository).collect(),
+            page_info,
         })
     }
 
-    async fn list_prs(&self, owner: &str, repo: &str, page: u32) -> Result<PagedResult<PrSummary>> {
+    async fn list_prs(
+        &self,
+        owner: &str,
+        repo: &str,
+        filter: PrFilter,
+        milestone: Option<&Milestone>,
+        page: u32,
+    ) -> Result<PagedResult<PrSummary>> {
+        // The pulls endpoint can't filter by person or milestone; search can
+        let mut qualifiers = Vec::new();
+        match filter {
+            PrFilter::All => {}
+            PrFilter::Authored => qualifiers.push("author:@me".to_string()),
+            PrFilter::Assigned => qualifiers.push("assignee:@me".to_string()),
+            PrFilter::ReviewRequested => qualifiers.push("review-requested:@me".to_string()),
+        }
+        if let Some(milestone) = milestone {
+            qualifiers.push(milestone_qualifier(&milestone.title)?);
+        }
+        if !qualifiers.is_empty() {
+            let query = format!(
+                "repo:{}/{} is:pr is:open {}",
+                owner,
+                repo,
+                qualifiers.join(" ")
+            );
+            return self.search_pr_summaries(&query, page).await;
+        }
+
         let prs = self
             .client
             .pulls(owner, repo)
@@ -108,14 +456,15 @@ impl Forge for GitHub {
             .state(octocrab::params::State::Open)
             .sort(octocrab::params::pulls::Sort::Updated)
             .direction(octocrab::params::Direction::Descending)
-            .per_page(50)
+            .per_page(self.page_size as u8)
             .page(page)
             .send()
             .await?;
 
-        let total = prs
-            .total_count
-            .or_else(|| prs.number_of_pages().map(|n| n as u64 * 50));
+        let page_info = PageInfo {
+            total_count: prs.total_count,
+            total_pages: prs.number_of_pages(),
+        };
 
         let summaries = prs
             .items
@@ -135,17 +484,43 @@ impl Forge for GitHub {
                     .map(|u| u.login)
                     .unwrap_or_else(|| "unknown".to_string()),
                 updated_at: pr.updated_at.unwrap_or_else(chrono::Utc::now),
+                milestone: pr.milestone.map(|m| m.title),
             })
             .collect();
 
         Ok(PagedResult {
             items: summaries,
-            total_count: total,
+            page_info,
         })
     }
 
     async fn get_pr(&self, owner: &str, repo: &str, number: u64) -> Result<PullRequest> {
-        let pr = self.client.pulls(owner, repo).get(number).await?;
+        // Raw first: octocrab's model drops `auto_merge`
+        let url = format!("/repos/{}/{}/pulls/{}", owner, repo, number);
+        let raw: serde_json::Value = self.client.get(&url, None::<&()>).await?;
+        let auto_merge = !raw["auto_merge"].is_null();
+        let pr: octocrab::models::pulls::PullRequest =
+            serde_json::from_value(raw).map_err(|e| GritError::Api(e.to_string()))?;
+
+        let mergeable_state = match pr.mergeable_state {
+            Some(OctoMergeableState::Clean) | Some(OctoMergeableState::HasHooks) => {
+                MergeableState::Clean
+            }
+            Some(OctoMergeableState::Unstable) => MergeableState::Unstable,
+            Some(OctoMergeableState::Behind) => MergeableState::Behind,
+            Some(OctoMergeableState::Dirty) => MergeableState::Conflicts,
+            Some(OctoMergeableState::Draft) => MergeableState::Draft,
+            // "blocked" covers both required checks and required reviews
+            Some(OctoMergeableState::Blocked) => {
+                match self.check_status_for_sha(owner, repo, &pr.head.sha).await {
+                    Ok(ChecksStatus::Failure) | Ok(ChecksStatus::Pending) => {
+                        MergeableState::BlockedByChecks
+                    }
+                    _ => MergeableState::BlockedByReviews,
+                }
+            }
+            _ => MergeableState::Unknown,
+        };
 
         let state = match pr.merged_at {
             Some(_) => PrState::Merged,
@@ -155,6 +530,8 @@ impl Forge for GitHub {
             },
         };
 
+        let from_fork = pr.head.repo.as_ref().map(|r| r.id) != pr.base.repo.as_ref().map(|r| r.id);
+
         Ok(PullRequest {
             number: pr.number,
             title: pr.title.unwrap_or_default(),
@@ -165,6 +542,9 @@ impl Forge for GitHub {
                 .map(|u| u.login)
                 .unwrap_or_else(|| "unknown".to_string()),
             head_branch: pr.head.ref_field,
+            head_sha: pr.head.sha,
+            from_fork,
+            auto_merge,
             base_branch: pr.base.ref_field,
             stats: PrStats {
                 additions: pr.additions.unwrap_or(0),
@@ -177,51 +557,66 @@ impl Forge for GitHub {
             updated_at: pr.updated_at.unwrap_or_else(chrono::Utc::now),
             merged_at: pr.merged_at,
             closed_at: pr.closed_at,
+            mergeable_state,
+            milestone: pr.milestone.map(|m| m.title),
+            labels: pr
+                .labels
+                .unwrap_or_default()
+                .into_iter()
+                .map(gh_label)
+                .collect(),
+            assignees: pr
+                .assignees
+                .unwrap_or_default()
+                .into_iter()
+                .map(|a| a.login)
+                .collect(),
         })
     }
 
-    async fn list_issues(&self, owner: &str, repo: &str, page: u32) -> Result<PagedResult<Issue>> {
-        let issues = self
-            .client
-            .issues(owner, repo)
+    async fn list_issues(
+        &self,
+        owner: &str,
+        repo: &str,
+        milestone: Option<&Milestone>,
+        page: u32,
+    ) -> Result<PagedResult<Issue>> {
+        let handler = self.client.issues(owner, repo);
+        let mut list = handler
             .list()
             .state(octocrab::params::State::Open)
             .sort(octocrab::params::issues::Sort::Updated)
             .direction(octocrab::params::Direction::Descending)
-            .per_page(50)
-            .page(page)
-            .send()
-            .await?;
+            .per_page(self.page_size as u8)
+            .page(page);
+        if let Some(milestone) = milestone {
+            list = list.milestone(milestone.id);
+        }
+        let issues = list.send().await?;
 
-        let total = issues
-            .total_count
-            .or_else(|| issues.number_of_pages().map(|n| n as u64 * 50));
+        let page_info = PageInfo {
+            total_count: issues.total_count,
+            total_pages: issues.number_of_pages(),
+        };
 
         let result = issues
             .items
             .into_iter()
             .filter(|i| i.pull_request.is_none()) // Filter out PRs
-            .map(|issue| Issue {
-                number: issue.number,
-                title: issue.title,
-                state: match issue.state {
-                    OctoIssueState::Closed => IssueState::Closed,
-                    _ => IssueState::Open,
-                },
-                author: issue.user.login,
-                labels: issue.labels.into_iter().map(|l| l.name).collect(),
-                comments: issue.comments,
-                created_at: issue.created_at,
-                updated_at: issue.updated_at,
-            })
+            .map(gh_issue)
             .collect();
 
         Ok(PagedResult {
             items: result,
-            total_count: total,
+            page_info,
         })
     }
 
+    async fn get_issue(&self, owner: &str, repo: &str, number: u64) -> Result<Issue> {
+        let issue = self.client.issues(owner, repo).get(number).await?;
+        Ok(gh_issue(issue))
+    }
+
     async fn list_commits(
         &self,
         owner: &str,
@@ -232,43 +627,21 @@ impl Forge for GitHub {
             .client
             .repos(owner, repo)
             .list_commits()
-            .per_page(50)
+            .per_page(self.page_size as u8)
             .page(page)
             .send()
             .await?;
 
-        let total = commits
-            .total_count
-            .or_else(|| commits.number_of_pages().map(|n| n as u64 * 50));
+        let page_info = PageInfo {
+            total_count: commits.total_count,
+            total_pages: commits.number_of_pages(),
+        };
 
-        let result = commits
-            .items
-            .into_iter()
-            .map(|c| {
-                let message = c.commit.message.lines().next().unwrap_or("").to_string();
-                let author = c
-                    .author
-                    .map(|a| a.login)
-                    .or_else(|| c.commit.author.as_ref().map(|a| a.name.clone()))
-                    .unwrap_or_else(|| "unknown".to_string());
-                let date = c
-                    .commit
-                    .author
-                    .and_then(|a| a.date)
-                    .unwrap_or_else(chrono::Utc::now);
-
-                Commit {
-                    sha: c.sha,
-                    message,
-                    author,
-                    date,
-                }
-            })
-            .collect();
+        let result = commits.items.into_iter().map(gh_commit).collect();
 
         Ok(PagedResult {
             items: result,
-            total_count: total,
+            page_info,
         })
     }
 
@@ -319,27 +692,7 @@ impl Forge for GitHub {
                 total: 0,
             });
 
-        let files = response
-            .get("files")
-            .and_then(|f| f.as_array())
-            .map(|files| {
-                files
-                    .iter()
-                    .filter_map(|f| {
-                        Some(CommitFile {
-                            filename: f.get("filename")?.as_str()?.to_string(),
-                            status: f.get("status")?.as_str()?.to_string(),
-                            additions: f.get("additions").and_then(|a| a.as_u64()).unwrap_or(0),
-                            deletions: f.get("deletions").and_then(|d| d.as_u64()).unwrap_or(0),
-                            patch: f
-                                .get("patch")
-                                .and_then(|p| p.as_str())
-                                .map(|s| s.to_string()),
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default();
+        let files = parse_files(response.get("files"));
 
         Ok(CommitDetail {
             sha: sha.to_string(),
@@ -352,31 +705,17 @@ impl Forge for GitHub {
     }
 
     async fn get_pr_diff(&self, owner: &str, repo: &str, number: u64) -> Result<String> {
-        let url = format!(
-            "https://api.github.com/repos/{}/{}/pulls/{}",
-            owner, repo, number
-        );
-        let client = reqwest::Client::new();
-        let response = client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.token))
-            .header("Accept", "application/vnd.github.diff")
-            .header("User-Agent", "grit")
-            .send()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(GritError::Api(format!(
-                "Failed to fetch diff: {}",
-                response.status()
-            )));
-        }
+        let response = self.pr_diff_response(owner, repo, number).await?;
+        response.text().await.map_err(http::api_error)
+    }
 
-        response
-            .text()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))
+    async fn stream_pr_diff(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Option<reqwest::Response>> {
+        self.pr_diff_response(owner, repo, number).await.map(Some)
     }
 
     async fn merge_pr(&self, owner: &str, repo: &str, number: u64, method: &str) -> Result<()> {
@@ -384,28 +723,67 @@ impl Forge for GitHub {
             "https://api.github.com/repos/{}/{}/pulls/{}/merge",
             owner, repo, number
         );
-        let client = reqwest::Client::new();
         let body = serde_json::json!({ "merge_method": method });
-        let response = client
+        let response = self
+            .http
             .put(&url)
             .header("Authorization", format!("Bearer {}", self.token))
             .header("Accept", "application/vnd.github+json")
-            .header("User-Agent", "grit")
             .json(&body)
-            .send()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
+            .send_retrying()
+            .await?;
+        http::check(response, "Merge").await?;
+        Ok(())
+    }
 
-        if !response.status().is_success() {
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown error".to_string());
-            return Err(GritError::Api(format!("Merge failed: {}", text)));
+    async fn enable_auto_merge(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        method: &str,
+        _delete_branch: bool,
+    ) -> Result<()> {
+        let pr = self.client.pulls(owner, repo).get(number).await?;
+        let id = pr
+            .node_id
+            .ok_or_else(|| GritError::Api(format!("PR #{} has no node id", number)))?;
+        let query = r#"
+            mutation($id: ID!, $method: PullRequestMergeMethod!) {
+              enablePullRequestAutoMerge(input: { pullRequestId: $id, mergeMethod: $method }) {
+                clientMutationId
+              }
+            }"#;
+        let payload = serde_json::json!({
+            "query": query,
+            "variables": { "id": id, "method": method.to_uppercase() },
+        });
+        let response: serde_json::Value = self.client.graphql(&payload).await?;
+        if let Some(message) = response
+            .pointer("/errors/0/message")
+            .and_then(|m| m.as_str())
+        {
+            return Err(GritError::Api(message.to_string()));
         }
         Ok(())
     }
 
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<()> {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/git/refs/heads/{}",
+            owner, repo, branch
+        );
+        let response = self
+            .http
+            .delete(&url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .send_retrying()
+            .await?;
+        http::check(response, "Delete branch").await?;
+        Ok(())
+    }
+
     async fn close_pr(&self, owner: &str, repo: &str, number: u64) -> Result<()> {
         self.client
             .pulls(owner, repo)
@@ -426,6 +804,11 @@ impl Forge for GitHub {
         Ok(())
     }
 
+    async fn get_issue_body(&self, owner: &str, repo: &str, number: u64) -> Result<Option<String>> {
+        let issue = self.client.issues(owner, repo).get(number).await?;
+        Ok(issue.body)
+    }
+
     async fn comment(&self, owner: &str, repo: &str, number: u64, body: &str) -> Result<()> {
         self.client
             .issues(owner, repo)
@@ -441,7 +824,7 @@ impl Forge for GitHub {
             .client
             .search()
             .issues_and_pull_requests(&query)
-            .per_page(50)
+            .per_page(self.page_size as u8)
             .send()
             .await?;
 
@@ -471,6 +854,30 @@ impl Forge for GitHub {
         Ok(review_requests)
     }
 
+    async fn list_assigned_issues(&self, username: &str) -> Result<Vec<IssueRef>> {
+        let query = format!("is:issue is:open archived:false assignee:{}", username);
+        Ok(self.search_issue_refs(&query, 1).await?.items)
+    }
+
+    async fn list_mentions(&self, username: &str) -> Result<Vec<IssueRef>> {
+        let query = format!("is:open archived:false mentions:{}", username);
+        Ok(self.search_issue_refs(&query, 1).await?.items)
+    }
+
+    async fn search_issues(&self, query: &str, page: u32) -> Result<PagedResult<IssueRef>> {
+        // Open items unless the query picks a state, as on the other forges
+        let picks_state = query.split_whitespace().any(|word| {
+            matches!(word, "is:open" | "is:closed" | "is:merged" | "is:unmerged")
+                || word.starts_with("state:")
+        });
+        let query = if picks_state {
+            query.to_string()
+        } else {
+            format!("{} is:open", query)
+        };
+        self.search_issue_refs(&query, page).await
+    }
+
     async fn list_my_prs(&self, username: &str) -> Result<Vec<MyPr>> {
         let query = format!("is:pr is:open author:{}", username);
 
@@ -478,7 +885,7 @@ impl Forge for GitHub {
             .client
             .search()
             .issues_and_pull_requests(&query)
-            .per_page(50)
+            .per_page(self.page_size as u8)
             .send()
             .await?;
 
@@ -524,6 +931,7 @@ impl Forge for GitHub {
                 title,
                 state,
                 checks_status,
+                mergeable: MergeableState::Unknown,
                 updated_at,
             });
         }
@@ -531,6 +939,19 @@ impl Forge for GitHub {
         Ok(my_prs)
     }
 
+    async fn list_home_prs(&self, username: &str) -> Result<(Vec<ReviewRequest>, Vec<MyPr>)> {
+        // Both searches with every PR's check rollup in one query; over REST
+        // each of your PRs costs a call of its own
+        match self.home_prs_graphql(username).await {
+            Ok(prs) => Ok(prs),
+            Err(GritError::RateLimited { reset }) => Err(GritError::RateLimited { reset }),
+            Err(_) => tokio::try_join!(
+                self.list_review_requests(username),
+                self.list_my_prs(username)
+            ),
+        }
+    }
+
     async fn list_action_runs(
         &self,
         owner: &str,
@@ -538,12 +959,15 @@ impl Forge for GitHub {
         page: u32,
     ) -> Result<PagedResult<ActionRun>> {
         let url = format!(
-            "/repos/{}/{}/actions/runs?per_page=50&page={}",
-            owner, repo, page
+            "/repos/{}/{}/actions/runs?per_page={}&page={}",
+            owner, repo, self.page_size, page
         );
         let response: serde_json::Value = self.client.get(&url, None::<&()>).await?;
 
-        let total = response.get("total_count").and_then(|v| v.as_u64());
+        let page_info = PageInfo {
+            total_count: response.get("total_count").and_then(|v| v.as_u64()),
+            total_pages: None,
+        };
 
         let runs = response
             .get("workflow_runs")
@@ -551,6 +975,13 @@ impl Forge for GitHub {
             .map(|runs| {
                 runs.iter()
                     .filter_map(|run| {
+                        let time = |field: &str| {
+                            run.get(field)
+                                .and_then(|d| d.as_str())
+                                .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+                                .map(|d| d.with_timezone(&chrono::Utc))
+                        };
+                        let completed = run.get("status")?.as_str()? == "completed";
                         Some(ActionRun {
                             id: run.get("id")?.as_u64()?,
                             name: run.get("name")?.as_str()?.to_string(),
@@ -579,12 +1010,19 @@ impl Forge for GitHub {
                                 .and_then(|e| e.as_str())
                                 .unwrap_or("unknown")
                                 .to_string(),
-                            created_at: run
-                                .get("created_at")
-                                .and_then(|d| d.as_str())
-                                .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
-                                .map(|d| d.with_timezone(&chrono::Utc))
-                                .unwrap_or_else(chrono::Utc::now),
+                            created_at: time("created_at").unwrap_or_else(chrono::Utc::now),
+                            // A finished run's last update is when it finished
+                            duration: match (
+                                completed,
+                                time("run_started_at").or_else(|| time("created_at")),
+                                time("updated_at"),
+                            ) {
+                                (true, Some(start), Some(end)) => {
+                                    (end - start).num_seconds().try_into().ok()
+                                }
+                                _ => None,
+                            },
+                            workflow: None,
                         })
                     })
                     .collect()
@@ -593,10 +1031,49 @@ impl Forge for GitHub {
 
         Ok(PagedResult {
             items: runs,
-            total_count: total,
+            page_info,
         })
     }
 
+    async fn list_artifacts(&self, owner: &str, repo: &str, run_id: u64) -> Result<Vec<Artifact>> {
+        let url = format!(
+            "/repos/{}/{}/actions/runs/{}/artifacts?per_page=100",
+            owner, repo, run_id
+        );
+        let response: serde_json::Value = self.client.get(&url, None::<&()>).await?;
+        Ok(response
+            .get("artifacts")
+            .and_then(|a| a.as_array())
+            .map(|artifacts| {
+                artifacts
+                    .iter()
+                    .filter_map(|a| {
+                        Some(Artifact {
+                            id: a.get("id")?.as_u64()?,
+                            name: a.get("name")?.as_str()?.to_string(),
+                            size: a.get("size_in_bytes").and_then(|s| s.as_u64()).unwrap_or(0),
+                            expired: a.get("expired").and_then(|e| e.as_bool()).unwrap_or(false),
+                            download_url: a.get("archive_download_url")?.as_str()?.to_string(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    // Answers with a redirect to blob storage, which reqwest follows without
+    // passing the token along
+    async fn download_artifact(&self, artifact: &Artifact) -> Result<reqwest::Response> {
+        let response = self
+            .http
+            .get(&artifact.download_url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .timeout(http::DOWNLOAD_TIMEOUT)
+            .send_retrying()
+            .await?;
+        http::check(response, "Download artifact").await
+    }
+
     async fn get_check_status(
         &self,
         owner: &str,
@@ -604,50 +1081,887 @@ impl Forge for GitHub {
         pr_number: u64,
     ) -> Result<ChecksStatus> {
         let pr = self.client.pulls(owner, repo).get(pr_number).await?;
-        let sha = pr.head.sha;
-
-        let url = format!("/repos/{}/{}/commits/{}/check-runs", owner, repo, sha);
-        let response: serde_json::Value = self.client.get(&url, None::<&()>).await?;
-
-        let check_runs = response.get("check_runs").and_then(|r| r.as_array());
+        self.check_status_for_sha(owner, repo, &pr.head.sha).await
+    }
 
-        let Some(runs) = check_runs else {
-            return Ok(ChecksStatus::None);
+    async fn list_pr_commits(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<Commit>> {
+        let url = format!(
+            "/repos/{}/{}/pulls/{}/commits?per_page=100",
+            owner, repo, number
+        );
+        let first: octocrab::Page<octocrab::models::repos::RepoCommit> =
+            self.client.get(&url, None::<&()>).await?;
+        let commits = self.client.all_pages(first).await?;
+        Ok(commits.into_iter().map(gh_commit).collect())
+    }
+
+    async fn list_pr_files(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<CommitFile>> {
+        let url = format!(
+            "/repos/{}/{}/pulls/{}/files?per_page=100",
+            owner, repo, number
+        );
+        let first: octocrab::Page<serde_json::Value> = self.client.get(&url, None::<&()>).await?;
+        let files = self.client.all_pages(first).await?;
+        Ok(parse_files(Some(&serde_json::Value::Array(files))))
+    }
+
+    async fn list_pr_comments(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<Comment>> {
+        // PR conversation comments live on the issue side of the API
+        let url = format!(
+            "/repos/{}/{}/issues/{}/comments?per_page=100",
+            owner, repo, number
+        );
+        let first: octocrab::Page<serde_json::Value> = self.client.get(&url, None::<&()>).await?;
+        let response = self.client.all_pages(first).await?;
+
+        let comments = response
+            .iter()
+            .filter_map(|c| {
+                Some(Comment {
+                    id: c.get("id")?.as_u64()?,
+                    author: c
+                        .get("user")
+                        .and_then(|u| u.get("login"))
+                        .and_then(|l| l.as_str())
+                        .unwrap_or("unknown")
+                        .to_string(),
+                    body: c
+                        .get("body")
+                        .and_then(|b| b.as_str())
+                        .unwrap_or("")
+                        .to_string(),
+                    created_at: c
+                        .get("created_at")
+                        .and_then(|d| d.as_str())
+                        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+                        .map(|d| d.with_timezone(&chrono::Utc))
+                        .unwrap_or_else(chrono::Utc::now),
+                })
+            })
+            .collect();
+
+        Ok(comments)
+    }
+
+    async fn list_reviews(&self, owner: &str, repo: &str, number: u64) -> Result<PrReviews> {
+        let url = format!(
+            "/repos/{}/{}/pulls/{}/reviews?per_page=100",
+            owner, repo, number
+        );
+        let first: octocrab::Page<serde_json::Value> = self.client.get(&url, None::<&()>).await?;
+        let response = self.client.all_pages(first).await?;
+
+        let reviews = response
+            .iter()
+            .filter_map(|r| {
+                let state = match r.get("state")?.as_str()? {
+                    "APPROVED" => ReviewState::Approved,
+                    "CHANGES_REQUESTED" => ReviewState::ChangesRequested,
+                    "COMMENTED" => ReviewState::Commented,
+                    "DISMISSED" => ReviewState::Dismissed,
+                    // PENDING reviews are unsubmitted drafts
+                    _ => return None,
+                };
+                Some(Review {
+                    reviewer: r.get("user")?.get("login")?.as_str()?.to_string(),
+                    state,
+                    submitted_at: r
+                        .get("submitted_at")
+                        .and_then(|d| d.as_str())
+                        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+                        .map(|d| d.with_timezone(&chrono::Utc)),
+                })
+            })
+            .collect();
+
+        let url = format!(
+            "/repos/{}/{}/pulls/{}/requested_reviewers",
+            owner, repo, number
+        );
+        let response: serde_json::Value = self.client.get(&url, None::<&()>).await?;
+
+        let users = response
+            .get("users")
+            .and_then(|u| u.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|u| u.get("login")?.as_str().map(String::from));
+        let teams = response
+            .get("teams")
+            .and_then(|t| t.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|t| {
+                t.get("slug")?
+                    .as_str()
+                    .map(|slug| format!("{}/{}", owner, slug))
+            });
+
+        // Required approvals live in branch protection, which needs admin access
+        Ok(PrReviews {
+            requested: users.chain(teams).collect(),
+            reviews,
+            required_approvals: None,
+        })
+    }
+
+    async fn get_pr_checks(&self, owner: &str, repo: &str, number: u64) -> Result<PrChecks> {
+        let pr = self.client.pulls(owner, repo).get(number).await?;
+        self.checks_for_sha(owner, repo, &pr.head.sha).await
+    }
+
+    async fn get_commit_checks(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<CommitCheck>> {
+        let url = format!(
+            "/repos/{}/{}/commits/{}/check-runs?per_page=100",
+            owner, repo, sha
+        );
+        let response: serde_json::Value = self.client.get(&url, None::<&()>).await?;
+        let time = |run: &serde_json::Value, field: &str| {
+            chrono::DateTime::parse_from_rfc3339(run.get(field)?.as_str()?).ok()
         };
+        let mut checks: Vec<CommitCheck> = response["check_runs"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|run| {
+                let state = gh_run_state(run);
+                let duration = match (time(run, "started_at"), time(run, "completed_at")) {
+                    (Some(start), Some(end)) => (end - start).num_seconds().try_into().ok(),
+                    _ => None,
+                };
+                CommitCheck {
+                    name: run["name"].as_str().unwrap_or("check").to_string(),
+                    state,
+                    duration,
+                }
+            })
+            .collect();
+
+        // Services outside GitHub Actions still report through commit statuses
+        let url = format!("/repos/{}/{}/commits/{}/status", owner, repo, sha);
+        let response: serde_json::Value = self.client.get(&url, None::<&()>).await?;
+        checks.extend(
+            response["statuses"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|status| CommitCheck {
+                    name: status["context"].as_str().unwrap_or("status").to_string(),
+                    state: match status["state"].as_str() {
+                        Some("success") => ChecksStatus::Success,
+                        Some("failure" | "error") => ChecksStatus::Failure,
+                        _ => ChecksStatus::Pending,
+                    },
+                    duration: None,
+                }),
+        );
+        Ok(checks)
+    }
 
-        if runs.is_empty() {
-            return Ok(ChecksStatus::None);
+    async fn get_repo_stats(&self, owner: &str, repo: &str) -> Result<RepoStats> {
+        // Every count in one query; REST would take a search call per count
+        let query = r#"
+            query($owner: String!, $repo: String!, $since: GitTimestamp!, $top: Int!) {
+              repository(owner: $owner, name: $repo) {
+                openIssues: issues(states: OPEN) { totalCount }
+                closedIssues: issues(states: CLOSED) { totalCount }
+                openPrs: pullRequests(states: OPEN) { totalCount }
+                mergedPrs: pullRequests(states: MERGED) { totalCount }
+                closedPrs: pullRequests(states: CLOSED) { totalCount }
+                languages(first: $top, orderBy: { field: SIZE, direction: DESC }) {
+                  edges { size node { name } }
+                }
+                defaultBranchRef {
+                  target { ... on Commit { history(since: $since) { totalCount } } }
+                }
+              }
+            }"#;
+        let since = chrono::Utc::now() - chrono::Duration::days(RECENT_ACTIVITY_DAYS);
+        let payload = serde_json::json!({
+            "query": query,
+            "variables": {
+                "owner": owner,
+                "repo": repo,
+                "since": since.to_rfc3339(),
+                "top": INSIGHTS_TOP,
+            },
+        });
+        let response: serde_json::Value = self.client.graphql(&payload).await?;
+        if let Some(message) = response
+            .pointer("/errors/0/message")
+            .and_then(|m| m.as_str())
+        {
+            return Err(GritError::Api(message.to_string()));
         }
+        let repository = &response["data"]["repository"];
+        let count = |field: &str| repository[field]["totalCount"].as_u64().unwrap_or(0);
+        let languages = language_shares(
+            repository["languages"]["edges"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|edge| {
+                    Some((
+                        edge["node"]["name"].as_str()?.to_string(),
+                        edge["size"].as_u64()?,
+                    ))
+                }),
+        );
 
-        let mut has_pending = false;
-        let mut has_failure = false;
+        // An empty repo answers 204, which isn't worth failing the popup over
+        let url = format!(
+            "/repos/{}/{}/contributors?per_page={}",
+            owner, repo, INSIGHTS_TOP
+        );
+        let contributors: serde_json::Value =
+            self.client.get(&url, None::<&()>).await.unwrap_or_default();
+        let contributors = contributors
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|c| {
+                Some((
+                    c["login"].as_str()?.to_string(),
+                    c["contributions"].as_u64()?,
+                ))
+            })
+            .collect();
+
+        Ok(RepoStats {
+            open_issues: count("openIssues"),
+            closed_issues: count("closedIssues"),
+            open_prs: count("openPrs"),
+            merged_prs: count("mergedPrs"),
+            closed_prs: count("closedPrs"),
+            contributors,
+            languages,
+            recent_commits: repository
+                .pointer("/defaultBranchRef/target/history/totalCount")
+                .and_then(|c| c.as_u64())
+                .unwrap_or(0),
+        })
+    }
+
+    async fn list_viewed_files(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<String>> {
+        // Viewed state is per-user and only exposed through GraphQL
+        let query = r#"
+            query($owner: String!, $repo: String!, $number: Int!, $after: String) {
+              repository(owner: $owner, name: $repo) {
+                pullRequest(number: $number) {
+                  files(first: 100, after: $after) {
+                    pageInfo { hasNextPage endCursor }
+                    nodes { path viewerViewedState }
+                  }
+                }
+              }
+            }"#;
+
+        let mut viewed = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let variables = serde_json::json!({
+                "owner": owner, "repo": repo, "number": number, "after": after,
+            });
+            let data = self.graphql_data(query, variables).await?;
+            let page = &data["repository"]["pullRequest"]["files"];
+            viewed.extend(
+                page["nodes"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter(|f| {
+                        f.get("viewerViewedState").and_then(|s| s.as_str()) == Some("VIEWED")
+                    })
+                    .filter_map(|f| f.get("path")?.as_str().map(String::from)),
+            );
+            after = next_cursor(page);
+            if after.is_none() {
+                break;
+            }
+        }
 
-        for run in runs {
-            let status = run.get("status").and_then(|s| s.as_str());
-            let conclusion = run.get("conclusion").and_then(|c| c.as_str());
+        Ok(viewed)
+    }
 
-            match status {
-                Some("completed") => match conclusion {
-                    Some("success") | Some("skipped") => {}
-                    Some("failure") | Some("cancelled") | Some("timed_out") => {
-                        has_failure = true;
+    async fn list_review_threads(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<ReviewThread>> {
+        // Thread resolution is only exposed through GraphQL. Threads come 100
+        // at a time; replies past a thread's first 100 are asked for on their own.
+        let query = r#"
+            query($owner: String!, $repo: String!, $number: Int!, $after: String) {
+              repository(owner: $owner, name: $repo) {
+                pullRequest(number: $number) {
+                  reviewThreads(first: 100, after: $after) {
+                    pageInfo { hasNextPage endCursor }
+                    nodes {
+                      id
+                      isResolved
+                      path
+                      line
+                      comments(first: 100) {
+                        pageInfo { hasNextPage endCursor }
+                        nodes { databaseId body createdAt author { login } }
+                      }
                     }
-                    _ => {}
-                },
-                Some("queued") | Some("in_progress") => {
-                    has_pending = true;
+                  }
+                }
+              }
+            }"#;
+        let replies = r#"
+            query($id: ID!, $after: String) {
+              node(id: $id) {
+                ... on PullRequestReviewThread {
+                  comments(first: 100, after: $after) {
+                    pageInfo { hasNextPage endCursor }
+                    nodes { databaseId body createdAt author { login } }
+                  }
                 }
-                _ => {}
+              }
+            }"#;
+
+        let mut threads = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let variables = serde_json::json!({
+                "owner": owner, "repo": repo, "number": number, "after": after,
+            });
+            let data = self.graphql_data(query, variables).await?;
+            let page = &data["repository"]["pullRequest"]["reviewThreads"];
+            for node in page["nodes"].as_array().into_iter().flatten() {
+                let Some(mut thread) = gh_review_thread(node) else {
+                    continue;
+                };
+                let mut cursor = next_cursor(&node["comments"]);
+                while let Some(after) = cursor {
+                    let variables = serde_json::json!({ "id": thread.id, "after": after });
+                    let data = self.graphql_data(replies, variables).await?;
+                    let comments = &data["node"]["comments"];
+                    thread.comments.extend(gh_thread_comments(comments));
+                    cursor = next_cursor(comments);
+                }
+                threads.push(thread);
+            }
+            match next_cursor(page) {
+                Some(cursor) => after = Some(cursor),
+                None => return Ok(threads),
             }
         }
+    }
 
-        if has_failure {
-            Ok(ChecksStatus::Failure)
-        } else if has_pending {
-            Ok(ChecksStatus::Pending)
+    async fn set_thread_resolved(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+        thread_id: &str,
+        resolved: bool,
+    ) -> Result<()> {
+        let mutation = if resolved {
+            "mutation($id: ID!) { resolveReviewThread(input: { threadId: $id }) { thread { id } } }"
+        } else {
+            "mutation($id: ID!) { unresolveReviewThread(input: { threadId: $id }) { thread { id } } }"
+        };
+        let payload = serde_json::json!({
+            "query": mutation,
+            "variables": { "id": thread_id },
+        });
+        let response: serde_json::Value = self.client.graphql(&payload).await?;
+
+        if let Some(message) = response
+            .pointer("/errors/0/message")
+            .and_then(|m| m.as_str())
+        {
+            return Err(GritError::Api(message.to_string()));
+        }
+        Ok(())
+    }
+
+    async fn list_milestones(&self, owner: &str, repo: &str) -> Result<Vec<Milestone>> {
+        let url = format!(
+            "/repos/{}/{}/milestones?state=open&sort=due_on&per_page=100",
+            owner, repo
+        );
+        let response: Vec<serde_json::Value> = self.client.get(&url, None::<&()>).await?;
+
+        let milestones = response
+            .iter()
+            .filter_map(|m| {
+                Some(Milestone {
+                    id: m.get("number")?.as_u64()?,
+                    title: m.get("title")?.as_str()?.to_string(),
+                    due_on: m
+                        .get("due_on")
+                        .and_then(|d| d.as_str())
+                        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+                        .map(|d| d.with_timezone(&chrono::Utc)),
+                })
+            })
+            .collect();
+
+        Ok(milestones)
+    }
+
+    async fn locate_repo(&self, owner: &str, repo: &str) -> Result<(String, String)> {
+        // Renamed and transferred repos answer with a 301 that octocrab follows
+        let url = format!("/repos/{}/{}", owner, repo);
+        let info: serde_json::Value = self.client.get(&url, None::<&()>).await?;
+        info["full_name"]
+            .as_str()
+            .and_then(split_repo_path)
+            .ok_or_else(|| GritError::Api(format!("No full_name for {}/{}", owner, repo)))
+    }
+
+    async fn get_repo_meta(&self, owner: &str, repo: &str) -> Result<RepoMeta> {
+        let repo = self.client.repos(owner, repo).get().await?;
+        Ok(RepoMeta {
+            description: repo.description.unwrap_or_default(),
+            topics: repo.topics.unwrap_or_default(),
+        })
+    }
+
+    async fn update_repo_meta(&self, owner: &str, repo: &str, meta: &RepoMeta) -> Result<()> {
+        let url = format!("/repos/{}/{}", owner, repo);
+        let body = serde_json::json!({ "description": meta.description });
+        let _: serde_json::Value = self.client.patch(&url, Some(&body)).await?;
+
+        let url = format!("/repos/{}/{}/topics", owner, repo);
+        let body = serde_json::json!({ "names": meta.topics });
+        let _: serde_json::Value = self.client.put(&url, Some(&body)).await?;
+        Ok(())
+    }
+
+    async fn fork_repo(&self, owner: &str, repo: &str) -> Result<Repository> {
+        let url = format!("/repos/{}/{}/forks", owner, repo);
+        let fork: octocrab::models::Repository =
+            self.client.post(&url, Some(&serde_json::json!({}))).await?;
+        Ok(gh_repository(fork))
+    }
+
+    async fn get_fork_status(&self, owner: &str, repo: &str) -> Result<Option<ForkStatus>> {
+        let url = format!("/repos/{}/{}", owner, repo);
+        let info: serde_json::Value = self.client.get(&url, None::<&()>).await?;
+        let parent = &info["parent"];
+        let (Some(upstream), Some(upstream_branch), Some(branch)) = (
+            parent["full_name"].as_str(),
+            parent["default_branch"].as_str(),
+            info["default_branch"].as_str(),
+        ) else {
+            return Ok(None);
+        };
+
+        // Compared from the upstream's side: ahead is what only the fork has
+        let url = format!(
+            "/repos/{}/compare/{}...{}:{}",
+            upstream, upstream_branch, owner, branch
+        );
+        let compare: serde_json::Value = self.client.get(&url, None::<&()>).await?;
+        Ok(Some(ForkStatus {
+            upstream: upstream.to_string(),
+            branch: branch.to_string(),
+            upstream_branch: upstream_branch.to_string(),
+            ahead: compare["ahead_by"].as_u64().unwrap_or(0),
+            behind: compare["behind_by"].as_u64().unwrap_or(0),
+        }))
+    }
+
+    async fn sync_fork(&self, owner: &str, repo: &str, branch: &str) -> Result<()> {
+        let url = format!("/repos/{}/{}/merge-upstream", owner, repo);
+        let body = serde_json::json!({ "branch": branch });
+        let _: serde_json::Value = self.client.post(&url, Some(&body)).await?;
+        Ok(())
+    }
+
+    async fn is_starred(&self, owner: &str, repo: &str) -> Result<bool> {
+        let url = format!("https://api.github.com/user/starred/{}/{}", owner, repo);
+        let response = self
+            .http
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .send_retrying()
+            .await?;
+        // 204 when starred, 404 when not
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        http::check(response, "Star lookup").await?;
+        Ok(true)
+    }
+
+    async fn set_star(&self, owner: &str, repo: &str, starred: bool) -> Result<()> {
+        let url = format!("https://api.github.com/user/starred/{}/{}", owner, repo);
+        let request = if starred {
+            self.http.put(&url)
         } else {
-            Ok(ChecksStatus::Success)
+            self.http.delete(&url)
+        };
+        let response = request
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("Content-Length", "0")
+            .send_retrying()
+            .await?;
+        http::check(response, "Star").await?;
+        Ok(())
+    }
+
+    async fn set_subscription(
+        &self,
+        owner: &str,
+        repo: &str,
+        subscription: Subscription,
+    ) -> Result<()> {
+        let url = format!("/repos/{}/{}/subscription", owner, repo);
+        // Not watching is the default: notified only when participating
+        let (subscribed, ignored) = match subscription {
+            Subscription::Watching => (true, false),
+            Subscription::Ignoring => (false, true),
+            Subscription::Participating => {
+                let url = format!("https://api.github.com{}", url);
+                let response = self
+                    .http
+                    .delete(&url)
+                    .header("Authorization", format!("Bearer {}", self.token))
+                    .header("Accept", "application/vnd.github+json")
+                    .send_retrying()
+                    .await?;
+                http::check(response, "Unwatch").await?;
+                return Ok(());
+            }
+        };
+        let body = serde_json::json!({ "subscribed": subscribed, "ignored": ignored });
+        let _: serde_json::Value = self.client.put(&url, Some(&body)).await?;
+        Ok(())
+    }
+
+    async fn search_commits(&self, query: &str, owners: &[String]) -> Result<Vec<CommitHit>> {
+        let term = if crate::types::is_sha_prefix(query) {
+            format!("hash:{}", query)
+        } else {
+            format!("\"{}\"", query.replace('"', ""))
+        };
+        // `user:` matches orgs too; several of them are OR'd together
+        let scope: Vec<String> = owners.iter().map(|o| format!("user:{}", o)).collect();
+        let q = format!("{} {}", term, scope.join(" "));
+        let response: serde_json::Value = self
+            .client
+            .get(
+                "/search/commits",
+                Some(&[("q", q.as_str()), ("per_page", "50")]),
+            )
+            .await?;
+
+        let str_at = |item: &serde_json::Value, pointer: &str| {
+            item.pointer(pointer)
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+        let hits = response
+            .get("items")
+            .and_then(|i| i.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| {
+                        let message = str_at(item, "/commit/message").unwrap_or_default();
+                        Some(CommitHit {
+                            owner: str_at(item, "/repository/owner/login")?,
+                            repo: str_at(item, "/repository/name")?,
+                            commit: Commit {
+                                sha: str_at(item, "/sha")?,
+                                message: message.lines().next().unwrap_or("").to_string(),
+                                author: str_at(item, "/author/login")
+                                    .or_else(|| str_at(item, "/commit/author/name"))
+                                    .unwrap_or_else(|| "unknown".to_string()),
+                                date: str_at(item, "/commit/author/date")
+                                    .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+                                    .map(|d| d.with_timezone(&chrono::Utc))
+                                    .unwrap_or_else(chrono::Utc::now),
+                            },
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(hits)
+    }
+
+    async fn reviewer_load(&self, owner: &str, repo: &str) -> Result<Vec<ReviewerLoad>> {
+        let query = r#"
+            query($owner: String!, $repo: String!) {
+              repository(owner: $owner, name: $repo) {
+                owner { __typename }
+                assignableUsers(first: 50) { nodes { login } }
+              }
+            }"#;
+        let payload = serde_json::json!({
+            "query": query,
+            "variables": { "owner": owner, "repo": repo },
+        });
+        let response: serde_json::Value = self.client.graphql(&payload).await?;
+        if let Some(message) = response
+            .pointer("/errors/0/message")
+            .and_then(|m| m.as_str())
+        {
+            return Err(GritError::Api(message.to_string()));
+        }
+
+        let scope = match response
+            .pointer("/data/repository/owner/__typename")
+            .and_then(|t| t.as_str())
+        {
+            Some("Organization") => "org",
+            _ => "user",
+        };
+        let logins: Vec<String> = response
+            .pointer("/data/repository/assignableUsers/nodes")
+            .and_then(|n| n.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|u| u.get("login")?.as_str().map(String::from))
+            .collect();
+        if logins.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // One aliased search per user, all in a single request; REST search
+        // would burn through its 30 requests a minute on a mid-sized team
+        let searches: String = logins
+            .iter()
+            .enumerate()
+            .map(|(i, login)| {
+                format!(
+                    "u{}: search(query: \"is:pr is:open review-requested:{} {}:{}\", type: ISSUE) {{ issueCount }}\n",
+                    i, login, scope, owner
+                )
+            })
+            .collect();
+        let payload = serde_json::json!({ "query": format!("query {{\n{}}}", searches) });
+        let response: serde_json::Value = self.client.graphql(&payload).await?;
+        if let Some(message) = response
+            .pointer("/errors/0/message")
+            .and_then(|m| m.as_str())
+        {
+            return Err(GritError::Api(message.to_string()));
+        }
+
+        Ok(logins
+            .into_iter()
+            .enumerate()
+            .map(|(i, login)| ReviewerLoad {
+                login,
+                open_reviews: response
+                    .pointer(&format!("/data/u{}/issueCount", i))
+                    .and_then(|c| c.as_u64())
+                    .unwrap_or(0),
+            })
+            .collect())
+    }
+
+    async fn add_reaction(
+        &self,
+        owner: &str,
+        repo: &str,
+        target: ReactionTarget,
+        reaction: Reaction,
+    ) -> Result<()> {
+        let url = match target {
+            ReactionTarget::Pr(number) => {
+                format!("/repos/{}/{}/issues/{}/reactions", owner, repo, number)
+            }
+            ReactionTarget::Comment { id, .. } => {
+                format!("/repos/{}/{}/issues/comments/{}/reactions", owner, repo, id)
+            }
+            ReactionTarget::ReviewComment { id, .. } => {
+                format!("/repos/{}/{}/pulls/comments/{}/reactions", owner, repo, id)
+            }
+        };
+        let body = serde_json::json!({ "content": reaction.as_api_str() });
+        let _: serde_json::Value = self.client.post(&url, Some(&body)).await?;
+        Ok(())
+    }
+
+    async fn comment_on_commit(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+        body: &str,
+    ) -> Result<()> {
+        let url = format!("/repos/{}/{}/commits/{}/comments", owner, repo, sha);
+        let body = serde_json::json!({ "body": body });
+        let _: serde_json::Value = self.client.post(&url, Some(&body)).await?;
+        Ok(())
+    }
+
+    async fn request_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        reviewers: &[String],
+    ) -> Result<()> {
+        let url = format!(
+            "/repos/{}/{}/pulls/{}/requested_reviewers",
+            owner, repo, number
+        );
+        let body = serde_json::json!({ "reviewers": reviewers });
+        let _: serde_json::Value = self.client.post(&url, Some(&body)).await?;
+        Ok(())
+    }
+
+    async fn set_milestone(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        milestone: Option<u64>,
+    ) -> Result<()> {
+        // PRs are issues as far as milestones are concerned
+        let url = format!("/repos/{}/{}/issues/{}", owner, repo, number);
+        let body = serde_json::json!({ "milestone": milestone });
+        let _: serde_json::Value = self.client.patch(&url, Some(&body)).await?;
+        Ok(())
+    }
+
+    async fn list_issue_templates(&self, owner: &str, repo: &str) -> Result<Vec<IssueTemplate>> {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/contents/.github/ISSUE_TEMPLATE",
+            owner, repo
+        );
+        let response = self
+            .http
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .send_retrying()
+            .await?;
+        // No template directory
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(vec![]);
         }
+        let response = http::check(response, "Issue templates").await?;
+        let entries: Vec<serde_json::Value> = response.json().await.map_err(http::api_error)?;
+
+        let mut templates = Vec::new();
+        for entry in &entries {
+            let (Some(name), Some(url)) = (entry["name"].as_str(), entry["url"].as_str()) else {
+                continue;
+            };
+            // Issue forms (`.yml`) and `config.yml` aren't text for the editor
+            if entry["type"] != "file" || !name.to_lowercase().ends_with(".md") {
+                continue;
+            }
+            let response = self
+                .http
+                .get(url)
+                .header("Authorization", format!("Bearer {}", self.token))
+                .header("Accept", "application/vnd.github.raw+json")
+                .send_retrying()
+                .await?;
+            let response = http::check(response, "Issue template").await?;
+            let text = response.text().await.map_err(http::api_error)?;
+            templates.push(IssueTemplate::parse(name, &text));
+        }
+        Ok(templates)
+    }
+
+    async fn create_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        fields: &IssueFields,
+        body: &str,
+    ) -> Result<u64> {
+        let url = format!("/repos/{}/{}/issues", owner, repo);
+        let body = serde_json::json!({
+            "title": fields.title,
+            "body": body,
+            "labels": fields.labels,
+            "assignees": fields.assignees,
+            "milestone": fields.milestone,
+        });
+        let issue: octocrab::models::issues::Issue = self.client.post(&url, Some(&body)).await?;
+        Ok(issue.number)
+    }
+
+    // PRs are issues as far as these fields are concerned
+    async fn update_fields(
+        &self,
+        owner: &str,
+        repo: &str,
+        _kind: &str,
+        number: u64,
+        fields: &IssueFields,
+    ) -> Result<()> {
+        let url = format!("/repos/{}/{}/issues/{}", owner, repo, number);
+        let body = serde_json::json!({
+            "title": fields.title,
+            "labels": fields.labels,
+            "assignees": fields.assignees,
+            "milestone": fields.milestone,
+        });
+        let _: serde_json::Value = self.client.patch(&url, Some(&body)).await?;
+        Ok(())
+    }
+
+    async fn update_pr(&self, owner: &str, repo: &str, number: u64, body: &str) -> Result<()> {
+        let url = format!("/repos/{}/{}/pulls/{}", owner, repo, number);
+        let body = serde_json::json!({ "body": body });
+        let _: serde_json::Value = self.client.patch(&url, Some(&body)).await?;
+        Ok(())
+    }
+
+    async fn list_labels(&self, owner: &str, repo: &str) -> Result<Vec<Label>> {
+        let page = self
+            .client
+            .issues(owner, repo)
+            .list_labels_for_repo()
+            .per_page(100)
+            .send()
+            .await?;
+
+        Ok(page.items.into_iter().map(gh_label).collect())
+    }
+
+    // PRs are issues as far as labels are concerned, so `kind` doesn't matter
+    async fn add_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        _kind: &str,
+        number: u64,
+        labels: &[String],
+    ) -> Result<()> {
+        self.client
+            .issues(owner, repo)
+            .add_labels(number, labels)
+            .await?;
+        Ok(())
+    }
+
+    async fn remove_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        _kind: &str,
+        number: u64,
+        labels: &[String],
+    ) -> Result<()> {
+        let issues = self.client.issues(owner, repo);
+        for label in labels {
+            issues.remove_label(number, label).await?;
+        }
+        Ok(())
     }
 
     async fn submit_review(
@@ -657,33 +1971,518 @@ impl Forge for GitHub {
         number: u64,
         event: &str,
         body: &str,
+        comments: &[ReviewComment],
     ) -> Result<()> {
         let url = format!(
             "https://api.github.com/repos/{}/{}/pulls/{}/reviews",
             owner, repo, number
         );
-        let client = reqwest::Client::new();
+        let comments: Vec<serde_json::Value> = comments
+            .iter()
+            .map(|c| {
+                // Context and added lines anchor on the new file, removed lines on the old
+                let (line, side) = match (c.line.new, c.line.old) {
+                    (Some(new), _) => (new, "RIGHT"),
+                    (None, old) => (old.unwrap_or(0), "LEFT"),
+                };
+                serde_json::json!({
+                    "path": c.path,
+                    "body": c.body,
+                    "line": line,
+                    "side": side,
+                })
+            })
+            .collect();
         let payload = serde_json::json!({
             "event": event,
             "body": body,
+            "comments": comments,
         });
-        let response = client
+        let response = self
+            .http
             .post(&url)
             .header("Authorization", format!("Bearer {}", self.token))
             .header("Accept", "application/vnd.github+json")
-            .header("User-Agent", "grit")
             .json(&payload)
-            .send()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
+            .send_retrying()
+            .await?;
+        http::check(response, "Review").await?;
+        Ok(())
+    }
 
-        if !response.status().is_success() {
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown error".to_string());
-            return Err(GritError::Api(format!("Review failed: {}", text)));
+    // REST has no revert endpoint, so each revert commit is built with the git
+    // data API: every file the commit touched goes back to its parent's blob.
+    // A file changed again since then would need a real merge, so that's refused.
+    async fn revert_pr(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr: &PullRequest,
+        commits: &[Commit],
+    ) -> Result<u64> {
+        let url = format!("/repos/{}/{}/git/ref/heads/{}", owner, repo, pr.base_branch);
+        let base: serde_json::Value = self.client.get(&url, None::<&()>).await?;
+        let mut head = base["object"]["sha"]
+            .as_str()
+            .ok_or_else(|| GritError::Api(format!("Branch {} not found", pr.base_branch)))?
+            .to_string();
+        let url = format!("/repos/{}/{}/git/commits/{}", owner, repo, head);
+        let head_commit: serde_json::Value = self.client.get(&url, None::<&()>).await?;
+        let mut tree = head_commit["tree"]["sha"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let mut files = self.tree_files(owner, repo, &head).await?;
+
+        // Newest first, as `git revert` walks a range
+        for commit in commits.iter().rev() {
+            let short = &commit.sha[..commit.sha.len().min(7)];
+            let url = format!("/repos/{}/{}/commits/{}", owner, repo, commit.sha);
+            let detail: serde_json::Value = self.client.get(&url, None::<&()>).await?;
+            let parent = detail["parents"][0]["sha"]
+                .as_str()
+                .ok_or_else(|| GritError::Api(format!("{} has no parent to revert to", short)))?;
+            let before = self.tree_files(owner, repo, parent).await?;
+            let after = self.tree_files(owner, repo, &commit.sha).await?;
+            let paths: BTreeSet<&str> = detail["files"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .flat_map(|f| [f["filename"].as_str(), f["previous_filename"].as_str()])
+                .flatten()
+                .collect();
+
+            let mut entries = Vec::new();
+            for path in paths {
+                if files.get(path) != after.get(path) {
+                    return Err(GritError::Api(format!(
+                        "{} changed after {}; revert it locally",
+                        path, short
+                    )));
+                }
+                entries.push(match before.get(path) {
+                    Some((sha, mode)) => serde_json::json!({
+                        "path": path, "mode": mode, "type": "blob", "sha": sha,
+                    }),
+                    None => serde_json::json!({
+                        "path": path, "mode": "100644", "type": "blob", "sha": null,
+                    }),
+                });
+                match before.get(path) {
+                    Some(blob) => files.insert(path.to_string(), blob.clone()),
+                    None => files.remove(path),
+                };
+            }
+
+            let url = format!("/repos/{}/{}/git/trees", owner, repo);
+            let body = serde_json::json!({ "base_tree": tree, "tree": entries });
+            let created: serde_json::Value = self.client.post(&url, Some(&body)).await?;
+            tree = created["sha"].as_str().unwrap_or_default().to_string();
+
+            let subject = commit.message.lines().next().unwrap_or_default();
+            let url = format!("/repos/{}/{}/git/commits", owner, repo);
+            let body = serde_json::json!({
+                "message": format!("Revert \"{}\"\n\nThis reverts commit {}.", subject, commit.sha),
+                "tree": tree,
+                "parents": [head],
+            });
+            let created: serde_json::Value = self.client.post(&url, Some(&body)).await?;
+            head = created["sha"].as_str().unwrap_or_default().to_string();
         }
-        Ok(())
+
+        let branch = format!("revert-{}", pr.number);
+        let url = format!("/repos/{}/{}/git/refs", owner, repo);
+        let body = serde_json::json!({ "ref": format!("refs/heads/{}", branch), "sha": head });
+        let _: serde_json::Value = self.client.post(&url, Some(&body)).await?;
+
+        let url = format!("/repos/{}/{}/pulls", owner, repo);
+        let body = serde_json::json!({
+            "title": format!("Revert \"{}\"", pr.title),
+            "head": branch,
+            "base": pr.base_branch,
+            "body": format!("Reverts #{}", pr.number),
+        });
+        let created: serde_json::Value = self.client.post(&url, Some(&body)).await?;
+        created["number"]
+            .as_u64()
+            .ok_or_else(|| GritError::Api("Revert PR created without a number".into()))
+    }
+}
+
+/// A search qualifier for PRs in the milestone titled `title`. Search has no
+/// way to escape a quote inside a quoted term, so such titles are refused
+fn milestone_qualifier(title: &str) -> Result<String> {
+    if title.contains('"') {
+        return Err(GritError::Validation {
+            message: format!(
+                "Can't search for milestone {}: its title has a quote",
+                title
+            ),
+        });
+    }
+    Ok(format!("milestone:\"{}\"", title))
+}
+
+/// State of one check run by its conclusion. Runs waiting on someone
+/// (`action_required`) fail; `stale` and unknown conclusions stay pending,
+/// and `neutral` neither passes nor fails (`None`)
+fn gh_run_state(run: &serde_json::Value) -> ChecksStatus {
+    let status = run.get("status").and_then(|s| s.as_str());
+    let conclusion = run.get("conclusion").and_then(|c| c.as_str());
+
+    match status {
+        Some("completed") => match conclusion {
+            Some("success") | Some("skipped") => ChecksStatus::Success,
+            Some("neutral") => ChecksStatus::None,
+            Some("failure")
+            | Some("cancelled")
+            | Some("timed_out")
+            | Some("action_required")
+            | Some("startup_failure") => ChecksStatus::Failure,
+            _ => ChecksStatus::Pending,
+        },
+        _ => ChecksStatus::Pending,
+    }
+}
+
+/// Count a check-runs response by `gh_run_state`
+fn gh_check_runs(response: &serde_json::Value) -> PrChecks {
+    let mut checks = PrChecks::default();
+    let runs = response
+        .get("check_runs")
+        .and_then(|r| r.as_array())
+        .into_iter()
+        .flatten();
+
+    for run in runs {
+        match gh_run_state(run) {
+            ChecksStatus::Success => checks.passed += 1,
+            ChecksStatus::Failure => checks.failed += 1,
+            ChecksStatus::Pending => checks.pending += 1,
+            ChecksStatus::None => checks.neutral += 1,
+        }
+    }
+    checks
+}
+
+/// Review requests and your PRs out of the `reviews` and `mine` searches of
+/// the Home query
+fn gh_home_prs(data: &serde_json::Value) -> (Vec<ReviewRequest>, Vec<MyPr>) {
+    let nodes = |search: &str| {
+        data[search]["nodes"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            // Nodes the token can't see come back empty
+            .filter(|pr| pr["number"].is_u64())
+    };
+    let text = |pr: &serde_json::Value, pointer: &str| {
+        pr.pointer(pointer)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let updated_at = |pr: &serde_json::Value| {
+        pr["updatedAt"]
+            .as_str()
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+            .map(|d| d.with_timezone(&chrono::Utc))
+            .unwrap_or_default()
+    };
+
+    let review_requests = nodes("reviews")
+        .map(|pr| ReviewRequest {
+            repo_owner: text(&pr, "/repository/owner/login"),
+            repo_name: text(&pr, "/repository/name"),
+            pr_number: pr["number"].as_u64().unwrap_or_default(),
+            pr_title: text(&pr, "/title"),
+            author: text(&pr, "/author/login"),
+            updated_at: updated_at(&pr),
+        })
+        .collect();
+
+    let my_prs = nodes("mine")
+        .map(|pr| {
+            let rollup = pr.pointer("/commits/nodes/0/commit/statusCheckRollup/state");
+            let checks_status = match rollup.and_then(|s| s.as_str()) {
+                Some("SUCCESS") => ChecksStatus::Success,
+                Some("FAILURE") | Some("ERROR") => ChecksStatus::Failure,
+                Some("PENDING") | Some("EXPECTED") => ChecksStatus::Pending,
+                _ => ChecksStatus::None,
+            };
+            let mergeable = if pr["isDraft"].as_bool() == Some(true) {
+                MergeableState::Draft
+            } else {
+                match pr["mergeable"].as_str() {
+                    Some("MERGEABLE") => MergeableState::Clean,
+                    Some("CONFLICTING") => MergeableState::Conflicts,
+                    _ => MergeableState::Unknown,
+                }
+            };
+            MyPr {
+                repo_owner: text(&pr, "/repository/owner/login"),
+                repo_name: text(&pr, "/repository/name"),
+                number: pr["number"].as_u64().unwrap_or_default(),
+                title: text(&pr, "/title"),
+                state: PrState::Open,
+                checks_status,
+                mergeable,
+                updated_at: updated_at(&pr),
+            }
+        })
+        .collect();
+
+    (review_requests, my_prs)
+}
+
+/// `endCursor` of a GraphQL connection with more after it
+fn next_cursor(connection: &serde_json::Value) -> Option<String> {
+    let info = &connection["pageInfo"];
+    if info["hasNextPage"].as_bool() != Some(true) {
+        return None;
+    }
+    info["endCursor"].as_str().map(String::from)
+}
+
+fn gh_review_thread(node: &serde_json::Value) -> Option<ReviewThread> {
+    Some(ReviewThread {
+        id: node.get("id")?.as_str()?.to_string(),
+        path: node.get("path").and_then(|p| p.as_str()).map(String::from),
+        line: node.get("line").and_then(|l| l.as_u64()),
+        resolved: node.get("isResolved")?.as_bool()?,
+        comments: gh_thread_comments(&node["comments"]),
+    })
+}
+
+/// The comments of a review thread's `comments` connection
+fn gh_thread_comments(connection: &serde_json::Value) -> Vec<Comment> {
+    connection["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|c| {
+            Some(Comment {
+                id: c.get("databaseId")?.as_u64()?,
+                author: c
+                    .pointer("/author/login")
+                    .and_then(|l| l.as_str())
+                    .unwrap_or("ghost")
+                    .to_string(),
+                body: c
+                    .get("body")
+                    .and_then(|b| b.as_str())
+                    .unwrap_or("")
+                    .to_string(),
+                created_at: c
+                    .get("createdAt")
+                    .and_then(|d| d.as_str())
+                    .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+                    .map(|d| d.with_timezone(&chrono::Utc))
+                    .unwrap_or_else(chrono::Utc::now),
+            })
+        })
+        .collect()
+}
+
+fn gh_repository(repo: octocrab::models::Repository) -> Repository {
+    Repository {
+        owner: repo
+            .owner
+            .map(|o| o.login)
+            .unwrap_or_else(|| "unknown".to_string()),
+        name: repo.name,
+        description: repo.description,
+        url: repo.html_url.map(|u| u.to_string()).unwrap_or_default(),
+        stars: repo.stargazers_count.unwrap_or(0),
+        updated_at: repo.updated_at.unwrap_or_else(chrono::Utc::now),
+    }
+}
+
+fn gh_label(l: octocrab::models::Label) -> Label {
+    Label {
+        name: l.name,
+        color: Some(l.color),
+    }
+}
+
+fn gh_issue(issue: octocrab::models::issues::Issue) -> Issue {
+    Issue {
+        number: issue.number,
+        title: issue.title,
+        state: match issue.state {
+            OctoIssueState::Closed => IssueState::Closed,
+            _ => IssueState::Open,
+        },
+        author: issue.user.login,
+        labels: issue.labels.into_iter().map(gh_label).collect(),
+        comments: issue.comments,
+        created_at: issue.created_at,
+        updated_at: issue.updated_at,
+        milestone: issue.milestone.map(|m| m.title),
+        assignees: issue.assignees.into_iter().map(|a| a.login).collect(),
+    }
+}
+
+fn gh_commit(c: octocrab::models::repos::RepoCommit) -> Commit {
+    let message = c.commit.message.lines().next().unwrap_or("").to_string();
+    let author = c
+        .author
+        .map(|a| a.login)
+        .or_else(|| c.commit.author.as_ref().map(|a| a.name.clone()))
+        .unwrap_or_else(|| "unknown".to_string());
+    let date = c
+        .commit
+        .author
+        .and_then(|a| a.date)
+        .unwrap_or_else(chrono::Utc::now);
+
+    Commit {
+        sha: c.sha,
+        message,
+        author,
+        date,
+    }
+}
+
+/// Map the `files` array shared by the commit and pull request endpoints
+fn parse_files(files: Option<&serde_json::Value>) -> Vec<CommitFile> {
+    files
+        .and_then(|f| f.as_array())
+        .map(|files| {
+            files
+                .iter()
+                .filter_map(|f| {
+                    Some(CommitFile {
+                        filename: f.get("filename")?.as_str()?.to_string(),
+                        status: f.get("status")?.as_str()?.to_string(),
+                        additions: f.get("additions").and_then(|a| a.as_u64()).unwrap_or(0),
+                        deletions: f.get("deletions").and_then(|d| d.as_u64()).unwrap_or(0),
+                        patch: f
+                            .get("patch")
+                            .and_then(|p| p.as_str())
+                            .map(|s| s.to_string()),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn milestone_titles_with_quotes_are_refused() {
+        assert_eq!(milestone_qualifier("v1.0").unwrap(), "milestone:\"v1.0\"");
+        assert!(matches!(
+            milestone_qualifier("v1\" repo:other/secret"),
+            Err(GritError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn check_runs_count_by_conclusion() {
+        let count = |conclusion: &str| {
+            gh_check_runs(&serde_json::json!({
+                "check_runs": [{ "status": "completed", "conclusion": conclusion }],
+            }))
+        };
+        let checks = |passed, failed, pending, neutral| PrChecks {
+            passed,
+            failed,
+            pending,
+            neutral,
+        };
+        assert_eq!(count("success"), checks(1, 0, 0, 0));
+        assert_eq!(count("skipped"), checks(1, 0, 0, 0));
+        assert_eq!(count("failure"), checks(0, 1, 0, 0));
+        assert_eq!(count("action_required"), checks(0, 1, 0, 0));
+        assert_eq!(count("stale"), checks(0, 0, 1, 0));
+        assert_eq!(count("something_new"), checks(0, 0, 1, 0));
+        assert_eq!(count("neutral"), checks(0, 0, 0, 1));
+
+        let running = gh_check_runs(&serde_json::json!({
+            "check_runs": [{ "status": "in_progress", "conclusion": null }],
+        }));
+        assert_eq!(running, checks(0, 0, 1, 0));
+
+        // Commit checks read runs the same way
+        let run = |conclusion: &str| {
+            gh_run_state(&serde_json::json!({ "status": "completed", "conclusion": conclusion }))
+        };
+        assert_eq!(run("startup_failure"), ChecksStatus::Failure);
+        assert_eq!(run("stale"), ChecksStatus::Pending);
+        assert_eq!(run("neutral"), ChecksStatus::None);
+    }
+
+    #[test]
+    fn review_threads_page_by_cursor() {
+        let thread = serde_json::json!({
+            "id": "T1",
+            "isResolved": false,
+            "path": "src/main.rs",
+            "line": 4,
+            "comments": {
+                "pageInfo": { "hasNextPage": true, "endCursor": "c100" },
+                "nodes": [{
+                    "databaseId": 7,
+                    "body": "nit",
+                    "createdAt": "2024-05-01T09:00:00Z",
+                    "author": { "login": "grace" },
+                }],
+            },
+        });
+        let parsed = gh_review_thread(&thread).unwrap();
+        assert_eq!(parsed.comments.len(), 1);
+        assert_eq!(parsed.comments[0].author, "grace");
+        assert_eq!(next_cursor(&thread["comments"]).as_deref(), Some("c100"));
+
+        let last = serde_json::json!({ "pageInfo": { "hasNextPage": false, "endCursor": "c200" } });
+        assert_eq!(next_cursor(&last), None);
+    }
+
+    #[test]
+    fn home_query_fills_both_sections() {
+        let pr = |number: u64, draft: bool, mergeable: &str, rollup: serde_json::Value| {
+            serde_json::json!({
+                "number": number,
+                "title": format!("PR {}", number),
+                "updatedAt": "2024-05-01T12:00:00Z",
+                "isDraft": draft,
+                "mergeable": mergeable,
+                "author": { "login": "alice" },
+                "repository": { "name": "grit", "owner": { "login": "pders01" } },
+                "commits": { "nodes": [{ "commit": { "statusCheckRollup": rollup } }] },
+            })
+        };
+        let data = serde_json::json!({
+            "reviews": { "nodes": [pr(1, false, "MERGEABLE", serde_json::Value::Null), {}] },
+            "mine": { "nodes": [
+                pr(2, false, "CONFLICTING", serde_json::json!({ "state": "FAILURE" })),
+                pr(3, true, "UNKNOWN", serde_json::json!({ "state": "PENDING" })),
+                pr(4, false, "MERGEABLE", serde_json::Value::Null),
+            ] },
+        });
+
+        let (reviews, mine) = gh_home_prs(&data);
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].repo_owner, "pders01");
+        assert_eq!(reviews[0].pr_number, 1);
+        assert_eq!(reviews[0].author, "alice");
+
+        let states: Vec<_> = mine
+            .iter()
+            .map(|p| (p.number, p.checks_status, p.mergeable))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                (2, ChecksStatus::Failure, MergeableState::Conflicts),
+                (3, ChecksStatus::Pending, MergeableState::Draft),
+                (4, ChecksStatus::None, MergeableState::Clean),
+            ]
+        );
     }
 }
@@ -1,104 +1,263 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::Deserialize;
+use tokio::sync::OnceCell;
 
+use crate::config::Transport;
 use crate::error::{GritError, Result};
-use crate::forge::Forge;
+use crate::forge::{next_page, page_info, Forge};
+use crate::http::{self, RetrySend};
 use crate::types::{
-    ActionConclusion, ActionRun, ActionStatus, ChecksStatus, Commit, CommitDetail, CommitFile,
-    CommitStats, Issue, IssueState, PagedResult, PrState, PrStats, PrSummary, PullRequest,
-    Repository,
+    split_repo_path, ActionConclusion, ActionRun, ActionStatus, Artifact, ChecksStatus, Comment,
+    Commit, CommitCheck, CommitDetail, CommitFile, CommitStats, ForkStatus, Issue, IssueFields,
+    IssueRef, IssueState, IssueTemplate, Label, MergeableState, Milestone, PageInfo, PagedResult,
+    PrChecks, PrFilter, PrReviews, PrState, PrStats, PrSummary, PullRequest, Reaction,
+    ReactionTarget, RepoMeta, RepoStats, Repository, Review, ReviewComment, ReviewState,
+    ReviewThread, ReviewerLoad, SearchQuery, SearchState, Subscription, INSIGHTS_TOP,
+    RECENT_ACTIVITY_DAYS,
 };
 
 pub struct GitLab {
     client: Client,
-    host: String,
+    /// Scheme, host, port and path the instance serves from
+    base_url: String,
     token: String,
+    page_size: usize,
+    /// Signed-in user, asked for once; filters by person need the name
+    user: OnceCell<String>,
 }
 
 impl std::fmt::Debug for GitLab {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("GitLab")
-            .field("host", &self.host)
+            .field("base_url", &self.base_url)
             .finish_non_exhaustive()
     }
 }
 
 impl GitLab {
-    pub fn new(host: String, token: String) -> Self {
-        Self {
-            client: Client::new(),
-            host,
+    /// `base_url` is `ForgeConfig::base_url`, so instances served under a
+    /// subpath ("https://example.com/gitlab") keep it
+    pub fn new(
+        base_url: String,
+        token: String,
+        timeout: Duration,
+        transport: &Transport,
+        page_size: usize,
+    ) -> Result<Self> {
+        Ok(Self {
+            client: http::client(timeout, transport)?,
+            base_url: base_url.trim_end_matches('/').to_string(),
             token,
-        }
+            page_size,
+            user: OnceCell::new(),
+        })
     }
 
     fn api_url(&self, path: &str) -> String {
-        format!("https://{}/api/v4{}", self.host, path)
+        format!("{}/api/v4{}", self.base_url, path)
     }
 
-    /// URL-encode owner/repo as a project path for GitLab API
+    /// URL-encode owner/repo as a project path for GitLab API; `owner` is the
+    /// full namespace, so nested groups encode as "group%2Fsub%2Fproject"
     fn project_path(owner: &str, repo: &str) -> String {
         urlencoding::encode(&format!("{}/{}", owner, repo)).into_owned()
     }
 
-    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
+    /// `field` is `add_labels` or `remove_labels`; both take a comma-separated list
+    async fn update_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        kind: &str,
+        number: u64,
+        field: &str,
+        labels: &[String],
+    ) -> Result<()> {
+        let project = Self::project_path(owner, repo);
+        let collection = if kind == "pr" {
+            "merge_requests"
+        } else {
+            "issues"
+        };
+        let url = self.api_url(&format!("/projects/{}/{}/{}", project, collection, number));
+        let body = serde_json::json!({ field: labels.join(",") });
         let response = self
             .client
-            .get(url)
+            .put(&url)
             .header("PRIVATE-TOKEN", &self.token)
-            .send()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown error".to_string());
-            return Err(GritError::Api(format!("GitLab API {}: {}", status, text)));
+            .json(&body)
+            .send_retrying()
+            .await?;
+        http::check(response, "Update labels").await?;
+        Ok(())
+    }
+
+    /// The ids GitLab expects in `assignee_ids`; unknown usernames are skipped
+    async fn user_ids(&self, usernames: &[String]) -> Result<Vec<u64>> {
+        let mut ids = Vec::new();
+        for name in usernames {
+            let url = self.api_url(&format!("/users?username={}", urlencoding::encode(name)));
+            let users: Vec<GlMrAuthor> = self.get_json(&url).await?;
+            ids.extend(users.first().map(|u| u.id));
         }
+        Ok(ids)
+    }
 
-        response
-            .json()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))
+    /// Approve an MR, or withdraw the current user's approval
+    async fn set_approval(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        approve: bool,
+    ) -> Result<()> {
+        let project = Self::project_path(owner, repo);
+        let endpoint = if approve { "approve" } else { "unapprove" };
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}/{}",
+            project, number, endpoint
+        ));
+        let response = self
+            .client
+            .post(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .send_retrying()
+            .await?;
+
+        // Unapproving an MR you never approved is a 404: nothing to withdraw
+        if !approve && response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(());
+        }
+        http::check(response, "Approval").await?;
+        Ok(())
+    }
+
+    /// The single-MR endpoint only names its labels, so colors come from the
+    /// project's label list (best-effort)
+    async fn colored_labels(&self, owner: &str, repo: &str, labels: Vec<GlLabelRef>) -> Vec<Label> {
+        let mut labels: Vec<Label> = labels.into_iter().map(gl_label).collect();
+        if labels.iter().any(|l| l.color.is_none()) {
+            if let Ok(known) = self.list_labels(owner, repo).await {
+                for label in labels.iter_mut().filter(|l| l.color.is_none()) {
+                    label.color = known
+                        .iter()
+                        .find(|k| k.name == label.name)
+                        .and_then(|k| k.color.clone());
+                }
+            }
+        }
+        labels
+    }
+
+    /// Open a discussion on one diff line of an MR
+    async fn post_diff_comment(
+        &self,
+        project: &str,
+        number: u64,
+        refs: &GlDiffRefs,
+        comment: &ReviewComment,
+    ) -> Result<()> {
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}/discussions",
+            project, number
+        ));
+        let payload = serde_json::json!({
+            "body": comment.body,
+            "position": {
+                "position_type": "text",
+                "base_sha": refs.base_sha,
+                "start_sha": refs.start_sha,
+                "head_sha": refs.head_sha,
+                "old_path": comment.path,
+                "new_path": comment.path,
+                "old_line": comment.line.old,
+                "new_line": comment.line.new,
+            },
+        });
+        let response = self
+            .client
+            .post(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .json(&payload)
+            .send_retrying()
+            .await?;
+        http::check(response, "Comment").await?;
+        Ok(())
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let response = self
+            .client
+            .get(url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .send_retrying()
+            .await?;
+        let response = http::check(response, "GitLab request").await?;
+        response.json().await.map_err(http::api_error)
     }
 
     async fn get_json_paged<T: serde::de::DeserializeOwned>(
         &self,
         url: &str,
-    ) -> Result<(Vec<T>, Option<u64>)> {
+    ) -> Result<(Vec<T>, PageInfo)> {
         let response = self
             .client
             .get(url)
             .header("PRIVATE-TOKEN", &self.token)
-            .send()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown error".to_string());
-            return Err(GritError::Api(format!("GitLab API {}: {}", status, text)));
-        }
+            .send_retrying()
+            .await?;
+        let response = http::check(response, "GitLab request").await?;
 
-        let total_count = response
-            .headers()
-            .get("x-total")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|s| s.parse::<u64>().ok());
+        let page_info = page_info(response.headers());
 
-        let items: Vec<T> = response
-            .json()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
+        let items: Vec<T> = response.json().await.map_err(http::api_error)?;
+
+        Ok((items, page_info))
+    }
+
+    /// Every page of a list, following `X-Next-Page` to the last one
+    async fn get_json_all<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<Vec<T>> {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        let mut page = 1;
+        loop {
+            let response = self
+                .client
+                .get(format!("{}{}page={}", url, separator, page))
+                .header("PRIVATE-TOKEN", &self.token)
+                .send_retrying()
+                .await?;
+            let response = http::check(response, "GitLab request").await?;
+            let next = next_page(response.headers());
+            let found: Vec<T> = response.json().await.map_err(http::api_error)?;
+            items.extend(found);
+            match next {
+                Some(next) => page = next,
+                None => return Ok(items),
+            }
+        }
+    }
 
-        Ok((items, total_count))
+    /// POST a JSON body, reporting failures as "{what} failed (status): ..."
+    async fn post_json<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+        what: &str,
+    ) -> Result<T> {
+        let response = self
+            .client
+            .post(url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .json(body)
+            .send_retrying()
+            .await?;
+        let response = http::check(response, what).await?;
+        response.json().await.map_err(http::api_error)
     }
 }
 
@@ -106,12 +265,74 @@ impl GitLab {
 
 #[derive(Deserialize)]
 struct GlProject {
+    #[serde(default)]
+    id: u64,
     path_with_namespace: String,
     name: String,
     description: Option<String>,
     web_url: String,
     star_count: Option<u32>,
     last_activity_at: Option<String>,
+    #[serde(default)]
+    topics: Vec<String>,
+    default_branch: Option<String>,
+    forked_from_project: Option<Box<GlProject>>,
+}
+
+/// An entry of a project's template list; `key` fetches its `content`
+#[derive(Deserialize)]
+struct GlTemplate {
+    key: Option<String>,
+    name: String,
+    content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GlCompare {
+    #[serde(default)]
+    commits: Vec<serde_json::Value>,
+}
+
+/// Issue from the instance-wide `/issues` list, which names its project
+/// only through `references`
+#[derive(Deserialize)]
+struct GlScopedIssue {
+    iid: u64,
+    title: String,
+    author: GlMrAuthor,
+    updated_at: Option<String>,
+    references: GlReferences,
+}
+
+#[derive(Deserialize)]
+struct GlReferences {
+    /// "group/project#12"
+    full: String,
+}
+
+#[derive(Deserialize)]
+struct GlTodo {
+    target_type: String,
+    target: GlTodoTarget,
+    project: GlTodoProject,
+}
+
+#[derive(Deserialize)]
+struct GlTodoTarget {
+    iid: u64,
+    title: String,
+    author: GlMrAuthor,
+    updated_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GlTodoProject {
+    path_with_namespace: String,
+}
+
+#[derive(Deserialize)]
+struct GlGroup {
+    full_path: String,
 }
 
 #[derive(Deserialize)]
@@ -119,6 +340,11 @@ struct GlUser {
     username: String,
 }
 
+#[derive(Deserialize)]
+struct GlToken {
+    scopes: Vec<String>,
+}
+
 #[derive(Deserialize)]
 struct GlMergeRequest {
     iid: u64,
@@ -134,23 +360,150 @@ struct GlMergeRequest {
     closed_at: Option<String>,
     user_notes_count: Option<u64>,
     changes_count: Option<String>,
+    #[serde(default)]
+    reviewers: Vec<GlMrAuthor>,
+    #[serde(default)]
+    assignees: Vec<GlMrAuthor>,
+    detailed_merge_status: Option<String>,
+    has_conflicts: Option<bool>,
+    milestone: Option<GlMilestoneRef>,
+    #[serde(default)]
+    labels: Vec<GlLabelRef>,
+    sha: Option<String>,
+    diff_refs: Option<GlDiffRefs>,
+    source_project_id: Option<u64>,
+    target_project_id: Option<u64>,
+    merge_when_pipeline_succeeds: Option<bool>,
+}
+
+/// Commits a diff position is relative to
+#[derive(Deserialize)]
+struct GlDiffRefs {
+    base_sha: String,
+    start_sha: String,
+    head_sha: String,
+}
+
+#[derive(Deserialize)]
+struct GlMilestoneRef {
+    title: String,
+}
+
+#[derive(Deserialize)]
+struct GlMilestone {
+    id: u64,
+    title: String,
+    due_date: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GlLabel {
+    name: String,
+    color: Option<String>,
+}
+
+/// A bare label name, or the full label when asked for `with_labels_details`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum GlLabelRef {
+    Name(String),
+    Detailed(GlLabel),
 }
 
 #[derive(Deserialize)]
 struct GlMrAuthor {
+    #[serde(default)]
+    id: u64,
     username: String,
 }
 
+#[derive(Deserialize)]
+struct GlApprovals {
+    #[serde(default)]
+    approved_by: Vec<GlApprover>,
+    approvals_required: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct GlMrPipeline {
+    id: u64,
+}
+
+#[derive(Deserialize)]
+struct GlJob {
+    status: String,
+    #[serde(default)]
+    name: String,
+    /// Seconds, fractional
+    duration: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct GlContributor {
+    name: String,
+    commits: u64,
+}
+
+/// A pipeline job, for the artifacts it kept
+#[derive(Deserialize)]
+struct GlArtifactJob {
+    id: u64,
+    name: String,
+    artifacts_file: Option<GlArtifactsFile>,
+    artifacts_expire_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GlArtifactsFile {
+    #[serde(default)]
+    size: u64,
+}
+
+#[derive(Deserialize)]
+struct GlApprover {
+    user: GlMrAuthor,
+}
+
+#[derive(Deserialize)]
+struct GlNote {
+    id: u64,
+    body: String,
+    author: GlMrAuthor,
+    created_at: Option<String>,
+    #[serde(default)]
+    system: bool,
+    #[serde(default)]
+    resolvable: bool,
+    #[serde(default)]
+    resolved: bool,
+    position: Option<GlNotePosition>,
+}
+
+#[derive(Deserialize)]
+struct GlNotePosition {
+    new_path: Option<String>,
+    new_line: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct GlDiscussion {
+    id: String,
+    notes: Vec<GlNote>,
+}
+
 #[derive(Deserialize)]
 struct GlIssue {
     iid: u64,
     title: String,
     state: String,
     author: GlMrAuthor,
-    labels: Vec<String>,
+    labels: Vec<GlLabelRef>,
     user_notes_count: Option<u32>,
     created_at: Option<String>,
     updated_at: Option<String>,
+    milestone: Option<GlMilestoneRef>,
+    #[serde(default)]
+    assignees: Vec<GlMrAuthor>,
 }
 
 #[derive(Deserialize)]
@@ -195,6 +548,7 @@ struct GlPipeline {
     ref_field: Option<String>,
     source: Option<String>,
     created_at: Option<String>,
+    updated_at: Option<String>,
     #[allow(dead_code)]
     web_url: Option<String>,
 }
@@ -215,71 +569,266 @@ impl Forge for GitLab {
         "GitLab"
     }
 
+    fn page_size(&self) -> usize {
+        self.page_size
+    }
+
     fn web_url(&self, owner: &str, repo: &str, kind: &str, id: &str) -> String {
+        let project = format!("{}/{}/{}", self.base_url, owner, repo);
         match kind {
-            "repo" => format!("https://{}/{}/{}", self.host, owner, repo),
-            "pr" => format!(
-                "https://{}/{}/{}/-/merge_requests/{}",
-                self.host, owner, repo, id
-            ),
-            "issue" => format!("https://{}/{}/{}/-/issues/{}", self.host, owner, repo, id),
-            "commit" => format!("https://{}/{}/{}/-/commit/{}", self.host, owner, repo, id),
-            "action_run" => format!(
-                "https://{}/{}/{}/-/pipelines/{}",
-                self.host, owner, repo, id
-            ),
-            _ => format!("https://{}/{}/{}", self.host, owner, repo),
+            "user" => format!("{}/{}", self.base_url, id),
+            "pr" => format!("{}/-/merge_requests/{}", project, id),
+            "issue" => format!("{}/-/issues/{}", project, id),
+            "commit" => format!("{}/-/commit/{}", project, id),
+            "action_run" => format!("{}/-/pipelines/{}", project, id),
+            _ => project,
         }
     }
 
+    fn pr_head_ref(&self, number: u64) -> String {
+        format!("merge-requests/{}/head", number)
+    }
+
     async fn get_current_user(&self) -> Result<String> {
-        let url = self.api_url("/user");
-        let user: GlUser = self.get_json(&url).await?;
-        Ok(user.username)
+        self.user
+            .get_or_try_init(|| async {
+                let url = self.api_url("/user");
+                let user: GlUser = self.get_json(&url).await?;
+                Ok(user.username)
+            })
+            .await
+            .cloned()
+    }
+
+    async fn token_scopes(&self) -> Result<Option<Vec<String>>> {
+        // Personal access tokens only (15.5+); OAuth tokens aren't found
+        let url = self.api_url("/personal_access_tokens/self");
+        match self.get_json::<GlToken>(&url).await {
+            Ok(token) => Ok(Some(token.scopes)),
+            Err(GritError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn required_scope(&self) -> Option<&'static str> {
+        Some("api")
     }
 
     async fn list_repos(&self, page: u32) -> Result<PagedResult<Repository>> {
         let url = self.api_url(&format!(
-            "/projects?membership=true&order_by=last_activity_at&sort=desc&per_page=50&page={}",
-            page
+            "/projects?membership=true&order_by=last_activity_at&sort=desc&per_page={}&page={}",
+            self.page_size, page
+        ));
+        let (projects, page_info) = self.get_json_paged::<GlProject>(&url).await?;
+
+        Ok(PagedResult {
+            items: projects.into_iter().map(gl_repository).collect(),
+            page_info,
+        })
+    }
+
+    async fn list_assigned_issues(&self, _username: &str) -> Result<Vec<IssueRef>> {
+        let url = self.api_url(&format!(
+            "/issues?scope=assigned_to_me&state=opened&order_by=updated_at&sort=desc&per_page={}",
+            self.page_size
         ));
-        let (projects, total_count) = self.get_json_paged::<GlProject>(&url).await?;
+        let issues: Vec<GlScopedIssue> = self.get_json(&url).await?;
 
-        let repos = projects
+        Ok(issues
             .into_iter()
-            .map(|p| {
-                let parts: Vec<&str> = p.path_with_namespace.splitn(2, '/').collect();
-                let owner = parts.first().unwrap_or(&"unknown").to_string();
-                let name = if parts.len() > 1 {
-                    parts[1].to_string()
-                } else {
-                    p.name
-                };
+            .filter_map(|issue| {
+                let (path, _) = issue.references.full.rsplit_once('#')?;
+                let (repo_owner, repo_name) = split_repo_path(path)?;
+                Some(IssueRef {
+                    repo_owner,
+                    repo_name,
+                    number: issue.iid,
+                    title: issue.title,
+                    author: issue.author.username,
+                    is_pr: false,
+                    updated_at: parse_optional_datetime(issue.updated_at.as_deref()),
+                })
+            })
+            .collect())
+    }
 
-                Repository {
-                    owner,
-                    name,
-                    description: p.description.filter(|d| !d.is_empty()),
-                    url: p.web_url,
-                    stars: p.star_count.unwrap_or(0),
-                    updated_at: parse_optional_datetime(p.last_activity_at.as_deref()),
-                }
+    /// Pending "mentioned" to-dos, GitLab's record of where you were @-mentioned
+    async fn list_mentions(&self, _username: &str) -> Result<Vec<IssueRef>> {
+        let url = self.api_url(&format!(
+            "/todos?action=mentioned&state=pending&per_page={}",
+            self.page_size
+        ));
+        let todos: Vec<GlTodo> = self.get_json(&url).await?;
+
+        Ok(todos
+            .into_iter()
+            .filter(|todo| matches!(todo.target_type.as_str(), "Issue" | "MergeRequest"))
+            .filter_map(|todo| {
+                let (repo_owner, repo_name) = split_repo_path(&todo.project.path_with_namespace)?;
+                Some(IssueRef {
+                    repo_owner,
+                    repo_name,
+                    number: todo.target.iid,
+                    title: todo.target.title,
+                    author: todo.target.author.username,
+                    is_pr: todo.target_type == "MergeRequest",
+                    updated_at: parse_optional_datetime(todo.target.updated_at.as_deref()),
+                })
             })
-            .collect();
+            .collect())
+    }
+
+    /// Issues and merge requests through their list endpoints' filters; with
+    /// neither `is:issue` nor `is:mr`, a page of each
+    async fn search_issues(&self, query: &str, page: u32) -> Result<PagedResult<IssueRef>> {
+        let query = SearchQuery::parse(query).map_err(GritError::Api)?;
+        if query.mentions.is_some() {
+            return Err(GritError::Api("GitLab can't search by mentions:".into()));
+        }
+        // GitLab filters by username, so `@me` needs a name
+        let wants_me = [&query.author, &query.assignee, &query.review_requested]
+            .iter()
+            .any(|user| user.as_deref() == Some("@me"));
+        let me = if wants_me {
+            self.get_current_user().await?
+        } else {
+            String::new()
+        };
+        let user = |user: &str| {
+            if user == "@me" {
+                me.clone()
+            } else {
+                user.to_string()
+            }
+        };
+
+        let state = match query.state {
+            SearchState::Open => "opened",
+            SearchState::Closed => "closed",
+            SearchState::Merged => "merged",
+        };
+        let mut params = format!(
+            "state={}&order_by=updated_at&sort=desc&per_page={}&page={}",
+            state, self.page_size, page
+        );
+        for (key, value) in [
+            ("author_username", &query.author),
+            ("assignee_username", &query.assignee),
+        ] {
+            if let Some(value) = value {
+                params.push_str(&format!("&{}={}", key, urlencoding::encode(&user(value))));
+            }
+        }
+        if !query.labels.is_empty() {
+            params.push_str(&format!(
+                "&labels={}",
+                urlencoding::encode(&query.labels.join(","))
+            ));
+        }
+        if !query.text.is_empty() {
+            params.push_str(&format!("&search={}", urlencoding::encode(&query.text)));
+        }
+        let scope = match &query.owner {
+            Some(group) => format!("/groups/{}", urlencoding::encode(group)),
+            None => {
+                params.push_str("&scope=all");
+                String::new()
+            }
+        };
+
+        let issues = query.is_pr != Some(true)
+            && query.review_requested.is_none()
+            && query.state != SearchState::Merged;
+        let mrs = query.is_pr != Some(false);
+        let mut items = Vec::new();
+        let mut page_info = PageInfo::default();
+        for (kind, wanted, is_pr) in [("issues", issues, false), ("merge_requests", mrs, true)] {
+            if !wanted {
+                continue;
+            }
+            let mut url = self.api_url(&format!("{}/{}?{}", scope, kind, params));
+            if let Some(reviewer) = query.review_requested.as_deref().filter(|_| is_pr) {
+                url.push_str(&format!(
+                    "&reviewer_username={}",
+                    urlencoding::encode(&user(reviewer))
+                ));
+            }
+            let (found, info) = self.get_json_paged::<GlScopedIssue>(&url).await?;
+            items.extend(found.into_iter().filter_map(|item| {
+                let (path, _) = item.references.full.rsplit_once(['#', '!'])?;
+                let (repo_owner, repo_name) = split_repo_path(path)?;
+                Some(IssueRef {
+                    repo_owner,
+                    repo_name,
+                    number: item.iid,
+                    title: item.title,
+                    author: item.author.username,
+                    is_pr,
+                    updated_at: parse_optional_datetime(item.updated_at.as_deref()),
+                })
+            }));
+            // Two lists page side by side: the longer one decides
+            page_info.total_count = match (page_info.total_count, info.total_count) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            };
+            page_info.total_pages = page_info.total_pages.max(info.pages(self.page_size));
+        }
+        items.sort_by_key(|item| std::cmp::Reverse(item.updated_at));
+        Ok(PagedResult { items, page_info })
+    }
+
+    async fn list_orgs(&self) -> Result<Vec<String>> {
+        let url = self.api_url("/groups?min_access_level=10&order_by=path&per_page=100");
+        let groups: Vec<GlGroup> = self.get_json(&url).await?;
+        Ok(groups.into_iter().map(|g| g.full_path).collect())
+    }
+
+    async fn list_org_repos(&self, org: &str, page: u32) -> Result<PagedResult<Repository>> {
+        let url = self.api_url(&format!(
+            "/groups/{}/projects?include_subgroups=true&order_by=last_activity_at&sort=desc&per_page={}&page={}",
+            urlencoding::encode(org),
+            self.page_size,
+            page
+        ));
+        let (projects, page_info) = self.get_json_paged::<GlProject>(&url).await?;
 
         Ok(PagedResult {
-            items: repos,
-            total_count,
+            items: projects.into_iter().map(gl_repository).collect(),
+            page_info,
         })
     }
 
-    async fn list_prs(&self, owner: &str, repo: &str, page: u32) -> Result<PagedResult<PrSummary>> {
+    async fn list_prs(
+        &self,
+        owner: &str,
+        repo: &str,
+        filter: PrFilter,
+        milestone: Option<&Milestone>,
+        page: u32,
+    ) -> Result<PagedResult<PrSummary>> {
         let project = Self::project_path(owner, repo);
-        let url = self.api_url(&format!(
-            "/projects/{}/merge_requests?state=opened&order_by=updated_at&sort=desc&per_page=50&page={}",
-            project, page
+        let mut url = self.api_url(&format!(
+            "/projects/{}/merge_requests?state=opened&order_by=updated_at&sort=desc&per_page={}&page={}",
+            project, self.page_size, page
         ));
-        let (mrs, total_count) = self.get_json_paged::<GlMergeRequest>(&url).await?;
+        if let Some(milestone) = milestone {
+            url.push_str(&format!(
+                "&milestone={}",
+                urlencoding::encode(&milestone.title)
+            ));
+        }
+        let person = match filter {
+            PrFilter::All => None,
+            PrFilter::Authored => Some("author_username"),
+            PrFilter::Assigned => Some("assignee_username"),
+            PrFilter::ReviewRequested => Some("reviewer_username"),
+        };
+        if let Some(key) = person {
+            let me = self.get_current_user().await?;
+            url.push_str(&format!("&{}={}", key, urlencoding::encode(&me)));
+        }
+        let (mrs, page_info) = self.get_json_paged::<GlMergeRequest>(&url).await?;
 
         let summaries = mrs
             .into_iter()
@@ -289,12 +838,13 @@ impl Forge for GitLab {
                 state: gl_mr_state(&mr.state),
                 author: mr.author.username,
                 updated_at: parse_optional_datetime(mr.updated_at.as_deref()),
+                milestone: mr.milestone.map(|m| m.title),
             })
             .collect();
 
         Ok(PagedResult {
             items: summaries,
-            total_count,
+            page_info,
         })
     }
 
@@ -316,6 +866,9 @@ impl Forge for GitLab {
             state: gl_mr_state(&mr.state),
             author: mr.author.username,
             head_branch: mr.source_branch.unwrap_or_default(),
+            head_sha: mr.sha.unwrap_or_default(),
+            from_fork: mr.source_project_id != mr.target_project_id,
+            auto_merge: mr.merge_when_pipeline_succeeds.unwrap_or(false),
             base_branch: mr.target_branch.unwrap_or_default(),
             stats: PrStats {
                 additions: 0,
@@ -328,38 +881,41 @@ impl Forge for GitLab {
             updated_at: parse_optional_datetime(mr.updated_at.as_deref()),
             merged_at: mr.merged_at.as_deref().map(parse_datetime),
             closed_at: mr.closed_at.as_deref().map(parse_datetime),
+            mergeable_state: gl_mergeable_state(
+                mr.detailed_merge_status.as_deref(),
+                mr.has_conflicts,
+            ),
+            milestone: mr.milestone.map(|m| m.title),
+            labels: self.colored_labels(owner, repo, mr.labels).await,
+            assignees: mr.assignees.into_iter().map(|a| a.username).collect(),
         })
     }
 
-    async fn list_issues(&self, owner: &str, repo: &str, page: u32) -> Result<PagedResult<Issue>> {
+    async fn list_issues(
+        &self,
+        owner: &str,
+        repo: &str,
+        milestone: Option<&Milestone>,
+        page: u32,
+    ) -> Result<PagedResult<Issue>> {
         let project = Self::project_path(owner, repo);
-        let url = self.api_url(&format!(
-            "/projects/{}/issues?state=opened&order_by=updated_at&sort=desc&per_page=50&page={}",
-            project, page
+        let mut url = self.api_url(&format!(
+            "/projects/{}/issues?state=opened&order_by=updated_at&sort=desc&with_labels_details=true&per_page={}&page={}",
+            project, self.page_size, page
         ));
-        let (issues, total_count) = self.get_json_paged::<GlIssue>(&url).await?;
+        if let Some(milestone) = milestone {
+            url.push_str(&format!(
+                "&milestone={}",
+                urlencoding::encode(&milestone.title)
+            ));
+        }
+        let (issues, page_info) = self.get_json_paged::<GlIssue>(&url).await?;
 
-        let result = issues
-            .into_iter()
-            .map(|i| Issue {
-                number: i.iid,
-                title: i.title,
-                state: if i.state == "closed" {
-                    IssueState::Closed
-                } else {
-                    IssueState::Open
-                },
-                author: i.author.username,
-                labels: i.labels,
-                comments: i.user_notes_count.unwrap_or(0),
-                created_at: parse_optional_datetime(i.created_at.as_deref()),
-                updated_at: parse_optional_datetime(i.updated_at.as_deref()),
-            })
-            .collect();
+        let result = issues.into_iter().map(gl_issue).collect();
 
         Ok(PagedResult {
             items: result,
-            total_count,
+            page_info,
         })
     }
 
@@ -371,34 +927,16 @@ impl Forge for GitLab {
     ) -> Result<PagedResult<Commit>> {
         let project = Self::project_path(owner, repo);
         let url = self.api_url(&format!(
-            "/projects/{}/repository/commits?per_page=50&page={}",
-            project, page
+            "/projects/{}/repository/commits?per_page={}&page={}",
+            project, self.page_size, page
         ));
-        let (commits, total_count) = self.get_json_paged::<GlCommit>(&url).await?;
+        let (commits, page_info) = self.get_json_paged::<GlCommit>(&url).await?;
 
-        let result = commits
-            .into_iter()
-            .map(|c| {
-                let message = c
-                    .title
-                    .or(c
-                        .message
-                        .as_ref()
-                        .map(|m| m.lines().next().unwrap_or("").to_string()))
-                    .unwrap_or_default();
-
-                Commit {
-                    sha: c.id,
-                    message,
-                    author: c.author_name.unwrap_or_else(|| "unknown".to_string()),
-                    date: parse_optional_datetime(c.created_at.as_deref()),
-                }
-            })
-            .collect();
+        let result = commits.into_iter().map(gl_commit).collect();
 
         Ok(PagedResult {
             items: result,
-            total_count,
+            page_info,
         })
     }
 
@@ -428,46 +966,7 @@ impl Forge for GitLab {
             },
         );
 
-        let files = diffs
-            .into_iter()
-            .map(|d| {
-                let status = if d.new_file {
-                    "added"
-                } else if d.deleted_file {
-                    "removed"
-                } else if d.renamed_file {
-                    "renamed"
-                } else {
-                    "modified"
-                };
-
-                // Count additions/deletions from diff text
-                let (additions, deletions) = d
-                    .diff
-                    .as_deref()
-                    .map(|text| {
-                        let mut adds: u64 = 0;
-                        let mut dels: u64 = 0;
-                        for line in text.lines() {
-                            if line.starts_with('+') && !line.starts_with("+++") {
-                                adds += 1;
-                            } else if line.starts_with('-') && !line.starts_with("---") {
-                                dels += 1;
-                            }
-                        }
-                        (adds, dels)
-                    })
-                    .unwrap_or((0, 0));
-
-                CommitFile {
-                    filename: d.new_path,
-                    status: status.to_string(),
-                    additions,
-                    deletions,
-                    patch: d.diff,
-                }
-            })
-            .collect();
+        let files = diffs.into_iter().map(gl_commit_file).collect();
 
         Ok(CommitDetail {
             sha: detail.id,
@@ -514,6 +1013,32 @@ impl Forge for GitLab {
         Ok(diff)
     }
 
+    async fn stream_pr_diff(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Option<reqwest::Response>> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}/raw_diffs",
+            project, number
+        ));
+        let response = self
+            .client
+            .get(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .timeout(http::DOWNLOAD_TIMEOUT)
+            .send_retrying()
+            .await?;
+        // Older instances (before 17.9) only have the changes, via `get_pr_diff`
+        match http::check(response, "Fetch diff").await {
+            Ok(response) => Ok(Some(response)),
+            Err(GritError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
     async fn merge_pr(&self, owner: &str, repo: &str, number: u64, method: &str) -> Result<()> {
         let project = Self::project_path(owner, repo);
         let url = self.api_url(&format!(
@@ -533,17 +1058,57 @@ impl Forge for GitLab {
             .put(&url)
             .header("PRIVATE-TOKEN", &self.token)
             .json(&body)
-            .send()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown error".to_string());
-            return Err(GritError::Api(format!("Merge failed: {}", text)));
-        }
+            .send_retrying()
+            .await?;
+        http::check(response, "Merge").await?;
+        Ok(())
+    }
+
+    async fn enable_auto_merge(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        method: &str,
+        delete_branch: bool,
+    ) -> Result<()> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}/merge",
+            project, number
+        ));
+        // `merge_when_pipeline_succeeds` is the pre-17 name of `auto_merge`
+        let body = serde_json::json!({
+            "auto_merge": true,
+            "merge_when_pipeline_succeeds": true,
+            "squash": method == "squash",
+            "should_remove_source_branch": delete_branch,
+        });
+        let response = self
+            .client
+            .put(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .json(&body)
+            .send_retrying()
+            .await?;
+        http::check(response, "Set auto-merge").await?;
+        Ok(())
+    }
+
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<()> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!(
+            "/projects/{}/repository/branches/{}",
+            project,
+            urlencoding::encode(branch)
+        ));
+        let response = self
+            .client
+            .delete(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .send_retrying()
+            .await?;
+        http::check(response, "Delete branch").await?;
         Ok(())
     }
 
@@ -556,20 +1121,32 @@ impl Forge for GitLab {
             .put(&url)
             .header("PRIVATE-TOKEN", &self.token)
             .json(&body)
-            .send()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown error".to_string());
-            return Err(GritError::Api(format!("Close MR failed: {}", text)));
-        }
+            .send_retrying()
+            .await?;
+        http::check(response, "Close MR").await?;
         Ok(())
     }
 
+    async fn get_issue(&self, owner: &str, repo: &str, number: u64) -> Result<Issue> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!(
+            "/projects/{}/issues/{}?with_labels_details=true",
+            project, number
+        ));
+        let issue: GlIssue = self.get_json(&url).await?;
+        Ok(gl_issue(issue))
+    }
+
+    async fn get_issue_body(&self, owner: &str, repo: &str, number: u64) -> Result<Option<String>> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}/issues/{}", project, number));
+        let issue: serde_json::Value = self.get_json(&url).await?;
+        Ok(issue
+            .get("description")
+            .and_then(|d| d.as_str())
+            .map(str::to_string))
+    }
+
     async fn close_issue(&self, owner: &str, repo: &str, number: u64) -> Result<()> {
         let project = Self::project_path(owner, repo);
         let url = self.api_url(&format!("/projects/{}/issues/{}", project, number));
@@ -579,17 +1156,9 @@ impl Forge for GitLab {
             .put(&url)
             .header("PRIVATE-TOKEN", &self.token)
             .json(&body)
-            .send()
-            .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown error".to_string());
-            return Err(GritError::Api(format!("Close issue failed: {}", text)));
-        }
+            .send_retrying()
+            .await?;
+        http::check(response, "Close issue").await?;
         Ok(())
     }
 
@@ -606,20 +1175,458 @@ impl Forge for GitLab {
             .post(&url)
             .header("PRIVATE-TOKEN", &self.token)
             .json(&payload)
-            .send()
+            .send_retrying()
+            .await?;
+        http::check(response, "Comment").await?;
+        Ok(())
+    }
+
+    async fn list_pr_commits(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<Commit>> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}/commits?per_page=100",
+            project, number
+        ));
+        let commits: Vec<GlCommit> = self.get_json_all(&url).await?;
+        // GitLab lists MR commits newest first
+        Ok(commits.into_iter().rev().map(gl_commit).collect())
+    }
+
+    async fn list_pr_files(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<CommitFile>> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}/diffs?per_page=100",
+            project, number
+        ));
+        let diffs: Vec<GlDiff> = self.get_json_all(&url).await?;
+        Ok(diffs.into_iter().map(gl_commit_file).collect())
+    }
+
+    async fn list_pr_comments(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<Comment>> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}/notes?sort=asc&order_by=created_at&per_page=100",
+            project, number
+        ));
+        let notes: Vec<GlNote> = self.get_json_all(&url).await?;
+
+        // System notes ("added 1 commit", "changed the description") aren't conversation,
+        // and resolvable notes belong to review threads
+        let comments = notes
+            .into_iter()
+            .filter(|n| !n.system && !n.resolvable)
+            .map(|n| Comment {
+                id: n.id,
+                author: n.author.username,
+                body: n.body,
+                created_at: parse_optional_datetime(n.created_at.as_deref()),
+            })
+            .collect();
+
+        Ok(comments)
+    }
+
+    async fn list_milestones(&self, owner: &str, repo: &str) -> Result<Vec<Milestone>> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!(
+            "/projects/{}/milestones?state=active&per_page=100",
+            project
+        ));
+        let milestones: Vec<GlMilestone> = self.get_json(&url).await?;
+
+        Ok(milestones
+            .into_iter()
+            .map(|m| Milestone {
+                id: m.id,
+                title: m.title,
+                // due_date is a plain date (YYYY-MM-DD)
+                due_on: m
+                    .due_date
+                    .as_deref()
+                    .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+                    .and_then(|d| d.and_hms_opt(0, 0, 0))
+                    .map(|d| d.and_utc()),
+            })
+            .collect())
+    }
+
+    async fn set_milestone(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        milestone: Option<u64>,
+    ) -> Result<()> {
+        let project = Self::project_path(owner, repo);
+        // Issues only: merge requests keep their milestone under /merge_requests
+        let url = self.api_url(&format!("/projects/{}/issues/{}", project, number));
+        // 0 unassigns
+        let body = serde_json::json!({ "milestone_id": milestone.unwrap_or(0) });
+        let response = self
+            .client
+            .put(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .json(&body)
+            .send_retrying()
+            .await?;
+        http::check(response, "Set milestone").await?;
+        Ok(())
+    }
+
+    async fn list_labels(&self, owner: &str, repo: &str) -> Result<Vec<Label>> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}/labels?per_page=100", project));
+        let labels: Vec<GlLabel> = self.get_json(&url).await?;
+
+        Ok(labels
+            .into_iter()
+            .map(|l| gl_label(GlLabelRef::Detailed(l)))
+            .collect())
+    }
+
+    async fn add_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        kind: &str,
+        number: u64,
+        labels: &[String],
+    ) -> Result<()> {
+        self.update_labels(owner, repo, kind, number, "add_labels", labels)
+            .await
+    }
+
+    async fn remove_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        kind: &str,
+        number: u64,
+        labels: &[String],
+    ) -> Result<()> {
+        self.update_labels(owner, repo, kind, number, "remove_labels", labels)
             .await
-            .map_err(|e| GritError::Api(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "unknown error".to_string());
-            return Err(GritError::Api(format!("Comment failed: {}", text)));
+    }
+
+    /// `.gitlab/issue_templates/*.md`; GitLab has no front matter, so only
+    /// the body is filled in
+    async fn list_issue_templates(&self, owner: &str, repo: &str) -> Result<Vec<IssueTemplate>> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}/templates/issues", project));
+        let entries: Vec<GlTemplate> = self.get_json(&url).await?;
+
+        let mut templates = Vec::new();
+        for entry in entries {
+            let key = entry.key.unwrap_or_else(|| entry.name.clone());
+            let url = self.api_url(&format!(
+                "/projects/{}/templates/issues/{}",
+                project,
+                urlencoding::encode(&key)
+            ));
+            let template: GlTemplate = self.get_json(&url).await?;
+            templates.push(IssueTemplate {
+                name: template.name,
+                body: template.content.unwrap_or_default(),
+                ..IssueTemplate::default()
+            });
         }
+        Ok(templates)
+    }
+
+    async fn create_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        fields: &IssueFields,
+        body: &str,
+    ) -> Result<u64> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}/issues", project));
+        let payload = serde_json::json!({
+            "title": fields.title,
+            "description": body,
+            "labels": fields.labels.join(","),
+            "assignee_ids": self.user_ids(&fields.assignees).await?,
+            "milestone_id": fields.milestone,
+        });
+        let issue: GlIssue = self.post_json(&url, &payload, "Create issue").await?;
+        Ok(issue.iid)
+    }
+
+    async fn update_fields(
+        &self,
+        owner: &str,
+        repo: &str,
+        kind: &str,
+        number: u64,
+        fields: &IssueFields,
+    ) -> Result<()> {
+        let project = Self::project_path(owner, repo);
+        let collection = if kind == "pr" {
+            "merge_requests"
+        } else {
+            "issues"
+        };
+        let url = self.api_url(&format!("/projects/{}/{}/{}", project, collection, number));
+        // An empty list or 0 clears; labels are one comma-separated string
+        let payload = serde_json::json!({
+            "title": fields.title,
+            "labels": fields.labels.join(","),
+            "assignee_ids": self.user_ids(&fields.assignees).await?,
+            "milestone_id": fields.milestone.unwrap_or(0),
+        });
+        let response = self
+            .client
+            .put(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .json(&payload)
+            .send_retrying()
+            .await?;
+        http::check(response, "Update").await?;
         Ok(())
     }
 
+    async fn update_pr(&self, owner: &str, repo: &str, number: u64, body: &str) -> Result<()> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}/merge_requests/{}", project, number));
+        let payload = serde_json::json!({ "description": body });
+        let response = self
+            .client
+            .put(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .json(&payload)
+            .send_retrying()
+            .await?;
+        http::check(response, "Update description").await?;
+        Ok(())
+    }
+
+    async fn list_review_threads(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<ReviewThread>> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}/discussions?per_page=100",
+            project, number
+        ));
+        let discussions: Vec<GlDiscussion> = self.get_json_all(&url).await?;
+
+        let threads = discussions
+            .into_iter()
+            .filter(|d| d.notes.first().is_some_and(|n| n.resolvable && !n.system))
+            .map(|d| {
+                let first = &d.notes[0];
+                let path = first.position.as_ref().and_then(|p| p.new_path.clone());
+                let line = first.position.as_ref().and_then(|p| p.new_line);
+                // A discussion is resolved once every resolvable note in it is
+                let resolved = d.notes.iter().filter(|n| n.resolvable).all(|n| n.resolved);
+                ReviewThread {
+                    id: d.id,
+                    path,
+                    line,
+                    resolved,
+                    comments: d
+                        .notes
+                        .into_iter()
+                        .filter(|n| !n.system)
+                        .map(|n| Comment {
+                            id: n.id,
+                            author: n.author.username,
+                            body: n.body,
+                            created_at: parse_optional_datetime(n.created_at.as_deref()),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Ok(threads)
+    }
+
+    async fn set_thread_resolved(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        thread_id: &str,
+        resolved: bool,
+    ) -> Result<()> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}/discussions/{}",
+            project, number, thread_id
+        ));
+        let body = serde_json::json!({ "resolved": resolved });
+        let response = self
+            .client
+            .put(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .json(&body)
+            .send_retrying()
+            .await?;
+        http::check(response, "Resolve").await?;
+        Ok(())
+    }
+
+    async fn list_reviews(&self, owner: &str, repo: &str, number: u64) -> Result<PrReviews> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}/merge_requests/{}", project, number));
+        let mr: GlMergeRequest = self.get_json(&url).await?;
+
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}/approvals",
+            project, number
+        ));
+        let approvals: GlApprovals = self.get_json(&url).await?;
+
+        // GitLab only tracks approvals; assigned reviewers who haven't
+        // approved are still pending
+        let approved: Vec<String> = approvals
+            .approved_by
+            .into_iter()
+            .map(|a| a.user.username)
+            .collect();
+
+        Ok(PrReviews {
+            requested: mr
+                .reviewers
+                .into_iter()
+                .map(|r| r.username)
+                .filter(|r| !approved.contains(r))
+                .collect(),
+            reviews: approved
+                .into_iter()
+                .map(|reviewer| Review {
+                    reviewer,
+                    state: ReviewState::Approved,
+                    submitted_at: None,
+                })
+                .collect(),
+            required_approvals: approvals.approvals_required,
+        })
+    }
+
+    async fn get_pr_checks(&self, owner: &str, repo: &str, number: u64) -> Result<PrChecks> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}/pipelines?per_page=1",
+            project, number
+        ));
+        let pipelines: Vec<GlMrPipeline> = self.get_json(&url).await?;
+        let Some(latest) = pipelines.first() else {
+            return Ok(PrChecks::default());
+        };
+
+        let url = self.api_url(&format!(
+            "/projects/{}/pipelines/{}/jobs?per_page=100",
+            project, latest.id
+        ));
+        let jobs: Vec<GlJob> = self.get_json(&url).await?;
+
+        let mut checks = PrChecks::default();
+        for job in jobs {
+            match job.status.as_str() {
+                "success" | "skipped" | "manual" => checks.passed += 1,
+                "failed" | "canceled" => checks.failed += 1,
+                _ => checks.pending += 1,
+            }
+        }
+        Ok(checks)
+    }
+
+    async fn get_repo_stats(&self, owner: &str, repo: &str) -> Result<RepoStats> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}/issues_statistics", project));
+        let issues: serde_json::Value = self.get_json(&url).await?;
+        let issue_count = |state: &str| issues["statistics"]["counts"][state].as_u64().unwrap_or(0);
+
+        // One-item pages: only the X-Total header is wanted
+        let mut mr_counts = [0; 3];
+        for (count, state) in mr_counts.iter_mut().zip(["opened", "merged", "closed"]) {
+            let url = self.api_url(&format!(
+                "/projects/{}/merge_requests?state={}&per_page=1",
+                project, state
+            ));
+            let (_, page_info) = self.get_json_paged::<serde_json::Value>(&url).await?;
+            *count = page_info.total_count.unwrap_or(0);
+        }
+
+        // Already percentages
+        let url = self.api_url(&format!("/projects/{}/languages", project));
+        let languages: HashMap<String, f64> = self.get_json(&url).await?;
+        let mut languages: Vec<(String, f64)> = languages.into_iter().collect();
+        languages.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        languages.truncate(INSIGHTS_TOP);
+
+        let url = self.api_url(&format!(
+            "/projects/{}/repository/contributors?order_by=commits&sort=desc&per_page={}",
+            project, INSIGHTS_TOP
+        ));
+        let contributors: Vec<GlContributor> = self.get_json(&url).await?;
+
+        // The commits list leaves out X-Total on large projects; a full page
+        // then reads as 100
+        let since = chrono::Utc::now() - chrono::Duration::days(RECENT_ACTIVITY_DAYS);
+        let url = self.api_url(&format!(
+            "/projects/{}/repository/commits?since={}&per_page=100",
+            project,
+            urlencoding::encode(&since.to_rfc3339())
+        ));
+        let (commits, page_info) = self.get_json_paged::<serde_json::Value>(&url).await?;
+
+        Ok(RepoStats {
+            open_issues: issue_count("opened"),
+            closed_issues: issue_count("closed"),
+            open_prs: mr_counts[0],
+            merged_prs: mr_counts[1],
+            closed_prs: mr_counts[2],
+            contributors: contributors
+                .into_iter()
+                .map(|c| (c.name, c.commits))
+                .collect(),
+            languages,
+            recent_commits: page_info.total_count.unwrap_or(commits.len() as u64),
+        })
+    }
+
+    async fn get_commit_checks(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<CommitCheck>> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!(
+            "/projects/{}/pipelines?sha={}&per_page=1",
+            project, sha
+        ));
+        let pipelines: Vec<GlPipeline> = self.get_json(&url).await?;
+        let Some(latest) = pipelines.first() else {
+            return Ok(Vec::new());
+        };
+
+        let url = self.api_url(&format!(
+            "/projects/{}/pipelines/{}/jobs?per_page=100",
+            project, latest.id
+        ));
+        let jobs: Vec<GlJob> = self.get_json(&url).await?;
+        Ok(jobs
+            .into_iter()
+            .map(|job| CommitCheck {
+                state: match job.status.as_str() {
+                    "success" | "skipped" | "manual" => ChecksStatus::Success,
+                    "failed" | "canceled" => ChecksStatus::Failure,
+                    _ => ChecksStatus::Pending,
+                },
+                name: job.name,
+                duration: job.duration.map(|d| d as u64),
+            })
+            .collect())
+    }
+
     async fn list_action_runs(
         &self,
         owner: &str,
@@ -628,15 +1635,25 @@ impl Forge for GitLab {
     ) -> Result<PagedResult<ActionRun>> {
         let project = Self::project_path(owner, repo);
         let url = self.api_url(&format!(
-            "/projects/{}/pipelines?per_page=50&page={}",
-            project, page
+            "/projects/{}/pipelines?per_page={}&page={}",
+            project, self.page_size, page
         ));
-        let (pipelines, total_count) = self.get_json_paged::<GlPipeline>(&url).await?;
+        let (pipelines, page_info) = self.get_json_paged::<GlPipeline>(&url).await?;
 
         let runs = pipelines
             .into_iter()
             .map(|p| {
                 let (status, conclusion) = gl_pipeline_status(&p.status);
+                let created_at = parse_optional_datetime(p.created_at.as_deref());
+                // A finished pipeline's last update is when it finished
+                let duration = match (status, p.updated_at.as_deref()) {
+                    (ActionStatus::Completed, Some(updated)) => (parse_datetime(updated)
+                        - created_at)
+                        .num_seconds()
+                        .try_into()
+                        .ok(),
+                    _ => None,
+                };
                 ActionRun {
                     id: p.id,
                     name: format!("Pipeline #{}", p.id),
@@ -644,17 +1661,56 @@ impl Forge for GitLab {
                     conclusion,
                     branch: p.ref_field.unwrap_or_else(|| "unknown".to_string()),
                     event: p.source.unwrap_or_else(|| "push".to_string()),
-                    created_at: parse_optional_datetime(p.created_at.as_deref()),
+                    created_at,
+                    duration,
+                    workflow: None,
                 }
             })
             .collect();
 
         Ok(PagedResult {
             items: runs,
-            total_count,
+            page_info,
         })
     }
 
+    // A job's artifacts come as one archive, so each job with any is one artifact
+    async fn list_artifacts(&self, owner: &str, repo: &str, run_id: u64) -> Result<Vec<Artifact>> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!(
+            "/projects/{}/pipelines/{}/jobs?per_page=100",
+            project, run_id
+        ));
+        let jobs: Vec<GlArtifactJob> = self.get_json(&url).await?;
+        Ok(jobs
+            .into_iter()
+            .filter_map(|job| {
+                let file = job.artifacts_file?;
+                Some(Artifact {
+                    id: job.id,
+                    name: job.name,
+                    size: file.size,
+                    expired: job
+                        .artifacts_expire_at
+                        .is_some_and(|at| parse_datetime(&at) < chrono::Utc::now()),
+                    download_url: self
+                        .api_url(&format!("/projects/{}/jobs/{}/artifacts", project, job.id)),
+                })
+            })
+            .collect())
+    }
+
+    async fn download_artifact(&self, artifact: &Artifact) -> Result<reqwest::Response> {
+        let response = self
+            .client
+            .get(&artifact.download_url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .timeout(http::DOWNLOAD_TIMEOUT)
+            .send_retrying()
+            .await?;
+        http::check(response, "Download artifact").await
+    }
+
     async fn get_check_status(
         &self,
         owner: &str,
@@ -683,6 +1739,478 @@ impl Forge for GitLab {
             _ => Ok(ChecksStatus::None),
         }
     }
+
+    async fn submit_review(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        event: &str,
+        body: &str,
+        comments: &[ReviewComment],
+    ) -> Result<()> {
+        // GitLab has no review object: line comments become diff discussions,
+        // the verdict is an approval and the body goes up as a regular note
+        if !comments.is_empty() {
+            let project = Self::project_path(owner, repo);
+            let url = self.api_url(&format!("/projects/{}/merge_requests/{}", project, number));
+            let mr: GlMergeRequest = self.get_json(&url).await?;
+            let refs = mr
+                .diff_refs
+                .ok_or_else(|| GritError::Api("Merge request has no diff to comment on".into()))?;
+            for comment in comments {
+                self.post_diff_comment(&project, number, &refs, comment)
+                    .await?;
+            }
+        }
+        let note = match event {
+            "APPROVE" => {
+                self.set_approval(owner, repo, number, true).await?;
+                body.to_string()
+            }
+            "REQUEST_CHANGES" => {
+                self.set_approval(owner, repo, number, false).await?;
+                format!("**Requested changes**\n\n{}", body)
+            }
+            _ => body.to_string(),
+        };
+        if body.trim().is_empty() && event != "REQUEST_CHANGES" {
+            return Ok(());
+        }
+        self.comment(owner, repo, number, &note).await
+    }
+
+    async fn locate_repo(&self, owner: &str, repo: &str) -> Result<(String, String)> {
+        // The old path keeps resolving after a move, to the project's new one
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}", project));
+        let project: GlProject = self.get_json(&url).await?;
+        split_repo_path(&project.path_with_namespace).ok_or_else(|| {
+            GritError::Api(format!("Bad project path {}", project.path_with_namespace))
+        })
+    }
+
+    async fn get_repo_meta(&self, owner: &str, repo: &str) -> Result<RepoMeta> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}", project));
+        let project: GlProject = self.get_json(&url).await?;
+        Ok(RepoMeta {
+            description: project.description.unwrap_or_default(),
+            topics: project.topics,
+        })
+    }
+
+    async fn update_repo_meta(&self, owner: &str, repo: &str, meta: &RepoMeta) -> Result<()> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}", project));
+        let body = serde_json::json!({
+            "description": meta.description,
+            "topics": meta.topics,
+        });
+        let response = self
+            .client
+            .put(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .json(&body)
+            .send_retrying()
+            .await?;
+        http::check(response, "Update project").await?;
+        Ok(())
+    }
+
+    async fn fork_repo(&self, owner: &str, repo: &str) -> Result<Repository> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}/fork", project));
+        let response = self
+            .client
+            .post(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .send_retrying()
+            .await?;
+        let response = http::check(response, "Fork").await?;
+        let fork: GlProject = response.json().await.map_err(http::api_error)?;
+        Ok(gl_repository(fork))
+    }
+
+    async fn get_fork_status(&self, owner: &str, repo: &str) -> Result<Option<ForkStatus>> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}", project));
+        let fork: GlProject = self.get_json(&url).await?;
+        let Some(upstream) = fork.forked_from_project else {
+            return Ok(None);
+        };
+        let (Some(branch), Some(upstream_branch)) = (fork.default_branch, upstream.default_branch)
+        else {
+            return Ok(None);
+        };
+
+        // `commits` lists what `to` has that `from` (in from_project_id) lacks
+        let count = |project: u64, from_project: u64, from: &str, to: &str| {
+            self.api_url(&format!(
+                "/projects/{}/repository/compare?from={}&to={}&from_project_id={}",
+                project,
+                urlencoding::encode(from),
+                urlencoding::encode(to),
+                from_project
+            ))
+        };
+        let ahead: GlCompare = self
+            .get_json(&count(fork.id, upstream.id, &upstream_branch, &branch))
+            .await?;
+        let behind: GlCompare = self
+            .get_json(&count(upstream.id, fork.id, &branch, &upstream_branch))
+            .await?;
+        Ok(Some(ForkStatus {
+            upstream: upstream.path_with_namespace,
+            branch,
+            upstream_branch,
+            ahead: ahead.commits.len() as u64,
+            behind: behind.commits.len() as u64,
+        }))
+    }
+
+    async fn sync_fork(&self, owner: &str, repo: &str, branch: &str) -> Result<()> {
+        // Only exposed through GraphQL
+        let url = format!("{}/api/graphql", self.base_url);
+        let query = "mutation($path: ID!, $branch: String!) { \
+            projectSyncFork(input: { projectPath: $path, targetBranch: $branch }) { errors } }";
+        let body = serde_json::json!({
+            "query": query,
+            "variables": { "path": format!("{}/{}", owner, repo), "branch": branch },
+        });
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .json(&body)
+            .send_retrying()
+            .await?;
+        let response = http::check(response, "Sync fork").await?;
+        let result: serde_json::Value = response.json().await.map_err(http::api_error)?;
+        let error = result
+            .pointer("/errors/0/message")
+            .or_else(|| result.pointer("/data/projectSyncFork/errors/0"))
+            .and_then(|e| e.as_str());
+        if let Some(message) = error {
+            return Err(GritError::Api(format!("Sync fork failed: {}", message)));
+        }
+        Ok(())
+    }
+
+    async fn is_starred(&self, owner: &str, repo: &str) -> Result<bool> {
+        // No per-project lookup: search the user's starred projects instead
+        let url = self.api_url(&format!(
+            "/projects?starred=true&simple=true&per_page=100&search={}",
+            urlencoding::encode(repo)
+        ));
+        let projects: Vec<serde_json::Value> = self.get_json(&url).await?;
+        let path = format!("{}/{}", owner, repo);
+        Ok(projects
+            .iter()
+            .any(|p| p.get("path_with_namespace").and_then(|v| v.as_str()) == Some(path.as_str())))
+    }
+
+    async fn set_star(&self, owner: &str, repo: &str, starred: bool) -> Result<()> {
+        let project = Self::project_path(owner, repo);
+        let endpoint = if starred { "star" } else { "unstar" };
+        let url = self.api_url(&format!("/projects/{}/{}", project, endpoint));
+        let response = self
+            .client
+            .post(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .send_retrying()
+            .await?;
+        // 304 when the project already was (un)starred
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(());
+        }
+        http::check(response, "Star").await?;
+        Ok(())
+    }
+
+    async fn set_subscription(
+        &self,
+        owner: &str,
+        repo: &str,
+        subscription: Subscription,
+    ) -> Result<()> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}/notification_settings", project));
+        let level = match subscription {
+            Subscription::Watching => "watch",
+            Subscription::Participating => "participating",
+            Subscription::Ignoring => "disabled",
+        };
+        let response = self
+            .client
+            .put(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .json(&serde_json::json!({ "level": level }))
+            .send_retrying()
+            .await?;
+        http::check(response, "Notification settings").await?;
+        Ok(())
+    }
+
+    async fn reviewer_load(&self, owner: &str, repo: &str) -> Result<Vec<ReviewerLoad>> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}", project));
+        let info: serde_json::Value = self.get_json(&url).await?;
+
+        // Count across the top-level group; personal projects only have themselves
+        let scope = match info.pointer("/namespace/kind").and_then(|k| k.as_str()) {
+            Some("group") => {
+                let group = owner.split('/').next().unwrap_or(owner);
+                format!("/groups/{}", urlencoding::encode(group))
+            }
+            _ => format!("/projects/{}", project),
+        };
+
+        // Developers and up can review
+        let url = self.api_url(&format!(
+            "/projects/{}/members/all?per_page=100&state=active",
+            project
+        ));
+        let members: Vec<serde_json::Value> = self.get_json(&url).await?;
+        let logins: Vec<String> = members
+            .iter()
+            .filter(|m| m.get("access_level").and_then(|l| l.as_u64()) >= Some(30))
+            .filter_map(|m| m.get("username")?.as_str().map(String::from))
+            .collect();
+
+        let counts = futures::future::join_all(logins.iter().map(|login| {
+            let url = self.api_url(&format!(
+                "{}/merge_requests?state=opened&reviewer_username={}&per_page=1",
+                scope,
+                urlencoding::encode(login)
+            ));
+            async move {
+                self.get_json_paged::<serde_json::Value>(&url)
+                    .await
+                    .map(|(items, page_info)| page_info.total_count.unwrap_or(items.len() as u64))
+            }
+        }))
+        .await;
+
+        logins
+            .into_iter()
+            .zip(counts)
+            .map(|(login, count)| {
+                Ok(ReviewerLoad {
+                    login,
+                    open_reviews: count?,
+                })
+            })
+            .collect()
+    }
+
+    /// Award emoji on the MR or on one of its notes
+    async fn add_reaction(
+        &self,
+        owner: &str,
+        repo: &str,
+        target: ReactionTarget,
+        reaction: Reaction,
+    ) -> Result<()> {
+        let project = Self::project_path(owner, repo);
+        let path = match target {
+            ReactionTarget::Pr(number) => format!("/merge_requests/{}", number),
+            ReactionTarget::Comment { pr, id } | ReactionTarget::ReviewComment { pr, id } => {
+                format!("/merge_requests/{}/notes/{}", pr, id)
+            }
+        };
+        let url = self.api_url(&format!("/projects/{}{}/award_emoji", project, path));
+        let payload = serde_json::json!({ "name": reaction.gitlab_name() });
+        let _: serde_json::Value = self.post_json(&url, &payload, "Reaction").await?;
+        Ok(())
+    }
+
+    async fn comment_on_commit(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+        body: &str,
+    ) -> Result<()> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!(
+            "/projects/{}/repository/commits/{}/comments",
+            project, sha
+        ));
+        let payload = serde_json::json!({ "note": body });
+        let _: serde_json::Value = self.post_json(&url, &payload, "Commit comment").await?;
+        Ok(())
+    }
+
+    async fn request_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        reviewers: &[String],
+    ) -> Result<()> {
+        let project = Self::project_path(owner, repo);
+        let url = self.api_url(&format!("/projects/{}/merge_requests/{}", project, number));
+        let mr: GlMergeRequest = self.get_json(&url).await?;
+
+        // The MR update takes the full id list, so keep the current reviewers
+        let mut ids: Vec<u64> = mr.reviewers.iter().map(|r| r.id).collect();
+        for login in reviewers {
+            let lookup = self.api_url(&format!("/users?username={}", urlencoding::encode(login)));
+            let users: Vec<GlMrAuthor> = self.get_json(&lookup).await?;
+            match users.first() {
+                Some(user) if !ids.contains(&user.id) => ids.push(user.id),
+                Some(_) => {}
+                None => return Err(GritError::Api(format!("No GitLab user {}", login))),
+            }
+        }
+
+        let body = serde_json::json!({ "reviewer_ids": ids });
+        let response = self
+            .client
+            .put(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .json(&body)
+            .send_retrying()
+            .await?;
+        http::check(response, "Request reviewers").await?;
+        Ok(())
+    }
+
+    async fn revert_pr(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr: &PullRequest,
+        commits: &[Commit],
+    ) -> Result<u64> {
+        let project = Self::project_path(owner, repo);
+        let branch = format!("revert-{}", pr.number);
+
+        let url = self.api_url(&format!("/projects/{}/repository/branches", project));
+        let body = serde_json::json!({ "branch": branch, "ref": pr.base_branch });
+        let _: serde_json::Value = self.post_json(&url, &body, "Branch").await?;
+
+        // Newest first, as `git revert` walks a range
+        for commit in commits.iter().rev() {
+            let url = self.api_url(&format!(
+                "/projects/{}/repository/commits/{}/revert",
+                project, commit.sha
+            ));
+            let body = serde_json::json!({ "branch": branch });
+            let _: serde_json::Value = self.post_json(&url, &body, "Revert").await?;
+        }
+
+        let url = self.api_url(&format!("/projects/{}/merge_requests", project));
+        let body = serde_json::json!({
+            "source_branch": branch,
+            "target_branch": pr.base_branch,
+            "title": format!("Revert \"{}\"", pr.title),
+            "description": format!("Reverts !{}", pr.number),
+        });
+        let mr: GlMergeRequest = self.post_json(&url, &body, "Merge request").await?;
+        Ok(mr.iid)
+    }
+}
+
+fn gl_repository(p: GlProject) -> Repository {
+    // The namespace may be several groups deep; only the last segment is the project
+    let (owner, name) =
+        split_repo_path(&p.path_with_namespace).unwrap_or_else(|| ("unknown".to_string(), p.name));
+
+    Repository {
+        owner,
+        name,
+        description: p.description.filter(|d| !d.is_empty()),
+        url: p.web_url,
+        stars: p.star_count.unwrap_or(0),
+        updated_at: parse_optional_datetime(p.last_activity_at.as_deref()),
+    }
+}
+
+fn gl_issue(i: GlIssue) -> Issue {
+    Issue {
+        number: i.iid,
+        title: i.title,
+        state: if i.state == "closed" {
+            IssueState::Closed
+        } else {
+            IssueState::Open
+        },
+        author: i.author.username,
+        labels: i.labels.into_iter().map(gl_label).collect(),
+        comments: i.user_notes_count.unwrap_or(0),
+        created_at: parse_optional_datetime(i.created_at.as_deref()),
+        updated_at: parse_optional_datetime(i.updated_at.as_deref()),
+        milestone: i.milestone.map(|m| m.title),
+        assignees: i.assignees.into_iter().map(|a| a.username).collect(),
+    }
+}
+
+fn gl_label(l: GlLabelRef) -> Label {
+    match l {
+        GlLabelRef::Name(name) => Label { name, color: None },
+        GlLabelRef::Detailed(l) => Label {
+            name: l.name,
+            color: l.color.map(|c| c.trim_start_matches('#').to_string()),
+        },
+    }
+}
+
+fn gl_commit(c: GlCommit) -> Commit {
+    let message = c
+        .title
+        .or(c
+            .message
+            .as_ref()
+            .map(|m| m.lines().next().unwrap_or("").to_string()))
+        .unwrap_or_default();
+
+    Commit {
+        sha: c.id,
+        message,
+        author: c.author_name.unwrap_or_else(|| "unknown".to_string()),
+        date: parse_optional_datetime(c.created_at.as_deref()),
+    }
+}
+
+/// Map a GitLab diff entry (commit or merge request) to a file with counted changes
+fn gl_commit_file(d: GlDiff) -> CommitFile {
+    let status = if d.new_file {
+        "added"
+    } else if d.deleted_file {
+        "removed"
+    } else if d.renamed_file {
+        "renamed"
+    } else {
+        "modified"
+    };
+
+    // Count additions/deletions from diff text
+    let (additions, deletions) = d
+        .diff
+        .as_deref()
+        .map(|text| {
+            let mut adds: u64 = 0;
+            let mut dels: u64 = 0;
+            for line in text.lines() {
+                if line.starts_with('+') && !line.starts_with("+++") {
+                    adds += 1;
+                } else if line.starts_with('-') && !line.starts_with("---") {
+                    dels += 1;
+                }
+            }
+            (adds, dels)
+        })
+        .unwrap_or((0, 0));
+
+    CommitFile {
+        filename: d.new_path,
+        status: status.to_string(),
+        additions,
+        deletions,
+        patch: d.diff,
+    }
 }
 
 fn gl_mr_state(state: &str) -> PrState {
@@ -693,6 +2221,25 @@ fn gl_mr_state(state: &str) -> PrState {
     }
 }
 
+/// Map GitLab's `detailed_merge_status` (15.6+) onto our states,
+/// falling back to `has_conflicts` on older instances
+fn gl_mergeable_state(detailed: Option<&str>, has_conflicts: Option<bool>) -> MergeableState {
+    match detailed {
+        Some("mergeable") => MergeableState::Clean,
+        Some("conflict") | Some("broken_status") => MergeableState::Conflicts,
+        Some("need_rebase") => MergeableState::Behind,
+        Some("ci_must_pass") | Some("ci_still_running") => MergeableState::BlockedByChecks,
+        Some("not_approved") | Some("requested_changes") => MergeableState::BlockedByReviews,
+        Some("draft_status") => MergeableState::Draft,
+        Some("checking") | Some("unchecked") | Some("preparing") => MergeableState::Unknown,
+        Some(_) => MergeableState::Blocked,
+        None => match has_conflicts {
+            Some(true) => MergeableState::Conflicts,
+            _ => MergeableState::Unknown,
+        },
+    }
+}
+
 fn gl_pipeline_status(status: &str) -> (ActionStatus, Option<ActionConclusion>) {
     match status {
         "created" | "waiting_for_resource" | "preparing" | "pending" => {
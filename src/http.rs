@@ -0,0 +1,435 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use http_body_util::BodyExt;
+use reqwest::header::HeaderMap;
+use reqwest::{Certificate, Client, Proxy, RequestBuilder, Response};
+
+use crate::config::Transport;
+use crate::error::{GritError, Result};
+
+/// Request timeout for forges that don't set `timeout`
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Downloads stream for far longer than an API call may take
+pub const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+/// Least time between two progress reports of a download
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+/// Longest wait for a connection, within the request timeout
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Attempts after the first
+const RETRIES: u32 = 2;
+/// Pause before the first retry; doubles after each one
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(300);
+
+/// The pooled client a forge sends all of its REST calls through, via the
+/// forge's proxy and trusting its extra certificates
+pub fn client(timeout: Duration, transport: &Transport) -> Result<Client> {
+    let mut builder = Client::builder()
+        .user_agent("grit")
+        .connect_timeout(CONNECT_TIMEOUT.min(timeout))
+        .timeout(timeout)
+        .danger_accept_invalid_certs(transport.insecure_skip_verify);
+    if let Some(proxy) = &transport.proxy {
+        let proxy = Proxy::all(proxy)
+            .map_err(|e| GritError::Network(format!("Bad proxy {}: {}", proxy, e)))?;
+        builder = builder.proxy(proxy);
+    }
+    if let Some(path) = &transport.ca_bundle {
+        let pem = std::fs::read(path)
+            .map_err(|e| GritError::Io(format!("ca_bundle {}: {}", path.display(), e)))?;
+        let certificates = Certificate::from_pem_bundle(&pem)
+            .map_err(|e| GritError::Io(format!("ca_bundle {}: {}", path.display(), e)))?;
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    builder.build().map_err(api_error)
+}
+
+/// Runs octocrab's requests on a reqwest client, so GitHub honors the same
+/// proxy and certificate settings as the other forges
+#[derive(Clone)]
+pub struct ReqwestService(pub Client);
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+impl<B> tower_service::Service<http::Request<B>> for ReqwestService
+where
+    B: http_body::Body + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Response = http::Response<reqwest::Body>;
+    type Error = BoxError;
+    type Future =
+        Pin<Box<dyn Future<Output = std::result::Result<Self::Response, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<std::result::Result<(), BoxError>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let client = self.0.clone();
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let body = body.collect().await.map_err(Into::into)?.to_bytes();
+            let request = reqwest::Request::try_from(http::Request::from_parts(parts, body))?;
+            Ok(client.execute(request).await?.into())
+        })
+    }
+}
+
+/// Transport failures, worded the same for every forge. Only a failed
+/// connection is a network error: the request provably never left, so it's
+/// safe to queue and send again. A timeout may have landed.
+pub fn api_error(err: reqwest::Error) -> GritError {
+    if err.is_connect() {
+        GritError::Network(err.to_string())
+    } else if err.is_timeout() {
+        GritError::Timeout(err.to_string())
+    } else {
+        GritError::Api(err.to_string())
+    }
+}
+
+#[async_trait]
+pub trait RetrySend {
+    /// Send, retrying with exponential backoff when the forge can't be
+    /// reached or answers 5xx. Only idempotent methods retry once the request
+    /// may have arrived, so a POST never lands twice.
+    async fn send_retrying(self) -> Result<Response>;
+}
+
+#[async_trait]
+impl RetrySend for RequestBuilder {
+    async fn send_retrying(self) -> Result<Response> {
+        let (client, request) = self.build_split();
+        let request = request.map_err(api_error)?;
+        let idempotent = request.method().is_idempotent();
+        let mut delay = RETRY_BASE_DELAY;
+        for _ in 0..RETRIES {
+            // Streaming bodies can't be replayed; those get the one attempt below
+            let Some(attempt) = request.try_clone() else {
+                break;
+            };
+            tracing::debug!("{} {}", request.method(), request.url());
+            match client.execute(attempt).await {
+                Ok(response) if idempotent && response.status().is_server_error() => {}
+                Err(e) if should_retry(&e, idempotent) => {}
+                result => return result.map_err(api_error),
+            }
+            tracing::debug!(
+                "retrying {} {} in {:?}",
+                request.method(),
+                request.url(),
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+        client.execute(request).await.map_err(api_error)
+    }
+}
+
+/// A refused connection never reached the forge; a timeout may have
+fn should_retry(err: &reqwest::Error, idempotent: bool) -> bool {
+    err.is_connect() || (idempotent && err.is_timeout())
+}
+
+/// Pass a 2xx response through; anything else becomes the matching
+/// `GritError`, worded "{what} failed ({status}): {body}"
+pub async fn check(response: Response, what: &str) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let reset = rate_limit_reset(response.headers());
+    let text = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "unknown error".to_string());
+    tracing::warn!("{} failed ({}): {}", what, status, text);
+    Err(GritError::from_status(
+        status.as_u16(),
+        format!("{} failed ({}): {}", what, status, text),
+        reset,
+    ))
+}
+
+/// Stream a response body into `path`, reporting bytes written so far and the
+/// expected total, if the forge said
+pub async fn download(
+    mut response: Response,
+    path: &std::path::Path,
+    mut progress: impl FnMut(u64, Option<u64>) + Send,
+) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let total = response.content_length();
+    let mut file = tokio::fs::File::create(path).await?;
+    let mut written = 0;
+    let mut reported = std::time::Instant::now();
+    while let Some(chunk) = response.chunk().await.map_err(api_error)? {
+        file.write_all(&chunk).await?;
+        written += chunk.len() as u64;
+        if reported.elapsed() >= PROGRESS_INTERVAL {
+            progress(written, total);
+            reported = std::time::Instant::now();
+        }
+    }
+    file.flush().await?;
+    progress(written, total);
+    Ok(())
+}
+
+/// A body read by `spool`
+#[derive(Debug, Clone)]
+pub enum Spooled {
+    /// Small enough to keep in memory
+    Text(String),
+    /// Outgrew the threshold and was moved to this temp file, which is
+    /// deleted once the last handle on it goes
+    File(std::sync::Arc<tempfile::TempPath>),
+}
+
+/// Read a response body like `download`, but keep it in memory unless it
+/// outgrows `threshold` bytes; from there on it goes to a fresh temp file
+/// only the user can read, so a huge body never sits in memory whole
+pub async fn spool(
+    mut response: Response,
+    threshold: usize,
+    mut progress: impl FnMut(u64, Option<u64>) + Send,
+) -> Result<Spooled> {
+    use tokio::io::AsyncWriteExt;
+
+    let total = response.content_length();
+    let mut buffer = Vec::new();
+    let mut file: Option<(tokio::fs::File, tempfile::TempPath)> = None;
+    let mut received = 0;
+    let mut reported = std::time::Instant::now();
+    while let Some(chunk) = response.chunk().await.map_err(api_error)? {
+        received += chunk.len() as u64;
+        match &mut file {
+            Some((file, _)) => file.write_all(&chunk).await?,
+            None => {
+                buffer.extend_from_slice(&chunk);
+                if buffer.len() > threshold {
+                    let (spill, path) = tempfile::Builder::new()
+                        .prefix("grit-")
+                        .tempfile()?
+                        .into_parts();
+                    let mut spill = tokio::fs::File::from_std(spill);
+                    spill.write_all(&std::mem::take(&mut buffer)).await?;
+                    file = Some((spill, path));
+                }
+            }
+        }
+        if reported.elapsed() >= PROGRESS_INTERVAL {
+            progress(received, total);
+            reported = std::time::Instant::now();
+        }
+    }
+    progress(received, total);
+    match file {
+        Some((mut file, path)) => {
+            file.flush().await?;
+            Ok(Spooled::File(std::sync::Arc::new(path)))
+        }
+        None => Ok(Spooled::Text(String::from_utf8_lossy(&buffer).into_owned())),
+    }
+}
+
+/// When an exhausted rate limit refills: `Retry-After` seconds, or the reset
+/// epoch GitHub and Gitea send as `X-RateLimit-Reset` and GitLab as
+/// `RateLimit-Reset`. None while requests remain.
+pub fn rate_limit_reset(headers: &HeaderMap) -> Option<DateTime<Utc>> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    if let Some(secs) = header("retry-after").and_then(|s| s.parse::<i64>().ok()) {
+        return Some(Utc::now() + chrono::Duration::seconds(secs));
+    }
+    let remaining = header("x-ratelimit-remaining").or_else(|| header("ratelimit-remaining"));
+    if remaining != Some("0") {
+        return None;
+    }
+    header("x-ratelimit-reset")
+        .or_else(|| header("ratelimit-reset"))
+        .and_then(|s| s.parse::<i64>().ok())
+        .and_then(|epoch| DateTime::from_timestamp(epoch, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Local server answering everything with `status`, and its request count
+    async fn server(status: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&hits);
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let response = format!(
+                    "HTTP/1.1 {}\r\ncontent-length: 4\r\nconnection: close\r\n\r\noops",
+                    status
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        (url, hits)
+    }
+
+    #[tokio::test]
+    async fn gets_retry_server_errors() {
+        let (url, hits) = server("503 Service Unavailable").await;
+        let response = client(DEFAULT_TIMEOUT, &Transport::default())
+            .unwrap()
+            .get(&url)
+            .send_retrying()
+            .await
+            .unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 1 + RETRIES as usize);
+
+        let err = check(response, "Fetch").await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "API error: Fetch failed (503 Service Unavailable): oops"
+        );
+    }
+
+    #[test]
+    fn rate_limit_reset_needs_an_exhausted_limit() {
+        use reqwest::header::HeaderValue;
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("12"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1700000000"));
+        assert_eq!(rate_limit_reset(&headers), None);
+
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        assert_eq!(
+            rate_limit_reset(&headers),
+            DateTime::from_timestamp(1_700_000_000, 0)
+        );
+    }
+
+    #[tokio::test]
+    async fn only_refused_connections_are_network_errors() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        // Accepts and reads, never answers
+        tokio::spawn(async move {
+            let mut open = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                open.push(socket);
+            }
+        });
+        let client = client(Duration::from_millis(200), &Transport::default()).unwrap();
+        let err = client.post(&url).send_retrying().await.unwrap_err();
+        assert!(matches!(err, GritError::Timeout(_)), "{:?}", err);
+
+        let closed = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", closed.local_addr().unwrap());
+        drop(closed);
+        let err = client.post(&url).send_retrying().await.unwrap_err();
+        assert!(matches!(err, GritError::Network(_)), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn posts_are_sent_once() {
+        let (url, hits) = server("500 Internal Server Error").await;
+        client(DEFAULT_TIMEOUT, &Transport::default())
+            .unwrap()
+            .post(&url)
+            .body("{}")
+            .send_retrying()
+            .await
+            .unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn octocrab_requests_go_through_the_proxy() {
+        let (proxy, hits) = server("200 OK").await;
+        let transport = Transport {
+            proxy: Some(proxy),
+            ..Transport::default()
+        };
+        let mut service = ReqwestService(client(DEFAULT_TIMEOUT, &transport).unwrap());
+        let request = http::Request::get("http://forge.invalid/user")
+            .body(String::new())
+            .unwrap();
+        let response = tower_service::Service::call(&mut service, request)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn unreadable_ca_bundle_names_the_file() {
+        let transport = Transport {
+            ca_bundle: Some("/nonexistent/corp-ca.pem".into()),
+            ..Transport::default()
+        };
+        let err = client(DEFAULT_TIMEOUT, &transport).unwrap_err();
+        assert!(err.to_string().contains("/nonexistent/corp-ca.pem"));
+    }
+
+    #[tokio::test]
+    async fn downloads_stream_to_the_file() {
+        let (url, _) = server("200 OK").await;
+        let response = client(DEFAULT_TIMEOUT, &Transport::default())
+            .unwrap()
+            .get(&url)
+            .send()
+            .await
+            .unwrap();
+        let path = std::env::temp_dir().join(format!("grit-download-{}", std::process::id()));
+        let mut reports = Vec::new();
+        download(response, &path, |written, total| {
+            reports.push((written, total))
+        })
+        .await
+        .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "oops");
+        assert_eq!(reports.last(), Some(&(4, Some(4))));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn spool_moves_to_the_file_past_the_threshold() {
+        let (url, _) = server("200 OK").await;
+        let client = client(DEFAULT_TIMEOUT, &Transport::default()).unwrap();
+
+        let response = client.get(&url).send().await.unwrap();
+        let small = spool(response, 4, |_, _| {}).await.unwrap();
+        assert!(matches!(small, Spooled::Text(text) if text == "oops"));
+
+        let response = client.get(&url).send().await.unwrap();
+        let Spooled::File(file) = spool(response, 3, |_, _| {}).await.unwrap() else {
+            panic!("expected a spooled file");
+        };
+        let path = file.to_path_buf();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "oops");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        // Gone with the last handle
+        drop(file);
+        assert!(!path.exists());
+    }
+}
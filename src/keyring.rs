@@ -0,0 +1,207 @@
+//! Tokens in the OS secret store, through the platform's own tools:
+//! `security` (Keychain) on macOS and `secret-tool` (Secret Service) on
+//! other Unixes. Tokens always travel over stdin, never argv. Windows has
+//! no store yet: every call there fails with an error saying so, rather than
+//! finding nothing.
+
+#[cfg(unix)]
+use std::io::Write;
+#[cfg(unix)]
+use std::process::{Command, Stdio};
+
+/// Service name the tokens are filed under; the account is the forge name
+#[cfg(any(unix, test))]
+const SERVICE: &str = "grit";
+
+/// Human-readable name of the store, for status output
+pub const STORE_NAME: &str = if cfg!(target_os = "macos") {
+    "macOS Keychain"
+} else {
+    "Secret Service"
+};
+
+/// The `security -i` command that files `token` for `account`. Its command
+/// line has no escapes, so quotes, backslashes and line breaks are refused.
+#[cfg(any(target_os = "macos", test))]
+fn add_password_command(account: &str, token: &str) -> Result<String, String> {
+    for (what, value) in [("Forge name", account), ("Token", token)] {
+        if value.contains(['"', '\\', '\n', '\r']) {
+            return Err(format!(
+                "{} can't be kept in the Keychain: it contains a quote, backslash or line break",
+                what
+            ));
+        }
+    }
+    Ok(format!(
+        "add-generic-password -U -s {} -a \"{}\" -w \"{}\"\n",
+        SERVICE, account, token
+    ))
+}
+
+/// Secret Service attributes a forge's token is filed under
+#[cfg(any(all(unix, not(target_os = "macos")), test))]
+fn attributes(account: &str) -> [&str; 4] {
+    ["service", SERVICE, "account", account]
+}
+
+/// Run `program` feeding `input` on stdin; stdout is returned on success
+#[cfg(unix)]
+fn run_with_stdin(program: &str, args: &[&str], input: &str) -> Result<String, String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Could not run {}: {}", program, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|e| format!("Could not write to {}: {}", program, e))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("{} failed: {}", program, e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(unix)]
+fn non_empty(token: String) -> Option<String> {
+    let token = token.trim().to_string();
+    (!token.is_empty()).then_some(token)
+}
+
+/// Token stored for `account`, if any
+#[cfg(target_os = "macos")]
+pub fn load(account: &str) -> Result<Option<String>, String> {
+    let Ok(output) = Command::new("security")
+        .args(["find-generic-password", "-s", SERVICE, "-a", account, "-w"])
+        .stderr(Stdio::null())
+        .output()
+    else {
+        return Ok(None);
+    };
+    Ok(output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+        .and_then(non_empty))
+}
+
+/// Store or replace the token for `account`
+#[cfg(target_os = "macos")]
+pub fn store(account: &str, token: &str) -> Result<(), String> {
+    // `security -i` reads the command from stdin, keeping the token out of ps
+    let command = add_password_command(account, token)?;
+    run_with_stdin("security", &["-i"], &command).map(|_| ())
+}
+
+/// Remove the token for `account`; returns whether there was one
+#[cfg(target_os = "macos")]
+pub fn delete(account: &str) -> Result<bool, String> {
+    if load(account)?.is_none() {
+        return Ok(false);
+    }
+    run_with_stdin(
+        "security",
+        &["delete-generic-password", "-s", SERVICE, "-a", account],
+        "",
+    )
+    .map(|_| true)
+}
+
+/// Token stored for `account`, if any
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn load(account: &str) -> Result<Option<String>, String> {
+    let [service, service_name, key, name] = attributes(account);
+    // secret-tool exits non-zero both when nothing is stored and when it's
+    // missing, so either way there's no token
+    Ok(run_with_stdin(
+        "secret-tool",
+        &["lookup", service, service_name, key, name],
+        "",
+    )
+    .ok()
+    .and_then(non_empty))
+}
+
+/// Store or replace the token for `account`
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn store(account: &str, token: &str) -> Result<(), String> {
+    let label = format!("grit token for {}", account);
+    let [service, service_name, key, name] = attributes(account);
+    run_with_stdin(
+        "secret-tool",
+        &["store", "--label", &label, service, service_name, key, name],
+        token,
+    )
+    .map(|_| ())
+}
+
+/// Remove the token for `account`; returns whether there was one
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn delete(account: &str) -> Result<bool, String> {
+    if load(account)?.is_none() {
+        return Ok(false);
+    }
+    let [service, service_name, key, name] = attributes(account);
+    run_with_stdin(
+        "secret-tool",
+        &["clear", service, service_name, key, name],
+        "",
+    )
+    .map(|_| true)
+}
+
+#[cfg(windows)]
+const UNSUPPORTED: &str = "token_source = \"keyring\" is not supported on Windows yet; \
+     use a token file or token_command instead";
+
+#[cfg(windows)]
+pub fn load(_account: &str) -> Result<Option<String>, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+#[cfg(windows)]
+pub fn store(_account: &str, _token: &str) -> Result<(), String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+#[cfg(windows)]
+pub fn delete(_account: &str) -> Result<bool, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_filed_under_the_forge_name() {
+        assert_eq!(
+            attributes("work-gitlab"),
+            ["service", "grit", "account", "work-gitlab"]
+        );
+        assert_eq!(
+            add_password_command("github", "ghp_abc").unwrap(),
+            "add-generic-password -U -s grit -a \"github\" -w \"ghp_abc\"\n"
+        );
+    }
+
+    #[test]
+    fn keychain_command_refuses_what_it_cannot_quote() {
+        assert!(add_password_command("git\"hub", "ghp_abc").is_err());
+        assert!(add_password_command("github", "ghp\\abc").is_err());
+        assert!(add_password_command("github", "ghp_abc\ndelete-keychain").is_err());
+        assert!(add_password_command("github", "ghp_abc\r").is_err());
+    }
+}
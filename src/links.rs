@@ -0,0 +1,242 @@
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::config::LinkPattern;
+
+/// A `[[ui.links]]` entry with its pattern compiled
+#[derive(Debug, Clone)]
+pub struct LinkRule {
+    regex: Regex,
+    url: String,
+}
+
+/// A tracker reference found in some text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackerLink {
+    pub token: String,
+    pub url: String,
+}
+
+/// A URL written in a description or comment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BodyLink {
+    pub url: String,
+    /// Embedded as an image (`![alt](url)` or `<img src>`) rather than linked
+    pub image: bool,
+}
+
+impl BodyLink {
+    /// `[n] url` as a Links footer lists it, `index` counting from 0
+    pub fn label(&self, index: usize) -> String {
+        let kind = if self.image { "image " } else { "" };
+        format!("[{}] {}{}", index + 1, kind, self.url)
+    }
+}
+
+/// A `#123` or `@user` written in a description or comment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrossRef {
+    /// A PR or issue of the same repo
+    Number(u64),
+    User(String),
+}
+
+/// Compile the configured patterns; invalid ones are logged and skipped
+pub fn compile(patterns: &[LinkPattern]) -> Vec<LinkRule> {
+    patterns
+        .iter()
+        .filter_map(|p| match Regex::new(&p.pattern) {
+            Ok(regex) => Some(LinkRule {
+                regex,
+                url: p.url.clone(),
+            }),
+            Err(e) => {
+                tracing::warn!("ignoring link pattern {:?}: {}", p.pattern, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Byte ranges of tracker references in `text`, in order and not overlapping
+pub fn spans(rules: &[LinkRule], text: &str) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = rules
+        .iter()
+        .flat_map(|rule| rule.regex.find_iter(text).map(|m| m.range()))
+        .filter(|r| !r.is_empty())
+        .collect();
+    ranges.sort_by_key(|r| (r.start, std::cmp::Reverse(r.end)));
+    let mut kept: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        if kept.last().is_none_or(|last| range.start >= last.end) {
+            kept.push(range);
+        }
+    }
+    kept
+}
+
+/// Distinct tracker references across `texts`, first occurrence first
+pub fn find<'a>(rules: &[LinkRule], texts: impl IntoIterator<Item = &'a str>) -> Vec<TrackerLink> {
+    let mut links: Vec<TrackerLink> = Vec::new();
+    for text in texts {
+        for rule in rules {
+            for caps in rule.regex.captures_iter(text) {
+                let mut url = String::new();
+                caps.expand(&rule.url, &mut url);
+                let link = TrackerLink {
+                    token: caps[0].to_string(),
+                    url,
+                };
+                if !links.contains(&link) {
+                    links.push(link);
+                }
+            }
+        }
+    }
+    links
+}
+
+/// Distinct http(s) URLs across `texts`, first occurrence first. Markdown
+/// links and images, `<url>` autolinks and bare URLs are all picked up.
+pub fn urls<'a>(texts: impl IntoIterator<Item = &'a str>) -> Vec<BodyLink> {
+    static URL: OnceLock<Regex> = OnceLock::new();
+    static IMAGE: OnceLock<Regex> = OnceLock::new();
+    let url = URL.get_or_init(|| Regex::new(r#"https?://[^\s<>()\[\]"'`]+"#).unwrap());
+    let image = IMAGE.get_or_init(|| {
+        Regex::new(r#"!\[[^\]]*\]\(\s*(https?://[^\s)]+)|<img\b[^>]*\bsrc="(https?://[^"]+)""#)
+            .unwrap()
+    });
+
+    let mut links: Vec<BodyLink> = Vec::new();
+    for text in texts {
+        let images: Vec<&str> = image
+            .captures_iter(text)
+            .filter_map(|caps| caps.get(1).or(caps.get(2)).map(|m| m.as_str()))
+            .collect();
+        for m in url.find_iter(text) {
+            // Sentence punctuation right after a bare URL isn't part of it
+            let found = m.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?']);
+            match links.iter_mut().find(|l| l.url == found) {
+                Some(link) => link.image |= images.contains(&found),
+                None => links.push(BodyLink {
+                    url: found.to_string(),
+                    image: images.contains(&found),
+                }),
+            }
+        }
+    }
+    links
+}
+
+/// `#123` and `@user` references in `text` with their byte ranges, in order.
+/// Fragments of URLs, HTML entities and email addresses don't count.
+pub fn cross_refs(text: &str) -> Vec<(Range<usize>, CrossRef)> {
+    static REF: OnceLock<Regex> = OnceLock::new();
+    let reference = REF.get_or_init(|| {
+        Regex::new(r"(?:^|[^\w/&@.])(?:#(\d+)\b|@([A-Za-z0-9][\w-]*(?:\.[\w-]+)*))").unwrap()
+    });
+    reference
+        .captures_iter(text)
+        .filter_map(|caps| match (caps.get(1), caps.get(2)) {
+            (Some(number), _) => Some((
+                number.start() - 1..number.end(),
+                CrossRef::Number(number.as_str().parse().ok()?),
+            )),
+            (_, Some(user)) => Some((
+                user.start() - 1..user.end(),
+                CrossRef::User(user.as_str().to_string()),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> Vec<LinkRule> {
+        compile(&[
+            LinkPattern {
+                pattern: r"JIRA-\d+".to_string(),
+                url: "https://jira.example.com/browse/$0".to_string(),
+            },
+            LinkPattern {
+                pattern: r"ZD#(\d+)".to_string(),
+                url: "https://help.example.com/tickets/$1".to_string(),
+            },
+        ])
+    }
+
+    #[test]
+    fn invalid_patterns_are_skipped() {
+        let rules = compile(&[LinkPattern {
+            pattern: "(".to_string(),
+            url: "x".to_string(),
+        }]);
+        assert!(rules.is_empty());
+    }
+
+    #[test]
+    fn find_expands_groups_and_dedups() {
+        let links = find(&rules(), ["Fix JIRA-12 (ZD#7)", "see JIRA-12"]);
+        assert_eq!(
+            links,
+            vec![
+                TrackerLink {
+                    token: "JIRA-12".to_string(),
+                    url: "https://jira.example.com/browse/JIRA-12".to_string(),
+                },
+                TrackerLink {
+                    token: "ZD#7".to_string(),
+                    url: "https://help.example.com/tickets/7".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn spans_are_ordered_and_disjoint() {
+        let text = "ZD#3 then JIRA-4";
+        let ranges = spans(&rules(), text);
+        let tokens: Vec<&str> = ranges.iter().map(|r| &text[r.clone()]).collect();
+        assert_eq!(tokens, vec!["ZD#3", "JIRA-4"]);
+    }
+
+    #[test]
+    fn cross_refs_skip_urls_entities_and_emails() {
+        let text = "Fixes #12, thanks @jane-doe. See https://x.io/a#3, &#39; and bob@example.com";
+        let refs = cross_refs(text);
+        let tokens: Vec<&str> = refs.iter().map(|(r, _)| &text[r.clone()]).collect();
+        assert_eq!(tokens, vec!["#12", "@jane-doe"]);
+        assert_eq!(refs[0].1, CrossRef::Number(12));
+        assert_eq!(refs[1].1, CrossRef::User("jane-doe".to_string()));
+    }
+
+    #[test]
+    fn urls_find_links_and_images_once() {
+        let links = urls([
+            "See https://example.com/docs. Screenshot: ![shot](https://img.example.com/a.png)",
+            r#"<img width="200" src="https://img.example.com/b"> and [docs](https://example.com/docs)"#,
+        ]);
+        assert_eq!(
+            links,
+            vec![
+                BodyLink {
+                    url: "https://example.com/docs".to_string(),
+                    image: false,
+                },
+                BodyLink {
+                    url: "https://img.example.com/a.png".to_string(),
+                    image: true,
+                },
+                BodyLink {
+                    url: "https://img.example.com/b".to_string(),
+                    image: true,
+                },
+            ]
+        );
+    }
+}
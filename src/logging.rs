@@ -0,0 +1,149 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+use crate::config::LogConfig;
+
+/// Size at which the log moves to `grit.log.1`
+const MAX_BYTES: u64 = 1024 * 1024;
+/// Rotated files kept next to the live one
+const KEEP: usize = 3;
+
+/// The live log file, under the platform's data directory
+pub fn log_path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("grit").join("grit.log"))
+}
+
+/// Send tracing output to the log file. `RUST_LOG` overrides the configured
+/// levels. Never writes to the terminal, which the TUI owns; if the file
+/// can't be opened, logs are dropped.
+pub fn init(config: &LogConfig) {
+    let filter = EnvFilter::try_from_default_env()
+        .or_else(|_| EnvFilter::try_new(config.filter()))
+        .unwrap_or_else(|_| EnvFilter::new("warn"));
+    let file = log_path().and_then(|path| RotatingFile::open(path, MAX_BYTES, KEEP).ok());
+    let layer = file.map(|file| {
+        tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .with_writer(Mutex::new(file))
+    });
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(layer)
+        .init();
+}
+
+/// The last `count` lines of the log at `path`; empty if there's none yet
+pub fn tail(path: &Path, count: usize) -> Vec<String> {
+    let Ok(text) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = text.lines().collect();
+    lines[lines.len().saturating_sub(count)..]
+        .iter()
+        .map(|l| l.to_string())
+        .collect()
+}
+
+/// An append-only file that moves itself aside once it passes `max` bytes,
+/// keeping `keep` old copies as `name.1` (newest) to `name.{keep}`
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max: u64,
+    keep: usize,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf, max: u64, keep: usize) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            file,
+            size,
+            max,
+            keep,
+        })
+    }
+
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        for n in (1..self.keep).rev() {
+            let from = self.rotated(n);
+            if from.exists() {
+                fs::rename(&from, self.rotated(n + 1))?;
+            }
+        }
+        if self.keep > 0 {
+            fs::rename(&self.path, self.rotated(1))?;
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > self.max {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotates_and_keeps_the_newest_copies() {
+        let dir = std::env::temp_dir().join(format!("grit-log-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("grit.log");
+        let mut file = RotatingFile::open(path.clone(), 10, 2).unwrap();
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            file.write_all(line.as_bytes()).unwrap();
+        }
+
+        assert_eq!(tail(&path, 5), vec!["fourth"]);
+        assert_eq!(tail(&file.rotated(1), 5), vec!["third"]);
+        assert_eq!(tail(&file.rotated(2), 5), vec!["second"]);
+        assert!(!file.rotated(3).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn tail_keeps_the_last_lines() {
+        let dir = std::env::temp_dir().join(format!("grit-log-tail-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("grit.log");
+        fs::write(&path, "a\nb\nc\n").unwrap();
+        assert_eq!(tail(&path, 2), vec!["b", "c"]);
+        assert!(tail(&dir.join("missing.log"), 2).is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -3,31 +3,44 @@ mod app;
 mod auth;
 mod cache;
 mod config;
+mod drafts;
 mod error;
 mod event;
 mod forge;
 mod gitea;
 mod github;
 mod gitlab;
+mod http;
+mod keyring;
+mod links;
+mod logging;
+mod notify;
+mod outbox;
 mod pager;
+mod palette;
+mod state;
+mod tape;
+#[cfg(test)]
+mod testing;
 mod tui;
 mod types;
 mod ui;
+mod validate;
+mod workspace;
 
+use std::collections::HashMap;
 use std::panic;
 use std::sync::Arc;
 use std::time::Duration;
 
 use clap::{Parser, Subcommand};
 use tokio::sync::mpsc;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
-use crate::action::{Action, EditorContext};
-use crate::app::App;
-use crate::config::{Config, ForgeType};
+use crate::action::{Action, EditorContext, GitRun};
+use crate::app::{App, ForgeState};
+use crate::config::Config;
 use crate::event::Event;
 use crate::forge::Forge;
-use crate::github::GitHub;
 use crate::tui::EventHandler;
 
 #[derive(Parser)]
@@ -40,9 +53,28 @@ struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
 
-    /// Start with a specific forge by name (must match a [[forges]] entry in config)
+    /// Open a repo or PR directly: owner/repo or owner/repo#123
+    target: Option<String>,
+
+    /// Open this PR of the target repo (or the current checkout's repo)
     #[arg(long)]
+    pr: Option<u64>,
+
+    /// Start with a specific forge by name (must match a [[forges]] entry in config)
+    #[arg(long, global = true)]
     forge: Option<String>,
+
+    /// Don't open the repo of the git checkout in the current directory
+    #[arg(long)]
+    no_auto: bool,
+
+    /// Save every forge response as JSON under DIR, for --replay
+    #[arg(long, value_name = "DIR", conflicts_with = "replay")]
+    record: Option<std::path::PathBuf>,
+
+    /// Answer from the responses --record saved in DIR, without the network
+    #[arg(long, value_name = "DIR")]
+    replay: Option<std::path::PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -52,6 +84,37 @@ enum Commands {
         #[command(subcommand)]
         action: ConfigAction,
     },
+    /// Log in to a forge and manage stored tokens
+    Auth {
+        #[command(subcommand)]
+        action: AuthAction,
+    },
+    /// Move local state (last locations) between machines as one JSON file
+    State {
+        #[command(subcommand)]
+        action: StateAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum StateAction {
+    /// Write local state as JSON to FILE, or stdout
+    Export { file: Option<std::path::PathBuf> },
+    /// Read state exported by `grit state export` from FILE, or stdin, over
+    /// the local state
+    Import { file: Option<std::path::PathBuf> },
+}
+
+#[derive(Subcommand)]
+enum AuthAction {
+    /// Log in with the OAuth device flow and store the token (--forge picks the forge)
+    Login,
+    /// Show where each configured forge's token comes from
+    Status,
+    /// Delete the stored token (--forge picks the forge)
+    Logout,
+    /// Move token files into the OS keyring for forges with token_source = "keyring"
+    Migrate,
 }
 
 #[derive(Subcommand)]
@@ -66,6 +129,16 @@ enum ConfigAction {
     },
     /// Print the config file path
     Path,
+    /// Check the config and each forge entry; exits 1 if a check fails, 2 if
+    /// the config can't be parsed
+    Validate {
+        /// Also sign in to each forge and check the token's scopes
+        #[arg(long)]
+        online: bool,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 fn handle_config_command(action: ConfigAction) {
@@ -104,44 +177,134 @@ fn handle_config_command(action: ConfigAction) {
             println!("Config file written to {}", path.display());
         }
         ConfigAction::Path => match config::config_path() {
-            Some(path) => println!("{}", path.display()),
+            Some(path) => {
+                println!("{}", path.display());
+                if let Some(project) = config::project_config_path() {
+                    println!("{} (project overrides)", project.display());
+                }
+            }
             None => {
                 eprintln!("Error: could not determine config directory");
                 std::process::exit(1);
             }
         },
+        // Needs the async runtime; main runs it before getting here
+        ConfigAction::Validate { .. } => unreachable!(),
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let cli = Cli::parse();
+fn handle_state_command(action: StateAction) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::{Read, Write};
 
-    if let Some(Commands::Config { action }) = cli.command {
-        handle_config_command(action);
-        return Ok(());
+    match action {
+        StateAction::Export { file } => {
+            let bundle = state::export();
+            let json = serde_json::to_string_pretty(&bundle)?;
+            match file {
+                Some(path) => {
+                    std::fs::write(&path, json + "\n")?;
+                    eprintln!(
+                        "Exported {} entries to {}",
+                        bundle.entries.len(),
+                        path.display()
+                    );
+                }
+                None => writeln!(std::io::stdout(), "{}", json)?,
+            }
+        }
+        StateAction::Import { file } => {
+            let json = match file {
+                Some(path) => std::fs::read_to_string(path)?,
+                None => {
+                    let mut json = String::new();
+                    std::io::stdin().read_to_string(&mut json)?;
+                    json
+                }
+            };
+            let bundle: state::Bundle = serde_json::from_str(&json)?;
+            let count = state::import(&bundle)?;
+            eprintln!("Imported {} entries", count);
+        }
     }
+    Ok(())
+}
 
-    // Initialize logging
-    tracing_subscriber::registry()
-        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")))
-        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
-        .init();
+async fn handle_auth_command(
+    action: AuthAction,
+    forge: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load();
+    match action {
+        AuthAction::Login => {
+            auth::login(&select_forge(&config, forge)?).await?;
+        }
+        AuthAction::Status => {
+            for forge_config in &config.forges {
+                match auth::token_source(forge_config) {
+                    Some(source) => println!(
+                        "{} ({}): token from {}",
+                        forge_config.name, forge_config.host, source
+                    ),
+                    None => println!(
+                        "{} ({}): not logged in",
+                        forge_config.name, forge_config.host
+                    ),
+                }
+            }
+        }
+        AuthAction::Migrate => {
+            let forges: Vec<config::ForgeConfig> = match forge {
+                Some(_) => vec![select_forge(&config, forge)?],
+                None => config
+                    .forges
+                    .iter()
+                    .filter(|f| f.token_source == config::TokenSource::Keyring)
+                    .cloned()
+                    .collect(),
+            };
+            if forges.is_empty() {
+                println!("No forge has token_source = \"keyring\".");
+            }
+            for forge_config in &forges {
+                if auth::migrate(forge_config)? {
+                    println!(
+                        "{}: moved the stored token to the {}.",
+                        forge_config.name,
+                        keyring::STORE_NAME
+                    );
+                } else {
+                    println!("{}: no token file to move.", forge_config.name);
+                }
+            }
+        }
+        AuthAction::Logout => {
+            let forge_config = select_forge(&config, forge)?;
+            if auth::logout(&forge_config)? {
+                println!("Removed the stored token for {}.", forge_config.name);
+            } else {
+                println!("No stored token for {}.", forge_config.name);
+            }
+        }
+    }
+    Ok(())
+}
 
-    // Set up panic hook to restore terminal
-    let original_hook = panic::take_hook();
-    panic::set_hook(Box::new(move |panic_info| {
-        let _ = tui::restore();
-        original_hook(panic_info);
-    }));
+/// The forge named by --forge or a project's `forge`, else the one matching
+/// the current checkout, else `default_forge`, else the first configured
+/// Token (prompting with the device flow if need be) and client for the
+/// forge grit was asked to start with
+async fn connect_preferred(forge_config: &config::ForgeConfig) -> Result<Arc<dyn Forge>, String> {
+    let token = auth::load_forge_token(forge_config).await?;
+    forge::client(forge_config, token).map_err(|e| e.to_string())
+}
 
-    // Load config and detect forge
-    let config = Config::load();
-    let forge_config = if let Some(ref name) = cli.forge {
-        config
+fn select_forge(config: &Config, name: Option<&str>) -> Result<config::ForgeConfig, String> {
+    if let Some(name) = name.or(config.forge.as_deref()) {
+        return config
             .forges
             .iter()
-            .find(|f| f.name == *name)
+            .find(|f| f.name == name)
+            .cloned()
             .ok_or_else(|| {
                 let available: Vec<&str> = config.forges.iter().map(|f| f.name.as_str()).collect();
                 format!(
@@ -149,54 +312,211 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     name,
                     available.join(", ")
                 )
-            })?
-            .clone()
-    } else {
-        config::detect_forge(&config)
-            .or_else(|| config.forges.first())
-            .ok_or("No forge configured")?
-            .clone()
-    };
+            });
+    }
+    config::detect_forge(config)
+        .or_else(|| {
+            let name = config.general.default_forge.as_deref()?;
+            config.forges.iter().find(|f| f.name == name)
+        })
+        .or_else(|| config.forges.first())
+        .cloned()
+        .ok_or_else(|| "No forge configured".to_string())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
 
-    // Load token for the detected forge
-    let token = auth::load_forge_token(&forge_config)
-        .await
-        .map_err(Box::<dyn std::error::Error>::from)?;
+    match cli.command {
+        Some(Commands::Config {
+            action: ConfigAction::Validate { online, json },
+        }) => {
+            let report = validate::run(online).await;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print!("{}", report.text());
+            }
+            std::process::exit(report.exit_code());
+        }
+        Some(Commands::Config { action }) => {
+            handle_config_command(action);
+            return Ok(());
+        }
+        Some(Commands::Auth { action }) => {
+            return handle_auth_command(action, cli.forge.as_deref()).await;
+        }
+        Some(Commands::State { action }) => {
+            return handle_state_command(action);
+        }
+        None => {}
+    }
 
-    // Initialize forge client
-    let forge: Arc<dyn Forge> = match forge_config.forge_type {
-        ForgeType::GitHub => Arc::new(GitHub::new(token)?),
-        ForgeType::GitLab => Arc::new(gitlab::GitLab::new(forge_config.host.clone(), token)),
-        ForgeType::Gitea => Arc::new(gitea::Gitea::new(forge_config.host.clone(), token)),
+    // Set up panic hook to restore terminal
+    let original_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |panic_info| {
+        let _ = tui::restore();
+        original_hook(panic_info);
+    }));
+
+    // Load config and detect forge
+    let mut config = Config::load();
+    logging::init(&config.log);
+    let forge_config = select_forge(&config, cli.forge.as_deref())?;
+    let tape = match (cli.record, cli.replay) {
+        (Some(dir), _) => Some((dir, tape::Mode::Record)),
+        (None, Some(dir)) => Some((dir, tape::Mode::Replay)),
+        (None, None) => None,
+    };
+
+    let mut forge_states = HashMap::new();
+    let (forge_config, forge) = if let Some((dir, mode)) = &tape {
+        // Just the one forge, and no cache or saved session from earlier
+        // runs, so a replay loads what the recording did
+        config.forges.retain(|f| f.name == forge_config.name);
+        cache::isolate();
+        let inner = match mode {
+            tape::Mode::Record => connect_preferred(&forge_config).await?,
+            // Only names things and builds links; it's never called
+            tape::Mode::Replay => forge::client(&forge_config, String::new())?,
+        };
+        let forge: Arc<dyn Forge> =
+            Arc::new(tape::Tape::new(inner, dir, *mode).map_err(|e| e.to_string())?);
+        (forge_config, forge)
+    } else {
+        match connect_preferred(&forge_config).await {
+            Ok(forge) => (forge_config, forge),
+            Err(e) => {
+                // A broken forge shouldn't lock out the others: try them all at
+                // once and start on the first that works, in config order
+                let others: Vec<_> = config
+                    .forges
+                    .iter()
+                    .filter(|f| f.name != forge_config.name)
+                    .cloned()
+                    .collect();
+                let results = futures::future::join_all(
+                    others
+                        .iter()
+                        .cloned()
+                        .map(|fc| tokio::task::spawn_blocking(move || forge::connect(&fc))),
+                )
+                .await;
+                let mut fallback = None;
+                for (fc, result) in others.into_iter().zip(results) {
+                    let state = match result.map_err(|e| e.to_string()).and_then(|r| r) {
+                        Ok(forge) => {
+                            if fallback.is_none() {
+                                fallback = Some((fc.clone(), Arc::clone(&forge)));
+                            }
+                            ForgeState::Ready(forge)
+                        }
+                        Err(e) => ForgeState::Failed(e),
+                    };
+                    forge_states.insert(fc.name, state);
+                }
+                let Some(fallback) = fallback else {
+                    return Err(e.into());
+                };
+                forge_states.insert(forge_config.name.clone(), ForgeState::Failed(e));
+                fallback
+            }
+        }
+    };
+    forge_states.insert(
+        forge_config.name.clone(),
+        ForgeState::Ready(Arc::clone(&forge)),
+    );
+
+    // An explicit target wins; otherwise jump into the repo we were launched inside of
+    let (start_repo, start_pr) = match cli.target.as_deref() {
+        Some(target) => {
+            let (owner, repo, number) = types::parse_repo_ref(target).ok_or_else(|| {
+                format!(
+                    "Can't open '{}': expected owner/repo or owner/repo#123",
+                    target
+                )
+            })?;
+            (Some((owner, repo)), cli.pr.or(number))
+        }
+        None => {
+            let detect = cli.pr.is_some() || (!cli.no_auto && config.ui.auto_repo());
+            let repo = detect.then(|| config::detect_repo(&forge_config)).flatten();
+            if cli.pr.is_some() && repo.is_none() {
+                return Err("--pr needs owner/repo or a checkout of the repo".into());
+            }
+            (repo, cli.pr)
+        }
     };
 
     // Run the application
-    let result = run(forge, config.forges).await;
+    let result = run(
+        forge,
+        forge_config.name,
+        forge_states,
+        config,
+        start_repo,
+        start_pr,
+    )
+    .await;
 
     // Restore terminal
     tui::restore()?;
+    if tape.is_some() {
+        cache::discard_isolated();
+    }
 
-    result
+    if let Some(summary) = result? {
+        println!("{}", summary);
+    }
+    Ok(())
 }
 
 /// Actions that require suspending the TUI and shelling out
 enum SuspendAction {
     Pager(String),
+    PagerFile(std::sync::Arc<tempfile::TempPath>),
     Editor(EditorContext),
+    EditFile(std::path::PathBuf),
+    Git(GitRun),
 }
 
 async fn run(
     forge: Arc<dyn Forge>,
-    forge_configs: Vec<crate::config::ForgeConfig>,
-) -> Result<(), Box<dyn std::error::Error>> {
+    active_forge: String,
+    forge_states: HashMap<String, ForgeState>,
+    config: Config,
+    start_repo: Option<(String, String)>,
+    start_pr: Option<u64>,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
     // Initialize terminal
-    let mut terminal = tui::init()?;
+    let mut terminal = tui::init(config.ui.mouse)?;
 
     // Create action channel
     let (action_tx, mut action_rx) = mpsc::unbounded_channel::<Action>();
 
     // Create app state
-    let mut app = App::new(forge, action_tx.clone(), forge_configs);
+    let mut app = App::new(forge, action_tx.clone(), config.forges, config.ui);
+    app.snippets = config.snippets.into_iter().collect();
+    app.searches = config.searches.into_iter().collect();
+    app.set_home_config(config.home);
+    app.active_forge = active_forge;
+    app.forge_states = forge_states;
+    let unavailable: Vec<&str> = app
+        .forge_states
+        .iter()
+        .filter(|(_, state)| matches!(state, ForgeState::Failed(_)))
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !unavailable.is_empty() {
+        app.flash_message = Some((
+            format!("Unavailable: {} (f shows why)", unavailable.join(", ")),
+            std::time::Instant::now(),
+        ));
+    }
+    app.start_repo = start_repo;
+    app.start_pr = start_pr;
 
     // Create event handler
     let tick_rate = Duration::from_millis(250);
@@ -204,7 +524,7 @@ async fn run(
     let mut events = EventHandler::new(tick_rate, render_rate);
 
     // Trigger initial data load (not from EventHandler to avoid re-triggering after pager suspend)
-    action_tx.send(Action::LoadHome)?;
+    action_tx.send(Action::Start)?;
 
     // Main loop
     loop {
@@ -235,9 +555,18 @@ async fn run(
                     Action::SuspendForPager(content) => {
                         suspend = Some(SuspendAction::Pager(content));
                     }
+                    Action::SuspendForPagerFile(path) => {
+                        suspend = Some(SuspendAction::PagerFile(path));
+                    }
                     Action::SuspendForEditor(ctx) => {
                         suspend = Some(SuspendAction::Editor(ctx));
                     }
+                    Action::SuspendForEditFile(path) => {
+                        suspend = Some(SuspendAction::EditFile(path));
+                    }
+                    Action::SuspendForGit(run) => {
+                        suspend = Some(SuspendAction::Git(run));
+                    }
                     other => {
                         app.update(other);
                     }
@@ -258,9 +587,67 @@ async fn run(
                     let pager_cmd = pager::detect_pager();
                     let _ = pager::open_pager(&content, &pager_cmd);
                 }
+                SuspendAction::PagerFile(path) => {
+                    let pager_cmd = pager::detect_pager();
+                    let _ = pager::open_pager_file(&path, &pager_cmd);
+                    // Dropping the last handle deletes the file
+                    drop(path);
+                }
+                SuspendAction::EditFile(path) => {
+                    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+                    let _ = std::process::Command::new("sh")
+                        .args(["-c", &format!("{} \"$1\"", editor), "sh"])
+                        .arg(&path)
+                        .status();
+                }
+                SuspendAction::Git(run) => {
+                    let result = run
+                        .steps
+                        .iter()
+                        .try_for_each(|args| workspace::run_git(&run.dir, args));
+                    // Leave git's output on screen until the user has read it
+                    println!("\nPress Enter to return to grit");
+                    let _ = std::io::stdin().read_line(&mut String::new());
+                    let _ = action_tx.send(match result {
+                        Ok(()) => Action::GitFinished(run.done),
+                        Err(e) => Action::Error(e),
+                    });
+                }
                 SuspendAction::Editor(ctx) => {
-                    if let Some(body) = open_editor() {
-                        if !body.trim().is_empty() {
+                    // Text from an earlier, unsent attempt wins over a snippet
+                    let draft = drafts::key(&app.forge_name, &ctx);
+                    let prefill = app.editor_prefill.take();
+                    let initial = match &ctx {
+                        EditorContext::EditRepo { meta, .. } => meta.to_template(),
+                        _ => draft
+                            .as_deref()
+                            .and_then(drafts::load)
+                            .or(prefill)
+                            .unwrap_or_default(),
+                    };
+                    match open_editor(&initial) {
+                        // Whatever was written before the editor failed
+                        Some((body, false)) => {
+                            if let (Some(key), true) =
+                                (&draft, body != initial && !body.trim().is_empty())
+                            {
+                                drafts::save(key, &body);
+                                app.flash_message = Some((
+                                    "Editor failed; draft kept for next time.".to_string(),
+                                    std::time::Instant::now(),
+                                ));
+                            }
+                        }
+                        Some((body, true)) => {
+                            let blank = body.trim().is_empty();
+                            // Kept until the forge has it; emptying the buffer discards it
+                            if let Some(key) = &draft {
+                                if blank {
+                                    drafts::clear(key);
+                                } else {
+                                    drafts::save(key, &body);
+                                }
+                            }
                             match ctx {
                                 EditorContext::CommentOnPr {
                                     owner,
@@ -271,24 +658,47 @@ async fn run(
                                     owner,
                                     repo,
                                     number,
-                                } => {
-                                    app.spawn_comment(owner, repo, number, body);
+                                } if !blank => {
+                                    app.spawn_comment(owner, repo, number, body, draft);
                                 }
+                                EditorContext::CommentOnCommit { owner, repo, sha } if !blank => {
+                                    app.spawn_commit_comment(owner, repo, sha, body, draft);
+                                }
+                                // Queued line comments may carry the review on their own
                                 EditorContext::ReviewPr {
                                     owner,
                                     repo,
                                     number,
                                     event,
                                 } => {
-                                    app.spawn_submit_review(owner, repo, number, event, body);
+                                    app.spawn_submit_review(
+                                        owner, repo, number, event, body, draft,
+                                    );
+                                }
+                                EditorContext::LineComment { path, line } if !blank => {
+                                    let _ = action_tx.send(Action::AddReviewComment(
+                                        crate::types::ReviewComment { path, line, body },
+                                    ));
+                                }
+                                // Saving unchanged (or emptied) leaves the repo alone
+                                EditorContext::EditRepo { owner, repo, meta } if !blank => {
+                                    let edited = crate::types::RepoMeta::parse(&body);
+                                    if edited != meta {
+                                        app.spawn_update_repo_meta(owner, repo, edited);
+                                    }
+                                }
+                                EditorContext::NewIssue { owner, repo, form } if !blank => {
+                                    app.spawn_save_form(owner, repo, form, body, draft);
                                 }
+                                _ => {}
                             }
                         }
+                        None => {}
                     }
                 }
             }
 
-            terminal = tui::init()?;
+            terminal = tui::init(app.ui_config.mouse)?;
             // Discard leftover keystrokes (e.g. extra q's from exiting the pager)
             tui::drain_events();
             events = EventHandler::new(tick_rate, render_rate);
@@ -300,29 +710,30 @@ async fn run(
         }
     }
 
-    Ok(())
+    app.save_last_location();
+    app.save_session();
+    app.save_recent();
+    Ok(app
+        .ui_config
+        .session_summary
+        .then(|| format!("grit: {}", app.stats.summary())))
 }
 
-/// Open $EDITOR with a temp file, return contents if saved
-fn open_editor() -> Option<String> {
+/// Open $EDITOR on a temp file holding `initial`; returns what the file holds
+/// afterwards and whether the editor exited cleanly
+fn open_editor(initial: &str) -> Option<(String, bool)> {
     let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
     let tmp_dir = std::env::temp_dir();
     let tmp_path = tmp_dir.join(format!("grit-{}.md", std::process::id()));
 
-    // Write empty file
-    std::fs::write(&tmp_path, "").ok()?;
+    std::fs::write(&tmp_path, initial).ok()?;
 
     let status = std::process::Command::new("sh")
         .args(["-c", &format!("{} {}", editor, tmp_path.display())])
         .status()
         .ok()?;
 
-    if !status.success() {
-        let _ = std::fs::remove_file(&tmp_path);
-        return None;
-    }
-
-    let content = std::fs::read_to_string(&tmp_path).ok()?;
+    let content = std::fs::read_to_string(&tmp_path);
     let _ = std::fs::remove_file(&tmp_path);
-    Some(content)
+    Some((content.ok()?, status.success()))
 }
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+
+use crate::types::{ChecksStatus, MyPr, ReviewRequest};
+
+/// What a Home refresh turned up since the previous load: review requests
+/// that weren't there before and PRs of yours whose checks just failed
+pub fn home_alerts(
+    old_reviews: &[ReviewRequest],
+    new_reviews: &[ReviewRequest],
+    old_prs: &[MyPr],
+    new_prs: &[MyPr],
+) -> Vec<String> {
+    let requested: HashSet<_> = old_reviews
+        .iter()
+        .map(|r| (&r.repo_owner, &r.repo_name, r.pr_number))
+        .collect();
+    let failing: HashSet<_> = old_prs
+        .iter()
+        .filter(|pr| pr.checks_status == ChecksStatus::Failure)
+        .map(|pr| (&pr.repo_owner, &pr.repo_name, pr.number))
+        .collect();
+
+    let reviews = new_reviews
+        .iter()
+        .filter(|r| !requested.contains(&(&r.repo_owner, &r.repo_name, r.pr_number)))
+        .map(|r| {
+            format!(
+                "Review requested: {}/{}#{} {}",
+                r.repo_owner, r.repo_name, r.pr_number, r.pr_title
+            )
+        });
+    let failures = new_prs
+        .iter()
+        .filter(|pr| pr.checks_status == ChecksStatus::Failure)
+        .filter(|pr| !failing.contains(&(&pr.repo_owner, &pr.repo_name, pr.number)))
+        .map(|pr| {
+            format!(
+                "Checks failed: {}/{}#{} {}",
+                pr.repo_owner, pr.repo_name, pr.number, pr.title
+            )
+        });
+    reviews.chain(failures).collect()
+}
+
+/// Show `alerts` as one desktop notification. Best-effort: without a
+/// notification daemon nothing happens.
+pub fn desktop(alerts: &[String]) {
+    let summary = match alerts.len() {
+        1 => "grit".to_string(),
+        n => format!("grit: {} updates", n),
+    };
+    let body = alerts.join("\n");
+    tokio::task::spawn_blocking(move || {
+        let _ = notify_rust::Notification::new()
+            .appname("grit")
+            .summary(&summary)
+            .body(&body)
+            .show();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::types::{MergeableState, PrState};
+
+    fn review(number: u64) -> ReviewRequest {
+        ReviewRequest {
+            repo_owner: "o".to_string(),
+            repo_name: "r".to_string(),
+            pr_number: number,
+            pr_title: format!("PR {}", number),
+            author: "a".to_string(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn my_pr(number: u64, checks_status: ChecksStatus) -> MyPr {
+        MyPr {
+            repo_owner: "o".to_string(),
+            repo_name: "r".to_string(),
+            number,
+            title: format!("PR {}", number),
+            state: PrState::Open,
+            checks_status,
+            mergeable: MergeableState::Unknown,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn only_new_review_requests_alert() {
+        let alerts = home_alerts(&[review(1)], &[review(1), review(2)], &[], &[]);
+        assert_eq!(alerts, vec!["Review requested: o/r#2 PR 2"]);
+    }
+
+    #[test]
+    fn checks_alert_when_they_start_failing() {
+        let old = [
+            my_pr(1, ChecksStatus::Pending),
+            my_pr(2, ChecksStatus::Failure),
+        ];
+        let new = [
+            my_pr(1, ChecksStatus::Failure),
+            my_pr(2, ChecksStatus::Failure),
+            my_pr(3, ChecksStatus::Success),
+        ];
+        let alerts = home_alerts(&[], &[], &old, &new);
+        assert_eq!(alerts, vec!["Checks failed: o/r#1 PR 1"]);
+    }
+}
@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+
+use crate::cache;
+use crate::error::Result;
+use crate::forge::Forge;
+
+/// A change made while offline, held until the forge is reachable again
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Mutation {
+    Comment {
+        owner: String,
+        repo: String,
+        number: u64,
+        body: String,
+    },
+    CommitComment {
+        owner: String,
+        repo: String,
+        sha: String,
+        body: String,
+    },
+    ClosePr {
+        owner: String,
+        repo: String,
+        number: u64,
+    },
+    CloseIssue {
+        owner: String,
+        repo: String,
+        number: u64,
+    },
+}
+
+impl Mutation {
+    /// Send it to the forge
+    pub async fn apply(&self, forge: &dyn Forge) -> Result<()> {
+        match self {
+            Mutation::Comment {
+                owner,
+                repo,
+                number,
+                body,
+            } => forge.comment(owner, repo, *number, body).await,
+            Mutation::CommitComment {
+                owner,
+                repo,
+                sha,
+                body,
+            } => forge.comment_on_commit(owner, repo, sha, body).await,
+            Mutation::ClosePr {
+                owner,
+                repo,
+                number,
+            } => forge.close_pr(owner, repo, *number).await,
+            Mutation::CloseIssue {
+                owner,
+                repo,
+                number,
+            } => forge.close_issue(owner, repo, *number).await,
+        }
+    }
+
+    /// Short description for flash messages
+    pub fn describe(&self) -> String {
+        match self {
+            Mutation::Comment {
+                owner,
+                repo,
+                number,
+                ..
+            } => format!("comment on {}/{}#{}", owner, repo, number),
+            Mutation::CommitComment {
+                owner, repo, sha, ..
+            } => format!("comment on {}/{}@{}", owner, repo, &sha[..7.min(sha.len())]),
+            Mutation::ClosePr {
+                owner,
+                repo,
+                number,
+            } => format!("close PR {}/{}#{}", owner, repo, number),
+            Mutation::CloseIssue {
+                owner,
+                repo,
+                number,
+            } => format!("close issue {}/{}#{}", owner, repo, number),
+        }
+    }
+}
+
+fn outbox_key(forge: &str) -> String {
+    format!("{}_outbox", forge)
+}
+
+/// Mutations still waiting to be sent to `forge`
+pub fn load(forge: &str) -> Vec<Mutation> {
+    cache::read(&outbox_key(forge)).unwrap_or_default()
+}
+
+/// Persist the outbox so it survives a restart
+pub fn save(forge: &str, outbox: &[Mutation]) {
+    cache::write(&outbox_key(forge), &outbox);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutations_round_trip_through_json() {
+        let outbox = vec![
+            Mutation::Comment {
+                owner: "o".to_string(),
+                repo: "r".to_string(),
+                number: 3,
+                body: "lgtm".to_string(),
+            },
+            Mutation::CloseIssue {
+                owner: "o".to_string(),
+                repo: "r".to_string(),
+                number: 9,
+            },
+        ];
+        let json = serde_json::to_string(&outbox).unwrap();
+        assert!(json.contains("\"kind\":\"close_issue\""));
+        let back: Vec<Mutation> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, outbox);
+    }
+
+    #[test]
+    fn describe_shortens_shas() {
+        let m = Mutation::CommitComment {
+            owner: "o".to_string(),
+            repo: "r".to_string(),
+            sha: "0123456789abcdef".to_string(),
+            body: String::new(),
+        };
+        assert_eq!(m.describe(), "comment on o/r@0123456");
+    }
+}
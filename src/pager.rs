@@ -53,6 +53,19 @@ pub fn open_pager(content: &str, pager_cmd: &str) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Page a file too large to pass around in memory. It becomes the pager's
+/// stdin directly, so pagers work as with `open_pager` and grit never reads
+/// the file itself.
+pub fn open_pager_file(path: &std::path::Path, pager_cmd: &str) -> std::io::Result<()> {
+    let file = std::fs::File::open(path)?;
+    let cmd = ensure_paging_always(pager_cmd);
+    Command::new("sh")
+        .args(["-c", &cmd])
+        .stdin(std::process::Stdio::from(file))
+        .status()?;
+    Ok(())
+}
+
 /// If the pager command invokes delta without an explicit --paging flag,
 /// append `--paging=always` so it always spawns its internal pager.
 fn ensure_paging_always(pager_cmd: &str) -> String {
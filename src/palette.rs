@@ -0,0 +1,149 @@
+use crossterm::event::KeyCode;
+
+use crate::action::{Action, ConfirmAction, EditorContext, FormTarget, MilestonePurpose, RepoTab};
+
+/// Keys whose actions are offered in the command palette. Each key is run
+/// through the normal key handler, so entries follow the current screen and
+/// bindings instead of keeping a second copy of them.
+pub const KEYS: &[char] = &[
+    'm', 'x', 'C', 'R', 'L', 'e', 'd', 'b', 't', 'T', ']', '[', '}', '{', 'M', 'F', 'p', 'i', 'c',
+    'a', 'g', 'V', 'A', 'w', 'D', 'E', 'I', '*', 'W', 'Y', 'U', 'B', 'O', 'S', '#', 'v', 'r', 'u',
+    'o', 'y', '/', 'f', 's', '+', 'N', 'X', 'Q', 'P', '!', 'q',
+];
+
+/// Keys without a character, listed after `KEYS` under the name shown
+pub const NAMED_KEYS: &[(KeyCode, &str)] = &[(KeyCode::F(12), "F12")];
+
+/// Palette name for an action, or `None` if it isn't worth listing
+pub fn label(action: &Action) -> Option<&'static str> {
+    let name = match action {
+        Action::ShowMergeMethodSelect => "merge pr",
+        Action::ShowConfirm(ConfirmAction::ClosePr(_)) => "close pr",
+        Action::ShowConfirm(ConfirmAction::CloseIssue(_)) => "close issue",
+        Action::ShowConfirm(ConfirmAction::CloseMarked(_)) => "close marked",
+        Action::MarkRange => "mark a range of rows",
+        Action::SuspendForEditor(EditorContext::CommentOnPr { .. })
+        | Action::ShowSnippetSelect(EditorContext::CommentOnPr { .. }) => "comment on pr",
+        Action::SuspendForEditor(EditorContext::CommentOnIssue { .. })
+        | Action::ShowSnippetSelect(EditorContext::CommentOnIssue { .. }) => "comment on issue",
+        Action::SuspendForEditor(EditorContext::CommentOnCommit { .. })
+        | Action::ShowSnippetSelect(EditorContext::CommentOnCommit { .. }) => "comment on commit",
+        Action::StartQuickComment => "quick comment on pr",
+        Action::ShowReviewSelect => "submit review",
+        Action::ShowLabelSelect => "edit labels",
+        Action::ShowIssueTemplateSelect => "new issue",
+        Action::ShowForm(FormTarget::Issue(_)) => "edit issue title / labels / assignees",
+        Action::ShowForm(FormTarget::Pr(_)) => "edit pr title / labels / assignees",
+        Action::OpenFileInEditor => "open file in editor",
+        Action::CloneRepo => "clone repo",
+        Action::EditRepoMeta => "edit repo description / topics",
+        Action::CheckoutPr => "check out pr locally",
+        Action::ShowRevertSelect => "revert pr",
+        Action::ShowReviewerLoad => "request review (by reviewer load)",
+        Action::ViewDiff => "view diff",
+        Action::ShowPrChanges => "show changes since last look",
+        Action::ToggleBotComments => "toggle bot comments",
+        Action::ShowReactionSelect => "add reaction",
+        Action::ToggleThreadResolved => "resolve / unresolve thread",
+        Action::ToggleResolvedThreads => "toggle resolved threads",
+        Action::ShowChecklist => "tick pr task list items",
+        Action::NextThread => "next review thread",
+        Action::PrevThread => "previous review thread",
+        Action::NextFile => "next file",
+        Action::PrevFile => "previous file",
+        Action::ShowMilestoneSelect(MilestonePurpose::Assign) => "set milestone",
+        Action::ShowMilestoneSelect(MilestonePurpose::Filter) => "filter by milestone",
+        Action::SwitchRepoTab(RepoTab::PullRequests) => "go to pull requests",
+        Action::SwitchRepoTab(RepoTab::Issues) => "go to issues",
+        Action::SwitchRepoTab(RepoTab::Commits) => "go to commits",
+        Action::SwitchRepoTab(RepoTab::Actions) => "go to actions",
+        Action::ShowSavedSearchSelect => "run a saved search",
+        Action::ShowPrFilterSelect => "filter pull requests (mine, assigned, to review)",
+        Action::Refresh => "refresh",
+        Action::DismissAlerts => "dismiss alerts",
+        Action::ShowLogs => "logs",
+        Action::RefreshItem => "refresh selected item",
+        Action::ShowRepoList => "browse repositories",
+        Action::CycleReviewSort => "sort review requests",
+        Action::OpenInBrowser => "open in browser",
+        Action::YankUrl => "copy url",
+        Action::EnterSearchMode => "search",
+        Action::ShowForgeSelect => "switch forge",
+        Action::ShowOrgSelect => "show an organization's repos",
+        Action::ToggleBrowse => "toggle column browser",
+        Action::StartFindCommit => "find commit across repos",
+        Action::StartJump => "jump to pr or issue number",
+        Action::ToggleWorkflowSummary => "toggle workflow summary",
+        Action::ShowArtifactSelect => "download artifact",
+        Action::ShowInsights => "repo insights",
+        Action::ToggleStar => "star / unstar repo",
+        Action::ShowWatchSelect => "watch / unwatch repo",
+        Action::ForkRepo => "fork repo",
+        Action::SyncFork => "sync fork with upstream",
+        Action::TogglePreview => "toggle preview pane",
+        Action::ToggleSplitDiff => "toggle side-by-side diff",
+        Action::Back => "back",
+        Action::Quit => "quit",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Fuzzy subsequence match: every query char must appear in order.
+/// Higher is better; consecutive runs and word starts score extra.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut prev_match: Option<usize> = None;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let offset = candidate[pos..].iter().position(|&c| c == q)?;
+        let at = pos + offset;
+        score += 1;
+        if prev_match.is_some_and(|p| p + 1 == at) {
+            score += 5;
+        }
+        if at == 0 || !candidate[at - 1].is_alphanumeric() {
+            score += 3;
+        }
+        // Gaps cost a little so tighter matches rank first
+        score -= offset as i64 / 4;
+        prev_match = Some(at);
+        pos = at + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_query_matches_everything() {
+        assert_eq!(fuzzy_score("", "merge pr"), Some(0));
+    }
+
+    #[test]
+    fn subsequence_matches_case_insensitively() {
+        assert!(fuzzy_score("MPR", "merge pr").is_some());
+        assert!(fuzzy_score("mrg", "merge pr").is_some());
+    }
+
+    #[test]
+    fn out_of_order_does_not_match() {
+        assert!(fuzzy_score("rm", "merge").is_none());
+        assert!(fuzzy_score("xyz", "merge pr").is_none());
+    }
+
+    #[test]
+    fn word_starts_and_runs_rank_higher() {
+        let close_pr = fuzzy_score("cp", "close pr").unwrap();
+        let comment = fuzzy_score("cp", "comment on pr").unwrap();
+        assert!(close_pr > comment);
+        let merge = fuzzy_score("merge", "merge pr").unwrap();
+        let scattered = fuzzy_score("merge", "resolve / unresolve thread");
+        assert!(scattered.is_none() || merge > scattered.unwrap());
+    }
+}
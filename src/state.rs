@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cache;
+use crate::drafts;
+
+/// Bundle format; bumped when entries change meaning
+const VERSION: u32 = 1;
+
+/// Cache keys that hold local state rather than copies of forge data, by
+/// suffix. Everything else refills itself from the forges.
+const STATE_SUFFIXES: &[&str] = &[
+    "_last_location",
+    "_session",
+    "_recent",
+    "_outbox",
+    drafts::SUFFIX,
+];
+
+/// Local state in one JSON document, for `grit state export` / `import`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Bundle {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    /// Values by cache key
+    pub entries: BTreeMap<String, serde_json::Value>,
+}
+
+pub fn is_state_key(key: &str) -> bool {
+    STATE_SUFFIXES.iter().any(|suffix| key.ends_with(suffix))
+}
+
+/// Gather every state entry from the cache
+pub fn export() -> Bundle {
+    let entries = cache::keys()
+        .into_iter()
+        .filter(|key| is_state_key(key))
+        .filter_map(|key| Some((key.clone(), cache::read(&key)?)))
+        .collect();
+    Bundle {
+        version: VERSION,
+        exported_at: Utc::now(),
+        entries,
+    }
+}
+
+/// Check a bundle before anything is written: a newer format, or keys that
+/// aren't state (or could escape the cache directory), reject it whole
+pub fn validate(bundle: &Bundle) -> Result<(), String> {
+    if bundle.version > VERSION {
+        return Err(format!(
+            "Bundle version {} is newer than this grit understands ({})",
+            bundle.version, VERSION
+        ));
+    }
+    let bad: Vec<&str> = bundle
+        .entries
+        .keys()
+        .filter(|key| !is_state_key(key) || key.contains(['/', '\\']) || key.starts_with('.'))
+        .map(String::as_str)
+        .collect();
+    if !bad.is_empty() {
+        return Err(format!("Not grit state: {}", bad.join(", ")));
+    }
+    Ok(())
+}
+
+/// Write a bundle's entries over the local ones; returns how many
+pub fn import(bundle: &Bundle) -> Result<usize, String> {
+    validate(bundle)?;
+    for (key, value) in &bundle.entries {
+        cache::try_write(key, value)?;
+    }
+    Ok(bundle.entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle(keys: &[&str]) -> Bundle {
+        Bundle {
+            version: VERSION,
+            exported_at: Utc::now(),
+            entries: keys
+                .iter()
+                .map(|k| (k.to_string(), serde_json::json!("Home")))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn only_state_keys_count() {
+        assert!(is_state_key("GitHub_last_location"));
+        assert!(!is_state_key("GitHub_repos"));
+        assert!(!is_state_key("pr_GitHub_owner_repo_1"));
+    }
+
+    #[test]
+    fn validate_rejects_cache_data_and_paths() {
+        assert!(validate(&bundle(&["GitHub_last_location"])).is_ok());
+        let err = validate(&bundle(&["GitHub_repos", "work_last_location"])).unwrap_err();
+        assert_eq!(err, "Not grit state: GitHub_repos");
+        assert!(validate(&bundle(&["../x_last_location"])).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_newer_versions() {
+        let mut newer = bundle(&[]);
+        newer.version = VERSION + 1;
+        assert!(validate(&newer).is_err());
+    }
+
+    #[test]
+    fn bundle_round_trips_as_json() {
+        let json = serde_json::to_string(&bundle(&["GitHub_last_location"])).unwrap();
+        let back: Bundle = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.entries["GitHub_last_location"], "Home");
+    }
+}
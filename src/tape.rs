@@ -0,0 +1,834 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::error::{GritError, Result};
+use crate::forge::Forge;
+use crate::types::{
+    ActionRun, Artifact, ChecksStatus, Comment, Commit, CommitCheck, CommitDetail, CommitFile,
+    CommitHit, ForkStatus, Issue, IssueFields, IssueRef, IssueTemplate, Label, Milestone, MyPr,
+    PagedResult, PrChecks, PrFilter, PrReviews, PrSummary, PullRequest, Reaction, ReactionTarget,
+    RepoMeta, RepoStats, Repository, ReviewComment, ReviewRequest, ReviewThread, ReviewerLoad,
+    Subscription,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Pass calls to the forge and save what comes back
+    Record,
+    /// Answer from the saved responses; the forge is never called
+    Replay,
+}
+
+/// A forge whose responses go to, or come from, a directory of JSON files:
+/// one per call, named for the method and its arguments
+/// (`get_pr-acme-rocket-42.json`). Errors are kept too, so a replay fails
+/// where the recording did. A call made twice keeps the later answer.
+#[derive(Debug)]
+pub struct Tape {
+    /// Does the recording, and names and links things in either mode
+    inner: Arc<dyn Forge>,
+    dir: PathBuf,
+    mode: Mode,
+}
+
+impl Tape {
+    pub fn new(inner: Arc<dyn Forge>, dir: &Path, mode: Mode) -> std::io::Result<Self> {
+        match mode {
+            Mode::Record => std::fs::create_dir_all(dir)?,
+            Mode::Replay if !dir.is_dir() => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("no recording at {}", dir.display()),
+                ))
+            }
+            Mode::Replay => {}
+        }
+        Ok(Self {
+            inner,
+            dir: dir.to_path_buf(),
+            mode,
+        })
+    }
+
+    /// Save `response`'s outcome when recording; when replaying, drop it
+    /// unsent and answer from the file instead
+    async fn call<T: Serialize + DeserializeOwned>(
+        &self,
+        method: &str,
+        args: &[&(dyn Display + Sync)],
+        response: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let name = file_name(method, args);
+        let path = self.dir.join(&name);
+        match self.mode {
+            Mode::Record => {
+                let result = response.await;
+                let written = serde_json::to_string_pretty(&result)
+                    .map_err(|e| e.to_string())
+                    .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()));
+                if let Err(e) = written {
+                    tracing::warn!("not recorded: {}: {}", path.display(), e);
+                }
+                result
+            }
+            Mode::Replay => {
+                let Ok(json) = std::fs::read_to_string(&path) else {
+                    return Err(GritError::Api(format!("{} isn't in the recording", name)));
+                };
+                serde_json::from_str::<Result<T>>(&json)
+                    .unwrap_or_else(|e| Err(GritError::Api(format!("{}: {}", path.display(), e))))
+            }
+        }
+    }
+}
+
+/// `method-arg-arg.json`, with anything but letters, digits, `.` and `_` in
+/// the arguments (a GitLab group's `/`, a search's spaces) replaced by `_`
+fn file_name(method: &str, args: &[&(dyn Display + Sync)]) -> String {
+    let mut name = method.to_string();
+    for arg in args {
+        name.push('-');
+        name.extend(arg.to_string().chars().map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        }));
+    }
+    name + ".json"
+}
+
+#[async_trait]
+impl Forge for Tape {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+    fn web_url(&self, owner: &str, repo: &str, kind: &str, id: &str) -> String {
+        self.inner.web_url(owner, repo, kind, id)
+    }
+    fn pr_head_ref(&self, number: u64) -> String {
+        self.inner.pr_head_ref(number)
+    }
+    fn page_size(&self) -> usize {
+        self.inner.page_size()
+    }
+    fn pr_filters(&self) -> &'static [PrFilter] {
+        self.inner.pr_filters()
+    }
+    fn required_scope(&self) -> Option<&'static str> {
+        self.inner.required_scope()
+    }
+
+    async fn get_current_user(&self) -> Result<String> {
+        self.call("get_current_user", &[], self.inner.get_current_user())
+            .await
+    }
+    async fn list_repos(&self, page: u32) -> Result<PagedResult<Repository>> {
+        self.call("list_repos", &[&page], self.inner.list_repos(page))
+            .await
+    }
+    async fn list_prs(
+        &self,
+        owner: &str,
+        repo: &str,
+        filter: PrFilter,
+        milestone: Option<&Milestone>,
+        page: u32,
+    ) -> Result<PagedResult<PrSummary>> {
+        // Lists in a milestone are recorded apart
+        let mut args: Vec<&(dyn Display + Sync)> = vec![&owner, &repo, &filter];
+        if let Some(milestone) = &milestone {
+            args.push(&milestone.title);
+        }
+        args.push(&page);
+        self.call(
+            "list_prs",
+            &args,
+            self.inner.list_prs(owner, repo, filter, milestone, page),
+        )
+        .await
+    }
+    async fn get_pr(&self, owner: &str, repo: &str, number: u64) -> Result<PullRequest> {
+        self.call(
+            "get_pr",
+            &[&owner, &repo, &number],
+            self.inner.get_pr(owner, repo, number),
+        )
+        .await
+    }
+    async fn list_issues(
+        &self,
+        owner: &str,
+        repo: &str,
+        milestone: Option<&Milestone>,
+        page: u32,
+    ) -> Result<PagedResult<Issue>> {
+        let mut args: Vec<&(dyn Display + Sync)> = vec![&owner, &repo];
+        if let Some(milestone) = &milestone {
+            args.push(&milestone.title);
+        }
+        args.push(&page);
+        self.call(
+            "list_issues",
+            &args,
+            self.inner.list_issues(owner, repo, milestone, page),
+        )
+        .await
+    }
+    async fn get_issue(&self, owner: &str, repo: &str, number: u64) -> Result<Issue> {
+        self.call(
+            "get_issue",
+            &[&owner, &repo, &number],
+            self.inner.get_issue(owner, repo, number),
+        )
+        .await
+    }
+    async fn list_commits(
+        &self,
+        owner: &str,
+        repo: &str,
+        page: u32,
+    ) -> Result<PagedResult<Commit>> {
+        self.call(
+            "list_commits",
+            &[&owner, &repo, &page],
+            self.inner.list_commits(owner, repo, page),
+        )
+        .await
+    }
+    async fn get_commit(&self, owner: &str, repo: &str, sha: &str) -> Result<CommitDetail> {
+        self.call(
+            "get_commit",
+            &[&owner, &repo, &sha],
+            self.inner.get_commit(owner, repo, sha),
+        )
+        .await
+    }
+    async fn get_pr_diff(&self, owner: &str, repo: &str, number: u64) -> Result<String> {
+        self.call(
+            "get_pr_diff",
+            &[&owner, &repo, &number],
+            self.inner.get_pr_diff(owner, repo, number),
+        )
+        .await
+    }
+    /// Streamed bodies aren't saved, so diffs go through `get_pr_diff`
+    /// where they are
+    async fn stream_pr_diff(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+    ) -> Result<Option<reqwest::Response>> {
+        Ok(None)
+    }
+    async fn merge_pr(&self, owner: &str, repo: &str, number: u64, method: &str) -> Result<()> {
+        self.call(
+            "merge_pr",
+            &[&owner, &repo, &number],
+            self.inner.merge_pr(owner, repo, number, method),
+        )
+        .await
+    }
+    async fn enable_auto_merge(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        method: &str,
+        delete_branch: bool,
+    ) -> Result<()> {
+        self.call(
+            "enable_auto_merge",
+            &[&owner, &repo, &number],
+            self.inner
+                .enable_auto_merge(owner, repo, number, method, delete_branch),
+        )
+        .await
+    }
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<()> {
+        self.call(
+            "delete_branch",
+            &[&owner, &repo, &branch],
+            self.inner.delete_branch(owner, repo, branch),
+        )
+        .await
+    }
+    async fn close_pr(&self, owner: &str, repo: &str, number: u64) -> Result<()> {
+        self.call(
+            "close_pr",
+            &[&owner, &repo, &number],
+            self.inner.close_pr(owner, repo, number),
+        )
+        .await
+    }
+    async fn close_issue(&self, owner: &str, repo: &str, number: u64) -> Result<()> {
+        self.call(
+            "close_issue",
+            &[&owner, &repo, &number],
+            self.inner.close_issue(owner, repo, number),
+        )
+        .await
+    }
+    async fn comment(&self, owner: &str, repo: &str, number: u64, body: &str) -> Result<()> {
+        self.call(
+            "comment",
+            &[&owner, &repo, &number],
+            self.inner.comment(owner, repo, number, body),
+        )
+        .await
+    }
+
+    async fn list_review_requests(&self, username: &str) -> Result<Vec<ReviewRequest>> {
+        self.call(
+            "list_review_requests",
+            &[&username],
+            self.inner.list_review_requests(username),
+        )
+        .await
+    }
+    async fn list_my_prs(&self, username: &str) -> Result<Vec<MyPr>> {
+        self.call(
+            "list_my_prs",
+            &[&username],
+            self.inner.list_my_prs(username),
+        )
+        .await
+    }
+    async fn list_home_prs(&self, username: &str) -> Result<(Vec<ReviewRequest>, Vec<MyPr>)> {
+        self.call(
+            "list_home_prs",
+            &[&username],
+            self.inner.list_home_prs(username),
+        )
+        .await
+    }
+    async fn list_assigned_issues(&self, username: &str) -> Result<Vec<IssueRef>> {
+        self.call(
+            "list_assigned_issues",
+            &[&username],
+            self.inner.list_assigned_issues(username),
+        )
+        .await
+    }
+    async fn list_mentions(&self, username: &str) -> Result<Vec<IssueRef>> {
+        self.call(
+            "list_mentions",
+            &[&username],
+            self.inner.list_mentions(username),
+        )
+        .await
+    }
+    async fn search_issues(&self, query: &str, page: u32) -> Result<PagedResult<IssueRef>> {
+        self.call(
+            "search_issues",
+            &[&query, &page],
+            self.inner.search_issues(query, page),
+        )
+        .await
+    }
+    async fn list_action_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        page: u32,
+    ) -> Result<PagedResult<ActionRun>> {
+        self.call(
+            "list_action_runs",
+            &[&owner, &repo, &page],
+            self.inner.list_action_runs(owner, repo, page),
+        )
+        .await
+    }
+    async fn list_artifacts(&self, owner: &str, repo: &str, run_id: u64) -> Result<Vec<Artifact>> {
+        self.call(
+            "list_artifacts",
+            &[&owner, &repo, &run_id],
+            self.inner.list_artifacts(owner, repo, run_id),
+        )
+        .await
+    }
+    /// Streamed, so never saved; replays can't download
+    async fn download_artifact(&self, artifact: &Artifact) -> Result<reqwest::Response> {
+        match self.mode {
+            Mode::Record => self.inner.download_artifact(artifact).await,
+            Mode::Replay => Err(GritError::Api("Artifact downloads aren't recorded".into())),
+        }
+    }
+    async fn get_check_status(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<ChecksStatus> {
+        self.call(
+            "get_check_status",
+            &[&owner, &repo, &pr_number],
+            self.inner.get_check_status(owner, repo, pr_number),
+        )
+        .await
+    }
+    async fn get_pr_checks(&self, owner: &str, repo: &str, number: u64) -> Result<PrChecks> {
+        self.call(
+            "get_pr_checks",
+            &[&owner, &repo, &number],
+            self.inner.get_pr_checks(owner, repo, number),
+        )
+        .await
+    }
+    async fn get_repo_stats(&self, owner: &str, repo: &str) -> Result<RepoStats> {
+        self.call(
+            "get_repo_stats",
+            &[&owner, &repo],
+            self.inner.get_repo_stats(owner, repo),
+        )
+        .await
+    }
+    async fn get_commit_checks(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<CommitCheck>> {
+        self.call(
+            "get_commit_checks",
+            &[&owner, &repo, &sha],
+            self.inner.get_commit_checks(owner, repo, sha),
+        )
+        .await
+    }
+    async fn list_viewed_files(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<String>> {
+        self.call(
+            "list_viewed_files",
+            &[&owner, &repo, &number],
+            self.inner.list_viewed_files(owner, repo, number),
+        )
+        .await
+    }
+    async fn list_pr_commits(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<Commit>> {
+        self.call(
+            "list_pr_commits",
+            &[&owner, &repo, &number],
+            self.inner.list_pr_commits(owner, repo, number),
+        )
+        .await
+    }
+    async fn list_pr_files(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<CommitFile>> {
+        self.call(
+            "list_pr_files",
+            &[&owner, &repo, &number],
+            self.inner.list_pr_files(owner, repo, number),
+        )
+        .await
+    }
+    async fn list_pr_comments(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<Comment>> {
+        self.call(
+            "list_pr_comments",
+            &[&owner, &repo, &number],
+            self.inner.list_pr_comments(owner, repo, number),
+        )
+        .await
+    }
+    async fn list_reviews(&self, owner: &str, repo: &str, number: u64) -> Result<PrReviews> {
+        self.call(
+            "list_reviews",
+            &[&owner, &repo, &number],
+            self.inner.list_reviews(owner, repo, number),
+        )
+        .await
+    }
+    async fn list_review_threads(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<ReviewThread>> {
+        self.call(
+            "list_review_threads",
+            &[&owner, &repo, &number],
+            self.inner.list_review_threads(owner, repo, number),
+        )
+        .await
+    }
+    async fn set_thread_resolved(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        thread_id: &str,
+        resolved: bool,
+    ) -> Result<()> {
+        self.call(
+            "set_thread_resolved",
+            &[&owner, &repo, &number, &thread_id],
+            self.inner
+                .set_thread_resolved(owner, repo, number, thread_id, resolved),
+        )
+        .await
+    }
+    async fn list_milestones(&self, owner: &str, repo: &str) -> Result<Vec<Milestone>> {
+        self.call(
+            "list_milestones",
+            &[&owner, &repo],
+            self.inner.list_milestones(owner, repo),
+        )
+        .await
+    }
+    async fn set_milestone(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        milestone: Option<u64>,
+    ) -> Result<()> {
+        self.call(
+            "set_milestone",
+            &[&owner, &repo, &number],
+            self.inner.set_milestone(owner, repo, number, milestone),
+        )
+        .await
+    }
+    async fn list_labels(&self, owner: &str, repo: &str) -> Result<Vec<Label>> {
+        self.call(
+            "list_labels",
+            &[&owner, &repo],
+            self.inner.list_labels(owner, repo),
+        )
+        .await
+    }
+    async fn add_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        kind: &str,
+        number: u64,
+        labels: &[String],
+    ) -> Result<()> {
+        self.call(
+            "add_labels",
+            &[&owner, &repo, &kind, &number],
+            self.inner.add_labels(owner, repo, kind, number, labels),
+        )
+        .await
+    }
+    async fn remove_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        kind: &str,
+        number: u64,
+        labels: &[String],
+    ) -> Result<()> {
+        self.call(
+            "remove_labels",
+            &[&owner, &repo, &kind, &number],
+            self.inner.remove_labels(owner, repo, kind, number, labels),
+        )
+        .await
+    }
+    async fn list_issue_templates(&self, owner: &str, repo: &str) -> Result<Vec<IssueTemplate>> {
+        self.call(
+            "list_issue_templates",
+            &[&owner, &repo],
+            self.inner.list_issue_templates(owner, repo),
+        )
+        .await
+    }
+    async fn create_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        fields: &IssueFields,
+        body: &str,
+    ) -> Result<u64> {
+        self.call(
+            "create_issue",
+            &[&owner, &repo],
+            self.inner.create_issue(owner, repo, fields, body),
+        )
+        .await
+    }
+    async fn update_fields(
+        &self,
+        owner: &str,
+        repo: &str,
+        kind: &str,
+        number: u64,
+        fields: &IssueFields,
+    ) -> Result<()> {
+        self.call(
+            "update_fields",
+            &[&owner, &repo, &kind, &number],
+            self.inner.update_fields(owner, repo, kind, number, fields),
+        )
+        .await
+    }
+    async fn update_pr(&self, owner: &str, repo: &str, number: u64, body: &str) -> Result<()> {
+        self.call(
+            "update_pr",
+            &[&owner, &repo, &number],
+            self.inner.update_pr(owner, repo, number, body),
+        )
+        .await
+    }
+    async fn submit_review(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        event: &str,
+        body: &str,
+        comments: &[ReviewComment],
+    ) -> Result<()> {
+        self.call(
+            "submit_review",
+            &[&owner, &repo, &number],
+            self.inner
+                .submit_review(owner, repo, number, event, body, comments),
+        )
+        .await
+    }
+    async fn locate_repo(&self, owner: &str, repo: &str) -> Result<(String, String)> {
+        self.call(
+            "locate_repo",
+            &[&owner, &repo],
+            self.inner.locate_repo(owner, repo),
+        )
+        .await
+    }
+    async fn get_repo_meta(&self, owner: &str, repo: &str) -> Result<RepoMeta> {
+        self.call(
+            "get_repo_meta",
+            &[&owner, &repo],
+            self.inner.get_repo_meta(owner, repo),
+        )
+        .await
+    }
+    async fn update_repo_meta(&self, owner: &str, repo: &str, meta: &RepoMeta) -> Result<()> {
+        self.call(
+            "update_repo_meta",
+            &[&owner, &repo],
+            self.inner.update_repo_meta(owner, repo, meta),
+        )
+        .await
+    }
+    async fn fork_repo(&self, owner: &str, repo: &str) -> Result<Repository> {
+        self.call(
+            "fork_repo",
+            &[&owner, &repo],
+            self.inner.fork_repo(owner, repo),
+        )
+        .await
+    }
+    async fn get_fork_status(&self, owner: &str, repo: &str) -> Result<Option<ForkStatus>> {
+        self.call(
+            "get_fork_status",
+            &[&owner, &repo],
+            self.inner.get_fork_status(owner, repo),
+        )
+        .await
+    }
+    async fn sync_fork(&self, owner: &str, repo: &str, branch: &str) -> Result<()> {
+        self.call(
+            "sync_fork",
+            &[&owner, &repo, &branch],
+            self.inner.sync_fork(owner, repo, branch),
+        )
+        .await
+    }
+    async fn is_starred(&self, owner: &str, repo: &str) -> Result<bool> {
+        self.call(
+            "is_starred",
+            &[&owner, &repo],
+            self.inner.is_starred(owner, repo),
+        )
+        .await
+    }
+    async fn set_star(&self, owner: &str, repo: &str, starred: bool) -> Result<()> {
+        self.call(
+            "set_star",
+            &[&owner, &repo, &starred],
+            self.inner.set_star(owner, repo, starred),
+        )
+        .await
+    }
+    async fn set_subscription(
+        &self,
+        owner: &str,
+        repo: &str,
+        subscription: Subscription,
+    ) -> Result<()> {
+        self.call(
+            "set_subscription",
+            &[&owner, &repo, &format!("{:?}", subscription)],
+            self.inner.set_subscription(owner, repo, subscription),
+        )
+        .await
+    }
+    async fn reviewer_load(&self, owner: &str, repo: &str) -> Result<Vec<ReviewerLoad>> {
+        self.call(
+            "reviewer_load",
+            &[&owner, &repo],
+            self.inner.reviewer_load(owner, repo),
+        )
+        .await
+    }
+    async fn request_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        reviewers: &[String],
+    ) -> Result<()> {
+        self.call(
+            "request_reviewers",
+            &[&owner, &repo, &number],
+            self.inner.request_reviewers(owner, repo, number, reviewers),
+        )
+        .await
+    }
+    async fn revert_pr(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr: &PullRequest,
+        commits: &[Commit],
+    ) -> Result<u64> {
+        self.call(
+            "revert_pr",
+            &[&owner, &repo, &pr.number],
+            self.inner.revert_pr(owner, repo, pr, commits),
+        )
+        .await
+    }
+    async fn get_issue_body(&self, owner: &str, repo: &str, number: u64) -> Result<Option<String>> {
+        self.call(
+            "get_issue_body",
+            &[&owner, &repo, &number],
+            self.inner.get_issue_body(owner, repo, number),
+        )
+        .await
+    }
+    async fn list_orgs(&self) -> Result<Vec<String>> {
+        self.call("list_orgs", &[], self.inner.list_orgs()).await
+    }
+    async fn list_org_repos(&self, org: &str, page: u32) -> Result<PagedResult<Repository>> {
+        self.call(
+            "list_org_repos",
+            &[&org, &page],
+            self.inner.list_org_repos(org, page),
+        )
+        .await
+    }
+    async fn add_reaction(
+        &self,
+        owner: &str,
+        repo: &str,
+        target: ReactionTarget,
+        reaction: Reaction,
+    ) -> Result<()> {
+        self.call(
+            "add_reaction",
+            &[&owner, &repo, &format!("{:?}", target)],
+            self.inner.add_reaction(owner, repo, target, reaction),
+        )
+        .await
+    }
+    async fn comment_on_commit(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+        body: &str,
+    ) -> Result<()> {
+        self.call(
+            "comment_on_commit",
+            &[&owner, &repo, &sha],
+            self.inner.comment_on_commit(owner, repo, sha, body),
+        )
+        .await
+    }
+    async fn search_commits(&self, query: &str, owners: &[String]) -> Result<Vec<CommitHit>> {
+        self.call(
+            "search_commits",
+            &[&query],
+            self.inner.search_commits(query, owners),
+        )
+        .await
+    }
+    async fn token_scopes(&self) -> Result<Option<Vec<String>>> {
+        self.call("token_scopes", &[], self.inner.token_scopes())
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockForge;
+
+    fn dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("grit-tape-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn file_names_are_flat() {
+        assert_eq!(
+            file_name("get_pr", &[&"group/sub", &"rocket", &42]),
+            "get_pr-group_sub-rocket-42.json"
+        );
+        assert_eq!(file_name("list_orgs", &[]), "list_orgs.json");
+        assert_eq!(
+            file_name("search_commits", &[&"fix ../x"]),
+            "search_commits-fix_.._x.json"
+        );
+    }
+
+    #[tokio::test]
+    async fn replays_what_was_recorded() {
+        let dir = dir("replay");
+        let recorder = Tape::new(Arc::new(MockForge::default()), &dir, Mode::Record).unwrap();
+        let pr = recorder.get_pr("acme", "rocket", 42).await.unwrap();
+        let missing = recorder.get_pr("acme", "rocket", 7).await.unwrap_err();
+
+        // Nothing left in the forge, so answers can only come from the files
+        let empty = MockForge {
+            pulls: vec![],
+            ..MockForge::default()
+        };
+        let player = Tape::new(Arc::new(empty), &dir, Mode::Replay).unwrap();
+        let replayed = player.get_pr("acme", "rocket", 42).await.unwrap();
+        assert_eq!(replayed.title, pr.title);
+        assert_eq!(replayed.head_sha, pr.head_sha);
+        assert_eq!(
+            player
+                .get_pr("acme", "rocket", 7)
+                .await
+                .unwrap_err()
+                .to_string(),
+            missing.to_string()
+        );
+        assert!(player
+            .list_issues("acme", "rocket", None, 1)
+            .await
+            .map(|_| ())
+            .unwrap_err()
+            .to_string()
+            .contains("list_issues-acme-rocket-1.json isn't in the recording"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn replay_needs_a_recording() {
+        let dir = dir("none");
+        assert!(Tape::new(Arc::new(MockForge::default()), &dir, Mode::Replay).is_err());
+    }
+}
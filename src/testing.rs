@@ -0,0 +1,542 @@
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::error::{GritError, Result};
+use crate::forge::Forge;
+use crate::types::{
+    ChecksStatus, Comment, Commit, CommitDetail, CommitFile, CommitStats, Issue, IssueRef,
+    IssueState, Label, MergeableState, Milestone, MyPr, PageInfo, PagedResult, PrChecks, PrFilter,
+    PrReviews, PrState, PrStats, PrSummary, PullRequest, Repository, Review, ReviewRequest,
+    ReviewState, SearchQuery,
+};
+
+/// A forge answering from canned fixtures, so the app and its screens can be
+/// driven in tests without a network. Fields are public for tests that need
+/// other data; writes succeed and change nothing.
+#[derive(Debug, Clone)]
+pub struct MockForge {
+    pub user: String,
+    pub repos: Vec<Repository>,
+    pub review_requests: Vec<ReviewRequest>,
+    pub my_prs: Vec<MyPr>,
+    pub prs: Vec<PrSummary>,
+    /// Full PRs by number; `prs` lists them
+    pub pulls: Vec<PullRequest>,
+    pub issues: Vec<Issue>,
+    pub commits: Vec<Commit>,
+    pub comments: Vec<Comment>,
+    pub reviews: PrReviews,
+    pub checks: PrChecks,
+    pub files: Vec<CommitFile>,
+    pub labels: Vec<Label>,
+    /// Repos known by an old name, and where they live now
+    pub moved: Vec<((String, String), (String, String))>,
+    /// PR tab filters the forge offers
+    pub pr_filters: &'static [PrFilter],
+}
+
+/// Fixed so absolute dates on screen don't move between runs
+pub fn at(day: u32, hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 5, day, hour, 0, 0).unwrap()
+}
+
+fn label(name: &str, color: &str) -> Label {
+    Label {
+        name: name.to_string(),
+        color: Some(color.to_string()),
+    }
+}
+
+fn summary(pr: &PullRequest) -> PrSummary {
+    PrSummary {
+        number: pr.number,
+        title: pr.title.clone(),
+        state: pr.state,
+        author: pr.author.clone(),
+        updated_at: pr.updated_at,
+        milestone: pr.milestone.clone(),
+    }
+}
+
+fn pull(number: u64, title: &str, author: &str, state: PrState, day: u32) -> PullRequest {
+    PullRequest {
+        number,
+        title: title.to_string(),
+        body: None,
+        state,
+        author: author.to_string(),
+        head_branch: format!("{}/pr-{}", author, number),
+        base_branch: "main".to_string(),
+        head_sha: format!("{:0<40}", number),
+        from_fork: false,
+        auto_merge: false,
+        stats: PrStats {
+            additions: 10,
+            deletions: 2,
+            changed_files: 1,
+            commits: 1,
+            comments: 0,
+        },
+        created_at: at(day, 9),
+        updated_at: at(day, 15),
+        merged_at: (state == PrState::Merged).then(|| at(day, 16)),
+        closed_at: (state != PrState::Open).then(|| at(day, 16)),
+        mergeable_state: MergeableState::Clean,
+        milestone: None,
+        labels: Vec::new(),
+        assignees: Vec::new(),
+    }
+}
+
+impl Default for MockForge {
+    fn default() -> Self {
+        let ignition = PullRequest {
+            body: Some(
+                "Splits ignition into three stages with a hold between each.\n\n\
+                 Fixes #12, cc @grace.\n\n\
+                 - [x] Split the sequence\n\
+                 - [ ] Tune the holds"
+                    .to_string(),
+            ),
+            stats: PrStats {
+                additions: 48,
+                deletions: 9,
+                changed_files: 2,
+                commits: 2,
+                comments: 2,
+            },
+            mergeable_state: MergeableState::BlockedByReviews,
+            milestone: Some("v1.0".to_string()),
+            labels: vec![label("enhancement", "a2eeef")],
+            assignees: vec!["ada".to_string()],
+            ..pull(42, "Add staged ignition sequence", "ada", PrState::Open, 20)
+        };
+        let pulls = vec![
+            ignition,
+            pull(40, "Retry telemetry uploads", "octo", PrState::Open, 18),
+            pull(38, "Fix fuel gauge rounding", "octo", PrState::Open, 15),
+            pull(
+                35,
+                "Drop the legacy launch API",
+                "grace",
+                PrState::Merged,
+                10,
+            ),
+        ];
+        let rocket = |name: &str| ("acme".to_string(), name.to_string());
+        let (owner, repo) = rocket("rocket");
+
+        Self {
+            user: "octo".to_string(),
+            repos: vec![
+                Repository {
+                    owner: owner.clone(),
+                    name: repo.clone(),
+                    description: Some("Rockets as a service".to_string()),
+                    url: "https://forge.test/acme/rocket".to_string(),
+                    stars: 1280,
+                    updated_at: at(20, 15),
+                },
+                Repository {
+                    owner: owner.clone(),
+                    name: "launchpad".to_string(),
+                    description: None,
+                    url: "https://forge.test/acme/launchpad".to_string(),
+                    stars: 12,
+                    updated_at: at(19, 8),
+                },
+            ],
+            review_requests: vec![
+                ReviewRequest {
+                    repo_owner: owner.clone(),
+                    repo_name: repo.clone(),
+                    pr_number: 42,
+                    pr_title: "Add staged ignition sequence".to_string(),
+                    author: "ada".to_string(),
+                    updated_at: at(20, 15),
+                },
+                ReviewRequest {
+                    repo_owner: owner.clone(),
+                    repo_name: "launchpad".to_string(),
+                    pr_number: 7,
+                    pr_title: "Bump serde to 1.0.200".to_string(),
+                    author: "dependabot[bot]".to_string(),
+                    updated_at: at(19, 8),
+                },
+            ],
+            my_prs: vec![
+                MyPr {
+                    repo_owner: owner.clone(),
+                    repo_name: repo.clone(),
+                    number: 40,
+                    title: "Retry telemetry uploads".to_string(),
+                    state: PrState::Open,
+                    checks_status: ChecksStatus::Success,
+                    mergeable: MergeableState::Clean,
+                    updated_at: at(18, 15),
+                },
+                MyPr {
+                    repo_owner: owner,
+                    repo_name: repo,
+                    number: 38,
+                    title: "Fix fuel gauge rounding".to_string(),
+                    state: PrState::Open,
+                    checks_status: ChecksStatus::Failure,
+                    mergeable: MergeableState::BlockedByChecks,
+                    updated_at: at(15, 15),
+                },
+            ],
+            prs: pulls.iter().map(summary).collect(),
+            pulls,
+            issues: vec![
+                Issue {
+                    number: 12,
+                    title: "Ignition fires all stages at once".to_string(),
+                    state: IssueState::Open,
+                    author: "grace".to_string(),
+                    labels: vec![label("bug", "d73a4a")],
+                    comments: 3,
+                    created_at: at(2, 10),
+                    updated_at: at(20, 15),
+                    milestone: Some("v1.0".to_string()),
+                    assignees: vec!["ada".to_string()],
+                },
+                Issue {
+                    number: 9,
+                    title: "Document the countdown API".to_string(),
+                    state: IssueState::Open,
+                    author: "octo".to_string(),
+                    labels: vec![label("docs", "0075ca")],
+                    comments: 0,
+                    created_at: at(1, 10),
+                    updated_at: at(1, 10),
+                    milestone: None,
+                    assignees: Vec::new(),
+                },
+            ],
+            commits: vec![
+                Commit {
+                    sha: "a1b2c3d4e5f60718293a4b5c6d7e8f9012345678".to_string(),
+                    message: "Hold between ignition stages".to_string(),
+                    author: "ada".to_string(),
+                    date: at(20, 14),
+                },
+                Commit {
+                    sha: "0f1e2d3c4b5a69788796a5b4c3d2e1f0abcdef12".to_string(),
+                    message: "Split ignition into stages".to_string(),
+                    author: "ada".to_string(),
+                    date: at(20, 9),
+                },
+            ],
+            comments: vec![
+                Comment {
+                    id: 1,
+                    author: "grace".to_string(),
+                    body: "How long is the hold between stages?".to_string(),
+                    created_at: at(20, 11),
+                },
+                Comment {
+                    id: 2,
+                    author: "ada".to_string(),
+                    body: "Two seconds, configurable per launch.".to_string(),
+                    created_at: at(20, 12),
+                },
+            ],
+            reviews: PrReviews {
+                requested: vec!["octo".to_string()],
+                reviews: vec![Review {
+                    reviewer: "grace".to_string(),
+                    state: ReviewState::Approved,
+                    submitted_at: Some(at(20, 13)),
+                }],
+                required_approvals: Some(2),
+            },
+            checks: PrChecks {
+                passed: 3,
+                failed: 0,
+                pending: 1,
+                neutral: 0,
+            },
+            files: vec![
+                CommitFile {
+                    filename: "src/ignition.rs".to_string(),
+                    status: "modified".to_string(),
+                    additions: 46,
+                    deletions: 9,
+                    patch: Some(
+                        "@@ -1,4 +1,6 @@\n-pub fn ignite() {\n-    fire_all();\n+pub fn ignite(stages: &[Stage]) {\n+    for stage in stages {\n+        stage.fire();\n+        hold();\n+    }\n }"
+                            .to_string(),
+                    ),
+                },
+                CommitFile {
+                    filename: "README.md".to_string(),
+                    status: "modified".to_string(),
+                    additions: 2,
+                    deletions: 0,
+                    patch: Some("@@ -10,2 +10,4 @@\n ## Launching\n+\n+Ignition runs in stages.".to_string()),
+                },
+            ],
+            labels: vec![label("bug", "d73a4a"), label("enhancement", "a2eeef")],
+            moved: Vec::new(),
+            pr_filters: &PrFilter::ALL,
+        }
+    }
+}
+
+fn page<T: Clone>(items: &[T], page: u32) -> PagedResult<T> {
+    PagedResult {
+        items: if page <= 1 {
+            items.to_vec()
+        } else {
+            Vec::new()
+        },
+        page_info: PageInfo::default(),
+    }
+}
+
+#[async_trait]
+impl Forge for MockForge {
+    fn name(&self) -> &str {
+        "mock"
+    }
+    fn pr_filters(&self) -> &'static [PrFilter] {
+        self.pr_filters
+    }
+
+    fn web_url(&self, owner: &str, repo: &str, kind: &str, id: &str) -> String {
+        format!("https://forge.test/{}/{}/{}/{}", owner, repo, kind, id)
+    }
+
+    async fn get_current_user(&self) -> Result<String> {
+        Ok(self.user.clone())
+    }
+
+    async fn list_repos(&self, page_number: u32) -> Result<PagedResult<Repository>> {
+        Ok(page(&self.repos, page_number))
+    }
+
+    async fn locate_repo(&self, owner: &str, repo: &str) -> Result<(String, String)> {
+        let name = (owner.to_string(), repo.to_string());
+        if let Some((_, to)) = self.moved.iter().find(|(from, _)| *from == name) {
+            return Ok(to.clone());
+        }
+        self.repos
+            .iter()
+            .any(|r| r.owner == owner && r.name == repo)
+            .then_some(name)
+            .ok_or_else(|| GritError::NotFound(format!("{}/{}", owner, repo)))
+    }
+
+    async fn list_prs(
+        &self,
+        owner: &str,
+        repo: &str,
+        filter: PrFilter,
+        milestone: Option<&Milestone>,
+        page_number: u32,
+    ) -> Result<PagedResult<PrSummary>> {
+        // Fixtures don't say who's assigned, so that filter finds nothing
+        let prs: Vec<PrSummary> = self
+            .prs
+            .iter()
+            .filter(|pr| milestone.is_none_or(|m| pr.milestone.as_ref() == Some(&m.title)))
+            .filter(|pr| match filter {
+                PrFilter::All => true,
+                PrFilter::Authored => pr.author == self.user,
+                PrFilter::Assigned => false,
+                PrFilter::ReviewRequested => self.review_requests.iter().any(|r| {
+                    (r.repo_owner.as_str(), r.repo_name.as_str(), r.pr_number)
+                        == (owner, repo, pr.number)
+                }),
+            })
+            .cloned()
+            .collect();
+        Ok(page(&prs, page_number))
+    }
+
+    async fn get_pr(&self, _owner: &str, _repo: &str, number: u64) -> Result<PullRequest> {
+        self.pulls
+            .iter()
+            .find(|pr| pr.number == number)
+            .cloned()
+            .ok_or_else(|| GritError::NotFound(format!("PR #{}", number)))
+    }
+
+    async fn list_issues(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        milestone: Option<&Milestone>,
+        page_number: u32,
+    ) -> Result<PagedResult<Issue>> {
+        let issues: Vec<Issue> = self
+            .issues
+            .iter()
+            .filter(|i| milestone.is_none_or(|m| i.milestone.as_ref() == Some(&m.title)))
+            .cloned()
+            .collect();
+        Ok(page(&issues, page_number))
+    }
+
+    async fn get_issue(&self, _owner: &str, _repo: &str, number: u64) -> Result<Issue> {
+        self.issues
+            .iter()
+            .find(|issue| issue.number == number)
+            .cloned()
+            .ok_or_else(|| GritError::NotFound(format!("Issue #{}", number)))
+    }
+
+    async fn list_commits(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        page_number: u32,
+    ) -> Result<PagedResult<Commit>> {
+        Ok(page(&self.commits, page_number))
+    }
+
+    async fn get_commit(&self, _owner: &str, _repo: &str, sha: &str) -> Result<CommitDetail> {
+        let commit = self
+            .commits
+            .iter()
+            .find(|c| c.sha.starts_with(sha))
+            .ok_or_else(|| GritError::NotFound(format!("Commit {}", sha)))?;
+        Ok(CommitDetail {
+            sha: commit.sha.clone(),
+            message: commit.message.clone(),
+            author: commit.author.clone(),
+            date: commit.date,
+            stats: CommitStats {
+                additions: self.files.iter().map(|f| f.additions).sum(),
+                deletions: self.files.iter().map(|f| f.deletions).sum(),
+                total: self.files.iter().map(|f| f.additions + f.deletions).sum(),
+            },
+            files: self.files.clone(),
+        })
+    }
+
+    async fn get_pr_diff(&self, _owner: &str, _repo: &str, _number: u64) -> Result<String> {
+        Ok(self
+            .files
+            .iter()
+            .filter_map(|f| f.patch.as_deref())
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    async fn merge_pr(&self, _owner: &str, _repo: &str, _number: u64, _method: &str) -> Result<()> {
+        Ok(())
+    }
+
+    async fn enable_auto_merge(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+        _method: &str,
+        _delete_branch: bool,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_branch(&self, _owner: &str, _repo: &str, _branch: &str) -> Result<()> {
+        Ok(())
+    }
+
+    async fn close_pr(&self, _owner: &str, _repo: &str, _number: u64) -> Result<()> {
+        Ok(())
+    }
+
+    async fn close_issue(&self, _owner: &str, _repo: &str, _number: u64) -> Result<()> {
+        Ok(())
+    }
+
+    async fn comment(&self, _owner: &str, _repo: &str, _number: u64, _body: &str) -> Result<()> {
+        Ok(())
+    }
+
+    async fn list_review_requests(&self, _username: &str) -> Result<Vec<ReviewRequest>> {
+        Ok(self.review_requests.clone())
+    }
+
+    async fn list_my_prs(&self, _username: &str) -> Result<Vec<MyPr>> {
+        Ok(self.my_prs.clone())
+    }
+
+    /// `prs` and `issues`, all of acme/rocket, whose titles hold the query's
+    /// words
+    async fn search_issues(&self, query: &str, page_number: u32) -> Result<PagedResult<IssueRef>> {
+        let query = SearchQuery::parse(query).map_err(GritError::Api)?;
+        let text = query.text.to_lowercase();
+        let hit = |number: u64, title: &str, author: &str, is_pr: bool, updated_at| IssueRef {
+            repo_owner: "acme".to_string(),
+            repo_name: "rocket".to_string(),
+            number,
+            title: title.to_string(),
+            author: author.to_string(),
+            is_pr,
+            updated_at,
+        };
+        let hits: Vec<IssueRef> = self
+            .prs
+            .iter()
+            .map(|pr| hit(pr.number, &pr.title, &pr.author, true, pr.updated_at))
+            .chain(
+                self.issues
+                    .iter()
+                    .map(|i| hit(i.number, &i.title, &i.author, false, i.updated_at)),
+            )
+            .filter(|h| query.is_pr.is_none_or(|is_pr| h.is_pr == is_pr))
+            .filter(|h| h.title.to_lowercase().contains(&text))
+            .collect();
+        Ok(page(&hits, page_number))
+    }
+
+    async fn get_pr_checks(&self, _owner: &str, _repo: &str, _number: u64) -> Result<PrChecks> {
+        Ok(self.checks)
+    }
+
+    async fn list_viewed_files(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+    ) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    async fn list_pr_commits(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+    ) -> Result<Vec<Commit>> {
+        Ok(self.commits.iter().rev().cloned().collect())
+    }
+
+    async fn list_pr_files(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+    ) -> Result<Vec<CommitFile>> {
+        Ok(self.files.clone())
+    }
+
+    async fn list_pr_comments(
+        &self,
+        _owner: &str,
+        _repo: &str,
+        _number: u64,
+    ) -> Result<Vec<Comment>> {
+        Ok(self.comments.clone())
+    }
+
+    async fn list_reviews(&self, _owner: &str, _repo: &str, _number: u64) -> Result<PrReviews> {
+        Ok(self.reviews.clone())
+    }
+
+    async fn list_labels(&self, _owner: &str, _repo: &str) -> Result<Vec<Label>> {
+        Ok(self.labels.clone())
+    }
+}
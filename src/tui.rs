@@ -2,7 +2,7 @@ use std::io::{self, Stdout};
 use std::time::Duration;
 
 use crossterm::{
-    event::{self, Event as CrosstermEvent, EventStream},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event as CrosstermEvent, EventStream},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -17,14 +17,18 @@ use crate::event::Event;
 
 pub type Tui = Terminal<CrosstermBackend<Stdout>>;
 
-pub fn init() -> io::Result<Tui> {
+/// Enter the alternate screen; `mouse` turns on mouse capture
+pub fn init(mouse: bool) -> io::Result<Tui> {
     execute!(io::stdout(), EnterAlternateScreen)?;
+    if mouse {
+        execute!(io::stdout(), EnableMouseCapture)?;
+    }
     enable_raw_mode()?;
     Terminal::new(CrosstermBackend::new(io::stdout()))
 }
 
 pub fn restore() -> io::Result<()> {
-    execute!(io::stdout(), LeaveAlternateScreen)?;
+    execute!(io::stdout(), DisableMouseCapture, LeaveAlternateScreen)?;
     disable_raw_mode()
 }
 
@@ -63,10 +67,14 @@ impl EventHandler {
                         tx.send(Event::Render).ok();
                     }
                     Some(Ok(evt)) = reader.next() => {
-                        if let CrosstermEvent::Key(key) = evt {
-                            if key.kind == event::KeyEventKind::Press {
+                        match evt {
+                            CrosstermEvent::Key(key) if key.kind == event::KeyEventKind::Press => {
                                 tx.send(Event::Key(key)).ok();
                             }
+                            CrosstermEvent::Mouse(mouse) => {
+                                tx.send(Event::Mouse(mouse)).ok();
+                            }
+                            _ => {}
                         }
                     }
                 }
@@ -2,12 +2,39 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+#[derive(Serialize, Deserialize)]
 pub struct PagedResult<T> {
     pub items: Vec<T>,
+    pub page_info: PageInfo,
+}
+
+/// Totals a forge reported alongside a page, when it knows them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageInfo {
     pub total_count: Option<u64>,
+    pub total_pages: Option<u32>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl PageInfo {
+    /// Number of pages, counted from the total if the forge didn't say
+    pub fn pages(&self, page_size: usize) -> Option<u32> {
+        self.total_pages.or_else(|| {
+            self.total_count
+                .map(|total| total.div_ceil(page_size as u64) as u32)
+        })
+    }
+
+    /// Whether there's a page after `page`. Without totals, a full page suggests so.
+    pub fn has_more(&self, page: u32, len: usize, page_size: usize) -> bool {
+        match self.pages(page_size) {
+            Some(pages) => page < pages,
+            None => len == page_size,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum MergeMethod {
     Merge,
     Squash,
@@ -61,11 +88,331 @@ impl fmt::Display for ReviewEvent {
     }
 }
 
+/// Emoji reactions every forge supports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reaction {
+    ThumbsUp,
+    ThumbsDown,
+    Laugh,
+    Confused,
+    Heart,
+    Hooray,
+    Rocket,
+    Eyes,
+}
+
+impl Reaction {
+    pub const ALL: [Reaction; 8] = [
+        Reaction::ThumbsUp,
+        Reaction::ThumbsDown,
+        Reaction::Laugh,
+        Reaction::Confused,
+        Reaction::Heart,
+        Reaction::Hooray,
+        Reaction::Rocket,
+        Reaction::Eyes,
+    ];
+
+    /// Reaction `content` on GitHub and Gitea
+    pub fn as_api_str(&self) -> &'static str {
+        match self {
+            Reaction::ThumbsUp => "+1",
+            Reaction::ThumbsDown => "-1",
+            Reaction::Laugh => "laugh",
+            Reaction::Confused => "confused",
+            Reaction::Heart => "heart",
+            Reaction::Hooray => "hooray",
+            Reaction::Rocket => "rocket",
+            Reaction::Eyes => "eyes",
+        }
+    }
+
+    /// GitLab award emoji name
+    pub fn gitlab_name(&self) -> &'static str {
+        match self {
+            Reaction::ThumbsUp => "thumbsup",
+            Reaction::ThumbsDown => "thumbsdown",
+            Reaction::Laugh => "laughing",
+            Reaction::Confused => "confused",
+            Reaction::Heart => "heart",
+            Reaction::Hooray => "tada",
+            Reaction::Rocket => "rocket",
+            Reaction::Eyes => "eyes",
+        }
+    }
+}
+
+impl fmt::Display for Reaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reaction::ThumbsUp => write!(f, "👍 +1"),
+            Reaction::ThumbsDown => write!(f, "👎 -1"),
+            Reaction::Laugh => write!(f, "😄 laugh"),
+            Reaction::Confused => write!(f, "😕 confused"),
+            Reaction::Heart => write!(f, "❤️ heart"),
+            Reaction::Hooray => write!(f, "🎉 hooray"),
+            Reaction::Rocket => write!(f, "🚀 rocket"),
+            Reaction::Eyes => write!(f, "👀 eyes"),
+        }
+    }
+}
+
+/// What a reaction goes on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReactionTarget {
+    /// The PR itself
+    Pr(u64),
+    /// A conversation comment on PR `pr`
+    Comment { pr: u64, id: u64 },
+    /// A comment in one of PR `pr`'s review threads
+    ReviewComment { pr: u64, id: u64 },
+}
+
+/// Old and new line numbers of one patch line: context lines have both,
+/// added lines only `new`, removed lines only `old`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffLine {
+    pub old: Option<u64>,
+    pub new: Option<u64>,
+}
+
+/// One row of a side-by-side diff, as indexes into the patch's lines. Context,
+/// hunk headers and markers have the same line on both sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitRow {
+    pub old: Option<usize>,
+    pub new: Option<usize>,
+}
+
+impl SplitRow {
+    /// Line the cursor lands on for this row, the new side when there is one
+    pub fn line(&self) -> usize {
+        self.new.or(self.old).unwrap_or(0)
+    }
+
+    pub fn contains(&self, line: usize) -> bool {
+        self.old == Some(line) || self.new == Some(line)
+    }
+}
+
+/// Pair a patch's lines into side-by-side rows: each run of removals lines
+/// up with the additions right after it, the longer side padded with gaps
+pub fn split_rows(patch: &str) -> Vec<SplitRow> {
+    fn flush(rows: &mut Vec<SplitRow>, removed: &mut Vec<usize>, added: &mut Vec<usize>) {
+        for k in 0..removed.len().max(added.len()) {
+            rows.push(SplitRow {
+                old: removed.get(k).copied(),
+                new: added.get(k).copied(),
+            });
+        }
+        removed.clear();
+        added.clear();
+    }
+
+    let mut rows = Vec::new();
+    let (mut removed, mut added) = (Vec::new(), Vec::new());
+    for (i, line) in patch.lines().enumerate() {
+        match line.chars().next() {
+            Some('-') => {
+                if !added.is_empty() {
+                    flush(&mut rows, &mut removed, &mut added);
+                }
+                removed.push(i);
+            }
+            Some('+') => added.push(i),
+            _ => {
+                flush(&mut rows, &mut removed, &mut added);
+                rows.push(SplitRow {
+                    old: Some(i),
+                    new: Some(i),
+                });
+            }
+        }
+    }
+    flush(&mut rows, &mut removed, &mut added);
+    rows
+}
+
+/// Line numbers for every line of a `patch`; hunk headers and
+/// "\ No newline at end of file" markers get `None`
+pub fn patch_line_numbers(patch: &str) -> Vec<Option<DiffLine>> {
+    let (mut old, mut new) = (0, 0);
+    patch
+        .lines()
+        .map(|line| {
+            if let Some(header) = line.strip_prefix("@@ ") {
+                // "@@ -12,7 +12,8 @@ fn context"
+                let mut ranges = header.split_whitespace();
+                let start = |r: Option<&str>| {
+                    r.and_then(|r| r[1..].split(',').next()?.parse::<u64>().ok())
+                        .unwrap_or(1)
+                };
+                old = start(ranges.next());
+                new = start(ranges.next());
+                return None;
+            }
+            let numbers = match line.chars().next() {
+                Some('+') => DiffLine {
+                    old: None,
+                    new: Some(new),
+                },
+                Some('-') => DiffLine {
+                    old: Some(old),
+                    new: None,
+                },
+                Some('\\') => return None,
+                _ => DiffLine {
+                    old: Some(old),
+                    new: Some(new),
+                },
+            };
+            if numbers.old.is_some() {
+                old += 1;
+            }
+            if numbers.new.is_some() {
+                new += 1;
+            }
+            Some(numbers)
+        })
+        .collect()
+}
+
+/// Soft-wrap `text` to lines of at most `width` chars, breaking at spaces
+/// where it can and cutting words longer than a line. Continuation lines keep
+/// the first line's indent. A `width` of 0 leaves the text whole.
+pub fn wrap_line(text: &str, width: usize) -> Vec<String> {
+    if width == 0 || text.chars().count() <= width {
+        return vec![text.to_string()];
+    }
+    let body = text.trim_start_matches(' ');
+    let mut indent = &text[..text.len() - body.len()];
+    // An indent eating most of the line would leave no room for words
+    if indent.len() * 2 > width {
+        indent = "";
+    }
+    let base = indent.len();
+
+    let mut lines = Vec::new();
+    let mut line = indent.to_string();
+    let mut len = base;
+    for mut word in body.split(' ') {
+        let mut word_len = word.chars().count();
+        if len > base && len + 1 + word_len > width {
+            lines.push(std::mem::replace(&mut line, indent.to_string()));
+            len = base;
+        } else if len > base {
+            line.push(' ');
+            len += 1;
+        }
+        while len + word_len > width {
+            let room = width - len;
+            let cut = word.char_indices().nth(room).map_or(word.len(), |(i, _)| i);
+            line.push_str(&word[..cut]);
+            lines.push(std::mem::replace(&mut line, indent.to_string()));
+            len = base;
+            word = &word[cut..];
+            word_len -= room;
+        }
+        line.push_str(word);
+        len += word_len;
+    }
+    lines.push(line);
+    lines
+}
+
+/// Line diff of two texts: every line prefixed with ' ', '-' or '+'.
+/// Longest-common-subsequence based; past a few million cell comparisons
+/// it gives up on alignment and shows all old lines removed, then all new.
+pub fn line_diff(old: &str, new: &str) -> Vec<String> {
+    let (a, b): (Vec<&str>, Vec<&str>) = (old.lines().collect(), new.lines().collect());
+    if a.len() * b.len() > 4_000_000 {
+        return a
+            .iter()
+            .map(|l| format!("-{}", l))
+            .chain(b.iter().map(|l| format!("+{}", l)))
+            .collect();
+    }
+
+    // lcs[i][j]: common lines between a[i..] and b[j..]
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::new();
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            out.push(format!(" {}", a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push(format!("-{}", a[i]));
+            i += 1;
+        } else {
+            out.push(format!("+{}", b[j]));
+            j += 1;
+        }
+    }
+    out
+}
+
+/// A comment on one line of a PR file, held until the review is submitted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewComment {
+    pub path: String,
+    pub line: DiffLine,
+    pub body: String,
+}
+
 /// Cached home screen data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HomeData {
     pub review_requests: Vec<ReviewRequest>,
     pub my_prs: Vec<MyPr>,
+    #[serde(default)]
+    pub assigned: Vec<IssueRef>,
+    #[serde(default)]
+    pub mentions: Vec<IssueRef>,
+}
+
+/// Where the last session quit, for `start_screen = "last"`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LastLocation {
+    Home,
+    Repos,
+    Repo { owner: String, name: String },
+}
+
+/// A repo or PR opened recently, for Home's Recent list and `Ctrl+O`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentItem {
+    pub owner: String,
+    pub repo: String,
+    /// PR number; `None` for the repo itself
+    pub number: Option<u64>,
+    pub title: String,
+    pub visited_at: DateTime<Utc>,
+}
+
+impl RecentItem {
+    /// Whether both point at the same repo or PR
+    pub fn same_target(&self, other: &RecentItem) -> bool {
+        self.owner == other.owner && self.repo == other.repo && self.number == other.number
+    }
+
+    pub fn label(&self) -> String {
+        match self.number {
+            Some(n) => format!("{}/{} #{} {}", self.owner, self.repo, n, self.title),
+            None => format!("{}/{}", self.owner, self.repo),
+        }
+    }
 }
 
 /// GitHub Issue
@@ -75,10 +422,121 @@ pub struct Issue {
     pub title: String,
     pub state: IssueState,
     pub author: String,
-    pub labels: Vec<String>,
+    pub labels: Vec<Label>,
     pub comments: u32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub milestone: Option<String>,
+    /// Logins
+    #[serde(default)]
+    pub assignees: Vec<String>,
+}
+
+/// Title and metadata of an issue or PR as one edit writes them
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IssueFields {
+    pub title: String,
+    pub labels: Vec<String>,
+    /// Logins
+    pub assignees: Vec<String>,
+    /// `Milestone::id`; `None` clears it
+    pub milestone: Option<u64>,
+}
+
+/// One of a repo's issue templates, its front matter split off the body
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueTemplate {
+    pub name: String,
+    /// What the template is for, shown next to its name
+    pub about: Option<String>,
+    /// Title, labels and assignees a new issue starts with
+    pub title: String,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    pub body: String,
+}
+
+impl IssueTemplate {
+    /// A Markdown template with optional YAML front matter (`name`, `about`,
+    /// `title`, `labels`, `assignees`), as GitHub and Gitea read it. Lists may
+    /// be comma-separated, `[a, b]` or one `- item` per line; the file name
+    /// stands in for a missing `name`.
+    pub fn parse(file_name: &str, text: &str) -> Self {
+        let mut template = IssueTemplate {
+            name: file_name
+                .rsplit_once('.')
+                .map_or(file_name, |(stem, _)| stem)
+                .to_string(),
+            body: text.to_string(),
+            ..IssueTemplate::default()
+        };
+        let Some(rest) = text.strip_prefix("---\n").or(text.strip_prefix("---\r\n")) else {
+            return template;
+        };
+        let Some((front, body)) = rest
+            .split_once("\n---\n")
+            .or(rest.split_once("\n---\r\n"))
+            .or(rest.strip_suffix("\n---").map(|front| (front, "")))
+        else {
+            return template;
+        };
+        template.body = body.trim_start_matches(['\r', '\n']).to_string();
+
+        let unquote = |s: &str| s.trim().trim_matches(['"', '\'']).to_string();
+        let list = |s: &str| -> Vec<String> {
+            s.trim()
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .split(',')
+                .map(unquote)
+                .filter(|s| !s.is_empty())
+                .collect()
+        };
+        let mut key = "";
+        for line in front.lines() {
+            // An item of the list under the last key
+            if let Some(item) = line.trim().strip_prefix("- ") {
+                let item = unquote(item);
+                match key {
+                    "labels" => template.labels.push(item),
+                    "assignees" => template.assignees.push(item),
+                    _ => {}
+                }
+                continue;
+            }
+            let Some((k, value)) = line.split_once(':') else {
+                continue;
+            };
+            key = k.trim();
+            match key {
+                "name" if !value.trim().is_empty() => template.name = unquote(value),
+                "about" => template.about = Some(unquote(value)).filter(|s| !s.is_empty()),
+                "title" => template.title = unquote(value),
+                "labels" => template.labels = list(value),
+                "assignees" => template.assignees = list(value),
+                _ => {}
+            }
+        }
+        template
+    }
+}
+
+/// A repository label
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Label {
+    pub name: String,
+    /// Hex color without the leading `#`
+    pub color: Option<String>,
+}
+
+/// A repository milestone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Milestone {
+    /// The id `Forge::set_milestone` expects (number on GitHub, global id on GitLab/Gitea)
+    pub id: u64,
+    pub title: String,
+    pub due_on: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -105,6 +563,44 @@ pub struct Commit {
     pub date: DateTime<Utc>,
 }
 
+/// A commit found by searching across repos
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitHit {
+    pub owner: String,
+    pub repo: String,
+    pub commit: Commit,
+}
+
+impl CommitHit {
+    pub fn label(&self) -> String {
+        format!(
+            "{}/{}  {}  {}",
+            self.owner,
+            self.repo,
+            &self.commit.sha[..self.commit.sha.len().min(7)],
+            self.commit.message
+        )
+    }
+}
+
+/// Whether `query` names this commit: a SHA prefix of 4+ hex digits, or
+/// text in the message (case-insensitive)
+pub fn commit_matches(commit: &Commit, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return false;
+    }
+    if is_sha_prefix(&query) && commit.sha.to_lowercase().starts_with(&query) {
+        return true;
+    }
+    commit.message.to_lowercase().contains(&query)
+}
+
+/// 4 to 40 hex digits, the shortest abbreviation git accepts
+pub fn is_sha_prefix(query: &str) -> bool {
+    (4..=40).contains(&query.len()) && query.chars().all(|c| c.is_ascii_hexdigit())
+}
+
 /// Git Commit (full detail)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommitDetail {
@@ -116,6 +612,28 @@ pub struct CommitDetail {
     pub files: Vec<CommitFile>,
 }
 
+impl CommitDetail {
+    /// Names from `Co-authored-by:` trailers in the commit message
+    pub fn co_authors(&self) -> Vec<String> {
+        self.message
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                let (key, value) = line.split_once(':')?;
+                if !key.trim().eq_ignore_ascii_case("co-authored-by") {
+                    return None;
+                }
+                let name = value.split('<').next().unwrap_or("").trim();
+                if name.is_empty() {
+                    None
+                } else {
+                    Some(name.to_string())
+                }
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommitStats {
     pub additions: u64,
@@ -132,6 +650,18 @@ pub struct CommitFile {
     pub patch: Option<String>,
 }
 
+impl CommitFile {
+    /// The patch as a one-file `git diff`, ready for a pager
+    pub fn diff(&self) -> Option<String> {
+        let patch = self.patch.as_ref()?;
+        Some(format!(
+            "diff --git a/{f} b/{f}\n{}\n",
+            patch,
+            f = self.filename
+        ))
+    }
+}
+
 /// GitHub Actions workflow run
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActionRun {
@@ -142,6 +672,119 @@ pub struct ActionRun {
     pub branch: String,
     pub event: String,
     pub created_at: DateTime<Utc>,
+    /// Seconds from start to finish, once the run completed
+    #[serde(default)]
+    pub duration: Option<u64>,
+    /// Workflow file, where `name` is the run's own title (Gitea); runs are
+    /// grouped by this, or by `name` without it
+    #[serde(default)]
+    pub workflow: Option<String>,
+}
+
+impl ActionRun {
+    pub fn workflow_name(&self) -> &str {
+        self.workflow.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Latest runs shown in a workflow's history strip
+pub const WORKFLOW_HISTORY_LEN: usize = 10;
+
+/// One workflow's recent record, for the Actions tab's summary view
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkflowSummary {
+    pub name: String,
+    /// Outcomes of the latest runs, oldest first; `None` for runs still going
+    pub history: Vec<Option<ActionConclusion>>,
+    /// Mean duration in seconds of the finished runs
+    pub average_duration: Option<u64>,
+    pub last_run: DateTime<Utc>,
+    /// Id of the newest run
+    pub latest_id: u64,
+}
+
+/// Group runs by workflow, most recently run first
+pub fn summarize_workflows(runs: &[ActionRun]) -> Vec<WorkflowSummary> {
+    let mut groups: Vec<(&str, Vec<&ActionRun>)> = Vec::new();
+    for run in runs {
+        let name = run.workflow_name();
+        match groups.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, group)) => group.push(run),
+            None => groups.push((name, vec![run])),
+        }
+    }
+
+    let mut summaries: Vec<WorkflowSummary> = groups
+        .into_iter()
+        .map(|(name, mut group)| {
+            group.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+            let durations: Vec<u64> = group.iter().filter_map(|r| r.duration).collect();
+            let history = group
+                .iter()
+                .take(WORKFLOW_HISTORY_LEN)
+                .rev()
+                .map(|r| match r.status {
+                    ActionStatus::Completed => r.conclusion,
+                    _ => None,
+                })
+                .collect();
+            WorkflowSummary {
+                name: name.to_string(),
+                history,
+                average_duration: (!durations.is_empty())
+                    .then(|| durations.iter().sum::<u64>() / durations.len() as u64),
+                last_run: group[0].created_at,
+                latest_id: group[0].id,
+            }
+        })
+        .collect();
+    summaries.sort_by_key(|s| std::cmp::Reverse(s.last_run));
+    summaries
+}
+
+/// Days of commits counted as a repo's recent activity
+pub const RECENT_ACTIVITY_DAYS: i64 = 30;
+/// Contributors and languages kept for the insights popup
+pub const INSIGHTS_TOP: usize = 8;
+
+/// Aggregates for a repo's insights popup
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RepoStats {
+    pub open_issues: u64,
+    pub closed_issues: u64,
+    pub open_prs: u64,
+    pub merged_prs: u64,
+    /// Closed without being merged
+    pub closed_prs: u64,
+    /// Name and commit count, most commits first
+    pub contributors: Vec<(String, u64)>,
+    /// Language and its percentage of the code, largest first
+    pub languages: Vec<(String, f64)>,
+    /// Commits on the default branch in the last `RECENT_ACTIVITY_DAYS`
+    pub recent_commits: u64,
+}
+
+impl RepoStats {
+    /// Share of closed PRs that were merged; None until one closed
+    pub fn merge_rate(&self) -> Option<f64> {
+        let closed = self.merged_prs + self.closed_prs;
+        (closed > 0).then(|| self.merged_prs as f64 / closed as f64)
+    }
+}
+
+/// Byte counts per language as percentages, largest first, `INSIGHTS_TOP` kept
+pub fn language_shares(bytes: impl IntoIterator<Item = (String, u64)>) -> Vec<(String, f64)> {
+    let mut bytes: Vec<(String, u64)> = bytes.into_iter().collect();
+    let total: u64 = bytes.iter().map(|(_, b)| b).sum();
+    if total == 0 {
+        return Vec::new();
+    }
+    bytes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    bytes
+        .into_iter()
+        .take(INSIGHTS_TOP)
+        .map(|(name, b)| (name, b as f64 * 100.0 / total as f64))
+        .collect()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -182,6 +825,36 @@ impl std::fmt::Display for ActionConclusion {
     }
 }
 
+/// A file a workflow run uploaded, downloadable as a zip
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Artifact {
+    /// Artifact id, or the job's on GitLab
+    pub id: u64,
+    pub name: String,
+    /// Size in bytes, as the forge reports it
+    pub size: u64,
+    /// Past the forge's retention; the download would fail
+    pub expired: bool,
+    /// API URL the zip is fetched from
+    pub download_url: String,
+}
+
+/// Byte count in the largest unit that keeps it at least 1, as `4.2 MB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
 /// Review request - a PR where the current user is requested as reviewer
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReviewRequest {
@@ -193,6 +866,159 @@ pub struct ReviewRequest {
     pub updated_at: DateTime<Utc>,
 }
 
+/// An open issue or PR somewhere on the forge, for Home's "Assigned to you"
+/// and "Mentions" sections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueRef {
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub is_pr: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Which of a repo's open PRs the PR tab lists; the forge does the filtering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PrFilter {
+    #[default]
+    All,
+    /// Opened by the signed-in user
+    Authored,
+    /// Assigned to the signed-in user
+    Assigned,
+    /// Waiting on the signed-in user's review
+    ReviewRequested,
+}
+
+impl PrFilter {
+    pub const ALL: [PrFilter; 4] = [
+        PrFilter::All,
+        PrFilter::Authored,
+        PrFilter::Assigned,
+        PrFilter::ReviewRequested,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PrFilter::All => "All open",
+            PrFilter::Authored => "Authored by me",
+            PrFilter::Assigned => "Assigned to me",
+            PrFilter::ReviewRequested => "Awaiting my review",
+        }
+    }
+}
+
+/// Short name for cache keys and recordings
+impl fmt::Display for PrFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PrFilter::All => "all",
+            PrFilter::Authored => "authored",
+            PrFilter::Assigned => "assigned",
+            PrFilter::ReviewRequested => "review-requested",
+        })
+    }
+}
+
+/// A saved search's GitHub-style qualifiers, for forges whose search takes
+/// filters as parameters rather than a query string
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// `is:pr` (or `is:mr`) / `is:issue`; both kinds when unset
+    pub is_pr: Option<bool>,
+    pub state: SearchState,
+    pub author: Option<String>,
+    pub assignee: Option<String>,
+    pub mentions: Option<String>,
+    pub review_requested: Option<String>,
+    pub labels: Vec<String>,
+    /// `org:`, `user:` or `group:`
+    pub owner: Option<String>,
+    /// Words that aren't qualifiers
+    pub text: String,
+}
+
+/// Which items a saved search matches; open ones unless it says otherwise
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchState {
+    #[default]
+    Open,
+    Closed,
+    Merged,
+}
+
+impl SearchQuery {
+    /// Read `query`, failing on a qualifier other than the ones above.
+    /// Values with spaces are quoted: `label:"good first issue"`.
+    pub fn parse(query: &str) -> std::result::Result<Self, String> {
+        let mut parsed = SearchQuery::default();
+        let mut text = Vec::new();
+        for word in split_quoted(query) {
+            let Some((key, value)) = word.split_once(':').filter(|(key, value)| {
+                !value.is_empty()
+                    && !value.starts_with('/')
+                    && !key.is_empty()
+                    && key.chars().all(|c| c.is_ascii_lowercase() || c == '-')
+            }) else {
+                text.push(word.replace('"', ""));
+                continue;
+            };
+            let value = value.replace('"', "");
+            match (key, value.as_str()) {
+                ("is", "pr" | "mr") => parsed.is_pr = Some(true),
+                ("is", "issue") => parsed.is_pr = Some(false),
+                ("is", "open") => parsed.state = SearchState::Open,
+                ("is", "closed") => parsed.state = SearchState::Closed,
+                ("is", "merged") => {
+                    parsed.is_pr = Some(true);
+                    parsed.state = SearchState::Merged;
+                }
+                ("author", _) => parsed.author = Some(value),
+                ("assignee", _) => parsed.assignee = Some(value),
+                ("mentions", _) => parsed.mentions = Some(value),
+                ("review-requested", _) => parsed.review_requested = Some(value),
+                ("label", _) => parsed.labels.push(value),
+                ("org" | "user" | "group", _) => parsed.owner = Some(value),
+                _ => {
+                    return Err(format!(
+                        "'{}' isn't a qualifier this forge can search by",
+                        word
+                    ))
+                }
+            }
+        }
+        parsed.text = text.join(" ");
+        Ok(parsed)
+    }
+}
+
+/// Whitespace-separated words, where a quoted stretch stays in one word
+fn split_quoted(s: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    for c in s.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                word.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
 /// Your open PR with CI status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MyPr {
@@ -202,6 +1028,9 @@ pub struct MyPr {
     pub title: String,
     pub state: PrState,
     pub checks_status: ChecksStatus,
+    /// Unknown unless the forge hands it over with the list
+    #[serde(default)]
+    pub mergeable: MergeableState,
     pub updated_at: DateTime<Utc>,
 }
 
@@ -227,6 +1056,7 @@ impl std::fmt::Display for ChecksStatus {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Repository {
+    /// User, org or GitLab group; nested groups keep their slashes ("group/sub")
     pub owner: String,
     pub name: String,
     pub description: Option<String>,
@@ -235,6 +1065,191 @@ pub struct Repository {
     pub updated_at: DateTime<Utc>,
 }
 
+impl Repository {
+    /// HTTPS URL `git clone` accepts on every supported forge
+    pub fn clone_url(&self) -> String {
+        format!("{}.git", self.url.trim_end_matches('/'))
+    }
+
+    /// "owner/name", the path forges use in URLs and API routes
+    pub fn full_path(&self) -> String {
+        format!("{}/{}", self.owner, self.name)
+    }
+}
+
+/// Where a fork's default branch stands against the same branch upstream
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForkStatus {
+    /// "owner/repo" this was forked from
+    pub upstream: String,
+    /// The fork's default branch
+    pub branch: String,
+    /// The upstream's default branch, compared against `branch`
+    pub upstream_branch: String,
+    pub ahead: u64,
+    pub behind: u64,
+}
+
+/// How much of a repository's activity notifies you
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Subscription {
+    /// Everything: new PRs, issues, pushes
+    Watching,
+    /// Only threads you take part in or are mentioned in
+    Participating,
+    /// Nothing, not even mentions
+    Ignoring,
+}
+
+impl Subscription {
+    pub const ALL: [Subscription; 3] = [
+        Subscription::Watching,
+        Subscription::Participating,
+        Subscription::Ignoring,
+    ];
+}
+
+impl fmt::Display for Subscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Subscription::Watching => write!(f, "Watching - all activity"),
+            Subscription::Participating => write!(f, "Participating and @mentions"),
+            Subscription::Ignoring => write!(f, "Ignoring"),
+        }
+    }
+}
+
+/// Repository metadata that can be edited from grit
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoMeta {
+    pub description: String,
+    pub topics: Vec<String>,
+}
+
+impl RepoMeta {
+    /// Text opened in $EDITOR: the description, then a `topics:` line
+    pub fn to_template(&self) -> String {
+        format!(
+            "{}\n\ntopics: {}\n\n# The text above is the description; topics are comma-separated.\n# Lines starting with '#' are ignored. Save unchanged to cancel.\n",
+            self.description,
+            self.topics.join(", ")
+        )
+    }
+
+    /// Read back an edited template
+    pub fn parse(text: &str) -> Self {
+        let mut meta = RepoMeta::default();
+        let mut description = Vec::new();
+        for line in text.lines().map(str::trim) {
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+            match line.strip_prefix("topics:") {
+                Some(topics) => {
+                    meta.topics = topics
+                        .split([',', ' '])
+                        .map(str::trim)
+                        .filter(|t| !t.is_empty())
+                        .map(str::to_lowercase)
+                        .collect();
+                }
+                None => description.push(line),
+            }
+        }
+        meta.description = description.join(" ");
+        meta
+    }
+}
+
+/// A `- [ ]` or `- [x]` item of a Markdown task list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskItem {
+    pub text: String,
+    pub done: bool,
+    /// Line of the body it's on, as `str::lines` counts
+    pub line: usize,
+    /// Byte offset of the character between the brackets
+    mark: usize,
+}
+
+/// Task-list items of a Markdown body, leaving out code blocks. Any list
+/// marker counts (`-`, `*`, `+`, `1.`), at any depth.
+pub fn task_items(body: &str) -> Vec<TaskItem> {
+    let mut items = Vec::new();
+    let mut fenced = false;
+    let mut start = 0;
+    for (line, text) in body.split_inclusive('\n').enumerate() {
+        let offset = start;
+        start += text.len();
+        let trimmed = text.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fenced = !fenced;
+            continue;
+        }
+        if fenced {
+            continue;
+        }
+        let rest = match trimmed.strip_prefix(['-', '*', '+']) {
+            Some(rest) => rest,
+            None => {
+                let number = trimmed.trim_start_matches(|c: char| c.is_ascii_digit());
+                match number.strip_prefix(['.', ')']) {
+                    Some(rest) if number.len() < trimmed.len() => rest,
+                    _ => continue,
+                }
+            }
+        };
+        let Some(boxed) = rest.strip_prefix(' ').map(|r| r.trim_start_matches(' ')) else {
+            continue;
+        };
+        let done = if boxed.starts_with("[ ]") {
+            false
+        } else if boxed.starts_with("[x]") || boxed.starts_with("[X]") {
+            true
+        } else {
+            continue;
+        };
+        let label = &boxed[3..];
+        if !(label.is_empty() || label.starts_with([' ', '\t', '\r', '\n'])) {
+            continue;
+        }
+        items.push(TaskItem {
+            text: label.trim().to_string(),
+            done,
+            line,
+            mark: offset + text.len() - boxed.len() + 1,
+        });
+    }
+    items
+}
+
+/// `body` with `item` ticked or cleared; everything else stays as it was
+pub fn set_task(body: &str, item: &TaskItem, done: bool) -> String {
+    let mut body = body.to_string();
+    body.replace_range(item.mark..item.mark + 1, if done { "x" } else { " " });
+    body
+}
+
+/// Split "group/sub/project" into owner "group/sub" and name "project";
+/// only the last segment is the repo name
+pub fn split_repo_path(path: &str) -> Option<(String, String)> {
+    let (owner, name) = path.trim_matches('/').rsplit_once('/')?;
+    if owner.is_empty() || name.is_empty() {
+        return None;
+    }
+    Some((owner.to_string(), name.to_string()))
+}
+
+/// Parse "owner/repo" or "owner/repo#123" into the repo and an optional number
+pub fn parse_repo_ref(s: &str) -> Option<(String, String, Option<u64>)> {
+    let (path, number) = match s.split_once('#') {
+        Some((path, number)) => (path, Some(number.parse().ok()?)),
+        None => (s, None),
+    };
+    let (owner, name) = split_repo_path(path)?;
+    Some((owner, name, number))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PrState {
     Open,
@@ -259,6 +1274,104 @@ pub struct PrSummary {
     pub state: PrState,
     pub author: String,
     pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub milestone: Option<String>,
+}
+
+/// A conversation comment on a PR or issue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: u64,
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An inline review thread on a PR, resolvable as a unit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewThread {
+    /// Forge-specific thread id (GraphQL node id on GitHub, discussion id on GitLab)
+    pub id: String,
+    pub path: Option<String>,
+    pub line: Option<u64>,
+    pub resolved: bool,
+    pub comments: Vec<Comment>,
+}
+
+/// State of a submitted review
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Dismissed,
+}
+
+impl std::fmt::Display for ReviewState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // `pad` so the reviewer list's `{:<18}` lines the dates up
+        f.pad(match self {
+            ReviewState::Approved => "approved",
+            ReviewState::ChangesRequested => "changes requested",
+            ReviewState::Commented => "commented",
+            ReviewState::Dismissed => "dismissed",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Review {
+    pub reviewer: String,
+    pub state: ReviewState,
+    pub submitted_at: Option<DateTime<Utc>>,
+}
+
+/// How many open review requests someone has across the repo's org or group
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewerLoad {
+    pub login: String,
+    pub open_reviews: u64,
+}
+
+/// Requested reviewers and submitted reviews for a PR
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PrReviews {
+    /// Reviewers (users or teams) still expected to review
+    pub requested: Vec<String>,
+    /// Submitted reviews, oldest first
+    pub reviews: Vec<Review>,
+    /// Approvals the forge requires before merging, when it exposes that
+    #[serde(default)]
+    pub required_approvals: Option<u32>,
+}
+
+impl PrReviews {
+    /// Reviewers whose latest verdict is an approval
+    pub fn approval_count(&self) -> usize {
+        self.latest_by_reviewer()
+            .iter()
+            .filter(|r| r.state == ReviewState::Approved)
+            .count()
+    }
+
+    /// One entry per reviewer: their latest approval / change request,
+    /// falling back to their latest comment if they never took a stance
+    pub fn latest_by_reviewer(&self) -> Vec<&Review> {
+        let mut latest: Vec<&Review> = Vec::new();
+        for review in &self.reviews {
+            match latest.iter_mut().find(|r| r.reviewer == review.reviewer) {
+                Some(existing) => {
+                    if review.state != ReviewState::Commented
+                        || existing.state == ReviewState::Commented
+                    {
+                        *existing = review;
+                    }
+                }
+                None => latest.push(review),
+            }
+        }
+        latest
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -279,17 +1392,409 @@ pub struct PullRequest {
     pub author: String,
     pub head_branch: String,
     pub base_branch: String,
+    /// Commit the head branch points at; empty in caches from older versions
+    #[serde(default)]
+    pub head_sha: String,
+    /// The head branch lives in another repository, so merging can't delete it
+    #[serde(default)]
+    pub from_fork: bool,
+    /// Set to merge on its own once checks pass. Gitea doesn't report this,
+    /// so there it only reflects auto-merge enabled from grit
+    #[serde(default)]
+    pub auto_merge: bool,
     pub stats: PrStats,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub merged_at: Option<DateTime<Utc>>,
     pub closed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub mergeable_state: MergeableState,
+    #[serde(default)]
+    pub milestone: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    /// Logins
+    #[serde(default)]
+    pub assignees: Vec<String>,
+}
+
+/// Per-run check counts for a PR's head commit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrChecks {
+    pub passed: u32,
+    pub failed: u32,
+    pub pending: u32,
+    /// Finished without passing or failing; doesn't hold up the rollup
+    #[serde(default)]
+    pub neutral: u32,
+}
+
+impl PrChecks {
+    pub fn total(&self) -> u32 {
+        self.passed + self.failed + self.pending + self.neutral
+    }
+
+    /// Roll the counts up into the single status shown in lists
+    pub fn status(&self) -> ChecksStatus {
+        if self.total() == 0 {
+            ChecksStatus::None
+        } else if self.failed > 0 {
+            ChecksStatus::Failure
+        } else if self.pending > 0 {
+            ChecksStatus::Pending
+        } else {
+            ChecksStatus::Success
+        }
+    }
+}
+
+/// One CI check on a commit: a check run, commit status or pipeline job
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitCheck {
+    pub name: String,
+    pub state: ChecksStatus,
+    /// Seconds from start to finish, when the forge reports both
+    pub duration: Option<u64>,
+}
+
+/// Whether a PR can be merged right now, and if not, why
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MergeableState {
+    Clean,
+    /// Mergeable, but non-required checks are failing
+    Unstable,
+    /// Mergeable, but the head branch is behind the base
+    Behind,
+    Conflicts,
+    BlockedByChecks,
+    BlockedByReviews,
+    /// Blocked for another reason (branch protection, unresolved discussions, ...)
+    Blocked,
+    Draft,
+    /// Not yet computed by the forge
+    #[default]
+    Unknown,
+}
+
+impl MergeableState {
+    /// Whether the forge will accept a merge request
+    pub fn can_merge(&self) -> bool {
+        matches!(
+            self,
+            MergeableState::Clean
+                | MergeableState::Unstable
+                | MergeableState::Behind
+                | MergeableState::Unknown
+        )
+    }
+
+    /// Whether the forge would take the merge once checks and reviews clear
+    pub fn can_auto_merge(&self) -> bool {
+        self.can_merge()
+            || matches!(
+                self,
+                MergeableState::BlockedByChecks
+                    | MergeableState::BlockedByReviews
+                    | MergeableState::Blocked
+            )
+    }
+
+    /// A caution to show when merging is allowed but not clean
+    pub fn merge_warning(&self) -> Option<&'static str> {
+        match self {
+            MergeableState::Unstable => Some("checks failing"),
+            MergeableState::Behind => Some("branch is behind base"),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for MergeableState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeableState::Clean => write!(f, "mergeable"),
+            MergeableState::Unstable => write!(f, "mergeable, checks failing"),
+            MergeableState::Behind => write!(f, "mergeable, behind base"),
+            MergeableState::Conflicts => write!(f, "merge conflicts"),
+            MergeableState::BlockedByChecks => write!(f, "blocked by checks"),
+            MergeableState::BlockedByReviews => write!(f, "blocked by reviews"),
+            MergeableState::Blocked => write!(f, "blocked"),
+            MergeableState::Draft => write!(f, "draft"),
+            MergeableState::Unknown => write!(f, "mergeability unknown"),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn commit_matches_sha_prefix_or_message() {
+        let commit = Commit {
+            sha: "a1b2c3d4e5".to_string(),
+            message: "Fix the Cache eviction".to_string(),
+            author: "dev".to_string(),
+            date: chrono::Utc::now(),
+        };
+        assert!(commit_matches(&commit, "A1B2C"));
+        assert!(commit_matches(&commit, "cache EVICTION"));
+        assert!(!commit_matches(&commit, "b2c3"));
+        assert!(!commit_matches(&commit, "a1b"));
+        assert!(!commit_matches(&commit, "  "));
+    }
+
+    #[test]
+    fn wrap_line_breaks_at_spaces_and_keeps_indent() {
+        assert_eq!(
+            wrap_line("  the quick brown fox", 11),
+            vec!["  the quick", "  brown fox"]
+        );
+        assert_eq!(wrap_line("short", 10), vec!["short"]);
+        assert_eq!(wrap_line("anything goes", 0), vec!["anything goes"]);
+    }
+
+    #[test]
+    fn wrap_line_cuts_words_longer_than_a_line() {
+        assert_eq!(
+            wrap_line("see https://example.com/long", 10),
+            vec!["see", "https://ex", "ample.com/", "long"]
+        );
+        assert_eq!(wrap_line("ééééé", 2), vec!["éé", "éé", "é"]);
+    }
+
+    #[test]
+    fn line_diff_marks_changed_lines() {
+        assert_eq!(
+            line_diff("a\nb\nc", "a\nB\nc\nd"),
+            vec![" a", "-b", "+B", " c", "+d"]
+        );
+        assert_eq!(line_diff("", "x"), vec!["+x"]);
+        assert!(line_diff("same", "same").iter().all(|l| l.starts_with(' ')));
+    }
+
+    #[test]
+    fn patch_line_numbers_follow_hunks() {
+        let patch = "@@ -10,3 +10,3 @@ fn main() {\n context\n-old\n+new\n@@ -40 +40,2 @@\n+added\n\\ No newline at end of file";
+        let lines = patch_line_numbers(patch);
+        let at = |old, new| Some(DiffLine { old, new });
+        assert_eq!(
+            lines,
+            vec![
+                None,
+                at(Some(10), Some(10)),
+                at(Some(11), None),
+                at(None, Some(11)),
+                None,
+                at(None, Some(40)),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn workflows_summarize_their_latest_runs() {
+        let start = Utc::now() - chrono::Duration::hours(1);
+        let run = |id: u64, name: &str, minutes: i64, conclusion, duration| ActionRun {
+            id,
+            name: name.to_string(),
+            status: ActionStatus::Completed,
+            conclusion: Some(conclusion),
+            branch: "main".to_string(),
+            event: "push".to_string(),
+            created_at: start + chrono::Duration::minutes(minutes),
+            duration,
+            workflow: None,
+        };
+        let mut running = run(4, "CI", 30, ActionConclusion::Success, None);
+        running.status = ActionStatus::InProgress;
+        let runs = vec![
+            running,
+            run(3, "Docs", 20, ActionConclusion::Success, Some(60)),
+            run(2, "CI", 10, ActionConclusion::Failure, Some(100)),
+            run(1, "CI", 0, ActionConclusion::Success, Some(200)),
+        ];
+
+        let summaries = summarize_workflows(&runs);
+        assert_eq!(summaries.len(), 2);
+        let ci = &summaries[0];
+        assert_eq!(ci.name, "CI");
+        assert_eq!(
+            ci.history,
+            vec![
+                Some(ActionConclusion::Success),
+                Some(ActionConclusion::Failure),
+                None
+            ]
+        );
+        assert_eq!(ci.average_duration, Some(150));
+        assert_eq!(ci.latest_id, 4);
+        assert_eq!(summaries[1].name, "Docs");
+    }
+
+    #[test]
+    fn split_rows_pair_removals_with_additions() {
+        let patch = "@@ -1,4 +1,4 @@\n a\n-b\n-c\n+B\n d\n+e\n-f";
+        let row = |old, new| SplitRow { old, new };
+        assert_eq!(
+            split_rows(patch),
+            vec![
+                row(Some(0), Some(0)),
+                row(Some(1), Some(1)),
+                row(Some(2), Some(4)),
+                row(Some(3), None),
+                row(Some(5), Some(5)),
+                row(None, Some(6)),
+                row(Some(7), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_repo_path_keeps_nested_groups_in_owner() {
+        assert_eq!(
+            split_repo_path("group/sub/project"),
+            Some(("group/sub".to_string(), "project".to_string()))
+        );
+        assert_eq!(
+            split_repo_path("octo/repo"),
+            Some(("octo".to_string(), "repo".to_string()))
+        );
+        assert_eq!(split_repo_path("project"), None);
+        assert_eq!(split_repo_path("/project"), None);
+    }
+
+    #[test]
+    fn parse_repo_ref_reads_optional_number() {
+        assert_eq!(
+            parse_repo_ref("octo/repo"),
+            Some(("octo".to_string(), "repo".to_string(), None))
+        );
+        assert_eq!(
+            parse_repo_ref("group/sub/project#42"),
+            Some(("group/sub".to_string(), "project".to_string(), Some(42)))
+        );
+        assert_eq!(parse_repo_ref("octo/repo#abc"), None);
+        assert_eq!(parse_repo_ref("#42"), None);
+    }
+
+    #[test]
+    fn repo_meta_template_round_trips() {
+        let meta = RepoMeta {
+            description: "A TUI for forges".to_string(),
+            topics: vec!["rust".to_string(), "tui".to_string()],
+        };
+        assert_eq!(RepoMeta::parse(&meta.to_template()), meta);
+
+        let edited = RepoMeta::parse("# note\nNew\ndescription\n\ntopics: Rust,  cli tui\n");
+        assert_eq!(edited.description, "New description");
+        assert_eq!(edited.topics, vec!["rust", "cli", "tui"]);
+    }
+
+    #[test]
+    fn task_items_and_toggling() {
+        let body = "Plan:\r\n- [ ] one\r\n  * [x] two\r\n3. [X] three\r\n- [ ]not a task\r\n```\n- [ ] code\n```\n- [] nor this\n";
+        let items = task_items(body);
+        let summary: Vec<(&str, bool, usize)> = items
+            .iter()
+            .map(|t| (t.text.as_str(), t.done, t.line))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("one", false, 1), ("two", true, 2), ("three", true, 3)]
+        );
+
+        let ticked = set_task(body, &items[0], true);
+        assert!(ticked.starts_with("Plan:\r\n- [x] one\r\n  * [x] two"));
+        let cleared = set_task(&ticked, &items[1], false);
+        assert_eq!(
+            task_items(&cleared)
+                .iter()
+                .map(|t| t.done)
+                .collect::<Vec<_>>(),
+            vec![true, false, true]
+        );
+        assert_eq!(cleared.len(), body.len());
+    }
+
+    #[test]
+    fn search_query_qualifiers() {
+        let q = SearchQuery::parse(
+            r#"is:pr author:@me label:"good first issue" label:docs org:acme flaky test"#,
+        )
+        .unwrap();
+        assert_eq!(q.is_pr, Some(true));
+        assert_eq!(q.state, SearchState::Open);
+        assert_eq!(q.author.as_deref(), Some("@me"));
+        assert_eq!(q.labels, vec!["good first issue", "docs"]);
+        assert_eq!(q.owner.as_deref(), Some("acme"));
+        assert_eq!(q.text, "flaky test");
+
+        let merged = SearchQuery::parse("is:merged review-requested:ada").unwrap();
+        assert_eq!(merged.state, SearchState::Merged);
+        assert_eq!(merged.is_pr, Some(true));
+        assert_eq!(merged.review_requested.as_deref(), Some("ada"));
+        // A URL isn't a qualifier
+        assert_eq!(
+            SearchQuery::parse("https://x.org").unwrap().text,
+            "https://x.org"
+        );
+        assert!(SearchQuery::parse("team-review-requested:acme/backend").is_err());
+    }
+
+    #[test]
+    fn issue_template_front_matter() {
+        let t = IssueTemplate::parse(
+            "bug.md",
+            "---\nname: Bug report\nabout: 'Something broke'\ntitle: \"[BUG] \"\nlabels: [\"bug\", triage]\nassignees:\n  - ada\n  - grace\n---\n\n## Steps\n",
+        );
+        assert_eq!(t.name, "Bug report");
+        assert_eq!(t.about.as_deref(), Some("Something broke"));
+        assert_eq!(t.title, "[BUG] ");
+        assert_eq!(t.labels, vec!["bug", "triage"]);
+        assert_eq!(t.assignees, vec!["ada", "grace"]);
+        assert_eq!(t.body, "## Steps\n");
+
+        let plain = IssueTemplate::parse("feature_request.md", "Describe it\n");
+        assert_eq!(plain.name, "feature_request");
+        assert_eq!(plain.body, "Describe it\n");
+        assert!(plain.labels.is_empty());
+        assert_eq!(
+            IssueTemplate::parse("x.md", "---\nlabels: bug, docs\n---\n").labels,
+            vec!["bug", "docs"]
+        );
+    }
+
+    #[test]
+    fn commit_file_diff_wraps_patch() {
+        let mut file = CommitFile {
+            filename: "src/lib.rs".to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: None,
+        };
+        assert_eq!(file.diff(), None);
+        file.patch = Some("@@ -1 +1 @@\n+x".to_string());
+        assert_eq!(
+            file.diff().as_deref(),
+            Some("diff --git a/src/lib.rs b/src/lib.rs\n@@ -1 +1 @@\n+x\n")
+        );
+    }
+
+    #[test]
+    fn page_info_counts_pages_from_total() {
+        let info = PageInfo {
+            total_count: Some(101),
+            total_pages: None,
+        };
+        assert_eq!(info.pages(50), Some(3));
+        assert!(info.has_more(2, 50, 50));
+        assert!(!info.has_more(3, 1, 50));
+        assert_eq!(PageInfo::default().pages(50), None);
+        assert!(PageInfo::default().has_more(1, 50, 50));
+    }
+
     // MergeMethod::as_api_str
     #[test]
     fn merge_method_api_str_merge() {
@@ -377,6 +1882,37 @@ mod tests {
         assert_eq!(ActionStatus::Completed.to_string(), "Done");
     }
 
+    // CommitDetail::co_authors
+    fn commit_with_message(message: &str) -> CommitDetail {
+        CommitDetail {
+            sha: "abc".to_string(),
+            message: message.to_string(),
+            author: "me".to_string(),
+            date: Utc::now(),
+            stats: CommitStats {
+                additions: 0,
+                deletions: 0,
+                total: 0,
+            },
+            files: vec![],
+        }
+    }
+
+    #[test]
+    fn co_authors_parsed_from_trailers() {
+        let commit = commit_with_message(
+            "Fix thing\n\nCo-authored-by: Alice <alice@example.com>\nco-authored-by: Bob <b@x>",
+        );
+        assert_eq!(commit.co_authors(), vec!["Alice", "Bob"]);
+    }
+
+    #[test]
+    fn co_authors_empty_without_trailers() {
+        assert!(commit_with_message("Just a message")
+            .co_authors()
+            .is_empty());
+    }
+
     // ChecksStatus::Display
     #[test]
     fn checks_status_display() {
@@ -385,4 +1921,128 @@ mod tests {
         assert_eq!(ChecksStatus::Failure.to_string(), "✗");
         assert_eq!(ChecksStatus::None.to_string(), "-");
     }
+
+    fn review(reviewer: &str, state: ReviewState) -> Review {
+        Review {
+            reviewer: reviewer.to_string(),
+            state,
+            submitted_at: None,
+        }
+    }
+
+    #[test]
+    fn mergeable_state_can_merge() {
+        assert!(MergeableState::Clean.can_merge());
+        assert!(MergeableState::Unstable.can_merge());
+        assert!(MergeableState::Unknown.can_merge());
+        assert!(!MergeableState::Conflicts.can_merge());
+        assert!(!MergeableState::BlockedByReviews.can_merge());
+        assert!(!MergeableState::Draft.can_merge());
+    }
+
+    #[test]
+    fn mergeable_state_defaults_for_old_cache() {
+        // PRs cached before the field existed must still deserialize
+        let json = r#"{"number":1,"title":"t","body":null,"state":"Open","author":"a",
+            "head_branch":"h","base_branch":"b",
+            "stats":{"additions":0,"deletions":0,"changed_files":0,"commits":0,"comments":0},
+            "created_at":"2024-01-01T00:00:00Z","updated_at":"2024-01-01T00:00:00Z",
+            "merged_at":null,"closed_at":null}"#;
+        let pr: PullRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(pr.mergeable_state, MergeableState::Unknown);
+    }
+
+    #[test]
+    fn pr_checks_rollup() {
+        let mut checks = PrChecks::default();
+        assert_eq!(checks.status(), ChecksStatus::None);
+        checks.passed = 3;
+        assert_eq!(checks.status(), ChecksStatus::Success);
+        checks.pending = 1;
+        assert_eq!(checks.status(), ChecksStatus::Pending);
+        checks.failed = 1;
+        assert_eq!(checks.status(), ChecksStatus::Failure);
+        assert_eq!(checks.total(), 5);
+        let neutral = PrChecks {
+            neutral: 1,
+            ..PrChecks::default()
+        };
+        assert_eq!(neutral.status(), ChecksStatus::Success);
+    }
+
+    #[test]
+    fn approval_count_uses_latest_verdict() {
+        let reviews = PrReviews {
+            requested: vec![],
+            reviews: vec![
+                review("alice", ReviewState::Approved),
+                review("bob", ReviewState::Approved),
+                review("bob", ReviewState::ChangesRequested),
+            ],
+            required_approvals: Some(2),
+        };
+        assert_eq!(reviews.approval_count(), 1);
+    }
+
+    #[test]
+    fn latest_review_wins_per_reviewer() {
+        let reviews = PrReviews {
+            requested: vec![],
+            required_approvals: None,
+            reviews: vec![
+                review("alice", ReviewState::ChangesRequested),
+                review("bob", ReviewState::Commented),
+                review("alice", ReviewState::Approved),
+            ],
+        };
+        let latest = reviews.latest_by_reviewer();
+        assert_eq!(latest.len(), 2);
+        assert_eq!(latest[0].reviewer, "alice");
+        assert_eq!(latest[0].state, ReviewState::Approved);
+        assert_eq!(latest[1].state, ReviewState::Commented);
+    }
+
+    #[test]
+    fn comment_does_not_override_approval() {
+        let reviews = PrReviews {
+            requested: vec![],
+            required_approvals: None,
+            reviews: vec![
+                review("alice", ReviewState::Approved),
+                review("alice", ReviewState::Commented),
+            ],
+        };
+        let latest = reviews.latest_by_reviewer();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].state, ReviewState::Approved);
+    }
+
+    #[test]
+    fn sizes_use_the_largest_whole_unit() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
+
+    #[test]
+    fn language_shares_are_percentages_largest_first() {
+        let shares = language_shares([("Shell".to_string(), 100), ("Rust".to_string(), 300)]);
+        assert_eq!(
+            shares,
+            [("Rust".to_string(), 75.0), ("Shell".to_string(), 25.0)]
+        );
+        assert!(language_shares([("C".to_string(), 0)]).is_empty());
+    }
+
+    #[test]
+    fn merge_rate_counts_closed_prs_only() {
+        let mut stats = RepoStats {
+            open_prs: 10,
+            ..Default::default()
+        };
+        assert_eq!(stats.merge_rate(), None);
+        stats.merged_prs = 3;
+        stats.closed_prs = 1;
+        assert_eq!(stats.merge_rate(), Some(0.75));
+    }
 }
@@ -0,0 +1,151 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+use ratatui::Frame;
+
+use crate::app::{App, BrowseColumn, ClickList};
+use crate::types::PrState;
+
+use super::{author_style, format_number, number_width};
+
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(20),
+            Constraint::Percentage(30),
+            Constraint::Percentage(50),
+        ])
+        .split(area);
+
+    let owners: Vec<Line> = app
+        .browse_owners()
+        .into_iter()
+        .map(|owner| Line::from(owner.to_string()))
+        .collect();
+    render_column(
+        frame,
+        app,
+        chunks[0],
+        BrowseColumn::Owners,
+        "Owners".to_string(),
+        owners,
+        app.browse.owner_index,
+    );
+
+    let repos: Vec<Line> = app
+        .browse_repos()
+        .into_iter()
+        .map(|repo| {
+            Line::from(vec![
+                Span::raw(repo.name.clone()),
+                Span::styled(
+                    format!(" ★{}", repo.stars),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])
+        })
+        .collect();
+    let repos_title = format!("Repos ({})", repos.len());
+    render_column(
+        frame,
+        app,
+        chunks[1],
+        BrowseColumn::Repos,
+        repos_title,
+        repos,
+        app.browse.repo_index,
+    );
+
+    let num_w = number_width(app.browse.prs.iter().map(|pr| pr.number));
+    let prs: Vec<Line> = app
+        .browse
+        .prs
+        .iter()
+        .map(|pr| {
+            let number_style = match pr.state {
+                PrState::Open => Style::default().fg(Color::Green),
+                PrState::Merged => Style::default().fg(Color::Magenta),
+                PrState::Closed => Style::default().fg(Color::Red),
+            };
+            Line::from(vec![
+                Span::styled(format_number(pr.number, num_w), number_style),
+                Span::raw(" "),
+                Span::raw(pr.title.clone()),
+                Span::raw("  "),
+                Span::styled(
+                    format!("@{}", pr.author),
+                    author_style(app, &pr.author, Style::default().fg(Color::Gray)),
+                ),
+            ])
+        })
+        .collect();
+    let prs_title = match &app.browse.prs_repo {
+        Some((_, repo)) => format!("{} - Pull Requests ({})", repo, prs.len()),
+        None => "Pull Requests".to_string(),
+    };
+    render_column(
+        frame,
+        app,
+        chunks[2],
+        BrowseColumn::PullRequests,
+        prs_title,
+        prs,
+        app.browse.pr_index,
+    );
+}
+
+fn render_column(
+    frame: &mut Frame,
+    app: &App,
+    area: Rect,
+    column: BrowseColumn,
+    title: String,
+    lines: Vec<Line>,
+    selected: usize,
+) {
+    let is_active = app.browse.column == column;
+
+    let title_style = if is_active {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Gray)
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled(format!(" {} ", title), title_style))
+        .border_style(if is_active {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        });
+
+    if lines.is_empty() {
+        let message = if app.loading { "" } else { "Nothing here" };
+        let empty = Paragraph::new(message)
+            .block(block)
+            .style(Style::default().fg(Color::Gray));
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = lines.into_iter().map(ListItem::new).collect();
+    // Inactive columns keep their cursor visible, just dimmer
+    let highlight = if is_active {
+        Style::default()
+            .bg(Color::DarkGray)
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().bg(Color::DarkGray)
+    };
+    let len = items.len();
+    let list = List::new(items).block(block).highlight_style(highlight);
+
+    let mut state = super::list_state(app, ClickList::Browse(column), area, len, Some(selected));
+    frame.render_stateful_widget(list, area, &mut state);
+    super::register_list(frame, app, area, ClickList::Browse(column), len, &state);
+}
@@ -4,10 +4,14 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
+use unicode_width::UnicodeWidthStr;
 
 use crate::app::App;
+use crate::types::ChecksStatus;
 
-use super::highlight_line;
+use super::repo_view::format_duration;
+use super::text::pad;
+use super::{author_style, highlight_line};
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let Some(commit) = &app.current_commit else {
@@ -26,7 +30,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let mut line_idx: usize = 0;
 
     // Header (line 0)
-    lines.push(Line::from(vec![
+    let mut header = vec![
         Span::styled(
             format!("Commit {}", short_sha),
             Style::default()
@@ -36,11 +40,20 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         Span::raw("  "),
         Span::styled(
             format!("@{}", commit.author),
-            Style::default().fg(Color::Cyan),
+            author_style(app, &commit.author, Style::default().fg(Color::Cyan)),
         ),
-        Span::raw("  "),
-        Span::styled(age, Style::default().fg(Color::DarkGray)),
-    ]));
+    ];
+    // Co-authors ride along on the header line so line indices stay fixed
+    for co_author in commit.co_authors() {
+        header.push(Span::styled(" + ", Style::default().fg(Color::DarkGray)));
+        header.push(Span::styled(
+            co_author.clone(),
+            author_style(app, &co_author, Style::default().fg(Color::Cyan)),
+        ));
+    }
+    header.push(Span::raw("  "));
+    header.push(Span::styled(age, Style::default().fg(Color::DarkGray)));
+    lines.push(Line::from(header));
     line_idx += 1;
 
     // blank (line 1)
@@ -85,6 +98,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             line_idx,
             Style::default().fg(Color::White),
             &app.search,
+            &app.link_rules,
         ));
         line_idx += 1;
     }
@@ -92,6 +106,59 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     lines.push(Line::from(""));
     line_idx += 1;
 
+    // Checks, once the forge reported any
+    let checks = app.current_commit_checks();
+    if !checks.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Checks:",
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        let width = checks.iter().map(|c| c.name.width()).max().unwrap_or(0);
+        for check in checks {
+            let color = match check.state {
+                ChecksStatus::Success => Color::Green,
+                ChecksStatus::Failure => Color::Red,
+                ChecksStatus::Pending => Color::Yellow,
+                ChecksStatus::None => Color::Gray,
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", check.state), Style::default().fg(color)),
+                Span::raw(pad(&check.name, width)),
+                Span::styled(
+                    check
+                        .duration
+                        .map(|d| format!("  {}", format_duration(d)))
+                        .unwrap_or_default(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+        lines.push(Line::from(""));
+        line_idx += checks.len() + 2;
+    }
+
+    // Calculate visible area (account for borders)
+    let inner_height = area.height.saturating_sub(2) as usize;
+    app.page_height.set(inner_height);
+
+    // URLs in the message, numbered below the diffs for `o` and `y`
+    let links = app.body_links();
+    let footer = if links.is_empty() { 0 } else { links.len() + 1 };
+
+    // Count the diff lines up front so only those in view get styled
+    let total = lines.len()
+        + footer
+        + commit
+            .files
+            .iter()
+            .map(|f| f.patch.as_deref().map_or(0, |p| p.lines().count()) + 2)
+            .sum::<usize>();
+
+    // Clamp scroll offset to content bounds
+    let max_scroll = total.saturating_sub(inner_height);
+    let scroll_offset = app.scroll_offset.min(max_scroll);
+    let shown = scroll_offset..scroll_offset + inner_height;
+
     // Add file diffs
     for file in &commit.files {
         let status_color = match file.status.as_str() {
@@ -139,6 +206,11 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         // Show diff if available
         if let Some(patch) = &file.patch {
             for diff_line in patch.lines() {
+                if !shown.contains(&line_idx) {
+                    lines.push(Line::default());
+                    line_idx += 1;
+                    continue;
+                }
                 let sanitized = diff_line.replace('\t', "    ");
 
                 let color = if sanitized.starts_with('+') && !sanitized.starts_with("+++") {
@@ -156,6 +228,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
                     line_idx,
                     Style::default().fg(color),
                     &app.search,
+                    &[],
                 ));
                 line_idx += 1;
             }
@@ -165,6 +238,20 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         line_idx += 1;
     }
 
+    if !links.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("Links ({}):", links.len()),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        lines.extend(links.iter().enumerate().map(|(i, link)| {
+            Line::styled(
+                format!("  {}", link.label(i)),
+                Style::default().fg(Color::Blue),
+            )
+        }));
+        line_idx += footer;
+    }
+
     // Suppress unused variable warning
     let _ = line_idx;
 
@@ -172,13 +259,6 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         .borders(Borders::ALL)
         .title(format!(" Commit {} ", short_sha));
 
-    // Calculate visible area (account for borders)
-    let inner_height = area.height.saturating_sub(2) as usize;
-
-    // Clamp scroll offset to content bounds
-    let max_scroll = lines.len().saturating_sub(inner_height);
-    let scroll_offset = app.scroll_offset.min(max_scroll);
-
     // Slice lines to visible range
     let visible_lines: Vec<Line> = lines
         .into_iter()
@@ -191,6 +271,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
 
     let paragraph = Paragraph::new(visible_lines).block(block);
     frame.render_widget(paragraph, area);
+    super::render_scrollbar(frame, area, total, scroll_offset);
 }
 
 fn format_age(dt: chrono::DateTime<chrono::Utc>) -> String {
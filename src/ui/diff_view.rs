@@ -0,0 +1,310 @@
+use std::ops::Range;
+
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+use ratatui::Frame;
+
+use crate::app::{App, ClickList};
+use crate::types::{DiffLine, SplitRow};
+use unicode_width::UnicodeWidthChar;
+
+/// Narrower than this, a side-by-side diff falls back to unified
+const SPLIT_MIN_WIDTH: u16 = 100;
+/// Width of a line number column plus its gap
+const NUMBER_WIDTH: usize = 5;
+
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let Some((file, patch)) = app
+        .pr_files
+        .get(app.file_index)
+        .and_then(|f| Some((f, f.patch.as_deref()?)))
+    else {
+        let block = Block::default().borders(Borders::ALL).title(" Diff ");
+        let empty = Paragraph::new("No diff loaded")
+            .block(block)
+            .style(Style::default().fg(Color::Gray));
+        frame.render_widget(empty, area);
+        return;
+    };
+
+    let pending = app.pending_review_comments();
+    let layout = app.diff_layout().unwrap_or_default();
+    let numbers = &layout.numbers;
+    let commented = |line: Option<DiffLine>| {
+        line.is_some_and(|line| {
+            pending
+                .iter()
+                .any(|c| c.path == file.filename && c.line == line)
+        })
+    };
+
+    let split = app.diff_split && area.width >= SPLIT_MIN_WIDTH;
+    app.diff_split_shown.set(split);
+    let (len, selected) = if split {
+        let selected = layout
+            .rows
+            .iter()
+            .position(|r| r.contains(app.diff_cursor))
+            .unwrap_or(0);
+        (layout.rows.len(), selected)
+    } else {
+        (numbers.len(), app.diff_cursor)
+    };
+    let mut state = super::list_state(app, ClickList::Diff, area, len, Some(selected));
+    let rows = super::visible_rows(&mut state, area, len);
+    let items = if split {
+        split_items(
+            patch,
+            &layout.rows[rows.clone()],
+            numbers,
+            &commented,
+            area.width,
+        )
+    } else {
+        unified_items(patch, rows.clone(), numbers, &commented)
+    };
+
+    let mut title = format!(
+        " {} ({}/{}) ",
+        file.filename,
+        app.file_index + 1,
+        app.pr_files.len()
+    );
+    if split {
+        title.push_str("- side by side ");
+    }
+    if !pending.is_empty() {
+        title.push_str(&format!("- {} pending ", pending.len()));
+    }
+    let block = Block::default().borders(Borders::ALL).title(Span::styled(
+        title,
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray));
+
+    frame.render_stateful_widget(list, area, &mut super::window_state(&state, &rows));
+    super::register_list(frame, app, area, ClickList::Diff, len, &state);
+}
+
+fn line_color(text: &str) -> Color {
+    if text.starts_with('+') {
+        Color::Green
+    } else if text.starts_with('-') {
+        Color::Red
+    } else if text.starts_with("@@") {
+        Color::Cyan
+    } else {
+        Color::Gray
+    }
+}
+
+fn comment_marker(commented: bool) -> Span<'static> {
+    Span::styled(
+        if commented { "● " } else { "  " },
+        Style::default().fg(Color::Yellow),
+    )
+}
+
+/// Items for the `rows` of a patch shown one line per row
+fn unified_items(
+    patch: &str,
+    rows: Range<usize>,
+    numbers: &[Option<DiffLine>],
+    commented: &dyn Fn(Option<DiffLine>) -> bool,
+) -> Vec<ListItem<'static>> {
+    let number = |n: Option<u64>| n.map_or_else(|| "    ".to_string(), |n| format!("{:>4}", n));
+    patch
+        .lines()
+        .zip(numbers)
+        .skip(rows.start)
+        .take(rows.len())
+        .map(|(text, &line)| {
+            let sanitized = text.replace('\t', "    ");
+            let color = line_color(&sanitized);
+            ListItem::new(Line::from(vec![
+                comment_marker(commented(line)),
+                Span::styled(
+                    format!(
+                        "{} {} ",
+                        number(line.and_then(|l| l.old)),
+                        number(line.and_then(|l| l.new))
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(sanitized, Style::default().fg(color)),
+            ]))
+        })
+        .collect()
+}
+
+/// Items for `rows` of a side-by-side diff. Old lines on the left, new on the
+/// right; a removal paired with an addition has the part that changed between
+/// them highlighted
+fn split_items(
+    patch: &str,
+    rows: &[SplitRow],
+    numbers: &[Option<DiffLine>],
+    commented: &dyn Fn(Option<DiffLine>) -> bool,
+    width: u16,
+) -> Vec<ListItem<'static>> {
+    // Only the lines these rows show are worth sanitizing
+    let first = rows
+        .iter()
+        .filter_map(|r| r.old.or(r.new))
+        .min()
+        .unwrap_or(0);
+    let last = rows
+        .iter()
+        .filter_map(|r| r.new.or(r.old))
+        .max()
+        .map_or(0, |i| i + 1);
+    let shown: Vec<String> = patch
+        .lines()
+        .skip(first)
+        .take(last.saturating_sub(first))
+        .map(|l| l.replace('\t', "    "))
+        .collect();
+    let lines = |i: usize| &shown[i - first];
+    // Borders, the comment marker and the divider take 7 columns
+    let column = (width as usize).saturating_sub(7) / 2;
+    let divider = || Span::styled(" │ ", Style::default().fg(Color::DarkGray));
+
+    rows.iter()
+        .map(|row| {
+            let marked = row
+                .old
+                .into_iter()
+                .chain(row.new)
+                .any(|i| commented(numbers[i]));
+            let mut spans = vec![comment_marker(marked)];
+            match (row.old, row.new) {
+                (Some(i), Some(j)) if i == j => {
+                    let text = lines(i);
+                    if text.starts_with("@@") {
+                        spans.push(Span::styled(text.clone(), Style::default().fg(Color::Cyan)));
+                    } else {
+                        let line = numbers[i];
+                        spans.extend(cell(line.and_then(|l| l.old), text, None, column));
+                        spans.push(divider());
+                        spans.extend(cell(line.and_then(|l| l.new), text, None, column));
+                    }
+                }
+                (old, new) => {
+                    let changed = match (old, new) {
+                        (Some(i), Some(j)) => Some(changed_ranges(lines(i), lines(j))),
+                        _ => None,
+                    };
+                    let side =
+                        |index: Option<usize>,
+                         number: fn(DiffLine) -> Option<u64>,
+                         range: Option<Range<usize>>| match index {
+                            Some(i) => cell(numbers[i].and_then(number), lines(i), range, column),
+                            None => vec![Span::raw(" ".repeat(column))],
+                        };
+                    spans.extend(side(old, |l| l.old, changed.clone().map(|c| c.0)));
+                    spans.push(divider());
+                    spans.extend(side(new, |l| l.new, changed.map(|c| c.1)));
+                }
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect()
+}
+
+/// One side of a split row, cut or padded to `width`, with `changed` (a
+/// char range of `text`) highlighted
+fn cell(
+    number: Option<u64>,
+    text: &str,
+    changed: Option<Range<usize>>,
+    width: usize,
+) -> Vec<Span<'static>> {
+    let number = number.map_or_else(|| " ".repeat(NUMBER_WIDTH), |n| format!("{:>4} ", n));
+    let room = width.saturating_sub(NUMBER_WIDTH);
+    // Cut by display width so wide characters never spill into the next column
+    let mut used = 0;
+    let mut chars = Vec::new();
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > room {
+            break;
+        }
+        used += w;
+        chars.push(c);
+    }
+    let style = Style::default().fg(line_color(text));
+    let piece = |r: Range<usize>| -> String { chars[r].iter().collect() };
+
+    let mut spans = vec![Span::styled(number, Style::default().fg(Color::DarkGray))];
+    match changed.filter(|r| !r.is_empty()) {
+        Some(r) => {
+            let start = r.start.min(chars.len());
+            let end = r.end.min(chars.len());
+            spans.push(Span::styled(piece(0..start), style));
+            spans.push(Span::styled(
+                piece(start..end),
+                style.add_modifier(Modifier::REVERSED),
+            ));
+            spans.push(Span::styled(piece(end..chars.len()), style));
+        }
+        None => spans.push(Span::styled(piece(0..chars.len()), style)),
+    }
+    spans.push(Span::raw(" ".repeat(room - used)));
+    spans
+}
+
+/// Char ranges of a removed and an added line that differ, once their common
+/// prefix and suffix are set aside. The leading `-`/`+` is never counted.
+fn changed_ranges(old: &str, new: &str) -> (Range<usize>, Range<usize>) {
+    let old: Vec<char> = old.chars().skip(1).collect();
+    let new: Vec<char> = new.chars().skip(1).collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    (
+        prefix + 1..old.len() - suffix + 1,
+        prefix + 1..new.len() - suffix + 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_ranges_skip_common_ends() {
+        let (old, new) = changed_ranges("-let x = 1;", "+let x = 42;");
+        assert_eq!(old, 9..10);
+        assert_eq!(new, 9..11);
+        assert_eq!(&"-let x = 1;"[old], "1");
+    }
+
+    #[test]
+    fn changed_ranges_of_identical_content_are_empty() {
+        let (old, new) = changed_ranges("-same", "+same");
+        assert!(old.is_empty() && new.is_empty());
+    }
+
+    #[test]
+    fn cells_fill_their_column() {
+        let spans = cell(Some(7), "+abcdef", Some(2..4), 10);
+        let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "   7 +abcd");
+        assert!(spans[2].style.add_modifier.contains(Modifier::REVERSED));
+        assert_eq!(spans[2].content, "bc");
+
+        // A wide character that would overflow the column is left out
+        let spans = cell(None, "+変更点", None, 11);
+        let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "     +変更 ");
+    }
+}
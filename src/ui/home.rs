@@ -2,20 +2,45 @@ use chrono::Utc;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
 use ratatui::Frame;
 
-use crate::app::{App, HomeSection};
+use crate::app::{App, ClickList, HomeSection};
+use crate::types::MergeableState;
+
+use super::text::{pad, truncate};
+use super::{author_style, format_number, number_width};
+
+/// Days a review request can wait before it's flagged stale
+const STALE_DAYS: i64 = 3;
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
-    // Split the area into two sections: review requests and my PRs
+    // Sections as `[home]` lays them out; a collapsed one keeps just its
+    // bordered title
+    let sections = app.home_sections();
+    let constraints: Vec<Constraint> = sections
+        .iter()
+        .map(|(section, size)| {
+            if app.collapsed.contains(section) {
+                Constraint::Length(2)
+            } else {
+                Constraint::Fill(*size)
+            }
+        })
+        .collect();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints(constraints)
         .split(area);
 
-    render_review_requests(frame, app, chunks[0]);
-    render_my_prs(frame, app, chunks[1]);
+    for (&(section, _), &chunk) in sections.iter().zip(chunks.iter()) {
+        match section {
+            HomeSection::ReviewRequests => render_review_requests(frame, app, chunk),
+            HomeSection::MyPrs => render_my_prs(frame, app, chunk),
+            HomeSection::Recent => render_recent(frame, app, chunk),
+            _ => render_issue_refs(frame, app, chunk, section),
+        }
+    }
 }
 
 fn render_review_requests(frame: &mut Frame, app: &App, area: Rect) {
@@ -31,10 +56,7 @@ fn render_review_requests(frame: &mut Frame, app: &App, area: Rect) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(Span::styled(
-            format!(" Review Requests ({}) ", app.review_requests.len()),
-            title_style,
-        ))
+        .title(Span::styled(review_title(app), title_style))
         .border_style(if is_active {
             Style::default().fg(Color::Yellow)
         } else {
@@ -50,7 +72,8 @@ fn render_review_requests(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     let w = area.width.saturating_sub(2) as usize;
-    let fixed = 57; // repo(25) + space(1) + #num(6) + space(1) + spaces(2) + @author(~16) + spaces(2) + age(~4)
+    let num_w = number_width(app.review_requests.iter().map(|r| r.pr_number));
+    let fixed = 51 + num_w; // repo(25) + space(1) + #num + space(1) + spaces(2) + @author(~16) + spaces(2) + age(~4)
     let flex = w.saturating_sub(fixed).max(10);
 
     let items: Vec<ListItem> = app
@@ -68,52 +91,74 @@ fn render_review_requests(frame: &mut Frame, app: &App, area: Rect) {
             };
 
             let repo = format!("{}/{}", req.repo_owner, req.repo_name);
-            let repo_display = if repo.len() > 25 {
-                format!("{}...", &repo[..22])
-            } else {
-                repo
-            };
+            let repo_display = truncate(&repo, 25);
 
-            let title = if req.pr_title.len() > flex {
-                format!("{}...", &req.pr_title[..flex.saturating_sub(3)])
-            } else {
-                req.pr_title.clone()
-            };
+            let title = truncate(&req.pr_title, flex);
 
             let age = format_age(req.updated_at);
+            let age_style = staleness_style(req.updated_at);
 
             let line = Line::from(vec![
-                Span::styled(
-                    format!("{:<25}", repo_display),
-                    Style::default().fg(Color::Cyan),
-                ),
+                Span::styled(pad(&repo_display, 25), Style::default().fg(Color::Cyan)),
                 Span::raw(" "),
                 Span::styled(
-                    format!("#{:<5}", req.pr_number),
+                    format_number(req.pr_number, num_w),
                     Style::default().fg(Color::Gray),
                 ),
                 Span::raw(" "),
-                Span::styled(format!("{:<flex$}", title), style),
+                Span::styled(pad(&title, flex), style),
                 Span::raw("  "),
-                Span::styled(format!("@{}", req.author), Style::default().fg(Color::Gray)),
+                Span::styled(
+                    format!("@{}", req.author),
+                    author_style(app, &req.author, Style::default().fg(Color::Gray)),
+                ),
                 Span::raw("  "),
-                Span::styled(age, Style::default().fg(Color::DarkGray)),
+                Span::styled(age, age_style),
             ]);
 
             ListItem::new(line)
         })
         .collect();
 
+    let len = items.len();
     let list = List::new(items)
         .block(block)
         .highlight_style(Style::default().bg(Color::DarkGray));
 
-    let mut state = ListState::default();
-    if is_active && !app.review_requests.is_empty() {
-        state.select(Some(app.review_index));
-    }
+    let selected = (is_active && !app.review_requests.is_empty()).then_some(app.review_index);
+    let mut state = super::list_state(app, ClickList::ReviewRequests, area, len, selected);
 
     frame.render_stateful_widget(list, area, &mut state);
+    super::register_list(frame, app, area, ClickList::ReviewRequests, len, &state);
+}
+
+/// " Review Requests (5) · 2 stale · oldest first "
+fn review_title(app: &App) -> String {
+    let stale = app
+        .review_requests
+        .iter()
+        .filter(|r| is_stale(r.updated_at))
+        .count();
+    let mut title = format!(" Review Requests ({})", app.review_requests.len());
+    if stale > 0 {
+        title.push_str(&format!(" · {} stale", stale));
+    }
+    format!("{} · {} ", title, app.review_sort.label())
+}
+
+fn is_stale(since: chrono::DateTime<chrono::Utc>) -> bool {
+    Utc::now().signed_duration_since(since) > chrono::Duration::days(STALE_DAYS)
+}
+
+/// Waiting more than a day turns the age yellow, more than `STALE_DAYS` red
+fn staleness_style(since: chrono::DateTime<chrono::Utc>) -> Style {
+    if is_stale(since) {
+        Style::default().fg(Color::Red)
+    } else if Utc::now().signed_duration_since(since) > chrono::Duration::days(1) {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    }
 }
 
 fn render_my_prs(frame: &mut Frame, app: &App, area: Rect) {
@@ -148,7 +193,8 @@ fn render_my_prs(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     let w = area.width.saturating_sub(2) as usize;
-    let fixed = 43; // repo(25) + space(1) + #num(6) + space(1) + spaces(2) + status(~8)
+    let num_w = number_width(app.my_prs.iter().map(|p| p.number));
+    let fixed = 37 + num_w; // repo(25) + space(1) + #num + space(1) + spaces(2) + status(~8)
     let flex = w.saturating_sub(fixed).max(10);
 
     let items: Vec<ListItem> = app
@@ -166,17 +212,9 @@ fn render_my_prs(frame: &mut Frame, app: &App, area: Rect) {
             };
 
             let repo = format!("{}/{}", pr.repo_owner, pr.repo_name);
-            let repo_display = if repo.len() > 25 {
-                format!("{}...", &repo[..22])
-            } else {
-                repo
-            };
+            let repo_display = truncate(&repo, 25);
 
-            let title = if pr.title.len() > flex {
-                format!("{}...", &pr.title[..flex.saturating_sub(3)])
-            } else {
-                pr.title.clone()
-            };
+            let title = truncate(&pr.title, flex);
 
             let status = pr.checks_status.to_string();
             let status_color = match pr.checks_status {
@@ -186,36 +224,260 @@ fn render_my_prs(frame: &mut Frame, app: &App, area: Rect) {
                 crate::types::ChecksStatus::None => Color::Gray,
             };
 
+            let mut spans = vec![
+                Span::styled(pad(&repo_display, 25), Style::default().fg(Color::Cyan)),
+                Span::raw(" "),
+                Span::styled(
+                    format_number(pr.number, num_w),
+                    Style::default().fg(Color::Gray),
+                ),
+                Span::raw(" "),
+                Span::styled(pad(&title, flex), style),
+                Span::raw("  "),
+                Span::styled(status, Style::default().fg(status_color)),
+            ];
+            match pr.mergeable {
+                MergeableState::Conflicts => {
+                    spans.push(Span::styled(" conflicts", Style::default().fg(Color::Red)))
+                }
+                MergeableState::Draft => {
+                    spans.push(Span::styled(" draft", Style::default().fg(Color::Gray)))
+                }
+                _ => {}
+            }
+            let line = Line::from(spans);
+
+            ListItem::new(line)
+        })
+        .collect();
+
+    let len = items.len();
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray));
+
+    let selected = (is_active && !app.my_prs.is_empty()).then_some(app.my_pr_index);
+    let mut state = super::list_state(app, ClickList::MyPrs, area, len, selected);
+
+    frame.render_stateful_widget(list, area, &mut state);
+    super::register_list(frame, app, area, ClickList::MyPrs, len, &state);
+}
+
+/// "Assigned to you", "Mentions" or a saved search: issues and PRs from
+/// anywhere on the forge
+fn render_issue_refs(frame: &mut Frame, app: &App, area: Rect, section: HomeSection) {
+    let is_active = app.home_section == section;
+    let (name, empty_text, refs, selected, click) = match section {
+        HomeSection::Assigned => (
+            "Assigned to You".to_string(),
+            "No issues assigned to you",
+            &app.assigned,
+            app.assigned_index,
+            ClickList::Assigned,
+        ),
+        HomeSection::Saved => (
+            format!(
+                "Saved: {}",
+                app.searches
+                    .get(app.search_choice)
+                    .map_or("", |(name, _)| name.as_str())
+            ),
+            "Nothing matches",
+            &app.saved,
+            app.saved_index,
+            ClickList::Saved,
+        ),
+        _ => (
+            "Mentions".to_string(),
+            "No open mentions",
+            &app.mentions,
+            app.mentions_index,
+            ClickList::Mentions,
+        ),
+    };
+    let count = match app.saved_pagination.total_count {
+        Some(total) if section == HomeSection::Saved && total > refs.len() as u64 => {
+            format!("{} of {}", refs.len(), total)
+        }
+        _ => refs.len().to_string(),
+    };
+
+    let title_style = if is_active {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Gray)
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled(format!(" {} ({}) ", name, count), title_style))
+        .border_style(if is_active {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        });
+
+    if refs.is_empty() && !app.loading {
+        let empty = Paragraph::new(empty_text)
+            .block(block)
+            .style(Style::default().fg(Color::Gray));
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let w = area.width.saturating_sub(2) as usize;
+    let num_w = number_width(refs.iter().map(|r| r.number));
+    let fixed = 57 + num_w; // repo(25) + space(1) + kind(5) + space(1) + #num + space(1) + spaces(2) + @author(~16) + spaces(2) + age(~4)
+    let flex = w.saturating_sub(fixed).max(10);
+
+    let items: Vec<ListItem> = refs
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let is_selected = is_active && i == selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+
+            let repo = format!("{}/{}", item.repo_owner, item.repo_name);
+            let repo_display = truncate(&repo, 25);
+
+            let title = truncate(&item.title, flex);
+
             let line = Line::from(vec![
+                Span::styled(pad(&repo_display, 25), Style::default().fg(Color::Cyan)),
+                Span::raw(" "),
                 Span::styled(
-                    format!("{:<25}", repo_display),
-                    Style::default().fg(Color::Cyan),
+                    if item.is_pr { "PR   " } else { "issue" },
+                    Style::default().fg(Color::DarkGray),
                 ),
                 Span::raw(" "),
                 Span::styled(
-                    format!("#{:<5}", pr.number),
+                    format_number(item.number, num_w),
                     Style::default().fg(Color::Gray),
                 ),
                 Span::raw(" "),
-                Span::styled(format!("{:<flex$}", title), style),
+                Span::styled(pad(&title, flex), style),
                 Span::raw("  "),
-                Span::styled(status, Style::default().fg(status_color)),
+                Span::styled(
+                    format!("@{}", item.author),
+                    author_style(app, &item.author, Style::default().fg(Color::Gray)),
+                ),
+                Span::raw("  "),
+                Span::styled(
+                    format_age(item.updated_at),
+                    Style::default().fg(Color::DarkGray),
+                ),
             ]);
 
             ListItem::new(line)
         })
         .collect();
 
+    let len = items.len();
     let list = List::new(items)
         .block(block)
         .highlight_style(Style::default().bg(Color::DarkGray));
 
-    let mut state = ListState::default();
-    if is_active && !app.my_prs.is_empty() {
-        state.select(Some(app.my_pr_index));
+    let selected = (is_active && !refs.is_empty()).then_some(selected);
+    let mut state = super::list_state(app, click, area, len, selected);
+
+    frame.render_stateful_widget(list, area, &mut state);
+    super::register_list(frame, app, area, click, len, &state);
+}
+
+fn render_recent(frame: &mut Frame, app: &App, area: Rect) {
+    let is_active = app.home_section == HomeSection::Recent;
+
+    let title_style = if is_active {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Gray)
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled(
+            format!(" Recent ({}) ", app.recent.len()),
+            title_style,
+        ))
+        .border_style(if is_active {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        });
+
+    if app.recent.is_empty() {
+        let empty = Paragraph::new("Nothing visited yet")
+            .block(block)
+            .style(Style::default().fg(Color::Gray));
+        frame.render_widget(empty, area);
+        return;
     }
 
+    let w = area.width.saturating_sub(2) as usize;
+    let num_w = number_width(app.recent.iter().filter_map(|r| r.number));
+    let fixed = 33 + num_w; // repo(25) + space(1) + #num + space(1) + spaces(2) + age(~4)
+    let flex = w.saturating_sub(fixed).max(10);
+
+    let items: Vec<ListItem> = app
+        .recent
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let is_selected = is_active && i == app.recent_index;
+            let style = if is_selected {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+
+            let repo = format!("{}/{}", item.owner, item.repo);
+            let repo_display = truncate(&repo, 25);
+
+            let (number, title) = match item.number {
+                Some(n) => (format_number(n, num_w), item.title.clone()),
+                None => (" ".repeat(num_w), "(repository)".to_string()),
+            };
+            let title = truncate(&title, flex);
+
+            let line = Line::from(vec![
+                Span::styled(pad(&repo_display, 25), Style::default().fg(Color::Cyan)),
+                Span::raw(" "),
+                Span::styled(number, Style::default().fg(Color::Gray)),
+                Span::raw(" "),
+                Span::styled(pad(&title, flex), style),
+                Span::raw("  "),
+                Span::styled(
+                    format_age(item.visited_at),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]);
+
+            ListItem::new(line)
+        })
+        .collect();
+
+    let len = items.len();
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray));
+
+    let selected = (is_active).then_some(app.recent_index);
+    let mut state = super::list_state(app, ClickList::Recent, area, len, selected);
+
     frame.render_stateful_widget(list, area, &mut state);
+    super::register_list(frame, app, area, ClickList::Recent, len, &state);
 }
 
 fn format_age(dt: chrono::DateTime<chrono::Utc>) -> String {
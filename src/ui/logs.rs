@@ -0,0 +1,53 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::App;
+
+use super::highlight_line;
+
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let title = match &app.log_path {
+        Some(path) => format!(" Log: {} ", path.display()),
+        None => " Log ".to_string(),
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
+    frame.render_widget(Clear, area);
+
+    if app.log_lines.is_empty() {
+        let empty = Paragraph::new("Nothing logged yet. Raise [log] level in the config for more.")
+            .block(block)
+            .style(Style::default().fg(Color::Gray));
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let inner_height = area.height.saturating_sub(2) as usize;
+    app.page_height.set(inner_height);
+    let total = app.log_lines.len();
+    let scroll_offset = app.scroll_offset.min(total.saturating_sub(inner_height));
+
+    let lines: Vec<Line> = app
+        .log_lines
+        .iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .take(inner_height)
+        .map(|(i, line)| highlight_line(line, i, level_style(line), &app.search, &[]))
+        .collect();
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+    super::render_scrollbar(frame, area, total, scroll_offset);
+}
+
+/// Color by the level `tracing`'s formatter writes after the timestamp
+fn level_style(line: &str) -> Style {
+    let level = line.split_whitespace().nth(1).unwrap_or("");
+    match level {
+        "ERROR" => Style::default().fg(Color::Red),
+        "WARN" => Style::default().fg(Color::Yellow),
+        "DEBUG" | "TRACE" => Style::default().fg(Color::DarkGray),
+        _ => Style::default(),
+    }
+}
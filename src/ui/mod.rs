@@ -1,28 +1,265 @@
+mod browse;
 mod commit_detail;
+mod diff_view;
 mod home;
+mod logs;
 mod popup;
 mod pr_detail;
 mod repo_list;
 mod repo_view;
+#[cfg(test)]
+mod snapshot_tests;
+mod text;
 
-pub fn format_count(loaded: usize, total: Option<u64>) -> String {
-    match total {
+/// Parse a forge label color ("d73a4a" or "#d73a4a")
+fn parse_hex(hex: &str) -> Option<(u8, u8, u8)> {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// A forge label color as the terminal can show it
+pub fn hex_color(hex: &str) -> Option<Color> {
+    parse_hex(hex).map(fit_color)
+}
+
+/// Exact RGB on truecolor terminals, the nearest 256-color entry elsewhere
+fn fit_color((r, g, b): (u8, u8, u8)) -> Color {
+    static TRUECOLOR: OnceLock<bool> = OnceLock::new();
+    let truecolor = *TRUECOLOR.get_or_init(|| {
+        matches!(
+            std::env::var("COLORTERM").as_deref(),
+            Ok("truecolor" | "24bit")
+        )
+    });
+    if truecolor {
+        Color::Rgb(r, g, b)
+    } else {
+        Color::Indexed(nearest_ansi256(r, g, b))
+    }
+}
+
+/// Closest xterm-256 entry, from the 6x6x6 color cube or the gray ramp
+fn nearest_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let level = |v: u8| {
+        (0..LEVELS.len())
+            .min_by_key(|&i| (LEVELS[i] as i32 - v as i32).abs())
+            .unwrap_or(0)
+    };
+    let dist = |(x, y, z): (u8, u8, u8)| {
+        [(x, r), (y, g), (z, b)]
+            .iter()
+            .map(|&(a, b)| (a as i32 - b as i32).pow(2))
+            .sum::<i32>()
+    };
+
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (LEVELS[ri], LEVELS[gi], LEVELS[bi]);
+    // Gray ramp 232..=255 runs from 8 to 238 in steps of 10
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let step = (avg.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + 10 * step;
+
+    if dist((gray, gray, gray)) < dist(cube) {
+        232 + step
+    } else {
+        16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8
+    }
+}
+
+/// Label name on its own color, with black or white text for contrast.
+/// Labels without a color fall back to magenta text.
+pub fn label_chip(label: &Label) -> Span<'static> {
+    let text = format!(" {} ", label.name);
+    match label.color.as_deref().and_then(parse_hex) {
+        Some((r, g, b)) => {
+            let luma = (299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000;
+            let fg = if luma > 140 {
+                Color::Black
+            } else {
+                Color::White
+            };
+            Span::styled(text, Style::default().fg(fg).bg(fit_color((r, g, b))))
+        }
+        None => Span::styled(text, Style::default().fg(Color::Magenta)),
+    }
+}
+
+/// Label chips that fit in `width` columns, ending in "+N" for any left out.
+/// Returns the spans and the columns they take.
+pub fn label_chips(labels: &[Label], width: usize) -> (Vec<Span<'static>>, usize) {
+    let mut spans = Vec::new();
+    let mut used = 0;
+    for (i, label) in labels.iter().enumerate() {
+        let gap = usize::from(i > 0);
+        let chip_width = label.name.width() + 2;
+        if used + gap + chip_width > width {
+            if i == 0 && width > 2 {
+                // A lone label too wide for the column is cut rather than hidden
+                let cut = Label {
+                    name: truncate(&label.name, width - 2),
+                    color: label.color.clone(),
+                };
+                used = cut.name.width() + 2;
+                spans.push(label_chip(&cut));
+            } else {
+                let more = format!(" +{}", labels.len() - i);
+                if used + more.len() <= width {
+                    used += more.len();
+                    spans.push(Span::styled(more, Style::default().fg(Color::Gray)));
+                }
+            }
+            break;
+        }
+        if gap > 0 {
+            spans.push(Span::raw(" "));
+        }
+        spans.push(label_chip(label));
+        used += gap + chip_width;
+    }
+    (spans, used)
+}
+
+/// Width of a "#123" column that fits every number in the list; never
+/// narrower than "#12345" so small repos keep their usual layout
+pub fn number_width(numbers: impl IntoIterator<Item = u64>) -> usize {
+    let digits = numbers.into_iter().max().unwrap_or(0).to_string().len();
+    1 + digits.max(5)
+}
+
+/// "#123" padded to a column from `number_width`
+pub fn format_number(number: u64, width: usize) -> String {
+    format!("{:<width$}", format!("#{}", number))
+}
+
+/// "loaded/total", plus "page X of Y" once a list spans several pages
+pub fn format_count(loaded: usize, pagination: &PaginationState) -> String {
+    let count = match pagination.total_count {
         Some(t) => format!("{}/{}", loaded, t),
         None => format!("{}", loaded),
+    };
+    match pagination.total_pages {
+        Some(pages) if pages > 1 => format!(
+            "{} · page {} of {}",
+            count,
+            pagination.page.min(pages),
+            pages
+        ),
+        _ => count,
+    }
+}
+
+/// Record a bordered list drawn in `area`: its rows become clickable, its
+/// height sets the PageUp/PageDown step, and a scrollbar shows when it overflows
+pub fn register_list(
+    frame: &mut Frame,
+    app: &App,
+    area: Rect,
+    list: ClickList,
+    len: usize,
+    state: &ListState,
+) {
+    let inner = area.inner(Margin::new(1, 1));
+    app.page_height.set(inner.height as usize);
+    app.list_offsets.borrow_mut().insert(list, state.offset());
+    render_scrollbar(frame, area, len, state.offset());
+    app.click_map.borrow_mut().push((
+        inner,
+        ClickTarget::Rows {
+            list,
+            first: state.offset(),
+        },
+    ));
+}
+
+/// Selection state for a bordered list drawn in `area`, scrolled where it was
+/// last frame, or with the selection mid-view right after `zz`
+pub fn list_state(
+    app: &App,
+    list: ClickList,
+    area: Rect,
+    len: usize,
+    selected: Option<usize>,
+) -> ListState {
+    let height = area.height.saturating_sub(2) as usize;
+    let offset = match selected {
+        Some(i) if app.center_selection.get() => i.saturating_sub(height / 2),
+        _ => app.list_offsets.borrow().get(&list).copied().unwrap_or(0),
+    };
+    // A list that shrank shouldn't leave blank rows under its end
+    ListState::default()
+        .with_offset(offset.min(len.saturating_sub(height)))
+        .with_selected(selected)
+}
+
+/// Rows built beyond either edge of a windowed list
+const WINDOW_MARGIN: usize = 4;
+
+/// Rows of a `len`-row list worth building items for when `state` is drawn
+/// in `area`: those in view plus a margin. Scrolls `state` to its selection
+/// first, the way the list widget would, since that only sees the window.
+pub fn visible_rows(state: &mut ListState, area: Rect, len: usize) -> Range<usize> {
+    let height = (area.height.saturating_sub(2) as usize).max(1);
+    let mut offset = state.offset();
+    if let Some(selected) = state.selected() {
+        if selected < offset {
+            offset = selected;
+        } else if selected >= offset + height {
+            offset = selected + 1 - height;
+        }
     }
+    let offset = offset.min(len.saturating_sub(height));
+    *state.offset_mut() = offset;
+    offset.saturating_sub(WINDOW_MARGIN)..(offset + height + WINDOW_MARGIN).min(len)
+}
+
+/// State to draw a list holding only the `rows` window of the one `state`
+/// describes
+pub fn window_state(state: &ListState, rows: &Range<usize>) -> ListState {
+    ListState::default()
+        .with_offset(state.offset().saturating_sub(rows.start))
+        .with_selected(state.selected().map(|i| i.saturating_sub(rows.start)))
 }
 
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::layout::{Constraint, Direction, Layout, Margin, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::Paragraph;
+use ratatui::widgets::{ListState, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState};
 use ratatui::Frame;
 
-use crate::app::{App, InputMode, Screen, SearchState};
+/// Scrollbar over the right border of a bordered `area` showing `total` lines
+/// from `offset` on; nothing when they all fit
+pub fn render_scrollbar(frame: &mut Frame, area: Rect, total: usize, offset: usize) {
+    let inner = area.height.saturating_sub(2) as usize;
+    if total <= inner {
+        return;
+    }
+    let mut state = ScrollbarState::new(total - inner + 1)
+        .position(offset)
+        .viewport_content_length(inner);
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_stateful_widget(scrollbar, area.inner(Margin::new(0, 1)), &mut state);
+}
 
-use crate::action::ConfirmAction;
+use crate::app::{App, ClickList, ClickTarget, InputMode, PaginationState, Screen, SearchState};
+
+use crate::action::{ConfirmAction, FormTarget};
+use crate::links::LinkRule;
+use crate::types::{format_size, Label};
+use std::ops::Range;
+use std::sync::OnceLock;
+use text::truncate;
+use unicode_width::UnicodeWidthStr;
 
 pub fn render(frame: &mut Frame, app: &App) {
+    app.click_map.borrow_mut().clear();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -37,12 +274,16 @@ pub fn render(frame: &mut Frame, app: &App) {
     match app.screen {
         Screen::Home => home::render(frame, app, chunks[1]),
         Screen::RepoList => repo_list::render(frame, app, chunks[1]),
+        Screen::Browse => browse::render(frame, app, chunks[1]),
         Screen::RepoView => repo_view::render(frame, app, chunks[1]),
         Screen::PrDetail => pr_detail::render(frame, app, chunks[1]),
         Screen::CommitDetail => commit_detail::render(frame, app, chunks[1]),
+        Screen::DiffView => diff_view::render(frame, app, chunks[1]),
+        Screen::Logs => logs::render(frame, app, chunks[1]),
     }
 
     render_status_bar(frame, app, chunks[2]);
+    app.center_selection.set(false);
 
     // Render popup overlays
     match &app.input_mode {
@@ -52,19 +293,157 @@ pub fn render(frame: &mut Frame, app: &App) {
                     ConfirmAction::ClosePr(n) => {
                         ("Close PR".to_string(), format!("Close PR #{}?", n))
                     }
-                    ConfirmAction::MergePr { number, method } => (
-                        "Merge PR".to_string(),
-                        format!("Merge PR #{} via {}?", number, method),
-                    ),
+                    ConfirmAction::MergePr {
+                        number,
+                        method,
+                        delete_branch,
+                        auto,
+                    } => {
+                        let mut message = format!("Merge PR #{} via {}", number, method);
+                        if *auto {
+                            message.push_str(" once checks pass");
+                        }
+                        if let Some(branch) = delete_branch {
+                            message.push_str(&format!(" and delete {}", branch));
+                        }
+                        message.push('?');
+                        ("Merge PR".to_string(), message)
+                    }
                     ConfirmAction::CloseIssue(n) => {
                         ("Close Issue".to_string(), format!("Close issue #{}?", n))
                     }
+                    ConfirmAction::CloseMarked(numbers) => {
+                        let noun = crate::app::item_noun(
+                            if app.repo_tab == crate::action::RepoTab::PullRequests {
+                                "pr"
+                            } else {
+                                "issue"
+                            },
+                            numbers.len(),
+                        );
+                        (
+                            "Close Marked".to_string(),
+                            format!("Close {} marked {}?", numbers.len(), noun),
+                        )
+                    }
+                    ConfirmAction::ForkRepo { owner, repo } => (
+                        "Fork".to_string(),
+                        format!("Fork {}/{} into your account?", owner, repo),
+                    ),
+                    ConfirmAction::SyncFork {
+                        branch,
+                        upstream,
+                        behind,
+                    } => (
+                        "Sync Fork".to_string(),
+                        format!(
+                            "Update {} with {} commit{} from {}?",
+                            branch,
+                            behind,
+                            if *behind == 1 { "" } else { "s" },
+                            upstream
+                        ),
+                    ),
+                    ConfirmAction::ForgetMissing {
+                        owner,
+                        repo,
+                        number: Some(number),
+                    } => (
+                        "Not Found".to_string(),
+                        format!(
+                            "PR #{} isn't on {}/{} anymore.\nRemove it from recent items and the cache?",
+                            number, owner, repo
+                        ),
+                    ),
+                    ConfirmAction::ForgetMissing {
+                        owner,
+                        repo,
+                        number: None,
+                    } => (
+                        "Not Found".to_string(),
+                        format!(
+                            "{}/{} was deleted, or this token can't see it.\nRemove it from recent items and the cache?",
+                            owner, repo
+                        ),
+                    ),
+                    ConfirmAction::RevertPr { number, commits } => (
+                        "Revert PR".to_string(),
+                        std::iter::once(format!(
+                            "Open a PR reverting {} of #{}?",
+                            if commits.len() == 1 {
+                                "1 commit".to_string()
+                            } else {
+                                format!("{} commits", commits.len())
+                            },
+                            number
+                        ))
+                        .chain(commits.iter().map(|c| {
+                            format!(
+                                "{} {}",
+                                &c.sha[..7.min(c.sha.len())],
+                                c.message.lines().next().unwrap_or("")
+                            )
+                        }))
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    ),
                 };
                 popup::render_confirm(frame, &title, &message);
             }
         }
         InputMode::SelectPopup => {
-            popup::render_select(frame, &app.popup_title, &app.popup_items, app.popup_index);
+            // Merging is allowed but not clean: say why before the user picks a method
+            let warning = match &app.current_pr {
+                Some(pr) if app.popup_title == "Merge Method" => {
+                    pr.mergeable_state.merge_warning().map(str::to_string)
+                }
+                _ if app.popup_title == "Quit grit?" => Some(app.quit_summary()),
+                _ => None,
+            };
+            popup::render_select(
+                frame,
+                &app.popup_title,
+                &app.popup_items,
+                app.popup_index,
+                warning.as_deref(),
+            );
+        }
+        InputMode::Insights => {
+            if let (Some((owner, repo)), Some(stats)) = (&app.current_repo, &app.repo_stats) {
+                popup::render_insights(frame, &format!("{}/{}", owner, repo), stats);
+            }
+        }
+        InputMode::Palette => {
+            let matches = app.palette_matches();
+            let entries: Vec<(&str, &str)> =
+                matches.iter().map(|e| (e.name, e.key.as_str())).collect();
+            popup::render_palette(frame, &app.palette_query, &entries, app.popup_index);
+        }
+        InputMode::Form => {
+            let title = match app.form.target {
+                FormTarget::NewIssue => "New Issue".to_string(),
+                FormTarget::Issue(n) => format!("Edit Issue #{}", n),
+                FormTarget::Pr(n) => format!("Edit PR #{}", n),
+            };
+            popup::render_form(frame, &title, &app.form.values, app.form.focus);
+        }
+        InputMode::MultiSelectPopup => {
+            // Only label names carry a color
+            let items: Vec<(String, Option<Color>)> = if app.popup_title == "Edit Labels" {
+                app.labels
+                    .iter()
+                    .map(|l| (l.name.clone(), l.color.as_deref().and_then(hex_color)))
+                    .collect()
+            } else {
+                app.popup_items.iter().map(|i| (i.clone(), None)).collect()
+            };
+            popup::render_multi_select(
+                frame,
+                &app.popup_title,
+                &items,
+                &app.popup_checked,
+                app.popup_index,
+            );
         }
         _ => {}
     }
@@ -74,6 +453,8 @@ fn render_header(frame: &mut Frame, app: &App, area: Rect) {
     let title = match app.screen {
         Screen::Home => "grit - Home".to_string(),
         Screen::RepoList => "grit - Repositories".to_string(),
+        Screen::Browse => "grit - Browse".to_string(),
+        Screen::Logs => "grit - Logs".to_string(),
         Screen::RepoView => {
             if let Some((owner, repo)) = &app.current_repo {
                 format!("grit - {}/{}", owner, repo)
@@ -88,15 +469,15 @@ fn render_header(frame: &mut Frame, app: &App, area: Rect) {
                 "grit - Pull Request".to_string()
             }
         }
+        Screen::DiffView => match (&app.current_pr, app.pr_files.get(app.file_index)) {
+            (Some(pr), Some(file)) => format!("grit - PR #{}: {}", pr.number, file.filename),
+            _ => "grit - Diff".to_string(),
+        },
         Screen::CommitDetail => {
             if let Some(commit) = &app.current_commit {
                 let short_sha = &commit.sha[..7.min(commit.sha.len())];
                 let msg_line = commit.message.lines().next().unwrap_or("");
-                let msg = if msg_line.len() > 50 {
-                    format!("{}...", &msg_line[..47])
-                } else {
-                    msg_line.to_string()
-                };
+                let msg = truncate(msg_line, 50);
                 format!("grit - Commit {}: {}", short_sha, msg)
             } else {
                 "grit - Commit".to_string()
@@ -104,17 +485,75 @@ fn render_header(frame: &mut Frame, app: &App, area: Rect) {
         }
     };
 
-    let header = Paragraph::new(Line::from(vec![Span::styled(
+    let mut spans = vec![Span::styled(
         title,
         Style::default()
             .fg(Color::Cyan)
             .add_modifier(Modifier::BOLD),
-    )]))
-    .style(Style::default().bg(Color::DarkGray));
+    )];
+    if app.workspaces.len() > 1 {
+        for (i, label) in app.workspace_labels().into_iter().enumerate() {
+            let style = if i == app.workspace_index {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            spans.push(Span::styled(format!("  {}:{}", i + 1, label), style));
+        }
+    }
+    if app.offline {
+        let label = match app.cached_at.get().and_then(|t| t.elapsed().ok()) {
+            Some(age) => format!("  OFFLINE (cached {} ago)", format_elapsed(age)),
+            None => "  OFFLINE".to_string(),
+        };
+        spans.push(Span::styled(
+            label,
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if !app.alerts.is_empty() {
+        spans.push(Span::styled(
+            format!("  ● {} new (!)", app.alerts.len()),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if !app.outbox.is_empty() {
+        spans.push(Span::styled(
+            format!("  {} queued", app.outbox.len()),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    let running = app.tasks.borrow().len();
+    if running > 0 {
+        spans.push(Span::styled(
+            format!("  ⟳ {}", running),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    let header = Paragraph::new(Line::from(spans)).style(Style::default().bg(Color::DarkGray));
 
     frame.render_widget(header, area);
 }
 
+/// Coarse age for the header: `45s`, `12m`, `2h`, `3d`
+fn format_elapsed(age: std::time::Duration) -> String {
+    let secs = age.as_secs();
+    if secs >= 86_400 {
+        format!("{}d", secs / 86_400)
+    } else if secs >= 3_600 {
+        format!("{}h", secs / 3_600)
+    } else if secs >= 60 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
 fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     // Search input mode takes over status bar
     if app.input_mode == InputMode::Search {
@@ -127,6 +566,54 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         frame.render_widget(bar, area);
         return;
     }
+    if app.input_mode == InputMode::Comment {
+        let prompt = match &app.current_pr {
+            Some(pr) => format!("comment on #{}: ", pr.number),
+            None => "comment: ".to_string(),
+        };
+        let line = Line::from(vec![
+            Span::styled(prompt, Style::default().fg(Color::Yellow)),
+            Span::styled(&app.comment_input, Style::default().fg(Color::White)),
+            Span::styled("_", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "  Enter: post | Esc: cancel",
+                Style::default().fg(Color::Gray),
+            ),
+        ]);
+        let bar = Paragraph::new(line).style(Style::default().bg(Color::DarkGray));
+        frame.render_widget(bar, area);
+        return;
+    }
+
+    if app.input_mode == InputMode::FindCommit {
+        let line = Line::from(vec![
+            Span::styled("find commit: ", Style::default().fg(Color::Yellow)),
+            Span::styled(&app.find_query, Style::default().fg(Color::White)),
+            Span::styled("_", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "  SHA or message text | Enter: search all repos | Esc: cancel",
+                Style::default().fg(Color::Gray),
+            ),
+        ]);
+        let bar = Paragraph::new(line).style(Style::default().bg(Color::DarkGray));
+        frame.render_widget(bar, area);
+        return;
+    }
+
+    if app.input_mode == InputMode::JumpToNumber {
+        let line = Line::from(vec![
+            Span::styled("#", Style::default().fg(Color::Yellow)),
+            Span::styled(&app.jump_input, Style::default().fg(Color::White)),
+            Span::styled("_", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "  PR or issue number | Enter: open | Esc: cancel",
+                Style::default().fg(Color::Gray),
+            ),
+        ]);
+        let bar = Paragraph::new(line).style(Style::default().bg(Color::DarkGray));
+        frame.render_widget(bar, area);
+        return;
+    }
 
     let status = if let Some(error) = &app.error {
         Line::from(vec![Span::styled(
@@ -138,6 +625,31 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
             "Loading...",
             Style::default().fg(Color::Yellow),
         )])
+    } else if let Some(download) = app.download.as_ref().or(app.diff_download.as_ref()) {
+        let progress = match download.total {
+            Some(total) if total > 0 => format!(
+                "{}% ({} of {})",
+                download.received * 100 / total,
+                format_size(download.received),
+                format_size(total)
+            ),
+            _ => format_size(download.received),
+        };
+        Line::from(vec![Span::styled(
+            format!("Downloading {}: {}", download.name, progress),
+            Style::default().fg(Color::Yellow),
+        )])
+    } else if let Some(bulk) = &app.bulk {
+        Line::from(vec![Span::styled(
+            format!(
+                "{} {}: {} of {}",
+                bulk.op.verb(),
+                crate::app::item_noun(bulk.kind, bulk.total),
+                bulk.done,
+                bulk.total
+            ),
+            Style::default().fg(Color::Yellow),
+        )])
     } else if let Some((msg, instant)) = &app.flash_message {
         if instant.elapsed() < std::time::Duration::from_secs(3) {
             Line::from(vec![Span::styled(
@@ -160,11 +672,12 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         } else {
             0
         };
+        let counter = match app.search_section() {
+            Some(section) => format!("[{}/{} · {}]", current, total, section),
+            None => format!("[{}/{}]", current, total),
+        };
         Line::from(vec![
-            Span::styled(
-                format!("[{}/{}]", current, total),
-                Style::default().fg(Color::Yellow),
-            ),
+            Span::styled(counter, Style::default().fg(Color::Yellow)),
             Span::raw(" "),
             Span::styled(
                 format!("\"{}\"", app.search.query),
@@ -178,32 +691,87 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         ])
     } else {
         let help = match app.screen {
-            Screen::Home => "/ search | r repos | f forge | o open | y yank | Enter open | q quit",
-            Screen::RepoList => "/ search | r refresh | o open | y yank | Enter select | q back",
+            Screen::Home => "/ search | : commands | r/u/^R refresh list/item/all | R repos | s sort reviews | Ctrl+O back | Ctrl+T/N/P/W workspaces | Ctrl+K tasks | B browse | S find commit | f forge | Q saved search | za collapse | o open | y yank | Enter open | q quit",
+            Screen::RepoList => "/ search | : commands | r/u/^R refresh list/item/all | g clone | E edit | * star | W watch | Y fork | O org | B columns | S find commit | o open | y yank | Enter select | q back",
+            Screen::Browse => "h/l column | / search | : commands | r/u/^R refresh list/item/all | O org | B list | S find commit | o open | Enter open | q back",
+            Screen::RepoView if app.marks().is_some() => {
+                "Space mark | V range | x close marked | L label marked | Esc clear marks | j/k move | q back"
+            }
             Screen::RepoView => match app.repo_tab {
                 crate::action::RepoTab::Issues => {
-                    "/ search | x close | C comment | o open | y yank | q back"
+                    "/ search | # jump | N new | e edit | x close | C comment | L labels | M milestone | F filter | Space/V mark | v preview | q back"
                 }
-                _ => "/ search | r refresh | o open | y yank | Enter detail | q back",
+                crate::action::RepoTab::PullRequests => "/ search | # jump | : commands | r/u/^R refresh list/item/all | E edit | I insights | */W star/watch | Y/U fork/sync | P filter | Space/V mark | v preview | o open | y yank | Enter detail | q back",
+                crate::action::RepoTab::Actions => "/ search | # jump | : commands | r/^R refresh | w workflows | D download | E edit | o open | y yank | q back",
+                _ => "/ search | # jump | : commands | r/^R refresh | E edit | I insights | */W star/watch | Y/U fork/sync | o open | y yank | Enter detail | q back",
             },
             Screen::PrDetail => {
-                "d diff | m merge | x close | C comment | R review | o open | q back"
+                "d diff | w changes | m merge | x close | c/C comment | + react | R review | A reviewer | L labels | {/} file | (/) commit | Tab/n #ref @user | Enter open | e edit file | E edit pr | g checkout | V revert | [/] thread | t resolve | T/b hide | X tasks | o open | q back"
+            }
+            Screen::CommitDetail => "d diff | C comment | / search | : commands | o open | y yank | q back",
+            Screen::Logs => "j/k scroll | g/G top/bottom (follows new lines) | / search | r reload | q back",
+            Screen::DiffView => {
+                "j/k line | v split | c comment | R review | {/} file | / search | d pager | o open | q back"
             }
-            Screen::CommitDetail => "d diff | / search | o open | y yank | q back",
         };
-        Line::from(vec![
-            Span::styled(
-                format!("[{}] ", app.forge_name),
-                Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
-            ),
-            Span::styled(help, Style::default().fg(Color::Gray)),
-        ])
+        let mut spans = vec![Span::styled(
+            format!("[{}] ", app.forge_name),
+            Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+        )];
+        if let Some(keys) = app.pending_keys.get().shown() {
+            spans.push(Span::styled(
+                format!("{} ", keys),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        spans.push(Span::styled(help, Style::default().fg(Color::Gray)));
+        Line::from(spans)
     };
 
     let status_bar = Paragraph::new(status).style(Style::default().bg(Color::DarkGray));
     frame.render_widget(status_bar, area);
 }
 
+/// Style used to set bot accounts apart from people
+pub fn bot_style() -> Style {
+    Style::default()
+        .fg(Color::Magenta)
+        .add_modifier(Modifier::ITALIC)
+}
+
+/// `base` for people, `bot_style()` for accounts the config marks as bots
+pub fn author_style(app: &App, login: &str, base: Style) -> Style {
+    if app.ui_config.is_bot(login) {
+        bot_style()
+    } else {
+        base
+    }
+}
+
+/// `text` with tracker references from `[[ui.links]]` underlined
+pub fn link_spans(links: &[LinkRule], text: &str, base_style: Style) -> Vec<Span<'static>> {
+    let ranges = crate::links::spans(links, text);
+    if ranges.is_empty() {
+        return vec![Span::styled(text.to_string(), base_style)];
+    }
+    let link_style = base_style
+        .fg(Color::Blue)
+        .add_modifier(Modifier::UNDERLINED);
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for range in ranges {
+        if pos < range.start {
+            spans.push(Span::styled(text[pos..range.start].to_string(), base_style));
+        }
+        spans.push(Span::styled(text[range.clone()].to_string(), link_style));
+        pos = range.end;
+    }
+    if pos < text.len() {
+        spans.push(Span::styled(text[pos..].to_string(), base_style));
+    }
+    spans
+}
+
 /// Highlight search matches within a line of text.
 /// Returns an owned `Line<'static>` so callers don't have lifetime issues.
 pub fn highlight_line(
@@ -211,9 +779,10 @@ pub fn highlight_line(
     line_idx: usize,
     base_style: Style,
     search: &SearchState,
+    links: &[LinkRule],
 ) -> Line<'static> {
     if !search.active || search.query.is_empty() || search.content_matches.is_empty() {
-        return Line::from(Span::styled(text.to_string(), base_style));
+        return Line::from(link_spans(links, text, base_style));
     }
 
     // Collect matches for this line
@@ -229,7 +798,7 @@ pub fn highlight_line(
         .collect();
 
     if line_matches.is_empty() {
-        return Line::from(Span::styled(text.to_string(), base_style));
+        return Line::from(link_spans(links, text, base_style));
     }
 
     let mut spans = Vec::new();
@@ -254,3 +823,67 @@ pub fn highlight_line(
 
     Line::from(spans)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(name: &str) -> Label {
+        Label {
+            name: name.to_string(),
+            color: Some("d73a4a".to_string()),
+        }
+    }
+
+    #[test]
+    fn nearest_ansi256_picks_cube_or_gray() {
+        assert_eq!(nearest_ansi256(255, 0, 0), 196);
+        assert_eq!(nearest_ansi256(0, 0, 0), 16);
+        // Mid gray sits closer to the gray ramp than to any cube corner
+        assert_eq!(nearest_ansi256(128, 128, 128), 244);
+    }
+
+    #[test]
+    fn format_elapsed_picks_the_largest_unit() {
+        use std::time::Duration;
+        assert_eq!(format_elapsed(Duration::from_secs(42)), "42s");
+        assert_eq!(format_elapsed(Duration::from_secs(7_300)), "2h");
+        assert_eq!(format_elapsed(Duration::from_secs(3 * 86_400 + 5)), "3d");
+    }
+
+    #[test]
+    fn label_chips_fit_width_and_count_the_rest() {
+        let labels = [label("bug"), label("p1"), label("needs-triage")];
+        let (spans, used) = label_chips(&labels, 14);
+        // " bug " + " " + " p1 " + " +1"
+        assert_eq!(used, 13);
+        assert_eq!(spans.last().unwrap().content, " +1");
+
+        let (spans, used) = label_chips(&labels[2..], 8);
+        assert_eq!(used, 8);
+        assert_eq!(spans[0].content, " nee... ");
+
+        // A wide name is cut on a character boundary, never past the column
+        let (spans, used) = label_chips(&[label("バグ修正")], 7);
+        assert_eq!(used, 7);
+        assert_eq!(spans[0].content, " バ... ");
+    }
+
+    #[test]
+    fn visible_rows_follow_the_selection_past_the_edge() {
+        // 10 rows fit between the borders
+        let area = Rect::new(0, 0, 40, 12);
+        let mut state = ListState::default().with_offset(0).with_selected(Some(25));
+        let rows = visible_rows(&mut state, area, 1000);
+        assert_eq!(state.offset(), 16);
+        assert_eq!(rows, 12..30);
+
+        let window = window_state(&state, &rows);
+        assert_eq!((window.offset(), window.selected()), (4, Some(13)));
+
+        // A list that ends in view keeps its last row on the bottom line
+        let mut state = ListState::default().with_offset(50).with_selected(Some(58));
+        assert_eq!(visible_rows(&mut state, area, 60), 46..60);
+        assert_eq!(state.offset(), 50);
+    }
+}
@@ -4,14 +4,20 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
 use ratatui::Frame;
 
+use crate::app::FORM_FIELDS;
+use crate::types::{RepoStats, RECENT_ACTIVITY_DAYS};
+
+use super::text::pad;
+
 /// Render a centered confirmation popup: [y]es / [n]o
 pub fn render_confirm(frame: &mut Frame, title: &str, message: &str) {
-    let area = centered_rect(50, 7, frame.area());
+    let message: Vec<Line> = message.lines().map(Line::raw).collect();
+    let area = centered_rect(50, message.len().min(12) as u16 + 6, frame.area());
     frame.render_widget(Clear, area);
 
-    let lines = vec![
-        Line::from(""),
-        Line::from(Span::raw(message)),
+    let mut lines = vec![Line::from("")];
+    lines.extend(message);
+    lines.extend([
         Line::from(""),
         Line::from(vec![
             Span::styled("[y]", Style::default().fg(Color::Green)),
@@ -19,7 +25,7 @@ pub fn render_confirm(frame: &mut Frame, title: &str, message: &str) {
             Span::styled("[n]", Style::default().fg(Color::Red)),
             Span::raw("o"),
         ]),
-    ];
+    ]);
 
     let popup = Paragraph::new(lines)
         .block(
@@ -36,7 +42,13 @@ pub fn render_confirm(frame: &mut Frame, title: &str, message: &str) {
 }
 
 /// Render a centered selectable list popup
-pub fn render_select(frame: &mut Frame, title: &str, items: &[String], selected: usize) {
+pub fn render_select(
+    frame: &mut Frame,
+    title: &str,
+    items: &[String],
+    selected: usize,
+    warning: Option<&str>,
+) {
     let height = (items.len() + 2).min(12) as u16; // +2 for borders
     let area = centered_rect(40, height, frame.area());
     frame.render_widget(Clear, area);
@@ -60,20 +72,302 @@ pub fn render_select(frame: &mut Frame, title: &str, items: &[String], selected:
         })
         .collect();
 
-    let list = List::new(list_items).block(
-        Block::default().borders(Borders::ALL).title(Span::styled(
+    let mut block = Block::default().borders(Borders::ALL).title(Span::styled(
+        format!(" {} ", title),
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ));
+    if let Some(warning) = warning {
+        block = block.title_bottom(Span::styled(
+            format!(" ⚠ {} ", warning),
+            Style::default().fg(Color::Red),
+        ));
+    }
+
+    let list = List::new(list_items).block(block);
+
+    let mut state = ListState::default();
+    state.select(Some(selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// Render a centered checkbox list popup; each item carries an optional swatch color
+pub fn render_multi_select(
+    frame: &mut Frame,
+    title: &str,
+    items: &[(String, Option<Color>)],
+    checked: &[bool],
+    selected: usize,
+) {
+    let height = (items.len() + 2).min(16) as u16; // +2 for borders
+    let area = centered_rect(40, height, frame.area());
+    frame.render_widget(Clear, area);
+
+    let list_items: Vec<ListItem> = items
+        .iter()
+        .enumerate()
+        .map(|(i, (name, color))| {
+            let style = if i == selected {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let prefix = if i == selected { "> " } else { "  " };
+            let check = if checked.get(i).copied().unwrap_or(false) {
+                "[x] "
+            } else {
+                "[ ] "
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{}{}", prefix, check), style),
+                Span::styled("● ", Style::default().fg(color.unwrap_or(Color::Gray))),
+                Span::styled(name.clone(), style),
+            ]))
+        })
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled(
             format!(" {} ", title),
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
-        )),
-    );
+        ))
+        .title_bottom(Span::styled(
+            " Space toggle | Enter apply | Esc cancel ",
+            Style::default().fg(Color::Gray),
+        ));
+
+    let list = List::new(list_items).block(block);
 
     let mut state = ListState::default();
     state.select(Some(selected));
     frame.render_stateful_widget(list, area, &mut state);
 }
 
+/// Render the issue form: one line per field of `FORM_FIELDS`, the focused
+/// one highlighted with a cursor
+pub fn render_form(frame: &mut Frame, title: &str, values: &[String], focus: usize) {
+    let area = centered_rect(60, FORM_FIELDS.len() as u16 + 4, frame.area());
+    frame.render_widget(Clear, area);
+
+    let mut lines = vec![Line::from("")];
+    lines.extend(
+        FORM_FIELDS
+            .iter()
+            .zip(values)
+            .enumerate()
+            .map(|(i, (name, value))| {
+                let mut spans = vec![Span::styled(
+                    format!(" {:<10} ", name),
+                    Style::default().fg(Color::Gray),
+                )];
+                if i == focus {
+                    spans[0].style = Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD);
+                    spans.push(Span::raw(value.clone()));
+                    spans.push(Span::styled("█", Style::default().fg(Color::Gray)));
+                } else {
+                    spans.push(Span::raw(value.clone()));
+                }
+                Line::from(spans)
+            }),
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled(
+            format!(" {} ", title),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Span::styled(
+            " Tab next field | comma separates | Enter save | Esc cancel ",
+            Style::default().fg(Color::Gray),
+        ));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Render the command palette: query line on top, matching commands with their key below
+pub fn render_palette(frame: &mut Frame, query: &str, entries: &[(&str, &str)], selected: usize) {
+    let height = (entries.len() + 4).clamp(5, 16) as u16; // borders + query + divider
+    let area = centered_rect(50, height, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default().borders(Borders::ALL).title(Span::styled(
+        " Command Palette ",
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(":", Style::default().fg(Color::Yellow)),
+        Span::raw(query.to_string()),
+        Span::styled("█", Style::default().fg(Color::Gray)),
+    ]));
+    frame.render_widget(input, chunks[0]);
+    frame.render_widget(
+        Paragraph::new("─".repeat(inner.width as usize))
+            .style(Style::default().fg(Color::DarkGray)),
+        chunks[1],
+    );
+
+    if entries.is_empty() {
+        frame.render_widget(
+            Paragraph::new("  No matching commands").style(Style::default().fg(Color::Gray)),
+            chunks[2],
+        );
+        return;
+    }
+
+    let width = chunks[2].width as usize;
+    let list_items: Vec<ListItem> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, (name, key))| {
+            let style = if i == selected {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let prefix = if i == selected { "> " } else { "  " };
+            let text = format!("{}{}", prefix, name);
+            let pad = width.saturating_sub(text.chars().count() + key.len() + 1);
+            ListItem::new(Line::from(vec![
+                Span::styled(text, style),
+                Span::raw(" ".repeat(pad)),
+                Span::styled(key.to_string(), Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(selected));
+    frame.render_stateful_widget(List::new(list_items), chunks[2], &mut state);
+}
+
+/// Columns of a bar in the insights popup
+const BAR_WIDTH: usize = 20;
+/// Width of the insights popup's label column
+const LABEL_WIDTH: usize = 16;
+
+/// Render a centered overview of a repo: issue and PR counts, activity,
+/// languages and top contributors, with bars scaled to the largest value
+pub fn render_insights(frame: &mut Frame, repo: &str, stats: &RepoStats) {
+    let label = |text: &str| Span::raw(format!("{} ", pad(text, LABEL_WIDTH - 1)));
+    let bar = |fraction: f64, color: Color| {
+        let filled = (fraction.clamp(0.0, 1.0) * BAR_WIDTH as f64).round() as usize;
+        vec![
+            Span::styled("█".repeat(filled), Style::default().fg(color)),
+            Span::styled(
+                "░".repeat(BAR_WIDTH - filled),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]
+    };
+    let heading = |text: &str| {
+        Line::from(Span::styled(
+            text.to_string(),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+    let share = |part: u64, whole: u64| match whole {
+        0 => 0.0,
+        whole => part as f64 / whole as f64,
+    };
+
+    let issues = stats.open_issues + stats.closed_issues;
+    let mut issue_line = vec![label("Issues")];
+    issue_line.extend(bar(share(stats.closed_issues, issues), Color::Green));
+    issue_line.push(Span::raw(format!(
+        "  {} open, {} closed",
+        stats.open_issues, stats.closed_issues
+    )));
+
+    let mut pr_line = vec![label("Pull requests")];
+    let merge_rate = stats.merge_rate().unwrap_or(0.0);
+    pr_line.extend(bar(merge_rate, Color::Magenta));
+    pr_line.push(Span::raw(format!(
+        "  {:.0}% merged, {} open",
+        merge_rate * 100.0,
+        stats.open_prs
+    )));
+
+    let mut lines = vec![
+        Line::from(issue_line),
+        Line::from(pr_line),
+        Line::from(vec![
+            label("Activity"),
+            Span::raw(format!(
+                "{} commits in the last {} days",
+                stats.recent_commits, RECENT_ACTIVITY_DAYS
+            )),
+        ]),
+    ];
+
+    if !stats.languages.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(heading("Languages"));
+        let top = stats.languages[0].1;
+        for (name, percent) in &stats.languages {
+            let mut spans = vec![label(name)];
+            spans.extend(bar(percent / top, Color::Yellow));
+            spans.push(Span::raw(format!("  {:.1}%", percent)));
+            lines.push(Line::from(spans));
+        }
+    }
+
+    if !stats.contributors.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(heading("Top contributors"));
+        let top = stats.contributors[0].1;
+        for (name, commits) in &stats.contributors {
+            let mut spans = vec![label(name)];
+            spans.extend(bar(share(*commits, top), Color::Blue));
+            spans.push(Span::raw(format!("  {}", commits)));
+            lines.push(Line::from(spans));
+        }
+    }
+
+    let area = centered_rect(64, lines.len() as u16 + 2, frame.area());
+    frame.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled(
+            format!(" Insights: {} ", repo),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Span::styled(
+            " Esc close ",
+            Style::default().fg(Color::Gray),
+        ));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
 /// Create a centered rect using percentage of the outer rect
 fn centered_rect(width: u16, height: u16, outer: Rect) -> Rect {
     let popup_width = width.min(outer.width);
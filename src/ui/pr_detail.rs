@@ -4,10 +4,11 @@ use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 
-use crate::app::App;
-use crate::types::PrState;
+use crate::app::{App, PrLine};
+use crate::types::{ChecksStatus, MergeableState, PrState, ReviewState};
 
-use super::highlight_line;
+use super::text::pad;
+use super::{author_style, bot_style, highlight_line, label_chips};
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let Some(pr) = &app.current_pr else {
@@ -19,35 +20,210 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         return;
     };
 
+    let review_lines = review_lines(app);
+    // Borders(2) + one row per reviewer, capped so the body keeps most of the space
+    let reviews_height = if review_lines.is_empty() {
+        0
+    } else {
+        review_lines.len().min(MAX_REVIEW_ROWS) as u16 + 2
+    };
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(6), Constraint::Min(0)])
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(6),
+            Constraint::Length(reviews_height),
+            Constraint::Min(0),
+        ])
         .split(area);
 
+    // At-a-glance review progress
+    frame.render_widget(Paragraph::new(progress_line(app, pr)), chunks[0]);
+
     // Header section with PR metadata
-    render_header(frame, pr, chunks[0]);
+    render_header(frame, app, pr, chunks[1]);
+
+    // Reviewers and their verdicts
+    if !review_lines.is_empty() {
+        let reviews = Paragraph::new(review_lines)
+            .block(Block::default().borders(Borders::ALL).title("Reviews"));
+        frame.render_widget(reviews, chunks[2]);
+    }
 
     // Body section with description
-    render_body(frame, app, pr, chunks[1]);
+    render_body(frame, app, chunks[3]);
+}
+
+/// Files viewed, unresolved threads, approvals, checks and the
+/// description's task list in one row.
+/// Green once satisfied, yellow while waiting, red when blocking.
+fn progress_line(app: &App, pr: &crate::types::PullRequest) -> Line<'static> {
+    let separator = || Span::styled(" │ ", Style::default().fg(Color::DarkGray));
+
+    let total_files = pr.stats.changed_files;
+    let files = match &app.viewed_files {
+        Some(viewed) => {
+            let color = if viewed.len() as u64 >= total_files {
+                Color::Green
+            } else {
+                Color::Yellow
+            };
+            Span::styled(
+                format!("Files {}/{} viewed", viewed.len(), total_files),
+                Style::default().fg(color),
+            )
+        }
+        None => Span::raw(format!("Files {}", total_files)),
+    };
+
+    let unresolved = app.review_threads.iter().filter(|t| !t.resolved).count();
+    let threads = Span::styled(
+        format!("Threads {} unresolved", unresolved),
+        Style::default().fg(if unresolved == 0 {
+            Color::Green
+        } else {
+            Color::Red
+        }),
+    );
+
+    let approved = app.pr_reviews.approval_count();
+    let approvals = match app.pr_reviews.required_approvals {
+        Some(required) => Span::styled(
+            format!("Approvals {}/{}", approved, required),
+            Style::default().fg(if approved >= required as usize {
+                Color::Green
+            } else {
+                Color::Yellow
+            }),
+        ),
+        None => Span::styled(
+            format!("Approvals {}", approved),
+            Style::default().fg(if approved > 0 {
+                Color::Green
+            } else {
+                Color::Gray
+            }),
+        ),
+    };
+
+    let checks_color = match app.pr_checks.status() {
+        ChecksStatus::Success => Color::Green,
+        ChecksStatus::Pending => Color::Yellow,
+        ChecksStatus::Failure => Color::Red,
+        ChecksStatus::None => Color::Gray,
+    };
+    let neutral = match app.pr_checks.neutral {
+        0 => String::new(),
+        n => format!(", {} neutral", n),
+    };
+    let checks = Span::styled(
+        format!(
+            "Checks {}/{} passing{}",
+            app.pr_checks.passed,
+            app.pr_checks.total(),
+            neutral
+        ),
+        Style::default().fg(checks_color),
+    );
+
+    let mut spans = vec![
+        Span::raw(" "),
+        files,
+        separator(),
+        threads,
+        separator(),
+        approvals,
+        separator(),
+        checks,
+    ];
+    let tasks = app.pr_tasks();
+    if !tasks.is_empty() {
+        let done = tasks.iter().filter(|t| t.done).count();
+        spans.push(separator());
+        spans.push(Span::styled(
+            format!("Tasks {}/{}", done, tasks.len()),
+            Style::default().fg(if done == tasks.len() {
+                Color::Green
+            } else {
+                Color::Yellow
+            }),
+        ));
+    }
+    Line::from(spans)
 }
 
-fn render_header(frame: &mut Frame, pr: &crate::types::PullRequest, area: Rect) {
+const MAX_REVIEW_ROWS: usize = 6;
+
+/// One row per reviewer: latest verdict first, then still-pending requests
+fn review_lines(app: &App) -> Vec<Line<'static>> {
+    let requested = &app.pr_reviews.requested;
+    let mut lines: Vec<Line> = app
+        .pr_reviews
+        .latest_by_reviewer()
+        .into_iter()
+        // A re-requested reviewer shows as pending, not under their old verdict
+        .filter(|review| !requested.contains(&review.reviewer))
+        .map(|review| {
+            let (icon, color) = match review.state {
+                ReviewState::Approved => ("✓", Color::Green),
+                ReviewState::ChangesRequested => ("✗", Color::Red),
+                ReviewState::Commented => ("●", Color::Gray),
+                ReviewState::Dismissed => ("–", Color::DarkGray),
+            };
+            let mut spans = vec![
+                Span::styled(format!("{} ", icon), Style::default().fg(color)),
+                Span::styled(
+                    format!("@{}", pad(&review.reviewer, 20)),
+                    author_style(app, &review.reviewer, Style::default()),
+                ),
+                Span::styled(
+                    pad(&review.state.to_string(), 18),
+                    Style::default().fg(color),
+                ),
+            ];
+            if let Some(at) = review.submitted_at {
+                spans.push(Span::styled(
+                    at.format("%Y-%m-%d %H:%M").to_string(),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    for reviewer in requested {
+        lines.push(Line::from(vec![
+            Span::styled("○ ", Style::default().fg(Color::Yellow)),
+            Span::styled(format!("@{}", pad(reviewer, 20)), Style::default()),
+            Span::styled("pending", Style::default().fg(Color::Yellow)),
+        ]));
+    }
+
+    lines
+}
+
+fn render_header(frame: &mut Frame, app: &App, pr: &crate::types::PullRequest, area: Rect) {
     let state_color = match pr.state {
         PrState::Open => Color::Green,
         PrState::Closed => Color::Red,
         PrState::Merged => Color::Magenta,
     };
 
+    let mut title = vec![Span::styled(
+        format!("#{} ", pr.number),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )];
+    title.extend(super::link_spans(
+        &app.link_rules,
+        &pr.title,
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+
     let lines = vec![
-        Line::from(vec![
-            Span::styled(
-                format!("#{} ", pr.number),
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(&pr.title, Style::default().add_modifier(Modifier::BOLD)),
-        ]),
+        Line::from(title),
         Line::from(vec![
             Span::styled(
                 format!("{}", pr.state),
@@ -58,12 +234,14 @@ fn render_header(frame: &mut Frame, pr: &crate::types::PullRequest, area: Rect)
             Span::raw(" | "),
             Span::styled(
                 format!("@{}", pr.author),
-                Style::default().fg(Color::Yellow),
+                author_style(app, &pr.author, Style::default().fg(Color::Yellow)),
             ),
             Span::raw(" wants to merge "),
             Span::styled(&pr.head_branch, Style::default().fg(Color::Cyan)),
             Span::raw(" into "),
             Span::styled(&pr.base_branch, Style::default().fg(Color::Cyan)),
+            mergeable_span(pr),
+            auto_merge_span(pr),
         ]),
         Line::from(vec![
             Span::styled(
@@ -82,13 +260,7 @@ fn render_header(frame: &mut Frame, pr: &crate::types::PullRequest, area: Rect)
             Span::raw(" | "),
             Span::raw(format!("{} comments", pr.stats.comments)),
         ]),
-        Line::from(vec![
-            Span::styled("Created: ", Style::default().fg(Color::Gray)),
-            Span::raw(pr.created_at.format("%Y-%m-%d %H:%M").to_string()),
-            Span::raw(" | "),
-            Span::styled("Updated: ", Style::default().fg(Color::Gray)),
-            Span::raw(pr.updated_at.format("%Y-%m-%d %H:%M").to_string()),
-        ]),
+        Line::from(dates_line(pr)),
     ];
 
     let header =
@@ -97,31 +269,146 @@ fn render_header(frame: &mut Frame, pr: &crate::types::PullRequest, area: Rect)
     frame.render_widget(header, area);
 }
 
-fn render_body(frame: &mut Frame, app: &App, pr: &crate::types::PullRequest, area: Rect) {
-    let body_text = pr.body.as_deref().unwrap_or("No description provided.");
+/// Created / updated dates, then the milestone and label chips when set
+fn dates_line(pr: &crate::types::PullRequest) -> Vec<Span<'static>> {
+    let mut spans = vec![
+        Span::styled("Created: ", Style::default().fg(Color::Gray)),
+        Span::raw(pr.created_at.format("%Y-%m-%d %H:%M").to_string()),
+        Span::raw(" | "),
+        Span::styled("Updated: ", Style::default().fg(Color::Gray)),
+        Span::raw(pr.updated_at.format("%Y-%m-%d %H:%M").to_string()),
+        Span::styled(
+            pr.milestone
+                .as_ref()
+                .map(|m| format!(" | Milestone: {}", m))
+                .unwrap_or_default(),
+            Style::default().fg(Color::Magenta),
+        ),
+    ];
+    if !pr.labels.is_empty() {
+        spans.push(Span::styled(
+            " | Labels: ",
+            Style::default().fg(Color::Blue),
+        ));
+        // The paragraph clips whatever runs past the edge
+        spans.extend(label_chips(&pr.labels, usize::MAX).0);
+    }
+    spans
+}
 
-    // Build lines with search highlighting
-    let lines: Vec<Line> = body_text
-        .lines()
-        .enumerate()
-        .map(|(line_idx, l)| {
-            let text = l.replace('\t', "    ");
-            highlight_line(&text, line_idx, Style::default(), &app.search)
-        })
-        .collect();
+/// Mergeability only matters while the PR is still open
+fn mergeable_span(pr: &crate::types::PullRequest) -> Span<'static> {
+    if pr.state != PrState::Open {
+        return Span::raw("");
+    }
+    let (icon, color) = match pr.mergeable_state {
+        MergeableState::Clean => ("✓", Color::Green),
+        MergeableState::Unstable | MergeableState::Behind => ("!", Color::Yellow),
+        MergeableState::Unknown => ("?", Color::DarkGray),
+        MergeableState::Conflicts
+        | MergeableState::BlockedByChecks
+        | MergeableState::BlockedByReviews
+        | MergeableState::Blocked
+        | MergeableState::Draft => ("✗", Color::Red),
+    };
+    Span::styled(
+        format!(" | {} {}", icon, pr.mergeable_state),
+        Style::default().fg(color),
+    )
+}
+
+/// Badge while the forge holds the PR to merge once checks pass
+fn auto_merge_span(pr: &crate::types::PullRequest) -> Span<'static> {
+    if pr.state != PrState::Open || !pr.auto_merge {
+        return Span::raw("");
+    }
+    Span::styled(
+        " | ⏵ auto-merge",
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+fn render_body(frame: &mut Frame, app: &App, area: Rect) {
+    app.detail_width.set(area.width.saturating_sub(2) as usize);
+    let detail = app.pr_detail_lines();
 
     // Calculate visible area (account for borders)
     let inner_height = area.height.saturating_sub(2) as usize;
+    app.page_height.set(inner_height);
+    let total = detail.len();
 
     // Clamp scroll offset to content bounds
-    let max_scroll = lines.len().saturating_sub(inner_height);
+    let max_scroll = total.saturating_sub(inner_height);
     let scroll_offset = app.scroll_offset.min(max_scroll);
+    let xref = app.selected_xref();
 
-    // Slice lines to visible range
-    let visible_lines: Vec<Line> = lines
-        .into_iter()
+    // Build the visible lines with search highlighting
+    let visible_lines: Vec<Line> = detail
+        .iter()
+        .enumerate()
         .skip(scroll_offset)
         .take(inner_height)
+        .map(|(line_idx, (kind, text))| {
+            let style = match kind {
+                PrLine::Body | PrLine::Comment => Style::default(),
+                PrLine::Section => Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+                PrLine::CommentHeader { bot: true, .. } => bot_style(),
+                PrLine::CommentHeader { bot: false, .. } => Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+                PrLine::File { index } => {
+                    let color = match text.chars().next() {
+                        Some('A') => Color::Green,
+                        Some('D') => Color::Red,
+                        _ => Color::Yellow,
+                    };
+                    let style = Style::default().fg(color);
+                    if *index == app.file_index {
+                        style.add_modifier(Modifier::REVERSED)
+                    } else {
+                        style
+                    }
+                }
+                PrLine::Commit { index } => {
+                    let style = Style::default().fg(Color::Blue);
+                    if *index == app.pr_commit_index {
+                        style.add_modifier(Modifier::REVERSED)
+                    } else {
+                        style
+                    }
+                }
+                PrLine::Link { .. } => Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::UNDERLINED),
+                PrLine::ThreadHeader { index, resolved } => {
+                    let style = if *resolved {
+                        Style::default().fg(Color::Green)
+                    } else {
+                        Style::default().fg(Color::Red)
+                    };
+                    if *index == app.thread_index {
+                        style.add_modifier(Modifier::REVERSED)
+                    } else {
+                        style
+                    }
+                }
+            };
+            match &xref {
+                Some((line, range, _)) if *line == line_idx => Line::from(vec![
+                    Span::styled(text[..range.start].to_string(), style),
+                    Span::styled(
+                        text[range.clone()].to_string(),
+                        style.fg(Color::Blue).add_modifier(Modifier::REVERSED),
+                    ),
+                    Span::styled(text[range.end..].to_string(), style),
+                ]),
+                _ => highlight_line(text, line_idx, style, &app.search, &app.link_rules),
+            }
+        })
         .collect();
 
     // Clear the area first to prevent artifacts
@@ -131,4 +418,5 @@ fn render_body(frame: &mut Frame, app: &App, pr: &crate::types::PullRequest, are
         .block(Block::default().borders(Borders::ALL).title("Description"));
 
     frame.render_widget(body, area);
+    super::render_scrollbar(frame, area, total, scroll_offset);
 }
@@ -1,14 +1,20 @@
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
+use ratatui::widgets::{Block, Borders, List, ListItem};
 use ratatui::Frame;
 
-use crate::app::App;
+use crate::app::{App, ClickList};
+
+use super::text::{pad, truncate};
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let title = match &app.repo_scope {
+        Some(org) => format!("Repositories of {}", org),
+        None => "Repositories".to_string(),
+    };
     if app.repos.is_empty() && !app.loading {
-        let block = Block::default().borders(Borders::ALL).title("Repositories");
+        let block = Block::default().borders(Borders::ALL).title(title);
         let empty = ratatui::widgets::Paragraph::new("No repositories found")
             .block(block)
             .style(Style::default().fg(Color::Gray));
@@ -36,49 +42,38 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             let description = repo
                 .description
                 .as_ref()
-                .map(|d| {
-                    if d.len() > flex {
-                        format!("{}...", &d[..flex.saturating_sub(3)])
-                    } else {
-                        d.clone()
-                    }
-                })
+                .map(|d| truncate(d, flex))
                 .unwrap_or_default();
 
-            let repo_name = format!("{}/{}", repo.owner, repo.name);
-            let repo_display = if repo_name.len() > 30 {
-                format!("{}...", &repo_name[..27])
-            } else {
-                repo_name
-            };
+            let repo_name = repo.full_path();
+            let repo_display = truncate(&repo_name, 30);
 
             let line = Line::from(vec![
-                Span::styled(format!("{:<30}", repo_display), style),
+                Span::styled(pad(&repo_display, 30), style),
                 Span::raw(" "),
                 Span::styled(
                     format!("★ {:>5}", repo.stars),
                     Style::default().fg(Color::DarkGray),
                 ),
                 Span::raw("  "),
-                Span::styled(
-                    format!("{:<flex$}", description),
-                    Style::default().fg(Color::Gray),
-                ),
+                Span::styled(pad(&description, flex), Style::default().fg(Color::Gray)),
             ]);
 
             ListItem::new(line)
         })
         .collect();
 
+    let len = items.len();
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title(format!(
-            "Repositories ({})",
-            super::format_count(app.repos.len(), app.repos_pagination.total_count)
+            "{} ({})",
+            title,
+            super::format_count(app.repos.len(), &app.repos_pagination)
         )))
         .highlight_style(Style::default().bg(Color::DarkGray));
 
-    let mut state = ListState::default();
-    state.select(Some(app.repo_index));
+    let mut state = super::list_state(app, ClickList::Repos, area, len, Some(app.repo_index));
 
     frame.render_stateful_widget(list, area, &mut state);
+    super::register_list(frame, app, area, ClickList::Repos, len, &state);
 }
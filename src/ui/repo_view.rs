@@ -2,12 +2,18 @@ use chrono::Utc;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Tabs, Wrap};
 use ratatui::Frame;
+use unicode_width::UnicodeWidthStr;
 
 use crate::action::RepoTab;
-use crate::app::App;
-use crate::types::{ActionStatus, IssueState, PrState};
+use crate::app::{App, ClickList, ClickTarget};
+use crate::types::{
+    ActionConclusion, ActionStatus, IssueState, PrFilter, PrState, WORKFLOW_HISTORY_LEN,
+};
+
+use super::text::{pad, truncate};
+use super::{author_style, format_number, label_chips, link_spans, number_width};
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let chunks = Layout::default()
@@ -23,7 +29,27 @@ fn render_tabs(frame: &mut Frame, app: &App, area: Rect) {
     let repo_name = app
         .current_repo
         .as_ref()
-        .map(|(owner, repo)| format!("{}/{}", owner, repo))
+        .map(|(owner, repo)| {
+            let mut name = format!("{}/{}", owner, repo);
+            // The count is only known when the repo came from the repo list
+            if let Some(r) = app
+                .repos
+                .iter()
+                .find(|r| &r.owner == owner && &r.name == repo)
+            {
+                name.push_str(&format!(" ★ {}", r.stars));
+            }
+            if app.is_starred(owner, repo) == Some(true) {
+                name.push_str(" (starred)");
+            }
+            if let Some(fork) = &app.fork_status {
+                name.push_str(&format!(
+                    " - fork of {}: {} ahead, {} behind",
+                    fork.upstream, fork.ahead, fork.behind
+                ));
+            }
+            name
+        })
         .unwrap_or_else(|| "Repository".to_string());
 
     let titles = vec![
@@ -33,7 +59,7 @@ fn render_tabs(frame: &mut Frame, app: &App, area: Rect) {
         "[A] Actions",
     ];
 
-    let tabs = Tabs::new(titles)
+    let tabs = Tabs::new(titles.clone())
         .block(
             Block::default().borders(Borders::ALL).title(Span::styled(
                 format!(" {} ", repo_name),
@@ -56,9 +82,37 @@ fn render_tabs(frame: &mut Frame, app: &App, area: Rect) {
         );
 
     frame.render_widget(tabs, area);
+
+    // Tabs pads each title with a space either side and puts a one-cell
+    // divider between them
+    let mut x = area.x + 1;
+    let mut click_map = app.click_map.borrow_mut();
+    for (title, tab) in titles.iter().zip([
+        RepoTab::PullRequests,
+        RepoTab::Issues,
+        RepoTab::Commits,
+        RepoTab::Actions,
+    ]) {
+        let width = title.width() as u16 + 2;
+        let rect = Rect::new(x, area.y + 1, width, 1).intersection(area);
+        click_map.push((rect, ClickTarget::Tab(tab)));
+        x += width + 1;
+    }
 }
 
 fn render_tab_content(frame: &mut Frame, app: &App, area: Rect) {
+    let area = match app.repo_tab {
+        RepoTab::PullRequests | RepoTab::Issues if app.preview.enabled => {
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .split(area);
+            render_preview(frame, app, chunks[1]);
+            chunks[0]
+        }
+        _ => area,
+    };
+
     match app.repo_tab {
         RepoTab::PullRequests => render_pr_preview(frame, app, area),
         RepoTab::Issues => render_issues(frame, app, area),
@@ -67,14 +121,151 @@ fn render_tab_content(frame: &mut Frame, app: &App, area: Rect) {
     }
 }
 
-fn render_pr_preview(frame: &mut Frame, app: &App, area: Rect) {
-    let block = Block::default().borders(Borders::ALL).title(format!(
-        " Pull Requests ({}) ",
-        super::format_count(app.prs.len(), app.prs_pagination.total_count)
+/// Title, state, author, labels and the start of the description of the
+/// selected PR or issue
+fn render_preview(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Preview ")
+        .border_style(Style::default().fg(Color::DarkGray));
+
+    let (number, title, state, author, labels, body) = match app.repo_tab {
+        RepoTab::PullRequests => {
+            let Some(pr) = app.prs.get(app.pr_index) else {
+                frame.render_widget(block, area);
+                return;
+            };
+            let color = match pr.state {
+                PrState::Open => Color::Green,
+                PrState::Closed => Color::Red,
+                PrState::Merged => Color::Magenta,
+            };
+            let full = app.preview.prs.get(&pr.number);
+            (
+                pr.number,
+                &pr.title,
+                Span::styled(pr.state.to_string(), Style::default().fg(color)),
+                &pr.author,
+                full.map(|f| f.labels.as_slice()).unwrap_or_default(),
+                full.map(|f| f.body.as_deref()),
+            )
+        }
+        _ => {
+            let Some(issue) = app.issues.get(app.issue_index) else {
+                frame.render_widget(block, area);
+                return;
+            };
+            let color = match issue.state {
+                IssueState::Open => Color::Green,
+                IssueState::Closed => Color::Red,
+            };
+            (
+                issue.number,
+                &issue.title,
+                Span::styled(issue.state.to_string(), Style::default().fg(color)),
+                &issue.author,
+                issue.labels.as_slice(),
+                app.preview
+                    .issue_bodies
+                    .get(&issue.number)
+                    .map(|b| b.as_deref()),
+            )
+        }
+    };
+
+    let width = area.width.saturating_sub(2) as usize;
+    let height = area.height.saturating_sub(2) as usize;
+    let mut heading = vec![Span::styled(
+        format!("#{} ", number),
+        Style::default().fg(Color::Cyan),
+    )];
+    heading.extend(link_spans(
+        &app.link_rules,
+        title,
+        Style::default().add_modifier(Modifier::BOLD),
     ));
+    let mut lines = vec![
+        Line::from(heading),
+        Line::from(vec![
+            state,
+            Span::raw("  "),
+            Span::styled(
+                format!("@{}", author),
+                author_style(app, author, Style::default().fg(Color::Gray)),
+            ),
+        ]),
+    ];
+    if !labels.is_empty() {
+        lines.push(Line::from(label_chips(labels, width).0));
+    }
+    lines.push(Line::from(""));
+    match body.map(|b| b.unwrap_or("").trim()) {
+        None => lines.push(Line::styled(
+            "Loading...",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Some("") => lines.push(Line::styled(
+            "No description.",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Some(text) => {
+            // Numbered URLs go last, for `o` and `y` to pick from
+            let links = app.body_links();
+            let footer = if links.is_empty() { 0 } else { links.len() + 2 };
+            let room = height.saturating_sub(lines.len() + footer);
+            lines.extend(
+                text.lines()
+                    .take(room)
+                    .map(|l| Line::from(link_spans(&app.link_rules, l, Style::default()))),
+            );
+            if !links.is_empty() {
+                lines.push(Line::from(""));
+                lines.push(Line::styled(
+                    format!("Links ({})", links.len()),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ));
+                lines.extend(links.iter().enumerate().map(|(i, link)| {
+                    Line::styled(link.label(i), Style::default().fg(Color::Blue))
+                }));
+            }
+        }
+    }
+
+    let preview = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(preview, area);
+}
+
+fn render_pr_preview(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(match &app.milestone_filter {
+            Some(milestone) => format!(
+                " Pull Requests ({}){} · {}{} ",
+                super::format_count(app.prs.len(), &app.prs_pagination),
+                pr_filter_label(app),
+                milestone.title,
+                marked_count(app)
+            ),
+            None => format!(
+                " Pull Requests ({}){}{}{} ",
+                super::format_count(app.prs.len(), &app.prs_pagination),
+                pr_filter_label(app),
+                new_count(app.prs.iter().filter(|pr| app.is_new_pr(pr)).count()),
+                marked_count(app)
+            ),
+        });
 
     if app.prs.is_empty() && !app.loading {
-        let empty = Paragraph::new("No open pull requests - Press Enter to view all")
+        let message = match app.pr_filter {
+            PrFilter::All => "No open pull requests - Press Enter to view all".to_string(),
+            filter => format!(
+                "No open pull requests {} - P changes the filter",
+                filter.label().to_lowercase()
+            ),
+        };
+        let empty = Paragraph::new(message)
             .block(block)
             .style(Style::default().fg(Color::Gray));
         frame.render_widget(empty, area);
@@ -82,13 +273,22 @@ fn render_pr_preview(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     let w = area.width.saturating_sub(2) as usize;
-    let fixed = 31; // #num(6) + space(1) + state(6) + space(1) + space(1) + @author(16)
+    let num_w = number_width(app.prs.iter().map(|pr| pr.number));
+    let fixed = 29 + num_w; // #num + space(1) + new(4) + state(6) + space(1) + space(1) + @author(16)
     let flex = w.saturating_sub(fixed).max(10);
 
+    let marked = app.marked_numbers();
+    let len = app.prs.len();
+    let selected = (!app.prs.is_empty()).then_some(app.pr_index);
+    let mut state = super::list_state(app, ClickList::Prs, area, len, selected);
+    let rows = super::visible_rows(&mut state, area, len);
+
     let items: Vec<ListItem> = app
         .prs
         .iter()
         .enumerate()
+        .skip(rows.start)
+        .take(rows.len())
         .map(|(i, pr)| {
             let is_selected = i == app.pr_index;
             let style = if is_selected {
@@ -105,32 +305,27 @@ fn render_pr_preview(frame: &mut Frame, app: &App, area: Rect) {
                 PrState::Merged => Color::Magenta,
             };
 
-            let title = if pr.title.len() > flex {
-                format!("{}...", &pr.title[..flex.saturating_sub(3)])
-            } else {
-                pr.title.clone()
-            };
+            let title = truncate(&pr.title, flex);
 
-            let author = if pr.author.len() > 15 {
-                format!("{}...", &pr.author[..12])
-            } else {
-                pr.author.clone()
-            };
+            let author = truncate(&pr.author, 15);
 
-            let line = Line::from(vec![
-                Span::styled(
-                    format!("#{:<5}", pr.number),
-                    Style::default().fg(Color::Cyan),
-                ),
+            let mut spans = vec![
+                number_span(&marked, pr.number, num_w),
                 Span::raw(" "),
+                new_badge(app.is_new_pr(pr)),
                 Span::styled(format!("{:6}", pr.state), Style::default().fg(state_color)),
                 Span::raw(" "),
-                Span::styled(format!("{:<flex$}", title), style),
+            ];
+            spans.extend(link_spans(&app.link_rules, &pad(&title, flex), style));
+            spans.extend([
                 Span::raw(" "),
-                Span::styled(format!("@{:<15}", author), Style::default().fg(Color::Gray)),
+                Span::styled(
+                    format!("@{}", pad(&author, 15)),
+                    author_style(app, &pr.author, Style::default().fg(Color::Gray)),
+                ),
             ]);
 
-            ListItem::new(line)
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
@@ -138,19 +333,27 @@ fn render_pr_preview(frame: &mut Frame, app: &App, area: Rect) {
         .block(block)
         .highlight_style(Style::default().bg(Color::DarkGray));
 
-    let mut state = ListState::default();
-    if !app.prs.is_empty() {
-        state.select(Some(app.pr_index));
-    }
-
-    frame.render_stateful_widget(list, area, &mut state);
+    frame.render_stateful_widget(list, area, &mut super::window_state(&state, &rows));
+    super::register_list(frame, app, area, ClickList::Prs, len, &state);
 }
 
 fn render_issues(frame: &mut Frame, app: &App, area: Rect) {
-    let block = Block::default().borders(Borders::ALL).title(format!(
-        " Issues ({}) ",
-        super::format_count(app.issues.len(), app.issues_pagination.total_count)
-    ));
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(match &app.milestone_filter {
+            Some(milestone) => format!(
+                " Issues ({}) · {}{} ",
+                super::format_count(app.issues.len(), &app.issues_pagination),
+                milestone.title,
+                marked_count(app)
+            ),
+            None => format!(
+                " Issues ({}){}{} ",
+                super::format_count(app.issues.len(), &app.issues_pagination),
+                new_count(app.issues.iter().filter(|i| app.is_new_issue(i)).count()),
+                marked_count(app)
+            ),
+        });
 
     if app.issues.is_empty() && !app.loading {
         let empty = Paragraph::new("No open issues")
@@ -161,13 +364,22 @@ fn render_issues(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     let w = area.width.saturating_sub(2) as usize;
-    let fixed = 50; // #num(6) + space(1) + state(6) + space(1) + space(1) + labels(18) + space(1) + @author(16)
+    let num_w = number_width(app.issues.iter().map(|issue| issue.number));
+    let fixed = 48 + num_w; // #num + space(1) + new(4) + state(6) + space(1) + space(1) + labels(18) + space(1) + @author(16)
     let flex = w.saturating_sub(fixed).max(10);
 
+    let marked = app.marked_numbers();
+    let len = app.issues.len();
+    let selected = (!app.issues.is_empty()).then_some(app.issue_index);
+    let mut state = super::list_state(app, ClickList::Issues, area, len, selected);
+    let rows = super::visible_rows(&mut state, area, len);
+
     let items: Vec<ListItem> = app
         .issues
         .iter()
         .enumerate()
+        .skip(rows.start)
+        .take(rows.len())
         .map(|(i, issue)| {
             let is_selected = i == app.issue_index;
             let style = if is_selected {
@@ -183,51 +395,35 @@ fn render_issues(frame: &mut Frame, app: &App, area: Rect) {
                 IssueState::Closed => Color::Red,
             };
 
-            let title = if issue.title.len() > flex {
-                format!("{}...", &issue.title[..flex.saturating_sub(3)])
-            } else {
-                issue.title.clone()
-            };
+            let title = truncate(&issue.title, flex);
 
-            let labels = if issue.labels.is_empty() {
-                String::new()
-            } else {
-                let joined = issue.labels.join(", ");
-                if joined.len() > 15 {
-                    format!("[{}...]", &joined[..12])
-                } else {
-                    format!("[{}]", joined)
-                }
-            };
+            let (labels, labels_width) = label_chips(&issue.labels, 18);
 
-            let author = if issue.author.len() > 15 {
-                format!("{}...", &issue.author[..12])
-            } else {
-                issue.author.clone()
-            };
+            let author = truncate(&issue.author, 15);
 
-            let line = Line::from(vec![
-                Span::styled(
-                    format!("#{:<5}", issue.number),
-                    Style::default().fg(Color::Cyan),
-                ),
+            let mut spans = vec![
+                number_span(&marked, issue.number, num_w),
                 Span::raw(" "),
+                new_badge(app.is_new_issue(issue)),
                 Span::styled(
                     format!("{:6}", issue.state),
                     Style::default().fg(state_color),
                 ),
                 Span::raw(" "),
-                Span::styled(format!("{:<flex$}", title), style),
+            ];
+            spans.extend(link_spans(&app.link_rules, &pad(&title, flex), style));
+            spans.push(Span::raw(" "));
+            spans.extend(labels);
+            spans.extend([
+                Span::raw(" ".repeat(18 - labels_width)),
                 Span::raw(" "),
                 Span::styled(
-                    format!("{:<18}", labels),
-                    Style::default().fg(Color::Magenta),
+                    format!("@{}", pad(&author, 15)),
+                    author_style(app, &issue.author, Style::default().fg(Color::Gray)),
                 ),
-                Span::raw(" "),
-                Span::styled(format!("@{:<15}", author), Style::default().fg(Color::Gray)),
             ]);
 
-            ListItem::new(line)
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
@@ -235,18 +431,14 @@ fn render_issues(frame: &mut Frame, app: &App, area: Rect) {
         .block(block)
         .highlight_style(Style::default().bg(Color::DarkGray));
 
-    let mut state = ListState::default();
-    if !app.issues.is_empty() {
-        state.select(Some(app.issue_index));
-    }
-
-    frame.render_stateful_widget(list, area, &mut state);
+    frame.render_stateful_widget(list, area, &mut super::window_state(&state, &rows));
+    super::register_list(frame, app, area, ClickList::Issues, len, &state);
 }
 
 fn render_commits(frame: &mut Frame, app: &App, area: Rect) {
     let block = Block::default().borders(Borders::ALL).title(format!(
         " Commits ({}) ",
-        super::format_count(app.commits.len(), app.commits_pagination.total_count)
+        super::format_count(app.commits.len(), &app.commits_pagination)
     ));
 
     if app.commits.is_empty() && !app.loading {
@@ -261,10 +453,17 @@ fn render_commits(frame: &mut Frame, app: &App, area: Rect) {
     let fixed = 29; // sha(7) + space(1) + space(1) + @author(16) + space(1) + age(3)
     let flex = w.saturating_sub(fixed).max(10);
 
+    let len = app.commits.len();
+    let selected = (!app.commits.is_empty()).then_some(app.commit_index);
+    let mut state = super::list_state(app, ClickList::Commits, area, len, selected);
+    let rows = super::visible_rows(&mut state, area, len);
+
     let items: Vec<ListItem> = app
         .commits
         .iter()
         .enumerate()
+        .skip(rows.start)
+        .take(rows.len())
         .map(|(i, commit)| {
             let is_selected = i == app.commit_index;
             let style = if is_selected {
@@ -275,17 +474,9 @@ fn render_commits(frame: &mut Frame, app: &App, area: Rect) {
                 Style::default()
             };
 
-            let message = if commit.message.len() > flex {
-                format!("{}...", &commit.message[..flex.saturating_sub(3)])
-            } else {
-                commit.message.clone()
-            };
+            let message = truncate(&commit.message, flex);
 
-            let author = if commit.author.len() > 15 {
-                format!("{}...", &commit.author[..12])
-            } else {
-                commit.author.clone()
-            };
+            let author = truncate(&commit.author, 15);
 
             let age = format_age(commit.date);
 
@@ -294,9 +485,12 @@ fn render_commits(frame: &mut Frame, app: &App, area: Rect) {
             let line = Line::from(vec![
                 Span::styled(short_sha, Style::default().fg(Color::Yellow)),
                 Span::raw(" "),
-                Span::styled(format!("{:<flex$}", message), style),
+                Span::styled(pad(&message, flex), style),
                 Span::raw(" "),
-                Span::styled(format!("@{:<15}", author), Style::default().fg(Color::Cyan)),
+                Span::styled(
+                    format!("@{}", pad(&author, 15)),
+                    author_style(app, &commit.author, Style::default().fg(Color::Cyan)),
+                ),
                 Span::raw(" "),
                 Span::styled(format!("{:>3}", age), Style::default().fg(Color::DarkGray)),
             ]);
@@ -309,18 +503,18 @@ fn render_commits(frame: &mut Frame, app: &App, area: Rect) {
         .block(block)
         .highlight_style(Style::default().bg(Color::DarkGray));
 
-    let mut state = ListState::default();
-    if !app.commits.is_empty() {
-        state.select(Some(app.commit_index));
-    }
-
-    frame.render_stateful_widget(list, area, &mut state);
+    frame.render_stateful_widget(list, area, &mut super::window_state(&state, &rows));
+    super::register_list(frame, app, area, ClickList::Commits, len, &state);
 }
 
 fn render_actions(frame: &mut Frame, app: &App, area: Rect) {
+    if app.workflow_summary {
+        render_workflows(frame, app, area);
+        return;
+    }
     let block = Block::default().borders(Borders::ALL).title(format!(
         " Actions ({}) ",
-        super::format_count(app.action_runs.len(), app.actions_pagination.total_count)
+        super::format_count(app.action_runs.len(), &app.actions_pagination)
     ));
 
     if app.action_runs.is_empty() && !app.loading {
@@ -332,13 +526,20 @@ fn render_actions(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     let w = area.width.saturating_sub(2) as usize;
-    let fixed = 31; // status(2) + space(1) + space(1) + branch(12) + space(1) + event(10) + space(1) + age(3)
+    let fixed = 34; // status(2) + space(1) + space(1) + branch(12) + space(1) + event(10) + space(1) + age(6)
     let flex = w.saturating_sub(fixed).max(10);
 
+    let len = app.action_runs.len();
+    let selected = (!app.action_runs.is_empty()).then_some(app.action_index);
+    let mut state = super::list_state(app, ClickList::Actions, area, len, selected);
+    let rows = super::visible_rows(&mut state, area, len);
+
     let items: Vec<ListItem> = app
         .action_runs
         .iter()
         .enumerate()
+        .skip(rows.start)
+        .take(rows.len())
         .map(|(i, run)| {
             let is_selected = i == app.action_index;
             let style = if is_selected {
@@ -368,36 +569,30 @@ fn render_actions(frame: &mut Frame, app: &App, area: Rect) {
                 ActionStatus::Queued => ("◯".to_string(), Color::Gray),
             };
 
-            let name = if run.name.len() > flex {
-                format!("{}...", &run.name[..flex.saturating_sub(3)])
-            } else {
-                run.name.clone()
-            };
+            let name = truncate(&run.name, flex);
+
+            let branch = truncate(&run.branch, 12);
 
-            let branch = if run.branch.len() > 12 {
-                format!("{}...", &run.branch[..9])
+            // Running jobs show a ticking elapsed time instead of their age
+            let (age, age_color) = if run.status == ActionStatus::InProgress {
+                (format_elapsed(run.created_at), Color::Yellow)
             } else {
-                run.branch.clone()
+                (format_age(run.created_at), Color::DarkGray)
             };
 
-            let age = format_age(run.created_at);
-
             let line = Line::from(vec![
-                Span::styled(
-                    format!("{:<2}", status_icon),
-                    Style::default().fg(status_color),
-                ),
+                Span::styled(pad(&status_icon, 2), Style::default().fg(status_color)),
                 Span::raw(" "),
-                Span::styled(format!("{:<flex$}", name), style),
+                Span::styled(pad(&name, flex), style),
                 Span::raw(" "),
-                Span::styled(format!("{:<12}", branch), Style::default().fg(Color::Cyan)),
+                Span::styled(pad(&branch, 12), Style::default().fg(Color::Cyan)),
                 Span::raw(" "),
                 Span::styled(
                     format!("{:<10}", run.event),
                     Style::default().fg(Color::Gray),
                 ),
                 Span::raw(" "),
-                Span::styled(format!("{:>3}", age), Style::default().fg(Color::DarkGray)),
+                Span::styled(format!("{:>6}", age), Style::default().fg(age_color)),
             ]);
 
             ListItem::new(line)
@@ -408,12 +603,8 @@ fn render_actions(frame: &mut Frame, app: &App, area: Rect) {
         .block(block)
         .highlight_style(Style::default().bg(Color::DarkGray));
 
-    let mut state = ListState::default();
-    if !app.action_runs.is_empty() {
-        state.select(Some(app.action_index));
-    }
-
-    frame.render_stateful_widget(list, area, &mut state);
+    frame.render_stateful_widget(list, area, &mut super::window_state(&state, &rows));
+    super::register_list(frame, app, area, ClickList::Actions, len, &state);
 }
 
 fn format_age(dt: chrono::DateTime<chrono::Utc>) -> String {
@@ -430,3 +621,148 @@ fn format_age(dt: chrono::DateTime<chrono::Utc>) -> String {
         "now".to_string()
     }
 }
+
+/// One row per workflow: its latest outcomes from oldest to newest, how long
+/// a run takes on average and when the last one started
+fn render_workflows(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Workflows ({}) ", app.workflows.len()));
+
+    if app.workflows.is_empty() {
+        let empty = Paragraph::new("Loading run history...")
+            .block(block)
+            .style(Style::default().fg(Color::Gray));
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let w = area.width.saturating_sub(2) as usize;
+    let fixed = WORKFLOW_HISTORY_LEN + 16; // history + space(1) + duration(8) + space(1) + age(6)
+    let flex = w.saturating_sub(fixed + 1).max(10);
+
+    let len = app.workflows.len();
+    let selected = Some(app.workflow_index);
+    let mut state = super::list_state(app, ClickList::Workflows, area, len, selected);
+    let rows = super::visible_rows(&mut state, area, len);
+
+    let items: Vec<ListItem> = app
+        .workflows
+        .iter()
+        .enumerate()
+        .skip(rows.start)
+        .take(rows.len())
+        .map(|(i, workflow)| {
+            let style = if i == app.workflow_index {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let name = truncate(&workflow.name, flex);
+
+            let mut spans = vec![
+                Span::styled(pad(&name, flex), style),
+                Span::raw(" "),
+                Span::raw(" ".repeat(WORKFLOW_HISTORY_LEN - workflow.history.len())),
+            ];
+            spans.extend(workflow.history.iter().map(|conclusion| match conclusion {
+                Some(c) => Span::styled(
+                    c.to_string(),
+                    Style::default().fg(match c {
+                        ActionConclusion::Success => Color::Green,
+                        ActionConclusion::Failure => Color::Red,
+                        _ => Color::Yellow,
+                    }),
+                ),
+                None => Span::styled("⟳", Style::default().fg(Color::Yellow)),
+            }));
+            let average = workflow
+                .average_duration
+                .map(format_duration)
+                .unwrap_or_else(|| "-".to_string());
+            spans.extend([
+                Span::raw(" "),
+                Span::styled(format!("{:>8}", average), Style::default().fg(Color::Cyan)),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{:>6}", format_age(workflow.last_run)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]);
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray));
+
+    frame.render_stateful_widget(list, area, &mut super::window_state(&state, &rows));
+    super::register_list(frame, app, area, ClickList::Workflows, len, &state);
+}
+
+/// Elapsed time since `dt` as `1h02m`, `4m07s` or `12s`
+fn format_elapsed(dt: chrono::DateTime<chrono::Utc>) -> String {
+    format_duration(Utc::now().signed_duration_since(dt).num_seconds().max(0) as u64)
+}
+
+pub(super) fn format_duration(secs: u64) -> String {
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Marks items updated since the last visit to the repo
+fn new_badge(is_new: bool) -> Span<'static> {
+    if is_new {
+        Span::styled(
+            "new ",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Span::raw("    ")
+    }
+}
+
+/// The number column, reversed out when the row is marked
+fn number_span(marked: &[u64], number: u64, width: usize) -> Span<'static> {
+    let style = if marked.contains(&number) {
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::Magenta)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Cyan)
+    };
+    Span::styled(format_number(number, width), style)
+}
+
+fn pr_filter_label(app: &App) -> String {
+    match app.pr_filter {
+        PrFilter::All => String::new(),
+        filter => format!(" · {}", filter.label().to_lowercase()),
+    }
+}
+
+fn marked_count(app: &App) -> String {
+    match app.marked_numbers().len() {
+        0 => String::new(),
+        count => format!(" · {} marked", count),
+    }
+}
+
+fn new_count(count: usize) -> String {
+    if count == 0 {
+        String::new()
+    } else {
+        format!(" · {} new", count)
+    }
+}
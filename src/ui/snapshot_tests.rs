@@ -0,0 +1,130 @@
+//! Each screen rendered from `MockForge` fixtures into a test terminal and
+//! compared with the snapshot under `snapshots/`. After an intended change,
+//! review and accept the new renders with `cargo insta review`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use tokio::sync::mpsc;
+
+use crate::action::{Action, RepoTab};
+use crate::app::App;
+use crate::config::UiConfig;
+use crate::forge::Forge;
+use crate::tape::{Mode, Tape};
+use crate::testing::MockForge;
+
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 30;
+
+fn mock_app() -> (App, mpsc::UnboundedReceiver<Action>) {
+    app_on(Arc::new(MockForge::default()))
+}
+
+fn app_on(forge: Arc<dyn Forge>) -> (App, mpsc::UnboundedReceiver<Action>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    // No background prefetching, so every load is done once `settle` returns
+    let ui_config = UiConfig {
+        prefetch: Some(0),
+        ..UiConfig::default()
+    };
+    let app = App::new(forge, tx, vec![], ui_config);
+    (app, rx)
+}
+
+/// Feed the app what its loads send back until they've all finished
+async fn settle(app: &mut App, rx: &mut mpsc::UnboundedReceiver<Action>) {
+    while let Ok(Some(action)) = tokio::time::timeout(Duration::from_millis(100), rx.recv()).await {
+        app.update(action);
+    }
+}
+
+fn render(app: &App) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|frame| super::render(frame, app)).unwrap();
+    terminal.backend().to_string()
+}
+
+/// Snapshot `app`'s screen. Fixture dates are fixed, so ages counted from
+/// now ("878d") are masked.
+macro_rules! assert_screen {
+    ($app:expr) => {
+        insta::with_settings!({ filters => vec![(r"\b\d+[mhdwy]\b( ago)?", "[age]")] }, {
+            insta::assert_snapshot!(render($app));
+        })
+    };
+}
+
+/// The app as `grit acme/rocket` (and `#number`) would start it
+async fn start_in_repo(number: Option<u64>) -> App {
+    let (mut app, mut rx) = mock_app();
+    app.start_repo = Some(("acme".to_string(), "rocket".to_string()));
+    app.start_pr = number;
+    app.update(Action::Start);
+    settle(&mut app, &mut rx).await;
+    app
+}
+
+#[tokio::test]
+async fn home() {
+    let (mut app, mut rx) = mock_app();
+    app.update(Action::Start);
+    settle(&mut app, &mut rx).await;
+    assert_screen!(&app);
+}
+
+#[tokio::test]
+async fn repo_view_pull_requests() {
+    let app = start_in_repo(None).await;
+    assert_screen!(&app);
+}
+
+#[tokio::test]
+async fn repo_view_issues() {
+    let (mut app, mut rx) = mock_app();
+    app.start_repo = Some(("acme".to_string(), "rocket".to_string()));
+    app.update(Action::Start);
+    settle(&mut app, &mut rx).await;
+    app.update(Action::SwitchRepoTab(RepoTab::Issues));
+    settle(&mut app, &mut rx).await;
+    assert_screen!(&app);
+}
+
+#[tokio::test]
+async fn pr_detail() {
+    let app = start_in_repo(Some(42)).await;
+    assert_screen!(&app);
+}
+
+#[tokio::test]
+async fn replay_renders_like_the_recording() {
+    let dir = std::env::temp_dir().join(format!("grit-replay-ui-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let open_pr = |forge: Tape| async move {
+        let (mut app, mut rx) = app_on(Arc::new(forge));
+        app.start_repo = Some(("acme".to_string(), "rocket".to_string()));
+        app.start_pr = Some(42);
+        app.update(Action::Start);
+        settle(&mut app, &mut rx).await;
+        render(&app)
+    };
+
+    let recorded =
+        open_pr(Tape::new(Arc::new(MockForge::default()), &dir, Mode::Record).unwrap()).await;
+    // Everything on screen has to come from the recording
+    let empty = MockForge {
+        repos: vec![],
+        prs: vec![],
+        pulls: vec![],
+        issues: vec![],
+        commits: vec![],
+        comments: vec![],
+        files: vec![],
+        ..MockForge::default()
+    };
+    let replayed = open_pr(Tape::new(Arc::new(empty), &dir, Mode::Replay).unwrap()).await;
+    assert_eq!(replayed, recorded);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
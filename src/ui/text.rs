@@ -0,0 +1,63 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// `text` cut to at most `width` terminal columns, ending in "..." when
+/// anything was dropped. Wide characters are never split.
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let ellipsis = if width >= 3 { "..." } else { "" };
+    let room = width - ellipsis.len();
+    let mut used = 0;
+    let mut cut = String::new();
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > room {
+            break;
+        }
+        used += w;
+        cut.push(c);
+    }
+    cut + ellipsis
+}
+
+/// `text` truncated to `width` columns and padded with spaces to fill them
+pub fn pad(text: &str, width: usize) -> String {
+    let mut cut = truncate(text, width);
+    let fill = width.saturating_sub(cut.width());
+    cut.push_str(&" ".repeat(fill));
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_text_is_untouched() {
+        assert_eq!(truncate("grit", 10), "grit");
+        assert_eq!(pad("grit", 6), "grit  ");
+    }
+
+    #[test]
+    fn long_text_ends_in_an_ellipsis() {
+        assert_eq!(truncate("pders01/grit", 8), "pders...");
+        assert_eq!(truncate("pders01/grit", 2), "pd");
+    }
+
+    #[test]
+    fn wide_characters_count_two_columns() {
+        // Each CJK character is two columns; a half-fitting one is dropped
+        assert_eq!(truncate("日本語のタイトル", 8), "日本...");
+        assert_eq!(pad("日本語のタイトル", 8).width(), 8);
+        assert_eq!(pad("修正", 6), "修正  ");
+        // Too narrow for the ellipsis, a half-fitting character becomes padding
+        assert_eq!(pad("修正", 1), " ");
+    }
+
+    #[test]
+    fn emoji_are_never_split() {
+        assert_eq!(truncate("🚀🚀🚀 launch", 7), "🚀🚀...");
+        assert_eq!(pad("🚀 ok", 6), "🚀 ok ");
+    }
+}
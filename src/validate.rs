@@ -0,0 +1,489 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::config::{self, Config, ForgeConfig, ForgeType};
+use crate::error::GritError;
+
+/// How a check came out; any `Fail` fails the run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Status::Ok => "ok",
+            Status::Warn => "warn",
+            Status::Fail => "FAIL",
+        })
+    }
+}
+
+/// One line of the report: `subject` is "config" or a forge's name
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub subject: String,
+    pub check: &'static str,
+    pub status: Status,
+    pub detail: String,
+}
+
+/// Everything `grit config validate` found
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    pub checks: Vec<Check>,
+    /// The config files couldn't be read or parsed, so nothing else was checked
+    #[serde(skip)]
+    unreadable: bool,
+}
+
+impl Report {
+    fn push(&mut self, subject: &str, check: &'static str, status: Status, detail: String) {
+        self.checks.push(Check {
+            subject: subject.to_string(),
+            check,
+            status,
+            detail,
+        });
+    }
+
+    /// 0 when nothing failed (warnings allowed), 1 when a check failed, 2
+    /// when the config itself couldn't be read
+    pub fn exit_code(&self) -> i32 {
+        if self.unreadable {
+            2
+        } else if self.checks.iter().any(|c| c.status == Status::Fail) {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// One aligned line per check, then a summary
+    pub fn text(&self) -> String {
+        let width = self
+            .checks
+            .iter()
+            .map(|c| c.subject.len())
+            .max()
+            .unwrap_or(0);
+        let mut out = String::new();
+        for c in &self.checks {
+            out.push_str(&format!(
+                "{:<width$}  {:<4}  {:<9}  {}\n",
+                c.subject, c.status, c.check, c.detail
+            ));
+        }
+        let count = |status| self.checks.iter().filter(|c| c.status == status).count();
+        out.push_str(&format!(
+            "{} ok, {} warnings, {} failed\n",
+            count(Status::Ok),
+            count(Status::Warn),
+            count(Status::Fail)
+        ));
+        out
+    }
+}
+
+/// Check the global config and the project's `.grit.toml`; with `online`,
+/// also sign in to every forge and look at the token's scopes
+pub async fn run(online: bool) -> Report {
+    let mut report = Report::default();
+    let files = [
+        ("config", config::config_path()),
+        ("project", config::project_config_path()),
+    ];
+    let mut layers = Vec::new();
+    for (label, path) in files {
+        let Some(path) = path else { continue };
+        match config::read_table(&path) {
+            Ok(Some(table)) => {
+                report.push(label, "file", Status::Ok, path.display().to_string());
+                let table = match label {
+                    "project" => {
+                        let (table, dropped) = config::project_layer(table);
+                        if !dropped.is_empty() {
+                            report.push(
+                                label,
+                                "keys",
+                                Status::Warn,
+                                format!(
+                                    "ignoring {}; only the global config may set them",
+                                    dropped.join(", ")
+                                ),
+                            );
+                        }
+                        table
+                    }
+                    _ => table,
+                };
+                layers.push(table);
+            }
+            Ok(None) => report.push(
+                label,
+                "file",
+                Status::Warn,
+                format!("{} not found; using defaults", path.display()),
+            ),
+            Err(e) => {
+                report.push(label, "file", Status::Fail, e);
+                report.unreadable = true;
+            }
+        }
+    }
+    if report.unreadable {
+        return report;
+    }
+    let config = match Config::from_layers(layers) {
+        Ok(config) => config,
+        Err(e) => {
+            report.push("config", "schema", Status::Fail, e.to_string());
+            report.unreadable = true;
+            return report;
+        }
+    };
+
+    check_config(&config, &mut report);
+    for forge in &config.forges {
+        check_forge(forge, &mut report);
+        if online {
+            check_online(forge, &mut report).await;
+        }
+    }
+    report
+}
+
+/// Settings that span forges: names that refer to forges, link patterns
+fn check_config(config: &Config, report: &mut Report) {
+    let mut names = HashSet::new();
+    for forge in &config.forges {
+        if !names.insert(forge.name.as_str()) {
+            report.push(
+                "config",
+                "forges",
+                Status::Fail,
+                format!("two [[forges]] are named '{}'", forge.name),
+            );
+        }
+    }
+    for (key, name) in [
+        ("forge", config.forge.as_deref()),
+        (
+            "general.default_forge",
+            config.general.default_forge.as_deref(),
+        ),
+    ] {
+        if let Some(name) = name.filter(|name| !names.contains(name)) {
+            report.push(
+                "config",
+                "forges",
+                Status::Fail,
+                format!("{} = '{}' names no [[forges]] entry", key, name),
+            );
+        }
+    }
+    check_home(config, report);
+    if let Err(e) = tracing_subscriber::EnvFilter::try_new(config.log.filter()) {
+        report.push("config", "log", Status::Fail, e.to_string());
+    }
+    for link in &config.ui.links {
+        if let Err(e) = regex::Regex::new(&link.pattern) {
+            report.push(
+                "config",
+                "links",
+                Status::Fail,
+                format!("pattern {:?}: {}", link.pattern, e),
+            );
+        }
+    }
+}
+
+/// `[home]`: sections named once, one size per section
+fn check_home(config: &Config, report: &mut Report) {
+    let home = &config.home;
+    let mut seen = HashSet::new();
+    for section in &home.sections {
+        if !seen.insert(section) {
+            report.push(
+                "config",
+                "home",
+                Status::Fail,
+                format!("home.sections lists {:?} twice", section),
+            );
+        }
+    }
+    if home.sizes.is_empty() {
+        return;
+    }
+    // Without `sections`, sizes go to all six in the usual order
+    let count = if home.sections.is_empty() {
+        6
+    } else {
+        home.sections.len()
+    };
+    if home.sizes.len() != count {
+        report.push(
+            "config",
+            "home",
+            Status::Fail,
+            format!(
+                "home.sizes has {} entries for {} sections",
+                home.sizes.len(),
+                count
+            ),
+        );
+    }
+    let total: u16 = home.sizes.iter().sum();
+    if total != 100 {
+        report.push(
+            "config",
+            "home",
+            Status::Warn,
+            format!(
+                "home.sizes add up to {}%; sections are scaled to fit",
+                total
+            ),
+        );
+    }
+}
+
+/// What can be told about a forge entry without the network
+fn check_forge(forge: &ForgeConfig, report: &mut Report) {
+    let name = forge.name.as_str();
+    if forge.host.contains("://") {
+        report.push(
+            name,
+            "host",
+            Status::Fail,
+            format!(
+                "{} is a URL; host takes a hostname (use scheme/port/base_path for the rest)",
+                forge.host
+            ),
+        );
+    } else {
+        match reqwest::Url::parse(&forge.base_url()) {
+            Ok(url) => {
+                if forge.forge_type == ForgeType::GitHub && forge.host != "github.com" {
+                    report.push(
+                        name,
+                        "host",
+                        Status::Warn,
+                        format!(
+                            "{}: GitHub Enterprise isn't supported; requests go to api.github.com",
+                            url
+                        ),
+                    );
+                } else {
+                    report.push(name, "host", Status::Ok, url.to_string());
+                }
+            }
+            Err(e) => report.push(name, "host", Status::Fail, format!("{}: {}", forge.host, e)),
+        }
+    }
+
+    if let Some(repo) = &forge.default_repo {
+        if forge.default_repo().is_none() {
+            report.push(
+                name,
+                "repo",
+                Status::Fail,
+                format!("default_repo {:?} isn't owner/repo", repo),
+            );
+        }
+    }
+    if let Some(size) = forge.page_size.filter(|&size| size != forge.page_size()) {
+        report.push(
+            name,
+            "page_size",
+            Status::Warn,
+            format!("{} is out of range; {} is used", size, forge.page_size()),
+        );
+    }
+
+    // Building the client reads the CA bundle and parses the proxy
+    match crate::http::client(forge.timeout(), &forge.transport) {
+        Ok(_) if forge.transport.insecure_skip_verify => report.push(
+            name,
+            "transport",
+            Status::Warn,
+            "insecure_skip_verify accepts any certificate".to_string(),
+        ),
+        Ok(_) => {}
+        Err(e) => report.push(name, "transport", Status::Fail, e.to_string()),
+    }
+
+    match crate::auth::token_source(forge) {
+        Some(source) => report.push(name, "token", Status::Ok, format!("from {}", source)),
+        None => report.push(
+            name,
+            "token",
+            Status::Fail,
+            "none found; set token_env or token_command, or run `grit auth login`".to_string(),
+        ),
+    }
+}
+
+/// Sign in to the forge and check the token may do everything grit does
+async fn check_online(forge_config: &ForgeConfig, report: &mut Report) {
+    let name = forge_config.name.as_str();
+    let Ok(token) = crate::auth::find_forge_token(forge_config) else {
+        return;
+    };
+    let forge = match crate::forge::client(forge_config, token) {
+        Ok(forge) => forge,
+        Err(e) => return report.push(name, "signin", Status::Fail, e.to_string()),
+    };
+    match forge.get_current_user().await {
+        Ok(user) => report.push(name, "signin", Status::Ok, format!("as {}", user)),
+        Err(e @ (GritError::Network(_) | GritError::Timeout(_))) => {
+            return report.push(name, "signin", Status::Fail, format!("unreachable: {}", e))
+        }
+        Err(e) => return report.push(name, "signin", Status::Fail, e.to_string()),
+    }
+
+    let Some(required) = forge.required_scope() else {
+        return;
+    };
+    match forge.token_scopes().await {
+        Ok(Some(scopes)) if scopes.iter().any(|s| s == required) => {
+            report.push(name, "scope", Status::Ok, scopes.join(", "))
+        }
+        Ok(Some(scopes)) => report.push(
+            name,
+            "scope",
+            Status::Fail,
+            format!(
+                "token lacks '{}' (has: {})",
+                required,
+                if scopes.is_empty() {
+                    "none".to_string()
+                } else {
+                    scopes.join(", ")
+                }
+            ),
+        ),
+        Ok(None) => report.push(
+            name,
+            "scope",
+            Status::Warn,
+            format!("not reported for this token; grit needs '{}'", required),
+        ),
+        Err(e) => report.push(name, "scope", Status::Warn, e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(toml: &str) -> Config {
+        Config::from_layers(vec![toml.parse().unwrap()]).unwrap()
+    }
+
+    fn failures(report: &Report) -> Vec<(&str, &str)> {
+        report
+            .checks
+            .iter()
+            .filter(|c| c.status == Status::Fail)
+            .map(|c| (c.subject.as_str(), c.check))
+            .collect()
+    }
+
+    #[test]
+    fn names_must_match_a_forge() {
+        let config = config(
+            r#"
+forge = "work"
+
+[[forges]]
+name = "github"
+type = "github"
+host = "github.com"
+
+[[forges]]
+name = "github"
+type = "gitea"
+host = "codeberg.org"
+
+[[ui.links]]
+pattern = "("
+url = "x"
+"#,
+        );
+        let mut report = Report::default();
+        check_config(&config, &mut report);
+        assert_eq!(
+            failures(&report),
+            vec![
+                ("config", "forges"),
+                ("config", "forges"),
+                ("config", "links")
+            ]
+        );
+        assert_eq!(report.exit_code(), 1);
+    }
+
+    #[test]
+    fn home_sizes_match_the_sections() {
+        let config = config(
+            r#"
+[home]
+sections = ["review_requests", "my_prs", "review_requests"]
+sizes = [60, 30]
+"#,
+        );
+        let mut report = Report::default();
+        check_home(&config, &mut report);
+        let checks: Vec<(&str, Status)> =
+            report.checks.iter().map(|c| (c.check, c.status)).collect();
+        assert_eq!(
+            checks,
+            vec![
+                ("home", Status::Fail),
+                ("home", Status::Fail),
+                ("home", Status::Warn)
+            ]
+        );
+    }
+
+    #[test]
+    fn forge_entries_are_checked_offline() {
+        let config = config(
+            r#"
+[[forges]]
+name = "work"
+type = "gitlab"
+host = "https://gitlab.company.com"
+default_repo = "nope"
+page_size = 500
+token_env = "GRIT_VALIDATE_TEST_UNSET"
+"#,
+        );
+        let mut report = Report::default();
+        check_forge(&config.forges[0], &mut report);
+        let checks: Vec<(&str, Status)> =
+            report.checks.iter().map(|c| (c.check, c.status)).collect();
+        assert_eq!(
+            checks,
+            vec![
+                ("host", Status::Fail),
+                ("repo", Status::Fail),
+                ("page_size", Status::Warn),
+                ("token", Status::Fail),
+            ]
+        );
+    }
+
+    #[test]
+    fn warnings_alone_pass() {
+        let mut report = Report::default();
+        report.push("github", "scope", Status::Warn, String::new());
+        assert_eq!(report.exit_code(), 0);
+        assert!(report.text().ends_with("0 ok, 1 warnings, 0 failed\n"));
+    }
+}
@@ -0,0 +1,125 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::{GritError, Result};
+
+/// Run git in `dir`, returning trimmed stdout if it succeeded
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Run git in `dir` on the terminal, so its progress and errors stay visible
+pub fn run_git(dir: &Path, args: &[String]) -> Result<()> {
+    let status = Command::new("git").current_dir(dir).args(args).status()?;
+    if !status.success() {
+        let cmd = args.first().map(String::as_str).unwrap_or("");
+        return Err(GritError::Git(format!("git {} failed", cmd)));
+    }
+    Ok(())
+}
+
+/// Root of the work tree around the current directory, if it's a clone of owner/repo
+pub fn find_clone(owner: &str, repo: &str) -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    let root = git(&cwd, &["rev-parse", "--show-toplevel"])?;
+    let remotes = git(&cwd, &["remote", "-v"])?;
+    remotes_match(&remotes, owner, repo).then(|| PathBuf::from(root))
+}
+
+/// Whether any `git remote -v` URL points at owner/repo, over https or ssh
+fn remotes_match(remotes: &str, owner: &str, repo: &str) -> bool {
+    let want = format!("{}/{}", owner, repo).to_lowercase();
+    remotes
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .any(|url| {
+            let url = url
+                .trim_end_matches('/')
+                .trim_end_matches(".git")
+                .to_lowercase();
+            url.ends_with(&format!("/{}", want)) || url.ends_with(&format!(":{}", want))
+        })
+}
+
+/// File to open for `path` at commit `sha`. When `sha` is checked out that's the
+/// working-tree file itself; otherwise a `git show` snapshot in the temp dir,
+/// fetching the commit (or failing that, `branch`) from origin first if needed.
+pub fn file_at(root: &Path, sha: &str, branch: &str, path: &str) -> Result<PathBuf> {
+    if sha.is_empty() {
+        return Err(GritError::Git(
+            "PR head commit unknown, refresh and try again".into(),
+        ));
+    }
+    if git(root, &["rev-parse", "HEAD"]).as_deref() == Some(sha) {
+        return Ok(root.join(path));
+    }
+
+    let commit = format!("{}^{{commit}}", sha);
+    if git(root, &["cat-file", "-e", &commit]).is_none()
+        && git(root, &["fetch", "--quiet", "origin", sha]).is_none()
+        && git(root, &["fetch", "--quiet", "origin", branch]).is_none()
+    {
+        return Err(GritError::Git(format!(
+            "Could not fetch {} from origin",
+            branch
+        )));
+    }
+
+    let output = Command::new("git")
+        .current_dir(root)
+        .args(["show", &format!("{}:{}", sha, path)])
+        .output()?;
+    let short = &sha[..sha.len().min(7)];
+    if !output.status.success() {
+        return Err(GritError::Git(format!("{} not found at {}", path, short)));
+    }
+
+    // Keep the file name so the editor still picks the right syntax
+    let name = Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "file".to_string());
+    let tmp_path = std::env::temp_dir().join(format!("grit-{}-{}", short, name));
+    std::fs::write(&tmp_path, output.stdout)?;
+    Ok(tmp_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REMOTES: &str = "origin\tgit@github.com:Owner/Repo.git (fetch)\n\
+                           origin\tgit@github.com:Owner/Repo.git (push)\n\
+                           fork\thttps://github.com/me/repo (fetch)";
+
+    #[test]
+    fn ssh_remote_matches_case_insensitively() {
+        assert!(remotes_match(REMOTES, "owner", "repo"));
+    }
+
+    #[test]
+    fn https_remote_matches() {
+        assert!(remotes_match(REMOTES, "me", "repo"));
+    }
+
+    #[test]
+    fn other_repo_does_not_match() {
+        assert!(!remotes_match(REMOTES, "owner", "other"));
+        // Suffix of a longer repo name isn't a match
+        assert!(!remotes_match(REMOTES, "wner", "repo"));
+    }
+
+    #[test]
+    fn nested_group_matches() {
+        let remotes = "origin\thttps://gitlab.com/group/sub/project.git (fetch)";
+        assert!(remotes_match(remotes, "group/sub", "project"));
+    }
+}